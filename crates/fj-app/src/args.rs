@@ -1,28 +1,117 @@
 use std::{path::PathBuf, str::FromStr as _};
 
 use anyhow::anyhow;
+use fj_export::View;
 use fj_host::Parameters;
-use fj_kernel::algorithms::Tolerance;
+use fj_kernel::algorithms::{Tolerance, TriangulationStrategy};
 use fj_math::Scalar;
+use fj_operations::{sdf::SdfConfig, shape_processor::Backend};
+use fj_viewer::screen::Size;
 
 /// Fornjot - Experimental CAD System
 #[derive(clap::Parser)]
 pub struct Args {
     /// The model to open
+    ///
+    /// This is the path of the model's crate. Models can also be fetched from
+    /// a git repository (`git+<url>`, optionally followed by `#<rev>`), or
+    /// from crates.io (`crates.io:<name>@<version>`). With the `script`
+    /// feature, this can also be a Rhai script (`*.rhai`), which can only be
+    /// viewed.
+    ///
+    /// Can be specified multiple times, to open multiple models at once. Each
+    /// of them is displayed in its own tab. Exporting and saving images only
+    /// supports a single model.
     #[clap(short, long)]
-    pub model: Option<PathBuf>,
+    pub model: Vec<PathBuf>,
 
-    /// Export model to this path
+    /// Export model to this path, without opening a window
+    ///
+    /// Can be specified multiple times, to export to multiple files at once.
     #[clap(short, long)]
-    pub export: Option<PathBuf>,
+    pub export: Vec<PathBuf>,
+
+    /// View from which 2D drawings (SVG, DXF) are exported
+    ///
+    /// One of `top`, `bottom`, `front`, `back`, `left`, or `right`. Has no
+    /// effect on the export of 3D meshes (3MF, STL).
+    #[clap(long, parse(try_from_str = parse_view))]
+    pub view: Option<View>,
 
     /// Parameters for the model, each in the form `key=value`
     #[clap(short, long, parse(try_from_str = parse_parameters))]
     pub parameters: Option<Parameters>,
 
+    /// Named set of parameters to load from the model's `parameters.toml`
+    ///
+    /// Parameters passed via `--parameters` override those from the preset.
+    #[clap(long)]
+    pub preset: Option<String>,
+
+    /// Parameters to sweep over, when exporting
+    ///
+    /// Each in the form `key=start..end:step`, for a range of numbers, or
+    /// `key=a|b|c`, for a list of values. One file is exported per
+    /// combination of values, with the values appended to the file name.
+    #[clap(short, long, parse(try_from_str = parse_sweep))]
+    pub sweep: Vec<SweepArg>,
+
+    /// Save an image of the model to this PNG file, without opening a window
+    #[clap(long)]
+    pub screenshot: Option<PathBuf>,
+
+    /// Save an animation of the model turning to this path
+    ///
+    /// If the path ends in `.gif`, an animated GIF is saved. Otherwise, one
+    /// PNG file is saved per frame, with the frame number appended to the file
+    /// name.
+    #[clap(long)]
+    pub turntable: Option<PathBuf>,
+
+    /// Number of frames in the animation saved by `--turntable`
+    #[clap(long, default_value = "36")]
+    pub frames: u32,
+
+    /// Size of the images saved by `--screenshot` and `--turntable`
+    ///
+    /// In the form `WIDTHxHEIGHT`, in pixels.
+    #[clap(long, parse(try_from_str = parse_image_size))]
+    pub image_size: Option<Size>,
+
+    /// Serve the models over HTTP at this address, instead of opening a window
+    ///
+    /// In the form `host:port`, for example `127.0.0.1:8080`. Other
+    /// applications can then load the models with different parameters, and
+    /// retrieve their meshes, or export them. The models are identified by
+    /// the name of their directory.
+    #[clap(long)]
+    pub serve: Option<String>,
+
     /// Model deviation tolerance
     #[clap[short, long, parse(try_from_str = parse_tolerance)]]
     pub tolerance: Option<Tolerance>,
+
+    /// Minimum angle of the triangles in the mesh, in degrees
+    ///
+    /// Additional points are inserted into the mesh, to get rid of triangles
+    /// with smaller angles. Pass `0` to disable this.
+    #[clap(long, parse(try_from_str = parse_min_angle))]
+    pub min_angle: Option<Scalar>,
+
+    /// Strategy for triangulating curved surfaces
+    ///
+    /// One of `delaunay` (the default), or `grid`, which produces a mesh that
+    /// follows the isolines of the surface.
+    #[clap(long, parse(try_from_str = parse_strategy))]
+    pub strategy: Option<TriangulationStrategy>,
+
+    /// Backend for evaluating the model
+    ///
+    /// One of `brep` (the default), or `sdf`, which approximates the model
+    /// using a signed distance field. The latter is more robust, but loses
+    /// sharp edges.
+    #[clap(long, parse(try_from_str = parse_backend))]
+    pub backend: Option<Backend>,
 }
 
 impl Args {
@@ -58,6 +147,55 @@ fn parse_parameters(input: &str) -> anyhow::Result<Parameters> {
     Ok(parameters)
 }
 
+/// A parameter to sweep over
+#[derive(Debug, Clone)]
+pub enum SweepArg {
+    /// A range of numbers
+    Range {
+        key: String,
+        start: f64,
+        end: f64,
+        step: f64,
+    },
+
+    /// A list of values
+    Values { key: String, values: Vec<String> },
+}
+
+fn parse_sweep(input: &str) -> anyhow::Result<SweepArg> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected parameter sweep in form `key=...`"))?;
+    let key = key.trim().to_owned();
+
+    if let Some((range, step)) = value.split_once(':') {
+        let (start, end) = range
+            .split_once("..")
+            .ok_or_else(|| anyhow!("Expected range in form `start..end`"))?;
+
+        let start = f64::from_str(start.trim())?;
+        let end = f64::from_str(end.trim())?;
+        let step = f64::from_str(step.trim())?;
+
+        if step <= 0. {
+            return Err(anyhow!("Step of parameter range must be positive"));
+        }
+
+        return Ok(SweepArg::Range {
+            key,
+            start,
+            end,
+            step,
+        });
+    }
+
+    let values = value.split('|').map(|value| value.trim().to_owned());
+    Ok(SweepArg::Values {
+        key,
+        values: values.collect(),
+    })
+}
+
 fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
     let tolerance = f64::from_str(input)?;
     let tolerance = Scalar::from_f64(tolerance);
@@ -65,3 +203,62 @@ fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
 
     Ok(tolerance)
 }
+
+fn parse_min_angle(input: &str) -> anyhow::Result<Scalar> {
+    let min_angle = f64::from_str(input)?;
+
+    if !(0. ..60.).contains(&min_angle) {
+        return Err(anyhow!("Minimum angle must be between 0 and 60 degrees"));
+    }
+
+    Ok(Scalar::from_f64(min_angle.to_radians()))
+}
+
+fn parse_strategy(input: &str) -> anyhow::Result<TriangulationStrategy> {
+    let strategy = match input {
+        "delaunay" => TriangulationStrategy::Delaunay,
+        "grid" => TriangulationStrategy::Grid,
+        _ => return Err(anyhow!("Unknown triangulation strategy: `{input}`")),
+    };
+
+    Ok(strategy)
+}
+
+fn parse_backend(input: &str) -> anyhow::Result<Backend> {
+    let backend = match input {
+        "brep" => Backend::BRep,
+        "sdf" => Backend::Sdf(SdfConfig::default()),
+        _ => return Err(anyhow!("Unknown backend: `{input}`")),
+    };
+
+    Ok(backend)
+}
+
+fn parse_image_size(input: &str) -> anyhow::Result<Size> {
+    let (width, height) = input
+        .split_once('x')
+        .ok_or_else(|| anyhow!("Expected image size in form `WIDTHxHEIGHT`"))?;
+
+    let width = u32::from_str(width.trim())?;
+    let height = u32::from_str(height.trim())?;
+
+    if width == 0 || height == 0 {
+        return Err(anyhow!("Image size must not be zero"));
+    }
+
+    Ok(Size { width, height })
+}
+
+fn parse_view(input: &str) -> anyhow::Result<View> {
+    let view = match input {
+        "top" => View::Top,
+        "bottom" => View::Bottom,
+        "front" => View::Front,
+        "back" => View::Back,
+        "left" => View::Left,
+        "right" => View::Right,
+        _ => return Err(anyhow!("Unknown view: `{input}`")),
+    };
+
+    Ok(view)
+}
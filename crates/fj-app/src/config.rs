@@ -1,10 +1,11 @@
-use std::path::PathBuf;
+use std::{env, path::PathBuf};
 
-use anyhow::Context as _;
+use anyhow::{anyhow, Context as _};
 use figment::{
     providers::{Env, Format as _, Toml},
     Figment,
 };
+use fj_viewer::input::NavigationConfig;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -12,6 +13,11 @@ pub struct Config {
     pub default_path: Option<PathBuf>,
     pub default_model: Option<PathBuf>,
     pub target_dir: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+    pub shape_cache: Option<PathBuf>,
+    pub timeout: Option<f64>,
+    #[serde(default)]
+    pub navigation: NavigationConfig,
 }
 
 impl Config {
@@ -22,4 +28,33 @@ impl Config {
             .extract()
             .context("Error loading configuration")
     }
+
+    /// The directory that models from remote sources are fetched into
+    ///
+    /// Defaults to the `fornjot` directory, within the user's cache
+    /// directory.
+    pub fn cache_dir(&self) -> anyhow::Result<PathBuf> {
+        if let Some(dir) = &self.cache_dir {
+            return Ok(dir.clone());
+        }
+
+        let home = || env::var_os("HOME").map(PathBuf::from);
+        let base = if cfg!(windows) {
+            env::var_os("LOCALAPPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            home().map(|home| home.join("Library").join("Caches"))
+        } else {
+            match env::var_os("XDG_CACHE_HOME") {
+                Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+                _ => home().map(|home| home.join(".cache")),
+            }
+        };
+
+        base.map(|dir| dir.join("fornjot")).ok_or_else(|| {
+            anyhow!(
+                "Could not determine cache directory. \
+                Specify one by setting `cache_dir` in `fj.toml`."
+            )
+        })
+    }
 }
@@ -14,18 +14,39 @@
 
 mod args;
 mod config;
+mod server;
 
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::{anyhow, Context as _};
-use fj_export::export;
-use fj_host::{Model, Parameters};
-use fj_operations::shape_processor::ShapeProcessor;
+use fj_export::{export, export_drawing};
+#[cfg(feature = "script")]
+use fj_host::Script;
+use fj_host::{Model, ModelSource, ParameterSweep, Parameters};
+use fj_kernel::algorithms::TriangulationConfig;
+use fj_operations::{shape_cache::ShapeCache, shape_processor::ShapeProcessor};
+use fj_viewer::{
+    camera::{Camera, StandardView},
+    graphics::{save_gif, DrawConfig, Headless},
+    screen::Size,
+};
 use fj_window::run::run;
+use futures::executor::block_on;
 use tracing_subscriber::fmt::format;
+use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::EnvFilter;
 
-use crate::{args::Args, config::Config};
+use crate::{
+    args::{Args, SweepArg},
+    config::Config,
+    server::{serve, ServedModel},
+};
+
+/// Time each frame of a turntable GIF is displayed for, in hundredths of a second
+const TURNTABLE_FRAME_DELAY: u16 = 10;
 
 fn main() -> anyhow::Result<()> {
     // Respect `RUST_LOG`. If that's not defined or erroneous, log warnings and
@@ -33,45 +54,308 @@ fn main() -> anyhow::Result<()> {
     //
     // It would be better to fail, if `RUST_LOG` is erroneous, but I don't know
     // how to distinguish between that and the "not defined" case.
+    //
+    // Closing spans are logged, as they include the time spent in them. With
+    // `RUST_LOG=debug`, this shows how long each operation and algorithm took.
     tracing_subscriber::fmt()
         .with_env_filter(
             EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| EnvFilter::new("WARN")),
         )
+        .with_span_events(FmtSpan::CLOSE)
         .event_format(format().pretty())
         .init();
 
     let args = Args::parse();
     let config = Config::load()?;
 
-    let mut path = config.default_path.unwrap_or_else(|| PathBuf::from(""));
-    let model = args.model.or(config.default_model).ok_or_else(|| {
-        anyhow!(
+    let default_path = config
+        .default_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(""));
+    let mut model_paths = args.model;
+    if model_paths.is_empty() {
+        model_paths.extend(config.default_model.clone());
+    }
+    if model_paths.is_empty() {
+        return Err(anyhow!(
             "No model specified, and no default model configured.\n\
                 Specify a model by passing `--model path/to/model`."
-        )
-    })?;
-    path.push(model);
+        ));
+    }
+
+    // The preset and parameters apply to all models.
+    let mut models = Vec::new();
+    #[cfg(feature = "script")]
+    let mut scripts = Vec::new();
+    for model in model_paths {
+        let path = match model.to_str().and_then(ModelSource::parse) {
+            Some(source) => source
+                .fetch(&config.cache_dir()?)
+                .with_context(|| format!("Failed to fetch model: {source}"))?,
+            None => default_path.join(model),
+        };
+
+        #[cfg(feature = "script")]
+        if path.extension() == Some("rhai".as_ref()) {
+            if args.preset.is_some() {
+                return Err(anyhow!("Scripts don't support presets"));
+            }
+
+            let mut script =
+                Script::from_path(path.clone()).with_context(|| {
+                    format!("Failed to load script: {}", path.display())
+                })?;
+            if let Some(timeout) = config.timeout {
+                script = script.with_timeout(Duration::from_secs_f64(timeout));
+            }
+            let parameters =
+                args.parameters.clone().unwrap_or_else(Parameters::empty);
+
+            scripts.push((path, script, parameters));
+            continue;
+        }
+
+        let mut model =
+            Model::from_path(path.clone(), config.target_dir.clone())
+                .with_context(|| {
+                    format!("Failed to load model: {}", path.display())
+                })?;
+        if let Some(timeout) = config.timeout {
+            model = model.with_timeout(Duration::from_secs_f64(timeout));
+        }
+        let mut parameters = match &args.preset {
+            Some(preset) => {
+                model.parameter_preset(preset).with_context(|| {
+                    format!("Failed to load parameter preset: {preset}")
+                })?
+            }
+            None => Parameters::empty(),
+        };
+        if let Some(overrides) = &args.parameters {
+            parameters.extend(overrides.0.clone());
+        }
+
+        models.push((path, model, parameters));
+    }
 
-    let model = Model::from_path(path.clone(), config.target_dir)
-        .with_context(|| format!("Failed to load model: {}", path.display()))?;
-    let parameters = args.parameters.unwrap_or_else(Parameters::empty);
+    let headless = !args.export.is_empty()
+        || args.screenshot.is_some()
+        || args.turntable.is_some();
+    if headless && args.serve.is_some() {
+        return Err(anyhow!(
+            "Serving models can't be combined with exporting or saving images"
+        ));
+    }
+    #[cfg(feature = "script")]
+    if !scripts.is_empty() && (headless || args.serve.is_some()) {
+        return Err(anyhow!(
+            "Scripts can only be viewed, not exported, rendered, or served"
+        ));
+    }
+    if headless && models.len() > 1 {
+        return Err(anyhow!(
+            "Exporting and saving images require a single model"
+        ));
+    }
+
+    let mut triangulation = TriangulationConfig::default();
+    if let Some(min_angle) = args.min_angle {
+        triangulation.min_angle = min_angle;
+    }
+    if let Some(strategy) = args.strategy {
+        triangulation.strategy = strategy;
+    }
 
     let shape_processor = ShapeProcessor {
         tolerance: args.tolerance,
+        triangulation,
+        backend: args.backend.unwrap_or_default(),
+        validation_cache: None,
     };
 
-    if let Some(path) = args.export {
-        let shape = model.load_once(&parameters)?;
+    if !args.export.is_empty() {
+        let (_, model, parameters) = &models[0];
+
+        let mut sweep = ParameterSweep::new(parameters.clone());
+        for arg in args.sweep {
+            sweep = match arg {
+                SweepArg::Range {
+                    key,
+                    start,
+                    end,
+                    step,
+                } => sweep.with_range(key, start, end, step),
+                SweepArg::Values { key, values } => {
+                    sweep.with_values(key, values)
+                }
+            };
+        }
+
+        let parameter_sets = sweep.parameter_sets();
+        let shapes = model.load_for_each(&parameter_sets)?;
+
+        for (parameters, shape) in parameter_sets.iter().zip(shapes) {
+            let shape = shape_processor.process(&shape)?;
+
+            // Faces are triangulated independently, which can leave vertices
+            // along shared edges slightly apart. Weld them, using a tolerance
+            // that is tiny compared to the size of the model.
+            let weld_tolerance = shape.aabb.size().magnitude() * 1e-9;
+            let mesh = shape.mesh.weld(weld_tolerance);
+
+            for path in &args.export {
+                let path = sweep_path(path, &sweep, parameters);
+
+                match args.view {
+                    Some(view) if is_drawing(&path) => {
+                        export_drawing(&mesh, view, &path)?
+                    }
+                    _ => export(&mesh, &path)?,
+                }
+            }
+        }
+
+        return Ok(());
+    }
+    if args.screenshot.is_some() || args.turntable.is_some() {
+        let (_, model, parameters) = &models[0];
+
+        let shape = model.load_once(parameters)?;
         let shape = shape_processor.process(&shape)?;
 
-        export(&shape.mesh, &path)?;
+        let mut headless = block_on(Headless::new())?;
+        headless.update_geometry(
+            (&shape.mesh).into(),
+            &shape.edges,
+            &shape.debug_info,
+            shape.aabb,
+        );
+
+        // Images are meant to show the model, without any visual aids.
+        let draw_config = DrawConfig {
+            draw_grid: false,
+            draw_axes: false,
+            ..DrawConfig::default()
+        };
+        let size = args.image_size.unwrap_or(Size {
+            width: 800,
+            height: 600,
+        });
+
+        if let Some(path) = &args.screenshot {
+            let mut camera = Camera::new(&shape.aabb);
+            camera.look_from(StandardView::Isometric, &shape.aabb);
+            camera.update_planes(&shape.aabb);
+
+            block_on(headless.capture(&camera, &draw_config, size))?
+                .save_png(path)?;
+        }
+        if let Some(path) = &args.turntable {
+            let frames =
+                block_on(headless.turntable(&draw_config, size, args.frames))?;
+
+            if path.extension() == Some("gif".as_ref()) {
+                save_gif(&frames, TURNTABLE_FRAME_DELAY, path)?;
+            } else {
+                for (i, frame) in frames.iter().enumerate() {
+                    frame.save_png(frame_path(path, i))?;
+                }
+            }
+        }
 
         return Ok(());
     }
+    if !args.sweep.is_empty() {
+        return Err(anyhow!("Sweeping parameters requires `--export`"));
+    }
 
-    let watcher = model.load_and_watch(parameters)?;
-    run(watcher, shape_processor)?;
+    if let Some(addr) = &args.serve {
+        let models = models
+            .into_iter()
+            .map(|(path, model, parameters)| ServedModel {
+                name: tab_name(&path),
+                model,
+                parameters,
+            })
+            .collect();
+        return serve(addr, models, shape_processor);
+    }
+
+    let watchers = models.into_iter().map(
+        |(path, model, parameters)| -> anyhow::Result<_> {
+            Ok((tab_name(&path), model.load_and_watch(parameters)?))
+        },
+    );
+    #[cfg(feature = "script")]
+    let watchers = watchers.chain(scripts.into_iter().map(
+        |(path, script, parameters)| -> anyhow::Result<_> {
+            Ok((tab_name(&path), script.load_and_watch(parameters)?))
+        },
+    ));
+    let watchers = watchers.collect::<anyhow::Result<_>>()?;
+    let shape_cache = config.shape_cache.map(ShapeCache::new);
+    run(
+        watchers,
+        shape_processor,
+        shape_cache,
+        config.navigation.bindings(),
+    )?;
 
     Ok(())
 }
+
+/// Determine the name of the tab that a model is displayed in
+///
+/// This is the name of the model's directory, which is usually the name of its
+/// crate too.
+fn tab_name(path: &Path) -> String {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Determine the path of one frame of a turntable animation
+///
+/// Appends the frame number to the file name, so `part.png` becomes
+/// `part_000.png`, for example.
+fn frame_path(path: &Path, frame: usize) -> PathBuf {
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(format!("_{frame:03}.png"));
+
+    path.with_file_name(file_name)
+}
+
+/// Determine whether a file is a 2D drawing, which is exported from a view
+///
+/// All other files are exported as 3D meshes, regardless of `--view`.
+fn is_drawing(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| {
+        let extension = extension.to_ascii_uppercase();
+        extension == "SVG" || extension == "DXF"
+    })
+}
+
+/// Determine the export path for one combination of a parameter sweep
+///
+/// Appends the swept parameters to the file name, so `part.stl` becomes
+/// `part_thickness-2.stl`, for example.
+fn sweep_path(
+    path: &Path,
+    sweep: &ParameterSweep,
+    parameters: &Parameters,
+) -> PathBuf {
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    for key in sweep.keys() {
+        file_name.push(format!("_{key}-{}", parameters[key]));
+    }
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+
+    path.with_file_name(file_name)
+}
@@ -0,0 +1,344 @@
+//! HTTP service for evaluating models remotely
+//!
+//! The service makes the models that Fornjot has been started with available
+//! over HTTP, so other applications, like a web viewer, or a set of rendering
+//! workers, can use a central instance to evaluate them. All endpoints accept
+//! the model's parameters as a query string (`?outer=8.0&inner=5.0`). They
+//! override the parameters that the service has been started with.
+//!
+//! - `GET /models`: The names of the models, as a JSON array.
+//! - `GET /models/<name>/parameters`: The parameters of the model, as a JSON
+//!   array with an object for each parameter.
+//! - `GET /models/<name>/mesh`: The triangle mesh of the model, in the binary
+//!   mesh format of `fj-interop`.
+//! - `GET /models/<name>/export.<extension>`: The model, exported to a file of
+//!   the format that the extension identifies (3MF, STL, SVG, or DXF).
+//!
+//! Each model is compiled once, when the service starts, and again whenever
+//! its source code changes. Requests only evaluate the compiled model. If
+//! compiling a changed model fails, the previous version is served, until the
+//! error has been fixed.
+
+use std::{
+    env, error, fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, PoisonError, RwLock, RwLockReadGuard,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context as _};
+use fj_export::export;
+use fj_host::{HostedModel, Model, Parameters, Watcher};
+use fj_operations::shape_processor::ShapeProcessor;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+/// A model that is made available by the service
+pub struct ServedModel {
+    /// The name that identifies the model in URLs
+    pub name: String,
+
+    /// The model
+    pub model: Model,
+
+    /// The parameters that requests override
+    pub parameters: Parameters,
+}
+
+/// Serve the models over HTTP, until the process is stopped
+///
+/// Each request is handled in its own thread, so a model that takes a while to
+/// evaluate doesn't block requests for other models.
+pub fn serve(
+    addr: &str,
+    models: Vec<ServedModel>,
+    shape_processor: ShapeProcessor,
+) -> anyhow::Result<()> {
+    let models = models
+        .into_iter()
+        .map(Served::start)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let server = Server::http(addr)
+        .map_err(|err| anyhow!("Failed to listen on `{addr}`: {err}"))?;
+    info!("Serving models on http://{addr}/models");
+
+    let models = Arc::new(models);
+    for request in server.incoming_requests() {
+        let models = models.clone();
+        let shape_processor = shape_processor.clone();
+        thread::spawn(move || {
+            // A panicking model must not leave the client waiting.
+            let response = panic::catch_unwind(AssertUnwindSafe(|| {
+                handle(&request, &models, &shape_processor)
+            }));
+            let response = match response {
+                Ok(Ok(response)) => response,
+                Ok(Err(err)) => err.into_response(),
+                Err(_) => {
+                    Error::Model("Model panicked".to_string()).into_response()
+                }
+            };
+
+            if let Err(err) = request.respond(response) {
+                warn!("Error sending response: {err}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// A model that is being served, after it has been compiled
+struct Served {
+    name: String,
+    model: SharedModel,
+    parameters: Parameters,
+}
+
+impl Served {
+    /// Compile the model, then start watching it for changes
+    fn start(served: ServedModel) -> anyhow::Result<Self> {
+        let ServedModel {
+            name,
+            model,
+            parameters,
+        } = served;
+
+        model
+            .load_once(&parameters)
+            .with_context(|| format!("Failed to load model `{name}`"))?;
+
+        let src_path = model.src_path().to_path_buf();
+        let model = SharedModel(Arc::new(RwLock::new(model)));
+
+        // The watcher can't be sent to another thread, so it's created on the
+        // thread that polls it.
+        let watched = model.clone();
+        let watched_name = name.clone();
+        let watched_parameters = parameters.clone();
+        thread::spawn(move || {
+            let watcher =
+                Watcher::watch_hosted(watched, &src_path, watched_parameters);
+            match watcher {
+                Ok(watcher) => watch(&watched_name, &watcher),
+                Err(err) => {
+                    warn!("Error watching model `{watched_name}`: {err}");
+                }
+            }
+        });
+
+        Ok(Self {
+            name,
+            model,
+            parameters,
+        })
+    }
+}
+
+/// Recompile the model whenever the watcher reports a change
+fn watch(name: &str, watcher: &Watcher) {
+    loop {
+        match watcher.receive_event() {
+            Ok(Some(_)) => info!("Reloaded model `{name}`"),
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+            Err(fj_host::Error::Disconnected) => return,
+            Err(err) => warn!("Error reloading model `{name}`: {err}"),
+        }
+    }
+}
+
+/// A model that is shared between the request handlers and its watcher
+///
+/// Compiling the model overwrites its dynamic library, so that must not happen
+/// while it is being evaluated. Requests only evaluate the model, and can do
+/// that concurrently. The watcher needs exclusive access to compile it.
+#[derive(Clone)]
+struct SharedModel(Arc<RwLock<Model>>);
+
+impl SharedModel {
+    /// Evaluate the most recently compiled version of the model
+    fn evaluate(
+        &self,
+        parameters: &Parameters,
+    ) -> Result<fj::Shape, fj_host::Error> {
+        self.read().evaluate(parameters)
+    }
+
+    /// Describe the parameters of the model
+    fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        self.read().parameter_metadata()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Model> {
+        // A model that panicked while it was being compiled and loaded doesn't
+        // leave the lock in an inconsistent state. Keep serving it.
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl HostedModel for SharedModel {
+    fn load(
+        &self,
+        parameters: &Parameters,
+    ) -> Result<fj::Shape, fj_host::Error> {
+        let model = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        model.load_once(parameters)
+    }
+}
+
+fn handle(
+    request: &Request,
+    models: &[Served],
+    shape_processor: &ShapeProcessor,
+) -> Result<Response<io::Cursor<Vec<u8>>>, Error> {
+    if request.method() != &Method::Get {
+        return Err(Error::MethodNotAllowed);
+    }
+
+    let (path, query) =
+        request.url().split_once('?').unwrap_or((request.url(), ""));
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+
+    let (served, resource) = match segments.as_slice() {
+        ["models"] => {
+            let names: Vec<_> =
+                models.iter().map(|model| &model.name).collect();
+            return Ok(json(&names));
+        }
+        ["models", name, resource] => {
+            let served = models
+                .iter()
+                .find(|model| model.name == *name)
+                .ok_or(Error::NotFound)?;
+            (served, *resource)
+        }
+        _ => return Err(Error::NotFound),
+    };
+
+    let mut parameters = served.parameters.clone();
+    parameters.extend(form_urlencoded::parse(query.as_bytes()).into_owned());
+
+    if resource == "parameters" {
+        served.model.evaluate(&parameters).map_err(Error::host)?;
+        return Ok(json(&served.model.parameter_metadata()));
+    }
+
+    let extension = match resource {
+        "mesh" => None,
+        _ => Some(
+            resource
+                .strip_prefix("export.")
+                .ok_or(Error::NotFound)?
+                .to_ascii_lowercase(),
+        ),
+    };
+
+    let shape = served.model.evaluate(&parameters).map_err(Error::host)?;
+    let shape = shape_processor.process(&shape).map_err(Error::model)?;
+
+    // Faces are triangulated independently, which can leave vertices along
+    // shared edges slightly apart. Weld them, like the exporter does.
+    let weld_tolerance = shape.aabb.size().magnitude() * 1e-9;
+    let mesh = shape.mesh.weld(weld_tolerance);
+
+    let (bytes, content_type) = match extension {
+        None => (
+            mesh.to_bytes().map_err(Error::model)?,
+            "application/octet-stream",
+        ),
+        Some(extension) => {
+            let content_type = match extension.as_str() {
+                "3mf" => "model/3mf",
+                "stl" => "model/stl",
+                "svg" => "image/svg+xml",
+                "dxf" => "image/vnd.dxf",
+                _ => return Err(Error::NotFound),
+            };
+
+            // The exporter writes to a file. Requests are handled
+            // concurrently, so each of them needs a file of its own.
+            let path = export_path(&extension);
+            let result = export(&mesh, &path).map_err(Error::model);
+            let bytes =
+                result.and_then(|()| fs::read(&path).map_err(Error::model));
+            let _ = fs::remove_file(&path);
+
+            (bytes?, content_type)
+        }
+    };
+
+    Ok(Response::from_data(bytes)
+        .with_header(content_type_header(content_type)))
+}
+
+fn json(value: &impl serde::Serialize) -> Response<io::Cursor<Vec<u8>>> {
+    let bytes = serde_json::to_vec(value)
+        .expect("Serializing to JSON in memory can't fail");
+
+    Response::from_data(bytes)
+        .with_header(content_type_header("application/json"))
+}
+
+fn content_type_header(content_type: &str) -> Header {
+    Header::from_bytes("Content-Type", content_type)
+        .expect("Content type is a valid header")
+}
+
+fn export_path(extension: &str) -> PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+    let file_name = format!("fj-serve-{}-{id}.{extension}", process::id());
+    env::temp_dir().join(file_name)
+}
+
+/// An error that is reported to the client
+enum Error {
+    NotFound,
+    MethodNotAllowed,
+
+    /// Loading, processing, or exporting the model failed
+    Model(String),
+}
+
+impl Error {
+    /// The compiler's output explains why a model failed to compile, so it is
+    /// passed on to the client.
+    fn host(err: fj_host::Error) -> Self {
+        match err {
+            fj_host::Error::Compile { output } => {
+                Self::Model(format!("Error compiling model\n\n{output}"))
+            }
+            err => Self::model(err),
+        }
+    }
+
+    fn model(err: impl error::Error) -> Self {
+        let mut message = err.to_string();
+
+        let mut current_err: &dyn error::Error = &err;
+        while let Some(err) = current_err.source() {
+            message.push_str(&format!("\n\nCaused by:\n    {err}"));
+            current_err = err;
+        }
+
+        Self::Model(message)
+    }
+
+    fn into_response(self) -> Response<io::Cursor<Vec<u8>>> {
+        let (status, message) = match self {
+            Self::NotFound => (404, "Not found".to_string()),
+            Self::MethodNotAllowed => (405, "Method not allowed".to_string()),
+            Self::Model(message) => (500, message),
+        };
+
+        Response::from_string(message).with_status_code(status)
+    }
+}
@@ -0,0 +1,244 @@
+//! Export of 2D drawings, projected from a 3D mesh
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs::File,
+    io::{self, Write as _},
+    path::Path,
+};
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar, Vector};
+
+/// The direction from which a shape is viewed, when projecting it to 2D
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum View {
+    /// View from above, looking down the negative z-axis
+    #[default]
+    Top,
+
+    /// View from below, looking up the positive z-axis
+    Bottom,
+
+    /// View from the front, looking along the positive y-axis
+    Front,
+
+    /// View from the back, looking along the negative y-axis
+    Back,
+
+    /// View from the left, looking along the positive x-axis
+    Left,
+
+    /// View from the right, looking along the negative x-axis
+    Right,
+}
+
+impl View {
+    /// The axes of the drawing, and the direction towards the viewer
+    fn axes(&self) -> [Vector<3>; 3] {
+        let [x, y, z] = [
+            Vector::from([1., 0., 0.]),
+            Vector::from([0., 1., 0.]),
+            Vector::from([0., 0., 1.]),
+        ];
+
+        match self {
+            Self::Top => [x, y, z],
+            Self::Bottom => [x, -y, -z],
+            Self::Front => [x, z, -y],
+            Self::Back => [-x, z, y],
+            Self::Left => [-y, z, -x],
+            Self::Right => [y, z, x],
+        }
+    }
+
+    fn project(&self, point: Point<3>) -> Point<2> {
+        let [u, v, _] = self.axes();
+        let point = point.coords;
+
+        Point::from([point.dot(&u), point.dot(&v)])
+    }
+}
+
+/// Project the mesh into a 2D drawing, as seen from `view`
+///
+/// The drawing consists of the edges of the mesh that are visible as lines:
+/// the outline of the shape (silhouette edges and open boundaries), as well as
+/// edges between front-facing triangles that meet at a sharp angle.
+///
+/// There is no hidden-line removal. Edges that are covered by other parts of
+/// the shape are still part of the drawing.
+pub fn project(mesh: &Mesh<Point<3>>, view: View) -> Vec<[Point<2>; 2]> {
+    // Edges between triangles whose normals are further apart than this are
+    // considered to be feature edges.
+    let crease_cos = (Scalar::PI / 6.).cos();
+
+    let [_, _, towards_viewer] = view.axes();
+
+    let mut normals_by_edge: BTreeMap<[Point<3>; 2], Vec<Vector<3>>> =
+        BTreeMap::new();
+    for triangle in mesh.triangles() {
+        let [a, b, c] = triangle.points;
+
+        let normal = (b - a).cross(&(c - a));
+        if normal.magnitude() == Scalar::ZERO {
+            continue;
+        }
+        let normal = normal.normalize();
+
+        for [p, q] in [[a, b], [b, c], [c, a]] {
+            let edge = if p <= q { [p, q] } else { [q, p] };
+            normals_by_edge.entry(edge).or_default().push(normal);
+        }
+    }
+
+    let mut lines = Vec::new();
+
+    for (edge, normals) in normals_by_edge {
+        // Triangles that are perpendicular to the view direction count as
+        // front-facing. Otherwise, the edges of faces that are parallel to the
+        // view direction would not be part of the outline.
+        let is_front_facing =
+            |normal: &Vector<3>| normal.dot(&towards_viewer) >= Scalar::ZERO;
+
+        let is_visible_line = match normals.as_slice() {
+            [normal] => is_front_facing(normal),
+            [a, b] => match (is_front_facing(a), is_front_facing(b)) {
+                (true, true) => a.dot(b) < crease_cos,
+                (false, false) => false,
+                _ => true,
+            },
+            // Non-manifold edges are always drawn, as long as any triangle
+            // they are part of is facing the viewer.
+            normals => normals.iter().any(is_front_facing),
+        };
+
+        if !is_visible_line {
+            continue;
+        }
+
+        let [a, b] = edge.map(|point| view.project(point));
+        if a != b {
+            lines.push([a, b]);
+        }
+    }
+
+    lines
+}
+
+pub(crate) fn export_svg(
+    mesh: &Mesh<Point<3>>,
+    view: View,
+    path: &Path,
+) -> io::Result<()> {
+    let lines = project(mesh, view);
+
+    let mut min = [f64::INFINITY; 2];
+    let mut max = [f64::NEG_INFINITY; 2];
+    for point in lines.iter().flatten() {
+        for (i, coord) in [point.u, point.v].into_iter().enumerate() {
+            min[i] = min[i].min(coord.into_f64());
+            max[i] = max[i].max(coord.into_f64());
+        }
+    }
+    if lines.is_empty() {
+        min = [0.; 2];
+        max = [0.; 2];
+    }
+
+    let width = max[0] - min[0];
+    let height = max[1] - min[1];
+    let stroke_width = width.max(height) / 500.;
+
+    // SVG's y-axis points down, so all y-coordinates are flipped.
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        min[0] - stroke_width,
+        -max[1] - stroke_width,
+        width + stroke_width * 2.,
+        height + stroke_width * 2.,
+    )
+    .expect("Writing to `String` can't fail");
+    writeln!(
+        svg,
+        "<g fill=\"none\" stroke=\"black\" stroke-width=\"{stroke_width}\" \
+        stroke-linecap=\"round\">",
+    )
+    .expect("Writing to `String` can't fail");
+    for [a, b] in lines {
+        writeln!(
+            svg,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+            a.u, -a.v, b.u, -b.v,
+        )
+        .expect("Writing to `String` can't fail");
+    }
+    writeln!(svg, "</g>\n</svg>").expect("Writing to `String` can't fail");
+
+    File::create(path)?.write_all(svg.as_bytes())
+}
+
+pub(crate) fn export_dxf(
+    mesh: &Mesh<Point<3>>,
+    view: View,
+    path: &Path,
+) -> io::Result<()> {
+    let lines = project(mesh, view);
+
+    // DXF files consist of pairs of lines: a group code, which specifies the
+    // type of the value, and the value itself.
+    let mut dxf = String::new();
+    let mut group = |code: u32, value: &dyn std::fmt::Display| {
+        writeln!(dxf, "{code}\n{value}")
+            .expect("Writing to `String` can't fail");
+    };
+
+    group(0, &"SECTION");
+    group(2, &"ENTITIES");
+    for [a, b] in lines {
+        group(0, &"LINE");
+        group(8, &0);
+        group(10, &a.u);
+        group(20, &a.v);
+        group(30, &0.);
+        group(11, &b.u);
+        group(21, &b.v);
+        group(31, &0.);
+    }
+    group(0, &"ENDSEC");
+    group(0, &"EOF");
+
+    File::create(path)?.write_all(dxf.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Mesh;
+    use fj_math::Point;
+
+    use super::View;
+
+    #[test]
+    fn project_tetrahedron() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+        let d = Point::from([0., 0., 1.]);
+
+        let color = [255, 0, 0, 255];
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, c, b], color);
+        mesh.push_triangle([a, b, d], color);
+        mesh.push_triangle([a, d, c], color);
+        mesh.push_triangle([b, c, d], color);
+
+        // From the top, the outline is the triangle `[a, b, c]`. In addition,
+        // the edges of the front-facing triangle `[b, c, d]` are visible.
+        let lines = super::project(&mesh, View::Top);
+        assert_eq!(lines.len(), 5);
+    }
+}
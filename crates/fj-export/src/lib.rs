@@ -14,6 +14,8 @@
 
 #![warn(missing_docs)]
 
+mod drawing;
+
 use std::{fs::File, path::Path};
 
 use thiserror::Error;
@@ -21,14 +23,25 @@ use thiserror::Error;
 use fj_interop::mesh::Mesh;
 use fj_math::{Point, Triangle};
 
+pub use self::drawing::{project, View};
+
 /// Export the provided mesh to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
 /// Currently 3MF & STL file types are supported. The case insensitive file extension of
 /// the provided path is used to switch between supported types.
+///
+/// In addition, SVG & DXF are supported as 2D drawing formats. Those use the
+/// default [`View`]. Use [`export_drawing`] to select a different one.
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     match path.extension() {
+        Some(extension)
+            if extension.to_ascii_uppercase() == "SVG"
+                || extension.to_ascii_uppercase() == "DXF" =>
+        {
+            export_drawing(mesh, View::default(), path)
+        }
         Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
             export_3mf(mesh, path)
         }
@@ -42,6 +55,33 @@ pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     }
 }
 
+/// Export a 2D drawing of the provided mesh to the file at the given path
+///
+/// The mesh is projected as seen from `view`. See [`project`] for details.
+///
+/// SVG & DXF file types are supported. Like with [`export`], the case
+/// insensitive file extension is used to switch between them.
+pub fn export_drawing(
+    mesh: &Mesh<Point<3>>,
+    view: View,
+    path: &Path,
+) -> Result<(), Error> {
+    match path.extension() {
+        Some(extension) if extension.to_ascii_uppercase() == "SVG" => {
+            drawing::export_svg(mesh, view, path)?;
+            Ok(())
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "DXF" => {
+            drawing::export_dxf(mesh, view, path)?;
+            Ok(())
+        }
+        Some(extension) => Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        )),
+        None => Err(Error::NoExtension),
+    }
+}
+
 fn export_3mf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     let vertices = mesh.vertices().map(|vertex| vertex.into()).collect();
 
@@ -0,0 +1,20 @@
+use std::env;
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+
+    // The header is generated from this crate's source alone, so `cbindgen`
+    // doesn't need to run `cargo metadata`.
+    cbindgen::Builder::new()
+        .with_config(
+            cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+                .expect("Failed to read `cbindgen.toml`"),
+        )
+        .with_src(format!("{crate_dir}/src/lib.rs"))
+        .generate()
+        .expect("Failed to generate C header")
+        .write_to_file(format!("{crate_dir}/include/fj_host.h"));
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,184 @@
+#ifndef FJ_HOST_H
+#define FJ_HOST_H
+
+/* Generated by `build.rs`. Don't edit this file manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The triangle mesh of a shape that a model has returned
+ */
+typedef struct FjMesh FjMesh;
+
+/**
+ * A model, together with the parameters it is loaded with
+ */
+typedef struct FjModel FjModel;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Initialize the model using the path to its crate
+ *
+ * `target_dir` is the directory that the model is compiled to. If it is
+ * `NULL`, the target directory is assumed to be located within the model
+ * path.
+ *
+ * Returns `NULL`, if the model can't be initialized. The model must be freed
+ * using [`fj_model_free`].
+ *
+ * # Safety
+ *
+ * `path` must point to a null-terminated string. `target_dir` must either be
+ * `NULL`, or point to a null-terminated string.
+ */
+struct FjModel *fj_model_new(const char *path, const char *target_dir);
+
+/**
+ * Free a model
+ *
+ * # Safety
+ *
+ * `model` must either be `NULL`, or have been returned by [`fj_model_new`],
+ * and not have been freed before.
+ */
+void fj_model_free(struct FjModel *model);
+
+/**
+ * Set a parameter, to pass it to the model when it is loaded
+ *
+ * The value is parsed by the model, so it must be written as it would be in
+ * Rust. Parameters that are not set use their default values.
+ *
+ * Returns `false`, if the arguments are invalid.
+ *
+ * # Safety
+ *
+ * `model` must have been returned by [`fj_model_new`]. `name` and `value`
+ * must point to null-terminated strings.
+ */
+bool fj_model_set_parameter(struct FjModel *model, const char *name, const char *value);
+
+/**
+ * Reset all parameters to their default values
+ *
+ * # Safety
+ *
+ * `model` must have been returned by [`fj_model_new`].
+ */
+void fj_model_clear_parameters(struct FjModel *model);
+
+/**
+ * Compile and load the model, then triangulate the shape it returns
+ *
+ * If `tolerance` is not positive, it is derived from the size of the shape.
+ *
+ * Returns `NULL`, if loading the model or processing its shape fails. The
+ * mesh must be freed using [`fj_mesh_free`].
+ *
+ * # Safety
+ *
+ * `model` must have been returned by [`fj_model_new`].
+ */
+struct FjMesh *fj_model_load(struct FjModel *model, double tolerance);
+
+/**
+ * Evaluate the model again, without compiling it first
+ *
+ * Works like [`fj_model_load`], but uses the library from the last time the
+ * model was loaded. This is much faster, if only the parameters have changed.
+ *
+ * # Safety
+ *
+ * `model` must have been returned by [`fj_model_new`].
+ */
+struct FjMesh *fj_model_evaluate(struct FjModel *model, double tolerance);
+
+/**
+ * The number of vertices in the mesh
+ *
+ * # Safety
+ *
+ * `mesh` must have been returned by [`fj_model_load`] or
+ * [`fj_model_evaluate`], and not have been freed.
+ */
+size_t fj_mesh_num_vertices(const struct FjMesh *mesh);
+
+/**
+ * The coordinates of the vertices in the mesh
+ *
+ * Contains 3 coordinates (x, y, z) per vertex. The pointer is valid, until
+ * the mesh is freed.
+ *
+ * # Safety
+ *
+ * `mesh` must have been returned by [`fj_model_load`] or
+ * [`fj_model_evaluate`], and not have been freed.
+ */
+const double *fj_mesh_vertices(const struct FjMesh *mesh);
+
+/**
+ * The number of triangles in the mesh
+ *
+ * # Safety
+ *
+ * `mesh` must have been returned by [`fj_model_load`] or
+ * [`fj_model_evaluate`], and not have been freed.
+ */
+size_t fj_mesh_num_triangles(const struct FjMesh *mesh);
+
+/**
+ * The vertex indices of the triangles in the mesh
+ *
+ * Contains 3 indices per triangle. The pointer is valid, until the mesh is
+ * freed.
+ *
+ * # Safety
+ *
+ * `mesh` must have been returned by [`fj_model_load`] or
+ * [`fj_model_evaluate`], and not have been freed.
+ */
+const uint32_t *fj_mesh_indices(const struct FjMesh *mesh);
+
+/**
+ * The colors of the triangles in the mesh
+ *
+ * Contains 4 components (red, green, blue, alpha) per triangle. The pointer
+ * is valid, until the mesh is freed.
+ *
+ * # Safety
+ *
+ * `mesh` must have been returned by [`fj_model_load`] or
+ * [`fj_model_evaluate`], and not have been freed.
+ */
+const uint8_t *fj_mesh_colors(const struct FjMesh *mesh);
+
+/**
+ * Free a mesh
+ *
+ * # Safety
+ *
+ * `mesh` must either be `NULL`, or have been returned by [`fj_model_load`]
+ * or [`fj_model_evaluate`], and not have been freed before.
+ */
+void fj_mesh_free(struct FjMesh *mesh);
+
+/**
+ * Describe the last error that occurred on the current thread
+ *
+ * Returns `NULL`, if no error has occurred. The string is valid, until the
+ * next function of this library is called on the same thread.
+ */
+const char *fj_last_error(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* FJ_HOST_H */
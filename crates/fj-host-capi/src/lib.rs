@@ -0,0 +1,459 @@
+//! # Fornjot Model Host C API
+//!
+//! This library is part of the [Fornjot] ecosystem. Fornjot is an open-source,
+//! code-first CAD application; and collection of libraries that make up the CAD
+//! application, but can be used independently.
+//!
+//! This library exposes [`fj_host`] through a C API, so applications that are
+//! not written in Rust can embed it. It loads models, sets their parameters,
+//! and provides the triangle mesh of the shapes they return.
+//!
+//! The C header, `include/fj_host.h`, is generated from this file by the
+//! build script.
+//!
+//! ``` c
+//! FjModel *model = fj_model_new("models/spacer", NULL);
+//! fj_model_set_parameter(model, "outer", "8.0");
+//!
+//! FjMesh *mesh = fj_model_load(model, 0.0);
+//! if (mesh == NULL) {
+//!     fprintf(stderr, "%s\n", fj_last_error());
+//! }
+//!
+//! fj_mesh_free(mesh);
+//! fj_model_free(model);
+//! ```
+//!
+//! Functions that can fail return `NULL` or `false`. [`fj_last_error`]
+//! describes the error in that case.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+
+#![warn(missing_docs)]
+
+use std::{
+    cell::RefCell,
+    error,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr,
+};
+
+use fj_host::{Model, Parameters};
+use fj_interop::mesh::Mesh;
+use fj_kernel::algorithms::{Tolerance, TriangulationConfig};
+use fj_math::Point;
+use fj_operations::shape_processor::{Backend, ShapeProcessor};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A model, together with the parameters it is loaded with
+pub struct FjModel {
+    model: Model,
+    parameters: Parameters,
+}
+
+/// The triangle mesh of a shape that a model has returned
+pub struct FjMesh {
+    vertices: Vec<f64>,
+    indices: Vec<u32>,
+    colors: Vec<u8>,
+}
+
+impl FjMesh {
+    fn from_mesh(mesh: &Mesh<Point<3>>) -> Self {
+        let vertices = mesh
+            .vertices()
+            .flat_map(|point| [point.x, point.y, point.z])
+            .map(|coord| coord.into_f64())
+            .collect();
+        let indices = mesh.indices().collect();
+        let colors = mesh
+            .triangles()
+            .flat_map(|triangle| triangle.color)
+            .collect();
+
+        Self {
+            vertices,
+            indices,
+            colors,
+        }
+    }
+}
+
+/// Initialize the model using the path to its crate
+///
+/// `target_dir` is the directory that the model is compiled to. If it is
+/// `NULL`, the target directory is assumed to be located within the model
+/// path.
+///
+/// Returns `NULL`, if the model can't be initialized. The model must be freed
+/// using [`fj_model_free`].
+///
+/// # Safety
+///
+/// `path` must point to a null-terminated string. `target_dir` must either be
+/// `NULL`, or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fj_model_new(
+    path: *const c_char,
+    target_dir: *const c_char,
+) -> *mut FjModel {
+    catch(ptr::null_mut(), || {
+        let path = PathBuf::from(string(path)?);
+        let target_dir = if target_dir.is_null() {
+            None
+        } else {
+            Some(PathBuf::from(string(target_dir)?))
+        };
+
+        let model = Model::from_path(path, target_dir).map_err(message)?;
+
+        Ok(Box::into_raw(Box::new(FjModel {
+            model,
+            parameters: Parameters::empty(),
+        })))
+    })
+}
+
+/// Free a model
+///
+/// # Safety
+///
+/// `model` must either be `NULL`, or have been returned by [`fj_model_new`],
+/// and not have been freed before.
+#[no_mangle]
+pub unsafe extern "C" fn fj_model_free(model: *mut FjModel) {
+    if !model.is_null() {
+        drop(Box::from_raw(model));
+    }
+}
+
+/// Set a parameter, to pass it to the model when it is loaded
+///
+/// The value is parsed by the model, so it must be written as it would be in
+/// Rust. Parameters that are not set use their default values.
+///
+/// Returns `false`, if the arguments are invalid.
+///
+/// # Safety
+///
+/// `model` must have been returned by [`fj_model_new`]. `name` and `value`
+/// must point to null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn fj_model_set_parameter(
+    model: *mut FjModel,
+    name: *const c_char,
+    value: *const c_char,
+) -> bool {
+    catch(false, || {
+        let model = model_mut(model)?;
+        model.parameters.insert(string(name)?, string(value)?);
+        Ok(true)
+    })
+}
+
+/// Reset all parameters to their default values
+///
+/// # Safety
+///
+/// `model` must have been returned by [`fj_model_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fj_model_clear_parameters(model: *mut FjModel) {
+    catch((), || {
+        model_mut(model)?.parameters = Parameters::empty();
+        Ok(())
+    })
+}
+
+/// Compile and load the model, then triangulate the shape it returns
+///
+/// If `tolerance` is not positive, it is derived from the size of the shape.
+///
+/// Returns `NULL`, if loading the model or processing its shape fails. The
+/// mesh must be freed using [`fj_mesh_free`].
+///
+/// # Safety
+///
+/// `model` must have been returned by [`fj_model_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fj_model_load(
+    model: *mut FjModel,
+    tolerance: f64,
+) -> *mut FjMesh {
+    catch(ptr::null_mut(), || {
+        let model = model_mut(model)?;
+        let shape =
+            model.model.load_once(&model.parameters).map_err(message)?;
+
+        process(&shape, tolerance)
+    })
+}
+
+/// Evaluate the model again, without compiling it first
+///
+/// Works like [`fj_model_load`], but uses the library from the last time the
+/// model was loaded. This is much faster, if only the parameters have changed.
+///
+/// # Safety
+///
+/// `model` must have been returned by [`fj_model_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fj_model_evaluate(
+    model: *mut FjModel,
+    tolerance: f64,
+) -> *mut FjMesh {
+    catch(ptr::null_mut(), || {
+        let model = model_mut(model)?;
+        let shape = model.model.evaluate(&model.parameters).map_err(message)?;
+
+        process(&shape, tolerance)
+    })
+}
+
+/// The number of vertices in the mesh
+///
+/// # Safety
+///
+/// `mesh` must have been returned by [`fj_model_load`] or
+/// [`fj_model_evaluate`], and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_num_vertices(mesh: *const FjMesh) -> usize {
+    (*mesh).vertices.len() / 3
+}
+
+/// The coordinates of the vertices in the mesh
+///
+/// Contains 3 coordinates (x, y, z) per vertex. The pointer is valid, until
+/// the mesh is freed.
+///
+/// # Safety
+///
+/// `mesh` must have been returned by [`fj_model_load`] or
+/// [`fj_model_evaluate`], and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_vertices(mesh: *const FjMesh) -> *const f64 {
+    (*mesh).vertices.as_ptr()
+}
+
+/// The number of triangles in the mesh
+///
+/// # Safety
+///
+/// `mesh` must have been returned by [`fj_model_load`] or
+/// [`fj_model_evaluate`], and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_num_triangles(mesh: *const FjMesh) -> usize {
+    (*mesh).indices.len() / 3
+}
+
+/// The vertex indices of the triangles in the mesh
+///
+/// Contains 3 indices per triangle. The pointer is valid, until the mesh is
+/// freed.
+///
+/// # Safety
+///
+/// `mesh` must have been returned by [`fj_model_load`] or
+/// [`fj_model_evaluate`], and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_indices(mesh: *const FjMesh) -> *const u32 {
+    (*mesh).indices.as_ptr()
+}
+
+/// The colors of the triangles in the mesh
+///
+/// Contains 4 components (red, green, blue, alpha) per triangle. The pointer
+/// is valid, until the mesh is freed.
+///
+/// # Safety
+///
+/// `mesh` must have been returned by [`fj_model_load`] or
+/// [`fj_model_evaluate`], and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_colors(mesh: *const FjMesh) -> *const u8 {
+    (*mesh).colors.as_ptr()
+}
+
+/// Free a mesh
+///
+/// # Safety
+///
+/// `mesh` must either be `NULL`, or have been returned by [`fj_model_load`]
+/// or [`fj_model_evaluate`], and not have been freed before.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_free(mesh: *mut FjMesh) {
+    if !mesh.is_null() {
+        drop(Box::from_raw(mesh));
+    }
+}
+
+/// Describe the last error that occurred on the current thread
+///
+/// Returns `NULL`, if no error has occurred. The string is valid, until the
+/// next function of this library is called on the same thread.
+#[no_mangle]
+pub extern "C" fn fj_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |err| err.as_ptr())
+    })
+}
+
+/// Run `f`, recording its error or panic, and returning `default` if it fails
+///
+/// Unwinding into C is undefined behavior, so panics must not escape.
+fn catch<T>(default: T, f: impl FnOnce() -> Result<T, String>) -> T {
+    set_last_error(None);
+
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(err)) => {
+            set_last_error(Some(err));
+            default
+        }
+        Err(_) => {
+            set_last_error(Some("Panic in Fornjot model host".to_string()));
+            default
+        }
+    }
+}
+
+fn set_last_error(err: Option<String>) {
+    // The message can't be passed to C, if it contains a null byte. Replace
+    // those, instead of dropping the message.
+    let err = err.map(|err| {
+        CString::new(err.replace('\0', " "))
+            .expect("Null bytes have been replaced")
+    });
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = err);
+}
+
+unsafe fn string(ptr: *const c_char) -> Result<String, String> {
+    if ptr.is_null() {
+        return Err("Unexpected `NULL` string".to_string());
+    }
+
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(str::to_string)
+        .map_err(|_| "String is not valid UTF-8".to_string())
+}
+
+unsafe fn model_mut<'r>(
+    model: *mut FjModel,
+) -> Result<&'r mut FjModel, String> {
+    model
+        .as_mut()
+        .ok_or_else(|| "Unexpected `NULL` model".to_string())
+}
+
+fn process(shape: &fj::Shape, tolerance: f64) -> Result<*mut FjMesh, String> {
+    let tolerance = if tolerance > 0. {
+        Some(Tolerance::from_scalar(tolerance).map_err(message)?)
+    } else {
+        None
+    };
+
+    let shape_processor = ShapeProcessor {
+        tolerance,
+        triangulation: TriangulationConfig::default(),
+        backend: Backend::default(),
+        validation_cache: None,
+    };
+    let shape = shape_processor.process(shape).map_err(message)?;
+
+    Ok(Box::into_raw(Box::new(FjMesh::from_mesh(&shape.mesh))))
+}
+
+/// Describe an error, including its sources
+fn message(err: impl error::Error) -> String {
+    let mut message = err.to_string();
+
+    let mut current_err: &dyn error::Error = &err;
+    while let Some(err) = current_err.source() {
+        message.push_str(&format!(": {err}"));
+        current_err = err;
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CStr, ptr, slice};
+
+    use fj_kernel::algorithms::TriangulationConfig;
+    use fj_operations::shape_processor::{Backend, ShapeProcessor};
+
+    use super::{
+        fj_last_error, fj_mesh_colors, fj_mesh_free, fj_mesh_indices,
+        fj_mesh_num_triangles, fj_mesh_num_vertices, fj_mesh_vertices,
+        fj_model_new, fj_model_set_parameter, process,
+    };
+
+    #[test]
+    fn mesh() {
+        let shape = fj::Primitive::cuboid([1., 2., 3.]).into();
+        let mesh = process(&shape, 0.).unwrap();
+
+        let expected = ShapeProcessor {
+            tolerance: None,
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        }
+        .process(&shape)
+        .unwrap()
+        .mesh;
+
+        unsafe {
+            let num_vertices = fj_mesh_num_vertices(mesh);
+            let num_triangles = fj_mesh_num_triangles(mesh);
+            assert_eq!(num_vertices, expected.vertices().count());
+            assert_eq!(num_triangles, expected.triangles().count());
+
+            let vertices =
+                slice::from_raw_parts(fj_mesh_vertices(mesh), num_vertices * 3);
+            let max = vertices.iter().copied().fold(f64::MIN, f64::max);
+            assert_eq!(max, 3.);
+
+            let indices =
+                slice::from_raw_parts(fj_mesh_indices(mesh), num_triangles * 3);
+            assert!(indices
+                .iter()
+                .all(|&index| (index as usize) < num_vertices));
+
+            let colors =
+                slice::from_raw_parts(fj_mesh_colors(mesh), num_triangles * 4);
+            assert_eq!(colors.len(), num_triangles * 4);
+
+            fj_mesh_free(mesh);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        unsafe {
+            let model =
+                fj_model_new("/does/not/exist\0".as_ptr().cast(), ptr::null());
+            assert!(model.is_null());
+            assert!(!fj_last_error().is_null());
+
+            let set = fj_model_set_parameter(
+                ptr::null_mut(),
+                "x\0".as_ptr().cast(),
+                "1\0".as_ptr().cast(),
+            );
+            assert!(!set);
+            let err = CStr::from_ptr(fj_last_error()).to_str().unwrap();
+            assert_eq!(err, "Unexpected `NULL` model");
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! # Fornjot Model Evaluator
+//!
+//! Evaluates a model in its own process, on behalf of `fj-host`. Not meant to
+//! be run directly. See `Model::with_evaluator` in `fj-host`.
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    fj_host::run_evaluator()
+}
@@ -0,0 +1,423 @@
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    process::{self, Command, ExitCode},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    evaluate_library, output_with_limits, Error, Evaluation, Model, Parameters,
+    ResourceLimits,
+};
+
+/// Identifies the requests and responses that are exchanged with the evaluator
+const MAGIC: [u8; 4] = *b"FJEV";
+
+/// The version of the format of requests and responses
+///
+/// Needs to be increased, whenever [`Request`] or [`Evaluation`] change.
+/// Shapes carry their own version. See [`fj::format`].
+const VERSION: u32 = 1;
+
+impl Model {
+    /// Use an alternative evaluator to evaluate the model
+    ///
+    /// The evaluator is a program that loads the model's dynamic library and
+    /// evaluates it, in a separate process. It is only used, if a timeout or
+    /// resource limits are set, as that process can be killed, if the model
+    /// doesn't return, and its resources can be limited. See
+    /// [`Model::with_timeout`] and [`Model::with_resource_limits`].
+    ///
+    /// `fj-host` provides the evaluator as the `fj-evaluate` binary. Defaults
+    /// to `fj-evaluate` in the directory of the current executable, where
+    /// cargo puts it when building an application within the same workspace,
+    /// or as found in the `PATH`, if it's not there.
+    pub fn with_evaluator(mut self, evaluator: impl Into<PathBuf>) -> Self {
+        self.evaluator = evaluator.into();
+        self
+    }
+}
+
+/// Determine which evaluator to use, if none has been configured
+pub(crate) fn default_path() -> PathBuf {
+    let name = format!("fj-evaluate{}", env::consts::EXE_SUFFIX);
+
+    let next_to_exe = env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join(&name)));
+    match next_to_exe {
+        Some(path) if path.exists() => path,
+        _ => name.into(),
+    }
+}
+
+/// Evaluate a model's dynamic library for each set of arguments, using the
+/// evaluator
+///
+/// If the evaluator doesn't finish within `timeout`, it is killed, and
+/// [`Error::Timeout`] is returned. If it exceeds one of `limits`, it is stopped,
+/// and [`Error::ResourceLimit`] is returned.
+pub(crate) fn evaluate(
+    evaluator: &Path,
+    lib_path: &Path,
+    arguments: &[Parameters],
+    state: Option<Vec<u8>>,
+    timeout: Option<Duration>,
+    limits: &ResourceLimits,
+) -> Result<Evaluation, Error> {
+    let dir = TempDir::new()?;
+    let request_path = dir.0.join("request");
+    let response_path = dir.0.join("response");
+
+    let request = Request {
+        arguments: arguments
+            .iter()
+            .map(|arguments| arguments.0.clone())
+            .collect(),
+        state,
+    };
+    fs::write(&request_path, encode(&request)?)?;
+
+    let mut command = Command::new(evaluator);
+    command.arg(lib_path).arg(&request_path).arg(&response_path);
+    limits.apply(&mut command)?;
+
+    let output = match output_with_limits(command, timeout, limits) {
+        Err(Error::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::EvaluatorNotFound(evaluator.to_path_buf()));
+        }
+        result => result?,
+    };
+
+    // Whatever the model printed would have shown up in the terminal, if it
+    // had been evaluated in this process. Make sure that it still does.
+    io::stdout().write_all(&output.stdout)?;
+    io::stderr().write_all(&output.stderr)?;
+
+    if !output.status.success() {
+        return Err(Error::Evaluator {
+            status: output.status,
+            output: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    decode(&fs::read(&response_path)?)
+}
+
+/// Run the evaluator
+///
+/// This is the entry point of the `fj-evaluate` binary. It isn't meant to be
+/// called from anywhere else. See [`Model::with_evaluator`].
+#[doc(hidden)]
+pub fn run_evaluator() -> ExitCode {
+    match run(env::args_os().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+
+            let mut source = std::error::Error::source(&err);
+            while let Some(err) = source {
+                eprintln!("  Caused by: {err}");
+                source = err.source();
+            }
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<OsString>) -> Result<(), Error> {
+    let (lib_path, request_path, response_path) = match args.as_slice() {
+        [lib_path, request_path, response_path] => {
+            (lib_path, request_path, response_path)
+        }
+        _ => {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Usage: fj-evaluate <library> <request> <response>",
+            )));
+        }
+    };
+
+    let request: Request = decode(&fs::read(request_path)?)?;
+    let arguments = request
+        .arguments
+        .into_iter()
+        .map(Parameters)
+        .collect::<Vec<_>>();
+
+    let evaluation =
+        evaluate_library(Path::new(lib_path), &arguments, request.state)?;
+    fs::write(response_path, encode(&evaluation)?)?;
+
+    Ok(())
+}
+
+/// What the host asks the evaluator to do
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Request {
+    /// The sets of arguments to evaluate the model with
+    ///
+    /// These have been evaluated already. See [`Parameters::evaluate`].
+    arguments: Vec<HashMap<String, String>>,
+
+    /// The state that the model saved, the last time it was unloaded
+    state: Option<Vec<u8>>,
+}
+
+fn encode(value: &impl Serialize) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+
+    bincode::serialize_into(&mut bytes, value).map_err(invalid_data)?;
+
+    Ok(bytes)
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let payload = match bytes {
+        [m0, m1, m2, m3, v0, v1, v2, v3, payload @ ..]
+            if [*m0, *m1, *m2, *m3] == MAGIC =>
+        {
+            let version = u32::from_le_bytes([*v0, *v1, *v2, *v3]);
+            if version != VERSION {
+                return Err(invalid_data(format!(
+                    "Evaluator uses version {version}, expected {VERSION}"
+                )));
+            }
+
+            payload
+        }
+        _ => return Err(invalid_data("Not a request or response")),
+    };
+
+    bincode::deserialize(payload).map_err(invalid_data)
+}
+
+fn invalid_data(
+    err: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// A directory for the files exchanged with the evaluator
+///
+/// The directory is removed again when this is dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> io::Result<Self> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path =
+            env::temp_dir().join(format!("fj-evaluate-{}-{id}", process::id()));
+        fs::create_dir_all(&path)?;
+
+        Ok(Self(path))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // Nothing can be done about an error here. At worst, the directory is
+        // left behind in the temporary directory.
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, env, fs, io, path::PathBuf, process};
+
+    #[cfg(unix)]
+    use std::{path::Path, time::Duration};
+
+    use crate::{Error, Parameters, ResourceLimits};
+
+    #[cfg(unix)]
+    use crate::{Evaluation, Resource};
+
+    use super::{decode, encode, Request};
+
+    #[test]
+    fn encode_and_decode() {
+        let request = Request {
+            arguments: vec![HashMap::from([("a".into(), "1".into())])],
+            state: Some(vec![1, 2, 3]),
+        };
+
+        let bytes = encode(&request).unwrap();
+        assert_eq!(decode::<Request>(&bytes).unwrap(), request);
+
+        let mut other_version = bytes.clone();
+        other_version[4] += 1;
+        assert_invalid_data(decode::<Request>(&other_version));
+        assert_invalid_data(decode::<Request>(&bytes[4..]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn evaluate() {
+        let dir = temp_dir("evaluate");
+
+        let shape: fj::Shape =
+            fj::Sketch::from_circle(fj::Circle::from_radius(1.)).into();
+        let response = Evaluation {
+            shapes: vec![shape.clone()],
+            parameter_metadata: None,
+            state: Some(vec![2]),
+        };
+        fs::write(dir.join("response"), encode(&response).unwrap()).unwrap();
+
+        let script = format!(
+            r#"cp "$1" "{0}/request"; cp "{0}/response" "$2""#,
+            dir.display()
+        );
+        let evaluation =
+            evaluate_script(&dir, &script, Some(vec![1]), None).unwrap();
+        assert_eq!(evaluation.shapes, [shape]);
+        assert_eq!(evaluation.state, Some(vec![2]));
+
+        // The evaluator has received the arguments, and the state.
+        let request: Request =
+            decode(&fs::read(dir.join("request")).unwrap()).unwrap();
+        assert_eq!(request.arguments[0]["a"], "1");
+        assert_eq!(request.state, Some(vec![1]));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn evaluator_fails() {
+        let dir = temp_dir("fails");
+
+        let result =
+            evaluate_script(&dir, "echo failure >&2; exit 3", None, None);
+        match result {
+            Err(Error::Evaluator { status, output }) => {
+                assert_eq!(status.code(), Some(3));
+                assert_eq!(output, "failure\n");
+            }
+            result => panic!("Expected evaluator error, got {result:?}"),
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn evaluator_times_out() {
+        let dir = temp_dir("timeout");
+
+        let timeout = Duration::from_millis(100);
+        let result = evaluate_script(&dir, "sleep 10", None, Some(timeout));
+        assert!(matches!(result, Err(Error::Timeout(t)) if t == timeout));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn evaluator_exceeds_limit() {
+        let dir = temp_dir("limit");
+
+        let lib_path = dir.join("evaluator.sh");
+        fs::write(&lib_path, "while :; do :; done").unwrap();
+
+        let limits = ResourceLimits {
+            cpu_time: Some(Duration::from_millis(500)),
+            ..ResourceLimits::default()
+        };
+        let result = super::evaluate(
+            Path::new("sh"),
+            &lib_path,
+            &[Parameters::empty()],
+            None,
+            None,
+            &limits,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::ResourceLimit {
+                resource: Resource::CpuTime,
+                ..
+            })
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn evaluator_not_found() {
+        let dir = temp_dir("not-found");
+
+        let evaluator = dir.join("fj-evaluate");
+        let result = super::evaluate(
+            &evaluator,
+            &dir.join("libmodel.so"),
+            &[Parameters::empty()],
+            None,
+            None,
+            &ResourceLimits::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::EvaluatorNotFound(path)) if path == evaluator
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir()
+            .join(format!("fj-host-evaluator-{name}-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Evaluate using `sh` as the evaluator
+    ///
+    /// `sh` runs `script` in place of the model's library, passing it the
+    /// paths of the request and the response.
+    #[cfg(unix)]
+    fn evaluate_script(
+        dir: &Path,
+        script: &str,
+        state: Option<Vec<u8>>,
+        timeout: Option<Duration>,
+    ) -> Result<Evaluation, Error> {
+        let lib_path = dir.join("evaluator.sh");
+        fs::write(&lib_path, script).unwrap();
+
+        let mut arguments = Parameters::empty();
+        arguments.insert("a", 1);
+
+        super::evaluate(
+            Path::new("sh"),
+            &lib_path,
+            &[arguments],
+            state,
+            timeout,
+            &ResourceLimits::default(),
+        )
+    }
+
+    fn assert_invalid_data<T: std::fmt::Debug>(result: Result<T, Error>) {
+        match result {
+            Err(Error::Io(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            }
+            result => panic!("Expected invalid data, got {result:?}"),
+        }
+    }
+}
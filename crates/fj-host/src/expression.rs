@@ -0,0 +1,338 @@
+use std::{collections::HashMap, iter::Peekable, str::Chars};
+
+use thiserror::Error;
+
+use crate::Parameters;
+
+impl Parameters {
+    /// Evaluate parameter values that are arithmetic expressions
+    ///
+    /// Values can be expressions like `width / 3`, made up of numbers, the
+    /// names of other parameters, the operators `+`, `-`, `*`, and `/`, and
+    /// parentheses. Each of those values is replaced with the result of its
+    /// expression.
+    ///
+    /// Values that aren't expressions (like `true` or `M3`) are left as they
+    /// are, as are plain numbers.
+    ///
+    /// Returns an error, if the expressions reference each other in a cycle, or
+    /// if an expression references a parameter that doesn't exist or doesn't
+    /// have a number value.
+    pub fn evaluate(&self) -> Result<Self, ExpressionError> {
+        let mut expressions = HashMap::new();
+        for (name, value) in self.iter() {
+            if let Some(expression) = Expression::parse(value) {
+                if let Expression::Reference(reference) = &expression {
+                    // A single word that doesn't name another parameter is
+                    // just a value, not an expression.
+                    if !self.contains_key(reference) {
+                        continue;
+                    }
+                }
+
+                expressions.insert(name.as_str(), expression);
+            }
+        }
+
+        let mut evaluation = Evaluation {
+            parameters: self,
+            expressions: &expressions,
+            values: HashMap::new(),
+            stack: Vec::new(),
+        };
+
+        let mut parameters = self.clone();
+        for (name, expression) in &expressions {
+            // Keep plain numbers as they are, so they don't get reformatted.
+            if let Expression::Number(_) = expression {
+                continue;
+            }
+
+            let value = evaluation.evaluate(name)?;
+            parameters.insert(*name, value);
+        }
+
+        Ok(parameters)
+    }
+}
+
+/// Error evaluating parameter expressions
+///
+/// See [`Parameters::evaluate`].
+#[derive(Debug, Error)]
+pub enum ExpressionError {
+    /// Parameters reference each other in a cycle
+    #[error("Parameters reference each other in a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+
+    /// An expression references a parameter that doesn't exist
+    #[error(
+        "Parameter \"{parameter}\" references unknown parameter \"{reference}\""
+    )]
+    UnknownReference {
+        /// The parameter whose expression contains the reference
+        parameter: String,
+
+        /// The name of the unknown parameter
+        reference: String,
+    },
+
+    /// A parameter that is used in an expression doesn't have a number value
+    #[error(
+        "Parameter \"{parameter}\" references \"{reference}\", which is not a \
+        number"
+    )]
+    NotANumber {
+        /// The parameter whose expression contains the reference
+        parameter: String,
+
+        /// The name of the parameter that is not a number
+        reference: String,
+    },
+}
+
+struct Evaluation<'r> {
+    parameters: &'r Parameters,
+    expressions: &'r HashMap<&'r str, Expression>,
+    values: HashMap<&'r str, f64>,
+    stack: Vec<&'r str>,
+}
+
+impl<'r> Evaluation<'r> {
+    fn evaluate(&mut self, name: &'r str) -> Result<f64, ExpressionError> {
+        if let Some(value) = self.values.get(name) {
+            return Ok(*value);
+        }
+
+        if let Some(start) = self.stack.iter().position(|&n| n == name) {
+            let mut cycle: Vec<_> =
+                self.stack[start..].iter().map(|&n| n.into()).collect();
+            cycle.push(name.into());
+            return Err(ExpressionError::Cycle(cycle));
+        }
+
+        let expression = &self.expressions[name];
+
+        self.stack.push(name);
+        let value = self.evaluate_expression(name, expression)?;
+        self.stack.pop();
+
+        self.values.insert(name, value);
+        Ok(value)
+    }
+
+    fn evaluate_expression(
+        &mut self,
+        name: &'r str,
+        expression: &'r Expression,
+    ) -> Result<f64, ExpressionError> {
+        let value = match expression {
+            Expression::Number(value) => *value,
+            Expression::Reference(reference) => {
+                if self.expressions.contains_key(reference.as_str()) {
+                    self.evaluate(reference)?
+                } else if self.parameters.contains_key(reference) {
+                    return Err(ExpressionError::NotANumber {
+                        parameter: name.into(),
+                        reference: reference.clone(),
+                    });
+                } else {
+                    return Err(ExpressionError::UnknownReference {
+                        parameter: name.into(),
+                        reference: reference.clone(),
+                    });
+                }
+            }
+            Expression::Negate(expression) => {
+                -self.evaluate_expression(name, expression)?
+            }
+            Expression::Binary(operator, a, b) => {
+                let a = self.evaluate_expression(name, a)?;
+                let b = self.evaluate_expression(name, b)?;
+
+                match operator {
+                    '+' => a + b,
+                    '-' => a - b,
+                    '*' => a * b,
+                    '/' => a / b,
+                    _ => unreachable!("Parser only produces known operators"),
+                }
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Expression {
+    Number(f64),
+    Reference(String),
+    Negate(Box<Expression>),
+    Binary(char, Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    /// Parse an expression
+    ///
+    /// Returns `None`, if the string is not a valid expression.
+    fn parse(s: &str) -> Option<Self> {
+        let mut parser = Parser {
+            chars: s.chars().peekable(),
+        };
+
+        let expression = parser.sum()?;
+
+        parser.skip_whitespace();
+        if parser.chars.next().is_some() {
+            return None;
+        }
+
+        Some(expression)
+    }
+}
+
+struct Parser<'r> {
+    chars: Peekable<Chars<'r>>,
+}
+
+impl Parser<'_> {
+    fn sum(&mut self) -> Option<Expression> {
+        let mut expression = self.product()?;
+
+        while let Some(operator) = self.operator(&['+', '-']) {
+            let rhs = self.product()?;
+            expression = Expression::Binary(
+                operator,
+                Box::new(expression),
+                Box::new(rhs),
+            );
+        }
+
+        Some(expression)
+    }
+
+    fn product(&mut self) -> Option<Expression> {
+        let mut expression = self.factor()?;
+
+        while let Some(operator) = self.operator(&['*', '/']) {
+            let rhs = self.factor()?;
+            expression = Expression::Binary(
+                operator,
+                Box::new(expression),
+                Box::new(rhs),
+            );
+        }
+
+        Some(expression)
+    }
+
+    fn factor(&mut self) -> Option<Expression> {
+        self.skip_whitespace();
+
+        match *self.chars.peek()? {
+            '-' => {
+                self.chars.next();
+                let expression = self.factor()?;
+                Some(Expression::Negate(Box::new(expression)))
+            }
+            '(' => {
+                self.chars.next();
+                let expression = self.sum()?;
+
+                self.skip_whitespace();
+                if self.chars.next()? != ')' {
+                    return None;
+                }
+
+                Some(expression)
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let number =
+                    self.take_while(|c| c.is_ascii_digit() || c == '.');
+                number.parse().ok().map(Expression::Number)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Some(Expression::Reference(name))
+            }
+            _ => None,
+        }
+    }
+
+    fn operator(&mut self, operators: &[char]) -> Option<char> {
+        self.skip_whitespace();
+
+        let c = *self.chars.peek()?;
+        if operators.contains(&c) {
+            self.chars.next();
+            return Some(c);
+        }
+
+        None
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let mut s = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !f(c) {
+                break;
+            }
+            s.push(c);
+            self.chars.next();
+        }
+        s
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parameters;
+
+    use super::ExpressionError;
+
+    #[test]
+    fn evaluate() {
+        let mut parameters = Parameters::empty();
+        parameters
+            .insert("width", "9")
+            .insert("hole_spacing", "width / 3")
+            .insert("offset", "-(hole_spacing + 1) * 2")
+            .insert("label", "M3");
+
+        let parameters = parameters.evaluate().unwrap();
+
+        assert_eq!(parameters["width"], "9");
+        assert_eq!(parameters["hole_spacing"], "3");
+        assert_eq!(parameters["offset"], "-8");
+        assert_eq!(parameters["label"], "M3");
+    }
+
+    #[test]
+    fn evaluate_errors() {
+        let mut parameters = Parameters::empty();
+        parameters.insert("a", "b + 1").insert("b", "a * 2");
+        assert!(matches!(
+            parameters.evaluate(),
+            Err(ExpressionError::Cycle(_))
+        ));
+
+        let mut parameters = Parameters::empty();
+        parameters.insert("a", "c + 1");
+        assert!(matches!(
+            parameters.evaluate(),
+            Err(ExpressionError::UnknownReference { .. })
+        ));
+
+        let mut parameters = Parameters::empty();
+        parameters.insert("a", "M3").insert("b", "a * 2");
+        assert!(matches!(
+            parameters.evaluate(),
+            Err(ExpressionError::NotANumber { .. })
+        ));
+    }
+}
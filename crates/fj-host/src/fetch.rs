@@ -0,0 +1,372 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{scaffold::is_valid_crate_name, Error, Model};
+
+impl Model {
+    /// Fetch a model from a remote source, then initialize the model from it
+    ///
+    /// The model is downloaded into `cache_dir`, and built there, just like a
+    /// model at a local path. Models from git are updated on every call,
+    /// while crates from crates.io are only downloaded once, as a published
+    /// version never changes.
+    ///
+    /// Requires `git`, or `cargo` respectively, to be available in the
+    /// `PATH`.
+    pub fn fetch(
+        source: &ModelSource,
+        cache_dir: &Path,
+    ) -> Result<Self, Error> {
+        let path = source.fetch(cache_dir)?;
+        Self::from_path(path, None)
+    }
+}
+
+/// A remote location that a model can be fetched from
+///
+/// See [`Model::fetch`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModelSource {
+    /// A git repository, whose root is a model crate
+    Git {
+        /// The URL of the repository
+        url: String,
+
+        /// The branch, tag, or commit to check out
+        ///
+        /// If this is `None`, the repository's default branch is used.
+        rev: Option<String>,
+    },
+
+    /// A model crate that has been published to crates.io
+    CratesIo {
+        /// The name of the crate
+        name: String,
+
+        /// The exact version of the crate
+        version: String,
+    },
+}
+
+impl ModelSource {
+    /// Parse a model source from a string
+    ///
+    /// Git repositories are specified as `git+<url>`, optionally followed by
+    /// `#<rev>`. Crates are specified as `crates.io:<name>@<version>`.
+    ///
+    /// Returns `None`, if the string is neither, which means it is probably
+    /// the path of a local model.
+    pub fn parse(source: &str) -> Option<Self> {
+        if let Some(source) = source.strip_prefix("git+") {
+            let (url, rev) = match source.split_once('#') {
+                Some((url, rev)) => (url, Some(rev.to_string())),
+                None => (source, None),
+            };
+
+            return Some(Self::Git {
+                url: url.to_string(),
+                rev,
+            });
+        }
+
+        if let Some(source) = source.strip_prefix("crates.io:") {
+            let (name, version) = source.split_once('@')?;
+
+            return Some(Self::CratesIo {
+                name: name.to_string(),
+                version: version.to_string(),
+            });
+        }
+
+        None
+    }
+
+    /// Fetch the model into `cache_dir`, and return the path of its crate
+    pub fn fetch(&self, cache_dir: &Path) -> Result<PathBuf, Error> {
+        match self {
+            Self::Git { url, rev } => {
+                let path = cache_dir.join("git").join(git_dir_name(url));
+                self.fetch_git(url, rev.as_deref(), &path)?;
+                Ok(path)
+            }
+            Self::CratesIo { name, version } => {
+                // Both end up in a path and in a generated `Cargo.toml`, so
+                // they must not contain anything but what they claim to be.
+                if !is_valid_crate_name(name) {
+                    return Err(Error::InvalidModelName(name.clone()));
+                }
+                if cargo_metadata::semver::Version::parse(version).is_err() {
+                    return Err(Error::Fetch {
+                        model: self.to_string(),
+                        output: format!("`{version}` is not a valid version"),
+                    });
+                }
+
+                let path =
+                    cache_dir.join("crates").join(format!("{name}-{version}"));
+                if !path.exists() {
+                    self.fetch_crate(name, version, cache_dir, &path)?;
+                }
+                Ok(path)
+            }
+        }
+    }
+
+    fn fetch_git(
+        &self,
+        url: &str,
+        rev: Option<&str>,
+        path: &Path,
+    ) -> Result<(), Error> {
+        if path.join(".git").exists() {
+            self.run(
+                Command::new("git")
+                    .arg("-C")
+                    .arg(path)
+                    .args(["fetch", "--tags", "origin"]),
+            )?;
+        } else {
+            fs::create_dir_all(path)?;
+            self.run(
+                Command::new("git").args(["clone", "--"]).arg(url).arg(path),
+            )?;
+        }
+
+        // The URL and the revision come from the user. `--` and
+        // `--end-of-options` make sure git never interprets them as options.
+        //
+        // Commits are checked out directly, instead of moving a local branch,
+        // so the checkout always matches the remote, even after a force push.
+        let rev = match rev {
+            Some(rev) => {
+                // Branches are only available as remote branches.
+                let branch = format!("origin/{rev}");
+                let is_branch = Command::new("git")
+                    .arg("-C")
+                    .arg(path)
+                    .args(["rev-parse", "--verify", "--quiet"])
+                    .args(["--end-of-options", &branch])
+                    .output()?
+                    .status
+                    .success();
+
+                if is_branch {
+                    branch
+                } else {
+                    rev.to_string()
+                }
+            }
+            None => "origin/HEAD".to_string(),
+        };
+        self.run(
+            Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(["checkout", "--quiet", "--detach"])
+                .args(["--end-of-options", &rev]),
+        )?;
+
+        Ok(())
+    }
+
+    fn fetch_crate(
+        &self,
+        name: &str,
+        version: &str,
+        cache_dir: &Path,
+        path: &Path,
+    ) -> Result<(), Error> {
+        // Cargo already knows how to download crates. Make it do that, by
+        // resolving a crate that depends on the model.
+        let resolver = cache_dir.join("resolver");
+        fs::create_dir_all(resolver.join("src"))?;
+        fs::write(
+            resolver.join("Cargo.toml"),
+            format!(
+                "[package]
+name = \"fj-resolver\"
+version = \"0.0.0\"
+edition = \"2021\"
+
+[dependencies]
+{name} = \"={version}\"
+"
+            ),
+        )?;
+        fs::write(resolver.join("src").join("lib.rs"), "")?;
+
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(resolver.join("Cargo.toml"))
+            .exec()
+            .map_err(|err| Error::Fetch {
+                model: self.to_string(),
+                output: err.to_string(),
+            })?;
+        let package = metadata
+            .packages
+            .iter()
+            .find(|package| {
+                package.name == name && package.version.to_string() == version
+            })
+            .ok_or_else(|| Error::Fetch {
+                model: self.to_string(),
+                output: "Crate not found after resolving it".to_string(),
+            })?;
+        let source = package
+            .manifest_path
+            .parent()
+            .expect("Manifest path has a parent directory");
+
+        // The downloaded source is shared by everything that cargo builds,
+        // and isn't supposed to be modified. The model is built in the cache
+        // instead. It is copied to a temporary directory first, so an
+        // interrupted copy doesn't leave a broken model behind.
+        let tmp = path.with_file_name(format!("{name}-{version}.tmp"));
+        if tmp.exists() {
+            fs::remove_dir_all(&tmp)?;
+        }
+        copy_dir(source.as_std_path(), &tmp)?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    fn run(&self, command: &mut Command) -> Result<(), Error> {
+        let output = command.output()?;
+
+        if !output.status.success() {
+            return Err(Error::Fetch {
+                model: self.to_string(),
+                output: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ModelSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Git { url, rev: None } => write!(f, "git+{url}"),
+            Self::Git {
+                url,
+                rev: Some(rev),
+            } => write!(f, "git+{url}#{rev}"),
+            Self::CratesIo { name, version } => {
+                write!(f, "crates.io:{name}@{version}")
+            }
+        }
+    }
+}
+
+/// The name of the directory that a git repository is cloned into
+///
+/// Starts with the name of the repository, so the directory is recognizable,
+/// followed by a hash of the URL, so repositories with the same name don't
+/// collide. The hash must not change between releases, or existing clones
+/// would be orphaned in the cache.
+fn git_dir_name(url: &str) -> String {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".git");
+
+    let hash = Sha256::digest(url.as_bytes());
+    let hash: String =
+        hash[..8].iter().map(|byte| format!("{byte:02x}")).collect();
+
+    format!("{name}-{hash}")
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), Error> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &path)?;
+        } else {
+            fs::copy(entry.path(), path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::Error;
+
+    use super::ModelSource;
+
+    #[test]
+    fn parse() {
+        let sources = [
+            ModelSource::Git {
+                url: "https://github.com/user/model".into(),
+                rev: None,
+            },
+            ModelSource::Git {
+                url: "https://github.com/user/model.git".into(),
+                rev: Some("v1.0".into()),
+            },
+            ModelSource::CratesIo {
+                name: "model".into(),
+                version: "0.1.0".into(),
+            },
+        ];
+
+        for source in sources {
+            assert_eq!(ModelSource::parse(&source.to_string()), Some(source));
+        }
+
+        assert_eq!(ModelSource::parse("models/spacer"), None);
+        assert_eq!(ModelSource::parse("crates.io:model"), None);
+    }
+
+    #[test]
+    fn git_dir_name() {
+        let name = super::git_dir_name("https://github.com/user/model.git");
+        assert!(name.starts_with("model-"));
+
+        assert_eq!(name, "model-90885959e102e560");
+        assert_ne!(
+            name,
+            super::git_dir_name("https://gitlab.com/user/model.git")
+        );
+    }
+
+    #[test]
+    fn fetch_invalid_crate() {
+        let cache_dir = env::temp_dir().join("fj-host-fetch-invalid");
+
+        let source = ModelSource::CratesIo {
+            name: "../model".into(),
+            version: "0.1.0".into(),
+        };
+        assert!(matches!(
+            source.fetch(&cache_dir),
+            Err(Error::InvalidModelName(_))
+        ));
+
+        let source = ModelSource::CratesIo {
+            name: "model".into(),
+            version: "0.1.0\"\n[patch]".into(),
+        };
+        assert!(matches!(source.fetch(&cache_dir), Err(Error::Fetch { .. })));
+
+        assert!(!cache_dir.exists());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Error, Parameters, Timings};
+
+#[cfg(feature = "native")]
+use crate::Model;
+
+/// A model that can be evaluated by the host
+///
+/// This is implemented by [`Model`], which compiles a model crate and loads it
+/// as a dynamic library. It is also implemented for plain Rust closures, which
+/// makes it possible to host a model in-process, without compiling anything.
+/// This is useful for integration tests, or when embedding Fornjot into
+/// another application.
+///
+/// See [`Watcher::from_hosted`](crate::Watcher::from_hosted).
+pub trait HostedModel {
+    /// Evaluate the model using the provided parameters
+    fn load(&self, parameters: &Parameters) -> Result<fj::Shape, Error>;
+
+    /// Evaluate the model again, after only the parameters have changed
+    ///
+    /// Models that need to be compiled can skip that here. By default, this
+    /// calls [`HostedModel::load`].
+    fn evaluate(&self, parameters: &Parameters) -> Result<fj::Shape, Error> {
+        self.load(parameters)
+    }
+
+    /// Describe the parameters of the model
+    ///
+    /// Returns the parameters as of the most recent time the model was loaded
+    /// or evaluated. Returns no parameters by default, as not every model can
+    /// describe them.
+    fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        Vec::new()
+    }
+
+    /// Report how long it took to load the model the last time
+    ///
+    /// Returns `None` by default, as not every model keeps track of that.
+    fn timings(&self) -> Option<Timings> {
+        None
+    }
+}
+
+#[cfg(feature = "native")]
+impl HostedModel for Model {
+    fn load(&self, parameters: &Parameters) -> Result<fj::Shape, Error> {
+        self.load_once(parameters)
+    }
+
+    fn evaluate(&self, parameters: &Parameters) -> Result<fj::Shape, Error> {
+        Model::evaluate(self, parameters)
+    }
+
+    fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        Model::parameter_metadata(self)
+    }
+
+    fn timings(&self) -> Option<Timings> {
+        Model::timings(self)
+    }
+}
+
+impl<F> HostedModel for F
+where
+    F: Fn(&Parameters) -> fj::Shape,
+{
+    fn load(&self, parameters: &Parameters) -> Result<fj::Shape, Error> {
+        Ok(self(&parameters.evaluate()?))
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::{Parameters, Watcher, WatcherEvent};
+
+    #[test]
+    fn from_hosted() {
+        let mut parameters = Parameters::empty();
+        parameters.insert("radius", 2);
+
+        let watcher = Watcher::from_hosted(
+            |parameters: &Parameters| {
+                let radius = parameters["radius"].parse().unwrap();
+                fj::Sketch::from_circle(fj::Circle::from_radius(radius)).into()
+            },
+            parameters,
+        );
+
+        // The initial load is triggered from another thread, so it might not
+        // be available right away.
+        let event = (0..100)
+            .find_map(|_| {
+                let event = watcher.receive_event().unwrap();
+                if event.is_none() {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                event
+            })
+            .expect("Expected initial load of hosted model");
+        let WatcherEvent::Shape(shape) = event;
+
+        let expected: fj::Shape =
+            fj::Sketch::from_circle(fj::Circle::from_radius(2.)).into();
+        assert_eq!(shape, expected);
+
+        // Hosted models are not watched for changes.
+        thread::sleep(Duration::from_millis(10));
+        assert!(watcher.receive_event().unwrap().is_none());
+    }
+
+    #[test]
+    fn update_parameters() {
+        let mut parameters = Parameters::empty();
+        parameters.insert("radius", 2);
+
+        let mut watcher = Watcher::from_hosted(
+            |parameters: &Parameters| {
+                let radius = parameters["radius"].parse().unwrap();
+                fj::Sketch::from_circle(fj::Circle::from_radius(radius)).into()
+            },
+            parameters.clone(),
+        );
+
+        parameters.insert("radius", 3);
+        watcher.update_parameters(parameters.clone());
+        assert_eq!(watcher.parameters(), &parameters);
+
+        // The model is evaluated right away, without waiting for the initial
+        // load.
+        let WatcherEvent::Shape(shape) = watcher
+            .receive_event()
+            .unwrap()
+            .expect("Expected model to be evaluated with new parameters");
+
+        let expected: fj::Shape =
+            fj::Sketch::from_circle(fj::Circle::from_radius(3.)).into();
+        assert_eq!(shape, expected);
+        assert!(watcher.parameter_metadata().is_empty());
+        assert!(watcher.timings().is_none());
+    }
+}
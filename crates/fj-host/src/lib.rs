@@ -15,31 +15,96 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "native")]
+mod evaluator;
+mod expression;
+#[cfg(feature = "native")]
+mod fetch;
+mod hosted;
+#[cfg(feature = "native")]
+mod limits;
+mod parameter_sweep;
+#[cfg(feature = "native")]
 mod platform;
+mod presets;
+#[cfg(feature = "native")]
+mod scaffold;
+#[cfg(feature = "script")]
+mod script;
+#[cfg(feature = "async")]
+mod watcher_stream;
 
 use std::{
-    collections::{HashMap, HashSet},
-    ffi::OsStr,
+    collections::HashMap,
     io,
     ops::{Deref, DerefMut},
-    path::{Path, PathBuf},
-    process::Command,
-    sync::mpsc,
+    path::PathBuf,
+    time::Duration,
+};
+
+#[cfg(feature = "native")]
+use std::{
+    collections::HashSet,
+    ffi::{OsStr, OsString},
+    io::{Read, Write as _},
+    path::Path,
+    process::{Command, ExitStatus, Output, Stdio},
+    slice,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Mutex,
+    },
     thread,
+    time::Instant,
 };
 
+#[cfg(feature = "native")]
 use notify::Watcher as _;
+#[cfg(feature = "native")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+#[cfg(feature = "native")]
 use self::platform::HostPlatform;
 
+#[doc(hidden)]
+#[cfg(feature = "native")]
+pub use self::evaluator::run_evaluator;
+#[cfg(feature = "script")]
+pub use self::script::Script;
+#[cfg(feature = "async")]
+pub use self::watcher_stream::WatcherStream;
+pub use self::{
+    expression::ExpressionError, hosted::HostedModel,
+    parameter_sweep::ParameterSweep,
+};
+#[cfg(feature = "native")]
+pub use self::{
+    fetch::ModelSource,
+    limits::{Resource, ResourceLimits},
+};
+
 /// Represents a Fornjot model
+#[cfg(feature = "native")]
 pub struct Model {
     src_path: PathBuf,
-    lib_path: PathBuf,
+    lib_name: String,
+    target_dir: PathBuf,
     manifest_path: PathBuf,
+    cargo: OsString,
+    features: Vec<String>,
+    target: Option<String>,
+    offline: bool,
+    envs: Vec<(OsString, OsString)>,
+    timeout: Option<Duration>,
+    evaluator: PathBuf,
+    resource_limits: ResourceLimits,
+    state: Mutex<Option<Vec<u8>>>,
+    parameter_metadata: Mutex<Vec<fj::ParameterMetadata>>,
+    timings: Mutex<Option<Timings>>,
 }
 
+#[cfg(feature = "native")]
 impl Model {
     /// Initialize the model using the path to its crate (i.e. the folder
     /// containing `Cargo.toml`).
@@ -58,23 +123,115 @@ impl Model {
             .exec()?;
 
         let pkg = package_associated_with_directory(&metadata, &crate_dir)?;
+        validate_package(pkg)?;
         let src_path = crate_dir.join("src");
 
-        let lib_path = {
-            let name = pkg.name.replace('-', "_");
-            let file = HostPlatform::lib_file_name(&name);
-            let target_dir = target_dir
-                .unwrap_or_else(|| metadata.target_directory.clone().into());
-            target_dir.join("debug").join(file)
-        };
+        let lib_name = pkg.name.replace('-', "_");
+        let target_dir = target_dir
+            .unwrap_or_else(|| metadata.target_directory.clone().into());
 
         Ok(Self {
             src_path,
-            lib_path,
+            lib_name,
+            target_dir,
             manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
+            cargo: "cargo".into(),
+            features: Vec::new(),
+            target: None,
+            offline: false,
+            envs: Vec::new(),
+            timeout: None,
+            evaluator: evaluator::default_path(),
+            resource_limits: ResourceLimits::default(),
+            state: Mutex::new(None),
+            parameter_metadata: Mutex::new(Vec::new()),
+            timings: Mutex::new(None),
         })
     }
 
+    /// Use an alternative cargo binary to compile the model
+    ///
+    /// Defaults to `cargo`, as found in the `PATH`.
+    pub fn with_cargo(mut self, cargo: impl Into<OsString>) -> Self {
+        self.cargo = cargo.into();
+        self
+    }
+
+    /// Enable the provided features when compiling the model
+    pub fn with_features(
+        mut self,
+        features: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Compile the model for the provided target triple
+    ///
+    /// Passes `--target` to cargo. The compiled library is then expected in
+    /// the subdirectory of the target directory that is named after the
+    /// target.
+    pub fn with_target(mut self, triple: impl Into<String>) -> Self {
+        self.target = Some(triple.into());
+        self
+    }
+
+    /// Compile the model without accessing the network
+    ///
+    /// Passes `--offline` to cargo.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Set an environment variable when compiling the model
+    pub fn with_env(
+        mut self,
+        key: impl Into<OsString>,
+        value: impl Into<OsString>,
+    ) -> Self {
+        self.envs.push((key.into(), value.into()));
+        self
+    }
+
+    /// Abort compiling or evaluating the model, if it takes longer
+    ///
+    /// The timeout applies to compiling the model and to evaluating it,
+    /// separately. If either of them takes longer, it is aborted, and
+    /// [`Error::Timeout`] is returned. By default, there is no timeout.
+    ///
+    /// Compiling is aborted by killing cargo. On Unix, this includes the
+    /// compiler processes that cargo started, as cargo runs in its own process
+    /// group. On other platforms, those finish on their own.
+    ///
+    /// A model that runs in this process can't be stopped safely. If a timeout
+    /// is set, the model is evaluated in a separate process instead, which is
+    /// killed, if the model doesn't return in time. That process runs the
+    /// evaluator. See [`Model::with_evaluator`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Limit the resources that evaluating the model may use
+    ///
+    /// The limits keep untrusted models from exhausting the machine. If limits
+    /// are set, the model is evaluated in a separate process, which runs the
+    /// evaluator (see [`Model::with_evaluator`]), and the limits apply to that
+    /// process. If one of them is exceeded, the process is stopped, and
+    /// [`Error::ResourceLimit`] is returned. By default, there are no limits.
+    ///
+    /// CPU time can be limited on Unix. Memory can only be limited on Linux.
+    /// On other platforms, evaluating fails with [`Error::Io`], if a limit is
+    /// set that isn't supported there. See [`ResourceLimits`] for details.
+    ///
+    /// Compiling the model is not limited. See [`Model::with_timeout`], which
+    /// at least bounds how long that takes.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = limits;
+        self
+    }
+
     /// Load the model once
     ///
     /// The passed arguments are provided to the model. Returns the shape that
@@ -86,42 +243,179 @@ impl Model {
         &self,
         arguments: &Parameters,
     ) -> Result<fj::Shape, Error> {
-        let manifest_path = self.manifest_path.display().to_string();
+        let mut shapes = self.load_for_each(slice::from_ref(arguments))?;
+        let shape =
+            shapes.pop().expect("Loaded model for one set of arguments");
 
-        let status = Command::new("cargo")
-            .arg("build")
-            .args(["--manifest-path", &manifest_path])
-            .status()?;
+        Ok(shape)
+    }
+
+    /// Load the model once, then evaluate it for each set of arguments
+    ///
+    /// The model is only compiled and loaded once, which makes this much
+    /// faster than calling [`Model::load_once`] repeatedly. Returns the shapes
+    /// that the model returns, in the order of the provided arguments.
+    ///
+    /// [`ParameterSweep`] can be used to generate the sets of arguments.
+    ///
+    /// Parameter values that are expressions are evaluated before they are
+    /// passed to the model. See [`Parameters::evaluate`].
+    ///
+    /// If the model defines state hooks (using `#[fj::on_unload]` and
+    /// `#[fj::on_load]`), the state it saved before being unloaded is
+    /// restored the next time it is loaded. This allows models to keep caches
+    /// across reloads.
+    pub fn load_for_each(
+        &self,
+        arguments: &[Parameters],
+    ) -> Result<Vec<fj::Shape>, Error> {
+        let arguments = arguments
+            .iter()
+            .map(Parameters::evaluate)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let command = self.build_command();
+
+        let start = Instant::now();
+        let output = output_with_timeout(command, self.timeout)?;
+        let compile = start.elapsed();
 
-        if !status.success() {
-            return Err(Error::Compile);
+        // The output is captured, so it can be passed along with the error.
+        // Forward it, so it still shows up in the terminal too.
+        io::stderr().write_all(&output.stderr)?;
+
+        if !output.status.success() {
+            let output = String::from_utf8_lossy(&output.stderr).into_owned();
+            return Err(Error::Compile { output });
         }
 
-        // So, strictly speaking this is all unsound:
-        // - `Library::new` requires us to abide by the arbitrary requirements
-        //   of any library initialization or termination routines.
-        // - `Library::get` requires us to specify the correct type for the
-        //   model function.
-        // - The model function itself is `unsafe`, because it is a function
-        //   from across an FFI interface.
-        //
-        // Typical models won't have initialization or termination routines (I
-        // think), should abide by the `ModelFn` signature, and might not do
-        // anything unsafe. But we have no way to know that the library the user
-        // told us to load actually does (I think).
-        //
-        // I don't know of a way to fix this. We should take this as motivation
-        // to switch to a better technique:
-        // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
-            let lib = libloading::Library::new(&self.lib_path)?;
-            let model: libloading::Symbol<ModelFn> = lib.get(b"model")?;
-            model(arguments)
-        };
+        self.evaluate_library(&arguments, Some(compile))
+    }
+
+    /// Evaluate the model again, without compiling it first
+    ///
+    /// Like [`Model::load_once`], but uses the dynamic library from the last
+    /// time the model was compiled. This is much faster, and useful if only
+    /// the arguments have changed, but not the model's source code.
+    pub fn evaluate(&self, arguments: &Parameters) -> Result<fj::Shape, Error> {
+        let arguments = arguments.evaluate()?;
+
+        let mut shapes =
+            self.evaluate_library(slice::from_ref(&arguments), None)?;
+        let shape = shapes
+            .pop()
+            .expect("Evaluated model for one set of arguments");
 
         Ok(shape)
     }
 
+    /// Describe the parameters of the model
+    ///
+    /// Returns the parameters as of the most recent time the model was loaded
+    /// or evaluated, using the last set of arguments it was evaluated with.
+    /// Returns no parameters, if the model has not been loaded yet, or if it
+    /// doesn't describe its parameters (which models defined using
+    /// `#[fj::model]` do).
+    pub fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        self.parameter_metadata
+            .lock()
+            .expect("Parameter metadata mutex poisoned")
+            .clone()
+    }
+
+    /// Report how long it took to load the model the last time
+    ///
+    /// Returns `None`, if the model has not been loaded successfully yet.
+    pub fn timings(&self) -> Option<Timings> {
+        *self.timings.lock().expect("Timings mutex poisoned")
+    }
+
+    /// Access the path of the model's source code
+    ///
+    /// This is the path that [`Model::load_and_watch`] watches for changes.
+    pub fn src_path(&self) -> &Path {
+        &self.src_path
+    }
+
+    /// Load the model's dynamic library, evaluate it for each set of
+    /// arguments, and record how long that took
+    ///
+    /// Expects the arguments to have been evaluated already. `compile` is how
+    /// long compiling the model took, if it was compiled before.
+    fn evaluate_library(
+        &self,
+        arguments: &[Parameters],
+        compile: Option<Duration>,
+    ) -> Result<Vec<fj::Shape>, Error> {
+        let start = Instant::now();
+
+        let state = self.state.lock().expect("State mutex poisoned").clone();
+        let evaluation = if self.timeout.is_some()
+            || self.resource_limits != ResourceLimits::default()
+        {
+            evaluator::evaluate(
+                &self.evaluator,
+                &self.lib_path(),
+                arguments,
+                state,
+                self.timeout,
+                &self.resource_limits,
+            )?
+        } else {
+            evaluate_library(&self.lib_path(), arguments, state)?
+        };
+
+        *self.state.lock().expect("State mutex poisoned") = evaluation.state;
+
+        // Older models don't describe their parameters. Keep whatever we
+        // know, in that case.
+        if let Some(metadata) = evaluation.parameter_metadata {
+            *self
+                .parameter_metadata
+                .lock()
+                .expect("Parameter metadata mutex poisoned") = metadata;
+        }
+
+        *self.timings.lock().expect("Timings mutex poisoned") = Some(Timings {
+            compile,
+            evaluate: start.elapsed(),
+        });
+
+        Ok(evaluation.shapes)
+    }
+
+    fn lib_path(&self) -> PathBuf {
+        HostPlatform::lib_path(
+            &self.target_dir,
+            self.target.as_deref(),
+            "debug",
+            &self.lib_name,
+        )
+    }
+
+    fn build_command(&self) -> Command {
+        let mut command = Command::new(&self.cargo);
+
+        command
+            .arg("build")
+            .arg("--manifest-path")
+            .arg(&self.manifest_path);
+
+        if !self.features.is_empty() {
+            command.args(["--features", &self.features.join(",")]);
+        }
+        if let Some(target) = &self.target {
+            command.args(["--target", target]);
+        }
+        if self.offline {
+            command.arg("--offline");
+        }
+
+        command.envs(self.envs.iter().map(|(key, value)| (key, value)));
+
+        command
+    }
+
     /// Load the model, then watch it for changes
     ///
     /// Whenever a change is detected, the model is being reloaded.
@@ -132,80 +426,108 @@ impl Model {
         self,
         parameters: Parameters,
     ) -> Result<Watcher, Error> {
-        let (tx, rx) = mpsc::sync_channel(0);
-        let tx2 = tx.clone();
+        let src_path = self.src_path.clone();
+        watch_and_load(
+            self,
+            &src_path,
+            notify::RecursiveMode::Recursive,
+            parameters,
+        )
+    }
+}
 
-        let watch_path = self.src_path.clone();
-
-        let mut watcher = notify::recommended_watcher(
-            move |event: notify::Result<notify::Event>| {
-                // Unfortunately the `notify` documentation doesn't say when
-                // this might happen, so no idea if it needs to be handled.
-                let event = event.expect("Error handling watch event");
-
-                // Various acceptable ModifyKind kinds. Varies across platforms
-                // (e.g. MacOs vs. Windows10)
-                if let notify::EventKind::Modify(
-                    notify::event::ModifyKind::Any,
-                )
-                | notify::EventKind::Modify(
-                    notify::event::ModifyKind::Data(
-                        notify::event::DataChange::Any,
-                    ),
-                )
-                | notify::EventKind::Modify(
-                    notify::event::ModifyKind::Data(
-                        notify::event::DataChange::Content,
-                    ),
-                ) = event.kind
-                {
-                    let file_ext = event
-                        .paths
-                        .get(0)
-                        .expect("File path missing in watch event")
-                        .extension();
-
-                    let black_list = HashSet::from([
-                        OsStr::new("swp"),
-                        OsStr::new("tmp"),
-                        OsStr::new("swx"),
-                    ]);
-
-                    if let Some(ext) = file_ext {
-                        if black_list.contains(ext) {
-                            return;
-                        }
-                    }
+/// Start watching `path` for changes
+///
+/// Sends a message through `tx` for every relevant change.
+#[cfg(feature = "native")]
+fn watch(
+    path: &Path,
+    recursive_mode: notify::RecursiveMode,
+    tx: mpsc::SyncSender<notify::Result<()>>,
+) -> Result<notify::RecommendedWatcher, Error> {
+    let mut watcher = notify::recommended_watcher(
+        move |event: notify::Result<notify::Event>| {
+            // Unfortunately the `notify` documentation doesn't say when
+            // this might happen. Leave it to the host to handle it.
+            let event = match event {
+                Ok(event) => event,
+                Err(err) => {
+                    // The other end only disconnects, if the `Watcher`
+                    // has been dropped. Nobody's interested in the error
+                    // then.
+                    let _ = tx.send(Err(err));
+                    return;
+                }
+            };
+
+            // Various acceptable ModifyKind kinds. Varies across platforms
+            // (e.g. MacOs vs. Windows10)
+            if let notify::EventKind::Modify(notify::event::ModifyKind::Any)
+            | notify::EventKind::Modify(notify::event::ModifyKind::Data(
+                notify::event::DataChange::Any,
+            ))
+            | notify::EventKind::Modify(notify::event::ModifyKind::Data(
+                notify::event::DataChange::Content,
+            )) = event.kind
+            {
+                let file_ext =
+                    event.paths.first().and_then(|path| path.extension());
 
-                    // This will panic, if the other end is disconnected, which
-                    // is probably the result of a panic on that thread, or the
-                    // application is being shut down.
-                    //
-                    // Either way, not much we can do about it here.
-                    tx.send(()).expect("Channel is disconnected");
+                let black_list = HashSet::from([
+                    OsStr::new("swp"),
+                    OsStr::new("tmp"),
+                    OsStr::new("swx"),
+                ]);
+
+                if let Some(ext) = file_ext {
+                    if black_list.contains(ext) {
+                        return;
+                    }
                 }
-            },
-        )?;
 
-        watcher.watch(&watch_path, notify::RecursiveMode::Recursive)?;
+                // The other end only disconnects, if the `Watcher` has
+                // been dropped. Then nobody is interested in the change.
+                let _ = tx.send(Ok(()));
+            }
+        },
+    )?;
 
-        // To prevent a race condition between the initial load and the start of
-        // watching, we'll trigger the initial load here, after having started
-        // watching.
-        //
-        // Will panic, if the receiving end has panicked. Not much we can do
-        // about that, if it happened.
-        thread::spawn(move || tx2.send(()).expect("Channel is disconnected"));
+    watcher.watch(path, recursive_mode)?;
 
-        Ok(Watcher {
-            _watcher: Box::new(watcher),
-            channel: rx,
-            model: self,
-            parameters,
-        })
-    }
+    Ok(watcher)
 }
 
+/// Load a model, then reload it whenever `path` changes
+#[cfg(feature = "native")]
+fn watch_and_load(
+    model: impl HostedModel + 'static,
+    path: &Path,
+    recursive_mode: notify::RecursiveMode,
+    parameters: Parameters,
+) -> Result<Watcher, Error> {
+    let (tx, rx) = mpsc::sync_channel(0);
+    let tx2 = tx.clone();
+
+    let watcher = watch(path, recursive_mode, tx)?;
+
+    // To prevent a race condition between the initial load and the start of
+    // watching, we'll trigger the initial load here, after having started
+    // watching.
+    thread::spawn(move || {
+        let _ = tx2.send(Ok(()));
+    });
+
+    Ok(Watcher {
+        _watcher: Some(Box::new(watcher)),
+        _sender: None,
+        channel: rx,
+        model: Box::new(model),
+        parameters,
+        parameters_updated: AtomicBool::new(false),
+    })
+}
+
+#[cfg(feature = "native")]
 fn package_associated_with_directory<'m>(
     metadata: &'m cargo_metadata::Metadata,
     dir: &Path,
@@ -224,6 +546,55 @@ fn package_associated_with_directory<'m>(
     Err(ambiguous_path_error(metadata, dir))
 }
 
+/// Check that the package can be loaded as a model
+///
+/// Doing this before building the model results in much better error messages
+/// than trying to load a library that doesn't contain a model.
+#[cfg(feature = "native")]
+fn validate_package(pkg: &cargo_metadata::Package) -> Result<(), Error> {
+    let invalid = |reason: String| Error::InvalidModelCrate {
+        manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
+        reason,
+    };
+
+    let is_cdylib = pkg
+        .targets
+        .iter()
+        .flat_map(|target| &target.crate_types)
+        .any(|crate_type| crate_type == "cdylib");
+    if !is_cdylib {
+        return Err(invalid(
+            "crate must have `crate-type = [\"cdylib\"]` in its `[lib]` \
+            section"
+                .into(),
+        ));
+    }
+
+    let fj = pkg.dependencies.iter().find(|dependency| {
+        dependency.name == "fj"
+            && dependency.kind == cargo_metadata::DependencyKind::Normal
+    });
+    let fj = match fj {
+        Some(fj) => fj,
+        None => return Err(invalid("crate must depend on `fj`".into())),
+    };
+
+    // `fj` and `fj-host` are always released together, with the same version.
+    let version =
+        cargo_metadata::semver::Version::parse(env!("CARGO_PKG_VERSION"))
+            .expect("Package version is a valid version");
+    if !fj.req.matches(&version) {
+        return Err(invalid(format!(
+            "dependency `fj = \"{}\"` is not compatible with version \
+            {version}, which this host requires",
+            fj.req
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "native")]
 fn ambiguous_path_error(
     metadata: &cargo_metadata::Metadata,
     dir: &Path,
@@ -251,51 +622,160 @@ fn ambiguous_path_error(
 }
 
 /// Watches a model for changes, reloading it continually
+#[cfg(feature = "native")]
 pub struct Watcher {
-    _watcher: Box<dyn notify::Watcher>,
-    channel: mpsc::Receiver<()>,
-    model: Model,
+    _watcher: Option<Box<dyn notify::Watcher>>,
+    _sender: Option<mpsc::SyncSender<notify::Result<()>>>,
+    channel: mpsc::Receiver<notify::Result<()>>,
+    model: Box<dyn HostedModel>,
     parameters: Parameters,
+    parameters_updated: AtomicBool,
 }
 
+#[cfg(feature = "native")]
 impl Watcher {
-    /// Receive an updated shape that the reloaded model created
+    /// Host a model in-process, instead of loading it from a dynamic library
     ///
-    /// Returns `None`, if the model has not changed since the last time this
-    /// method was called.
-    pub fn receive(&self) -> Option<fj::Shape> {
-        match self.channel.try_recv() {
-            Ok(()) => {
-                let shape = match self.model.load_once(&self.parameters) {
-                    Ok(shape) => shape,
-                    Err(Error::Compile) => {
-                        // It would be better to display an error in the UI,
-                        // where the user can actually see it. Issue:
-                        // https://github.com/hannobraun/fornjot/issues/30
-                        println!("Error compiling model");
-                        return None;
-                    }
-                    Err(err) => {
-                        panic!("Error reloading model: {:?}", err);
-                    }
-                };
+    /// The model is loaded once, as soon as the returned `Watcher` is first
+    /// queried. Since there's no source code to watch, it is never reloaded
+    /// after that.
+    ///
+    /// See [`HostedModel`].
+    pub fn from_hosted(
+        model: impl HostedModel + 'static,
+        parameters: Parameters,
+    ) -> Self {
+        let (tx, rx) = mpsc::sync_channel(0);
+        let tx2 = tx.clone();
+
+        thread::spawn(move || {
+            let _ = tx2.send(Ok(()));
+        });
 
-                Some(shape)
+        Self {
+            _watcher: None,
+            // Nothing else is keeping the channel open, so we need to keep a
+            // sender around. Otherwise the channel would be reported as
+            // disconnected.
+            _sender: Some(tx),
+            channel: rx,
+            model: Box::new(model),
+            parameters,
+            parameters_updated: AtomicBool::new(false),
+        }
+    }
+
+    /// Host a model, and load it again whenever `path` changes
+    ///
+    /// Like [`Model::load_and_watch`], but for any [`HostedModel`]. This
+    /// allows applications to wrap a [`Model`], for example to share it
+    /// between threads. The model is loaded once, as soon as the returned
+    /// `Watcher` is first queried, and again after every change.
+    pub fn watch_hosted(
+        model: impl HostedModel + 'static,
+        path: &Path,
+        parameters: Parameters,
+    ) -> Result<Self, Error> {
+        watch_and_load(
+            model,
+            path,
+            notify::RecursiveMode::Recursive,
+            parameters,
+        )
+    }
+
+    /// Access the parameters that are passed to the model
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    /// Update the parameters that are passed to the model
+    ///
+    /// The model is evaluated again with the new parameters, the next time
+    /// [`Watcher::receive_event`] is called. This doesn't require the model
+    /// to be compiled again, so it is fast enough to update the parameters
+    /// interactively.
+    pub fn update_parameters(&mut self, parameters: Parameters) {
+        self.parameters = parameters;
+        self.parameters_updated.store(true, Ordering::SeqCst);
+    }
+
+    /// Describe the parameters of the model
+    ///
+    /// See [`HostedModel::parameter_metadata`].
+    pub fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        self.model.parameter_metadata()
+    }
+
+    /// Report how long it took to load the model the last time
+    ///
+    /// See [`HostedModel::timings`].
+    pub fn timings(&self) -> Option<Timings> {
+        self.model.timings()
+    }
+
+    /// Receive the next event from the watcher
+    ///
+    /// Returns `Ok(None)`, if the model has not changed since the last time
+    /// this method was called.
+    ///
+    /// Returns an error, if watching the model failed, or if the model failed
+    /// to load. Errors are not fatal, unless they are
+    /// [`Error::Disconnected`]. The watcher keeps watching the model, and can
+    /// continue to be used after any other error.
+    pub fn receive_event(&self) -> Result<Option<WatcherEvent>, Error> {
+        if self.parameters_updated.swap(false, Ordering::SeqCst) {
+            let shape = self.model.evaluate(&self.parameters)?;
+            return Ok(Some(WatcherEvent::Shape(shape)));
+        }
+
+        match self.channel.try_recv() {
+            Ok(Ok(())) => {
+                let shape = self.model.load(&self.parameters)?;
+                Ok(Some(WatcherEvent::Shape(shape)))
             }
+            Ok(Err(err)) => Err(Error::Notify(err)),
             Err(mpsc::TryRecvError::Empty) => {
                 // Nothing to receive from the channel.
-                None
+                Ok(None)
             }
             Err(mpsc::TryRecvError::Disconnected) => {
                 // The other end has disconnected. This is probably the result
                 // of a panic on the other thread, or a program shutdown in
-                // progress. In any case, not much we can do here.
-                panic!();
+                // progress.
+                Err(Error::Disconnected)
             }
         }
     }
 }
 
+/// How long it took to load a model
+///
+/// Returned by [`Model::timings`], for the most recent time the model was
+/// loaded. Applications can use this to tell the user how long regenerating
+/// the model took, or to spot regressions.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timings {
+    /// How long compiling the model took
+    ///
+    /// This is `None`, if the model was evaluated again, without compiling
+    /// it. See [`Model::evaluate`].
+    pub compile: Option<Duration>,
+
+    /// How long evaluating the model took
+    ///
+    /// Includes loading the model's dynamic library.
+    pub evaluate: Duration,
+}
+
+/// An event emitted by [`Watcher`]
+#[cfg(feature = "native")]
+#[derive(Debug)]
+pub enum WatcherEvent {
+    /// The model has been (re-)loaded and created a new shape
+    Shape(fj::Shape),
+}
+
 /// Parameters that are passed to a model.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Parameters(pub HashMap<String, String>);
@@ -337,22 +817,132 @@ impl DerefMut for Parameters {
 pub enum Error {
     /// Model failed to compile
     #[error("Error compiling model")]
-    Compile,
+    Compile {
+        /// The output of the compiler, including its diagnostics
+        output: String,
+    },
 
     /// I/O error while loading the model
     #[error("I/O error while loading model")]
     Io(#[from] io::Error),
 
     /// Failed to load the model's dynamic library
+    #[cfg(feature = "native")]
     #[error("Error loading model from dynamic library")]
     LibLoading(#[from] libloading::Error),
 
     /// Error while watching the model code for changes
+    #[cfg(feature = "native")]
     #[error("Error watching model for changes")]
     Notify(#[from] notify::Error),
 
+    /// The model crate is not configured correctly
+    #[error("Invalid model crate \"{}\": {reason}", manifest_path.display())]
+    InvalidModelCrate {
+        /// The path to the model crate's `Cargo.toml`
+        manifest_path: PathBuf,
+        /// What's wrong with the model crate
+        reason: String,
+    },
+
+    /// The name of a new model is not a valid crate name
+    #[error("\"{0}\" is not a valid name for a model crate")]
+    InvalidModelName(String),
+
+    /// A new model can't be created, because its directory is not empty
+    #[error("Can't create model at \"{}\", directory is not empty", .0.display())]
+    ModelExists(PathBuf),
+
+    /// The parameters passed to the model could not be evaluated
+    #[error("Error evaluating parameters")]
+    Expression(#[from] ExpressionError),
+
+    /// A file with parameter presets could not be parsed
+    #[error("Invalid parameter presets \"{}\": {reason}", path.display())]
+    InvalidPresets {
+        /// The path to the file with the presets
+        path: PathBuf,
+        /// What's wrong with the file
+        reason: String,
+    },
+
+    /// The requested preset doesn't exist in the file with the presets
+    #[error("No preset \"{preset}\" in \"{}\"", path.display())]
+    UnknownPreset {
+        /// The path to the file with the presets
+        path: PathBuf,
+        /// The name of the preset that was requested
+        preset: String,
+    },
+
+    /// A script could not be evaluated
+    ///
+    /// See [`Script`].
+    #[cfg(feature = "script")]
+    #[error("Error evaluating script \"{}\":\n{message}", path.display())]
+    Script {
+        /// The path to the script
+        path: PathBuf,
+        /// Why the script could not be evaluated
+        message: String,
+    },
+
+    /// The thread watching the model for changes has stopped
+    #[error("Watcher has been disconnected")]
+    Disconnected,
+
+    /// A model could not be fetched from a remote source
+    ///
+    /// See [`Model::fetch`].
+    #[error("Error fetching model `{model}`:\n{output}")]
+    Fetch {
+        /// The source that the model was fetched from
+        model: String,
+        /// The output of the tool that failed to fetch the model
+        output: String,
+    },
+
+    /// Compiling or evaluating the model took longer than the timeout
+    ///
+    /// Compiling or evaluating has been aborted. See [`Model::with_timeout`].
+    #[error("Model did not finish within {0:?}")]
+    Timeout(Duration),
+
+    /// The evaluator could not be found
+    ///
+    /// See [`Model::with_evaluator`].
+    #[cfg(feature = "native")]
+    #[error("Model evaluator \"{}\" not found", .0.display())]
+    EvaluatorNotFound(PathBuf),
+
+    /// The evaluator failed to evaluate the model
+    ///
+    /// This happens if the model can't be loaded, or if it crashes. See
+    /// [`Model::with_evaluator`].
+    #[cfg(feature = "native")]
+    #[error("Error evaluating model ({status}):\n{output}")]
+    Evaluator {
+        /// How the evaluator exited
+        status: ExitStatus,
+        /// What the evaluator printed to stderr
+        output: String,
+    },
+
+    /// Evaluating the model exceeded one of its resource limits
+    ///
+    /// See [`Model::with_resource_limits`].
+    #[cfg(feature = "native")]
+    #[error("Evaluating the model exceeded its {resource} limit")]
+    ResourceLimit {
+        /// The resource whose limit was exceeded
+        resource: Resource,
+        /// What the evaluator printed to stderr, before it was stopped
+        output: String,
+    },
+
     /// An error occurred while trying to use evaluate
     /// [`cargo_metadata::MetadataCommand`].
+    #[cfg(feature = "native")]
     #[error("Unable to determine the crate's metadata")]
     CargoMetadata(#[from] cargo_metadata::Error),
 
@@ -374,4 +964,451 @@ pub enum Error {
     },
 }
 
+/// Load a model's dynamic library, and evaluate it for each set of arguments
+///
+/// `state` is what the model saved the last time it was unloaded, if anything.
+/// This runs in the host's process, or in the evaluator's. See
+/// [`Model::with_evaluator`].
+#[cfg(feature = "native")]
+fn evaluate_library(
+    lib_path: &Path,
+    arguments: &[Parameters],
+    mut state: Option<Vec<u8>>,
+) -> Result<Evaluation, Error> {
+    // So, strictly speaking this is all unsound:
+    // - `Library::new` requires us to abide by the arbitrary requirements
+    //   of any library initialization or termination routines.
+    // - `Library::get` requires us to specify the correct type for the
+    //   model function.
+    // - The model function itself is `unsafe`, because it is a function
+    //   from across an FFI interface.
+    //
+    // Typical models won't have initialization or termination routines (I
+    // think), should abide by the `ModelFn` signature, and might not do
+    // anything unsafe. But we have no way to know that the library the user
+    // told us to load actually does (I think).
+    //
+    // I don't know of a way to fix this. We should take this as motivation
+    // to switch to a better technique:
+    // https://github.com/hannobraun/Fornjot/issues/71
+    unsafe {
+        let lib = libloading::Library::new(lib_path)?;
+        let model: libloading::Symbol<ModelFn> = lib.get(b"model")?;
+
+        // The state hooks are optional. If the model doesn't define them,
+        // there's nothing to do.
+        let on_load: Option<libloading::Symbol<OnLoadFn>> =
+            lib.get(b"fj_on_load").ok();
+        let on_unload: Option<libloading::Symbol<OnUnloadFn>> =
+            lib.get(b"fj_on_unload").ok();
+
+        if let (Some(on_load), Some(state)) = (on_load, state.as_ref()) {
+            on_load(&fj::FfiVec::from_vec(state.clone()));
+        }
+
+        let shapes =
+            arguments.iter().map(|arguments| model(arguments)).collect();
+
+        // The parameters are optional too, as older models don't describe
+        // them.
+        let parameters: Option<libloading::Symbol<ParametersFn>> =
+            lib.get(b"fj_parameters").ok();
+        let parameter_metadata = match (parameters, arguments.last()) {
+            (Some(parameters), Some(arguments)) => {
+                Some(parameters(arguments).to_vec())
+            }
+            _ => None,
+        };
+
+        if let Some(on_unload) = on_unload {
+            state = Some(on_unload().to_vec());
+        }
+
+        Ok(Evaluation {
+            shapes,
+            parameter_metadata,
+            state,
+        })
+    }
+}
+
+/// The result of evaluating a model's dynamic library
+///
+/// See [`evaluate_library`]. This is also what the evaluator sends back to the
+/// host.
+#[cfg(feature = "native")]
+#[derive(Debug, Serialize, Deserialize)]
+struct Evaluation {
+    /// The shapes that the model returned, one for each set of arguments
+    shapes: Vec<fj::Shape>,
+
+    /// The parameters of the model, for the last set of arguments
+    ///
+    /// This is `None`, if the model doesn't describe its parameters.
+    parameter_metadata: Option<Vec<fj::ParameterMetadata>>,
+
+    /// The state that the model saved when it was unloaded
+    state: Option<Vec<u8>>,
+}
+
+/// Run a command to completion, and collect its output
+///
+/// If the command doesn't finish within `timeout`, it is killed, and
+/// [`Error::Timeout`] is returned. On Unix, the command runs in its own process
+/// group, and the whole group is killed, including any processes that the
+/// command started.
+#[cfg(feature = "native")]
+fn output_with_timeout(
+    command: Command,
+    timeout: Option<Duration>,
+) -> Result<Output, Error> {
+    output_with_limits(command, timeout, &ResourceLimits::default())
+}
+
+/// Run a command to completion within its resource limits, and collect its
+/// output
+///
+/// Like [`output_with_timeout`], but if the command exceeds one of `limits`,
+/// it is stopped, and [`Error::ResourceLimit`] is returned. The limits must
+/// have been applied to `command` already. See [`ResourceLimits::apply`].
+#[cfg(feature = "native")]
+fn output_with_limits(
+    mut command: Command,
+    timeout: Option<Duration>,
+    limits: &ResourceLimits,
+) -> Result<Output, Error> {
+    if timeout.is_none() && *limits == ResourceLimits::default() {
+        return Ok(command.output()?);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt as _;
+
+        // Safe, as `setpgid` is async-signal-safe, and nothing else happens
+        // between forking and executing the command.
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setpgid(0, 0) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Read the output while waiting, or the command might block, once the
+    // pipes are full.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let output = |handle: thread::JoinHandle<io::Result<Vec<u8>>>| {
+        handle.join().expect("Reading output panicked")
+    };
+
+    let deadline = timeout.map(|timeout| (Instant::now() + timeout, timeout));
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if let Some((deadline, timeout)) = deadline {
+            if Instant::now() >= deadline {
+                kill_process_group(&mut child)?;
+                child.wait()?;
+                return Err(Error::Timeout(timeout));
+            }
+        }
+        if let Some(resource) = limits.exceeded_while_running(child.id())? {
+            kill_process_group(&mut child)?;
+            child.wait()?;
+
+            // The process group is gone, so the pipes have been closed, and
+            // reading the output finishes.
+            let output = output(stderr)?;
+            return Err(Error::ResourceLimit {
+                resource,
+                output: String::from_utf8_lossy(&output).into_owned(),
+            });
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let stdout = output(stdout)?;
+    let stderr = output(stderr)?;
+
+    if let Some(resource) = limits.exceeded(status) {
+        return Err(Error::ResourceLimit {
+            resource,
+            output: String::from_utf8_lossy(&stderr).into_owned(),
+        });
+    }
+
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Kill a child process, and on Unix, all other processes in its group
+#[cfg(feature = "native")]
+fn kill_process_group(child: &mut std::process::Child) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        // The child is the leader of its own process group, so the ID of the
+        // group is the same as its ID. A negative ID addresses the group.
+        let group = -(child.id() as libc::pid_t);
+        if unsafe { libc::kill(group, libc::SIGKILL) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    // Other platforms have no process groups. Only the child is killed.
+    // Processes it started finish on their own.
+    #[cfg(not(unix))]
+    {
+        child.kill()
+    }
+}
+
+#[cfg(feature = "native")]
+fn read_in_background(
+    pipe: Option<impl Read + Send + 'static>,
+) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut output)?;
+        }
+        Ok(output)
+    })
+}
+
+#[cfg(feature = "native")]
 type ModelFn = unsafe extern "C" fn(args: &Parameters) -> fj::Shape;
+#[cfg(feature = "native")]
+type OnLoadFn = unsafe extern "C" fn(state: &fj::FfiVec<u8>);
+#[cfg(feature = "native")]
+type OnUnloadFn = unsafe extern "C" fn() -> fj::FfiVec<u8>;
+#[cfg(feature = "native")]
+type ParametersFn = unsafe extern "C" fn(
+    args: &Parameters,
+) -> fj::FfiVec<fj::ParameterMetadata>;
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use std::{
+        env,
+        ffi::OsStr,
+        path::PathBuf,
+        process::{self, Command},
+        sync::Mutex,
+        thread,
+        time::Duration,
+    };
+
+    use super::{validate_package, Error, Model, Parameters, ResourceLimits};
+
+    #[test]
+    fn build_command() {
+        let model = Model {
+            src_path: PathBuf::from("model/src"),
+            lib_name: "model".into(),
+            target_dir: PathBuf::from("target"),
+            manifest_path: PathBuf::from("model/Cargo.toml"),
+            cargo: "cargo".into(),
+            features: Vec::new(),
+            target: None,
+            offline: false,
+            envs: Vec::new(),
+            timeout: None,
+            evaluator: PathBuf::from("fj-evaluate"),
+            resource_limits: ResourceLimits::default(),
+            state: Mutex::new(None),
+            parameter_metadata: Mutex::new(Vec::new()),
+            timings: Mutex::new(None),
+        }
+        .with_cargo("/opt/cargo")
+        .with_features(["a", "b"])
+        .with_target("x86_64-unknown-linux-musl")
+        .offline(true)
+        .with_env("CARGO_NET_GIT_FETCH_WITH_CLI", "true");
+
+        let command = model.build_command();
+
+        assert_eq!(command.get_program(), "/opt/cargo");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            [
+                "build",
+                "--manifest-path",
+                "model/Cargo.toml",
+                "--features",
+                "a,b",
+                "--target",
+                "x86_64-unknown-linux-musl",
+                "--offline",
+            ]
+        );
+        assert_eq!(
+            command.get_envs().collect::<Vec<_>>(),
+            [(
+                OsStr::new("CARGO_NET_GIT_FETCH_WITH_CLI"),
+                Some(OsStr::new("true"))
+            )]
+        );
+    }
+
+    #[test]
+    fn compile_error() {
+        let model = Model {
+            src_path: PathBuf::from("does-not-exist/src"),
+            lib_name: "model".into(),
+            target_dir: PathBuf::from("target"),
+            manifest_path: PathBuf::from("does-not-exist/Cargo.toml"),
+            cargo: "cargo".into(),
+            features: Vec::new(),
+            target: None,
+            offline: true,
+            envs: Vec::new(),
+            timeout: None,
+            evaluator: PathBuf::from("fj-evaluate"),
+            resource_limits: ResourceLimits::default(),
+            state: Mutex::new(None),
+            parameter_metadata: Mutex::new(Vec::new()),
+            timings: Mutex::new(None),
+        };
+
+        let result = model.load_once(&Parameters::empty());
+        match result {
+            Err(Error::Compile { output }) => {
+                assert!(output.contains("does-not-exist"));
+            }
+            result => panic!("Expected compile error, got {result:?}"),
+        }
+
+        // Only successful loads are timed.
+        assert_eq!(model.timings(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_with_timeout() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo output"]);
+        let output =
+            super::output_with_timeout(command, Some(Duration::from_secs(10)))
+                .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"output\n");
+
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        let timeout = Duration::from_millis(10);
+        let result = super::output_with_timeout(command, Some(timeout));
+        assert!(matches!(result, Err(Error::Timeout(t)) if t == timeout));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_with_timeout_kills_process_group() {
+        let marker = env::temp_dir()
+            .join(format!("fj-host-process-group-{}", process::id()));
+
+        // The command starts another process, like cargo starts the compiler.
+        // That process must be killed too, or it would create the marker.
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(r#"(sleep 0.5; touch "$1") & wait"#)
+            .arg("sh")
+            .arg(&marker);
+        let timeout = Duration::from_millis(100);
+        let result = super::output_with_timeout(command, Some(timeout));
+        assert!(matches!(result, Err(Error::Timeout(_))));
+
+        thread::sleep(Duration::from_secs(1));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn validate_package_valid() {
+        let pkg = package(&["cdylib"], Some(env!("CARGO_PKG_VERSION")));
+        assert!(validate_package(&pkg).is_ok());
+    }
+
+    #[test]
+    fn validate_package_not_cdylib() {
+        let pkg = package(&["lib"], Some(env!("CARGO_PKG_VERSION")));
+        assert_invalid(&pkg, "crate-type");
+    }
+
+    #[test]
+    fn validate_package_no_fj_dependency() {
+        let pkg = package(&["cdylib"], None);
+        assert_invalid(&pkg, "must depend on `fj`");
+    }
+
+    #[test]
+    fn validate_package_incompatible_fj_version() {
+        let pkg = package(&["cdylib"], Some("=0.1.0"));
+        assert_invalid(&pkg, "is not compatible");
+    }
+
+    fn package(
+        crate_types: &[&str],
+        fj_req: Option<&str>,
+    ) -> cargo_metadata::Package {
+        let dependencies = fj_req
+            .map(|req| {
+                serde_json::json!({
+                    "name": "fj",
+                    "source": null,
+                    "req": req,
+                    "kind": null,
+                    "optional": false,
+                    "uses_default_features": true,
+                    "features": [],
+                })
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        serde_json::from_value(serde_json::json!({
+            "name": "model",
+            "version": "0.1.0",
+            "id": "model 0.1.0 (path+file:///model)",
+            "dependencies": dependencies,
+            "targets": [{
+                "name": "model",
+                "kind": crate_types,
+                "crate_types": crate_types,
+                "src_path": "/model/src/lib.rs",
+            }],
+            "features": {},
+            "manifest_path": "/model/Cargo.toml",
+        }))
+        .unwrap()
+    }
+
+    fn assert_invalid(pkg: &cargo_metadata::Package, expected: &str) {
+        match validate_package(pkg) {
+            Err(Error::InvalidModelCrate {
+                manifest_path,
+                reason,
+            }) => {
+                assert_eq!(manifest_path, PathBuf::from("/model/Cargo.toml"));
+                assert!(reason.contains(expected), "Unexpected: {reason}");
+            }
+            result => panic!("Expected invalid model crate, got {result:?}"),
+        }
+    }
+}
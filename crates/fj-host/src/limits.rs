@@ -0,0 +1,238 @@
+use std::{
+    fmt, io,
+    process::{Command, ExitStatus},
+    time::Duration,
+};
+
+/// Limits on the resources that evaluating a model may use
+///
+/// See [`Model::with_resource_limits`].
+///
+/// [`Model::with_resource_limits`]: crate::Model::with_resource_limits
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ResourceLimits {
+    /// The maximum amount of memory that the evaluator may use, in bytes
+    ///
+    /// This is measured as the evaluator's resident set size, which is checked
+    /// every few milliseconds. A model that allocates memory quickly can
+    /// exceed the limit briefly, before it is stopped. Only supported on Linux.
+    pub memory: Option<u64>,
+
+    /// The maximum CPU time that the evaluator may use
+    ///
+    /// Rounded up to whole seconds. Only supported on Unix.
+    pub cpu_time: Option<Duration>,
+}
+
+impl ResourceLimits {
+    /// Apply the limits to a command
+    ///
+    /// Returns an error, if a limit is set, but the platform doesn't support
+    /// limiting it.
+    pub(crate) fn apply(&self, command: &mut Command) -> io::Result<()> {
+        if self.memory.is_some() && !cfg!(target_os = "linux") {
+            return Err(unsupported(
+                "Memory limits are only supported on Linux",
+            ));
+        }
+
+        let cpu_time = match self.cpu_time {
+            Some(cpu_time) => cpu_time,
+            None => return Ok(()),
+        };
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt as _;
+
+            let mut seconds = cpu_time.as_secs();
+            if cpu_time.subsec_nanos() > 0 || seconds == 0 {
+                seconds += 1;
+            }
+
+            // Exceeding the soft limit sends `SIGXCPU`, which terminates the
+            // process, unless it handles the signal. Exceeding the hard limit
+            // kills it, regardless.
+            let limit = libc::rlimit {
+                rlim_cur: seconds as libc::rlim_t,
+                rlim_max: (seconds + 1) as libc::rlim_t,
+            };
+
+            // Safe, as `setrlimit` is async-signal-safe, and nothing else
+            // happens between forking and executing the command.
+            unsafe {
+                command.pre_exec(move || {
+                    if libc::setrlimit(libc::RLIMIT_CPU, &limit) == -1 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(())
+                });
+            }
+
+            Ok(())
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (command, cpu_time);
+            Err(unsupported("CPU time limits are only supported on Unix"))
+        }
+    }
+
+    /// Determine whether the running process has exceeded a limit
+    ///
+    /// This covers the limits that the operating system doesn't enforce by
+    /// itself. The caller needs to stop the process, if one has been exceeded.
+    pub(crate) fn exceeded_while_running(
+        &self,
+        pid: u32,
+    ) -> io::Result<Option<Resource>> {
+        if let Some(memory) = self.memory {
+            if resident_memory(pid)? > memory {
+                return Ok(Some(Resource::Memory));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Determine whether the process has exited, because it exceeded a limit
+    ///
+    /// The operating system stops a process that exceeds its CPU time limit by
+    /// sending it `SIGXCPU`.
+    pub(crate) fn exceeded(&self, status: ExitStatus) -> Option<Resource> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt as _;
+
+            if self.cpu_time.is_some() && status.signal() == Some(libc::SIGXCPU)
+            {
+                return Some(Resource::CpuTime);
+            }
+        }
+
+        let _ = status;
+        None
+    }
+}
+
+/// Determine how much memory a process uses, as its resident set size in bytes
+#[cfg(target_os = "linux")]
+fn resident_memory(pid: u32) -> io::Result<u64> {
+    // The second field is the resident set size, in pages.
+    let statm = std::fs::read_to_string(format!("/proc/{pid}/statm"))?;
+    let pages = statm
+        .split_whitespace()
+        .nth(1)
+        .and_then(|pages| pages.parse::<u64>().ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unexpected format of /proc/{pid}/statm: {statm}"),
+            )
+        })?;
+
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Ok(pages * page_size as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory(_: u32) -> io::Result<u64> {
+    Err(unsupported("Memory limits are only supported on Linux"))
+}
+
+fn unsupported(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, message)
+}
+
+/// A resource that can be limited using [`ResourceLimits`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Resource {
+    /// Memory, as limited by [`ResourceLimits::memory`]
+    Memory,
+
+    /// CPU time, as limited by [`ResourceLimits::cpu_time`]
+    CpuTime,
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Memory => write!(f, "memory"),
+            Self::CpuTime => write!(f, "CPU time"),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{process::Command, time::Duration};
+
+    use crate::{output_with_limits, Error};
+
+    use super::{Resource, ResourceLimits};
+
+    #[test]
+    fn cpu_time() {
+        let limits = ResourceLimits {
+            cpu_time: Some(Duration::from_millis(500)),
+            ..ResourceLimits::default()
+        };
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo started >&2; while :; do :; done"]);
+        limits.apply(&mut command).unwrap();
+
+        let result = output_with_limits(command, None, &limits);
+        assert_resource_limit(result, Resource::CpuTime, "started\n");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn memory() {
+        let limits = ResourceLimits {
+            memory: Some(64 * 1024 * 1024),
+            ..ResourceLimits::default()
+        };
+
+        // Doubles the length of a string, until it is stopped.
+        let mut command = Command::new("awk");
+        command.arg(
+            r#"BEGIN { print "started" > "/dev/stderr"; s = "x"; while (1) s = s s }"#,
+        );
+        limits.apply(&mut command).unwrap();
+
+        let result = output_with_limits(command, None, &limits);
+        assert_resource_limit(result, Resource::Memory, "started\n");
+    }
+
+    #[test]
+    fn within_limits() {
+        let limits = ResourceLimits {
+            memory: cfg!(target_os = "linux").then_some(64 * 1024 * 1024),
+            cpu_time: Some(Duration::from_secs(10)),
+        };
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo output"]);
+        limits.apply(&mut command).unwrap();
+
+        let output = output_with_limits(command, None, &limits).unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"output\n");
+    }
+
+    fn assert_resource_limit(
+        result: Result<std::process::Output, Error>,
+        expected: Resource,
+        expected_output: &str,
+    ) {
+        match result {
+            Err(Error::ResourceLimit { resource, output }) => {
+                assert_eq!(resource, expected);
+                assert_eq!(output, expected_output);
+            }
+            result => panic!("Expected resource limit, got {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::Parameters;
+
+/// A set of parameter combinations to evaluate a model with
+///
+/// Starts out with a set of base parameters, that are the same for all
+/// combinations. Each parameter that is added to the sweep has a number of
+/// values, and the model is evaluated for every combination of those values.
+///
+/// See [`Model::load_for_each`](crate::Model::load_for_each).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSweep {
+    base: Parameters,
+    axes: Vec<(String, Vec<String>)>,
+}
+
+impl ParameterSweep {
+    /// Construct a new instance of `ParameterSweep`
+    pub fn new(base: Parameters) -> Self {
+        Self {
+            base,
+            axes: Vec::new(),
+        }
+    }
+
+    /// Add a parameter that takes each of the provided values
+    pub fn with_values(
+        mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = impl ToString>,
+    ) -> Self {
+        let values = values.into_iter().map(|value| value.to_string());
+        self.axes.push((key.into(), values.collect()));
+        self
+    }
+
+    /// Add a parameter that ranges from `start` to `end`, in steps of `step`
+    ///
+    /// `end` is included, if it is reached by a whole number of steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `step` is not positive.
+    pub fn with_range(
+        self,
+        key: impl Into<String>,
+        start: f64,
+        end: f64,
+        step: f64,
+    ) -> Self {
+        assert!(step > 0., "Step of parameter range must be positive");
+
+        // Allow for some floating point error, so `end` isn't accidentally
+        // left out.
+        let num_steps = ((end - start) / step + 1e-9).floor().max(-1.) as i64;
+        let values = (0..=num_steps).map(|i| start + step * i as f64);
+
+        self.with_values(key, values)
+    }
+
+    /// Access the keys of the parameters that vary between combinations
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.axes.iter().map(|(key, _)| key.as_str())
+    }
+
+    /// Compute all combinations of parameters
+    pub fn parameter_sets(&self) -> Vec<Parameters> {
+        let mut sets = vec![self.base.clone()];
+
+        for (key, values) in &self.axes {
+            sets = sets
+                .into_iter()
+                .flat_map(|set| {
+                    values.iter().map(move |value| {
+                        let mut set = set.clone();
+                        set.insert(key.clone(), value);
+                        set
+                    })
+                })
+                .collect();
+        }
+
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parameters;
+
+    use super::ParameterSweep;
+
+    #[test]
+    fn parameter_sets() {
+        let mut base = Parameters::empty();
+        base.insert("height", 1);
+
+        let sweep = ParameterSweep::new(base)
+            .with_range("thickness", 2., 3., 0.5)
+            .with_values("material", ["steel", "wood"]);
+
+        let sets = sweep.parameter_sets();
+        assert_eq!(sets.len(), 6);
+
+        for set in &sets {
+            assert_eq!(set["height"], "1");
+        }
+        assert_eq!(sets[0]["thickness"], "2");
+        assert_eq!(sets[0]["material"], "steel");
+        assert_eq!(sets[5]["thickness"], "3");
+        assert_eq!(sets[5]["material"], "wood");
+    }
+}
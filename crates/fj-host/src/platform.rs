@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 // Represents platform trait
 pub trait Platform {
     fn model_lib_file_name(&self, name: &str) -> String;
@@ -11,6 +13,8 @@ struct Macos;
 struct Windows;
 // Linux
 struct Unix;
+// WebAssembly
+struct Wasm;
 
 impl Platform for Windows {
     fn model_lib_file_name(&self, name: &str) -> String {
@@ -30,6 +34,12 @@ impl Platform for Unix {
     }
 }
 
+impl Platform for Wasm {
+    fn model_lib_file_name(&self, name: &str) -> String {
+        format!("{}.wasm", name)
+    }
+}
+
 // Represents common apis availiable independent of hosts
 pub struct HostPlatform;
 
@@ -44,7 +54,78 @@ impl HostPlatform {
         }
     }
 
+    pub fn for_target(triple: &str) -> Box<dyn Platform> {
+        if triple.contains("windows") {
+            Box::new(Windows)
+        } else if triple.contains("apple") {
+            Box::new(Macos)
+        } else if triple.starts_with("wasm") {
+            Box::new(Wasm)
+        } else {
+            Box::new(Unix)
+        }
+    }
+
     pub fn lib_file_name(name: &str) -> String {
         Self::get_os().model_lib_file_name(name)
     }
+
+    /// Compute the path of a model library, as compiled by cargo
+    ///
+    /// If a `target` triple is passed, the library is assumed to have been
+    /// compiled for that target, using `--target`.
+    pub fn lib_path(
+        target_dir: &Path,
+        target: Option<&str>,
+        profile: &str,
+        name: &str,
+    ) -> PathBuf {
+        match target {
+            Some(triple) => target_dir
+                .join(triple)
+                .join(profile)
+                .join(Self::for_target(triple).model_lib_file_name(name)),
+            None => target_dir.join(profile).join(Self::lib_file_name(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::HostPlatform;
+
+    #[test]
+    fn lib_path() {
+        let target_dir = Path::new("target");
+
+        assert_eq!(
+            HostPlatform::lib_path(
+                target_dir,
+                Some("x86_64-pc-windows-msvc"),
+                "debug",
+                "model"
+            ),
+            Path::new("target/x86_64-pc-windows-msvc/debug/model.dll"),
+        );
+        assert_eq!(
+            HostPlatform::lib_path(
+                target_dir,
+                Some("x86_64-unknown-linux-musl"),
+                "debug",
+                "model"
+            ),
+            Path::new("target/x86_64-unknown-linux-musl/debug/libmodel.so"),
+        );
+        assert_eq!(
+            HostPlatform::lib_path(
+                target_dir,
+                Some("wasm32-unknown-unknown"),
+                "debug",
+                "model"
+            ),
+            Path::new("target/wasm32-unknown-unknown/debug/model.wasm"),
+        );
+    }
 }
@@ -0,0 +1,223 @@
+#[cfg(feature = "native")]
+use std::path::PathBuf;
+use std::{collections::BTreeMap, ffi::OsStr, fs, path::Path};
+
+#[cfg(feature = "native")]
+use crate::Model;
+use crate::{Error, Parameters};
+
+impl Parameters {
+    /// Load a named set of parameters from a file
+    ///
+    /// The file can be either TOML or JSON (if its extension is `.json`). Its
+    /// top-level tables (or objects) are the presets. Top-level values that
+    /// are not in a table are shared by all presets, unless a preset overrides
+    /// them.
+    ///
+    /// ``` toml
+    /// height = 1.0
+    ///
+    /// [small]
+    /// radius = 2.0
+    ///
+    /// [large]
+    /// radius = 5.0
+    /// height = 2.0
+    /// ```
+    pub fn from_file(path: &Path, preset: &str) -> Result<Self, Error> {
+        let invalid = |reason: String| Error::InvalidPresets {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let source = fs::read_to_string(path)?;
+        let is_json = path.extension() == Some(OsStr::new("json"));
+        let presets = if is_json {
+            parse_json(&source)
+        } else {
+            parse_toml(&source)
+        }
+        .map_err(invalid)?;
+
+        let mut parameters = Parameters::empty();
+        let mut found = false;
+
+        for (key, value) in presets {
+            match value {
+                Entry::Value(value) => {
+                    // Values from the preset override shared values, so only
+                    // insert if there's nothing there yet.
+                    parameters.entry(key).or_insert(value);
+                }
+                Entry::Preset(values) if key == preset => {
+                    parameters.extend(values);
+                    found = true;
+                }
+                Entry::Preset(_) => {}
+            }
+        }
+
+        if !found {
+            return Err(Error::UnknownPreset {
+                path: path.to_path_buf(),
+                preset: preset.into(),
+            });
+        }
+
+        Ok(parameters)
+    }
+}
+
+#[cfg(feature = "native")]
+impl Model {
+    /// Load a named set of parameters from the model's preset file
+    ///
+    /// The preset file is `parameters.toml` or `parameters.json`, located in
+    /// the model's crate directory, next to its `Cargo.toml`. See
+    /// [`Parameters::from_file`] for the format.
+    pub fn parameter_preset(&self, preset: &str) -> Result<Parameters, Error> {
+        Parameters::from_file(&self.presets_path(), preset)
+    }
+
+    fn presets_path(&self) -> PathBuf {
+        let crate_dir = self
+            .manifest_path
+            .parent()
+            .expect("Manifest path should have a parent directory");
+
+        let toml = crate_dir.join("parameters.toml");
+        let json = crate_dir.join("parameters.json");
+
+        if !toml.exists() && json.exists() {
+            return json;
+        }
+
+        toml
+    }
+}
+
+enum Entry {
+    Value(String),
+    Preset(BTreeMap<String, String>),
+}
+
+fn parse_toml(source: &str) -> Result<BTreeMap<String, Entry>, String> {
+    let value: toml::Value = source.parse().map_err(|err| format!("{err}"))?;
+    let table = match value {
+        toml::Value::Table(table) => table,
+        _ => unreachable!("TOML document is always a table"),
+    };
+
+    let to_string = |key: &str, value: toml::Value| match value {
+        toml::Value::String(value) => Ok(value),
+        toml::Value::Integer(value) => Ok(value.to_string()),
+        toml::Value::Float(value) => Ok(value.to_string()),
+        toml::Value::Boolean(value) => Ok(value.to_string()),
+        _ => Err(format!("Value of parameter `{key}` is not supported")),
+    };
+
+    let mut entries = BTreeMap::new();
+    for (key, value) in table {
+        let entry = match value {
+            toml::Value::Table(table) => {
+                let mut values = BTreeMap::new();
+                for (key, value) in table {
+                    let value = to_string(&key, value)?;
+                    values.insert(key, value);
+                }
+                Entry::Preset(values)
+            }
+            value => Entry::Value(to_string(&key, value)?),
+        };
+
+        entries.insert(key, entry);
+    }
+
+    Ok(entries)
+}
+
+fn parse_json(source: &str) -> Result<BTreeMap<String, Entry>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(source).map_err(|err| format!("{err}"))?;
+    let object = match value {
+        serde_json::Value::Object(object) => object,
+        _ => return Err("Expected object at top level".into()),
+    };
+
+    let to_string = |key: &str, value: serde_json::Value| match value {
+        serde_json::Value::String(value) => Ok(value),
+        serde_json::Value::Number(value) => Ok(value.to_string()),
+        serde_json::Value::Bool(value) => Ok(value.to_string()),
+        _ => Err(format!("Value of parameter `{key}` is not supported")),
+    };
+
+    let mut entries = BTreeMap::new();
+    for (key, value) in object {
+        let entry = match value {
+            serde_json::Value::Object(object) => {
+                let mut values = BTreeMap::new();
+                for (key, value) in object {
+                    let value = to_string(&key, value)?;
+                    values.insert(key, value);
+                }
+                Entry::Preset(values)
+            }
+            value => Entry::Value(to_string(&key, value)?),
+        };
+
+        entries.insert(key, entry);
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{Error, Parameters};
+
+    #[test]
+    fn from_file() {
+        let dir =
+            env::temp_dir().join(format!("fj-host-presets-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let toml = dir.join("parameters.toml");
+        fs::write(
+            &toml,
+            "height = 1.0\n\
+            \n\
+            [small]\n\
+            radius = 2\n\
+            \n\
+            [large]\n\
+            radius = 5\n\
+            height = 2.5\n",
+        )
+        .unwrap();
+
+        let small = Parameters::from_file(&toml, "small").unwrap();
+        assert_eq!(small["radius"], "2");
+        assert_eq!(small["height"], "1");
+
+        let large = Parameters::from_file(&toml, "large").unwrap();
+        assert_eq!(large["radius"], "5");
+        assert_eq!(large["height"], "2.5");
+
+        assert!(matches!(
+            Parameters::from_file(&toml, "medium"),
+            Err(Error::UnknownPreset { .. })
+        ));
+
+        let json = dir.join("parameters.json");
+        fs::write(&json, r#"{ "small": { "radius": 2, "label": "S" } }"#)
+            .unwrap();
+
+        let small = Parameters::from_file(&json, "small").unwrap();
+        assert_eq!(small["radius"], "2");
+        assert_eq!(small["label"], "S");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
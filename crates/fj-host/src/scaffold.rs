@@ -0,0 +1,118 @@
+use std::{fs, path::Path};
+
+use crate::{Error, Model};
+
+impl Model {
+    /// Create a new model crate at `path`, then initialize the model from it
+    ///
+    /// The generated crate is named `name`. It depends on the version of `fj`
+    /// that matches this version of `fj-host`, and contains a starter model
+    /// that can be built and loaded right away.
+    ///
+    /// Returns an error, if `name` isn't a valid crate name, or if `path`
+    /// already exists and isn't an empty directory.
+    pub fn create_new(path: &Path, name: &str) -> Result<Self, Error> {
+        write_model_crate(path, name)?;
+        Self::from_path(path.to_path_buf(), None)
+    }
+}
+
+fn write_model_crate(path: &Path, name: &str) -> Result<(), Error> {
+    if !is_valid_crate_name(name) {
+        return Err(Error::InvalidModelName(name.into()));
+    }
+
+    if path.exists() && fs::read_dir(path)?.next().is_some() {
+        return Err(Error::ModelExists(path.to_path_buf()));
+    }
+
+    fs::create_dir_all(path.join("src"))?;
+    fs::write(path.join("Cargo.toml"), cargo_toml(name))?;
+    fs::write(path.join("src").join("lib.rs"), LIB_RS)?;
+    fs::write(path.join(".gitignore"), "/target\n")?;
+
+    Ok(())
+}
+
+/// Check whether `name` is a name that crates.io accepts for a crate
+pub(crate) fn is_valid_crate_name(name: &str) -> bool {
+    name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+}
+
+fn cargo_toml(name: &str) -> String {
+    // `fj` and `fj-host` are always released together, with the same version.
+    let version = env!("CARGO_PKG_VERSION");
+
+    format!(
+        "[package]
+name = \"{name}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[lib]
+crate-type = [\"cdylib\"]
+
+[dependencies]
+fj = \"{version}\"
+"
+    )
+}
+
+const LIB_RS: &str = "#[fj::model]
+pub fn model(
+    #[param(default = 3.0)] x: f64,
+    #[param(default = 2.0)] y: f64,
+    #[param(default = 1.0)] z: f64,
+) -> fj::Shape {
+    #[rustfmt::skip]
+    let rectangle = fj::Sketch::from_points(vec![
+        [-x / 2., -y / 2.],
+        [ x / 2., -y / 2.],
+        [ x / 2.,  y / 2.],
+        [-x / 2.,  y / 2.],
+    ]);
+
+    let cuboid = fj::Sweep::from_path(rectangle.into(), [0., 0., z]);
+
+    cuboid.into()
+}
+";
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::Error;
+
+    use super::write_model_crate;
+
+    #[test]
+    fn scaffold() {
+        let path = env::temp_dir()
+            .join(format!("fj-host-scaffold-{}", process::id()))
+            .join("my-model");
+
+        assert!(matches!(
+            write_model_crate(&path, "1-model"),
+            Err(Error::InvalidModelName(_))
+        ));
+
+        write_model_crate(&path, "my-model").unwrap();
+
+        let cargo_toml = fs::read_to_string(path.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"my-model\""));
+        assert!(cargo_toml.contains("crate-type = [\"cdylib\"]"));
+        assert!(path.join("src/lib.rs").exists());
+
+        assert!(matches!(
+            write_model_crate(&path, "my-model"),
+            Err(Error::ModelExists(_))
+        ));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
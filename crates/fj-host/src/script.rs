@@ -0,0 +1,458 @@
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use fj::syntax::{
+    Boolean as _, Difference as _, Group as _, Offset as _, Sweep as _,
+    Transform as _,
+};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, FLOAT, INT};
+
+use crate::{watch_and_load, Error, HostedModel, Parameters, Timings, Watcher};
+
+/// A model that is defined by a script, instead of a crate
+///
+/// Scripts are written in [Rhai], and are evaluated without compiling
+/// anything, which makes them convenient for quick experiments. The value of
+/// the last expression in the script is the shape of the model. The operations
+/// are functions, named after the methods of [`fj::syntax`], and take 3D
+/// shapes, unless noted otherwise:
+///
+/// - `cuboid([x, y, z])`, `cylinder(radius, height)`, `sphere(radius)`,
+///   `cone(radius, height)`, and `torus(major_radius, minor_radius)` create 3D
+///   primitives.
+/// - `circle(radius)` and `polygon([[x, y], ...])` create 2D shapes.
+/// - `linear_extrude(shape, height)` and `sweep(shape, [x, y, z])` turn a 2D
+///   shape into a 3D shape.
+/// - `union(a, b)`, `intersection(a, b)`, `difference(a, b)`, and
+///   `group(a, b)` combine two shapes. `difference` also works for 2D shapes.
+/// - `translate(shape, [x, y, z])`, `rotate(shape, [x, y, z], degrees)`, and
+///   `offset(shape, distance)` transform a shape.
+/// - `param(name, default)` returns the value of a parameter of the model, or
+///   the default, if it hasn't been set. The type of the default determines
+///   the type of the parameter.
+///
+/// Like in any Rhai script, functions can be called as methods of their first
+/// argument. This is a plate with a hole:
+///
+/// ``` rhai
+/// let thickness = param("thickness", 0.5);
+///
+/// let hole = circle(1.0).linear_extrude(thickness);
+/// cuboid([4.0, 4.0, thickness]).difference(hole.translate([2.0, 2.0, 0.0]))
+/// ```
+///
+/// [Rhai]: https://rhai.rs/
+pub struct Script {
+    path: PathBuf,
+    timeout: Option<Duration>,
+    parameter_metadata: Mutex<Vec<fj::ParameterMetadata>>,
+    timings: Mutex<Option<Timings>>,
+}
+
+impl Script {
+    /// Initialize the model using the path to its script
+    pub fn from_path(path: PathBuf) -> Result<Self, Error> {
+        let path = path.canonicalize()?;
+
+        Ok(Self {
+            path,
+            timeout: None,
+            parameter_metadata: Mutex::new(Vec::new()),
+            timings: Mutex::new(None),
+        })
+    }
+
+    /// Abort evaluating the script, if it takes longer than `timeout`
+    ///
+    /// [`Error::Timeout`] is returned in that case. By default, there is no
+    /// timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Evaluate the script once, with the provided parameters
+    pub fn load_once(
+        &self,
+        parameters: &Parameters,
+    ) -> Result<fj::Shape, Error> {
+        let start = Instant::now();
+
+        let source = fs::read_to_string(&self.path)?;
+        let metadata = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = engine(parameters.evaluate()?, metadata.clone());
+
+        if let Some(timeout) = self.timeout {
+            engine.on_progress(move |operations| {
+                // Checking the time after every operation would slow the
+                // script down considerably.
+                let expired =
+                    operations % 1024 == 0 && start.elapsed() > timeout;
+                expired.then_some(Dynamic::UNIT)
+            });
+        }
+
+        let shape = engine
+            .eval_with_scope::<Dynamic>(&mut Scope::new(), &source)
+            .map_err(|err| match (*err, self.timeout) {
+                (EvalAltResult::ErrorTerminated(..), Some(timeout)) => {
+                    Error::Timeout(timeout)
+                }
+                (err, _) => script_error(&self.path, &err),
+            })?;
+        let shape = if shape.is::<fj::Shape2d>() {
+            fj::Shape::Shape2d(shape.cast())
+        } else {
+            shape.try_cast().ok_or_else(|| Error::Script {
+                path: self.path.clone(),
+                message: "Script doesn't evaluate to a shape".into(),
+            })?
+        };
+
+        *self
+            .parameter_metadata
+            .lock()
+            .expect("Parameter metadata mutex poisoned") = metadata.take();
+        *self.timings.lock().expect("Timings mutex poisoned") = Some(Timings {
+            compile: None,
+            evaluate: start.elapsed(),
+        });
+
+        Ok(shape)
+    }
+
+    /// Describe the parameters of the model
+    ///
+    /// These are the parameters that the script asked for, the most recent
+    /// time it was evaluated.
+    pub fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        self.parameter_metadata
+            .lock()
+            .expect("Parameter metadata mutex poisoned")
+            .clone()
+    }
+
+    /// Load the script, then watch it for changes
+    ///
+    /// Whenever a change is detected, the script is evaluated again.
+    pub fn load_and_watch(
+        self,
+        parameters: Parameters,
+    ) -> Result<Watcher, Error> {
+        let path = self.path.clone();
+        watch_and_load(
+            self,
+            &path,
+            notify::RecursiveMode::NonRecursive,
+            parameters,
+        )
+    }
+}
+
+impl HostedModel for Script {
+    fn load(&self, parameters: &Parameters) -> Result<fj::Shape, Error> {
+        self.load_once(parameters)
+    }
+
+    fn parameter_metadata(&self) -> Vec<fj::ParameterMetadata> {
+        Script::parameter_metadata(self)
+    }
+
+    fn timings(&self) -> Option<Timings> {
+        *self.timings.lock().expect("Timings mutex poisoned")
+    }
+}
+
+type EvalResult<T> = Result<T, Box<EvalAltResult>>;
+
+fn engine(
+    parameters: Parameters,
+    metadata: Rc<RefCell<Vec<fj::ParameterMetadata>>>,
+) -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<fj::Shape>("Shape")
+        .register_type_with_name::<fj::Shape2d>("Shape2d");
+
+    engine
+        .register_fn("cuboid", |size: Array| -> EvalResult<fj::Shape> {
+            Ok(fj::Primitive::cuboid(vector(size)?).into())
+        })
+        .register_fn(
+            "cylinder",
+            |radius: Dynamic, height: Dynamic| -> EvalResult<fj::Shape> {
+                let (radius, height) = (number(radius)?, number(height)?);
+                Ok(fj::Primitive::cylinder(radius, height).into())
+            },
+        )
+        .register_fn("sphere", |radius: Dynamic| -> EvalResult<fj::Shape> {
+            Ok(fj::Primitive::sphere(number(radius)?).into())
+        })
+        .register_fn(
+            "cone",
+            |radius: Dynamic, height: Dynamic| -> EvalResult<fj::Shape> {
+                let (radius, height) = (number(radius)?, number(height)?);
+                Ok(fj::Primitive::cone(radius, height).into())
+            },
+        )
+        .register_fn(
+            "torus",
+            |major_radius: Dynamic,
+             minor_radius: Dynamic|
+             -> EvalResult<fj::Shape> {
+                let (major_radius, minor_radius) =
+                    (number(major_radius)?, number(minor_radius)?);
+                Ok(fj::Primitive::torus(major_radius, minor_radius).into())
+            },
+        );
+
+    engine
+        .register_fn("circle", |radius: Dynamic| -> EvalResult<fj::Shape2d> {
+            let circle = fj::Circle::from_radius(number(radius)?);
+            Ok(fj::Sketch::from_circle(circle).into())
+        })
+        .register_fn("polygon", |points: Array| -> EvalResult<fj::Shape2d> {
+            let points = points
+                .into_iter()
+                .map(|point| vector(point.into_array()?))
+                .collect::<EvalResult<_>>()?;
+            Ok(fj::Sketch::from_points(points).into())
+        })
+        .register_fn("difference", |a: fj::Shape2d, b: fj::Shape2d| {
+            fj::Shape2d::from(a.difference(&b))
+        })
+        .register_fn(
+            "linear_extrude",
+            |shape: fj::Shape2d, height: Dynamic| -> EvalResult<fj::Shape> {
+                Ok(shape.sweep([0., 0., number(height)?]).into())
+            },
+        )
+        .register_fn(
+            "sweep",
+            |shape: fj::Shape2d, path: Array| -> EvalResult<fj::Shape> {
+                Ok(shape.sweep(vector(path)?).into())
+            },
+        );
+
+    engine
+        .register_fn("union", |a: fj::Shape, b: fj::Shape| {
+            fj::Shape::from(a.union(&b))
+        })
+        .register_fn("intersection", |a: fj::Shape, b: fj::Shape| {
+            fj::Shape::from(a.intersection(&b))
+        })
+        .register_fn("difference", |a: fj::Shape, b: fj::Shape| {
+            fj::Shape::from(a.subtract(&b))
+        })
+        .register_fn("group", |a: fj::Shape, b: fj::Shape| {
+            fj::Shape::from(a.group(&b))
+        })
+        .register_fn(
+            "translate",
+            |shape: fj::Shape, offset: Array| -> EvalResult<fj::Shape> {
+                Ok(shape.translate(vector(offset)?).into())
+            },
+        )
+        .register_fn(
+            "rotate",
+            |shape: fj::Shape,
+             axis: Array,
+             degrees: Dynamic|
+             -> EvalResult<fj::Shape> {
+                let angle = fj::Angle::from_deg(number(degrees)?);
+                Ok(shape.rotate(vector(axis)?, angle).into())
+            },
+        )
+        .register_fn(
+            "offset",
+            |shape: fj::Shape, distance: Dynamic| -> EvalResult<fj::Shape> {
+                Ok(shape.offset(number(distance)?).into())
+            },
+        );
+
+    engine.register_fn(
+        "param",
+        move |name: &str, default: Dynamic| -> EvalResult<Dynamic> {
+            let (value, ty) = parameter(parameters.get(name), &default)
+                .map_err(|reason| format!("Parameter `{name}`: {reason}"))?;
+
+            let mut metadata = metadata.borrow_mut();
+            if metadata.iter().all(|parameter| parameter.name() != name) {
+                metadata.push(
+                    fj::ParameterMetadata::new(
+                        name.to_owned(),
+                        ty.to_owned(),
+                        value.to_string(),
+                    )
+                    .with_default(default.to_string()),
+                );
+            }
+
+            Ok(value)
+        },
+    );
+
+    engine
+}
+
+/// Determine the value and type of a parameter, from its default
+fn parameter(
+    value: Option<&String>,
+    default: &Dynamic,
+) -> Result<(Dynamic, &'static str), String> {
+    let value = match value {
+        Some(value) => value,
+        None if default.is::<INT>() => return Ok((default.clone(), "i64")),
+        None if default.is::<FLOAT>() => return Ok((default.clone(), "f64")),
+        None if default.is::<bool>() => return Ok((default.clone(), "bool")),
+        None if default.is_string() => return Ok((default.clone(), "String")),
+        None => {
+            return Err(
+                "Default must be a number, a boolean, or a string".into()
+            )
+        }
+    };
+
+    let invalid = |ty| format!("Expected {ty}, got `{value}`");
+
+    if default.is::<INT>() {
+        let value = value.parse::<INT>().map_err(|_| invalid("integer"))?;
+        Ok((value.into(), "i64"))
+    } else if default.is::<FLOAT>() {
+        let value = value.parse::<FLOAT>().map_err(|_| invalid("number"))?;
+        Ok((value.into(), "f64"))
+    } else if default.is::<bool>() {
+        let value = value.parse::<bool>().map_err(|_| invalid("boolean"))?;
+        Ok((value.into(), "bool"))
+    } else if default.is_string() {
+        Ok((value.clone().into(), "String"))
+    } else {
+        Err("Default must be a number, a boolean, or a string".into())
+    }
+}
+
+/// Accept integers where numbers are expected, so scripts can use `1` for `1.0`
+fn number(value: Dynamic) -> EvalResult<f64> {
+    if let Ok(value) = value.as_float() {
+        return Ok(value);
+    }
+    if let Ok(value) = value.as_int() {
+        return Ok(value as f64);
+    }
+
+    Err(format!("Expected number, got {}", value.type_name()).into())
+}
+
+fn vector<const N: usize>(values: Array) -> EvalResult<[f64; N]> {
+    if values.len() != N {
+        return Err(
+            format!("Expected {N} coordinates, got {}", values.len()).into()
+        );
+    }
+
+    let mut vector = [0.; N];
+    for (coordinate, value) in vector.iter_mut().zip(values) {
+        *coordinate = number(value)?;
+    }
+
+    Ok(vector)
+}
+
+fn script_error(path: &Path, err: &EvalAltResult) -> Error {
+    Error::Script {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process, time::Duration};
+
+    use fj::syntax::{
+        Boolean as _, Difference as _, Sweep as _, Transform as _,
+    };
+
+    use crate::{Error, Parameters};
+
+    use super::Script;
+
+    #[test]
+    fn load() {
+        let dir =
+            env::temp_dir().join(format!("fj-host-script-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("plate.rhai");
+        fs::write(
+            &path,
+            "let thickness = param(\"thickness\", 0.5);\n\
+            let hole = circle(1).linear_extrude(thickness);\n\
+            cuboid([4, 4, thickness])\
+                .difference(hole.translate([2.0, 2.0, 0.0]))\n",
+        )
+        .unwrap();
+        let script = Script::from_path(path.clone()).unwrap();
+
+        let plate = |thickness: f64| -> fj::Shape {
+            let hole = fj::Sketch::from_circle(fj::Circle::from_radius(1.))
+                .sweep([0., 0., thickness]);
+            fj::Primitive::cuboid([4., 4., thickness])
+                .subtract(&hole.translate([2., 2., 0.]))
+                .into()
+        };
+
+        let shape = script.load_once(&Parameters::empty()).unwrap();
+        assert_eq!(shape, plate(0.5));
+
+        let mut parameters = Parameters::empty();
+        parameters.insert("thickness", 2);
+        let shape = script.load_once(&parameters).unwrap();
+        assert_eq!(shape, plate(2.));
+
+        let metadata = script.parameter_metadata();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata[0].name(), "thickness");
+        assert_eq!(metadata[0].ty(), "f64");
+        assert_eq!(metadata[0].value(), "2.0");
+        assert_eq!(metadata[0].default(), Some("0.5"));
+
+        // 2D shapes are valid models too, but other values aren't.
+        fs::write(&path, "circle(1.0).difference(circle(0.5))").unwrap();
+        let shape = script.load_once(&Parameters::empty()).unwrap();
+        let circle =
+            |radius| fj::Sketch::from_circle(fj::Circle::from_radius(radius));
+        assert_eq!(shape, circle(1.).difference(&circle(0.5)).into());
+
+        fs::write(&path, "1 + 2").unwrap();
+        let result = script.load_once(&Parameters::empty());
+        assert!(matches!(result, Err(Error::Script { .. })));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn timeout() {
+        let dir = env::temp_dir()
+            .join(format!("fj-host-script-timeout-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("loop.rhai");
+        fs::write(&path, "loop {}").unwrap();
+
+        let timeout = Duration::from_millis(100);
+        let script = Script::from_path(path).unwrap().with_timeout(timeout);
+
+        let result = script.load_once(&Parameters::empty());
+        assert!(matches!(result, Err(Error::Timeout(t)) if t == timeout));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    pin::Pin,
+    sync::mpsc,
+    task::{Context, Poll},
+    thread,
+};
+
+use futures::{channel::mpsc::UnboundedReceiver, Stream};
+
+use crate::{watch, Error, Model, Parameters, WatcherEvent};
+
+impl Model {
+    /// Load the model, then watch it for changes, asynchronously
+    ///
+    /// Like [`Model::load_and_watch`], but returns a [`WatcherStream`], which
+    /// yields an item for every time the model has been (re-)loaded, or has
+    /// failed to.
+    ///
+    /// Loading the model happens on a separate thread, so polling the stream
+    /// never blocks.
+    pub fn load_and_watch_async(
+        self,
+        parameters: Parameters,
+    ) -> Result<WatcherStream, Error> {
+        let (tx, rx) = mpsc::sync_channel(0);
+        let watcher =
+            watch(&self.src_path, notify::RecursiveMode::Recursive, tx)?;
+
+        let (events_tx, events_rx) = futures::channel::mpsc::unbounded();
+
+        thread::spawn(move || {
+            // We've already started watching, so we can do the initial load
+            // here without missing any changes.
+            let mut trigger = Ok(());
+
+            loop {
+                let event = trigger
+                    .map_err(Error::Notify)
+                    .and_then(|()| self.load_once(&parameters))
+                    .map(WatcherEvent::Shape);
+
+                // The receiver only disconnects, if the stream has been
+                // dropped. Nobody's interested in any more events then.
+                if events_tx.unbounded_send(event).is_err() {
+                    break;
+                }
+
+                trigger = match rx.recv() {
+                    Ok(trigger) => trigger,
+                    Err(mpsc::RecvError) => {
+                        // The file watcher is owned by the stream. If it has
+                        // disconnected, the stream has been dropped.
+                        break;
+                    }
+                };
+            }
+        });
+
+        Ok(WatcherStream {
+            _watcher: Box::new(watcher),
+            events: events_rx,
+        })
+    }
+}
+
+/// Watches a model for changes, yielding an item whenever it is reloaded
+///
+/// Returned by [`Model::load_and_watch_async`].
+pub struct WatcherStream {
+    _watcher: Box<dyn notify::Watcher + Send>,
+    events: UnboundedReceiver<Result<WatcherEvent, Error>>,
+}
+
+impl Stream for WatcherStream {
+    type Item = Result<WatcherEvent, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
@@ -4,13 +4,22 @@
 //! ecosystem. The types in here aren't very useful in themselves, but they
 //! define an interface that other crates use to communicate between each other.
 
-use fj_math::{Point, Segment};
+use std::{collections::BTreeSet, fmt};
+
+use fj_math::{Point, Vector};
+
+use crate::mesh::Color;
 
 /// Debug info from the CAD kernel that can be visualized
-#[derive(Default)]
+///
+/// Algorithms add geometry to this collector as they run, using the various
+/// `push_` methods. Each piece of geometry belongs to a [`DebugCategory`], so
+/// consumers (like the viewer) can show or hide the geometry of each category
+/// separately.
+#[derive(Clone, Debug, Default)]
 pub struct DebugInfo {
-    /// Rays being used during face triangulation
-    pub triangle_edge_checks: Vec<TriangleEdgeCheck>,
+    /// The debug geometry that has been collected
+    pub geometry: Vec<DebugGeometry>,
 }
 
 impl DebugInfo {
@@ -19,31 +28,237 @@ impl DebugInfo {
         Self::default()
     }
 
+    /// Add a point
+    pub fn push_point(
+        &mut self,
+        category: DebugCategory,
+        point: Point<3>,
+        color: Color,
+    ) {
+        self.push(category, color, DebugGeometryKind::Point(point));
+    }
+
+    /// Add a ray that starts at `origin` and goes into `direction`
+    pub fn push_ray(
+        &mut self,
+        category: DebugCategory,
+        origin: Point<3>,
+        direction: Vector<3>,
+        color: Color,
+    ) {
+        self.push(
+            category,
+            color,
+            DebugGeometryKind::Ray { origin, direction },
+        );
+    }
+
+    /// Add a curve, approximated by a polyline through the provided points
+    pub fn push_curve(
+        &mut self,
+        category: DebugCategory,
+        points: impl IntoIterator<Item = Point<3>>,
+        color: Color,
+    ) {
+        let points = points.into_iter().collect();
+        self.push(category, color, DebugGeometryKind::Curve(points));
+    }
+
+    /// Add a text label at `position`
+    pub fn push_label(
+        &mut self,
+        category: DebugCategory,
+        position: Point<3>,
+        text: impl Into<String>,
+        color: Color,
+    ) {
+        let text = text.into();
+        self.push(category, color, DebugGeometryKind::Label { position, text });
+    }
+
+    /// Add all debug geometry from another instance
+    ///
+    /// This is useful for combining the debug info of operations that ran in
+    /// parallel, each with their own instance.
+    pub fn merge(&mut self, other: DebugInfo) {
+        self.geometry.extend(other.geometry);
+    }
+
+    /// Iterate over the debug geometry of the given category
+    pub fn geometry_in(
+        &self,
+        category: DebugCategory,
+    ) -> impl Iterator<Item = &DebugGeometry> + '_ {
+        self.geometry
+            .iter()
+            .filter(move |geometry| geometry.category == category)
+    }
+
+    /// Access the categories that debug geometry has been collected for
+    pub fn categories(&self) -> BTreeSet<DebugCategory> {
+        self.geometry
+            .iter()
+            .map(|geometry| geometry.category)
+            .collect()
+    }
+
     /// Clear all information within this instance
     ///
     /// The resulting instance is the same, as if created by [`DebugInfo::new`],
     /// but calling `clear` might be more efficient in regard to heap
     /// allocations.
     pub fn clear(&mut self) {
-        self.triangle_edge_checks.clear();
+        self.geometry.clear();
+    }
+
+    fn push(
+        &mut self,
+        category: DebugCategory,
+        color: Color,
+        kind: DebugGeometryKind,
+    ) {
+        self.geometry.push(DebugGeometry {
+            category,
+            color,
+            kind,
+        });
     }
 }
 
-/// Record of a check to determine if a triangle edge is within a face
-pub struct TriangleEdgeCheck {
-    /// The origin of the ray used to perform the check
-    pub origin: Point<3>,
+/// A piece of debug geometry
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugGeometry {
+    /// The category that the geometry belongs to
+    pub category: DebugCategory,
+
+    /// The color that the geometry should be displayed with
+    pub color: Color,
 
-    /// The points where the ray hit edges of the face
-    pub hits: Vec<Segment<3>>,
+    /// The kind of geometry
+    pub kind: DebugGeometryKind,
 }
 
-impl TriangleEdgeCheck {
-    /// Construct a new instance
-    pub fn new(origin: Point<3>) -> Self {
-        Self {
-            origin,
-            hits: Vec::new(),
-        }
+/// The kind of a piece of [`DebugGeometry`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugGeometryKind {
+    /// A single point
+    Point(Point<3>),
+
+    /// A ray
+    ///
+    /// Consumers are free to decide how long to display the ray. The length of
+    /// `direction` is a good default.
+    Ray {
+        /// The point where the ray starts
+        origin: Point<3>,
+
+        /// The direction of the ray
+        direction: Vector<3>,
+    },
+
+    /// A curve, approximated by a polyline
+    Curve(Vec<Point<3>>),
+
+    /// A text label
+    Label {
+        /// The position of the label
+        position: Point<3>,
+
+        /// The text of the label
+        text: String,
+    },
+}
+
+/// The category of a piece of [`DebugGeometry`]
+///
+/// Categories group debug geometry by the algorithm that produced it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum DebugCategory {
+    /// Approximations of face boundaries, as used for triangulation
+    FaceApproximation,
+
+    /// Rays used to check whether triangle edges are within a face
+    TriangleEdgeCheck,
+
+    /// Segments where a plane cuts a solid
+    Section,
+
+    /// Segments where the surfaces of two solids intersect, as computed by
+    /// boolean operations
+    Intersection,
+}
+
+impl DebugCategory {
+    /// All debug categories
+    pub const ALL: [Self; 4] = [
+        Self::FaceApproximation,
+        Self::TriangleEdgeCheck,
+        Self::Section,
+        Self::Intersection,
+    ];
+}
+
+impl fmt::Display for DebugCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::FaceApproximation => "Face approximation",
+            Self::TriangleEdgeCheck => "Triangle edge checks",
+            Self::Section => "Section",
+            Self::Intersection => "Intersection curves",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fj_math::{Point, Vector};
+
+    use super::{DebugCategory, DebugInfo};
+
+    #[test]
+    fn geometry_in() {
+        let mut debug_info = DebugInfo::new();
+
+        let color = [255, 0, 0, 255];
+        debug_info.push_point(DebugCategory::Section, Point::origin(), color);
+        debug_info.push_ray(
+            DebugCategory::TriangleEdgeCheck,
+            Point::origin(),
+            Vector::unit_x(),
+            color,
+        );
+        debug_info.push_label(
+            DebugCategory::TriangleEdgeCheck,
+            Point::origin(),
+            "label",
+            color,
+        );
+
+        assert_eq!(
+            debug_info.categories(),
+            BTreeSet::from([
+                DebugCategory::TriangleEdgeCheck,
+                DebugCategory::Section
+            ])
+        );
+        assert_eq!(
+            debug_info
+                .geometry_in(DebugCategory::TriangleEdgeCheck)
+                .count(),
+            2
+        );
+        assert_eq!(
+            debug_info
+                .geometry_in(DebugCategory::FaceApproximation)
+                .count(),
+            0
+        );
+
+        debug_info.clear();
+        assert!(debug_info.categories().is_empty());
     }
 }
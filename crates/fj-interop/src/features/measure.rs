@@ -0,0 +1,334 @@
+use fj_math::{Point, Scalar, Segment, Triangle, Vector};
+
+use super::{
+    ray::closest_point, CurveGeometry, Feature, Features, SurfaceGeometry,
+};
+
+/// A measurement between two features
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Measurement {
+    /// The minimum distance between the features
+    ///
+    /// This is computed from the approximations of the features, and is
+    /// accurate within the tolerance that was used to create those. Since
+    /// vertices, straight edges, and planar faces are approximated exactly,
+    /// the distance between those is exact.
+    pub distance: Scalar,
+
+    /// The points on both features that are closest to each other
+    ///
+    /// The point on the first feature comes first, the point on the second
+    /// feature second.
+    pub points: [Point<3>; 2],
+
+    /// The angle between the features, in radians
+    ///
+    /// Computed from the exact geometry of the features. Only available, if
+    /// both features have a direction, meaning they are straight edges,
+    /// circular edges (using the plane of the circle), planar faces, or
+    /// cylindrical faces (using the axis of the cylinder).
+    pub angle: Option<Scalar>,
+}
+
+impl Features {
+    /// Measure the distance and angle between two features
+    ///
+    /// Returns `None`, if one of the features is a face without triangles.
+    pub fn measure(&self, a: Feature, b: Feature) -> Option<Measurement> {
+        let primitives_a = self.primitives(a);
+        let primitives_b = self.primitives(b);
+
+        let mut points: Option<[Point<3>; 2]> = None;
+        for a in &primitives_a {
+            for b in &primitives_b {
+                let candidate = a.closest_points(b);
+
+                if let Some([p, q]) = points {
+                    if (q - p).magnitude()
+                        <= (candidate[1] - candidate[0]).magnitude()
+                    {
+                        continue;
+                    }
+                }
+
+                points = Some(candidate);
+            }
+        }
+        let points = points?;
+
+        let angle = match (self.direction(a), self.direction(b)) {
+            (Some(a), Some(b)) => Some(a.angle_to(&b)),
+            _ => None,
+        };
+
+        Some(Measurement {
+            distance: (points[1] - points[0]).magnitude(),
+            points,
+            angle,
+        })
+    }
+
+    fn primitives(&self, feature: Feature) -> Vec<Primitive> {
+        match feature {
+            Feature::Vertex(i) => vec![Primitive::Point(self.vertices[i])],
+            Feature::Edge(i) => self.edges[i]
+                .approx
+                .segments()
+                .into_iter()
+                .map(Primitive::Segment)
+                .collect(),
+            Feature::Face(i) => self.faces[i]
+                .triangles
+                .iter()
+                .copied()
+                .map(Primitive::Triangle)
+                .collect(),
+        }
+    }
+
+    fn direction(&self, feature: Feature) -> Option<Direction> {
+        match feature {
+            Feature::Vertex(_) => None,
+            Feature::Edge(i) => match self.edges[i].curve? {
+                CurveGeometry::Circle(circle) => {
+                    Some(Direction::Normal(circle.a.cross(&circle.b)))
+                }
+                CurveGeometry::Line(line) => {
+                    Some(Direction::Along(line.direction))
+                }
+            },
+            Feature::Face(i) => match self.faces[i].surface? {
+                SurfaceGeometry::Cylinder { axis, .. } => {
+                    Some(Direction::Along(axis.direction))
+                }
+                SurfaceGeometry::Plane { normal, .. } => {
+                    Some(Direction::Normal(normal))
+                }
+            },
+        }
+    }
+}
+
+/// A part of the approximation of a feature
+enum Primitive {
+    Point(Point<3>),
+    Segment(Segment<3>),
+    Triangle(Triangle<3>),
+}
+
+impl Primitive {
+    /// Compute the closest points between this primitive and another
+    fn closest_points(&self, other: &Self) -> [Point<3>; 2] {
+        match (self, other) {
+            (Self::Point(a), Self::Point(b)) => [*a, *b],
+            (Self::Point(a), Self::Segment(b)) => [*a, closest_point(*b, *a)],
+            (Self::Point(a), Self::Triangle(b)) => [*a, b.closest_point(*a)],
+            (Self::Segment(a), Self::Segment(b)) => a.closest_points(b),
+            (Self::Segment(a), Self::Triangle(b)) => segment_triangle(a, b),
+            (Self::Triangle(a), Self::Triangle(b)) => a.closest_points(b),
+            (a, b) => {
+                let [q, p] = b.closest_points(a);
+                [p, q]
+            }
+        }
+    }
+}
+
+/// Compute the closest points between a segment and a triangle
+fn segment_triangle(
+    segment: &Segment<3>,
+    triangle: &Triangle<3>,
+) -> [Point<3>; 2] {
+    let [start, end] = segment.points();
+
+    // If the segment pierces the triangle, that's where they are closest.
+    if let Some(t) = triangle.cast_local_ray(start, end - start, 1., true) {
+        let point = start + (end - start) * t;
+        return [point, point];
+    }
+
+    // Otherwise, one of the end points of the segment is closest to the
+    // triangle, or the segment is closest to one of the triangle's edges.
+    let mut candidates = vec![
+        [start, triangle.closest_point(start)],
+        [end, triangle.closest_point(end)],
+    ];
+    let [a, b, c] = triangle.points();
+    for edge in [[a, b], [b, c], [c, a]] {
+        candidates.push(segment.closest_points(&Segment::from_points(edge)));
+    }
+
+    candidates
+        .into_iter()
+        .min_by_key(|&[a, b]| (b - a).magnitude())
+        .expect("Candidates are not empty")
+}
+
+/// The direction of a feature, used to compute angles between features
+enum Direction {
+    /// The feature extends along this direction
+    Along(Vector<3>),
+
+    /// The feature is perpendicular to this direction
+    Normal(Vector<3>),
+}
+
+impl Direction {
+    /// Compute the angle between two directions
+    ///
+    /// The result is between zero and a right angle, as the angle between
+    /// lines or planes doesn't depend on their orientation.
+    fn angle_to(&self, other: &Self) -> Scalar {
+        let (a, b, complement) = match (self, other) {
+            (Self::Along(a), Self::Along(b))
+            | (Self::Normal(a), Self::Normal(b)) => (a, b, false),
+            (Self::Along(a), Self::Normal(b))
+            | (Self::Normal(a), Self::Along(b)) => (a, b, true),
+        };
+
+        let cos = a.normalize().dot(&b.normalize()).abs();
+        let angle = if cos > Scalar::ONE { Scalar::ONE } else { cos }.acos();
+
+        if complement {
+            Scalar::PI / 2. - angle
+        } else {
+            angle
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Line, Point, PolyChain, Scalar, Triangle, Vector};
+
+    use crate::features::{
+        CurveGeometry, EdgeFeature, FaceFeature, Feature, Features,
+        SurfaceGeometry,
+    };
+
+    #[test]
+    fn measure_vertices() {
+        let features = Features {
+            vertices: vec![
+                Point::from([0., 0., 0.]),
+                Point::from([3., 4., 0.]),
+            ],
+            ..Features::default()
+        };
+
+        let measurement = features
+            .measure(Feature::Vertex(0), Feature::Vertex(1))
+            .unwrap();
+        assert_eq!(measurement.distance, Scalar::from(5.));
+        assert_eq!(measurement.angle, None);
+    }
+
+    #[test]
+    fn measure_faces() {
+        // Two faces of a unit cube: the bottom one, and the one at x = 1.
+        let bottom = FaceFeature {
+            surface: Some(SurfaceGeometry::Plane {
+                origin: Point::origin(),
+                normal: Vector::from([0., 0., -1.]),
+            }),
+            triangles: vec![
+                Triangle::from_points([
+                    [0., 0., 0.],
+                    [1., 0., 0.],
+                    [1., 1., 0.],
+                ]),
+                Triangle::from_points([
+                    [0., 0., 0.],
+                    [1., 1., 0.],
+                    [0., 1., 0.],
+                ]),
+            ],
+        };
+        let side = FaceFeature {
+            surface: Some(SurfaceGeometry::Plane {
+                origin: Point::from([1., 0., 0.]),
+                normal: Vector::from([1., 0., 0.]),
+            }),
+            triangles: vec![
+                Triangle::from_points([
+                    [1., 0., 0.],
+                    [1., 1., 0.],
+                    [1., 1., 1.],
+                ]),
+                Triangle::from_points([
+                    [1., 0., 0.],
+                    [1., 1., 1.],
+                    [1., 0., 1.],
+                ]),
+            ],
+        };
+        let features = Features {
+            faces: vec![bottom, side],
+            ..Features::default()
+        };
+
+        let measurement = features
+            .measure(Feature::Face(0), Feature::Face(1))
+            .unwrap();
+        assert_eq!(measurement.distance, Scalar::ZERO);
+        assert_eq!(measurement.angle, Some(Scalar::PI / 2.));
+
+        let empty = Features {
+            faces: vec![FaceFeature::default(), features.faces[1].clone()],
+            ..features
+        };
+        assert!(empty.measure(Feature::Face(0), Feature::Face(1)).is_none());
+    }
+
+    #[test]
+    fn measure_edges() {
+        let circle = Circle {
+            center: Point::from([0., 0., 1.]),
+            a: Vector::from([2., 0., 0.]),
+            b: Vector::from([0., 2., 0.]),
+        };
+        let line = Line {
+            origin: Point::from([3., 0., 0.]),
+            direction: Vector::from([0., 0., 1.]),
+        };
+
+        let features = Features {
+            edges: vec![
+                EdgeFeature {
+                    curve: Some(CurveGeometry::Circle(circle)),
+                    approx: PolyChain::from_points([
+                        [2., 0., 1.],
+                        [0., 2., 1.],
+                        [-2., 0., 1.],
+                        [0., -2., 1.],
+                        [2., 0., 1.],
+                    ]),
+                },
+                EdgeFeature {
+                    curve: Some(CurveGeometry::Line(line)),
+                    approx: PolyChain::from_points([
+                        [3., 0., 0.],
+                        [3., 0., 2.],
+                    ]),
+                },
+            ],
+            ..Features::default()
+        };
+
+        assert_eq!(features.radius(Feature::Edge(0)), Some(Scalar::from(2.)));
+        assert_eq!(features.radius(Feature::Edge(1)), None);
+
+        let measurement = features
+            .measure(Feature::Edge(0), Feature::Edge(1))
+            .unwrap();
+        assert_eq!(measurement.distance, Scalar::ONE);
+        assert_eq!(
+            measurement.points,
+            [Point::from([2., 0., 1.]), Point::from([3., 0., 1.])]
+        );
+
+        // The line is parallel to the axis of the circle, which makes it
+        // perpendicular to the plane of the circle.
+        assert_eq!(measurement.angle, Some(Scalar::PI / 2.));
+    }
+}
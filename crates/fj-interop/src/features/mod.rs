@@ -0,0 +1,300 @@
+//! The features of a shape
+//!
+//! Describes the vertices, edges, and faces of a shape, including their exact
+//! geometry, where that is available. This allows consumers to pick features
+//! of a shape and measure them, without access to its boundary representation.
+
+mod measure;
+mod ray;
+
+pub use self::{
+    measure::Measurement,
+    ray::{Pick, Ray, RayHit},
+};
+
+use fj_math::{Circle, Line, Point, PolyChain, Scalar, Triangle, Vector};
+
+use crate::mesh::FaceMesh;
+
+/// The features of a shape
+#[derive(Clone, Debug, Default)]
+pub struct Features {
+    /// The vertices of the shape
+    pub vertices: Vec<Point<3>>,
+
+    /// The edges of the shape
+    pub edges: Vec<EdgeFeature>,
+
+    /// The faces of the shape
+    ///
+    /// The index of each face matches the [`FaceId`] of its triangles.
+    ///
+    /// [`FaceId`]: crate::mesh::FaceId
+    pub faces: Vec<FaceFeature>,
+}
+
+impl Features {
+    /// Construct an empty instance of `Features`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the triangles of a face to the face with the same [`FaceId`]
+    ///
+    /// Adds faces without exact geometry, if no face with that id exists yet.
+    /// Triangles that don't span any area are ignored, as they can't be hit by
+    /// a ray.
+    ///
+    /// [`FaceId`]: crate::mesh::FaceId
+    pub fn push_face_mesh(&mut self, face_mesh: &FaceMesh) {
+        let index = face_mesh.face_id.0;
+        if self.faces.len() <= index {
+            self.faces.resize_with(index + 1, FaceFeature::default);
+        }
+
+        let triangles = face_mesh.triangles.iter().filter_map(|triangle| {
+            let [a, b, c] = triangle.points;
+            if (b - a).cross(&(c - a)).magnitude() == Scalar::ZERO {
+                return None;
+            }
+
+            Some(Triangle::from_points(triangle.points))
+        });
+        self.faces[index].triangles.extend(triangles);
+    }
+
+    /// Compute the length of a feature
+    ///
+    /// Returns `None`, unless the feature is an edge. The length of circular
+    /// edges is computed from their exact geometry. Otherwise, it is computed
+    /// from the approximation of the edge, which is exact for straight edges.
+    pub fn length(&self, feature: Feature) -> Option<Scalar> {
+        match feature {
+            Feature::Edge(i) => {
+                let edge = &self.edges[i];
+                let points = edge.approx.segments();
+
+                if let Some(CurveGeometry::Circle(circle)) = edge.curve {
+                    let angle = points
+                        .into_iter()
+                        .map(|segment| {
+                            let [a, b] = segment.points();
+                            angle_between(&circle, a, b)
+                        })
+                        .fold(Scalar::ZERO, |angle, step| angle + step);
+
+                    return Some(circle.a.magnitude() * angle.abs());
+                }
+
+                Some(
+                    points
+                        .into_iter()
+                        .map(|segment| {
+                            let [a, b] = segment.points();
+                            (b - a).magnitude()
+                        })
+                        .fold(Scalar::ZERO, |length, segment| length + segment),
+                )
+            }
+            Feature::Vertex(_) | Feature::Face(_) => None,
+        }
+    }
+
+    /// Compute the area of a feature
+    ///
+    /// Returns `None`, unless the feature is a face. The area is computed from
+    /// the triangles of the face, which is exact for planar faces.
+    pub fn area(&self, feature: Feature) -> Option<Scalar> {
+        match feature {
+            Feature::Face(i) => Some(
+                self.faces[i]
+                    .triangles
+                    .iter()
+                    .map(|triangle| {
+                        let [a, b, c] = triangle.points();
+                        (b - a).cross(&(c - a)).magnitude() / 2.
+                    })
+                    .fold(Scalar::ZERO, |area, triangle| area + triangle),
+            ),
+            Feature::Vertex(_) | Feature::Edge(_) => None,
+        }
+    }
+
+    /// Compute the radius of a feature
+    ///
+    /// Returns `None`, unless the feature is a circular edge or a cylindrical
+    /// face.
+    pub fn radius(&self, feature: Feature) -> Option<Scalar> {
+        match feature {
+            Feature::Vertex(_) => None,
+            Feature::Edge(i) => match self.edges[i].curve? {
+                CurveGeometry::Circle(circle) => Some(circle.a.magnitude()),
+                CurveGeometry::Line(_) => None,
+            },
+            Feature::Face(i) => match self.faces[i].surface? {
+                SurfaceGeometry::Cylinder { radius, .. } => Some(radius),
+                SurfaceGeometry::Plane { .. } => None,
+            },
+        }
+    }
+}
+
+/// Compute the signed angle from `a` to `b`, around the center of the circle
+///
+/// The points of an edge's approximation lie on its curve, and neighboring ones
+/// are less than half a turn apart, so summing up the angles between them
+/// yields the exact angle that the edge spans.
+fn angle_between(circle: &Circle<3>, a: Point<3>, b: Point<3>) -> Scalar {
+    let [a, b] = [a, b].map(|point| {
+        let v = point - circle.center;
+        (v.dot(&circle.a), v.dot(&circle.b))
+    });
+
+    let cross = a.0 * b.1 - a.1 * b.0;
+    let dot = a.0 * b.0 + a.1 * b.1;
+
+    cross.atan2(dot)
+}
+
+/// An edge of a shape
+#[derive(Clone, Debug)]
+pub struct EdgeFeature {
+    /// The exact geometry of the edge, if available
+    pub curve: Option<CurveGeometry>,
+
+    /// The approximation of the edge
+    pub approx: PolyChain<3>,
+}
+
+/// A face of a shape
+#[derive(Clone, Debug, Default)]
+pub struct FaceFeature {
+    /// The exact geometry of the face, if available
+    pub surface: Option<SurfaceGeometry>,
+
+    /// The triangles that approximate the face
+    pub triangles: Vec<Triangle<3>>,
+}
+
+/// The exact geometry of an edge
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CurveGeometry {
+    /// The edge is a circle, or an arc of one
+    Circle(Circle<3>),
+
+    /// The edge is a straight line segment
+    Line(Line<3>),
+}
+
+/// The exact geometry of a face
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SurfaceGeometry {
+    /// The face is planar
+    Plane {
+        /// A point on the plane
+        origin: Point<3>,
+
+        /// The normal of the plane
+        normal: Vector<3>,
+    },
+
+    /// The face is part of a cylinder
+    Cylinder {
+        /// The axis of the cylinder
+        axis: Line<3>,
+
+        /// The radius of the cylinder
+        radius: Scalar,
+    },
+}
+
+/// Refers to a feature in [`Features`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Feature {
+    /// The vertex with the given index
+    Vertex(usize),
+
+    /// The edge with the given index
+    Edge(usize),
+
+    /// The face with the given index
+    Face(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Point, PolyChain, Scalar, Triangle, Vector};
+
+    use super::{CurveGeometry, EdgeFeature, FaceFeature, Feature, Features};
+
+    #[test]
+    fn length_and_area() {
+        let features = Features {
+            edges: vec![EdgeFeature {
+                curve: None,
+                approx: PolyChain::from_points([
+                    [0., 0., 0.],
+                    [3., 4., 0.],
+                    [3., 4., 1.],
+                ]),
+            }],
+            faces: vec![FaceFeature {
+                surface: None,
+                triangles: vec![
+                    Triangle::from_points([
+                        [0., 0., 0.],
+                        [2., 0., 0.],
+                        [2., 3., 0.],
+                    ]),
+                    Triangle::from_points([
+                        [0., 0., 0.],
+                        [2., 3., 0.],
+                        [0., 3., 0.],
+                    ]),
+                ],
+            }],
+            ..Features::default()
+        };
+
+        assert_eq!(features.length(Feature::Edge(0)), Some(Scalar::from(6.)));
+        assert_eq!(features.length(Feature::Face(0)), None);
+
+        assert_eq!(features.area(Feature::Face(0)), Some(Scalar::from(6.)));
+        assert_eq!(features.area(Feature::Edge(0)), None);
+    }
+
+    #[test]
+    fn length_of_circular_edges() {
+        let circle = Circle {
+            center: Point::from([0., 0., 1.]),
+            a: Vector::from([2., 0., 0.]),
+            b: Vector::from([0., 2., 0.]),
+        };
+
+        let features = Features {
+            edges: vec![
+                EdgeFeature {
+                    curve: Some(CurveGeometry::Circle(circle)),
+                    approx: PolyChain::from_points([
+                        [2., 0., 1.],
+                        [0., 2., 1.],
+                        [-2., 0., 1.],
+                        [0., -2., 1.],
+                        [2., 0., 1.],
+                    ]),
+                },
+                EdgeFeature {
+                    curve: Some(CurveGeometry::Circle(circle.reverse())),
+                    approx: PolyChain::from_points([
+                        [2., 0., 1.],
+                        [0., -2., 1.],
+                    ]),
+                },
+            ],
+            ..Features::default()
+        };
+
+        assert_eq!(features.length(Feature::Edge(0)), Some(Scalar::PI * 4.));
+        assert_eq!(features.length(Feature::Edge(1)), Some(Scalar::PI));
+    }
+}
@@ -0,0 +1,238 @@
+use fj_math::{Point, Scalar, Segment, Vector};
+
+use super::{Feature, Features};
+
+/// A ray
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ray {
+    /// The point where the ray starts
+    pub origin: Point<3>,
+
+    /// The direction of the ray
+    pub direction: Vector<3>,
+}
+
+/// The point where a ray hits a face
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RayHit {
+    /// The index of the face that was hit
+    pub face: usize,
+
+    /// The point where the ray hits the face
+    pub point: Point<3>,
+
+    /// The distance from the origin of the ray to `point`
+    pub distance: Scalar,
+}
+
+/// A feature that has been picked
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pick {
+    /// The feature that has been picked
+    pub feature: Feature,
+
+    /// The point on the feature that has been picked
+    pub point: Point<3>,
+}
+
+impl Features {
+    /// Cast a ray against the faces of the shape
+    ///
+    /// Returns the hit that is closest to the origin of the ray, or `None`, if
+    /// the ray doesn't hit any face.
+    pub fn cast_ray(&self, ray: &Ray) -> Option<RayHit> {
+        let direction = ray.direction.normalize();
+
+        let mut closest: Option<RayHit> = None;
+        for (i, face) in self.faces.iter().enumerate() {
+            for triangle in &face.triangles {
+                let distance = match triangle.cast_local_ray(
+                    ray.origin,
+                    direction,
+                    f64::INFINITY,
+                    true,
+                ) {
+                    Some(distance) => distance,
+                    None => continue,
+                };
+
+                if let Some(closest) = closest {
+                    if closest.distance <= distance {
+                        continue;
+                    }
+                }
+
+                closest = Some(RayHit {
+                    face: i,
+                    point: ray.origin + direction * distance,
+                    distance,
+                });
+            }
+        }
+
+        closest
+    }
+
+    /// Pick the feature at a ray hit
+    ///
+    /// Vertices and edges are thin, and can't be hit by a ray directly.
+    /// Instead, a vertex or edge is picked, if it is within `snap_distance` of
+    /// the hit point. Vertices take precedence over edges, and edges over the
+    /// face that was hit.
+    pub fn pick(&self, hit: &RayHit, snap_distance: Scalar) -> Pick {
+        let vertex = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (i, vertex, (vertex - hit.point).magnitude()))
+            .filter(|&(_, _, distance)| distance <= snap_distance)
+            .min_by_key(|&(_, _, distance)| distance);
+        if let Some((i, point, _)) = vertex {
+            return Pick {
+                feature: Feature::Vertex(i),
+                point,
+            };
+        }
+
+        self.pick_edge_or_face(hit, snap_distance)
+    }
+
+    /// Pick the edge or face at a ray hit
+    ///
+    /// Like [`Features::pick`], but never picks a vertex.
+    pub fn pick_edge_or_face(
+        &self,
+        hit: &RayHit,
+        snap_distance: Scalar,
+    ) -> Pick {
+        let edge =
+            self.edges
+                .iter()
+                .enumerate()
+                .flat_map(|(i, edge)| {
+                    edge.approx.segments().into_iter().map(move |segment| {
+                        (i, closest_point(segment, hit.point))
+                    })
+                })
+                .map(|(i, point)| (i, point, (point - hit.point).magnitude()))
+                .filter(|&(_, _, distance)| distance <= snap_distance)
+                .min_by_key(|&(_, _, distance)| distance);
+        if let Some((i, point, _)) = edge {
+            return Pick {
+                feature: Feature::Edge(i),
+                point,
+            };
+        }
+
+        Pick {
+            feature: Feature::Face(hit.face),
+            point: hit.point,
+        }
+    }
+}
+
+/// Compute the point on a segment that is closest to another point
+pub(super) fn closest_point(segment: Segment<3>, point: Point<3>) -> Point<3> {
+    let [a, b] = segment.points();
+    let direction = b - a;
+
+    let t = (point - a).dot(&direction) / direction.dot(&direction);
+    let t = if t < Scalar::ZERO {
+        Scalar::ZERO
+    } else if t > Scalar::ONE {
+        Scalar::ONE
+    } else {
+        t
+    };
+
+    a + direction * t
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, PolyChain, Scalar, Triangle, Vector};
+
+    use crate::features::{EdgeFeature, FaceFeature, Feature, Features};
+
+    use super::Ray;
+
+    #[test]
+    fn cast_ray() {
+        let features = square();
+
+        let ray = Ray {
+            origin: Point::from([0.25, 0.5, 1.]),
+            direction: Vector::from([0., 0., -2.]),
+        };
+        let hit = features.cast_ray(&ray).unwrap();
+        assert_eq!(hit.face, 0);
+        assert_eq!(hit.point, Point::from([0.25, 0.5, 0.]));
+        assert_eq!(hit.distance, Scalar::ONE);
+
+        let miss = Ray {
+            origin: Point::from([2., 0.5, 1.]),
+            direction: Vector::from([0., 0., -1.]),
+        };
+        assert!(features.cast_ray(&miss).is_none());
+    }
+
+    #[test]
+    fn pick() {
+        let features = square();
+        let snap_distance = Scalar::from(0.1);
+
+        let pick = |x, y| {
+            let ray = Ray {
+                origin: Point::from([x, y, 1.]),
+                direction: Vector::from([0., 0., -1.]),
+            };
+            let hit = features.cast_ray(&ray).unwrap();
+            features.pick(&hit, snap_distance)
+        };
+
+        let vertex = pick(0.95, 0.05);
+        assert_eq!(vertex.feature, Feature::Vertex(1));
+        assert_eq!(vertex.point, Point::from([1., 0., 0.]));
+
+        let edge = pick(0.5, 0.05);
+        assert_eq!(edge.feature, Feature::Edge(0));
+        assert_eq!(edge.point, Point::from([0.5, 0., 0.]));
+
+        let face = pick(0.5, 0.5);
+        assert_eq!(face.feature, Feature::Face(0));
+        assert_eq!(face.point, Point::from([0.5, 0.5, 0.]));
+
+        let ray = Ray {
+            origin: Point::from([0.95, 0.05, 1.]),
+            direction: Vector::from([0., 0., -1.]),
+        };
+        let hit = features.cast_ray(&ray).unwrap();
+        let edge = features.pick_edge_or_face(&hit, snap_distance);
+        assert!(matches!(edge.feature, Feature::Edge(_)));
+    }
+
+    fn square() -> Features {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([1., 1., 0.]);
+        let d = Point::from([0., 1., 0.]);
+
+        Features {
+            vertices: vec![a, b, c, d],
+            edges: [[a, b], [b, c], [c, d], [d, a]]
+                .into_iter()
+                .map(|points| EdgeFeature {
+                    curve: None,
+                    approx: PolyChain::from_points(points),
+                })
+                .collect(),
+            faces: vec![FaceFeature {
+                surface: None,
+                triangles: vec![
+                    Triangle::from_points([a, b, c]),
+                    Triangle::from_points([a, c, d]),
+                ],
+            }],
+        }
+    }
+}
@@ -15,5 +15,7 @@
 #![warn(missing_docs)]
 
 pub mod debug;
+pub mod features;
 pub mod mesh;
 pub mod processed_shape;
+pub mod progress;
@@ -0,0 +1,87 @@
+//! Per-vertex attributes
+
+use fj_math::{Point, Scalar, Vector};
+
+use super::{Color, Mesh};
+
+/// A vertex that carries a normal and a color, in addition to its position
+///
+/// See [`Mesh::with_attributes`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Vertex {
+    /// The position of the vertex
+    pub position: Point<3>,
+
+    /// The normal of the vertex
+    pub normal: Vector<3>,
+
+    /// The color of the vertex
+    pub color: Color,
+}
+
+impl Mesh<Point<3>> {
+    /// Create a mesh whose vertices carry a normal and a color
+    ///
+    /// The normals are computed by [`Mesh::vertex_normals`], using the provided
+    /// `crease_angle`. Each vertex gets the color of the triangle it belongs
+    /// to.
+    ///
+    /// A point that is shared by triangles, for which it has different normals
+    /// or colors, results in multiple vertices. All other points result in a
+    /// single vertex, which is shared by the indices of all their triangles.
+    pub fn with_attributes(&self, crease_angle: Scalar) -> Mesh<Vertex> {
+        let mut mesh = Mesh::new();
+
+        let normals = self.vertex_normals(crease_angle);
+        for (triangle, normals) in self.triangles().zip(normals) {
+            for (position, normal) in triangle.points.into_iter().zip(normals) {
+                mesh.push_vertex(Vertex {
+                    position,
+                    normal,
+                    color: triangle.color,
+                });
+            }
+
+            mesh.triangles.push(triangle);
+        }
+
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::mesh::Mesh;
+
+    #[test]
+    fn with_attributes() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+        let d = Point::from([1., 1., 0.]);
+
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], red);
+        mesh.push_triangle([b, d, c], red);
+        mesh.push_triangle([a, c, Point::from([0., 0., 1.])], blue);
+
+        let mesh = mesh.with_attributes(Scalar::ZERO);
+
+        // The points that are only shared between the coplanar triangles of
+        // the same color are only present once.
+        assert_eq!(mesh.vertices().count(), 7);
+        assert_eq!(mesh.indices().count(), 9);
+        assert_eq!(mesh.triangles().count(), 3);
+
+        let normal = Vector::from([0., 0., 1.]);
+        for vertex in mesh.vertices().take(4) {
+            assert_eq!(vertex.normal, normal);
+            assert_eq!(vertex.color, red);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Splitting of meshes into their connected parts
+
+use std::collections::HashMap;
+
+use fj_math::Point;
+
+use super::Mesh;
+
+impl Mesh<Point<3>> {
+    /// Split the mesh into the bodies it consists of
+    ///
+    /// A body is a part of the mesh whose triangles are connected to each
+    /// other by shared vertices. Bodies that merely touch each other at a
+    /// vertex are considered to be a single body, and a cavity inside of a
+    /// solid is a body of its own.
+    ///
+    /// The bodies are ordered by the first of their triangles in this mesh, so
+    /// the order is stable, as long as the mesh doesn't change.
+    pub fn bodies(&self) -> Vec<Self> {
+        let triangles: Vec<_> = self.triangles().collect();
+
+        // Every triangle starts out as its own body. Bodies are merged, when
+        // a triangle shares a vertex with a triangle that came before it.
+        let mut bodies = DisjointSets::new(triangles.len());
+        let mut triangles_by_vertex = HashMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            for point in triangle.points {
+                let j = *triangles_by_vertex.entry(point).or_insert(i);
+                bodies.merge(i, j);
+            }
+        }
+
+        let mut meshes: Vec<Self> = Vec::new();
+        let mut meshes_by_root = HashMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            let index =
+                *meshes_by_root.entry(bodies.root(i)).or_insert_with(|| {
+                    meshes.push(Self::new());
+                    meshes.len() - 1
+                });
+
+            meshes[index].push_triangle(triangle.points, triangle.color);
+        }
+
+        meshes
+    }
+}
+
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn root(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            // Point each visited element to its grandparent, so later
+            // lookups are shorter.
+            self.parents[element] = self.parents[self.parents[element]];
+            element = self.parents[element];
+        }
+
+        element
+    }
+
+    fn merge(&mut self, a: usize, b: usize) {
+        let [a, b] = [self.root(a), self.root(b)];
+        self.parents[a.max(b)] = a.min(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::mesh::Mesh;
+
+    #[test]
+    fn bodies() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+        let d = Point::from([1., 1., 0.]);
+
+        let e = Point::from([0., 0., 1.]);
+        let f = Point::from([1., 0., 1.]);
+        let g = Point::from([0., 1., 1.]);
+
+        let color = [255, 0, 0, 255];
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([e, f, g], color);
+        mesh.push_triangle([a, b, c], color);
+        mesh.push_triangle([b, d, c], color);
+
+        let bodies = mesh.bodies();
+
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0].triangles().count(), 1);
+        assert!(bodies[0].contains_triangle([e, f, g]));
+        assert_eq!(bodies[1].triangles().count(), 2);
+        assert!(bodies[1].contains_triangle([a, b, c]));
+        assert!(bodies[1].contains_triangle([b, d, c]));
+    }
+}
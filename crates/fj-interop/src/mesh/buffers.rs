@@ -0,0 +1,236 @@
+//! Conversion of meshes into buffers, as used by GPU APIs
+
+use std::hash::Hash;
+
+use super::{Mesh, Vertex};
+
+impl<V> Mesh<V>
+where
+    V: Copy + Eq + Hash,
+{
+    /// Create an index buffer that uses the provided format
+    ///
+    /// Returns `None`, if the mesh has too many vertices to index them using
+    /// `format`.
+    pub fn index_buffer(&self, format: IndexFormat) -> Option<IndexBuffer> {
+        if format.max_vertices() < self.vertices.len() {
+            return None;
+        }
+
+        let buffer = match format {
+            IndexFormat::Uint16 => IndexBuffer::Uint16(
+                self.indices().map(|index| index as u16).collect(),
+            ),
+            IndexFormat::Uint32 => {
+                IndexBuffer::Uint32(self.indices().collect())
+            }
+        };
+
+        Some(buffer)
+    }
+
+    /// Create an index buffer that uses the smallest possible format
+    pub fn compact_index_buffer(&self) -> IndexBuffer {
+        [IndexFormat::Uint16, IndexFormat::Uint32]
+            .into_iter()
+            .find_map(|format| self.index_buffer(format))
+            .expect("Mesh indices are 32-bit; must fit into 32-bit buffer")
+    }
+}
+
+impl Mesh<Vertex> {
+    /// Create a vertex buffer that uses the provided layout
+    ///
+    /// The attributes of each vertex are tightly packed, in the order defined
+    /// by `layout`. All values are little-endian.
+    pub fn vertex_buffer(&self, layout: VertexLayout) -> Vec<u8> {
+        let mut buffer =
+            Vec::with_capacity(self.vertices.len() * layout.stride());
+
+        for vertex in self.vertices() {
+            let position: [f32; 3] = vertex.position.into();
+            let normal: [f32; 3] = vertex.normal.into();
+
+            for value in position.into_iter().chain(normal) {
+                buffer.extend(value.to_le_bytes());
+            }
+
+            match layout {
+                VertexLayout::PositionNormalColorF32 => {
+                    for value in vertex.color {
+                        let value = f32::from(value) / 255.;
+                        buffer.extend(value.to_le_bytes());
+                    }
+                }
+                VertexLayout::PositionNormalColorUnorm8 => {
+                    buffer.extend(vertex.color);
+                }
+                VertexLayout::PositionNormal => {}
+            }
+        }
+
+        buffer
+    }
+}
+
+/// The width of the indices in an [`IndexBuffer`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum IndexFormat {
+    /// 16-bit indices
+    Uint16,
+
+    /// 32-bit indices
+    Uint32,
+}
+
+impl IndexFormat {
+    /// The maximum number of vertices that can be indexed using this format
+    ///
+    /// The maximum value of each format is not used, as graphics APIs might
+    /// reserve it to restart primitives.
+    pub fn max_vertices(&self) -> usize {
+        match self {
+            Self::Uint16 => u16::MAX as usize,
+            Self::Uint32 => u32::MAX as usize,
+        }
+    }
+
+    /// The size of a single index, in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Uint16 => 2,
+            Self::Uint32 => 4,
+        }
+    }
+}
+
+/// An index buffer
+///
+/// Created by [`Mesh::index_buffer`] and [`Mesh::compact_index_buffer`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexBuffer {
+    /// An index buffer with 16-bit indices
+    Uint16(Vec<u16>),
+
+    /// An index buffer with 32-bit indices
+    Uint32(Vec<u32>),
+}
+
+impl IndexBuffer {
+    /// Access the format of the index buffer
+    pub fn format(&self) -> IndexFormat {
+        match self {
+            Self::Uint16(_) => IndexFormat::Uint16,
+            Self::Uint32(_) => IndexFormat::Uint32,
+        }
+    }
+
+    /// Access the number of indices in the buffer
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Uint16(indices) => indices.len(),
+            Self::Uint32(indices) => indices.len(),
+        }
+    }
+
+    /// Determine whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Convert the buffer into bytes, using little-endian byte order
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Uint16(indices) => indices
+                .iter()
+                .flat_map(|index| index.to_le_bytes())
+                .collect(),
+            Self::Uint32(indices) => indices
+                .iter()
+                .flat_map(|index| index.to_le_bytes())
+                .collect(),
+        }
+    }
+}
+
+/// The layout of the vertices in a vertex buffer
+///
+/// See [`Mesh::vertex_buffer`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum VertexLayout {
+    /// Position (`f32x3`), normal (`f32x3`), and color (`f32x4`)
+    PositionNormalColorF32,
+
+    /// Position (`f32x3`), normal (`f32x3`), and color (`unorm8x4`)
+    PositionNormalColorUnorm8,
+
+    /// Position (`f32x3`) and normal (`f32x3`)
+    PositionNormal,
+}
+
+impl VertexLayout {
+    /// The size of a single vertex, in bytes
+    pub fn stride(&self) -> usize {
+        match self {
+            Self::PositionNormalColorF32 => 40,
+            Self::PositionNormalColorUnorm8 => 28,
+            Self::PositionNormal => 24,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::mesh::Mesh;
+
+    use super::{IndexBuffer, IndexFormat, VertexLayout};
+
+    #[test]
+    fn index_buffer() {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+            [255, 0, 0, 255],
+        );
+
+        let buffer = mesh.compact_index_buffer();
+        assert_eq!(buffer, IndexBuffer::Uint16(vec![0, 1, 2]));
+        assert_eq!(buffer.to_bytes(), [0, 0, 1, 0, 2, 0]);
+
+        let buffer = mesh.index_buffer(IndexFormat::Uint32).unwrap();
+        assert_eq!(buffer.to_bytes().len(), 3 * buffer.format().size());
+
+        // Too many vertices for 16-bit indices.
+        let mut mesh = Mesh::new();
+        for i in 0..=u16::MAX {
+            mesh.push_vertex(i);
+        }
+        assert_eq!(mesh.index_buffer(IndexFormat::Uint16), None);
+        assert_eq!(mesh.compact_index_buffer().format(), IndexFormat::Uint32);
+    }
+
+    #[test]
+    fn vertex_buffer() {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+            [255, 0, 0, 255],
+        );
+        let mesh = mesh.with_attributes(Scalar::ZERO);
+
+        for layout in [
+            VertexLayout::PositionNormalColorF32,
+            VertexLayout::PositionNormalColorUnorm8,
+            VertexLayout::PositionNormal,
+        ] {
+            let buffer = mesh.vertex_buffer(layout);
+            assert_eq!(buffer.len(), 3 * layout.stride());
+        }
+
+        let buffer =
+            mesh.vertex_buffer(VertexLayout::PositionNormalColorUnorm8);
+        assert_eq!(buffer[24..28], [255, 0, 0, 255]);
+    }
+}
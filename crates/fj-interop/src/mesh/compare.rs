@@ -0,0 +1,158 @@
+//! Approximate comparison and hashing of meshes
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use fj_math::{Point, Scalar};
+
+use super::{Mesh, Triangle};
+
+impl Mesh<Point<3>> {
+    /// Determine whether two meshes are equal, within `tolerance`
+    ///
+    /// Two meshes are considered equal, if each triangle of one mesh has a
+    /// matching triangle in the other. Triangles match, if they have the same
+    /// color and winding, and their points are no further than `tolerance`
+    /// from each other. The order of the triangles doesn't matter, neither
+    /// does the order of the points within a triangle, as long as their
+    /// winding stays the same.
+    pub fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        if self.triangles.len() != other.triangles.len() {
+            return false;
+        }
+
+        let mut unmatched: Vec<_> = other.triangles().collect();
+
+        for triangle in self.triangles() {
+            let matching = unmatched
+                .iter()
+                .position(|t| triangles_match(&triangle, t, tolerance));
+
+            match matching {
+                Some(i) => {
+                    unmatched.swap_remove(i);
+                }
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Compute a hash of the mesh that ignores small deviations
+    ///
+    /// Points are snapped to a grid with a spacing of `tolerance` before being
+    /// hashed, and the hash doesn't depend on the order of the triangles, or
+    /// of the points within them (as long as their winding stays the same).
+    ///
+    /// Meshes that are equal according to [`Mesh::approx_eq`] will usually,
+    /// but not always, have the same hash. A point that is very close to a
+    /// grid line can snap to either side of it, depending on small deviations.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `tolerance` is not positive.
+    pub fn structural_hash(&self, tolerance: Scalar) -> u64 {
+        assert!(
+            tolerance > Scalar::ZERO,
+            "Tolerance for hashing must be positive"
+        );
+
+        let snap = |point: Point<3>| {
+            point
+                .coords
+                .components
+                .map(|c| (c / tolerance).round().into_f64() as i64)
+        };
+
+        let mut triangles: Vec<_> = self
+            .triangles()
+            .map(|triangle| {
+                let mut points = triangle.points.map(snap);
+
+                // Rotate the points, so the smallest one comes first. This
+                // makes the result independent of which point the triangle
+                // starts with, without changing its winding.
+                let first = (0..3).min_by_key(|&i| points[i]).unwrap_or(0);
+                points.rotate_left(first);
+
+                (points, triangle.color)
+            })
+            .collect();
+        triangles.sort();
+
+        let mut hasher = DefaultHasher::new();
+        triangles.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+fn triangles_match(a: &Triangle, b: &Triangle, tolerance: Scalar) -> bool {
+    if a.color != b.color {
+        return false;
+    }
+
+    (0..3).any(|offset| {
+        (0..3).all(|i| {
+            let p = a.points[i];
+            let q = b.points[(i + offset) % 3];
+
+            (p - q).magnitude() <= tolerance
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::mesh::Mesh;
+
+    #[test]
+    fn approx_eq_and_hash() {
+        let color = [255, 0, 0, 255];
+        let tolerance = Scalar::from(0.01);
+
+        let mut a = Mesh::new();
+        a.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+            color,
+        );
+        a.push_triangle(
+            [[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]].map(Point::from),
+            color,
+        );
+
+        // Same triangles in a different order, starting with different points,
+        // and with slight deviations.
+        let mut b = Mesh::new();
+        b.push_triangle(
+            [[1., 1., 0.], [0., 1., 0.], [1., 0., 0.001]].map(Point::from),
+            color,
+        );
+        b.push_triangle(
+            [[0., 1., 0.], [0., 0., 0.], [1., 0., 0.]].map(Point::from),
+            color,
+        );
+
+        // Same points as `a`, but one triangle has the opposite winding.
+        let mut c = Mesh::new();
+        c.push_triangle(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.]].map(Point::from),
+            color,
+        );
+        c.push_triangle(
+            [[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]].map(Point::from),
+            color,
+        );
+
+        assert!(a.approx_eq(&b, tolerance));
+        assert!(!a.approx_eq(&c, tolerance));
+
+        let tolerance = Scalar::from(0.1);
+        assert_eq!(a.structural_hash(tolerance), b.structural_hash(tolerance));
+        assert_ne!(a.structural_hash(tolerance), c.structural_hash(tolerance));
+    }
+}
@@ -0,0 +1,448 @@
+//! Mesh decimation using quadric error metrics
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    ops,
+};
+
+use fj_math::{Point, Scalar, Vector};
+
+use super::{Color, Mesh};
+
+impl Mesh<Point<3>> {
+    /// Create a simplified version of the mesh with fewer triangles
+    ///
+    /// Uses quadric error metrics to repeatedly collapse the edge whose removal
+    /// introduces the smallest error, until the provided target is reached.
+    /// Boundary edges, and edges between triangles of different colors, are
+    /// preserved as well as possible.
+    ///
+    /// This is intended for preview or export, where a lower triangle count can
+    /// be more valuable than an exact approximation of the shape.
+    pub fn decimate(&self, target: DecimationTarget) -> Self {
+        let mut decimator = Decimator::new(self);
+        decimator.run(target);
+        decimator.into_mesh()
+    }
+}
+
+/// The condition at which [`Mesh::decimate`] stops simplifying a mesh
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum DecimationTarget {
+    /// Stop, once the mesh has no more than the given number of triangles
+    TriangleCount(usize),
+
+    /// Stop, before an edge collapse would exceed the given error
+    ///
+    /// The error of an edge collapse is the square root of the sum of squared
+    /// distances between the new vertex and the planes of the original
+    /// triangles around the collapsed edge.
+    MaxError(Scalar),
+}
+
+struct Decimator {
+    positions: Vec<Point<3>>,
+    quadrics: Vec<Quadric>,
+    versions: Vec<u32>,
+    removed: Vec<bool>,
+
+    triangles: Vec<Option<([usize; 3], Color)>>,
+    triangles_by_vertex: Vec<Vec<usize>>,
+    num_triangles: usize,
+
+    queue: BinaryHeap<Reverse<Collapse>>,
+}
+
+impl Decimator {
+    fn new(mesh: &Mesh<Point<3>>) -> Self {
+        let mut positions = Vec::new();
+        let mut indices_by_position = HashMap::new();
+        let mut triangles = Vec::new();
+
+        for triangle in mesh.triangles() {
+            let indices = triangle.points.map(|point| {
+                *indices_by_position.entry(point).or_insert_with(|| {
+                    let index = positions.len();
+                    positions.push(point);
+                    index
+                })
+            });
+
+            let [a, b, c] = indices;
+            if a == b || b == c || c == a {
+                continue;
+            }
+
+            triangles.push(Some((indices, triangle.color)));
+        }
+
+        let mut quadrics = vec![Quadric::default(); positions.len()];
+        let mut triangles_by_vertex = vec![Vec::new(); positions.len()];
+        let mut triangles_by_edge: HashMap<_, Vec<_>> = HashMap::new();
+
+        for (t, triangle) in triangles.iter().enumerate() {
+            let (indices, _) =
+                triangle.expect("Only valid triangles have been added");
+            let [a, b, c] = indices.map(|i| positions[i]);
+
+            let quadric = Quadric::from_triangle([a, b, c]);
+            for i in indices {
+                quadrics[i] = quadrics[i] + quadric;
+                triangles_by_vertex[i].push(t);
+            }
+
+            for edge in [[0, 1], [1, 2], [2, 0]] {
+                let mut edge = edge.map(|i| indices[i]);
+                edge.sort_unstable();
+
+                triangles_by_edge.entry(edge).or_default().push(t);
+            }
+        }
+
+        // Edges that bound the mesh, or that separate triangles of different
+        // colors, are features that should survive the decimation. Penalize
+        // any movement away from them, by adding planes that are perpendicular
+        // to the adjacent triangles.
+        for (&edge, adjacent) in &triangles_by_edge {
+            let is_feature = match adjacent.as_slice() {
+                [_] => true,
+                [t1, t2] => {
+                    triangles[*t1].map(|(_, color)| color)
+                        != triangles[*t2].map(|(_, color)| color)
+                }
+                _ => false,
+            };
+
+            if !is_feature {
+                continue;
+            }
+
+            let [a, b] = edge.map(|i| positions[i]);
+            for &t in adjacent {
+                let (indices, _) =
+                    triangles[t].expect("Only valid triangles have been added");
+                let [p0, p1, p2] = indices.map(|i| positions[i]);
+                let normal = (p1 - p0).cross(&(p2 - p0));
+
+                let quadric = Quadric::from_edge_constraint([a, b], normal);
+                for i in edge {
+                    quadrics[i] = quadrics[i] + quadric;
+                }
+            }
+        }
+
+        let num_triangles = triangles.len();
+
+        let mut self_ = Self {
+            versions: vec![0; positions.len()],
+            removed: vec![false; positions.len()],
+            positions,
+            quadrics,
+            triangles,
+            triangles_by_vertex,
+            num_triangles,
+            queue: BinaryHeap::new(),
+        };
+
+        for [a, b] in triangles_by_edge.into_keys() {
+            self_.push_collapse(a, b);
+        }
+
+        self_
+    }
+
+    fn run(&mut self, target: DecimationTarget) {
+        while let Some(Reverse(collapse)) = self.queue.pop() {
+            match target {
+                DecimationTarget::TriangleCount(count) => {
+                    if self.num_triangles <= count {
+                        break;
+                    }
+                }
+                DecimationTarget::MaxError(max_error) => {
+                    if collapse.cost > max_error * max_error {
+                        break;
+                    }
+                }
+            }
+
+            let [a, b] = collapse.vertices;
+
+            let is_outdated = self.removed[a]
+                || self.removed[b]
+                || collapse.versions != [self.versions[a], self.versions[b]];
+            if is_outdated {
+                continue;
+            }
+
+            if self.collapse_would_flip_triangles(a, b, collapse.position) {
+                continue;
+            }
+
+            self.collapse(a, b, collapse.position);
+        }
+    }
+
+    fn push_collapse(&mut self, a: usize, b: usize) {
+        let quadric = self.quadrics[a] + self.quadrics[b];
+
+        let [pa, pb] = [a, b].map(|i| self.positions[i]);
+        let midpoint = pa + (pb - pa) / Scalar::TWO;
+
+        let (cost, position) = [pa, pb, midpoint]
+            .into_iter()
+            .map(|position| (quadric.error(position), position))
+            .min()
+            .expect("Array of candidates is not empty");
+
+        self.queue.push(Reverse(Collapse {
+            cost,
+            position,
+            vertices: [a, b],
+            versions: [self.versions[a], self.versions[b]],
+        }));
+    }
+
+    fn collapse_would_flip_triangles(
+        &self,
+        a: usize,
+        b: usize,
+        position: Point<3>,
+    ) -> bool {
+        for v in [a, b] {
+            for &t in &self.triangles_by_vertex[v] {
+                let (indices, _) = match self.triangles[t] {
+                    Some(triangle) => triangle,
+                    None => continue,
+                };
+
+                // This triangle is going to be removed by the collapse, so it
+                // can't flip.
+                if indices.contains(&a) && indices.contains(&b) {
+                    continue;
+                }
+
+                let before = indices.map(|i| self.positions[i]);
+                let after = indices.map(|i| {
+                    if i == a || i == b {
+                        position
+                    } else {
+                        self.positions[i]
+                    }
+                });
+
+                let [normal_before, normal_after] = [before, after]
+                    .map(|[p0, p1, p2]| (p1 - p0).cross(&(p2 - p0)));
+
+                // This also catches triangles that would become degenerate.
+                if normal_before.dot(&normal_after) <= Scalar::ZERO {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn collapse(&mut self, a: usize, b: usize, position: Point<3>) {
+        self.positions[a] = position;
+        self.quadrics[a] = self.quadrics[a] + self.quadrics[b];
+        self.removed[b] = true;
+
+        self.versions[a] += 1;
+        self.versions[b] += 1;
+
+        for t in std::mem::take(&mut self.triangles_by_vertex[b]) {
+            let triangle = match &mut self.triangles[t] {
+                Some((indices, _)) => indices,
+                None => continue,
+            };
+
+            for i in triangle.iter_mut() {
+                if *i == b {
+                    *i = a;
+                }
+            }
+
+            let [i0, i1, i2] = *triangle;
+            if i0 == i1 || i1 == i2 || i2 == i0 {
+                self.triangles[t] = None;
+                self.num_triangles -= 1;
+            } else {
+                self.triangles_by_vertex[a].push(t);
+            }
+        }
+
+        let triangles = &self.triangles;
+        self.triangles_by_vertex[a].retain(|&t| triangles[t].is_some());
+        self.triangles_by_vertex[a].sort_unstable();
+        self.triangles_by_vertex[a].dedup();
+
+        let mut neighbors = Vec::new();
+        for &t in &self.triangles_by_vertex[a] {
+            if let Some((indices, _)) = self.triangles[t] {
+                neighbors.extend(indices.into_iter().filter(|&i| i != a));
+            }
+        }
+        neighbors.sort_unstable();
+        neighbors.dedup();
+
+        for neighbor in neighbors {
+            self.push_collapse(a, neighbor);
+        }
+    }
+
+    fn into_mesh(self) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        for (indices, color) in self.triangles.into_iter().flatten() {
+            let points = indices.map(|i| self.positions[i]);
+            mesh.push_triangle(points, color);
+        }
+
+        mesh
+    }
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd)]
+struct Collapse {
+    cost: Scalar,
+    position: Point<3>,
+    vertices: [usize; 2],
+    versions: [u32; 2],
+}
+
+/// A symmetric 4x4 matrix that measures the squared distance to a set of planes
+///
+/// Only the upper triangle of the matrix is stored, row by row.
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn from_triangle(points: [Point<3>; 3]) -> Self {
+        let [a, b, c] = points;
+        let normal = (b - a).cross(&(c - a));
+
+        Self::from_plane(a, normal)
+    }
+
+    fn from_edge_constraint(edge: [Point<3>; 2], normal: Vector<3>) -> Self {
+        let [a, b] = edge;
+        let normal = (b - a).cross(&normal);
+
+        Self::from_plane(a, normal)
+    }
+
+    fn from_plane(point: Point<3>, normal: Vector<3>) -> Self {
+        // A plane that we can't compute a normal for doesn't contribute any
+        // error.
+        if normal.magnitude() == Scalar::ZERO {
+            return Self::default();
+        }
+
+        let [a, b, c]: [f64; 3] = normal.normalize().into();
+        let d = -(a * point.x.into_f64()
+            + b * point.y.into_f64()
+            + c * point.z.into_f64());
+
+        Self([
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ])
+    }
+
+    fn error(&self, point: Point<3>) -> Scalar {
+        let [x, y, z]: [f64; 3] = point.into();
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+
+        let error = aa * x * x
+            + 2. * ab * x * y
+            + 2. * ac * x * z
+            + 2. * ad * x
+            + bb * y * y
+            + 2. * bc * y * z
+            + 2. * bd * y
+            + cc * z * z
+            + 2. * cd * z
+            + dd;
+
+        // Rounding errors could make the result slightly negative.
+        Scalar::from_f64(error.max(0.))
+    }
+}
+
+impl ops::Add<Self> for Quadric {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::mesh::Mesh;
+
+    use super::DecimationTarget;
+
+    #[test]
+    fn decimate_to_triangle_count() {
+        let mesh = grid(4);
+        let decimated = mesh.decimate(DecimationTarget::TriangleCount(16));
+
+        assert!(decimated.triangles().count() <= 16);
+        assert!(decimated.vertices().all(|vertex| vertex.z == Scalar::ZERO));
+    }
+
+    #[test]
+    fn decimate_to_max_error() {
+        let mesh = grid(4);
+        let decimated = mesh.decimate(DecimationTarget::MaxError(Scalar::ZERO));
+
+        assert!(decimated.triangles().count() < mesh.triangles().count());
+        assert!(decimated.vertices().all(|vertex| vertex.z == Scalar::ZERO));
+
+        // The corners of the grid are the only features of a flat grid. They
+        // must not be moved.
+        for corner in [[0., 0., 0.], [4., 0., 0.], [4., 4., 0.], [0., 4., 0.]] {
+            assert!(decimated.vertices().any(|v| v == Point::from(corner)));
+        }
+    }
+
+    fn grid(n: u32) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        for i in 0..n {
+            for j in 0..n {
+                let [x0, y0] = [i, j].map(f64::from);
+                let [x1, y1] = [x0 + 1., y0 + 1.];
+
+                let a = Point::from([x0, y0, 0.]);
+                let b = Point::from([x1, y0, 0.]);
+                let c = Point::from([x1, y1, 0.]);
+                let d = Point::from([x0, y1, 0.]);
+
+                let color = [255, 0, 0, 255];
+                mesh.push_triangle([a, b, c], color);
+                mesh.push_triangle([a, c, d], color);
+            }
+        }
+
+        mesh
+    }
+}
@@ -0,0 +1,185 @@
+//! Binary format for meshes
+//!
+//! A serialized mesh consists of the following parts:
+//!
+//! 1. The 4 bytes `FJME`, identifying the format.
+//! 2. The version of the format, as a little-endian `u32`.
+//! 3. The mesh, encoded using [bincode] 1.x with its default configuration.
+//!    It consists of the list of vertices, each as an `[f64; 3]`, followed by
+//!    the list of triangles, each as three vertex indices and a color.
+//!
+//! [bincode]: https://docs.rs/bincode/1
+
+use std::{error, fmt};
+
+use fj_math::{Point, Scalar};
+use serde::{Deserialize, Serialize};
+
+use super::{Color, Index, Mesh};
+
+const MAGIC: [u8; 4] = *b"FJME";
+
+/// The current version of the binary mesh format
+pub const FORMAT_VERSION: u32 = 1;
+
+impl Mesh<Point<3>> {
+    /// Serialize the mesh into a versioned binary format
+    ///
+    /// The format is made up of a 4-byte header (`FJME`), the format version
+    /// as a little-endian `u32` ([`FORMAT_VERSION`]), and the vertices and
+    /// triangles of the mesh, encoded using bincode 1.x.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let mesh = MeshSerde {
+            vertices: self
+                .vertices()
+                .map(|point| point.coords.components.map(Scalar::into_f64))
+                .collect(),
+            triangles: self
+                .indices
+                .chunks(3)
+                .zip(self.triangles())
+                .map(|(indices, triangle)| {
+                    ([indices[0], indices[1], indices[2]], triangle.color)
+                })
+                .collect(),
+        };
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+
+        bincode::serialize_into(&mut bytes, &mesh)?;
+
+        Ok(bytes)
+    }
+
+    /// Deserialize a mesh from the binary format
+    ///
+    /// See [`Mesh::to_bytes`] for details on the format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormatError> {
+        let payload = match bytes {
+            [m0, m1, m2, m3, v0, v1, v2, v3, payload @ ..]
+                if [*m0, *m1, *m2, *m3] == MAGIC =>
+            {
+                let version = u32::from_le_bytes([*v0, *v1, *v2, *v3]);
+                if version != FORMAT_VERSION {
+                    return Err(FormatError::UnsupportedVersion(version));
+                }
+
+                payload
+            }
+            _ => return Err(FormatError::InvalidHeader),
+        };
+
+        let serde_mesh: MeshSerde = bincode::deserialize(payload)?;
+
+        let mut mesh = Self::new();
+        for (indices, color) in serde_mesh.triangles {
+            let mut points = [Point::origin(); 3];
+            for (point, index) in points.iter_mut().zip(indices) {
+                let vertex = serde_mesh
+                    .vertices
+                    .get(index as usize)
+                    .ok_or(FormatError::InvalidIndex(index))?;
+                *point = Point::from(*vertex);
+            }
+
+            mesh.push_triangle(points, color);
+        }
+
+        Ok(mesh)
+    }
+}
+
+/// An error that can occur while serializing or deserializing a mesh
+#[derive(Debug)]
+pub enum FormatError {
+    /// The data doesn't start with the expected header
+    InvalidHeader,
+
+    /// The data was serialized using an unsupported version of the format
+    UnsupportedVersion(u32),
+
+    /// A triangle refers to a vertex that doesn't exist
+    InvalidIndex(Index),
+
+    /// Error encoding or decoding the mesh
+    Encoding(bincode::Error),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "invalid header"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported format version {version}, expected \
+                {FORMAT_VERSION}"
+            ),
+            Self::InvalidIndex(index) => {
+                write!(f, "invalid vertex index {index}")
+            }
+            Self::Encoding(err) => write!(f, "encoding error: {err}"),
+        }
+    }
+}
+
+impl error::Error for FormatError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Encoding(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<bincode::Error> for FormatError {
+    fn from(err: bincode::Error) -> Self {
+        Self::Encoding(err)
+    }
+}
+
+/// An owned, serializable representation of [`Mesh`]
+#[derive(Serialize, Deserialize)]
+struct MeshSerde {
+    vertices: Vec<[f64; 3]>,
+    triangles: Vec<([Index; 3], Color)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::mesh::Mesh;
+
+    use super::FormatError;
+
+    #[test]
+    fn loopback() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+        let d = Point::from([1., 1., 0.]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], [255, 0, 0, 255]);
+        mesh.push_triangle([b, d, c], [0, 255, 0, 255]);
+
+        let bytes = mesh.to_bytes().unwrap();
+        let mesh_de = Mesh::from_bytes(&bytes).unwrap();
+
+        assert_eq!(
+            mesh.vertices().collect::<Vec<_>>(),
+            mesh_de.vertices().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            mesh.triangles().collect::<Vec<_>>(),
+            mesh_de.triangles().collect::<Vec<_>>(),
+        );
+
+        assert!(matches!(
+            Mesh::from_bytes(&bytes[..6]),
+            Err(FormatError::InvalidHeader)
+        ));
+    }
+}
@@ -1,10 +1,34 @@
 //! A triangle mesh
 
+mod attributes;
+mod bodies;
+mod buffers;
+mod compare;
+mod decimate;
+#[cfg(feature = "serde")]
+mod format;
+mod normals;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod weld;
+
+#[cfg(feature = "serde")]
+pub use self::format::{FormatError, FORMAT_VERSION};
+#[cfg(feature = "serde")]
+pub use self::snapshot::{assert_snapshot, UPDATE_SNAPSHOTS};
+pub use self::{
+    attributes::Vertex,
+    buffers::{IndexBuffer, IndexFormat, VertexLayout},
+    decimate::DecimationTarget,
+    weld::Welder,
+};
+
 use std::{collections::HashMap, hash::Hash};
 
 use fj_math::Point;
 
 /// A triangle mesh
+#[derive(Clone)]
 pub struct Mesh<V> {
     vertices: Vec<V>,
     indices: Vec<Index>,
@@ -108,3 +132,23 @@ pub struct Triangle {
 
 /// RGBA color
 pub type Color = [u8; 4];
+
+/// The triangles of a single face of a shape
+///
+/// Used to deliver a mesh incrementally, face by face, so consumers can start
+/// displaying a shape before all of it has been triangulated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FaceMesh {
+    /// The face that the triangles belong to
+    pub face_id: FaceId,
+
+    /// The triangles of the face
+    pub triangles: Vec<Triangle>,
+}
+
+/// Identifies a face within the shape that is being triangulated
+///
+/// Faces are numbered in the order in which they are triangulated, starting
+/// at zero.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct FaceId(pub usize);
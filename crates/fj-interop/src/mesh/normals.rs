@@ -0,0 +1,143 @@
+//! Vertex normal generation
+
+use std::collections::HashMap;
+
+use fj_math::{Point, Scalar, Vector};
+
+use super::Mesh;
+
+impl Mesh<Point<3>> {
+    /// Compute a normal for each vertex of each triangle
+    ///
+    /// Returns one entry per triangle, in the same order as
+    /// [`Mesh::triangles`], with the normals of the triangle's vertices.
+    ///
+    /// The normal of a vertex is the area-weighted average of the normals of
+    /// all triangles that share that vertex, as long as their angle to the
+    /// triangle the vertex belongs to does not exceed `crease_angle` (in
+    /// radians). Edges where triangles meet at a larger angle stay sharp.
+    ///
+    /// This means a `crease_angle` of zero results in flat shading, while
+    /// larger values result in curved surfaces being shaded smoothly.
+    pub fn vertex_normals(&self, crease_angle: Scalar) -> Vec<[Vector<3>; 3]> {
+        let min_cos = crease_angle.cos();
+
+        // The cross product of two triangle edges. Its length is twice the
+        // area of the triangle, which gives us area weighting for free.
+        let face_normals: Vec<_> = self
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.points;
+                (b - a).cross(&(c - a))
+            })
+            .collect();
+
+        let mut triangles_by_point: HashMap<Point<3>, Vec<usize>> =
+            HashMap::new();
+        for (i, triangle) in self.triangles().enumerate() {
+            for point in triangle.points {
+                triangles_by_point.entry(point).or_default().push(i);
+            }
+        }
+
+        self.triangles()
+            .zip(&face_normals)
+            .map(|(triangle, &face_normal)| {
+                triangle.points.map(|point| {
+                    let mut normal = Vector::from([0., 0., 0.]);
+
+                    for &i in &triangles_by_point[&point] {
+                        let other = face_normals[i];
+
+                        if angle_cos(face_normal, other) >= min_cos {
+                            normal = normal + other;
+                        }
+                    }
+
+                    if normal.magnitude() == Scalar::ZERO {
+                        return normal;
+                    }
+
+                    normal.normalize()
+                })
+            })
+            .collect()
+    }
+}
+
+fn angle_cos(a: Vector<3>, b: Vector<3>) -> Scalar {
+    let magnitudes = a.magnitude() * b.magnitude();
+
+    // Degenerate triangles don't have a meaningful normal. Treating them as
+    // perpendicular to everything makes sure they don't influence anything.
+    if magnitudes == Scalar::ZERO {
+        return Scalar::ZERO;
+    }
+
+    a.dot(&b) / magnitudes
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::mesh::Mesh;
+
+    #[test]
+    fn vertex_normals_smooth() {
+        // Two triangles that are folded along their shared edge by a small
+        // angle.
+        let mesh = folded(0.1);
+
+        let normals = mesh.vertex_normals(Scalar::PI / 4.);
+
+        // The vertices on the shared edge are averaged.
+        let expected = Vector::from([0., 0., 1.]);
+        assert_close(normals[0][0], expected);
+        assert_close(normals[0][1], expected);
+        assert_close(normals[1][0], expected);
+        assert_close(normals[1][2], expected);
+    }
+
+    #[test]
+    fn vertex_normals_sharp() {
+        // Two triangles at a right angle to each other.
+        let mesh = folded(1.);
+
+        let normals = mesh.vertex_normals(Scalar::PI / 4.);
+
+        let [face_a, face_b] = {
+            let mut triangles = mesh.triangles();
+            [triangles.next(), triangles.next()].map(|triangle| {
+                let [a, b, c] = triangle.unwrap().points;
+                (b - a).cross(&(c - a)).normalize()
+            })
+        };
+
+        for normal in normals[0] {
+            assert_close(normal, face_a);
+        }
+        for normal in normals[1] {
+            assert_close(normal, face_b);
+        }
+    }
+
+    fn folded(height: f64) -> Mesh<Point<3>> {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([0., 1., 0.]);
+        let c = Point::from([-1., 0., height]);
+        let d = Point::from([1., 0., height]);
+
+        let color = [255, 0, 0, 255];
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], color);
+        mesh.push_triangle([a, d, b], color);
+
+        mesh
+    }
+
+    fn assert_close(a: Vector<3>, b: Vector<3>) {
+        assert!((a - b).magnitude() < Scalar::from(1e-8), "{a:?} != {b:?}");
+    }
+}
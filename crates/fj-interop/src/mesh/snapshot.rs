@@ -0,0 +1,106 @@
+//! Snapshot tests for meshes
+
+use std::{env, fs, path::Path};
+
+use fj_math::{Point, Scalar};
+
+use super::Mesh;
+
+/// The environment variable that causes snapshots to be updated
+///
+/// If this variable is set, [`assert_snapshot`] overwrites existing snapshots
+/// with the current mesh, instead of comparing against them.
+pub const UPDATE_SNAPSHOTS: &str = "FJ_UPDATE_SNAPSHOTS";
+
+/// Assert that a mesh matches the snapshot stored at `path`
+///
+/// This is meant to be used in regression tests, to catch unintended changes
+/// to the geometry of a model. The snapshot is stored in the binary mesh
+/// format (see [`Mesh::to_bytes`]) and compared using [`Mesh::approx_eq`].
+///
+/// If no snapshot exists at `path`, or if the environment variable
+/// [`UPDATE_SNAPSHOTS`] is set, the mesh is written to `path` instead, and the
+/// assertion passes.
+///
+/// # Panics
+///
+/// Panics, if the mesh doesn't match the snapshot, or if the snapshot can't
+/// be read or written.
+pub fn assert_snapshot(
+    mesh: &Mesh<Point<3>>,
+    path: impl AsRef<Path>,
+    tolerance: impl Into<Scalar>,
+) {
+    let path = path.as_ref();
+
+    if env::var_os(UPDATE_SNAPSHOTS).is_some() || !path.exists() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap_or_else(|err| {
+                panic!("Failed to create `{}`: {err}", dir.display())
+            });
+        }
+
+        let bytes = mesh.to_bytes().unwrap_or_else(|err| {
+            panic!("Failed to serialize mesh: {err}");
+        });
+        fs::write(path, bytes).unwrap_or_else(|err| {
+            panic!("Failed to write snapshot `{}`: {err}", path.display())
+        });
+
+        return;
+    }
+
+    let bytes = fs::read(path).unwrap_or_else(|err| {
+        panic!("Failed to read snapshot `{}`: {err}", path.display())
+    });
+    let snapshot = Mesh::from_bytes(&bytes).unwrap_or_else(|err| {
+        panic!("Failed to load snapshot `{}`: {err}", path.display())
+    });
+
+    assert!(
+        mesh.approx_eq(&snapshot, tolerance.into()),
+        "Mesh doesn't match snapshot `{}` ({} triangles, expected {}). Set \
+        `{UPDATE_SNAPSHOTS}` to update the snapshot, if the change is \
+        intended.",
+        path.display(),
+        mesh.triangles().count(),
+        snapshot.triangles().count(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, panic, process};
+
+    use fj_math::Point;
+
+    use crate::mesh::Mesh;
+
+    use super::assert_snapshot;
+
+    #[test]
+    fn snapshot() {
+        let dir = env::temp_dir()
+            .join(format!("fj-interop-snapshot-{}", process::id()));
+        let path = dir.join("triangle.fjm");
+
+        let triangle = |z: f64| {
+            let mut mesh = Mesh::new();
+            mesh.push_triangle(
+                [[0., 0., z], [1., 0., z], [0., 1., z]].map(Point::from),
+                [255, 0, 0, 255],
+            );
+            mesh
+        };
+
+        // The first call writes the snapshot, the second compares against it.
+        assert_snapshot(&triangle(0.), &path, 0.01);
+        assert_snapshot(&triangle(0.001), &path, 0.01);
+
+        let result =
+            panic::catch_unwind(|| assert_snapshot(&triangle(1.), &path, 0.01));
+        assert!(result.is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
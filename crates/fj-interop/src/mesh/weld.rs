@@ -0,0 +1,139 @@
+//! Welding of near-duplicate vertices
+
+use std::collections::HashMap;
+
+use fj_math::{Point, Scalar};
+
+use super::Mesh;
+
+impl Mesh<Point<3>> {
+    /// Merge vertices that are closer to each other than `tolerance`
+    ///
+    /// Faces are triangulated independently of each other. Vertices that are
+    /// supposed to be shared between them can end up very close to, but not
+    /// exactly at, the same position. This method merges those vertices, so
+    /// the resulting mesh is properly connected along shared edges.
+    ///
+    /// Triangles that are degenerate, either before or as a result of the
+    /// welding, are removed.
+    ///
+    /// A `tolerance` of zero only merges vertices that are exactly identical.
+    pub fn weld(&self, tolerance: Scalar) -> Self {
+        let mut welder = Welder::new(tolerance);
+        let mut mesh = Self::new();
+
+        for triangle in self.triangles() {
+            let [a, b, c] = triangle.points.map(|point| welder.weld(point));
+
+            if a == b || b == c || c == a {
+                continue;
+            }
+            if (b - a).cross(&(c - a)).magnitude() == Scalar::ZERO {
+                continue;
+            }
+
+            mesh.push_triangle([a, b, c], triangle.color);
+        }
+
+        mesh
+    }
+}
+
+/// Merges points that are closer to each other than a tolerance
+///
+/// This is what [`Mesh::weld`] uses under the hood. It is also useful for
+/// other code that needs to identify points that are supposed to be identical,
+/// but might not be, due to floating-point inaccuracies.
+pub struct Welder {
+    tolerance: Scalar,
+    points_by_cell: HashMap<[i64; 3], Vec<Point<3>>>,
+}
+
+impl Welder {
+    /// Construct a new instance of `Welder`
+    ///
+    /// A `tolerance` of zero only merges points that are exactly identical.
+    pub fn new(tolerance: Scalar) -> Self {
+        Self {
+            tolerance,
+            points_by_cell: HashMap::new(),
+        }
+    }
+
+    /// Return the point that `point` is welded to
+    ///
+    /// That's either a previously seen point that is close enough, or `point`
+    /// itself, which then becomes a candidate for later points.
+    pub fn weld(&mut self, point: Point<3>) -> Point<3> {
+        if self.tolerance <= Scalar::ZERO {
+            return point;
+        }
+
+        // The cells are as large as the tolerance, so any point that is close
+        // enough must be in the same cell, or one of its direct neighbors.
+        let cell = point
+            .coords
+            .components
+            .map(|coord| (coord / self.tolerance).into_f64().floor() as i64);
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let [x, y, z] = cell;
+                    let neighbor = [x + dx, y + dy, z + dz];
+
+                    let candidates = match self.points_by_cell.get(&neighbor) {
+                        Some(candidates) => candidates,
+                        None => continue,
+                    };
+
+                    for &candidate in candidates {
+                        if (candidate - point).magnitude() <= self.tolerance {
+                            return candidate;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.points_by_cell.entry(cell).or_default().push(point);
+        point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::mesh::Mesh;
+
+    #[test]
+    fn weld() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+        let d = Point::from([1., 1., 0.]);
+
+        // Slightly offset copies of the shared vertices.
+        let b2 = Point::from([1. + 1e-12, 0., 0.]);
+        let c2 = Point::from([0., 1. - 1e-12, 0.]);
+
+        let color = [255, 0, 0, 255];
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], color);
+        mesh.push_triangle([b2, d, c2], color);
+
+        // This triangle becomes degenerate after welding.
+        mesh.push_triangle([b, b2, d], color);
+
+        assert_eq!(mesh.vertices().count(), 6);
+
+        let welded = mesh.weld(Scalar::from(1e-9));
+
+        assert_eq!(welded.vertices().count(), 4);
+        assert_eq!(welded.triangles().count(), 2);
+        assert!(welded.contains_triangle([a, b, c]));
+        assert!(welded.contains_triangle([b, d, c]));
+    }
+}
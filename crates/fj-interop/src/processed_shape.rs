@@ -1,8 +1,8 @@
 //! A processed shape
 
-use fj_math::{Aabb, Point};
+use fj_math::{Aabb, Point, PolyChain};
 
-use crate::{debug::DebugInfo, mesh::Mesh};
+use crate::{debug::DebugInfo, features::Features, mesh::Mesh};
 
 /// A processed shape
 pub struct ProcessedShape {
@@ -12,6 +12,17 @@ pub struct ProcessedShape {
     /// The triangle mesh that approximates the original shape
     pub mesh: Mesh<Point<3>>,
 
+    /// The approximated edges of the original shape
+    ///
+    /// These can be used to render the feature edges of the shape, which can't
+    /// be reliably inferred from the triangle mesh.
+    pub edges: Vec<PolyChain<3>>,
+
+    /// The features of the original shape
+    ///
+    /// These can be used to pick parts of the shape, and measure them.
+    pub features: Features,
+
     /// The debug info generated while processing the shape
     pub debug_info: DebugInfo,
 }
@@ -0,0 +1,182 @@
+//! Progress reporting and cancellation of long-running operations
+//!
+//! Defines types that allow the CAD kernel and other crates to report how far
+//! along they are with an operation, without depending on the crate that
+//! displays that progress to the user. The same types allow that crate to
+//! cancel an operation that is no longer needed.
+
+use std::{
+    error, fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Receives progress reports from long-running operations
+///
+/// Operations that support progress reporting take a `&mut ProgressHandle`
+/// and call [`ProgressHandle::report`] as they make progress.
+///
+/// Operations that support cancellation also call [`ProgressHandle::check`]
+/// regularly, and abort, if that returns an error.
+///
+/// Clones of a handle share the same callback and cancellation token. This
+/// allows operations that run parts of their work in parallel to hand each of
+/// those parts its own handle.
+#[derive(Clone, Default)]
+pub struct ProgressHandle {
+    callback: Option<Arc<Mutex<ProgressCallback>>>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl ProgressHandle {
+    /// Construct an instance of `ProgressHandle` that ignores all reports
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct an instance of `ProgressHandle` that forwards all reports
+    pub fn from_callback(
+        callback: impl FnMut(Progress) + Send + 'static,
+    ) -> Self {
+        Self {
+            callback: Some(Arc::new(Mutex::new(callback))),
+            cancellation_token: None,
+        }
+    }
+
+    /// Allow the operation to be cancelled using the provided token
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Check whether the operation has been cancelled
+    ///
+    /// Returns an error, if the operation has been cancelled. Operations that
+    /// support cancellation are expected to stop what they're doing and
+    /// propagate that error.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match &self.cancellation_token {
+            Some(token) if token.is_cancelled() => Err(Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Report that `completed` out of `total` steps of `phase` are done
+    pub fn report(&mut self, phase: Phase, completed: usize, total: usize) {
+        if let Some(callback) = &self.callback {
+            let mut callback =
+                callback.lock().expect("Progress callback mutex poisoned");
+            callback(Progress {
+                phase,
+                completed,
+                total,
+            });
+        }
+    }
+}
+
+impl fmt::Debug for ProgressHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProgressHandle")
+            .field("has_callback", &self.callback.is_some())
+            .field("cancellation_token", &self.cancellation_token)
+            .finish()
+    }
+}
+
+type ProgressCallback = dyn FnMut(Progress) + Send;
+
+/// A progress report
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Progress {
+    /// The phase of the operation that is being reported on
+    pub phase: Phase,
+
+    /// The number of steps of the phase that are completed
+    pub completed: usize,
+
+    /// The total number of steps of the phase
+    pub total: usize,
+}
+
+impl Progress {
+    /// The completed part of the phase, as a number between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.;
+        }
+
+        self.completed as f64 / self.total as f64
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:.0}%", self.phase, self.fraction() * 100.)
+    }
+}
+
+/// A phase of processing a shape
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Phase {
+    /// Sweeping sketches into solids
+    Sweep,
+
+    /// Triangulating faces
+    Triangulation,
+
+    /// Sampling a signed distance field
+    Sampling,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Sweep => "Sweeping",
+            Self::Triangulation => "Triangulating",
+            Self::Sampling => "Sampling",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// A token that can be used to cancel a long-running operation
+///
+/// Clones of the token share the same state. Pass one to the operation, via
+/// [`ProgressHandle::with_cancellation_token`], and keep another, to be able
+/// to cancel the operation.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Construct a new instance of `CancellationToken`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the operation that this token was passed to
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Determine whether the operation has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The error returned by operations that have been cancelled
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Operation was cancelled")
+    }
+}
+
+impl error::Error for Cancelled {}
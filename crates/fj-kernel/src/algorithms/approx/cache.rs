@@ -0,0 +1,157 @@
+use std::{collections::HashMap, fmt};
+
+use fj_math::{Point, Scalar};
+use parking_lot::Mutex;
+
+use crate::{
+    local::Local,
+    objects::{Curve, Handle, ObjectId},
+};
+
+use super::Tolerance;
+
+/// A cache of curve approximations
+///
+/// Curves that are shared between multiple edges, for example the circle that
+/// bounds both the side and the top face of a cylinder, are kept in a
+/// [`Store`]. A cache makes sure that the approximation of such a curve is
+/// only computed once per tolerance, and then shared between all of those
+/// edges.
+///
+/// Curves are identified by their [`Handle`], so only curves from the same
+/// store are shared. Surfaces are not kept in stores, and their approximation
+/// depends on the boundary of each face, so they are not cached.
+///
+/// [`Store`]: crate::objects::Store
+#[derive(Default)]
+pub struct ApproxCache {
+    curves: Mutex<HashMap<CurveKey, CurveApprox>>,
+}
+
+impl ApproxCache {
+    /// Construct an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop all entries
+    pub fn clear(&self) {
+        self.curves.lock().clear();
+    }
+
+    /// Return the number of approximations in the cache
+    pub fn len(&self) -> usize {
+        self.curves.lock().len()
+    }
+
+    /// Indicate whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Access the approximation of a curve, computing it, if it's not cached
+    ///
+    /// `section` is the range of curve coordinates that is approximated, or
+    /// `None`, if the whole curve is.
+    pub(super) fn curve(
+        &self,
+        curve: &Handle<Curve<3>>,
+        tolerance: Tolerance,
+        section: Option<[Scalar; 2]>,
+        approx: impl FnOnce() -> Vec<Local<Point<1>>>,
+    ) -> Vec<Local<Point<1>>> {
+        let key = CurveKey {
+            curve: curve.id(),
+            tolerance,
+            section,
+        };
+
+        if let Some(cached) = self.curves.lock().get(&key) {
+            return cached.points.clone();
+        }
+
+        let points = approx();
+        self.curves.lock().insert(
+            key,
+            CurveApprox {
+                _curve: curve.clone(),
+                points: points.clone(),
+            },
+        );
+
+        points
+    }
+}
+
+impl fmt::Debug for ApproxCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ApproxCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[derive(Eq, PartialEq, Hash)]
+struct CurveKey {
+    curve: ObjectId,
+    tolerance: Tolerance,
+    section: Option<[Scalar; 2]>,
+}
+
+struct CurveApprox {
+    /// The curve that was approximated
+    ///
+    /// Keeps the curve alive, as long as the entry exists, so its ID can't be
+    /// reused by another curve.
+    _curve: Handle<Curve<3>>,
+
+    points: Vec<Local<Point<1>>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{EdgeApprox, Tolerance},
+        objects::{Edge, Stores, Surface},
+    };
+
+    use super::ApproxCache;
+
+    #[test]
+    fn curves() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let cache = ApproxCache::new();
+
+        let fine = Tolerance::from_scalar(0.001)?;
+        let coarse = Tolerance::from_scalar(0.1)?;
+
+        // The store returns the same curve for both edges.
+        let a = Edge::circle_from_radius(&stores, Scalar::ONE);
+        let b = Edge::circle_from_radius(&stores, Scalar::ONE);
+
+        let approx = EdgeApprox::cached(&a, fine, &cache);
+        assert_eq!(approx, EdgeApprox::new(&a, fine));
+        assert_eq!(EdgeApprox::cached(&b, fine, &cache), approx);
+        assert_eq!(cache.len(), 1);
+
+        EdgeApprox::cached(&a, coarse, &cache);
+        assert_eq!(cache.len(), 2);
+
+        // Lines are approximated by their vertices alone.
+        let surface = Surface::xy_plane();
+        let line = Edge::line_segment_from_points(
+            &stores,
+            &surface,
+            [[0., 0.], [1., 0.]],
+        );
+        EdgeApprox::cached(&line, fine, &cache);
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use std::cmp::max;
+use std::cmp::{max, min};
 
-use fj_math::{Circle, Point, Scalar};
+use fj_math::{Arc, Circle, Point, Scalar};
 
 use crate::{local::Local, objects::Curve};
 
@@ -11,15 +11,8 @@ use super::Tolerance;
 /// `tolerance` defines how far the approximation is allowed to deviate from the
 /// actual edge.
 ///
-/// # Implementation Note
-///
-/// This only works as it is, because edges are severely limited and don't
-/// define which section of the curve they inhabit. Once they do that, we need
-/// an `approximate_between(a, b)` method instead, where `a` and `b` are the
-/// vertices that bound the edge on the curve.
-///
-/// The `approximate_between` methods of the curves then need to make sure to
-/// only return points in between those vertices, not the vertices themselves.
+/// This approximates the whole curve. For the section of a circle that is
+/// bounded by the vertices of an edge, see [`approx_arc`].
 pub fn approx_curve(
     curve: &Curve<3>,
     tolerance: Tolerance,
@@ -57,8 +50,40 @@ pub fn approx_circle(
     }
 }
 
-fn number_of_vertices_for_circle(tolerance: Tolerance, radius: Scalar) -> u64 {
-    let n = (Scalar::PI / (Scalar::ONE - (tolerance.inner() / radius)).acos())
+/// Approximate an arc
+///
+/// Only the points in between the start and end of the arc are returned, not
+/// the points at its boundaries. They are ordered from the start of the arc to
+/// its end.
+///
+/// The points are a subset of those that [`approx_circle`] returns (or their
+/// equivalents, outside of `0..2π`), so approximations of neighboring arcs on
+/// the same circle match up.
+pub fn approx_arc(
+    arc: &Arc<3>,
+    tolerance: Tolerance,
+    out: &mut Vec<Local<Point<1>>>,
+) {
+    let radius = arc.circle.a.magnitude();
+    let n = number_of_vertices_for_circle(tolerance, radius);
+    let step = Scalar::PI * 2. / n as f64;
+
+    for angle in arc.grid_angles(step) {
+        let point = arc.circle.point_from_circle_coords([angle]);
+        out.push(Local::new([angle], point));
+    }
+}
+
+pub(super) fn number_of_vertices_for_circle(
+    tolerance: Tolerance,
+    radius: Scalar,
+) -> u64 {
+    // Tolerances that are large compared to the radius would lead to an
+    // invalid argument for `acos`. The minimum number of vertices is the
+    // correct result in that case anyway.
+    let ratio = min(tolerance.inner() / radius, Scalar::ONE);
+
+    let n = (Scalar::PI / (Scalar::ONE - ratio).acos())
         .ceil()
         .into_u64();
 
@@ -67,15 +92,43 @@ fn number_of_vertices_for_circle(tolerance: Tolerance, radius: Scalar) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Scalar;
+    use fj_math::{Arc, Circle, Point, Scalar, Vector};
 
     use crate::algorithms::Tolerance;
 
+    #[test]
+    fn approx_arc() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(0.3)?;
+        let circle = Circle {
+            center: Point::origin(),
+            a: Vector::unit_x(),
+            b: Vector::unit_y(),
+        };
+
+        let mut full = Vec::new();
+        super::approx_circle(&circle, tolerance, &mut full);
+
+        let full_turn = Scalar::PI * 2.;
+
+        let mut forward = Vec::new();
+        let arc = Arc::from_circle_coords(circle, [Scalar::ZERO, full_turn]);
+        super::approx_arc(&arc, tolerance, &mut forward);
+        assert_eq!(forward, full[1..]);
+
+        let mut backward = Vec::new();
+        super::approx_arc(&arc.reverse(), tolerance, &mut backward);
+        forward.reverse();
+        assert_eq!(backward, forward);
+
+        Ok(())
+    }
+
     #[test]
     fn number_of_vertices_for_circle() {
         verify_result(50., 100., 3);
         verify_result(10., 100., 7);
         verify_result(1., 100., 23);
+        verify_result(500., 100., 3);
 
         fn verify_result(
             tolerance: impl Into<Tolerance>,
@@ -2,7 +2,7 @@ use fj_math::{Point, Segment};
 
 use crate::{local::Local, objects::Cycle};
 
-use super::{curves::approx_curve, edges::approx_edge, Tolerance};
+use super::{ApproxCache, EdgeApprox, Tolerance};
 
 /// An approximation of a [`Cycle`]
 #[derive(Debug, Eq, PartialEq, Hash)]
@@ -17,12 +17,30 @@ impl CycleApprox {
     /// `tolerance` defines how far the approximation is allowed to deviate from
     /// the actual face.
     pub fn new(cycle: &Cycle, tolerance: Tolerance) -> Self {
+        Self::with_cache(cycle, tolerance, None)
+    }
+
+    /// Compute the approximation of a cycle, using a cache
+    ///
+    /// See [`EdgeApprox::cached`].
+    pub fn cached(
+        cycle: &Cycle,
+        tolerance: Tolerance,
+        cache: &ApproxCache,
+    ) -> Self {
+        Self::with_cache(cycle, tolerance, Some(cache))
+    }
+
+    pub(super) fn with_cache(
+        cycle: &Cycle,
+        tolerance: Tolerance,
+        cache: Option<&ApproxCache>,
+    ) -> Self {
         let mut points = Vec::new();
 
         for edge in &cycle.edges {
-            let mut edge_points = Vec::new();
-            approx_curve(&edge.curve(), tolerance, &mut edge_points);
-            approx_edge(edge.vertices, &mut edge_points);
+            let edge_points =
+                EdgeApprox::with_cache(edge, tolerance, cache).points;
 
             points.extend(edge_points.into_iter().map(|point| {
                 let local =
@@ -35,6 +53,15 @@ impl CycleApprox {
         // could lead to subtly different surface coordinates.
         points.dedup_by(|a, b| a.global() == b.global());
 
+        // The last point closes the cycle, and refers to the same vertex as
+        // the first one. For the same reason as above, its surface coordinates
+        // could be subtly different, so make sure they're identical.
+        if let [first, .., last] = points.as_mut_slice() {
+            if first.global() == last.global() {
+                *last = *first;
+            }
+        }
+
         Self { points }
     }
 
@@ -1,6 +1,79 @@
 use fj_math::Point;
 
-use crate::{local::Local, objects::VerticesOfEdge};
+use crate::{
+    local::Local,
+    objects::{Curve, Edge, VerticesOfEdge},
+};
+
+use super::{
+    curves::{approx_arc, approx_curve},
+    ApproxCache, Tolerance,
+};
+
+/// An approximation of an [`Edge`]
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct EdgeApprox {
+    /// The points that approximate the edge
+    ///
+    /// If the edge is continuous (i.e. connected to itself), the first point
+    /// is repeated at the end.
+    pub points: Vec<Local<Point<1>>>,
+}
+
+impl EdgeApprox {
+    /// Compute the approximation of an edge
+    ///
+    /// `tolerance` defines how far the approximation is allowed to deviate from
+    /// the actual edge.
+    pub fn new(edge: &Edge, tolerance: Tolerance) -> Self {
+        Self::with_cache(edge, tolerance, None)
+    }
+
+    /// Compute the approximation of an edge, using a cache
+    ///
+    /// Same as [`EdgeApprox::new`], except that the approximation of the
+    /// edge's curve is taken from `cache`, if it has been computed before.
+    pub fn cached(
+        edge: &Edge,
+        tolerance: Tolerance,
+        cache: &ApproxCache,
+    ) -> Self {
+        Self::with_cache(edge, tolerance, Some(cache))
+    }
+
+    pub(super) fn with_cache(
+        edge: &Edge,
+        tolerance: Tolerance,
+        cache: Option<&ApproxCache>,
+    ) -> Self {
+        let approx = || {
+            let mut points = Vec::new();
+            match (edge.arc(), edge.vertices()) {
+                (Some(arc), Some(_)) => {
+                    approx_arc(&arc, tolerance, &mut points);
+                }
+                _ => approx_curve(&edge.curve(), tolerance, &mut points),
+            }
+            points
+        };
+
+        let curve = edge.curve.global();
+        let mut points = match (cache, *curve) {
+            // Lines are approximated by their vertices alone, so there's
+            // nothing to cache.
+            (Some(cache), Curve::Circle(_)) => {
+                let section = edge
+                    .vertices()
+                    .map(|vertices| vertices.map(|vertex| vertex.position().t));
+                cache.curve(&curve, tolerance, section, approx)
+            }
+            _ => approx(),
+        };
+        approx_edge(edge.vertices.clone(), &mut points);
+
+        Self { points }
+    }
+}
 
 pub fn approx_edge(
     vertices: VerticesOfEdge,
@@ -38,18 +111,24 @@ mod test {
 
     use crate::{
         local::Local,
-        objects::{GlobalVertex, Vertex, VerticesOfEdge},
+        objects::{GlobalVertex, Stores, Vertex, VerticesOfEdge},
     };
 
     #[test]
     fn approx_edge() {
+        let stores = Stores::new();
+
         let a = Point::from([1., 2., 3.]);
         let b = Point::from([2., 3., 5.]);
         let c = Point::from([3., 5., 8.]);
         let d = Point::from([5., 8., 13.]);
 
-        let v1 = GlobalVertex::from_position(a);
-        let v2 = GlobalVertex::from_position(d);
+        let v1 = stores
+            .global_vertices
+            .insert(GlobalVertex::from_position(a));
+        let v2 = stores
+            .global_vertices
+            .insert(GlobalVertex::from_position(d));
 
         let vertices = VerticesOfEdge::from_vertices([
             Vertex::new(Point::from([0.]), v1),
@@ -1,10 +1,18 @@
 use std::collections::HashSet;
 
-use fj_math::Point;
+use fj_interop::debug::DebugInfo;
+use fj_math::{Aabb, Point, Scalar, Segment};
 
-use crate::{local::Local, objects::Face};
+use crate::{
+    algorithms::triangulate::polygon::Polygon,
+    local::Local,
+    objects::{Curve, Face, Surface},
+};
 
-use super::{CycleApprox, Tolerance};
+use super::{
+    curves::number_of_vertices_for_circle, grid::Grid, ApproxCache,
+    CycleApprox, Tolerance,
+};
 
 /// An approximation of a [`Face`]
 #[derive(Debug, PartialEq)]
@@ -28,53 +36,282 @@ impl FaceApprox {
     /// `tolerance` defines how far the approximation is allowed to deviate from
     /// the actual face.
     pub fn new(face: &Face, tolerance: Tolerance) -> Self {
-        // Curved faces whose curvature is not fully defined by their edges
-        // are not supported yet. For that reason, we can fully ignore `face`'s
-        // `surface` field and just pass the edges to `Self::for_edges`.
-        //
-        // An example of a curved face that is supported, is the cylinder. Its
-        // curvature is fully defined be the edges (circles) that border it. The
-        // circle approximations are sufficient to triangulate the surface.
-        //
-        // An example of a curved face that is currently not supported, and thus
-        // doesn't need to be handled here, is a sphere. A spherical face would
-        // would need to provide its own approximation, as the edges that bound
-        // it have nothing to do with its curvature.
+        Self::with_cache(face, tolerance, None)
+    }
 
-        let mut points = HashSet::new();
-        let mut exteriors = Vec::new();
-        let mut interiors = HashSet::new();
+    /// Compute the approximation of a face, using a cache
+    ///
+    /// See [`EdgeApprox::cached`].
+    ///
+    /// [`EdgeApprox::cached`]: super::EdgeApprox::cached
+    pub fn cached(
+        face: &Face,
+        tolerance: Tolerance,
+        cache: &ApproxCache,
+    ) -> Self {
+        Self::with_cache(face, tolerance, Some(cache))
+    }
 
-        for cycle in face.exteriors() {
-            let cycle = CycleApprox::new(&cycle, tolerance);
+    pub(crate) fn with_cache(
+        face: &Face,
+        tolerance: Tolerance,
+        cache: Option<&ApproxCache>,
+    ) -> Self {
+        // The only curved surfaces that are currently supported, are those
+        // swept from a circle, like the cylinder. Their curvature is not fully
+        // defined by the edges that bound a face: A face with a hole, or a face
+        // whose edges are straight lines in surface coordinates, can span a
+        // large part of the surface without any points in between. For those
+        // faces, additional points are added to the approximation, along the
+        // cycles and in the interior of the face.
+        //
+        // Surfaces whose curvature isn't uniform along one axis, like a sphere,
+        // aren't supported yet, and don't need to be handled here.
+        let curvature = Curvature::of(&face.surface(), tolerance);
 
-            points.extend(cycle.points.iter().copied());
-            exteriors.push(cycle);
+        let (mut exterior, mut interiors) =
+            approx_cycles(face, tolerance, cache);
+        if let Some(curvature) = &curvature {
+            curvature.refine_cycle(&mut exterior);
+            for interior in &mut interiors {
+                curvature.refine_cycle(interior);
+            }
         }
-        for cycle in face.interiors() {
-            let cycle = CycleApprox::new(&cycle, tolerance);
 
-            points.extend(cycle.points.iter().copied());
-            interiors.insert(cycle);
+        let mut approx = Self::from_cycles(exterior, interiors);
+        if let Some(curvature) = &curvature {
+            approx.points.extend(
+                curvature.interior_points(&approx.exterior, &approx.interiors),
+            );
         }
 
-        // Only polygons with exactly one exterior cycle are supported.
-        //
-        // See this issue for some background:
-        // https://github.com/hannobraun/Fornjot/issues/250
-        let exterior = exteriors
-            .pop()
-            .expect("Can't approximate face without exterior cycle");
-        assert!(
-            exteriors.is_empty(),
-            "Approximation only supports faces with one exterior cycle",
+        approx
+    }
+
+    /// Compute the approximation of a face, sampling its surface on a grid
+    ///
+    /// Unlike [`FaceApprox::new`], which spreads the points in the interior of
+    /// a curved face evenly, this places them on a structured grid of
+    /// isolines of the surface (lines of constant u or v). The spacing of the
+    /// grid lines adapts to the curvature of the surface, and the cycles are
+    /// split where they cross a grid line. This results in a more regular
+    /// triangulation.
+    ///
+    /// Returns `None`, if the surface is flat within the face's boundary, as
+    /// there is no need for a grid then.
+    pub fn on_grid(face: &Face, tolerance: Tolerance) -> Option<Self> {
+        Self::on_grid_with_cache(face, tolerance, None)
+    }
+
+    pub(crate) fn on_grid_with_cache(
+        face: &Face,
+        tolerance: Tolerance,
+        cache: Option<&ApproxCache>,
+    ) -> Option<Self> {
+        let (mut exterior, mut interiors) =
+            approx_cycles(face, tolerance, cache);
+
+        let aabb = Aabb::<2>::from_points(
+            exterior.points.iter().map(|point| point.local()),
         );
+        let grid = Grid::new(face.surface(), aabb, tolerance)?;
+
+        grid.refine_cycle(&mut exterior);
+        for interior in &mut interiors {
+            grid.refine_cycle(interior);
+        }
+
+        let mut approx = Self::from_cycles(exterior, interiors);
+        approx
+            .points
+            .extend(grid.interior_points(&approx.exterior, &approx.interiors));
+
+        Some(approx)
+    }
+
+    fn from_cycles(exterior: CycleApprox, interiors: Vec<CycleApprox>) -> Self {
+        let mut points = HashSet::new();
+
+        for cycle in Some(&exterior).into_iter().chain(&interiors) {
+            points.extend(cycle.points.iter().copied());
+        }
 
         Self {
             points,
             exterior,
-            interiors,
+            interiors: interiors.into_iter().collect(),
+        }
+    }
+}
+
+/// Approximate the exterior and interior cycles of a face
+fn approx_cycles(
+    face: &Face,
+    tolerance: Tolerance,
+    cache: Option<&ApproxCache>,
+) -> (CycleApprox, Vec<CycleApprox>) {
+    let mut exteriors: Vec<_> = face
+        .exteriors()
+        .map(|cycle| CycleApprox::with_cache(&cycle, tolerance, cache))
+        .collect();
+    let interiors = face
+        .interiors()
+        .map(|cycle| CycleApprox::with_cache(&cycle, tolerance, cache))
+        .collect();
+
+    // Only polygons with exactly one exterior cycle are supported.
+    //
+    // See this issue for some background:
+    // https://github.com/hannobraun/Fornjot/issues/250
+    let exterior = exteriors
+        .pop()
+        .expect("Can't approximate face without exterior cycle");
+    assert!(
+        exteriors.is_empty(),
+        "Approximation only supports faces with one exterior cycle",
+    );
+
+    (exterior, interiors)
+}
+
+/// Select the points that are in the interior of a face
+///
+/// Points that are close to one of the cycles, closer than `margin` in surface
+/// coordinates, are left out, to prevent degenerate triangles.
+pub(super) fn interior_points(
+    surface: Surface,
+    exterior: &CycleApprox,
+    interiors: &HashSet<CycleApprox>,
+    candidates: impl IntoIterator<Item = Point<2>>,
+    margin: Scalar,
+) -> Vec<Local<Point<2>>> {
+    let local_points = |cycle: &CycleApprox| -> Vec<Point<2>> {
+        cycle.points.iter().map(|point| point.local()).collect()
+    };
+
+    let polygon = Polygon::new(surface)
+        .with_exterior(local_points(exterior))
+        .with_interiors(interiors.iter().map(local_points));
+    let segments: Vec<_> = Some(exterior)
+        .into_iter()
+        .chain(interiors)
+        .flat_map(|cycle| {
+            local_points(cycle)
+                .windows(2)
+                .map(|segment| {
+                    Segment::from([segment[0], segment[1]].map(Point::to_xyz))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    // The debug info generated by the containment check is of no interest
+    // here, as these points are not part of the face's boundary.
+    let mut debug_info = DebugInfo::new();
+
+    candidates
+        .into_iter()
+        .filter(|&local| {
+            let is_near_boundary = segments.iter().any(|segment| {
+                segment.distance_to_point(local.to_xyz()) < margin
+            });
+
+            !is_near_boundary && polygon.contains_point(local, &mut debug_info)
+        })
+        .map(|local| {
+            Local::new(local, surface.point_from_surface_coords(local))
+        })
+        .collect()
+}
+
+/// The curvature of a surface that was swept from a circle
+///
+/// Such a surface curves along its u axis. Any part of the approximation that
+/// spans more than `du` along that axis deviates from the surface by more than
+/// the tolerance.
+struct Curvature {
+    surface: Surface,
+    du: Scalar,
+    dv: Scalar,
+}
+
+impl Curvature {
+    fn of(surface: &Surface, tolerance: Tolerance) -> Option<Self> {
+        let Surface::SweptCurve(swept) = surface;
+
+        let radius = match swept.curve {
+            Curve::Circle(circle) => circle.a.magnitude(),
+            Curve::Line(_) => return None,
+        };
+
+        let n = number_of_vertices_for_circle(tolerance, radius);
+        let du = Scalar::PI * 2. / n as f64;
+
+        // Space the interior points along the v axis, so they are about as far
+        // apart in model space as they are along the u axis.
+        let dv = du * radius / swept.path.magnitude();
+
+        Some(Self {
+            surface: *surface,
+            du,
+            dv,
+        })
+    }
+
+    /// Subdivide the segments of a cycle that span too far along the u axis
+    fn refine_cycle(&self, cycle: &mut CycleApprox) {
+        let mut points = Vec::new();
+
+        for segment in cycle.points.windows(2) {
+            // This can't panic, as we passed `2` to `windows`. Can be cleaned
+            // up, once `array_windows` is stable.
+            let [a, b] = [segment[0], segment[1]];
+            points.push(a);
+
+            // The points of circle approximations are exactly `du` apart.
+            // Allow for some floating-point noise, so those segments aren't
+            // subdivided.
+            let span = (b.local().u - a.local().u).abs() / self.du;
+            let n = (span - Scalar::from(1e-6)).ceil().into_u64();
+
+            for i in 1..n {
+                let local =
+                    a.local() + (b.local() - a.local()) * (i as f64 / n as f64);
+                let global = self.surface.point_from_surface_coords(local);
+
+                points.push(Local::new(local, global));
+            }
+        }
+        points.extend(cycle.points.last().copied());
+
+        cycle.points = points;
+    }
+
+    /// Create points on a grid in the interior of the face
+    fn interior_points(
+        &self,
+        exterior: &CycleApprox,
+        interiors: &HashSet<CycleApprox>,
+    ) -> Vec<Local<Point<2>>> {
+        let aabb = Aabb::<2>::from_points(
+            exterior.points.iter().map(|point| point.local()),
+        );
+        let margin = if self.du < self.dv { self.du } else { self.dv } / 2.;
+
+        let mut candidates = Vec::new();
+
+        let mut u = aabb.min.u + self.du;
+        while u < aabb.max.u {
+            let mut v = aabb.min.v + self.dv;
+            while v < aabb.max.v {
+                candidates.push(Point::from([u, v]));
+                v += self.dv;
+            }
+
+            u += self.du;
         }
+
+        interior_points(self.surface, exterior, interiors, candidates, margin)
     }
 }
 
@@ -85,13 +322,15 @@ mod tests {
 
     use crate::{
         local::Local,
-        objects::{Face, Surface},
+        objects::{Face, Stores, Surface},
     };
 
     use super::{CycleApprox, FaceApprox, Tolerance};
 
     #[test]
     fn for_face_closed() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
         // Test a closed face, i.e. one that is completely encircled by edges.
 
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
@@ -106,7 +345,7 @@ mod tests {
         let g = Point::from([2., 2.]);
         let h = Point::from([1., 2.]);
 
-        let face = Face::builder(Surface::xy_plane())
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([a, b, c, d])
             .with_interior_polygon([e, f, g, h])
             .build();
@@ -127,7 +366,7 @@ mod tests {
                 points: vec![a, b, c, d, a],
             },
             interiors: set![CycleApprox {
-                points: vec![e, f, g, h, e],
+                points: vec![h, g, f, e, h],
             }],
         };
 
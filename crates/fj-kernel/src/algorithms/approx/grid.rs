@@ -0,0 +1,280 @@
+use std::collections::HashSet;
+
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::{local::Local, objects::Surface};
+
+use super::{faces::interior_points, CycleApprox, Tolerance};
+
+/// The maximum ratio between the side lengths of a grid cell
+///
+/// Cells are measured in model space. Without this limit, a surface that only
+/// curves in one direction would be sampled by long and thin cells.
+const MAX_ASPECT_RATIO: f64 = 2.;
+
+/// The maximum number of times an interval of the grid is split in half
+///
+/// Prevents endless subdivision, in case the tolerance can't be met.
+const MAX_DEPTH: u32 = 16;
+
+/// A structured grid of isolines on a surface
+///
+/// The lines of the grid are lines of constant u or v in surface coordinates.
+/// Their spacing is chosen adaptively, by splitting the intervals between them
+/// in half, until the surface deviates from the straight lines between grid
+/// points by no more than a fraction of the tolerance.
+pub struct Grid {
+    surface: Surface,
+    tolerance: Tolerance,
+    us: Vec<Scalar>,
+    vs: Vec<Scalar>,
+}
+
+impl Grid {
+    /// Create a grid that covers an area of a surface
+    ///
+    /// Returns `None`, if the surface is flat within that area.
+    pub fn new(
+        surface: Surface,
+        aabb: Aabb<2>,
+        tolerance: Tolerance,
+    ) -> Option<Self> {
+        let [u0, v0] = aabb.min.coords.components;
+        let [u1, v1] = aabb.max.coords.components;
+
+        let point = |u: Scalar, v: Scalar| {
+            surface.point_from_surface_coords(Point::from([u, v]))
+        };
+        let deviation = |a: Point<3>, center: Point<3>, b: Point<3>| {
+            (center - (a + (b - a) / 2.)).magnitude()
+        };
+
+        // Sample the deviation at the borders and in the middle of the area.
+        // This is sufficient for surfaces that were swept along a straight
+        // path.
+        let deviation_u = |a: Scalar, b: Scalar| {
+            [v0, (v0 + v1) / 2., v1]
+                .into_iter()
+                .map(|v| {
+                    deviation(point(a, v), point((a + b) / 2., v), point(b, v))
+                })
+                .max()
+                .unwrap_or(Scalar::ZERO)
+        };
+        let deviation_v = |a: Scalar, b: Scalar| {
+            [u0, (u0 + u1) / 2., u1]
+                .into_iter()
+                .map(|u| {
+                    deviation(point(u, a), point(u, (a + b) / 2.), point(u, b))
+                })
+                .max()
+                .unwrap_or(Scalar::ZERO)
+        };
+
+        // Triangles near the boundary of a face can span more than one cell.
+        // The deviation grows with the square of the distance between the
+        // points, so this makes sure that triangles spanning two cells are
+        // still within the tolerance.
+        let max_deviation = tolerance.inner() / 4.;
+
+        let us = subdivide(&[u0, u1], |a, b| deviation_u(a, b) > max_deviation);
+        let vs = subdivide(&[v0, v1], |a, b| deviation_v(a, b) > max_deviation);
+
+        if us.len() == 2 && vs.len() == 2 {
+            return None;
+        }
+
+        let length_u =
+            |a: Scalar, b: Scalar| (point(b, v0) - point(a, v0)).magnitude();
+        let length_v =
+            |a: Scalar, b: Scalar| (point(u0, b) - point(u0, a)).magnitude();
+
+        let max_u = max_length(&us, length_u);
+        let vs =
+            subdivide(&vs, |a, b| length_v(a, b) > max_u * MAX_ASPECT_RATIO);
+        let max_v = max_length(&vs, length_v);
+        let us =
+            subdivide(&us, |a, b| length_u(a, b) > max_v * MAX_ASPECT_RATIO);
+
+        Some(Self {
+            surface,
+            tolerance,
+            us,
+            vs,
+        })
+    }
+
+    /// Split the segments of a cycle where they cross a grid line
+    ///
+    /// If a segment is close enough to the surface, the new points are placed
+    /// on the segment, so they are also part of the approximation of any
+    /// neighboring face. Otherwise, they are placed on the surface.
+    pub fn refine_cycle(&self, cycle: &mut CycleApprox) {
+        let mut points = Vec::new();
+
+        for segment in cycle.points.windows(2) {
+            // This can't panic, as we passed `2` to `windows`. Can be cleaned
+            // up, once `array_windows` is stable.
+            let [a, b] = [segment[0], segment[1]];
+            points.push(a);
+
+            let [a_local, b_local] = [a.local(), b.local()];
+            let crossings = |lines: &[Scalar], a: Scalar, b: Scalar| {
+                lines
+                    .iter()
+                    .filter(|&&line| {
+                        (a < line && line < b) || (b < line && line < a)
+                    })
+                    .map(|&line| (line - a) / (b - a))
+                    .collect::<Vec<_>>()
+            };
+            let mut ts = crossings(&self.us, a_local.u, b_local.u);
+            ts.extend(crossings(&self.vs, a_local.v, b_local.v));
+            ts.sort();
+            ts.dedup();
+
+            let center = self
+                .surface
+                .point_from_surface_coords(a_local + (b_local - a_local) / 2.);
+            let is_on_surface = (center
+                - (a.global() + (b.global() - a.global()) / 2.))
+                .magnitude()
+                <= self.tolerance.inner();
+
+            for t in ts {
+                let local = a_local + (b_local - a_local) * t;
+                let global = if is_on_surface {
+                    a.global() + (b.global() - a.global()) * t
+                } else {
+                    self.surface.point_from_surface_coords(local)
+                };
+
+                points.push(Local::new(local, global));
+            }
+        }
+        points.extend(cycle.points.last().copied());
+
+        cycle.points = points;
+    }
+
+    /// Create points where the grid lines cross in the interior of a face
+    pub fn interior_points(
+        &self,
+        exterior: &CycleApprox,
+        interiors: &HashSet<CycleApprox>,
+    ) -> Vec<Local<Point<2>>> {
+        let inner = |lines: &[Scalar]| lines[1..lines.len() - 1].to_vec();
+
+        let mut candidates = Vec::new();
+        for &u in &inner(&self.us) {
+            for &v in &inner(&self.vs) {
+                candidates.push(Point::from([u, v]));
+            }
+        }
+
+        let margin = [&self.us, &self.vs]
+            .into_iter()
+            .flat_map(|lines| lines.windows(2).map(|w| w[1] - w[0]))
+            .min()
+            .unwrap_or(Scalar::ZERO)
+            / 2.;
+
+        interior_points(self.surface, exterior, interiors, candidates, margin)
+    }
+}
+
+/// Split intervals in half, until they no longer need to be split
+///
+/// `lines` are the boundaries of the intervals, in ascending order.
+fn subdivide(
+    lines: &[Scalar],
+    needs_split: impl Fn(Scalar, Scalar) -> bool,
+) -> Vec<Scalar> {
+    fn bisect(
+        a: Scalar,
+        b: Scalar,
+        depth: u32,
+        needs_split: &impl Fn(Scalar, Scalar) -> bool,
+        out: &mut Vec<Scalar>,
+    ) {
+        if depth < MAX_DEPTH && needs_split(a, b) {
+            let center = (a + b) / 2.;
+            bisect(a, center, depth + 1, needs_split, out);
+            bisect(center, b, depth + 1, needs_split, out);
+        } else {
+            out.push(b);
+        }
+    }
+
+    let mut result = lines.first().copied().into_iter().collect();
+    for interval in lines.windows(2) {
+        bisect(interval[0], interval[1], 0, &needs_split, &mut result);
+    }
+
+    result
+}
+
+/// Compute the length of the longest interval
+fn max_length(
+    lines: &[Scalar],
+    length: impl Fn(Scalar, Scalar) -> Scalar,
+) -> Scalar {
+    lines
+        .windows(2)
+        .map(|interval| length(interval[0], interval[1]))
+        .max()
+        .unwrap_or(Scalar::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Circle, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Curve, Surface, SweptCurve},
+    };
+
+    use super::{Grid, MAX_ASPECT_RATIO};
+
+    #[test]
+    fn grid() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let aabb = Aabb {
+            min: Point::from([0., 0.]),
+            max: Point::from([Scalar::PI.into_f64(), 1.]),
+        };
+
+        assert!(Grid::new(Surface::xy_plane(), aabb, tolerance).is_none());
+
+        let cylinder = Surface::SweptCurve(SweptCurve {
+            curve: Curve::Circle(Circle {
+                center: Point::origin(),
+                a: Vector::unit_x(),
+                b: Vector::unit_y(),
+            }),
+            path: Vector::unit_z() * 10.,
+        });
+        let grid =
+            Grid::new(cylinder, aabb, tolerance).expect("Cylinder is not flat");
+
+        // The cylinder curves uniformly along u, and not at all along v.
+        let du = grid.us[1] - grid.us[0];
+        for interval in grid.us.windows(2) {
+            assert!(
+                (interval[1] - interval[0] - du).abs() < Scalar::from(1e-9)
+            );
+        }
+        let max_deviation = tolerance.inner() / 4.;
+        assert!(Scalar::ONE - (du / 2.).cos() <= max_deviation);
+        assert!(Scalar::ONE - du.cos() > max_deviation);
+
+        // The grid lines along v are only there to limit the aspect ratio.
+        let chord = (du / 2.).sin_cos().0 * 2.;
+        let dv = (grid.vs[1] - grid.vs[0]) * 10.;
+        assert!(dv <= chord * MAX_ASPECT_RATIO);
+        assert!(dv * 2. > chord * MAX_ASPECT_RATIO);
+
+        Ok(())
+    }
+}
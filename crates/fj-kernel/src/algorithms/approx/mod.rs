@@ -1,11 +1,17 @@
+mod cache;
 mod curves;
 mod cycles;
 mod edges;
 mod faces;
+mod grid;
 mod tolerance;
 
+pub(super) use self::curves::approx_curve;
+
 pub use self::{
+    cache::ApproxCache,
     cycles::CycleApprox,
+    edges::EdgeApprox,
     faces::FaceApprox,
-    tolerance::{InvalidTolerance, Tolerance},
+    tolerance::{InvalidTolerance, Tolerance, ToleranceStack},
 };
@@ -52,7 +52,85 @@ where
     }
 }
 
+/// The tolerances that apply at some point in a tree of operations
+///
+/// Starts out with a base tolerance, that applies to the whole tree. Parts of
+/// the tree can [tighten](Self::tighten) the tolerance, which then applies to
+/// everything below them. Tolerances can only be tightened, never loosened.
+///
+/// The stack is passed down the tree by value. This means that entering a
+/// part of the tree pushes a tolerance onto the stack, and leaving it pops that
+/// tolerance again.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ToleranceStack {
+    base: Tolerance,
+    current: Tolerance,
+}
+
+impl ToleranceStack {
+    /// Construct a `ToleranceStack` from a base tolerance
+    pub fn new(base: Tolerance) -> Self {
+        Self {
+            base,
+            current: base,
+        }
+    }
+
+    /// Return the base tolerance, which applies to the whole tree
+    pub fn base(&self) -> Tolerance {
+        self.base
+    }
+
+    /// Return the tolerance that applies at this point in the tree
+    pub fn current(&self) -> Tolerance {
+        self.current
+    }
+
+    /// Indicate whether the current tolerance is tighter than the base one
+    pub fn is_tightened(&self) -> bool {
+        self.current < self.base
+    }
+
+    /// Push a tolerance onto the stack
+    ///
+    /// The returned stack's current tolerance is the tighter one of the given
+    /// tolerance and the current tolerance of `self`.
+    #[must_use]
+    pub fn tighten(self, tolerance: Tolerance) -> Self {
+        Self {
+            current: self.current.min(tolerance),
+            ..self
+        }
+    }
+}
+
+impl From<Tolerance> for ToleranceStack {
+    fn from(base: Tolerance) -> Self {
+        Self::new(base)
+    }
+}
+
 /// Error converting scalar to tolerance
 #[derive(Debug, thiserror::Error)]
 #[error("Invalid tolerance ({0}); must be above zero")]
 pub struct InvalidTolerance(Scalar);
+
+#[cfg(test)]
+mod tests {
+    use super::{Tolerance, ToleranceStack};
+
+    #[test]
+    fn tighten() {
+        let stack = ToleranceStack::new(Tolerance::from(1.));
+        assert!(!stack.is_tightened());
+
+        let tightened = stack.tighten(Tolerance::from(0.1));
+        assert_eq!(tightened.current(), Tolerance::from(0.1));
+        assert_eq!(tightened.base(), Tolerance::from(1.));
+        assert!(tightened.is_tightened());
+
+        // A looser tolerance has no effect.
+        let loosened = tightened.tighten(Tolerance::from(0.5));
+        assert_eq!(loosened.current(), Tolerance::from(0.1));
+    }
+}
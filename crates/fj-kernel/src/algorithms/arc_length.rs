@@ -0,0 +1,281 @@
+use fj_math::{Point, Scalar};
+
+use crate::objects::{Curve, Edge};
+
+use super::Tolerance;
+
+/// The arc-length parameterization of a section of a curve
+///
+/// Curve coordinates don't generally correspond to distances along the curve.
+/// For a circle, they are angles, for example. This type maps between curve
+/// coordinates and the distance along the curve, measured from the start of
+/// the section. This makes it possible to place points at even distances along
+/// a curve, for example to space out features along an edge.
+///
+/// Lines are parameterized exactly. All other curves are approximated by a
+/// polyline, which is refined until its length is within the tolerance of the
+/// actual length of the curve.
+#[derive(Clone, Debug)]
+pub struct ArcLength<const D: usize> {
+    curve: Curve<D>,
+
+    /// Curve coordinates, and the distance along the curve at which they are
+    ///
+    /// The distances are in ascending order, starting at zero.
+    samples: Vec<[Scalar; 2]>,
+}
+
+impl<const D: usize> ArcLength<D> {
+    /// Parameterize the section of `curve` between two curve coordinates
+    ///
+    /// The section runs from the first coordinate to the second one, which may
+    /// be smaller than the first.
+    pub fn new(
+        curve: &Curve<D>,
+        range: [impl Into<Scalar>; 2],
+        tolerance: Tolerance,
+    ) -> Self {
+        let [start, end] = range.map(Into::into);
+
+        let samples = match curve {
+            Curve::Line(_) => sample(curve, [start, end], 1),
+            Curve::Circle(_) => {
+                // Start with about 16 segments per full turn, then double the
+                // number of segments, until the length no longer changes by
+                // more than the tolerance. The length of the polyline grows
+                // towards the actual length, and each doubling reduces the
+                // difference to about a quarter, so the remaining error is
+                // smaller than the last change.
+                let span = (end - start).abs();
+                let mut n = (span / (Scalar::PI / 8.)).ceil().into_u64().max(1);
+
+                let mut samples = sample(curve, [start, end], n);
+                while n < MAX_SEGMENTS {
+                    n *= 2;
+
+                    let refined = sample(curve, [start, end], n);
+                    let change = length_of(&refined) - length_of(&samples);
+                    samples = refined;
+
+                    if change.abs() <= tolerance.inner() {
+                        break;
+                    }
+                }
+
+                samples
+            }
+        };
+
+        Self {
+            curve: *curve,
+            samples,
+        }
+    }
+
+    /// Return the length of the section
+    pub fn length(&self) -> Scalar {
+        length_of(&self.samples)
+    }
+
+    /// Convert a distance along the section into curve coordinates
+    ///
+    /// Distances outside of `0..=length` are clamped to that range.
+    pub fn curve_coords_at(&self, distance: impl Into<Scalar>) -> Point<1> {
+        let distance = distance.into().max(Scalar::ZERO).min(self.length());
+
+        // The first sample whose distance is not smaller.
+        let i = self
+            .samples
+            .partition_point(|[_, s]| *s < distance)
+            .clamp(1, self.samples.len() - 1);
+
+        let [t_a, s_a] = self.samples[i - 1];
+        let [t_b, s_b] = self.samples[i];
+
+        let t = if s_b > s_a {
+            t_a + (t_b - t_a) * ((distance - s_a) / (s_b - s_a))
+        } else {
+            t_a
+        };
+
+        Point::from([t])
+    }
+
+    /// Convert a distance along the section into a point on the curve
+    pub fn point_at(&self, distance: impl Into<Scalar>) -> Point<D> {
+        self.curve
+            .point_from_curve_coords(self.curve_coords_at(distance))
+    }
+
+    /// Convert curve coordinates into the distance along the section
+    ///
+    /// Coordinates outside of the section are clamped to it.
+    pub fn distance_at(&self, point: impl Into<Point<1>>) -> Scalar {
+        let t = point.into().t;
+
+        let [start, _] = self.samples[0];
+        let [end, _] = self.samples[self.samples.len() - 1];
+
+        // Work with coordinates that ascend along the section, regardless of
+        // its direction.
+        let sign = if end < start {
+            -Scalar::ONE
+        } else {
+            Scalar::ONE
+        };
+        let t = (t * sign).max(start * sign).min(end * sign);
+
+        let i = self
+            .samples
+            .partition_point(|[t_sample, _]| *t_sample * sign < t)
+            .clamp(1, self.samples.len() - 1);
+
+        let [t_a, s_a] = self.samples[i - 1];
+        let [t_b, s_b] = self.samples[i];
+
+        let [t_a, t_b] = [t_a * sign, t_b * sign];
+        if t_b > t_a {
+            s_a + (s_b - s_a) * ((t - t_a) / (t_b - t_a))
+        } else {
+            s_a
+        }
+    }
+
+    /// Divide the section into `n` parts of equal length
+    ///
+    /// Returns the curve coordinates of the `n + 1` points that bound those
+    /// parts, including the start and the end of the section.
+    pub fn evenly_spaced(&self, n: usize) -> Vec<Point<1>> {
+        let n = n.max(1);
+        let length = self.length();
+
+        (0..=n)
+            .map(|i| self.curve_coords_at(length * (i as f64 / n as f64)))
+            .collect()
+    }
+}
+
+impl ArcLength<3> {
+    /// Parameterize an edge
+    ///
+    /// The section runs from the edge's first vertex to its second. Edges
+    /// without vertices cover their whole curve, starting at curve coordinate
+    /// zero.
+    pub fn of_edge(edge: &Edge, tolerance: Tolerance) -> Self {
+        let range = match edge.vertices() {
+            Some(vertices) => vertices.map(|vertex| vertex.position().t),
+            None => [Scalar::ZERO, Scalar::PI * 2.],
+        };
+
+        Self::new(&edge.curve(), range, tolerance)
+    }
+}
+
+/// The maximum number of segments that curves are approximated with
+const MAX_SEGMENTS: u64 = 1 << 20;
+
+/// Sample the section of a curve at `n + 1` evenly spaced curve coordinates
+fn sample<const D: usize>(
+    curve: &Curve<D>,
+    [start, end]: [Scalar; 2],
+    n: u64,
+) -> Vec<[Scalar; 2]> {
+    let mut samples = Vec::new();
+    let mut distance = Scalar::ZERO;
+    let mut previous = curve.point_from_curve_coords([start]);
+
+    for i in 0..=n {
+        let t = start + (end - start) * (i as f64 / n as f64);
+        let point = curve.point_from_curve_coords([t]);
+
+        distance += (point - previous).magnitude();
+        previous = point;
+
+        samples.push([t, distance]);
+    }
+
+    samples
+}
+
+fn length_of(samples: &[[Scalar; 2]]) -> Scalar {
+    samples
+        .last()
+        .map(|[_, length]| *length)
+        .unwrap_or(Scalar::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::{Circle, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Curve, Edge, Stores},
+    };
+
+    use super::ArcLength;
+
+    #[test]
+    fn line() {
+        let curve = Curve::line_from_points([[0., 0.], [2., 0.]]);
+        let arc_length = ArcLength::new(
+            &curve,
+            [0.5, 0.],
+            Tolerance::from_scalar(1.).unwrap(),
+        );
+
+        assert_eq!(arc_length.length(), Scalar::ONE);
+        assert_eq!(arc_length.curve_coords_at(0.5), Point::from([0.25]));
+        assert_eq!(arc_length.point_at(1.), Point::from([0., 0.]));
+        assert_eq!(arc_length.distance_at([0.]), Scalar::ONE);
+        assert_eq!(arc_length.distance_at([2.]), Scalar::ZERO);
+    }
+
+    #[test]
+    fn circle() {
+        let tolerance = Tolerance::from_scalar(1e-6).unwrap();
+        let eps = 1e-5;
+
+        let stores = Stores::new();
+        let edge = Edge::circle_from_radius(&stores, Scalar::from(2.));
+
+        let arc_length = ArcLength::of_edge(&edge, tolerance);
+        assert!((arc_length.length().into_f64() - 4. * PI).abs() < eps);
+
+        let points = arc_length.evenly_spaced(4);
+        let [start, quarter, end] = [points[0], points[1], points[4]];
+        assert!((quarter.t.into_f64() - PI / 2.).abs() < eps);
+        assert_eq!(start.t, Scalar::ZERO);
+        assert!((end.t.into_f64() - PI * 2.).abs() < eps);
+
+        let distance = arc_length.distance_at([PI]).into_f64();
+        assert!((distance - 2. * PI).abs() < eps);
+    }
+
+    #[test]
+    fn ellipse() {
+        // Not actually a circle, as `a` and `b` have different lengths. Such
+        // curves can result from scaling a circle.
+        let curve = Curve::Circle(Circle {
+            center: Point::origin(),
+            a: Vector::from([2., 0.]),
+            b: Vector::from([0., 1.]),
+        });
+        let tolerance = Tolerance::from_scalar(1e-6).unwrap();
+
+        let arc_length = ArcLength::new(&curve, [0., PI * 2.], tolerance);
+
+        // The circumference of an ellipse with semi-axes 2 and 1.
+        let expected = 9.688_448_220_547_675;
+        assert!((arc_length.length().into_f64() - expected).abs() < 1e-5);
+
+        // Evenly spaced points are closer together in terms of the angle,
+        // where the ellipse is flat.
+        let points = arc_length.evenly_spaced(8);
+        let first = points[1].t - points[0].t;
+        let second = points[2].t - points[1].t;
+        assert!(first > second);
+    }
+}
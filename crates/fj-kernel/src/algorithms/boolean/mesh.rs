@@ -0,0 +1,547 @@
+#[cfg(feature = "exact")]
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use fj_interop::{
+    debug::{DebugCategory, DebugInfo},
+    progress::ProgressHandle,
+};
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{
+        offset::orient, triangulate, triangulate::delaunay, Tolerance,
+        TriangulationConfig,
+    },
+    local::Local,
+    objects::{Face, Solid},
+};
+
+use super::BooleanOperation;
+
+/// Compute a boolean operation on the triangle meshes of two solids
+///
+/// The triangles of both meshes are split along the curves where the meshes
+/// intersect each other. Then each of the resulting triangles is classified as
+/// being inside or outside of the other solid, and the operation determines
+/// which of them are kept.
+///
+/// # Implementation Note
+///
+/// Faces of the solids that are coincident with each other are not handled
+/// specially. Whether they end up in the result depends on rounding errors.
+///
+/// With the `exact` feature, where triangles intersect is decided using exact
+/// arithmetic, which makes the result independent of rounding errors in those
+/// decisions. Points that are only off a plane due to rounding errors in the
+/// input are then no longer considered to be on it.
+pub fn boolean(
+    a: &Solid,
+    b: &Solid,
+    operation: BooleanOperation,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> Solid {
+    let a = mesh(a, tolerance);
+    let b = mesh(b, tolerance);
+
+    // Points that are closer than this are considered to be identical. It's
+    // much smaller than the tolerance, as it's only meant to absorb rounding
+    // errors.
+    let epsilon = tolerance.inner() * 1e-6;
+
+    let mut segments_a = vec![Vec::new(); a.len()];
+    let mut segments_b = vec![Vec::new(); b.len()];
+    for (i, triangle_a) in a.iter().enumerate() {
+        for (j, triangle_b) in b.iter().enumerate() {
+            if !overlap(&triangle_a.aabb, &triangle_b.aabb, epsilon) {
+                continue;
+            }
+
+            if let Some(segment) = intersect(triangle_a, triangle_b, epsilon) {
+                segments_a[i].push(segment);
+                segments_b[j].push(segment);
+
+                debug_info.push_curve(
+                    DebugCategory::Intersection,
+                    segment,
+                    [255, 128, 0, 255],
+                );
+            }
+        }
+    }
+
+    let intersection_points: HashSet<_> = segments_a
+        .iter()
+        .flatten()
+        .flat_map(|segment| *segment)
+        .collect();
+
+    let split_a = split(&a, &segments_a);
+    let split_b = split(&b, &segments_b);
+
+    let inside_a = classify(&split_a, &intersection_points, &b);
+    let inside_b = classify(&split_b, &intersection_points, &a);
+
+    let (keep_a, keep_b) = match operation {
+        BooleanOperation::Union => (false, false),
+        BooleanOperation::Intersection => (true, true),
+        BooleanOperation::Difference => (false, true),
+    };
+
+    let mut triangles = Vec::new();
+    for ((points, color), inside) in split_a.into_iter().zip(inside_a) {
+        if inside == keep_a {
+            triangles.push((Triangle::from_points(points), color));
+        }
+    }
+    for ((points, color), inside) in split_b.into_iter().zip(inside_b) {
+        if inside == keep_b {
+            // The part of the second solid that bounds a difference faces
+            // towards its inside.
+            let points = match operation {
+                BooleanOperation::Difference => {
+                    let [a, b, c] = points;
+                    [a, c, b]
+                }
+                _ => points,
+            };
+
+            triangles.push((Triangle::from_points(points), color));
+        }
+    }
+
+    Solid::from_faces([Face::Triangles(triangles)])
+}
+
+/// A triangle of the mesh of a solid
+struct MeshTriangle {
+    triangle: Triangle<3>,
+    color: [u8; 4],
+    normal: Vector<3>,
+    aabb: Aabb<3>,
+}
+
+impl MeshTriangle {
+    fn points(&self) -> [Point<3>; 3] {
+        self.triangle.points()
+    }
+
+    /// Compute the signed distance of a point from the plane of the triangle
+    #[cfg(not(feature = "exact"))]
+    fn distance(&self, point: Point<3>) -> Scalar {
+        (point - self.points()[0]).dot(&self.normal)
+    }
+
+    /// Determine on which side of the plane of the triangle a point is
+    ///
+    /// Returns zero, if the point is on the plane. Otherwise, the sign of the
+    /// result tells the side, with positive values being in front of the
+    /// triangle. Points closer to the plane than `epsilon` are considered to
+    /// be on it.
+    #[cfg(not(feature = "exact"))]
+    fn side(&self, point: Point<3>, epsilon: Scalar) -> Scalar {
+        let distance = self.distance(point);
+        if distance.abs() <= epsilon {
+            Scalar::ZERO
+        } else {
+            distance
+        }
+    }
+
+    /// Determine on which side of the plane of the triangle a point is
+    ///
+    /// Returns zero, if the point is on the plane. Otherwise, the sign of the
+    /// result tells the side, with positive values being in front of the
+    /// triangle. The result is exact, so only points that are exactly on the
+    /// plane are considered to be on it.
+    #[cfg(feature = "exact")]
+    fn side(&self, point: Point<3>, _: Scalar) -> Scalar {
+        match fj_math::exact::orient3d(self.points(), point).sign() {
+            Ordering::Greater => Scalar::ONE,
+            Ordering::Less => -Scalar::ONE,
+            Ordering::Equal => Scalar::ZERO,
+        }
+    }
+
+    /// Compute the point where an edge crosses the plane of the triangle
+    ///
+    /// Expects the points of the edge to be on opposite sides of the plane.
+    #[cfg(not(feature = "exact"))]
+    fn crossing(&self, [p, q]: [Point<3>; 2]) -> Option<Point<3>> {
+        let [dp, dq] = [p, q].map(|point| self.distance(point));
+        Some(p + (q - p) * (dp / (dp - dq)))
+    }
+
+    /// Compute the point where an edge crosses the plane of the triangle
+    ///
+    /// Expects the points of the edge to be on opposite sides of the plane.
+    #[cfg(feature = "exact")]
+    fn crossing(&self, edge: [Point<3>; 2]) -> Option<Point<3>> {
+        fj_math::exact::segment_plane_crossing(self.points(), edge)
+    }
+
+    /// Determine whether a point on the plane of the triangle is within it
+    fn contains(&self, point: Point<3>, epsilon: Scalar) -> bool {
+        let [a, b, c] = self.points();
+
+        [(a, b), (b, c), (c, a)].into_iter().all(|(p, q)| {
+            let edge = q - p;
+            edge.cross(&(point - p)).dot(&self.normal)
+                >= -epsilon * edge.magnitude()
+        })
+    }
+}
+
+/// Triangulate a solid into a consistently oriented mesh
+fn mesh(solid: &Solid, tolerance: Tolerance) -> Vec<MeshTriangle> {
+    let mesh = triangulate(
+        solid.faces().cloned().collect(),
+        tolerance,
+        &TriangulationConfig::default(),
+        &mut DebugInfo::new(),
+        &mut ProgressHandle::new(),
+    )
+    .expect("Triangulation can't be cancelled without token")
+    .weld(tolerance.inner());
+
+    let points: Vec<_> = mesh.vertices().collect();
+    let indices: Vec<_> = mesh.indices().map(|i| i as usize).collect();
+    let mut triangles: Vec<_> = indices
+        .chunks(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+
+    orient(&points, &mut triangles);
+
+    triangles
+        .into_iter()
+        .zip(mesh.triangles())
+        .map(|(triangle, original)| {
+            let points = triangle.map(|i| points[i]);
+            let triangle = Triangle::from_points(points);
+
+            MeshTriangle {
+                triangle,
+                color: original.color,
+                normal: triangle.normal(),
+                aabb: Aabb::<3>::from_points(points),
+            }
+        })
+        .collect()
+}
+
+/// Determine whether two bounding boxes overlap
+fn overlap(a: &Aabb<3>, b: &Aabb<3>, epsilon: Scalar) -> bool {
+    (0..3).all(|i| {
+        a.min.coords.components[i] <= b.max.coords.components[i] + epsilon
+            && b.min.coords.components[i]
+                <= a.max.coords.components[i] + epsilon
+    })
+}
+
+/// Compute the segment along which two triangles intersect
+///
+/// Returns `None`, if the triangles don't intersect, only touch in a point, or
+/// are coplanar.
+fn intersect(
+    a: &MeshTriangle,
+    b: &MeshTriangle,
+    epsilon: Scalar,
+) -> Option<[Point<3>; 2]> {
+    let distances = |of: &MeshTriangle, to: &MeshTriangle| {
+        of.points().map(|point| to.side(point, epsilon))
+    };
+    let distances_a = distances(a, b);
+    let distances_b = distances(b, a);
+
+    for distances in [distances_a, distances_b] {
+        let is_coplanar = distances.iter().all(|&d| d == Scalar::ZERO);
+        let is_separate = distances.iter().all(|&d| d > Scalar::ZERO)
+            || distances.iter().all(|&d| d < Scalar::ZERO);
+
+        if is_coplanar || is_separate {
+            return None;
+        }
+    }
+
+    let mut points = Vec::new();
+    crossings(a, distances_a, b, epsilon, &mut points);
+    crossings(b, distances_b, a, epsilon, &mut points);
+
+    let mut segment = None;
+    let mut max_length = epsilon;
+    for (i, &p) in points.iter().enumerate() {
+        for &q in &points[i + 1..] {
+            let length = (q - p).magnitude();
+            if length > max_length {
+                segment = Some([p, q]);
+                max_length = length;
+            }
+        }
+    }
+
+    segment
+}
+
+/// Find the points where a triangle crosses the plane of another
+///
+/// Only points that are within the other triangle are collected.
+fn crossings(
+    triangle: &MeshTriangle,
+    distances: [Scalar; 3],
+    other: &MeshTriangle,
+    epsilon: Scalar,
+    points: &mut Vec<Point<3>>,
+) {
+    let vertices = triangle.points();
+
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+
+        if distances[i] == Scalar::ZERO {
+            if other.contains(vertices[i], epsilon) {
+                points.push(vertices[i]);
+            }
+            continue;
+        }
+
+        if distances[i] * distances[j] < Scalar::ZERO {
+            // The neighboring triangle shares this edge and computes the same
+            // crossing. Ordering the vertices makes sure that the result is
+            // identical, not just equal up to rounding errors. Otherwise, the
+            // split triangles wouldn't fit together.
+            let [p, q] = {
+                let mut edge = [vertices[i], vertices[j]];
+                edge.sort();
+                edge
+            };
+            if let Some(point) = other.crossing([p, q]) {
+                if other.contains(point, epsilon) {
+                    points.push(point);
+                }
+            }
+        }
+    }
+}
+
+/// Split the triangles of a mesh along intersection segments
+fn split(
+    mesh: &[MeshTriangle],
+    segments: &[Vec<[Point<3>; 2]>],
+) -> Vec<([Point<3>; 3], [u8; 4])> {
+    let mut triangles = Vec::new();
+
+    for (triangle, segments) in mesh.iter().zip(segments) {
+        if segments.is_empty() {
+            triangles.push((triangle.points(), triangle.color));
+            continue;
+        }
+
+        // Triangulate within the plane of the triangle. The frame is chosen
+        // such that the triangulation has the same orientation as the
+        // triangle.
+        let [origin, next, _] = triangle.points();
+        let u = (next - origin).normalize();
+        let v = triangle.normal.cross(&u);
+        let local = |point: Point<3>| {
+            let offset = point - origin;
+            Local::new(Point::from([offset.dot(&u), offset.dot(&v)]), point)
+        };
+
+        let mut points: Vec<_> = triangle
+            .points()
+            .into_iter()
+            .chain(segments.iter().flatten().copied())
+            .collect();
+        points.sort();
+        points.dedup();
+
+        let points = points.into_iter().map(local).collect();
+        let segments: Vec<_> =
+            segments.iter().map(|segment| segment.map(local)).collect();
+
+        for split in delaunay::triangulate(
+            points,
+            segments.iter().map(|segment| segment.as_slice()),
+        ) {
+            triangles.push((split.map(|point| point.global()), triangle.color));
+        }
+    }
+
+    triangles
+}
+
+/// Determine which triangles are inside of the solid that `other` bounds
+///
+/// Triangles are grouped into regions that aren't separated by intersection
+/// points. Only one triangle of each region needs to be checked then.
+fn classify(
+    triangles: &[([Point<3>; 3], [u8; 4])],
+    intersection_points: &HashSet<Point<3>>,
+    other: &[MeshTriangle],
+) -> Vec<bool> {
+    let mut edges: HashMap<[Point<3>; 2], Vec<usize>> = HashMap::new();
+    for (t, ([a, b, c], _)) in triangles.iter().enumerate() {
+        for mut edge in [[*a, *b], [*b, *c], [*c, *a]] {
+            if edge.iter().all(|point| intersection_points.contains(point)) {
+                continue;
+            }
+
+            edge.sort();
+            edges.entry(edge).or_default().push(t);
+        }
+    }
+
+    let mut inside = vec![None; triangles.len()];
+    for start in 0..triangles.len() {
+        if inside[start].is_some() {
+            continue;
+        }
+
+        let mut region = vec![start];
+        let mut queue = VecDeque::from([start]);
+        inside[start] = Some(false);
+
+        while let Some(t) = queue.pop_front() {
+            let [a, b, c] = triangles[t].0;
+            for mut edge in [[a, b], [b, c], [c, a]] {
+                edge.sort();
+
+                for &u in edges.get(&edge).into_iter().flatten() {
+                    if inside[u].is_none() {
+                        inside[u] = Some(false);
+                        region.push(u);
+                        queue.push_back(u);
+                    }
+                }
+            }
+        }
+
+        // The center of the largest triangle is least likely to be ambiguous.
+        let area = |t: &usize| {
+            let [a, b, c] = triangles[*t].0;
+            (b - a).cross(&(c - a)).magnitude()
+        };
+        let largest = *region
+            .iter()
+            .max_by_key(|t| area(t))
+            .expect("Region contains at least one triangle");
+        let [a, b, c] = triangles[largest].0;
+        let center = a + ((b - a) + (c - a)) / 3.;
+
+        let is_inside = contains(other, center);
+        for t in region {
+            inside[t] = Some(is_inside);
+        }
+    }
+
+    inside
+        .into_iter()
+        .map(|inside| inside.expect("All triangles have been classified"))
+        .collect()
+}
+
+/// Determine whether the solid bounded by a mesh contains a point
+///
+/// Counts how often a ray, cast from the point, crosses the mesh. The
+/// direction of the ray is chosen such that it is unlikely to hit an edge or
+/// vertex.
+fn contains(mesh: &[MeshTriangle], point: Point<3>) -> bool {
+    let direction = Vector::from([1., 0.3172, 0.1423]).normalize();
+
+    let hits = mesh
+        .iter()
+        .filter(|triangle| {
+            triangle
+                .triangle
+                .cast_local_ray(point, direction, f64::INFINITY, true)
+                .is_some()
+        })
+        .count();
+
+    hits % 2 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::{DebugCategory, DebugInfo};
+    use fj_math::{Point, Transform};
+
+    use crate::{
+        algorithms::{Tolerance, TransformObject},
+        objects::{Face, Solid, Stores},
+    };
+
+    use super::{boolean, BooleanOperation};
+
+    #[test]
+    fn boolean_() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let a = Solid::cube_from_edge_length(&stores, 1.);
+        let b = a
+            .clone()
+            .transform(&Transform::translation([0.3, 0.4, 0.2]));
+
+        // The cubes overlap in a box of 0.7 x 0.6 x 0.8.
+        let overlap = 0.336;
+
+        for (operation, expected) in [
+            (BooleanOperation::Union, 2. - overlap),
+            (BooleanOperation::Intersection, overlap),
+            (BooleanOperation::Difference, 1. - overlap),
+        ] {
+            let result =
+                boolean(&a, &b, operation, tolerance, &mut DebugInfo::new());
+
+            let volume = volume(&result);
+            assert!(
+                (volume - expected).abs() < 1e-9,
+                "{operation:?}: {volume} != {expected}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_debug_info() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let a = Solid::cube_from_edge_length(&stores, 1.);
+        let b = a
+            .clone()
+            .transform(&Transform::translation([0.3, 0.4, 0.2]));
+
+        let mut debug_info = DebugInfo::new();
+        boolean(&a, &b, BooleanOperation::Union, tolerance, &mut debug_info);
+
+        assert!(debug_info
+            .geometry_in(DebugCategory::Intersection)
+            .next()
+            .is_some());
+
+        Ok(())
+    }
+
+    fn volume(solid: &Solid) -> f64 {
+        solid
+            .faces()
+            .flat_map(|face| match face {
+                Face::Triangles(triangles) => triangles
+                    .iter()
+                    .map(|(triangle, _)| triangle.points())
+                    .collect::<Vec<_>>(),
+                Face::Face(_) => panic!("Expected triangle representation"),
+            })
+            .map(|[a, b, c]: [Point<3>; 3]| {
+                a.coords.dot(&b.coords.cross(&c.coords)).into_f64() / 6.
+            })
+            .sum()
+    }
+}
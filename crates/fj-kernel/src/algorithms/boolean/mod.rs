@@ -0,0 +1,198 @@
+//! Boolean operations on solids
+
+mod mesh;
+
+use fj_interop::debug::DebugInfo;
+
+use crate::objects::{Face, Solid};
+
+use super::{interference, reverse_face, Containment, Tolerance};
+
+/// A boolean operation on two solids
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum BooleanOperation {
+    /// Everything that is in either solid
+    Union,
+
+    /// Everything that is in both solids
+    Intersection,
+
+    /// Everything that is in the first solid, but not in the second one
+    Difference,
+}
+
+/// The backend that computes a boolean operation
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum BooleanBackend {
+    /// Compute the result as a boundary representation
+    ///
+    /// Where that isn't possible, falls back to [`BooleanBackend::Mesh`].
+    /// Currently, this is the case whenever the faces of the solids intersect
+    /// each other.
+    #[default]
+    Exact,
+
+    /// Compute the result from triangulations of the solids
+    ///
+    /// Works for all solids, but the result is approximate. Its faces are in
+    /// triangle representation.
+    Mesh,
+}
+
+/// Compute a boolean operation on two solids
+///
+/// The solids are approximated, according to `tolerance`, where necessary.
+/// Where the faces of the solids intersect, the intersection is added to
+/// `debug_info`.
+#[tracing::instrument(level = "debug", skip(a, b, tolerance, debug_info))]
+pub fn boolean(
+    a: &Solid,
+    b: &Solid,
+    operation: BooleanOperation,
+    backend: BooleanBackend,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> Solid {
+    if backend == BooleanBackend::Exact {
+        if let Some(solid) = exact(a, b, operation, tolerance) {
+            return solid;
+        }
+    }
+
+    mesh::boolean(a, b, operation, tolerance, debug_info)
+}
+
+/// Compute a boolean operation on two solids whose faces don't intersect
+///
+/// Then the result consists of the faces of the original solids. Returns
+/// `None`, if the faces intersect.
+fn exact(
+    a: &Solid,
+    b: &Solid,
+    operation: BooleanOperation,
+    tolerance: Tolerance,
+) -> Option<Solid> {
+    let interference = interference(a, b, tolerance);
+    if !interference.face_pairs.is_empty() {
+        return None;
+    }
+
+    let faces = |solid: &Solid| solid.faces().cloned().collect::<Vec<_>>();
+
+    let faces = match (operation, interference.containment) {
+        (BooleanOperation::Union, None) => {
+            faces(a).into_iter().chain(faces(b)).collect()
+        }
+        (BooleanOperation::Union, Some(Containment::FirstInSecond)) => faces(b),
+        (BooleanOperation::Union, Some(Containment::SecondInFirst)) => faces(a),
+
+        (BooleanOperation::Intersection, None) => Vec::new(),
+        (BooleanOperation::Intersection, Some(Containment::FirstInSecond)) => {
+            faces(a)
+        }
+        (BooleanOperation::Intersection, Some(Containment::SecondInFirst)) => {
+            faces(b)
+        }
+
+        (BooleanOperation::Difference, None) => faces(a),
+        (BooleanOperation::Difference, Some(Containment::FirstInSecond)) => {
+            Vec::new()
+        }
+        (BooleanOperation::Difference, Some(Containment::SecondInFirst)) => {
+            // The second solid becomes a cavity in the first one, which
+            // requires its faces to be reversed. That's not supported for
+            // faces in triangle representation.
+            let mut cavity = Vec::new();
+            for face in b.faces() {
+                match face {
+                    Face::Face(_) => cavity.push(reverse_face(face)),
+                    Face::Triangles(_) => return None,
+                }
+            }
+
+            faces(a).into_iter().chain(cavity).collect()
+        }
+    };
+
+    Some(Solid::from_faces(faces))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Transform;
+
+    use crate::{
+        algorithms::{Tolerance, TransformObject},
+        objects::{Face, Solid, Stores},
+    };
+
+    use fj_interop::debug::DebugInfo;
+
+    use super::{boolean, BooleanBackend, BooleanOperation};
+
+    #[test]
+    fn exact() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let small = Solid::cube_from_edge_length(&stores, 0.5);
+        let separate = cube
+            .clone()
+            .transform(&Transform::translation([2., 0., 0.]));
+
+        let num_faces = |solid: &Solid| solid.faces().count();
+        let is_exact = |solid: &Solid| {
+            solid.faces().all(|face| matches!(face, Face::Face(_)))
+        };
+
+        let union = boolean(
+            &cube,
+            &separate,
+            BooleanOperation::Union,
+            BooleanBackend::Exact,
+            tolerance,
+            &mut DebugInfo::new(),
+        );
+        assert_eq!(num_faces(&union), 12);
+        assert!(is_exact(&union));
+
+        let intersection = boolean(
+            &cube,
+            &small,
+            BooleanOperation::Intersection,
+            BooleanBackend::Exact,
+            tolerance,
+            &mut DebugInfo::new(),
+        );
+        assert_eq!(intersection, small);
+
+        let difference = boolean(
+            &cube,
+            &small,
+            BooleanOperation::Difference,
+            BooleanBackend::Exact,
+            tolerance,
+            &mut DebugInfo::new(),
+        );
+        assert_eq!(num_faces(&difference), 12);
+        assert!(is_exact(&difference));
+
+        // Intersecting faces require the fallback.
+        let overlapping = cube
+            .clone()
+            .transform(&Transform::translation([0.5, 0.5, 0.5]));
+        let union = boolean(
+            &cube,
+            &overlapping,
+            BooleanOperation::Union,
+            BooleanBackend::Exact,
+            tolerance,
+            &mut DebugInfo::new(),
+        );
+        assert!(!is_exact(&union));
+
+        Ok(())
+    }
+}
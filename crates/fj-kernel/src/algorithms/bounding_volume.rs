@@ -0,0 +1,501 @@
+use fj_math::{Aabb, Circle, Obb, Point, Scalar, Sphere, Vector};
+
+use crate::objects::{
+    Curve, Cycle, Edge, Face, GlobalVertex, Shell, Sketch, Solid, Surface,
+    SweptCurve,
+};
+
+use super::{approx::approx_curve, EdgeApprox, FaceApprox, Tolerance};
+
+/// Compute the bounding volumes of an object
+///
+/// Curves and edges are bounded exactly by their axis-aligned bounding box.
+/// Faces on curved surfaces are bounded using their approximation, which is
+/// expanded by `tolerance`, to make sure the bounding box contains the actual
+/// face.
+///
+/// Oriented bounding boxes and bounding spheres are computed from the points
+/// that approximate the object, and expanded the same way.
+pub trait BoundingVolume {
+    /// Compute the axis-aligned bounding box of the object
+    ///
+    /// Returns `None`, if the object is empty, or if it is unbounded (like a
+    /// line).
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>>;
+
+    /// Compute the points that approximate the object
+    ///
+    /// The object deviates from these points by no more than `tolerance`.
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>>;
+
+    /// Compute an oriented bounding box of the object
+    ///
+    /// Returns `None`, if the object is empty, or if it is unbounded.
+    fn obb(&self, tolerance: Tolerance) -> Option<Obb> {
+        Obb::from_points(self.points(tolerance)).map(|obb| Obb {
+            half_extents: obb.half_extents
+                + Vector::from([tolerance.inner(); 3]),
+            ..obb
+        })
+    }
+
+    /// Compute the bounding sphere of the object
+    ///
+    /// Returns `None`, if the object is empty, or if it is unbounded.
+    fn bounding_sphere(&self, tolerance: Tolerance) -> Option<Sphere> {
+        Sphere::from_points(self.points(tolerance)).map(|sphere| Sphere {
+            radius: sphere.radius + tolerance.inner(),
+            ..sphere
+        })
+    }
+}
+
+impl BoundingVolume for GlobalVertex {
+    fn aabb(&self, _: Tolerance) -> Option<Aabb<3>> {
+        Some(Aabb::<3>::from_points([self.position()]))
+    }
+
+    fn points(&self, _: Tolerance) -> Vec<Point<3>> {
+        vec![self.position()]
+    }
+}
+
+impl BoundingVolume for Curve<3> {
+    fn aabb(&self, _: Tolerance) -> Option<Aabb<3>> {
+        match self {
+            Self::Circle(circle) => Some(circle_aabb(circle)),
+            Self::Line(_) => None,
+        }
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        let mut points = Vec::new();
+        approx_curve(self, tolerance, &mut points);
+
+        points.into_iter().map(|point| point.global()).collect()
+    }
+
+    fn obb(&self, _: Tolerance) -> Option<Obb> {
+        match self {
+            Self::Circle(circle) => {
+                let radius = circle.a.magnitude();
+                let [a, b] = [circle.a, circle.b].map(|v| v.normalize());
+
+                Some(Obb {
+                    center: circle.center,
+                    axes: [a, b, a.cross(&b)],
+                    half_extents: Vector::from([radius, radius, Scalar::ZERO]),
+                })
+            }
+            Self::Line(_) => None,
+        }
+    }
+
+    fn bounding_sphere(&self, _: Tolerance) -> Option<Sphere> {
+        match self {
+            Self::Circle(circle) => Some(Sphere {
+                center: circle.center,
+                radius: circle.a.magnitude(),
+            }),
+            Self::Line(_) => None,
+        }
+    }
+}
+
+impl BoundingVolume for Edge {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        let [a, b] = match self.vertices() {
+            Some(vertices) => vertices,
+            None => return self.curve().aabb(tolerance),
+        };
+
+        let points = [&a, &b].map(|vertex| vertex.global().position());
+        let aabb = Aabb::<3>::from_points(points);
+
+        match self.arc() {
+            Some(arc) => Some(
+                arc.extremes()
+                    .iter()
+                    .fold(aabb, |aabb, point| aabb.include_point(point)),
+            ),
+            None => Some(aabb),
+        }
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        EdgeApprox::new(self, tolerance)
+            .points
+            .into_iter()
+            .map(|point| point.global())
+            .collect()
+    }
+}
+
+impl BoundingVolume for Cycle {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        merge(self.edges().map(|edge| edge.aabb(tolerance)))
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        self.edges()
+            .flat_map(|edge| edge.points(tolerance))
+            .collect()
+    }
+}
+
+impl BoundingVolume for Face {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        match self {
+            Self::Face(brep) => match brep.surface() {
+                Surface::SweptCurve(SweptCurve {
+                    curve: Curve::Line(_),
+                    ..
+                }) => {
+                    // A planar face is bounded by its exterior cycles.
+                    merge(brep.exteriors().map(|cycle| cycle.aabb(tolerance)))
+                }
+                Surface::SweptCurve(_) => {
+                    // A curved face can bulge out beyond its cycles.
+                    let approx = FaceApprox::new(self, tolerance);
+                    let aabb = Aabb::<3>::from_points(
+                        approx.points.iter().map(|point| point.global()),
+                    );
+
+                    let margin = Vector::from([tolerance.inner(); 3]);
+                    Some(Aabb {
+                        min: aabb.min - margin,
+                        max: aabb.max + margin,
+                    })
+                }
+            },
+            Self::Triangles(triangles) => {
+                let points: Vec<_> = triangles
+                    .iter()
+                    .flat_map(|(triangle, _)| triangle.points())
+                    .collect();
+
+                (!points.is_empty()).then(|| Aabb::<3>::from_points(points))
+            }
+        }
+    }
+
+    fn obb(&self, tolerance: Tolerance) -> Option<Obb> {
+        faces_obb([self], tolerance)
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        match self {
+            Self::Face(_) => FaceApprox::new(self, tolerance)
+                .points
+                .into_iter()
+                .map(|point| point.global())
+                .collect(),
+            Self::Triangles(triangles) => triangles
+                .iter()
+                .flat_map(|(triangle, _)| triangle.points())
+                .collect(),
+        }
+    }
+}
+
+impl BoundingVolume for Shell {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        merge(self.faces().map(|face| face.aabb(tolerance)))
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        self.faces()
+            .flat_map(|face| face.points(tolerance))
+            .collect()
+    }
+
+    fn obb(&self, tolerance: Tolerance) -> Option<Obb> {
+        faces_obb(self.faces(), tolerance)
+    }
+}
+
+impl BoundingVolume for Sketch {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        merge(self.faces().map(|face| face.aabb(tolerance)))
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        self.faces()
+            .flat_map(|face| face.points(tolerance))
+            .collect()
+    }
+
+    fn obb(&self, tolerance: Tolerance) -> Option<Obb> {
+        faces_obb(self.faces(), tolerance)
+    }
+}
+
+impl BoundingVolume for Solid {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        merge(self.faces().map(|face| face.aabb(tolerance)))
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        self.faces()
+            .flat_map(|face| face.points(tolerance))
+            .collect()
+    }
+
+    fn obb(&self, tolerance: Tolerance) -> Option<Obb> {
+        faces_obb(self.faces(), tolerance)
+    }
+}
+
+impl BoundingVolume for Vec<Face> {
+    fn aabb(&self, tolerance: Tolerance) -> Option<Aabb<3>> {
+        merge(self.iter().map(|face| face.aabb(tolerance)))
+    }
+
+    fn points(&self, tolerance: Tolerance) -> Vec<Point<3>> {
+        self.iter()
+            .flat_map(|face| face.points(tolerance))
+            .collect()
+    }
+
+    fn obb(&self, tolerance: Tolerance) -> Option<Obb> {
+        faces_obb(self.iter(), tolerance)
+    }
+}
+
+/// Compute an oriented bounding box of faces
+///
+/// Principal axes don't fit symmetric shapes well, so the axes that are defined
+/// by the planar faces are considered too: Each face's normal, combined with
+/// the direction of each of its straight edges.
+fn faces_obb<'r>(
+    faces: impl IntoIterator<Item = &'r Face>,
+    tolerance: Tolerance,
+) -> Option<Obb> {
+    let mut points = Vec::new();
+    let mut candidates = Vec::new();
+
+    for face in faces {
+        points.extend(face.points(tolerance));
+
+        let brep = match face {
+            Face::Face(brep) => brep,
+            Face::Triangles(_) => continue,
+        };
+        let Surface::SweptCurve(SweptCurve { curve, path }) = brep.surface();
+        let normal = match curve {
+            Curve::Line(line) => line.direction.cross(&path).normalize(),
+            Curve::Circle(_) => continue,
+        };
+
+        for edge in brep.exteriors().flat_map(|cycle| cycle.edges) {
+            if let Curve::Line(line) = edge.curve() {
+                let direction = line.direction.normalize();
+                candidates.push([direction, normal.cross(&direction), normal]);
+            }
+        }
+    }
+
+    Obb::from_points_and_axes(points, candidates).map(|obb| Obb {
+        half_extents: obb.half_extents + Vector::from([tolerance.inner(); 3]),
+        ..obb
+    })
+}
+
+/// Merge bounding boxes, ignoring those of empty objects
+fn merge(aabbs: impl IntoIterator<Item = Option<Aabb<3>>>) -> Option<Aabb<3>> {
+    aabbs.into_iter().flatten().reduce(|a, b| a.merged(&b))
+}
+
+/// Compute the bounding box of a full circle
+///
+/// Along each axis, `a * cos(t) + b * sin(t)` reaches its extremes at
+/// `±|(a, b)|`.
+fn circle_aabb(circle: &Circle<3>) -> Aabb<3> {
+    let extent = Vector::from([0, 1, 2].map(|i| {
+        let [a, b] = [circle.a, circle.b].map(|v| v.components[i]);
+        Vector::from([a, b]).magnitude()
+    }));
+
+    Aabb {
+        min: circle.center - extent,
+        max: circle.center + extent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Circle, Point, Scalar, Transform, Vector};
+
+    use crate::{
+        algorithms::{Tolerance, TransformObject},
+        local::Local,
+        objects::{
+            Curve, Edge, GlobalVertex, Solid, Stores, Vertex, VerticesOfEdge,
+        },
+    };
+
+    use super::BoundingVolume;
+
+    #[test]
+    fn circle() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // A circle of radius 1 around the origin, tilted by 45 degrees around
+        // the x axis.
+        let s = Scalar::from(0.5_f64.sqrt());
+        let circle = Curve::Circle(Circle {
+            center: Point::origin(),
+            a: Vector::from([1., 0., 0.]),
+            b: Vector::from([Scalar::ZERO, s, s]),
+        });
+
+        let aabb = circle.aabb(tolerance).unwrap();
+        assert_aabb_eq(
+            aabb,
+            [-1., -s.into_f64(), -s.into_f64()],
+            [1., s.into_f64(), s.into_f64()],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn arc() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // A quarter circle in the xy-plane, from the positive x axis to the
+        // positive y axis, the rest of the circle, and the quarter circle in
+        // reverse.
+        let circle = Circle {
+            center: Point::origin(),
+            a: Vector::from([1., 0., 0.]),
+            b: Vector::from([0., 1., 0.]),
+        };
+        let arc = |start: f64, end: f64| {
+            let vertex = |t: f64| {
+                let position = circle.point_from_circle_coords([t]);
+                let global = GlobalVertex::from_position(position);
+                Vertex::new([t], stores.global_vertices.insert(global))
+            };
+
+            Edge {
+                curve: Local::new(
+                    Curve::Circle(Circle {
+                        center: Point::origin(),
+                        a: Vector::from([1., 0.]),
+                        b: Vector::from([0., 1.]),
+                    }),
+                    stores.curves.insert(Curve::Circle(circle)),
+                ),
+                vertices: VerticesOfEdge::from_vertices([
+                    vertex(start),
+                    vertex(end),
+                ]),
+            }
+        };
+
+        let quarter = arc(0., std::f64::consts::FRAC_PI_2);
+        assert_aabb_eq(
+            quarter.aabb(tolerance).unwrap(),
+            [0., 0., 0.],
+            [1., 1., 0.],
+        );
+
+        let rest = arc(std::f64::consts::FRAC_PI_2, std::f64::consts::PI * 2.);
+        assert_aabb_eq(
+            rest.aabb(tolerance).unwrap(),
+            [-1., -1., 0.],
+            [1., 1., 0.],
+        );
+
+        // The arc runs in the direction of decreasing coordinates, if the
+        // second vertex has the smaller one.
+        let reversed = arc(std::f64::consts::FRAC_PI_2, 0.);
+        assert_aabb_eq(
+            reversed.aabb(tolerance).unwrap(),
+            [0., 0., 0.],
+            [1., 1., 0.],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rotated_cube() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let transform = Transform::rotation(Vector::from([0., 0., 1.]) * 0.1);
+
+        let aabb = cube.aabb(tolerance).unwrap();
+        assert_aabb_eq(aabb, [-0.5; 3], [0.5; 3]);
+
+        // Rotating the cube around the z axis, then back, results in the
+        // original bounding box.
+        let restored = cube
+            .clone()
+            .transform(&transform)
+            .transform(&transform.inverse())
+            .aabb(tolerance)
+            .unwrap();
+        assert_aabb_eq(restored, [-0.5; 3], [0.5; 3]);
+
+        // Rotating the cube makes its bounding box grow along the x and y
+        // axes. Transforming the bounding box of the cube would not reflect
+        // that.
+        let rotated = cube.transform(&transform).aabb(tolerance).unwrap();
+        let extent = (0.1_f64.cos() + 0.1_f64.sin()) / 2.;
+        assert_aabb_eq(
+            rotated,
+            [-extent, -extent, -0.5],
+            [extent, extent, 0.5],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn obb_and_sphere() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // The OBB of a rotated cube fits tightly, unlike its AABB.
+        let transform =
+            Transform::rotation(Vector::from([1., 1., 0.]).normalize() * 0.5);
+        let cube =
+            Solid::cube_from_edge_length(&stores, 1.).transform(&transform);
+
+        let obb = cube.obb(tolerance).unwrap();
+        let aabb = cube.aabb(tolerance).unwrap();
+        let side = Scalar::ONE + tolerance.inner() * 2.;
+        let expected = side * side * side;
+        assert!((obb.volume() - expected).abs() < Scalar::from(1e-9));
+        assert!(obb.volume() < aabb.size().x * aabb.size().y * aabb.size().z);
+
+        let sphere = cube.bounding_sphere(tolerance).unwrap();
+        let expected = Scalar::from(3_f64.sqrt() / 2.) + tolerance.inner();
+        assert!((sphere.center - Point::origin()).magnitude() < 1e-9.into());
+        assert!((sphere.radius - expected).abs() < Scalar::from(1e-9));
+
+        Ok(())
+    }
+
+    fn assert_aabb_eq(aabb: Aabb<3>, min: [f64; 3], max: [f64; 3]) {
+        let epsilon = Scalar::from(1e-9);
+        let expected = Aabb {
+            min: Point::from(min),
+            max: Point::from(max),
+        };
+
+        assert!(
+            (aabb.min - expected.min).magnitude() < epsilon
+                && (aabb.max - expected.max).magnitude() < epsilon,
+            "{aabb:?} != {expected:?}"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::objects::{
+    Curve, Cycle, Edge, Face, GlobalVertex, Sketch, Solid, Surface,
+};
+
+/// Compare objects, allowing for small deviations
+///
+/// Objects are compared based on their global geometry and their structure.
+/// Points and vectors are considered equal, if they are no further apart
+/// than `tolerance`.
+///
+/// This is useful for regression tests, which should catch changes to a shape,
+/// but not fail because of floating-point noise.
+pub trait ApproxEq {
+    /// Determine whether two objects are equal, within `tolerance`
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool;
+}
+
+impl ApproxEq for Point<3> {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        (*self - *other).magnitude() <= tolerance
+    }
+}
+
+impl ApproxEq for Vector<3> {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        (*self - *other).magnitude() <= tolerance
+    }
+}
+
+impl ApproxEq for Curve<3> {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        match (self, other) {
+            (
+                Self::Circle(Circle { center, a, b }),
+                Self::Circle(Circle {
+                    center: other_center,
+                    a: other_a,
+                    b: other_b,
+                }),
+            ) => {
+                center.approx_eq(other_center, tolerance)
+                    && a.approx_eq(other_a, tolerance)
+                    && b.approx_eq(other_b, tolerance)
+            }
+            (
+                Self::Line(Line { origin, direction }),
+                Self::Line(Line {
+                    origin: other_origin,
+                    direction: other_direction,
+                }),
+            ) => {
+                origin.approx_eq(other_origin, tolerance)
+                    && direction.approx_eq(other_direction, tolerance)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ApproxEq for Surface {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        let (Self::SweptCurve(a), Self::SweptCurve(b)) = (self, other);
+
+        a.curve.approx_eq(&b.curve, tolerance)
+            && a.path.approx_eq(&b.path, tolerance)
+    }
+}
+
+impl ApproxEq for GlobalVertex {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        self.position().approx_eq(&other.position(), tolerance)
+    }
+}
+
+impl ApproxEq for Edge {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        let vertices_match = match (self.vertices(), other.vertices()) {
+            (Some(a), Some(b)) => a
+                .iter()
+                .zip(&b)
+                .all(|(a, b)| a.global().approx_eq(b.global(), tolerance)),
+            (None, None) => true,
+            _ => false,
+        };
+
+        vertices_match && self.curve().approx_eq(&other.curve(), tolerance)
+    }
+}
+
+impl ApproxEq for Cycle {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        all_approx_eq(self.edges(), other.edges(), tolerance)
+    }
+}
+
+impl ApproxEq for Face {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        match (self, other) {
+            (Self::Face(a), Self::Face(b)) => {
+                a.color == b.color
+                    && a.surface().approx_eq(&b.surface(), tolerance)
+                    && all_approx_eq(a.exteriors(), b.exteriors(), tolerance)
+                    && all_approx_eq(a.interiors(), b.interiors(), tolerance)
+            }
+            (Self::Triangles(a), Self::Triangles(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b).all(|((a, a_color), (b, b_color))| {
+                        a_color == b_color
+                            && all_approx_eq(a.points(), b.points(), tolerance)
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ApproxEq for Sketch {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        let a: Vec<_> = self.faces().collect();
+        let b: Vec<_> = other.faces().collect();
+
+        unordered_approx_eq(&a, &b, tolerance)
+    }
+}
+
+impl ApproxEq for Solid {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        let a: Vec<_> = self.faces().collect();
+        let b: Vec<_> = other.faces().collect();
+
+        unordered_approx_eq(&a, &b, tolerance)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for &T {
+    fn approx_eq(&self, other: &Self, tolerance: Scalar) -> bool {
+        (*self).approx_eq(*other, tolerance)
+    }
+}
+
+/// Compare two sequences of objects pairwise
+fn all_approx_eq<T: ApproxEq>(
+    a: impl IntoIterator<Item = T>,
+    b: impl IntoIterator<Item = T>,
+    tolerance: Scalar,
+) -> bool {
+    let a: Vec<_> = a.into_iter().collect();
+    let b: Vec<_> = b.into_iter().collect();
+
+    a.len() == b.len()
+        && a.iter().zip(&b).all(|(a, b)| a.approx_eq(b, tolerance))
+}
+
+/// Compare two sets of objects, regardless of their order
+///
+/// The faces of sketches and solids are ordered by their geometry, so small
+/// deviations can change their order.
+fn unordered_approx_eq<T: ApproxEq>(
+    a: &[T],
+    b: &[T],
+    tolerance: Scalar,
+) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut unmatched: Vec<_> = b.iter().collect();
+    for a in a {
+        match unmatched.iter().position(|b| a.approx_eq(b, tolerance)) {
+            Some(i) => {
+                unmatched.swap_remove(i);
+            }
+            None => return false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::TransformObject,
+        objects::{Face, Solid, Stores, Surface},
+    };
+
+    use super::ApproxEq;
+
+    #[test]
+    fn approx_eq() {
+        let stores = Stores::new();
+
+        let tolerance = Scalar::from(0.01);
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        assert!(
+            cube.approx_eq(&cube.clone().translate([0.001, 0., 0.]), tolerance)
+        );
+        assert!(
+            !cube.approx_eq(&cube.clone().translate([0.1, 0., 0.]), tolerance)
+        );
+        assert!(!cube
+            .approx_eq(&Solid::cube_from_edge_length(&stores, 1.1), tolerance));
+
+        let triangle = |c: f64| {
+            Face::builder(&stores, Surface::xy_plane())
+                .with_exterior_polygon([[0., 0.], [1., 0.], [0., c]])
+                .build()
+        };
+        assert!(triangle(1.).approx_eq(&triangle(1.001), tolerance));
+        assert!(!triangle(1.).approx_eq(&triangle(2.), tolerance));
+    }
+}
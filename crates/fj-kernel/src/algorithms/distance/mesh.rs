@@ -0,0 +1,248 @@
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{triangulate, BoundingVolume, Tolerance, TriangulationConfig},
+    objects::{Face, Solid},
+};
+
+use super::Distance;
+
+/// Compute the minimum distance between a point and a face
+///
+/// The point comes first in the returned [`Distance`]. Returns `None`, if the
+/// face is empty.
+pub fn point_face(
+    point: impl Into<Point<3>>,
+    face: &Face,
+    tolerance: Tolerance,
+) -> Option<Distance> {
+    let point = point.into();
+
+    triangles(face, tolerance)
+        .into_iter()
+        .map(|(triangle, _)| {
+            Distance::from_points([point, triangle.closest_point(point)])
+        })
+        .min_by_key(|distance| distance.distance)
+}
+
+/// Compute the minimum distance between two faces
+///
+/// Returns `None`, if one of the faces is empty.
+pub fn face_face(a: &Face, b: &Face, tolerance: Tolerance) -> Option<Distance> {
+    closest(&triangles(a, tolerance), &triangles(b, tolerance), None)
+}
+
+/// Compute the minimum distance between two solids
+///
+/// This is the distance between the boundaries of the solids. If one of them
+/// is inside of the other, the distance is not zero, even though the solids
+/// overlap.
+///
+/// Returns `None`, if one of the solids is empty.
+pub fn solid_solid(
+    a: &Solid,
+    b: &Solid,
+    tolerance: Tolerance,
+) -> Option<Distance> {
+    fn faces(solid: &Solid, tolerance: Tolerance) -> Vec<(&Face, Aabb<3>)> {
+        solid
+            .faces()
+            .filter_map(|face| Some((face, face.aabb(tolerance)?)))
+            .collect()
+    }
+    let (faces_a, faces_b) = (faces(a, tolerance), faces(b, tolerance));
+
+    // Visit the pairs of faces that are closest to each other first. Then the
+    // distance found early on can be used to skip the remaining pairs.
+    let mut pairs = Vec::new();
+    for (face_a, aabb_a) in &faces_a {
+        for (face_b, aabb_b) in &faces_b {
+            pairs.push((aabb_distance(aabb_a, aabb_b), *face_a, *face_b));
+        }
+    }
+    pairs.sort_by_key(|&(distance, _, _)| distance);
+
+    let mut best: Option<Distance> = None;
+    for (lower_bound, face_a, face_b) in pairs {
+        if let Some(best) = best {
+            if lower_bound >= best.distance {
+                break;
+            }
+        }
+
+        let distance = closest(
+            &triangles(face_a, tolerance),
+            &triangles(face_b, tolerance),
+            best,
+        );
+        if distance.is_some() {
+            best = distance;
+        }
+    }
+
+    best
+}
+
+/// Triangulate a face, and compute the bounding box of each triangle
+fn triangles(face: &Face, tolerance: Tolerance) -> Vec<(Triangle<3>, Aabb<3>)> {
+    let mesh = triangulate(
+        vec![face.clone()],
+        tolerance,
+        &TriangulationConfig::default(),
+        &mut DebugInfo::new(),
+        &mut ProgressHandle::new(),
+    )
+    .expect("Triangulation can't be cancelled without token");
+
+    mesh.triangles()
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.points;
+
+            // Triangles that don't span any area don't contribute anything,
+            // as their points are also part of neighboring triangles.
+            if (b - a).cross(&(c - a)).magnitude() == Scalar::ZERO {
+                return None;
+            }
+
+            Some((
+                Triangle::from_points(triangle.points),
+                Aabb::<3>::from_points(triangle.points),
+            ))
+        })
+        .collect()
+}
+
+/// Find the closest points between two sets of triangles
+///
+/// Returns `best`, if no pair of triangles is closer than that.
+fn closest(
+    a: &[(Triangle<3>, Aabb<3>)],
+    b: &[(Triangle<3>, Aabb<3>)],
+    mut best: Option<Distance>,
+) -> Option<Distance> {
+    for (triangle_a, aabb_a) in a {
+        for (triangle_b, aabb_b) in b {
+            if let Some(best) = best {
+                if aabb_distance(aabb_a, aabb_b) >= best.distance {
+                    continue;
+                }
+            }
+
+            let distance =
+                Distance::from_points(triangle_a.closest_points(triangle_b));
+            let is_closer = match best {
+                Some(best) => distance.distance < best.distance,
+                None => true,
+            };
+            if is_closer {
+                best = Some(distance);
+            }
+        }
+    }
+
+    best
+}
+
+/// Compute the distance between two bounding boxes
+///
+/// This is a lower bound for the distance between anything they contain.
+fn aabb_distance(a: &Aabb<3>, b: &Aabb<3>) -> Scalar {
+    let gaps = [0, 1, 2].map(|i| {
+        let gap = (a.min.coords.components[i] - b.max.coords.components[i])
+            .max(b.min.coords.components[i] - a.max.coords.components[i]);
+        gap.max(Scalar::ZERO)
+    });
+
+    Vector::from(gaps).magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Transform, Vector};
+
+    use crate::{
+        algorithms::{Tolerance, TransformObject},
+        objects::{Face, Solid, Stores, Surface},
+    };
+
+    use super::{face_face, point_face, solid_solid};
+
+    #[test]
+    fn point_face_() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .with_interior_polygon([
+                [0.5, 0.5],
+                [1.5, 0.5],
+                [1.5, 1.5],
+                [0.5, 1.5],
+            ])
+            .build();
+
+        // Above the face.
+        let distance = point_face([0.25, 1., 3.], &face, tolerance).unwrap();
+        assert_eq!(distance.distance, Scalar::from(3.));
+        assert_eq!(distance.points[1], Point::from([0.25, 1., 0.]));
+
+        // Above the hole.
+        let distance = point_face([1., 1., 0.], &face, tolerance).unwrap();
+        assert_eq!(distance.distance, Scalar::from(0.5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_face_() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+        let a = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon(square)
+            .build();
+        let b = Face::builder(&stores, Surface::yz_plane())
+            .with_exterior_polygon(square)
+            .build()
+            .transform(&Transform::translation([2., 0.5, 1.]));
+
+        let distance = face_face(&a, &b, tolerance).unwrap();
+        let expected = Vector::from([1., 0., 1.]).magnitude();
+        assert!((distance.distance - expected).abs() < Scalar::from(1e-12));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solid_solid_() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let a = Solid::cube_from_edge_length(&stores, 1.);
+        let b = a
+            .clone()
+            .transform(&Transform::rotation(Vector::unit_z() * 0.5))
+            .transform(&Transform::translation([3., 0., 0.]));
+
+        // The vertex of the rotated cube that is closest to the other cube
+        // is the one with the smallest x coordinate.
+        let distance = solid_solid(&a, &b, tolerance).unwrap();
+        let (sin, cos) = 0.5_f64.sin_cos();
+        let expected = Scalar::from(2.5 - 0.5 * (sin + cos));
+        assert!((distance.distance - expected).abs() < Scalar::from(1e-12));
+
+        // Overlapping solids.
+        let b = a.clone().transform(&Transform::translation([0.5, 0., 0.]));
+        let distance = solid_solid(&a, &b, tolerance).unwrap();
+        assert_eq!(distance.distance, Scalar::ZERO);
+
+        Ok(())
+    }
+}
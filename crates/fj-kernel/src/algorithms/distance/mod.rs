@@ -0,0 +1,39 @@
+//! Distance algorithms
+//!
+//! Distances between points and curves are computed exactly. All other
+//! distances are computed using the triangle meshes of the faces involved, and
+//! are accurate within the tolerance that was used to create those meshes.
+
+mod mesh;
+mod point_curve;
+
+use fj_math::{Point, Scalar};
+
+pub use self::{
+    mesh::{face_face, point_face, solid_solid},
+    point_curve::point_curve,
+};
+
+/// The minimum distance between two objects
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Distance {
+    /// The minimum distance between the objects
+    pub distance: Scalar,
+
+    /// The points on both objects that are closest to each other
+    ///
+    /// The point on the first object comes first, the point on the second
+    /// object second.
+    pub points: [Point<3>; 2],
+}
+
+impl Distance {
+    fn from_points(points: [Point<3>; 2]) -> Self {
+        let [a, b] = points;
+
+        Self {
+            distance: (b - a).magnitude(),
+            points,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use fj_math::{Point, Scalar};
+
+use crate::objects::Curve;
+
+use super::Distance;
+
+/// Compute the minimum distance between a point and a curve
+///
+/// The point comes first in the returned [`Distance`]. If the point is on the
+/// axis of a circle, all points on the circle are equally close, and one of
+/// them is returned.
+pub fn point_curve(point: impl Into<Point<3>>, curve: &Curve<3>) -> Distance {
+    let point = point.into();
+
+    let closest = match curve {
+        Curve::Circle(circle) => {
+            let offset = point - circle.center;
+
+            let in_plane = [circle.a, circle.b]
+                .into_iter()
+                .map(|axis| axis * (offset.dot(&axis) / axis.dot(&axis)))
+                .reduce(|a, b| a + b)
+                .expect("Array is not empty");
+
+            if in_plane.magnitude() == Scalar::ZERO {
+                circle.center + circle.a
+            } else {
+                circle.center + in_plane.normalize() * circle.a.magnitude()
+            }
+        }
+        Curve::Line(line) => {
+            let t = (point - line.origin).dot(&line.direction)
+                / line.direction.dot(&line.direction);
+            line.origin + line.direction * t
+        }
+    };
+
+    Distance::from_points([point, closest])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+    use crate::objects::Curve;
+
+    use super::point_curve;
+
+    #[test]
+    fn line() {
+        let line = Curve::Line(Line {
+            origin: Point::from([1., 0., 0.]),
+            direction: Vector::from([2., 0., 0.]),
+        });
+
+        let distance = point_curve([3., 4., 0.], &line);
+        assert_eq!(distance.distance, Scalar::from(4.));
+        assert_eq!(distance.points[1], Point::from([3., 0., 0.]));
+    }
+
+    #[test]
+    fn circle() {
+        let circle = Curve::Circle(Circle {
+            center: Point::from([0., 0., 1.]),
+            a: Vector::from([2., 0., 0.]),
+            b: Vector::from([0., 2., 0.]),
+        });
+
+        let distance = point_curve([0., 5., 5.], &circle);
+        assert_eq!(distance.distance, Scalar::from(5.));
+        assert_eq!(distance.points[1], Point::from([0., 2., 1.]));
+
+        // All points on the circle are equally close to its center.
+        let distance = point_curve([0., 0., 1.], &circle);
+        assert_eq!(distance.distance, Scalar::from(2.));
+    }
+}
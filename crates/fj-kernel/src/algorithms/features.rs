@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use fj_interop::features::{
+    CurveGeometry, EdgeFeature, FaceFeature, Features, SurfaceGeometry,
+};
+use fj_math::{Line, Point, PolyChain};
+
+use crate::objects::{Curve, Face, Surface};
+
+use super::{EdgeApprox, Tolerance};
+
+/// Extract the features of a shape
+///
+/// Returns the vertices, edges, and faces of the shape, including their exact
+/// geometry. Edges are approximated as polygonal chains, using the provided
+/// tolerance. The triangles of the faces are left empty, as they are produced
+/// by the triangulation. See [`Features::push_face_mesh`].
+///
+/// Vertices and edges that are shared between faces are only returned once.
+/// Faces that are represented as triangles have no exact geometry, and don't
+/// contribute any vertices or edges. They are still returned, so the index of
+/// each face matches the order of the faces that were passed in.
+#[tracing::instrument(level = "debug", skip_all, fields(faces = faces.len()))]
+pub fn features(faces: &[Face], tolerance: Tolerance) -> Features {
+    let mut features = Features::new();
+    let mut visited = HashSet::new();
+
+    for face in faces {
+        let face = match face {
+            Face::Face(face) => face,
+            Face::Triangles(_) => {
+                features.faces.push(FaceFeature::default());
+                continue;
+            }
+        };
+
+        features.faces.push(FaceFeature {
+            surface: Some(surface_geometry(&face.surface())),
+            triangles: Vec::new(),
+        });
+
+        for cycle in face.all_cycles() {
+            for edge in cycle.edges() {
+                for vertex in edge.vertices().into_iter().flatten() {
+                    let position = vertex.global().position();
+                    if !features.vertices.contains(&position) {
+                        features.vertices.push(position);
+                    }
+                }
+
+                let mut points: Vec<Point<3>> =
+                    EdgeApprox::new(&edge, tolerance)
+                        .points
+                        .into_iter()
+                        .map(|point| point.global())
+                        .collect();
+                points.dedup();
+
+                if points.len() < 2 {
+                    continue;
+                }
+
+                // Neighboring faces refer to the same edge, but might do so in
+                // opposite directions.
+                let key = {
+                    let mut reversed = points.clone();
+                    reversed.reverse();
+                    points.clone().min(reversed)
+                };
+                if !visited.insert(key) {
+                    continue;
+                }
+
+                let curve = match edge.curve() {
+                    Curve::Circle(circle) => CurveGeometry::Circle(circle),
+                    Curve::Line(line) => CurveGeometry::Line(line),
+                };
+
+                features.edges.push(EdgeFeature {
+                    curve: Some(curve),
+                    approx: PolyChain::from_points(points),
+                });
+            }
+        }
+    }
+
+    features
+}
+
+fn surface_geometry(surface: &Surface) -> SurfaceGeometry {
+    let Surface::SweptCurve(surface) = surface;
+
+    match surface.curve {
+        Curve::Circle(circle) => SurfaceGeometry::Cylinder {
+            axis: Line {
+                origin: circle.center,
+                direction: surface.path,
+            },
+            radius: circle.a.magnitude(),
+        },
+        Curve::Line(line) => SurfaceGeometry::Plane {
+            origin: line.origin,
+            normal: line.direction.cross(&surface.path).normalize(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        features::{CurveGeometry, Feature, SurfaceGeometry},
+        progress::ProgressHandle,
+    };
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{sweep, Tolerance},
+        objects::{Cycle, Edge, Face, Sketch, Solid, Stores, Surface},
+    };
+
+    #[test]
+    fn cube() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let faces: Vec<_> = cube.into_faces().into_iter().collect();
+
+        let features = super::features(&faces, tolerance);
+
+        assert_eq!(features.vertices.len(), 8);
+        assert_eq!(features.edges.len(), 12);
+        assert_eq!(features.faces.len(), 6);
+        for edge in &features.edges {
+            assert!(matches!(edge.curve, Some(CurveGeometry::Line(_))));
+        }
+        for face in &features.faces {
+            assert!(matches!(
+                face.surface,
+                Some(SurfaceGeometry::Plane { .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn cylinder() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::from(0.1))?;
+
+        let circle = Edge::circle_from_radius(&stores, Scalar::from(2.));
+        let face = Face::new(
+            Surface::xy_plane(),
+            [Cycle::new(&stores, [circle])],
+            [],
+            [255, 0, 0, 255],
+        );
+        let sketch = Sketch::from_faces([face]);
+        let solid = sweep(
+            sketch,
+            [0., 0., 1.],
+            0.,
+            tolerance,
+            &stores,
+            [255, 0, 0, 255],
+            &mut ProgressHandle::new(),
+        )?;
+        let faces: Vec<_> = solid.into_faces().into_iter().collect();
+
+        let features = super::features(&faces, tolerance);
+
+        let radii: Vec<_> = (0..features.faces.len())
+            .filter_map(|i| features.radius(Feature::Face(i)))
+            .collect();
+        assert_eq!(radii, vec![Scalar::from(2.)]);
+
+        // The sweep adds a straight seam edge, in addition to the circles.
+        let edge_radii: Vec<_> = (0..features.edges.len())
+            .filter_map(|i| features.radius(Feature::Edge(i)))
+            .collect();
+        assert!(edge_radii.len() >= 2);
+        assert!(edge_radii.iter().all(|&radius| radius == Scalar::from(2.)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use fj_interop::debug::DebugInfo;
+use fj_math::Point;
+
+use crate::objects::{Cycle, Face, Sketch, Surface};
+
+use super::{triangulate::polygon::Polygon, CycleApprox, Tolerance};
+
+/// Imprint a sketch onto a face, splitting the face along the sketch
+///
+/// Every cycle of the sketch becomes the boundary of a new face. The first of
+/// the returned faces is what remains of the original face, with the imprinted
+/// regions cut out. It is followed by one face per cycle of the sketch. Cycles
+/// that are nested within each other result in nested faces, and holes of the
+/// original face end up in the face whose region they lie in. All returned
+/// faces have the color of the original face.
+///
+/// The cycles are approximated, according to `tolerance`, to determine how they
+/// are nested.
+///
+/// Returns an error, if the sketch is not defined in the surface of the face,
+/// or if any of its cycles isn't fully contained in the face, or intersects
+/// another cycle.
+///
+/// # Panics
+///
+/// Panics, if `face` is not in boundary representation.
+pub fn imprint(
+    face: &Face,
+    sketch: &Sketch,
+    tolerance: Tolerance,
+) -> Result<Vec<Face>, ImprintError> {
+    let face = match face {
+        Face::Face(face) => face,
+        Face::Triangles(_) => {
+            panic!("Imprinting onto tri-rep faces is not supported")
+        }
+    };
+    let surface = face.surface();
+
+    let mut cycles = Vec::new();
+    for sketch_face in sketch.faces() {
+        if sketch_face.surface() != surface {
+            return Err(ImprintError::SurfaceMismatch);
+        }
+
+        cycles.extend(
+            sketch_face
+                .all_cycles()
+                .map(|cycle| Region::new(surface, cycle, tolerance)),
+        );
+    }
+
+    let exteriors: Vec<_> = face
+        .exteriors()
+        .map(|cycle| Region::new(surface, cycle, tolerance))
+        .collect();
+    let holes: Vec<_> = face
+        .interiors()
+        .map(|cycle| Region::new(surface, cycle, tolerance))
+        .collect();
+
+    for cycle in &cycles {
+        let mut is_in_face = false;
+        for exterior in &exteriors {
+            is_in_face |= exterior.contains(cycle)?;
+        }
+        if !is_in_face {
+            return Err(ImprintError::NotContained);
+        }
+        for hole in &holes {
+            if hole.contains(cycle)? {
+                return Err(ImprintError::NotContained);
+            }
+        }
+    }
+
+    // For every cycle, find the innermost of the other cycles that contains
+    // it. That's the cycle whose face it becomes a hole of.
+    let mut containing = Vec::new();
+    for (i, cycle) in cycles.iter().enumerate() {
+        let mut containing_cycle = Vec::new();
+        for (j, other) in cycles.iter().enumerate() {
+            if i != j && other.contains(cycle)? {
+                containing_cycle.push(j);
+            }
+        }
+        containing.push(containing_cycle);
+    }
+    let innermost = |candidates: &[usize]| {
+        candidates
+            .iter()
+            .copied()
+            .max_by_key(|&j| containing[j].len())
+    };
+
+    let mut remainder_holes = Vec::new();
+    let mut cycle_holes = vec![Vec::new(); cycles.len()];
+
+    for (cycle, candidates) in cycles.iter().zip(&containing) {
+        let cycle = cycle.cycle.clone();
+        match innermost(candidates) {
+            Some(parent) => cycle_holes[parent].push(cycle),
+            None => remainder_holes.push(cycle),
+        }
+    }
+    for hole in holes {
+        let mut candidates = Vec::new();
+        for (j, cycle) in cycles.iter().enumerate() {
+            if cycle.contains(&hole)? {
+                candidates.push(j);
+            }
+        }
+
+        match innermost(&candidates) {
+            Some(parent) => cycle_holes[parent].push(hole.cycle),
+            None => remainder_holes.push(hole.cycle),
+        }
+    }
+
+    let mut faces = vec![Face::new(
+        surface,
+        face.exteriors(),
+        remainder_holes,
+        face.color,
+    )];
+    for (cycle, holes) in cycles.into_iter().zip(cycle_holes) {
+        faces.push(Face::new(surface, [cycle.cycle], holes, face.color));
+    }
+
+    Ok(faces)
+}
+
+/// Error imprinting a sketch onto a face
+#[derive(Debug, thiserror::Error)]
+pub enum ImprintError {
+    /// The sketch is not defined in the surface of the face
+    #[error("Sketch is not defined in the surface of the face")]
+    SurfaceMismatch,
+
+    /// A cycle of the sketch is not fully contained in the face
+    #[error("Sketch is not fully contained in the face")]
+    NotContained,
+
+    /// A cycle of the sketch intersects another cycle, or the face boundary
+    #[error("Sketch intersects itself or the boundary of the face")]
+    Intersection,
+}
+
+struct Region {
+    surface: Surface,
+    cycle: Cycle,
+    points: Vec<Point<2>>,
+}
+
+impl Region {
+    fn new(surface: Surface, cycle: Cycle, tolerance: Tolerance) -> Self {
+        let points = CycleApprox::new(&cycle, tolerance)
+            .points
+            .into_iter()
+            .map(|point| point.local())
+            .collect();
+
+        Self {
+            surface,
+            cycle,
+            points,
+        }
+    }
+
+    /// Determine whether `other` lies within this region
+    ///
+    /// Returns an error, if `other` lies partially within this region.
+    fn contains(&self, other: &Self) -> Result<bool, ImprintError> {
+        // `Polygon` generates debug info, which we're not interested in here.
+        let polygon =
+            Polygon::new(self.surface).with_exterior(self.points.clone());
+        let mut debug_info = DebugInfo::new();
+
+        let mut inside = 0;
+        for &point in &other.points {
+            if polygon.contains_point(point, &mut debug_info) {
+                inside += 1;
+            }
+        }
+
+        if inside == 0 {
+            Ok(false)
+        } else if inside == other.points.len() {
+            Ok(true)
+        } else {
+            Err(ImprintError::Intersection)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Face, Sketch, Stores, Surface},
+    };
+
+    use super::{imprint, ImprintError};
+
+    #[test]
+    fn imprint_nested() {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let surface = Surface::xy_plane();
+
+        let face = Face::builder(&stores, surface)
+            .with_exterior_polygon([[0., 0.], [10., 0.], [10., 10.], [0., 10.]])
+            .with_interior_polygon([[4., 4.], [6., 4.], [6., 6.], [4., 6.]])
+            .build();
+        let sketch = Sketch::from_faces([Face::builder(&stores, surface)
+            .with_exterior_polygon([[1., 1.], [9., 1.], [9., 9.], [1., 9.]])
+            .with_interior_polygon([[2., 2.], [8., 2.], [8., 8.], [2., 8.]])
+            .build()]);
+
+        let faces = imprint(&face, &sketch, tolerance).unwrap();
+        let [remainder, between, innermost]: [Face; 3] =
+            faces.try_into().unwrap();
+
+        let [sketch_exterior, sketch_interior]: [_; 2] = sketch
+            .faces()
+            .flat_map(|face| face.all_cycles())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        // The remainder of the face only has the outer sketch cycle as a hole.
+        // The region between the sketch cycles has the inner one, and the
+        // innermost region has inherited the original hole.
+        assert!(remainder.exteriors().eq(face.exteriors()));
+        assert!(remainder.interiors().eq([sketch_exterior.clone()]));
+        assert!(between.exteriors().eq([sketch_exterior]));
+        assert!(between.interiors().eq([sketch_interior.clone()]));
+        assert!(innermost.exteriors().eq([sketch_interior]));
+        assert!(innermost.interiors().eq(face.interiors()));
+    }
+
+    #[test]
+    fn imprint_invalid() {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let surface = Surface::xy_plane();
+
+        let face = Face::builder(&stores, surface)
+            .with_exterior_polygon([[0., 0.], [10., 0.], [10., 10.], [0., 10.]])
+            .build();
+
+        let outside = Sketch::from_faces([Face::builder(&stores, surface)
+            .with_exterior_polygon([[11., 0.], [12., 0.], [12., 1.]])
+            .build()]);
+        assert!(matches!(
+            imprint(&face, &outside, tolerance),
+            Err(ImprintError::NotContained)
+        ));
+
+        let crossing = Sketch::from_faces([Face::builder(&stores, surface)
+            .with_exterior_polygon([[5., 5.], [15., 5.], [15., 6.]])
+            .build()]);
+        assert!(matches!(
+            imprint(&face, &crossing, tolerance),
+            Err(ImprintError::Intersection)
+        ));
+
+        let other_surface =
+            Sketch::from_faces([Face::builder(&stores, Surface::xz_plane())
+                .with_exterior_polygon([[1., 1.], [2., 1.], [2., 2.]])
+                .build()]);
+        assert!(matches!(
+            imprint(&face, &other_surface, tolerance),
+            Err(ImprintError::SurfaceMismatch)
+        ));
+    }
+}
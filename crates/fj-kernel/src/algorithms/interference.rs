@@ -0,0 +1,277 @@
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::objects::{Face, Solid};
+
+use super::{triangulate, BoundingVolume, Tolerance, TriangulationConfig};
+
+/// The interference between two solids
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Interference {
+    /// The pairs of faces that intersect each other
+    ///
+    /// The face of the first solid comes first. Faces that merely touch, like
+    /// the faces of two solids that are placed next to each other, are not
+    /// included.
+    pub face_pairs: Vec<[Face; 2]>,
+
+    /// Indicates whether one of the solids is inside of the other
+    ///
+    /// Only checked, if no faces intersect each other. Solids that are
+    /// identical, or that share some faces, count as one being inside of the
+    /// other.
+    pub containment: Option<Containment>,
+}
+
+impl Interference {
+    /// Indicate whether the solids overlap
+    pub fn overlaps(&self) -> bool {
+        !self.face_pairs.is_empty() || self.containment.is_some()
+    }
+}
+
+/// Which of two solids is inside of the other
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Containment {
+    /// The first solid is inside of the second one
+    FirstInSecond,
+
+    /// The second solid is inside of the first one
+    SecondInFirst,
+}
+
+/// Check whether two solids interfere with each other
+///
+/// This is useful to validate an assembly of solids, which shouldn't overlap.
+/// The faces of the solids are approximated, according to `tolerance`, before
+/// being checked against each other.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn interference(
+    a: &Solid,
+    b: &Solid,
+    tolerance: Tolerance,
+) -> Interference {
+    let faces_a = faces(a, tolerance);
+    let faces_b = faces(b, tolerance);
+
+    let mut face_pairs = Vec::new();
+    for (face_a, aabb_a, triangles_a) in &faces_a {
+        for (face_b, aabb_b, triangles_b) in &faces_b {
+            if !overlap(aabb_a, aabb_b, tolerance)
+                || !triangles_intersect(triangles_a, triangles_b, tolerance)
+            {
+                continue;
+            }
+
+            face_pairs.push([(*face_a).clone(), (*face_b).clone()]);
+        }
+    }
+
+    // If no faces intersect, the solids are either separate, or one of them
+    // is completely inside of the other. In the latter case, any point in the
+    // interior of a solid is inside of the other one.
+    let containment = if !face_pairs.is_empty() {
+        None
+    } else if matches!(
+        interior_point(&faces_a, tolerance),
+        Some(point) if contains(&faces_b, point)
+    ) {
+        Some(Containment::FirstInSecond)
+    } else if matches!(
+        interior_point(&faces_b, tolerance),
+        Some(point) if contains(&faces_a, point)
+    ) {
+        Some(Containment::SecondInFirst)
+    } else {
+        None
+    };
+
+    Interference {
+        face_pairs,
+        containment,
+    }
+}
+
+type Triangles = Vec<(Triangle<3>, Aabb<3>)>;
+
+/// Triangulate the faces of a solid
+fn faces(
+    solid: &Solid,
+    tolerance: Tolerance,
+) -> Vec<(&Face, Aabb<3>, Triangles)> {
+    solid
+        .faces()
+        .filter_map(|face| {
+            let aabb = face.aabb(tolerance)?;
+
+            let mesh = triangulate(
+                vec![face.clone()],
+                tolerance,
+                &TriangulationConfig::default(),
+                &mut DebugInfo::new(),
+                &mut ProgressHandle::new(),
+            )
+            .expect("Triangulation can't be cancelled without token");
+
+            let triangles = mesh
+                .triangles()
+                .filter_map(|triangle| {
+                    let [a, b, c] = triangle.points;
+                    if (b - a).cross(&(c - a)).magnitude() == Scalar::ZERO {
+                        return None;
+                    }
+
+                    Some((
+                        Triangle::from_points(triangle.points),
+                        Aabb::<3>::from_points(triangle.points),
+                    ))
+                })
+                .collect();
+
+            Some((face, aabb, triangles))
+        })
+        .collect()
+}
+
+/// Determine whether two bounding boxes overlap, allowing for the tolerance
+fn overlap(a: &Aabb<3>, b: &Aabb<3>, tolerance: Tolerance) -> bool {
+    (0..3).all(|i| {
+        a.min.coords.components[i]
+            <= b.max.coords.components[i] + tolerance.inner()
+            && b.min.coords.components[i]
+                <= a.max.coords.components[i] + tolerance.inner()
+    })
+}
+
+/// Determine whether any triangles of two sets cross each other
+fn triangles_intersect(
+    a: &Triangles,
+    b: &Triangles,
+    tolerance: Tolerance,
+) -> bool {
+    a.iter().any(|(triangle_a, aabb_a)| {
+        b.iter().any(|(triangle_b, aabb_b)| {
+            overlap(aabb_a, aabb_b, tolerance)
+                && crosses(triangle_a, triangle_b, tolerance)
+                && crosses(triangle_b, triangle_a, tolerance)
+                && {
+                    let [p, q] = triangle_a.closest_points(triangle_b);
+                    p == q
+                }
+        })
+    })
+}
+
+/// Determine whether a triangle has points on both sides of another's plane
+///
+/// Points that are closer to the plane than the tolerance are considered to
+/// be on the plane. This prevents triangles that merely touch from being
+/// considered intersecting.
+fn crosses(a: &Triangle<3>, b: &Triangle<3>, tolerance: Tolerance) -> bool {
+    let normal = b.normal();
+    let origin = b.points()[0];
+
+    let distances = a.points().map(|point| (point - origin).dot(&normal));
+
+    distances.iter().any(|&d| d > tolerance.inner())
+        && distances.iter().any(|&d| d < -tolerance.inner())
+}
+
+/// Find a point in the interior of a solid
+///
+/// Returns a point just behind one of the triangles of the solid. Returns
+/// `None`, if the solid is empty.
+fn interior_point(
+    faces: &[(&Face, Aabb<3>, Triangles)],
+    tolerance: Tolerance,
+) -> Option<Point<3>> {
+    let (triangle, _) = faces
+        .iter()
+        .flat_map(|(_, _, triangles)| triangles)
+        .next()?;
+
+    let [a, b, c] = triangle.points();
+    let center = a + ((b - a) + (c - a)) / 3.;
+
+    // We don't know which way the triangle is facing, so we check which side
+    // of it is inside of the solid.
+    let offset = triangle.normal() * tolerance.inner();
+    [center - offset, center + offset]
+        .into_iter()
+        .find(|&point| contains(faces, point))
+}
+
+/// Determine whether a solid contains a point
+///
+/// Counts how often a ray, cast from the point, crosses the boundary of the
+/// solid. The direction of the ray is chosen such that it is unlikely to hit
+/// an edge or vertex.
+fn contains(faces: &[(&Face, Aabb<3>, Triangles)], point: Point<3>) -> bool {
+    let direction = Vector::from([1., 0.3172, 0.1423]).normalize();
+
+    let hits = faces
+        .iter()
+        .flat_map(|(_, _, triangles)| triangles)
+        .filter(|(triangle, _)| {
+            triangle
+                .cast_local_ray(point, direction, f64::INFINITY, true)
+                .is_some()
+        })
+        .count();
+
+    hits % 2 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Transform, Vector};
+
+    use crate::{
+        algorithms::{Tolerance, TransformObject},
+        objects::{Solid, Stores},
+    };
+
+    use super::{interference, Containment};
+
+    #[test]
+    fn interference_() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let translated = |offset: [f64; 3]| {
+            cube.clone().transform(&Transform::translation(offset))
+        };
+
+        // Separate cubes.
+        let result = interference(&cube, &translated([2., 0., 0.]), tolerance);
+        assert!(!result.overlaps());
+
+        // Cubes that touch.
+        let result = interference(&cube, &translated([1., 0., 0.]), tolerance);
+        assert!(!result.overlaps());
+
+        // Overlapping cubes.
+        let other = translated([0.5, 0.5, 0.])
+            .transform(&Transform::rotation(Vector::unit_z() * 0.1));
+        let result = interference(&cube, &other, tolerance);
+        assert!(result.overlaps());
+        assert!(!result.face_pairs.is_empty());
+
+        // A cube inside of another.
+        let small = Solid::cube_from_edge_length(&stores, 0.5);
+        let result = interference(&small, &cube, tolerance);
+        assert!(result.face_pairs.is_empty());
+        assert_eq!(result.containment, Some(Containment::FirstInSecond));
+
+        let result = interference(&cube, &small, tolerance);
+        assert_eq!(result.containment, Some(Containment::SecondInFirst));
+
+        // Identical cubes.
+        let result = interference(&cube, &cube, tolerance);
+        assert!(result.overlaps());
+
+        Ok(())
+    }
+}
@@ -184,12 +184,14 @@ pub type CurveFaceIntersection = [Scalar; 2];
 mod tests {
     use fj_math::{Line, Point, Vector};
 
-    use crate::objects::{Curve, Face, Surface};
+    use crate::objects::{Curve, Face, Stores, Surface};
 
     use super::CurveFaceIntersectionList;
 
     #[test]
     fn compute() {
+        let stores = Stores::new();
+
         let curve = Curve::Line(Line {
             origin: Point::from([-3., 0.]),
             direction: Vector::from([1., 0.]),
@@ -210,7 +212,7 @@ mod tests {
             [-1.,  1.],
         ];
 
-        let face = Face::builder(Surface::xy_plane())
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon(exterior)
             .with_interior_polygon(interior)
             .build();
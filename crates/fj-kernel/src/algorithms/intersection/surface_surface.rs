@@ -1,4 +1,4 @@
-use fj_math::{Line, Point, Scalar, Vector};
+use fj_math::{Interval, Line, Point, Scalar, Vector};
 
 use crate::objects::{Curve, Surface};
 
@@ -19,20 +19,13 @@ pub fn surface_surface(
     let a = PlaneConstantNormal::from_parametric_plane(&a_parametric);
     let b = PlaneConstantNormal::from_parametric_plane(&b_parametric);
 
-    let direction = a.normal.cross(&b.normal);
-
-    let denom = direction.dot(&direction);
-    if denom == Scalar::ZERO {
-        // Comparing `denom` against zero looks fishy. It's probably better to
-        // compare it against an epsilon value, but I don't know how large that
-        // epsilon should be.
-        //
-        // I'll just leave it like that, until we had the opportunity to collect
-        // some experience with this code.
-        // - @hannobraun
+    if may_be_parallel(&a_parametric, &b_parametric) {
         return None;
     }
 
+    let direction = a.normal.cross(&b.normal);
+    let denom = direction.dot(&direction);
+
     let origin = (b.normal * a.distance - a.normal * b.distance)
         .cross(&direction)
         / denom;
@@ -96,6 +89,32 @@ impl PlaneConstantNormal {
     }
 }
 
+/// Determine whether two planes might be parallel
+///
+/// The direction of their intersection line is computed using interval
+/// arithmetic, from the vectors that span the planes. If that direction might
+/// be zero, the planes are parallel, as far as the precision of the
+/// computation can tell. Comparing the result of a floating-point computation
+/// against zero instead would let rounding errors turn parallel planes into
+/// ones that intersect somewhere far away.
+fn may_be_parallel(a: &PlaneParametric, b: &PlaneParametric) -> bool {
+    let normal = |plane: &PlaneParametric| {
+        let [u, v] = [plane.u, plane.v]
+            .map(|vector| vector.components.map(Interval::from));
+        cross(u, v)
+    };
+
+    let direction = cross(normal(a), normal(b));
+    direction.iter().all(Interval::contains_zero)
+}
+
+fn cross(a: [Interval; 3], b: [Interval; 3]) -> [Interval; 3] {
+    let [ax, ay, az] = a;
+    let [bx, by, bz] = b;
+
+    [ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx]
+}
+
 fn project_line_into_plane(
     line: &Line<3>,
     plane: &PlaneParametric,
@@ -151,6 +170,21 @@ mod tests {
             None,
         );
 
+        // Rounding errors must not make parallel planes look like they
+        // intersect.
+        let skewed = Surface::plane_from_points([
+            [0.1, 0.2, 0.3],
+            [1.7, 0.4, 2.9],
+            [0.3, 1.1, 0.5],
+        ]);
+        assert_eq!(
+            surface_surface(
+                &skewed,
+                &skewed.transform(&Transform::translation([0.7, 0.3, 0.1]))
+            ),
+            None,
+        );
+
         let expected_xy = Curve::u_axis();
         let expected_xz = Curve::u_axis();
         let expected_global = Curve::x_axis();
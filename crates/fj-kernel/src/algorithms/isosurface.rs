@@ -0,0 +1,308 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::objects::{Face, Solid};
+
+/// Mesh the isosurface of an implicit function
+///
+/// The solid is the region where `f` is negative, and its boundary is the
+/// surface where `f` is zero. `f` is sampled on a regular grid that covers
+/// `aabb`, with cells no larger than `cell_size`, and the surface is meshed
+/// using marching tetrahedra.
+///
+/// The region is cut off at the boundary of `aabb`, so the resulting solid is
+/// always closed. Its faces are in triangle representation, all with `color`,
+/// which means it can be combined and exported like any other solid.
+///
+/// Unlike a signed distance field, `f` doesn't need to return a distance. It
+/// just needs to be continuous, and have its zero-crossings in the right
+/// places. Features that are smaller than `cell_size` might get lost.
+///
+/// # Panics
+///
+/// Panics, if `cell_size` is not positive.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn isosurface(
+    f: impl Fn(Point<3>) -> f64,
+    aabb: &Aabb<3>,
+    cell_size: impl Into<Scalar>,
+    color: [u8; 4],
+) -> Solid {
+    let cell_size = cell_size.into();
+    assert!(cell_size > Scalar::ZERO, "Cell size must be positive");
+
+    let grid = Grid::new(aabb, cell_size);
+    let values = grid.sample(f);
+
+    let mut triangles = Vec::new();
+    for x in 0..grid.cells[0] {
+        for y in 0..grid.cells[1] {
+            for z in 0..grid.cells[2] {
+                let corners = CORNERS.map(|[dx, dy, dz]| {
+                    let corner = [x + dx, y + dy, z + dz];
+                    (corner, values[grid.index(corner)])
+                });
+
+                for tetrahedron in TETRAHEDRA {
+                    let tetrahedron = tetrahedron.map(|i| corners[i]);
+
+                    for points in polygonize(&grid, tetrahedron) {
+                        let [a, b, c] = points;
+                        if (b - a).cross(&(c - a)).magnitude() == Scalar::ZERO {
+                            continue;
+                        }
+
+                        triangles.push((Triangle::from_points(points), color));
+                    }
+                }
+            }
+        }
+    }
+
+    Solid::from_faces([Face::Triangles(triangles)])
+}
+
+/// A regular grid that covers a bounding box
+struct Grid {
+    origin: Point<3>,
+    cell_size: Vector<3>,
+    cells: [usize; 3],
+}
+
+impl Grid {
+    fn new(aabb: &Aabb<3>, cell_size: Scalar) -> Self {
+        let size = aabb.size();
+
+        // Round the number of cells up, so the cells are no larger than
+        // requested, then shrink them to fit the bounding box exactly.
+        let cells = size.components.map(|extent| {
+            let cells = (extent / cell_size).ceil().into_u64() as usize;
+            cells.max(1)
+        });
+        let cell_size = Vector::from(
+            [0, 1, 2]
+                .map(|i| size.components[i] / Scalar::from(cells[i] as f64)),
+        );
+
+        Self {
+            origin: aabb.min,
+            cell_size,
+            cells,
+        }
+    }
+
+    /// Sample `f` at all corners of the grid
+    ///
+    /// Corners on the boundary of the grid are treated as being outside, so
+    /// the surface is closed there.
+    fn sample(&self, f: impl Fn(Point<3>) -> f64) -> Vec<f64> {
+        let [nx, ny, nz] = self.cells;
+        let mut values = Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1));
+
+        for x in 0..=nx {
+            for y in 0..=ny {
+                for z in 0..=nz {
+                    let value = f(self.point([x, y, z]));
+
+                    let boundary = x == 0
+                        || y == 0
+                        || z == 0
+                        || x == nx
+                        || y == ny
+                        || z == nz;
+                    let value = if boundary {
+                        value.max(f64::EPSILON)
+                    } else {
+                        value
+                    };
+
+                    values.push(value);
+                }
+            }
+        }
+
+        values
+    }
+
+    fn index(&self, [x, y, z]: [usize; 3]) -> usize {
+        let [_, ny, nz] = self.cells;
+        (x * (ny + 1) + y) * (nz + 1) + z
+    }
+
+    fn point(&self, corner: [usize; 3]) -> Point<3> {
+        let offset = [0, 1, 2].map(|i| {
+            self.cell_size.components[i] * Scalar::from(corner[i] as f64)
+        });
+        self.origin + Vector::from(offset)
+    }
+}
+
+/// Triangulate the part of the surface that passes through a tetrahedron
+///
+/// Takes the grid corners of the tetrahedron, and the values of the function
+/// at those corners.
+fn polygonize(
+    grid: &Grid,
+    tetrahedron: [([usize; 3], f64); 4],
+) -> Vec<[Point<3>; 3]> {
+    let (inside, outside): (Vec<_>, Vec<_>) =
+        tetrahedron.into_iter().partition(|(_, value)| *value < 0.);
+
+    // The points where the surface crosses the edges of the tetrahedron. Each
+    // point is computed from the ordered corners of its edge, so neighboring
+    // cells compute identical points.
+    let crossing = |a: ([usize; 3], f64), b: ([usize; 3], f64)| {
+        let (a, b) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        let [pa, pb] = [a.0, b.0].map(|corner| grid.point(corner));
+        pa + (pb - pa) * (a.1 / (a.1 - b.1))
+    };
+
+    let polygon = match (inside.as_slice(), outside.as_slice()) {
+        ([a], [b, c, d]) | ([b, c, d], [a]) => {
+            vec![crossing(*a, *b), crossing(*a, *c), crossing(*a, *d)]
+        }
+        ([a, b], [c, d]) => vec![
+            crossing(*a, *c),
+            crossing(*a, *d),
+            crossing(*b, *d),
+            crossing(*b, *c),
+        ],
+        _ => return Vec::new(),
+    };
+
+    // The triangles need to face outwards, from the inside to the outside
+    // corners.
+    let centroid = |corners: &[([usize; 3], f64)]| {
+        let sum = corners
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, (corner, _)| {
+                sum + grid.point(*corner).coords
+            });
+        sum / corners.len() as f64
+    };
+    let outwards = centroid(&outside) - centroid(&inside);
+
+    polygon[1..]
+        .windows(2)
+        .map(|edge| {
+            let [a, b, c] = [polygon[0], edge[0], edge[1]];
+            if (b - a).cross(&(c - a)).dot(&outwards) < Scalar::ZERO {
+                [a, c, b]
+            } else {
+                [a, b, c]
+            }
+        })
+        .collect()
+}
+
+/// The corners of a cell, relative to its first corner
+const CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+
+/// The tetrahedra that a cell is split into, as indices into [`CORNERS`]
+///
+/// All tetrahedra share the diagonal from the first to the last corner. As all
+/// cells are split the same way, the faces of neighboring cells match up.
+const TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 1, 5, 7],
+    [0, 2, 3, 7],
+    [0, 2, 6, 7],
+    [0, 4, 5, 7],
+    [0, 4, 6, 7],
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fj_math::{Aabb, Point};
+
+    use crate::objects::{Face, Solid};
+
+    use super::isosurface;
+
+    #[test]
+    fn sphere() {
+        let aabb = Aabb {
+            min: Point::from([-1.5, -1.5, -1.5]),
+            max: Point::from([1.5, 1.5, 1.5]),
+        };
+        let solid = isosurface(
+            |point| point.coords.magnitude().into_f64() - 1.,
+            &aabb,
+            0.1,
+            [255, 0, 0, 255],
+        );
+
+        let expected = 4. / 3. * std::f64::consts::PI;
+        let volume = volume(&solid);
+        assert!((volume - expected).abs() < 0.05, "{volume} != {expected}");
+
+        assert_closed(&solid);
+    }
+
+    #[test]
+    fn clipped() {
+        // The function is negative everywhere within the bounding box, so the
+        // solid is the bounding box itself, except for its sharp edges, which
+        // are cut off.
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([2., 1., 1.]),
+        };
+        let solid = isosurface(|_| -1., &aabb, 0.1, [255, 0, 0, 255]);
+
+        for [a, b, c] in triangles(&solid) {
+            assert!([a, b, c].into_iter().all(|point| aabb.contains(point)));
+        }
+
+        let volume = volume(&solid);
+        assert!(volume < 2. && volume > 1.9, "{volume}");
+
+        assert_closed(&solid);
+    }
+
+    fn triangles(solid: &Solid) -> Vec<[Point<3>; 3]> {
+        solid
+            .faces()
+            .flat_map(|face| match face {
+                Face::Triangles(triangles) => triangles
+                    .iter()
+                    .map(|(triangle, _)| triangle.points())
+                    .collect::<Vec<_>>(),
+                Face::Face(_) => panic!("Expected triangle representation"),
+            })
+            .collect()
+    }
+
+    fn volume(solid: &Solid) -> f64 {
+        triangles(solid)
+            .into_iter()
+            .map(|[a, b, c]| {
+                a.coords.dot(&b.coords.cross(&c.coords)).into_f64() / 6.
+            })
+            .sum()
+    }
+
+    /// Check that each edge is shared by two triangles, in opposite directions
+    fn assert_closed(solid: &Solid) {
+        let mut edges: HashMap<[Point<3>; 2], i32> = HashMap::new();
+        for [a, b, c] in triangles(solid) {
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                *edges.entry([p, q]).or_default() += 1;
+                *edges.entry([q, p]).or_default() -= 1;
+            }
+        }
+
+        let open = edges.values().filter(|&&count| count != 0).count();
+        assert_eq!(open, 0);
+    }
+}
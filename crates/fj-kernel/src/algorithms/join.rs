@@ -0,0 +1,149 @@
+use fj_math::{Point, Scalar};
+
+use crate::objects::{Curve, Edge, Vertex, VerticesOfEdge};
+
+use super::Tolerance;
+
+/// Join two adjacent edges that lie on the same curve
+///
+/// `b` must start where `a` ends, and continue along `a`'s curve, within
+/// `tolerance`. This is the case for collinear line segments, and for arcs on
+/// the same circle, even if their curves are distinct objects. Both edges must
+/// be defined on the same surface.
+///
+/// Returns an edge on `a`'s curve, that runs from `a`'s first vertex to `b`'s
+/// second vertex. This is the inverse of [`split_edge`].
+///
+/// Returns `None`, if the edges can't be joined. This includes the case where
+/// joining them would result in a closed edge, which would have to be
+/// represented by an edge without vertices.
+///
+/// [`split_edge`]: super::split_edge
+pub fn join_edges(a: &Edge, b: &Edge, tolerance: Tolerance) -> Option<Edge> {
+    let [start, a_end] = a.vertices()?;
+    let [b_start, end] = b.vertices()?;
+
+    if a_end.global() != b_start.global() || start.global() == end.global() {
+        return None;
+    }
+
+    let curve = a.curve();
+    let [t_start, t_joint] = [start.position().t, a_end.position().t];
+    let direction = if t_joint < t_start {
+        -Scalar::ONE
+    } else {
+        Scalar::ONE
+    };
+
+    // Compute the position of a point of `b` on the curve of `a`, as the
+    // distance from the joint, in the direction of `a`.
+    let offset = |point: Point<3>| {
+        let t = curve.point_to_curve_coords(point).t;
+
+        let distance = (curve.point_from_curve_coords([t]) - point).magnitude();
+        if distance > tolerance.inner() {
+            return None;
+        }
+
+        let offset = (t - t_joint) * direction;
+        Some(match curve {
+            Curve::Circle(_) => {
+                let tau = Scalar::PI * 2.;
+                let offset = offset % tau;
+                if offset < Scalar::ZERO {
+                    offset + tau
+                } else {
+                    offset
+                }
+            }
+            Curve::Line(_) => offset,
+        })
+    };
+
+    let [t_b_start, t_b_end] = [b_start.position().t, end.position().t];
+    let middle = b
+        .curve()
+        .point_from_curve_coords([(t_b_start + t_b_end) / 2.]);
+
+    // `b` must continue in the direction of `a`, instead of doubling back.
+    let offset_of_middle = offset(middle)?;
+    let offset_of_end = offset(end.global().position())?;
+    if offset_of_middle <= Scalar::ZERO || offset_of_end <= offset_of_middle {
+        return None;
+    }
+
+    let t_end = t_joint + offset_of_end * direction;
+    if let Curve::Circle(_) = curve {
+        if (t_end - t_start).abs() >= Scalar::PI * 2. {
+            return None;
+        }
+    }
+
+    Some(Edge {
+        curve: a.curve.clone(),
+        vertices: VerticesOfEdge::from_vertices([
+            start,
+            Vertex::new([t_end], end.global().clone()),
+        ]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{split_edge, Tolerance},
+        objects::{Edge, Stores, Surface},
+    };
+
+    use super::join_edges;
+
+    #[test]
+    fn join_lines() {
+        let stores = Stores::new();
+        let surface = Surface::xy_plane();
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let [a, b, c, d] = [
+            [[0., 0.], [1., 0.]],
+            [[1., 0.], [2., 0.]],
+            [[2., 0.], [2., 1.]],
+            [[1., 0.], [0.5, 0.]],
+        ]
+        .map(|points| {
+            Edge::line_segment_from_points(&stores, &surface, points)
+        });
+
+        let joined = join_edges(&a, &b, tolerance).unwrap();
+        assert_eq!(joined.curve, a.curve);
+        let [start, end] = joined.vertices.expect_vertices();
+        assert_eq!(&start, &a.vertices().unwrap()[0]);
+        assert_eq!(end.position().t.into_f64(), 2.);
+        assert!(end.global().is_same(b.vertices().unwrap()[1].global()));
+
+        // Not collinear.
+        assert!(join_edges(&b, &c, tolerance).is_none());
+
+        // Not adjacent.
+        assert!(join_edges(&b, &a, tolerance).is_none());
+
+        // Doubling back.
+        assert!(join_edges(&a, &d, tolerance).is_none());
+    }
+
+    #[test]
+    fn split_and_join() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let edge = Edge::line_segment_from_points(
+            &stores,
+            &Surface::xy_plane(),
+            [[0., 0.], [2., 0.]],
+        );
+
+        let [a, b] = split_edge(&edge, [0.25])?;
+        assert_eq!(join_edges(&a, &b, tolerance), Some(edge));
+
+        Ok(())
+    }
+}
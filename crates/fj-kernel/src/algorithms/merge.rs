@@ -0,0 +1,369 @@
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    local::Local,
+    objects::{Curve, Cycle, Edge, Face, Handle, Surface, Winding},
+};
+
+use super::Tolerance;
+
+/// Merge adjacent faces that lie in the same plane
+///
+/// Two faces are merged, if their surfaces are coplanar within `tolerance`,
+/// face in the same direction, and the faces share at least one edge, which
+/// they run along in opposite directions. The shared edges are removed, and
+/// the remaining edges of both faces become the cycles of the merged face.
+/// Faces are merged repeatedly, so any number of adjacent faces can end up in
+/// a single face.
+///
+/// Faces that surround an area that neither of them covers result in a face
+/// with a new interior cycle. The merged face is defined in the surface of
+/// one of the original faces.
+///
+/// Only faces of the same color are merged. Faces that are not in boundary
+/// representation, or that are not planar, are returned unchanged.
+///
+/// # Limitations
+///
+/// Shared edges must match exactly, meaning they must be bounded by the same
+/// global vertices. Edges that only partially overlap need to be split first,
+/// using [`split_edge`]. Edges that end up collinear after the merge are not
+/// joined, as other faces might still refer to the vertex between them.
+///
+/// [`split_edge`]: super::split_edge
+pub fn merge_coplanar_faces(
+    mut faces: Vec<Face>,
+    tolerance: Tolerance,
+) -> Vec<Face> {
+    let mut i = 0;
+    while i < faces.len() {
+        let mut j = i + 1;
+        while j < faces.len() {
+            match merge(&faces[i], &faces[j], tolerance) {
+                Some(merged) => {
+                    // The merged face might be adjacent to faces that have
+                    // been checked already, so start over.
+                    faces[i] = merged;
+                    faces.remove(j);
+                    j = i + 1;
+                }
+                None => j += 1,
+            }
+        }
+
+        i += 1;
+    }
+
+    faces
+}
+
+/// Merge two faces, if possible
+fn merge(a: &Face, b: &Face, tolerance: Tolerance) -> Option<Face> {
+    let (a, b) = match (a, b) {
+        (Face::Face(a), Face::Face(b)) => (a, b),
+        _ => return None,
+    };
+
+    if a.color != b.color || !are_coplanar(&a.surface, &b.surface, tolerance) {
+        return None;
+    }
+
+    let [a_exterior, b_exterior] = [a, b].map(|face| {
+        let mut exteriors = face.exteriors();
+        match (exteriors.next(), exteriors.next()) {
+            (Some(exterior), None) => Some(exterior),
+            _ => None,
+        }
+    });
+    let (a_exterior, b_exterior) = (a_exterior?, b_exterior?);
+
+    // From here on, everything is in the surface of `a`.
+    let b_exterior = convert_cycle(&b_exterior, &b.surface, &a.surface);
+
+    let mut shared = Vec::new();
+    for (i, edge) in a_exterior.edges.iter().enumerate() {
+        for (j, other) in b_exterior.edges.iter().enumerate() {
+            if are_opposite(edge, other, tolerance) {
+                shared.push((i, j));
+            }
+        }
+    }
+    if shared.is_empty() {
+        return None;
+    }
+
+    let remaining = a_exterior
+        .edges
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !shared.iter().any(|(shared, _)| shared == i))
+        .map(|(_, edge)| edge.clone())
+        .chain(
+            b_exterior
+                .edges
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| !shared.iter().any(|(_, shared)| shared == j))
+                .map(|(_, edge)| edge.clone()),
+        )
+        .collect();
+
+    let mut exterior = None;
+    let mut interiors: Vec<_> = a.interiors().collect();
+    interiors.extend(
+        b.interiors()
+            .map(|cycle| convert_cycle(&cycle, &b.surface, &a.surface)),
+    );
+
+    for cycle in chain_cycles(remaining)? {
+        match cycle.winding() {
+            Winding::CounterClockwise if exterior.is_none() => {
+                exterior = Some(cycle);
+            }
+            Winding::CounterClockwise => return None,
+            Winding::Clockwise => interiors.push(cycle),
+        }
+    }
+
+    let mut merged = Face::new(a.surface, [exterior?], interiors, a.color);
+    for tolerance in [a.tolerance, b.tolerance].into_iter().flatten() {
+        merged = merged.with_tolerance(tolerance);
+    }
+
+    Some(merged)
+}
+
+/// Check whether two surfaces are coplanar, and face in the same direction
+fn are_coplanar(a: &Surface, b: &Surface, tolerance: Tolerance) -> bool {
+    let plane = |surface: &Surface| {
+        let Surface::SweptCurve(surface) = surface;
+        match surface.curve {
+            Curve::Line(line) => Some((
+                line.origin,
+                line.direction.cross(&surface.path).normalize(),
+            )),
+            Curve::Circle(_) => None,
+        }
+    };
+
+    let ((origin_a, normal_a), (origin_b, normal_b)) =
+        match (plane(a), plane(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+
+    let is_parallel = (normal_a - normal_b).magnitude() <= tolerance.inner();
+    let distance = (origin_b - origin_a).dot(&normal_a).abs();
+
+    is_parallel && distance <= tolerance.inner()
+}
+
+/// Check whether two edges are bounded by the same vertices, in opposite order
+fn are_opposite(a: &Edge, b: &Edge, tolerance: Tolerance) -> bool {
+    let (a_vertices, b_vertices) = match (a.vertices(), b.vertices()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+    let [a_start, a_end] =
+        a_vertices.clone().map(|vertex| vertex.global().clone());
+    let [b_start, b_end] =
+        b_vertices.clone().map(|vertex| vertex.global().clone());
+
+    if a_start != b_end || a_end != b_start {
+        return false;
+    }
+
+    // Two arcs can be bounded by the same vertices, without being the same.
+    let middle = |edge: &Edge, [start, end]: [Scalar; 2]| {
+        edge.curve().point_from_curve_coords([(start + end) / 2.])
+    };
+    let [a_middle, b_middle] =
+        [(a, a_vertices), (b, b_vertices)].map(|(edge, vertices)| {
+            middle(edge, vertices.map(|vertex| vertex.position().t))
+        });
+
+    (a_middle - b_middle).magnitude() <= tolerance.inner()
+}
+
+/// Chain edges into cycles, by connecting each edge to the one that follows
+///
+/// Returns `None`, if the edges don't form cycles, or if that is ambiguous,
+/// because more than two edges meet at a vertex.
+fn chain_cycles(mut edges: Vec<Handle<Edge>>) -> Option<Vec<Cycle>> {
+    let start_of = |edge: &Edge| edge.vertices().map(|[start, _]| start);
+    let end_of = |edge: &Edge| edge.vertices().map(|[_, end]| end);
+
+    let mut cycles = Vec::new();
+    while !edges.is_empty() {
+        let first = edges.remove(0);
+        let start = start_of(&first)?.global().clone();
+        let mut end = end_of(&first)?.global().clone();
+
+        let mut cycle = vec![first];
+        while end != start {
+            let mut next = edges.iter().enumerate().filter(|(_, edge)| {
+                matches!(start_of(edge), Some(vertex) if *vertex.global() == end)
+            });
+
+            let i = match (next.next(), next.next()) {
+                (Some((i, _)), None) => i,
+                _ => return None,
+            };
+
+            let edge = edges.remove(i);
+            end = end_of(&edge)?.global().clone();
+            cycle.push(edge);
+        }
+
+        cycles.push(Cycle { edges: cycle });
+    }
+
+    Some(cycles)
+}
+
+/// Convert a cycle from the coordinates of one surface into those of another
+///
+/// Both surfaces must be coplanar.
+fn convert_cycle(cycle: &Cycle, from: &Surface, to: &Surface) -> Cycle {
+    if from == to {
+        return cycle.clone();
+    }
+
+    let convert_point = |point: Point<2>| {
+        to.point_to_surface_coords(from.point_from_surface_coords(point))
+    };
+    let convert_vector = |origin: Point<2>, vector: Vector<2>| {
+        convert_point(origin + vector) - convert_point(origin)
+    };
+
+    let edges = cycle
+        .edges
+        .iter()
+        .map(|edge| {
+            let local = match edge.curve.local() {
+                Curve::Circle(mut circle) => {
+                    circle.a = convert_vector(circle.center, circle.a);
+                    circle.b = convert_vector(circle.center, circle.b);
+                    circle.center = convert_point(circle.center);
+                    Curve::Circle(circle)
+                }
+                Curve::Line(mut line) => {
+                    line.direction =
+                        convert_vector(line.origin, line.direction);
+                    line.origin = convert_point(line.origin);
+                    Curve::Line(line)
+                }
+            };
+
+            edge.store().insert(Edge {
+                curve: Local::new(local, edge.curve.global()),
+                vertices: edge.vertices.clone(),
+            })
+        })
+        .collect();
+
+    Cycle { edges }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Face, Stores, Surface, Winding},
+    };
+
+    use super::merge_coplanar_faces;
+
+    #[test]
+    fn merge_squares() {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(1e-9).unwrap();
+
+        let square = |surface: Surface, offset: f64| {
+            Face::builder(&stores, surface)
+                .with_exterior_polygon([
+                    [offset, 0.],
+                    [offset + 1., 0.],
+                    [offset + 1., 1.],
+                    [offset, 1.],
+                ])
+                .build()
+        };
+
+        // Both coplanar, but in different surfaces, like after a pattern.
+        let a = square(Surface::xy_plane(), 0.);
+        let b = square(
+            Surface::plane_from_points([
+                [1., 0., 0.],
+                [2., 0., 0.],
+                [1., 1., 0.],
+            ]),
+            0.,
+        );
+
+        // Not adjacent to either.
+        let c = square(Surface::xy_plane(), 5.);
+
+        // Adjacent, but facing the other way.
+        let d = Face::builder(&stores, Surface::xy_plane().reverse())
+            .with_exterior_polygon([[-1., 0.], [0., 0.], [0., -1.], [-1., -1.]])
+            .build();
+
+        let merged =
+            merge_coplanar_faces(vec![a, b, c.clone(), d.clone()], tolerance);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[1], c);
+        assert_eq!(merged[2], d);
+
+        let merged = &merged[0];
+        let exteriors: Vec<_> = merged.exteriors().collect();
+        assert_eq!(exteriors.len(), 1);
+        assert_eq!(exteriors[0].edges.len(), 6);
+        assert_eq!(exteriors[0].winding(), Winding::CounterClockwise);
+        assert_eq!(merged.interiors().count(), 0);
+    }
+
+    #[test]
+    fn merge_into_ring() {
+        let stores = Stores::new();
+        let surface = Surface::xy_plane();
+        let tolerance = Tolerance::from_scalar(1e-9).unwrap();
+
+        // Two U-shaped faces, which enclose a square hole together.
+        let bottom = Face::builder(&stores, surface)
+            .with_exterior_polygon([
+                [0., 0.],
+                [3., 0.],
+                [3., 1.],
+                [2., 1.],
+                [1., 1.],
+                [0., 1.],
+            ])
+            .build();
+        let left = Face::builder(&stores, surface)
+            .with_exterior_polygon([[0., 1.], [1., 1.], [1., 2.], [0., 2.]])
+            .build();
+        let right = Face::builder(&stores, surface)
+            .with_exterior_polygon([[2., 1.], [3., 1.], [3., 2.], [2., 2.]])
+            .build();
+        let top = Face::builder(&stores, surface)
+            .with_exterior_polygon([
+                [0., 2.],
+                [1., 2.],
+                [2., 2.],
+                [3., 2.],
+                [3., 3.],
+                [0., 3.],
+            ])
+            .build();
+
+        let merged =
+            merge_coplanar_faces(vec![bottom, top, left, right], tolerance);
+        assert_eq!(merged.len(), 1);
+
+        let interiors: Vec<_> = merged[0].interiors().collect();
+        assert_eq!(interiors.len(), 1);
+        assert_eq!(interiors[0].edges.len(), 4);
+        assert_eq!(interiors[0].winding(), Winding::Clockwise);
+    }
+}
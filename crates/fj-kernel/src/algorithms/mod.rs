@@ -4,17 +4,59 @@
 //! on their respective purpose.
 
 mod approx;
+mod arc_length;
+mod boolean;
+mod bounding_volume;
+mod compare;
+mod features;
+mod imprint;
+mod interference;
+mod isosurface;
+mod join;
+mod merge;
+mod offset;
+mod project;
 mod reverse;
+mod section;
+mod split;
 mod sweep;
+mod thicken;
 mod transform;
 mod triangulate;
+mod volume;
+mod wireframe;
 
+pub mod distance;
 pub mod intersection;
+pub mod numeric;
 
 pub use self::{
-    approx::{CycleApprox, FaceApprox, InvalidTolerance, Tolerance},
+    approx::{
+        ApproxCache, CycleApprox, EdgeApprox, FaceApprox, InvalidTolerance,
+        Tolerance, ToleranceStack,
+    },
+    arc_length::ArcLength,
+    boolean::{boolean, BooleanBackend, BooleanOperation},
+    bounding_volume::BoundingVolume,
+    compare::ApproxEq,
+    features::features,
+    imprint::{imprint, ImprintError},
+    interference::{interference, Containment, Interference},
+    isosurface::isosurface,
+    join::join_edges,
+    merge::merge_coplanar_faces,
+    offset::offset,
+    project::project_curve,
     reverse::reverse_face,
-    sweep::sweep,
+    section::{section, Section},
+    split::{split_edge, split_edge_at_point, SplitError},
+    sweep::{sweep, sweep_named, SweepError},
+    thicken::{thicken, ThickenSide},
     transform::{transform_faces, TransformObject},
-    triangulate::triangulate,
+    triangulate::{
+        triangulate, triangulate_faces, TriangulationConfig,
+        TriangulationStrategy,
+    },
+    volume::volume,
+    wireframe::wireframe,
 };
@@ -0,0 +1,275 @@
+use fj_math::Scalar;
+
+use super::{NumericError, SolverConfig};
+
+/// Find a local minimum of a function within an interval, using Brent's method
+///
+/// Combines golden-section search with parabolic interpolation. If the function
+/// has more than one local minimum within the interval, any of them might be
+/// returned. A minimum at one of the ends of the interval is approached, but
+/// never reached exactly.
+///
+/// The position of a minimum can't be determined more precisely than about the
+/// square root of the machine precision, relative to its magnitude, so that is
+/// the best precision to expect, regardless of the configured tolerance.
+///
+/// The algorithm follows the description in Numerical Recipes, section 10.3.
+pub fn minimize_scalar(
+    f: impl Fn(Scalar) -> Scalar,
+    interval: [impl Into<Scalar>; 2],
+    config: &SolverConfig,
+) -> Result<Scalar, NumericError> {
+    // The fraction of an interval that golden-section search steps into it
+    const GOLDEN: f64 = 0.381_966_011_250_105;
+
+    let f = |x: f64| f(Scalar::from_f64(x)).into_f64();
+    let tolerance = config.tolerance.into_f64();
+
+    let [a, b] = interval.map(|x| x.into().into_f64());
+    let [mut a, mut b] = [a.min(b), a.max(b)];
+
+    // `x` is the point with the smallest function value so far, `w` the one
+    // with the second-smallest, and `v` the previous value of `w`.
+    let mut x = a + GOLDEN * (b - a);
+    let [mut w, mut v] = [x, x];
+    let mut fx = f(x);
+    let [mut fw, mut fv] = [fx, fx];
+
+    // The step taken in the current and the previous iteration
+    let mut d: f64 = 0.;
+    let mut e: f64 = 0.;
+
+    for _ in 0..config.max_iterations {
+        let m = 0.5 * (a + b);
+        let tol = f64::EPSILON.sqrt() * x.abs() + tolerance / 2.;
+        if (x - m).abs() <= 2. * tol - 0.5 * (b - a) {
+            return Ok(Scalar::from_f64(x));
+        }
+
+        let mut golden_section = true;
+        if e.abs() > tol {
+            // Try to fit a parabola through `x`, `w`, and `v`.
+            let r = (x - w) * (fx - fv);
+            let q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            let mut q = 2. * (q - r);
+            if q > 0. {
+                p = -p;
+            }
+            q = q.abs();
+
+            // Only accept the parabolic step, if it stays within the interval
+            // and is smaller than half the step before last.
+            let is_acceptable = p.abs() < (0.5 * q * e).abs()
+                && p > q * (a - x)
+                && p < q * (b - x);
+            if is_acceptable {
+                e = d;
+                d = p / q;
+
+                // Don't evaluate the function too close to the interval ends.
+                let u = x + d;
+                if u - a < 2. * tol || b - u < 2. * tol {
+                    d = tol.copysign(m - x);
+                }
+
+                golden_section = false;
+            }
+        }
+        if golden_section {
+            e = if x >= m { a - x } else { b - x };
+            d = GOLDEN * e;
+        }
+
+        let u = if d.abs() >= tol {
+            x + d
+        } else {
+            x + tol.copysign(d)
+        };
+        let fu = f(u);
+
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            v = w;
+            fv = fw;
+            w = x;
+            fw = fx;
+            x = u;
+            fx = fu;
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                fv = fw;
+                w = u;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+
+    Err(NumericError::NotConverged(config.max_iterations))
+}
+
+/// Find a local minimum of a function of multiple variables
+///
+/// Uses the Nelder-Mead method, which doesn't require derivatives and is
+/// robust, but only efficient for a small number of variables. It starts with
+/// a simplex around `start`, whose size is defined by `step`. That should be
+/// on the order of the distance to the expected minimum.
+///
+/// Stops, once all points of the simplex are within the configured tolerance
+/// of the best one, and the function values at those points are within the
+/// tolerance of each other.
+pub fn minimize<const N: usize>(
+    f: impl Fn([Scalar; N]) -> Scalar,
+    start: [impl Into<Scalar>; N],
+    step: impl Into<Scalar>,
+    config: &SolverConfig,
+) -> Result<[Scalar; N], NumericError> {
+    if N == 0 {
+        return Ok([Scalar::ZERO; N]);
+    }
+
+    let f = |x: &[f64; N]| f(x.map(Scalar::from_f64)).into_f64();
+    let tolerance = config.tolerance.into_f64();
+
+    let start = start.map(|x| x.into().into_f64());
+    let step = step.into().into_f64();
+
+    let mut simplex = Vec::with_capacity(N + 1);
+    simplex.push(start);
+    for i in 0..N {
+        let mut point = start;
+        point[i] += step;
+        simplex.push(point);
+    }
+    let mut simplex: Vec<_> = simplex
+        .into_iter()
+        .map(|point| {
+            let value = f(&point);
+            (point, value)
+        })
+        .collect();
+
+    for _ in 0..config.max_iterations {
+        simplex.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let (best, best_value) = simplex[0];
+        let (worst, worst_value) = simplex[N];
+
+        let is_converged = (worst_value - best_value).abs() <= tolerance
+            && simplex.iter().all(|(point, _)| {
+                point
+                    .iter()
+                    .zip(&best)
+                    .all(|(a, b)| (a - b).abs() <= tolerance)
+            });
+        if is_converged {
+            return Ok(best.map(Scalar::from_f64));
+        }
+
+        // The centroid of all points except the worst
+        let mut centroid = [0.; N];
+        for (point, _) in &simplex[..N] {
+            for (c, x) in centroid.iter_mut().zip(point) {
+                *c += x / N as f64;
+            }
+        }
+
+        let towards = |factor: f64| {
+            let mut point = centroid;
+            for (p, w) in point.iter_mut().zip(&worst) {
+                *p += factor * (*p - w);
+            }
+            let value = f(&point);
+            (point, value)
+        };
+
+        let reflected = towards(1.);
+        let second_worst_value = simplex[N - 1].1;
+
+        if reflected.1 < best_value {
+            let expanded = towards(2.);
+            simplex[N] = if expanded.1 < reflected.1 {
+                expanded
+            } else {
+                reflected
+            };
+        } else if reflected.1 < second_worst_value {
+            simplex[N] = reflected;
+        } else {
+            let contracted = if reflected.1 < worst_value {
+                towards(0.5)
+            } else {
+                towards(-0.5)
+            };
+
+            if contracted.1 < reflected.1.min(worst_value) {
+                simplex[N] = contracted;
+            } else {
+                // Shrink the simplex towards the best point.
+                for (point, value) in &mut simplex[1..] {
+                    for (p, b) in point.iter_mut().zip(&best) {
+                        *p = b + 0.5 * (*p - b);
+                    }
+                    *value = f(point);
+                }
+            }
+        }
+    }
+
+    Err(NumericError::NotConverged(config.max_iterations))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::algorithms::numeric::SolverConfig;
+
+    use super::{minimize, minimize_scalar};
+
+    #[test]
+    fn minimize_scalar_parabola() {
+        let config = SolverConfig::default();
+
+        let min = minimize_scalar(
+            |x| (x - Scalar::ONE) * (x - Scalar::ONE) + Scalar::TWO,
+            [-4., 3.],
+            &config,
+        );
+        assert!((min.unwrap() - Scalar::ONE).abs() < Scalar::from(1e-7));
+
+        let min = minimize_scalar(Scalar::cos, [2., 5.], &config);
+        assert!((min.unwrap() - Scalar::PI).abs() < Scalar::from(1e-7));
+    }
+
+    #[test]
+    fn minimize_rosenbrock() {
+        let config = SolverConfig::default();
+
+        // The Rosenbrock function has its minimum at the end of a long, curved
+        // valley, which makes it hard to minimize.
+        let rosenbrock = |[x, y]: [Scalar; 2]| {
+            let a = Scalar::ONE - x;
+            let b = y - x * x;
+            a * a + b * b * 100.
+        };
+
+        let min = minimize(rosenbrock, [-1.2, 1.], 0.5, &config).unwrap();
+        for coord in min {
+            assert!((coord - Scalar::ONE).abs() < Scalar::from(1e-5));
+        }
+    }
+}
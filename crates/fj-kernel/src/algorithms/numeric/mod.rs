@@ -0,0 +1,57 @@
+//! Numeric methods
+//!
+//! Root finding and minimization, as shared infrastructure for algorithms that
+//! can't compute their results in closed form, like intersections between
+//! curves and surfaces, projecting points onto curves and surfaces, or
+//! evaluating NURBS.
+//!
+//! All methods work on functions of [`Scalar`]s. They stop as soon as the
+//! result is within the tolerance that [`SolverConfig`] defines, or fail, if
+//! they need more than the maximum number of iterations to get there.
+
+mod minimize;
+mod root;
+
+pub use self::{
+    minimize::{minimize, minimize_scalar},
+    root::{find_root, find_root_newton},
+};
+
+use fj_math::Scalar;
+
+/// Configuration for the numeric methods
+#[derive(Clone, Copy, Debug)]
+pub struct SolverConfig {
+    /// The tolerance of the result
+    ///
+    /// Methods stop, once they have narrowed down the result to an interval
+    /// that is not wider than this value.
+    pub tolerance: Scalar,
+
+    /// The maximum number of iterations, before a method gives up
+    pub max_iterations: usize,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            tolerance: Scalar::from_f64(1e-12),
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// Error that can occur in a numeric method
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum NumericError {
+    /// The function has the same sign at both ends of the bracket
+    ///
+    /// Root finding requires a bracket that is known to contain a root.
+    #[error("Function has the same sign at both ends of the bracket")]
+    NoSignChange,
+
+    /// The method didn't reach the tolerance in the maximum number of
+    /// iterations
+    #[error("Method did not converge in {0} iterations")]
+    NotConverged(usize),
+}
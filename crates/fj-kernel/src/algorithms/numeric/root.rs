@@ -0,0 +1,241 @@
+use fj_math::Scalar;
+
+use super::{NumericError, SolverConfig};
+
+/// Find a root of a function within a bracket, using Brent's method
+///
+/// The function must have different signs at both ends of the bracket, which
+/// means that it has a root in between, if it is continuous. Brent's method
+/// combines bisection with secant steps and inverse quadratic interpolation.
+/// It converges as fast as the latter, if the function is well-behaved, but
+/// never more slowly than bisection.
+///
+/// The algorithm follows the description in Numerical Recipes, section 9.3.
+pub fn find_root(
+    f: impl Fn(Scalar) -> Scalar,
+    bracket: [impl Into<Scalar>; 2],
+    config: &SolverConfig,
+) -> Result<Scalar, NumericError> {
+    let f = |x: f64| f(Scalar::from_f64(x)).into_f64();
+    let tolerance = config.tolerance.into_f64();
+
+    let [mut a, mut b] = bracket.map(|x| x.into().into_f64());
+    let [mut fa, mut fb] = [f(a), f(b)];
+
+    if fa == 0. {
+        return Ok(Scalar::from_f64(a));
+    }
+    if fb == 0. {
+        return Ok(Scalar::from_f64(b));
+    }
+    if fa.signum() == fb.signum() {
+        return Err(NumericError::NoSignChange);
+    }
+
+    // `b` is the current estimate of the root, `c` the other end of the
+    // bracket, and `a` the previous estimate.
+    let [mut c, mut fc] = [b, fb];
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..config.max_iterations {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol = 2. * f64::EPSILON * b.abs() + 0.5 * tolerance;
+        let m = 0.5 * (c - b);
+        if m.abs() <= tol || fb == 0. {
+            return Ok(Scalar::from_f64(b));
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Try interpolation.
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // Secant method
+                (2. * m * s, 1. - s)
+            } else {
+                // Inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2. * m * q * (q - r) - (b - a) * (r - 1.)),
+                    (q - 1.) * (r - 1.) * (s - 1.),
+                )
+            };
+            if p > 0. {
+                q = -q;
+            }
+            p = p.abs();
+
+            // Only accept the interpolation, if it stays within the bracket
+            // and converges quickly enough. Bisect otherwise.
+            let min = (3. * m * q - (tol * q).abs()).min((e * q).abs());
+            if 2. * p < min {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = d;
+            }
+        } else {
+            d = m;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+    }
+
+    Err(NumericError::NotConverged(config.max_iterations))
+}
+
+/// Find a root of a function within a bracket, using Newton's method
+///
+/// Requires the derivative `df` of the function. Converges faster than
+/// [`find_root`], if the derivative is cheap to compute.
+///
+/// Newton's method on its own can diverge or oscillate. This implementation
+/// is safeguarded: It keeps track of a bracket around the root, and falls back
+/// to bisection whenever a Newton step would leave the bracket, or not reduce
+/// it quickly enough. As with [`find_root`], the function must have different
+/// signs at both ends of the initial bracket.
+///
+/// The algorithm follows the description in Numerical Recipes, section 9.4.
+pub fn find_root_newton(
+    f: impl Fn(Scalar) -> Scalar,
+    df: impl Fn(Scalar) -> Scalar,
+    bracket: [impl Into<Scalar>; 2],
+    config: &SolverConfig,
+) -> Result<Scalar, NumericError> {
+    let f = |x: f64| f(Scalar::from_f64(x)).into_f64();
+    let df = |x: f64| df(Scalar::from_f64(x)).into_f64();
+    let tolerance = config.tolerance.into_f64();
+
+    let [a, b] = bracket.map(|x| x.into().into_f64());
+    let [fa, fb] = [f(a), f(b)];
+
+    if fa == 0. {
+        return Ok(Scalar::from_f64(a));
+    }
+    if fb == 0. {
+        return Ok(Scalar::from_f64(b));
+    }
+    if fa.signum() == fb.signum() {
+        return Err(NumericError::NoSignChange);
+    }
+
+    // Orient the bracket, so the function is negative at `low`.
+    let [mut low, mut high] = if fa < 0. { [a, b] } else { [b, a] };
+
+    let mut x = 0.5 * (a + b);
+    let mut dx_old = (b - a).abs();
+    let mut dx = dx_old;
+    let mut fx = f(x);
+    let mut dfx = df(x);
+
+    for _ in 0..config.max_iterations {
+        let leaves_bracket =
+            ((x - high) * dfx - fx) * ((x - low) * dfx - fx) > 0.;
+        let is_too_slow = (2. * fx).abs() > (dx_old * dfx).abs();
+
+        dx_old = dx;
+        if leaves_bracket || is_too_slow {
+            dx = 0.5 * (high - low);
+            x = low + dx;
+        } else {
+            dx = fx / dfx;
+            x -= dx;
+        }
+
+        if dx.abs() < tolerance || dx == 0. {
+            return Ok(Scalar::from_f64(x));
+        }
+
+        fx = f(x);
+        dfx = df(x);
+
+        if fx < 0. {
+            low = x;
+        } else {
+            high = x;
+        }
+    }
+
+    Err(NumericError::NotConverged(config.max_iterations))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::algorithms::numeric::{NumericError, SolverConfig};
+
+    use super::{find_root, find_root_newton};
+
+    #[test]
+    fn find_root_brent() {
+        let config = SolverConfig::default();
+
+        let root = find_root(|x| x * x - Scalar::TWO, [0., 2.], &config);
+        assert!(
+            (root.unwrap() - Scalar::from(2_f64.sqrt())).abs()
+                < Scalar::from(1e-12)
+        );
+
+        // A function that is flat around its root, which interpolation
+        // handles badly.
+        let f = |x: Scalar| {
+            let x = x - Scalar::ONE;
+            x * x * x * x * x
+        };
+        let root = find_root(f, [-3., 2.], &config);
+        assert!((root.unwrap() - Scalar::ONE).abs() < Scalar::from(1e-2));
+
+        let root = find_root(|x| x * x + Scalar::ONE, [-1., 1.], &config);
+        assert_eq!(root, Err(NumericError::NoSignChange));
+    }
+
+    #[test]
+    fn find_root_newton_safeguarded() {
+        let config = SolverConfig::default();
+
+        let root = find_root_newton(
+            |x| x.cos() - x,
+            |x| -x.sin_cos().0 - Scalar::ONE,
+            [0., 1.],
+            &config,
+        );
+        assert!(
+            (root.unwrap() - Scalar::from(0.7390851332151607)).abs()
+                < Scalar::from(1e-12)
+        );
+
+        // Started at the center of the bracket, plain Newton oscillates
+        // between 0 and 1 forever. The safeguard makes it converge.
+        let root = find_root_newton(
+            |x| x * x * x - x * 2. + Scalar::TWO,
+            |x| x * x * 3. - Scalar::TWO,
+            [-2., 2.],
+            &config,
+        );
+        assert!(
+            (root.unwrap() - Scalar::from(-1.7692923542386314)).abs()
+                < Scalar::from(1e-12)
+        );
+    }
+}
@@ -0,0 +1,740 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_math::{Point, Scalar, Triangle, Vector};
+
+use crate::objects::{Face, Solid};
+
+use super::{triangulate, Tolerance, TriangulationConfig};
+
+/// Offset all faces of a solid by a distance
+///
+/// A positive `distance` grows the solid, a negative one shrinks it. Where
+/// faces move apart, the gap between them is filled with a blend: Edges become
+/// cylindrical, vertices spherical, with a radius of `distance`. Where faces
+/// move towards each other, they are trimmed against each other, and the edge
+/// between them stays sharp.
+///
+/// The solid is triangulated, according to `tolerance`, before being offset.
+/// The faces of the resulting solid are in triangle representation.
+///
+/// # Implementation Note
+///
+/// Only local trimming is performed. Shrinking a solid by more than the size of
+/// its features results in a self-intersecting solid.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn offset(
+    solid: &Solid,
+    distance: impl Into<Scalar>,
+    tolerance: Tolerance,
+) -> Solid {
+    let distance = distance.into();
+    if distance == Scalar::ZERO {
+        return solid.clone();
+    }
+
+    let mesh = OffsetMesh::new(solid, tolerance);
+    let triangles = mesh.offset(distance, tolerance);
+
+    Solid::from_faces([Face::Triangles(triangles)])
+}
+
+/// The triangle mesh of a solid, prepared for offsetting
+struct OffsetMesh {
+    points: Vec<Point<3>>,
+    triangles: Vec<[usize; 3]>,
+    colors: Vec<[u8; 4]>,
+    normals: Vec<Vector<3>>,
+
+    /// The triangles on each side of an edge, keyed by the edge's vertices
+    ///
+    /// The first triangle contains the edge in the order of the key, the
+    /// second one in reverse order.
+    edges: HashMap<[usize; 2], Vec<usize>>,
+}
+
+impl OffsetMesh {
+    fn new(solid: &Solid, tolerance: Tolerance) -> Self {
+        let mesh = triangulate(
+            solid.faces().cloned().collect(),
+            tolerance,
+            &TriangulationConfig::default(),
+            &mut DebugInfo::new(),
+            &mut ProgressHandle::new(),
+        )
+        .expect("Triangulation can't be cancelled without token")
+        .weld(tolerance.inner());
+
+        let points: Vec<_> = mesh.vertices().collect();
+        let indices: Vec<_> = mesh.indices().map(|i| i as usize).collect();
+        let mut triangles: Vec<_> = indices
+            .chunks(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect();
+        let colors = mesh.triangles().map(|triangle| triangle.color).collect();
+
+        orient(&points, &mut triangles);
+
+        let normals = triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let [a, b, c] = [a, b, c].map(|i| points[i]);
+                (b - a).cross(&(c - a)).normalize()
+            })
+            .collect();
+
+        let mut edges: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+        for (t, triangle) in triangles.iter().enumerate() {
+            for [a, b] in directed_edges(triangle) {
+                edges.entry(key(a, b)).or_default().push(t);
+            }
+        }
+        for (&[a, b], triangles_of_edge) in &mut edges {
+            triangles_of_edge
+                .sort_by_key(|&t| !triangles[t].contains_edge(a, b));
+        }
+
+        Self {
+            points,
+            triangles,
+            colors,
+            normals,
+            edges,
+        }
+    }
+
+    fn offset(
+        &self,
+        distance: Scalar,
+        tolerance: Tolerance,
+    ) -> Vec<(Triangle<3>, [u8; 4])> {
+        // The largest angle that an arc of the blends can span, before it
+        // deviates from the blend by more than the tolerance.
+        let max_angle = {
+            let ratio = Scalar::ONE - tolerance.inner() / distance.abs();
+            if ratio <= -Scalar::ONE {
+                Scalar::PI
+            } else {
+                ratio.acos() * 2.
+            }
+        };
+
+        // Gaps that are smaller than the tolerance don't need to be filled.
+        // Those occur between neighboring triangles of a curved face.
+        let min_angle = tolerance.inner() / distance.abs();
+
+        let opening = |[a, b]: [usize; 2]| -> Option<(usize, usize, Scalar)> {
+            let triangles = self.edges.get(&key(a, b))?;
+            let (t, u) = match triangles.as_slice() {
+                &[t, u] => (t, u),
+                _ => return None,
+            };
+
+            let angle = self.normals[t].dot(&self.normals[u]);
+            let angle = if angle >= Scalar::ONE {
+                Scalar::ZERO
+            } else {
+                angle.acos()
+            };
+            if angle <= min_angle {
+                return None;
+            }
+
+            // The edge is convex, if the opposite vertex of the other triangle
+            // is behind the plane of this one. Growing opens convex edges,
+            // shrinking opens concave ones.
+            let opposite = self.triangles[u].opposite(a, b);
+            let height =
+                (self.points[opposite] - self.points[a]).dot(&self.normals[t]);
+            let is_convex = height < Scalar::ZERO;
+
+            (is_convex == (distance > Scalar::ZERO)).then_some((t, u, angle))
+        };
+
+        // Each vertex gets one copy for each group of its triangles that are
+        // not separated by an opening edge.
+        let sectors = self.sectors(|edge| opening(edge).is_some());
+        let mut copies = HashMap::new();
+        for (&(vertex, sector), triangles) in &sectors.triangles {
+            let normals = triangles.iter().map(|&t| self.normals[t]);
+            let point =
+                self.points[vertex] + miter(normals, distance, min_angle);
+            copies.insert((vertex, sector), point);
+        }
+        let copy = |vertex: usize, t: usize| {
+            copies[&(vertex, sectors.of_triangle[&(vertex, t)])]
+        };
+
+        let mut result = Vec::new();
+        let mut push = |points: [Point<3>; 3], color: [u8; 4]| {
+            let [a, b, c] = points;
+            if (b - a).cross(&(c - a)).magnitude() > Scalar::ZERO {
+                result.push((Triangle::from_points(points), color));
+            }
+        };
+
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            push(triangle.map(|vertex| copy(vertex, t)), self.colors[t]);
+        }
+
+        // Fill the gaps along opening edges with strips of triangles. The
+        // arcs at both ends of the strips are kept, to fill the gaps at the
+        // vertices later.
+        let mut arcs = HashMap::new();
+        for &[a, b] in self.edges.keys() {
+            let (t, u, angle) = match opening([a, b]) {
+                Some(opening) => opening,
+                None => continue,
+            };
+
+            let segments = (angle / max_angle).ceil().into_u64().max(1);
+            let [arc_a, arc_b] = [a, b].map(|vertex| {
+                let mut arc: Vec<_> = (0..=segments)
+                    .map(|i| {
+                        self.points[vertex]
+                            + slerp(
+                                self.normals[t],
+                                self.normals[u],
+                                i as f64 / segments as f64,
+                            ) * distance
+                    })
+                    .collect();
+
+                // The ends of the arc are the copies of the vertex. They are
+                // not on the blend, if the vertex is part of a sharp edge.
+                let (from, to) = (copy(vertex, t), copy(vertex, u));
+                arc[0] = from;
+                arc[segments as usize] = to;
+                if from == to {
+                    arc.iter_mut().for_each(|point| *point = from);
+                }
+
+                arc
+            });
+
+            // Triangle `t` contains the edge from `a` to `b`, so the strip
+            // needs to contain it from `b` to `a`, where it meets `t`.
+            for (a, b) in arc_a.windows(2).zip(arc_b.windows(2)) {
+                push([b[0], a[0], a[1]], self.colors[t]);
+                push([b[0], a[1], b[1]], self.colors[t]);
+            }
+
+            arcs.insert((a, [a, b]), (t, arc_a));
+            arcs.insert((b, [a, b]), (t, arc_b));
+        }
+
+        // Fill the gaps at the vertices, where multiple strips meet.
+        for (vertex, fan) in self.fans() {
+            let mut polygon: Vec<Point<3>> = Vec::new();
+
+            for &(t, other) in &fan {
+                let edge = key(vertex, other);
+                let arc = match arcs.get(&(vertex, edge)) {
+                    Some((from, arc)) if *from == t => arc.clone(),
+                    Some((_, arc)) => arc.iter().rev().copied().collect(),
+                    None => vec![copy(vertex, t)],
+                };
+
+                for point in arc {
+                    if polygon.last() != Some(&point) {
+                        polygon.push(point);
+                    }
+                }
+            }
+            while polygon.len() > 1 && polygon.first() == polygon.last() {
+                polygon.pop();
+            }
+
+            if polygon.len() >= 3 {
+                let color = self.colors[fan[0].0];
+                for triangle in
+                    patch(self.points[vertex], &polygon, distance, max_angle)
+                {
+                    push(triangle, color);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Group the triangles around each vertex into sectors
+    ///
+    /// Triangles that share an edge are in the same sector, unless the edge
+    /// separates them.
+    fn sectors(&self, separates: impl Fn([usize; 2]) -> bool) -> Sectors {
+        let mut of_triangle = HashMap::new();
+        let mut triangles: HashMap<_, Vec<usize>> = HashMap::new();
+
+        let mut triangles_of_vertex: HashMap<usize, Vec<usize>> =
+            HashMap::new();
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            for &vertex in triangle {
+                triangles_of_vertex.entry(vertex).or_default().push(t);
+            }
+        }
+
+        for (&vertex, incident) in &triangles_of_vertex {
+            let mut sector = 0;
+
+            for &start in incident {
+                if of_triangle.contains_key(&(vertex, start)) {
+                    continue;
+                }
+
+                let mut queue = VecDeque::from([start]);
+                of_triangle.insert((vertex, start), sector);
+
+                while let Some(t) = queue.pop_front() {
+                    triangles.entry((vertex, sector)).or_default().push(t);
+
+                    for other in self.triangles[t] {
+                        if other == vertex {
+                            continue;
+                        }
+
+                        let edge = key(vertex, other);
+                        if separates(edge) {
+                            continue;
+                        }
+
+                        for &u in self.edges.get(&edge).into_iter().flatten() {
+                            if let Entry::Vacant(entry) =
+                                of_triangle.entry((vertex, u))
+                            {
+                                entry.insert(sector);
+                                queue.push_back(u);
+                            }
+                        }
+                    }
+                }
+
+                sector += 1;
+            }
+        }
+
+        Sectors {
+            of_triangle,
+            triangles,
+        }
+    }
+
+    /// Find the closed fans of triangles around the vertices
+    ///
+    /// Each fan is a list of triangles around a vertex, in order. Each
+    /// triangle is paired with the other vertex of the edge, that connects it
+    /// to the next triangle. Vertices on the boundary of an open mesh, or on
+    /// non-manifold edges, don't have a closed fan and are skipped.
+    fn fans(&self) -> Vec<(usize, Vec<(usize, usize)>)> {
+        let mut first_triangle = HashMap::new();
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            for &vertex in triangle {
+                first_triangle.entry(vertex).or_insert(t);
+            }
+        }
+
+        let mut fans = Vec::new();
+        'vertices: for (&vertex, &start) in &first_triangle {
+            let mut fan = Vec::new();
+            let mut t = start;
+
+            loop {
+                // The edge leaving `vertex` within `t`. Since the triangles are
+                // oriented consistently, the next triangle contains it in the
+                // reverse direction.
+                let other = self.triangles[t].next(vertex);
+                let next = match self.edges[&key(vertex, other)].as_slice() {
+                    &[a, b] => {
+                        if a == t {
+                            b
+                        } else {
+                            a
+                        }
+                    }
+                    _ => continue 'vertices,
+                };
+
+                fan.push((t, other));
+                t = next;
+
+                if t == start {
+                    break;
+                }
+                if fan.len() > self.triangles.len() {
+                    continue 'vertices;
+                }
+            }
+
+            fans.push((vertex, fan));
+        }
+
+        fans
+    }
+}
+
+struct Sectors {
+    of_triangle: HashMap<(usize, usize), usize>,
+    triangles: HashMap<(usize, usize), Vec<usize>>,
+}
+
+trait TriangleExt {
+    fn contains_edge(&self, a: usize, b: usize) -> bool;
+    fn opposite(&self, a: usize, b: usize) -> usize;
+    fn next(&self, vertex: usize) -> usize;
+}
+
+impl TriangleExt for [usize; 3] {
+    /// Determine whether the triangle contains the edge from `a` to `b`
+    fn contains_edge(&self, a: usize, b: usize) -> bool {
+        directed_edges(self).contains(&[a, b])
+    }
+
+    /// Find the vertex that is not part of the edge between `a` and `b`
+    fn opposite(&self, a: usize, b: usize) -> usize {
+        *self
+            .iter()
+            .find(|&&vertex| vertex != a && vertex != b)
+            .expect("Triangle has three distinct vertices")
+    }
+
+    /// Find the vertex that follows `vertex` in the triangle
+    fn next(&self, vertex: usize) -> usize {
+        let i = self
+            .iter()
+            .position(|&v| v == vertex)
+            .expect("Vertex is part of triangle");
+        self[(i + 1) % 3]
+    }
+}
+
+fn directed_edges(triangle: &[usize; 3]) -> [[usize; 2]; 3] {
+    let [a, b, c] = *triangle;
+    [[a, b], [b, c], [c, a]]
+}
+
+fn key(a: usize, b: usize) -> [usize; 2] {
+    if a < b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+/// Orient the triangles of a mesh consistently, facing outward
+pub(super) fn orient(points: &[Point<3>], triangles: &mut [[usize; 3]]) {
+    let mut edges: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for [a, b] in directed_edges(triangle) {
+            edges.entry(key(a, b)).or_default().push(t);
+        }
+    }
+
+    let mut visited = vec![false; triangles.len()];
+    for start in 0..triangles.len() {
+        if visited[start] {
+            continue;
+        }
+
+        // Propagate the orientation of the first triangle to all triangles
+        // that are connected to it.
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+
+        while let Some(t) = queue.pop_front() {
+            component.push(t);
+
+            for [a, b] in directed_edges(&triangles[t]) {
+                let neighbors = &edges[&key(a, b)];
+                if neighbors.len() != 2 {
+                    continue;
+                }
+
+                for &u in neighbors {
+                    if visited[u] {
+                        continue;
+                    }
+
+                    // Neighbors contain their shared edge in opposite
+                    // directions.
+                    if triangles[u].contains_edge(a, b) {
+                        triangles[u].swap(0, 1);
+                    }
+
+                    visited[u] = true;
+                    queue.push_back(u);
+                }
+            }
+        }
+
+        // Outward-facing triangles of a closed mesh enclose a positive volume.
+        let volume = component
+            .iter()
+            .map(|&t| {
+                let [a, b, c] = triangles[t].map(|i| points[i].coords);
+                a.dot(&b.cross(&c))
+            })
+            .fold(Scalar::ZERO, |sum, volume| sum + volume);
+        if volume < Scalar::ZERO {
+            for t in component {
+                triangles[t].swap(0, 1);
+            }
+        }
+    }
+}
+
+/// Compute the offset of a vertex, that is shared by triangles with `normals`
+///
+/// The offset vertex is `distance` away from the planes of all triangles, which
+/// trims their offset copies against each other.
+fn miter(
+    normals: impl IntoIterator<Item = Vector<3>>,
+    distance: Scalar,
+    min_angle: Scalar,
+) -> Vector<3> {
+    // Collect the distinct normals. Normals of neighboring triangles on curved
+    // faces are very similar and would make the problem ill-conditioned.
+    let mut distinct: Vec<Vector<3>> = Vec::new();
+    for normal in normals {
+        let is_distinct = distinct
+            .iter()
+            .all(|other| other.dot(&normal) < min_angle.cos());
+        if is_distinct {
+            distinct.push(normal);
+        }
+    }
+
+    // Solve for the offset, using the normals that are most different from
+    // each other.
+    match distinct.as_slice() {
+        [] => Vector::from([0., 0., 0.]),
+        [n] => *n * distance,
+        [n1, rest @ ..] => {
+            let n2 = *rest
+                .iter()
+                .min_by_key(|n2| n1.dot(n2))
+                .expect("Slice is not empty");
+            let two_planes =
+                (*n1 + n2) * (distance / (n1.dot(&n2) + Scalar::ONE));
+
+            let n3 = rest
+                .iter()
+                .map(|n3| (n3, n1.cross(&n2).dot(n3).abs()))
+                .max_by_key(|(_, volume)| *volume);
+            match n3 {
+                Some((n3, volume)) if volume > Scalar::from(1e-6) => {
+                    let n3 = *n3;
+                    let det = n1.dot(&n2.cross(&n3));
+                    (n2.cross(&n3) + n3.cross(n1) + n1.cross(&n2))
+                        * (distance / det)
+                }
+                _ => two_planes,
+            }
+        }
+    }
+}
+
+/// Interpolate between two unit vectors, along the arc between them
+fn slerp(a: Vector<3>, b: Vector<3>, t: f64) -> Vector<3> {
+    let cos = a.dot(&b);
+    if cos >= Scalar::ONE {
+        return a;
+    }
+
+    let angle = cos.acos();
+    let sin = angle.sin_cos().0;
+    let [sa, sb] = [1. - t, t].map(|t| (angle * t).sin_cos().0 / sin);
+
+    a * sa + b * sb
+}
+
+/// Triangulate the blend at a vertex, bounded by `polygon`
+///
+/// The blend is a part of a sphere around `center`, with a radius of
+/// `distance`. It is triangulated in rings around its middle.
+fn patch(
+    center: Point<3>,
+    polygon: &[Point<3>],
+    distance: Scalar,
+    max_angle: Scalar,
+) -> Vec<[Point<3>; 3]> {
+    let offsets: Vec<_> = polygon.iter().map(|&point| point - center).collect();
+
+    let sum = offsets
+        .iter()
+        .fold(Vector::from([0., 0., 0.]), |sum, &offset| sum + offset);
+    if sum.magnitude() == Scalar::ZERO {
+        return Vec::new();
+    }
+    let middle = sum.normalize();
+
+    let max_spread = offsets
+        .iter()
+        .map(|offset| {
+            let cos = offset.normalize().dot(&middle);
+            if cos >= Scalar::ONE {
+                Scalar::ZERO
+            } else {
+                cos.acos()
+            }
+        })
+        .max()
+        .unwrap_or(Scalar::ZERO);
+    let num_rings = (max_spread / max_angle).ceil().into_u64().max(1);
+
+    // The points of each ring, from the middle of the blend to its boundary.
+    let rings: Vec<Vec<Point<3>>> = (1..=num_rings)
+        .map(|ring| {
+            let t = ring as f64 / num_rings as f64;
+            offsets
+                .iter()
+                .map(|&offset| {
+                    if ring == num_rings {
+                        return center + offset;
+                    }
+
+                    let magnitude = distance.abs()
+                        + (offset.magnitude() - distance.abs()) * t;
+                    center + slerp(middle, offset.normalize(), t) * magnitude
+                })
+                .collect()
+        })
+        .collect();
+    let apex = center + middle * distance.abs();
+
+    let n = polygon.len();
+    let mut triangles = Vec::new();
+    for i in 0..n {
+        let j = (i + 1) % n;
+        triangles.push([apex, rings[0][i], rings[0][j]]);
+
+        for ring in rings.windows(2) {
+            let [inner, outer] = [&ring[0], &ring[1]];
+            triangles.push([inner[i], outer[i], outer[j]]);
+            triangles.push([inner[i], outer[j], inner[j]]);
+        }
+    }
+
+    // The blend faces away from the vertex when growing, and towards it when
+    // shrinking.
+    let facing = triangles
+        .iter()
+        .map(|&[a, b, c]| (b - a).cross(&(c - a)).dot(&middle))
+        .fold(Scalar::ZERO, |sum, facing| sum + facing);
+    if (facing > Scalar::ZERO) != (distance > Scalar::ZERO) {
+        for triangle in &mut triangles {
+            triangle.swap(1, 2);
+        }
+    }
+
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::{
+        algorithms::{BoundingVolume, Tolerance},
+        objects::{Solid, Stores},
+    };
+
+    use super::offset;
+
+    #[test]
+    fn grow() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let grown = offset(&cube, 0.1, tolerance);
+
+        assert_aabb(&grown, tolerance, 0.6);
+
+        // Faces, plus quarter-cylinders along the edges, plus eighth-spheres
+        // at the vertices.
+        let pi = Scalar::PI.into_f64();
+        let expected = 1. + 6. * 0.1 + 3. * pi * 0.01 + 4. / 3. * pi * 0.001;
+        let volume = volume(&grown);
+        assert!((volume - expected).abs() < 0.01, "{volume} != {expected}");
+
+        assert_closed(&grown);
+
+        Ok(())
+    }
+
+    #[test]
+    fn shrink() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let shrunk = offset(&cube, -0.1, tolerance);
+
+        assert_aabb(&shrunk, tolerance, 0.4);
+
+        let volume = volume(&shrunk);
+        assert!((volume - 0.512).abs() < 1e-9, "{volume}");
+
+        assert_closed(&shrunk);
+
+        Ok(())
+    }
+
+    fn assert_aabb(solid: &Solid, tolerance: Tolerance, half_size: f64) {
+        let aabb = solid.aabb(tolerance).unwrap();
+        let expected = Aabb {
+            min: Point::from([-half_size; 3]),
+            max: Point::from([half_size; 3]),
+        };
+
+        let epsilon = Scalar::from(1e-9);
+        assert!(
+            (aabb.min - expected.min).magnitude() < epsilon
+                && (aabb.max - expected.max).magnitude() < epsilon,
+            "{aabb:?} != {expected:?}"
+        );
+    }
+
+    fn triangles(solid: &Solid) -> Vec<[Point<3>; 3]> {
+        solid
+            .faces()
+            .flat_map(|face| match face {
+                crate::objects::Face::Triangles(triangles) => triangles
+                    .iter()
+                    .map(|(triangle, _)| triangle.points())
+                    .collect::<Vec<_>>(),
+                crate::objects::Face::Face(_) => {
+                    panic!("Expected triangle representation")
+                }
+            })
+            .collect()
+    }
+
+    fn volume(solid: &Solid) -> f64 {
+        triangles(solid)
+            .into_iter()
+            .map(|[a, b, c]| {
+                a.coords.dot(&b.coords.cross(&c.coords)).into_f64() / 6.
+            })
+            .sum()
+    }
+
+    /// Check that each edge is shared by two triangles, in opposite directions
+    fn assert_closed(solid: &Solid) {
+        let mut edges: HashMap<[Point<3>; 2], i32> = HashMap::new();
+        for [a, b, c] in triangles(solid) {
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                *edges.entry([p, q]).or_default() += 1;
+                *edges.entry([q, p]).or_default() -= 1;
+            }
+        }
+
+        let open = edges.values().filter(|&&count| count != 0).count();
+        assert_eq!(open, 0);
+    }
+}
@@ -0,0 +1,213 @@
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::objects::{Curve, Surface};
+
+use super::Tolerance;
+
+/// Project a curve onto a surface
+///
+/// Returns the projected curve in surface coordinates. Points are projected
+/// as defined by [`Surface::point_to_surface_coords`].
+///
+/// Returns `None`, if the projection can't be represented as a [`Curve`]. This
+/// is the case, for example, for a line that is perpendicular to a plane
+/// (which is projected into a single point), or for a circle that is tilted
+/// against a plane (which is projected into an ellipse). Callers can fall back
+/// to approximating the curve, then projecting the points of the
+/// approximation.
+///
+/// `tolerance` defines how far the orientation of the curve may deviate from
+/// the orientation required for an exact projection.
+pub fn project_curve(
+    curve: &Curve<3>,
+    surface: &Surface,
+    tolerance: Tolerance,
+) -> Option<Curve<2>> {
+    let Surface::SweptCurve(swept) = surface;
+
+    match (swept.curve, curve) {
+        (Curve::Line(_), Curve::Line(line)) => {
+            let origin = surface.point_to_surface_coords(line.origin);
+            let direction = project_vector(surface, origin, line.direction);
+
+            let normal = surface_normal(swept.path, swept.curve);
+            if is_parallel(line.direction, normal, tolerance) {
+                return None;
+            }
+
+            Some(Curve::Line(Line { origin, direction }))
+        }
+        (Curve::Line(_), Curve::Circle(circle)) => {
+            let center = surface.point_to_surface_coords(circle.center);
+            let a = project_vector(surface, center, circle.a);
+            let b = project_vector(surface, center, circle.b);
+
+            // Only a circle in surface coordinates can be represented. If `a`
+            // and `b` aren't of equal length and perpendicular anymore, the
+            // projection is an ellipse.
+            let is_circle = (a.magnitude() - b.magnitude()).abs()
+                <= tolerance.inner()
+                && a.dot(&b).abs() <= tolerance.inner() * a.magnitude();
+            if !is_circle {
+                return None;
+            }
+
+            Some(Curve::Circle(Circle { center, a, b }))
+        }
+        (Curve::Circle(_), Curve::Line(line)) => {
+            // Only lines that are parallel to the path run along the surface.
+            // All others would be projected into a circle, or a helix.
+            if !is_parallel(line.direction, swept.path, tolerance) {
+                return None;
+            }
+
+            let origin = surface.point_to_surface_coords(line.origin);
+            let direction = project_vector(surface, origin, line.direction).v;
+
+            Some(Curve::Line(Line {
+                origin,
+                direction: Vector::from([Scalar::ZERO, direction]),
+            }))
+        }
+        (Curve::Circle(surface_circle), Curve::Circle(circle)) => {
+            // Only circles that are coaxial with the surface can be projected
+            // into a straight line along the surface's u-axis.
+            let normal = circle.a.cross(&circle.b);
+            let axis = Line {
+                origin: surface_circle.center,
+                direction: swept.path,
+            };
+            let center_on_axis = axis.point_from_line_coords(
+                axis.point_to_line_coords(circle.center),
+            );
+            let is_coaxial = is_parallel(normal, swept.path, tolerance)
+                && (circle.center - center_on_axis).magnitude()
+                    <= tolerance.inner();
+            if !is_coaxial {
+                return None;
+            }
+
+            let origin =
+                surface.point_to_surface_coords(circle.center + circle.a);
+
+            // If the circle is oriented the other way around than the surface,
+            // the direction of its coordinates is reversed on the surface.
+            let surface_normal = surface_circle.a.cross(&surface_circle.b);
+            let direction = if normal.dot(&surface_normal) > Scalar::ZERO {
+                Scalar::ONE
+            } else {
+                -Scalar::ONE
+            };
+
+            Some(Curve::Line(Line {
+                origin,
+                direction: Vector::from([direction, Scalar::ZERO]),
+            }))
+        }
+    }
+}
+
+/// Project a vector that starts at `origin` (in surface coordinates)
+fn project_vector(
+    surface: &Surface,
+    origin: Point<2>,
+    vector: Vector<3>,
+) -> Vector<2> {
+    let start = surface.point_from_surface_coords(origin);
+    surface.point_to_surface_coords(start + vector) - origin
+}
+
+fn surface_normal(path: Vector<3>, curve: Curve<3>) -> Vector<3> {
+    curve.vector_from_curve_coords([1.]).cross(&path)
+}
+
+fn is_parallel(a: Vector<3>, b: Vector<3>, tolerance: Tolerance) -> bool {
+    a.normalize().cross(&b.normalize()).magnitude() <= tolerance.inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Line, Point, Vector};
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Curve, Surface, SweptCurve},
+    };
+
+    use super::project_curve;
+
+    #[test]
+    fn line_onto_plane() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let plane = Surface::xy_plane();
+
+        let line = Curve::line_from_points([[1., 2., 3.], [2., 2., 4.]]);
+        assert_eq!(
+            project_curve(&line, &plane, tolerance),
+            Some(Curve::line_from_points([[1., 2.], [2., 2.]])),
+        );
+
+        let perpendicular =
+            Curve::line_from_points([[1., 2., 3.], [1., 2., 4.]]);
+        assert_eq!(project_curve(&perpendicular, &plane, tolerance), None);
+    }
+
+    #[test]
+    fn circle_onto_plane() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let plane = Surface::xy_plane();
+
+        let circle = Curve::Circle(Circle {
+            center: Point::from([1., 2., 3.]),
+            a: Vector::from([1., 0., 0.]),
+            b: Vector::from([0., 1., 0.]),
+        });
+        assert_eq!(
+            project_curve(&circle, &plane, tolerance),
+            Some(Curve::Circle(Circle {
+                center: Point::from([1., 2.]),
+                a: Vector::from([1., 0.]),
+                b: Vector::from([0., 1.]),
+            })),
+        );
+
+        let tilted = Curve::Circle(Circle {
+            center: Point::from([1., 2., 3.]),
+            a: Vector::from([1., 0., 0.]),
+            b: Vector::from([0., 0., 1.]),
+        });
+        assert_eq!(project_curve(&tilted, &plane, tolerance), None);
+    }
+
+    #[test]
+    fn onto_cylinder() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let cylinder = Surface::SweptCurve(SweptCurve {
+            curve: Curve::Circle(Circle {
+                center: Point::origin(),
+                a: Vector::from([1., 0., 0.]),
+                b: Vector::from([0., 1., 0.]),
+            }),
+            path: Vector::from([0., 0., 1.]),
+        });
+
+        let line = Curve::line_from_points([[2., 0., 1.], [2., 0., 2.]]);
+        assert_eq!(
+            project_curve(&line, &cylinder, tolerance),
+            Some(Curve::line_from_points([[0., 1.], [0., 2.]])),
+        );
+
+        let circle = Curve::Circle(Circle {
+            center: Point::from([0., 0., 3.]),
+            a: Vector::from([2., 0., 0.]),
+            b: Vector::from([0., -2., 0.]),
+        });
+        assert_eq!(
+            project_curve(&circle, &cylinder, tolerance),
+            Some(Curve::Line(Line {
+                origin: Point::from([0., 3.]),
+                direction: Vector::from([-1., 0.]),
+            })),
+        );
+    }
+}
@@ -6,6 +6,11 @@ use crate::{
 };
 
 /// Reverse the direction of a face
+///
+/// Reverses the surface of the face, which flips its normal. The local forms of
+/// the cycles are mirrored, to match the reversed surface, and the cycles
+/// themselves are reversed, so exteriors still wind counter-clockwise and
+/// interiors clockwise.
 pub fn reverse_face(face: &Face) -> Face {
     let face = match face {
         Face::Face(face) => face,
@@ -19,7 +24,11 @@ pub fn reverse_face(face: &Face) -> Face {
     let exteriors = reverse_local_coordinates_in_cycle(&face.exteriors);
     let interiors = reverse_local_coordinates_in_cycle(&face.interiors);
 
-    Face::new(surface, exteriors, interiors, face.color)
+    let reversed = Face::new(surface, exteriors, interiors, face.color);
+    match face.tolerance {
+        Some(tolerance) => reversed.with_tolerance(tolerance),
+        None => reversed,
+    }
 }
 
 fn reverse_local_coordinates_in_cycle(
@@ -52,14 +61,16 @@ fn reverse_local_coordinates_in_cycle(
                     Local::new(local, edge.curve.global())
                 };
 
-                Edge {
+                edge.store().insert(Edge {
                     curve,
-                    vertices: edge.vertices,
-                }
+                    vertices: edge.vertices.clone(),
+                })
             })
             .collect();
 
-        Cycle { edges }
+        // Mirroring the local coordinates has reversed the winding of the
+        // cycle. Reverse the cycle, to restore it.
+        Cycle { edges }.reverse()
     });
 
     cycles
@@ -69,20 +80,27 @@ fn reverse_local_coordinates_in_cycle(
 mod tests {
     use pretty_assertions::assert_eq;
 
-    use crate::objects::{Face, Surface};
+    use crate::objects::{Face, Stores, Surface, Winding};
 
     #[test]
     fn reverse_face() {
-        let original = Face::builder(Surface::xy_plane())
-            .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
+        let stores = Stores::new();
+
+        let original = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [3., 0.], [0., 3.]])
+            .with_interior_polygon([[1., 1.], [1., 0.5], [0.5, 1.]])
             .build();
 
         let reversed = super::reverse_face(&original);
 
-        let expected = Face::builder(Surface::xy_plane().reverse())
-            .with_exterior_polygon([[0., 0.], [1., 0.], [0., -1.]])
-            .build();
+        assert_eq!(reversed.surface(), Surface::xy_plane().reverse());
+        for cycle in reversed.exteriors() {
+            assert_eq!(cycle.winding(), Winding::CounterClockwise);
+        }
+        for cycle in reversed.interiors() {
+            assert_eq!(cycle.winding(), Winding::Clockwise);
+        }
 
-        assert_eq!(expected, reversed);
+        assert_eq!(super::reverse_face(&reversed), original);
     }
 }
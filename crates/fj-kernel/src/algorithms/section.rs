@@ -0,0 +1,435 @@
+use std::collections::{BTreeMap, HashSet};
+
+use fj_interop::{
+    debug::{DebugCategory, DebugInfo},
+    progress::ProgressHandle,
+};
+use fj_math::{Point, Scalar, Triangle, Vector};
+
+use crate::objects::{Curve, Face, Sketch, Solid, Stores, Surface, SweptCurve};
+
+use super::{triangulate, Tolerance, TriangulationConfig};
+
+/// The result of cutting a [`Solid`] with a plane
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Section {
+    /// The cross-section, where the plane intersects the solid
+    ///
+    /// The faces of the cross-section are defined in the cutting plane.
+    pub cross_section: Sketch,
+
+    /// The part of the solid that is kept, capped by the cross-section
+    ///
+    /// That's the part that lies behind the cutting plane, i.e. on the side
+    /// opposite to where the plane's normal points.
+    pub solid: Solid,
+}
+
+/// Cut a solid with a plane
+///
+/// The faces of the solid are approximated, according to `tolerance`, before
+/// being cut. The faces of the kept part of the solid are returned in triangle
+/// representation, while the faces of the cross-section, which also cap the
+/// kept part of the solid, use boundary representation and have the provided
+/// color. The objects that make up the cross-section are inserted into
+/// `stores`.
+///
+/// # Panics
+///
+/// Panics, if `plane` is not a plane.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn section(
+    solid: &Solid,
+    plane: Surface,
+    tolerance: Tolerance,
+    stores: &Stores,
+    color: [u8; 4],
+    debug_info: &mut DebugInfo,
+) -> Section {
+    let plane = Plane::new(plane);
+
+    let mut faces = Vec::new();
+    let mut cut_segments = Vec::new();
+
+    for face in solid.faces() {
+        let mesh = triangulate(
+            vec![face.clone()],
+            tolerance,
+            &TriangulationConfig::default(),
+            debug_info,
+            &mut ProgressHandle::new(),
+        )
+        .expect("Triangulation can't be cancelled without token");
+
+        let mut triangles = Vec::new();
+        for triangle in mesh.triangles() {
+            cut_triangle(
+                &plane,
+                triangle.points,
+                triangle.color,
+                &mut triangles,
+                &mut cut_segments,
+            );
+        }
+
+        if !triangles.is_empty() {
+            faces.push(Face::Triangles(triangles));
+        }
+    }
+
+    for &segment in &cut_segments {
+        debug_info.push_curve(
+            DebugCategory::Section,
+            segment,
+            [255, 0, 255, 255],
+        );
+    }
+
+    let cross_section = cross_section(&plane, cut_segments, stores, color);
+    faces.extend(cross_section.faces().cloned());
+
+    Section {
+        cross_section,
+        solid: Solid::from_faces(faces),
+    }
+}
+
+struct Plane {
+    surface: Surface,
+    origin: Point<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+    normal: Vector<3>,
+}
+
+impl Plane {
+    fn new(surface: Surface) -> Self {
+        match surface {
+            Surface::SweptCurve(SweptCurve {
+                curve: Curve::Line(_),
+                ..
+            }) => {}
+            _ => panic!("Can only cut solids with planes"),
+        }
+
+        let origin = surface.point_from_surface_coords([0., 0.]);
+        let u = surface.vector_from_surface_coords([1., 0.]);
+        let v = surface.vector_from_surface_coords([0., 1.]);
+        let normal = u.cross(&v);
+
+        Self {
+            surface,
+            origin,
+            u,
+            v,
+            normal,
+        }
+    }
+
+    /// A value that is positive in front of the plane, negative behind it
+    fn side_of(&self, point: Point<3>) -> Scalar {
+        (point - self.origin).dot(&self.normal)
+    }
+
+    fn point_to_surface_coords(&self, point: Point<3>) -> Point<2> {
+        let w = point - self.origin;
+
+        let uu = self.u.dot(&self.u);
+        let uv = self.u.dot(&self.v);
+        let vv = self.v.dot(&self.v);
+        let uw = self.u.dot(&w);
+        let vw = self.v.dot(&w);
+
+        let det = uu * vv - uv * uv;
+
+        Point::from([(vv * uw - uv * vw) / det, (uu * vw - uv * uw) / det])
+    }
+}
+
+/// Cut a triangle, keeping the part that is behind the plane
+///
+/// Adds the kept part of the triangle to `triangles`, and the segment where
+/// the plane cuts the triangle, if any, to `cut_segments`.
+fn cut_triangle(
+    plane: &Plane,
+    points: [Point<3>; 3],
+    color: [u8; 4],
+    triangles: &mut Vec<(Triangle<3>, [u8; 4])>,
+    cut_segments: &mut Vec<[Point<3>; 2]>,
+) {
+    let sides = points.map(|point| plane.side_of(point));
+
+    let mut kept = Vec::new();
+    let mut cut = Vec::new();
+
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+
+        let [p, q] = [points[i], points[j]];
+        let [p_is_behind, q_is_behind] =
+            [sides[i], sides[j]].map(|side| side <= Scalar::ZERO);
+
+        if p_is_behind {
+            kept.push(p);
+        }
+        if p_is_behind != q_is_behind {
+            let point = cut_edge([p, q], [sides[i], sides[j]]);
+
+            kept.push(point);
+            cut.push(point);
+        }
+    }
+
+    kept.dedup();
+    if kept.len() > 1 && kept.first() == kept.last() {
+        kept.pop();
+    }
+
+    for i in 1..kept.len().saturating_sub(1) {
+        let [a, b, c] = [kept[0], kept[i], kept[i + 1]];
+
+        if (b - a).cross(&(c - a)).magnitude() != Scalar::ZERO {
+            triangles.push((Triangle::from_points([a, b, c]), color));
+        }
+    }
+
+    if let [a, b] = cut.as_slice() {
+        if a != b {
+            cut_segments.push([*a, *b]);
+        }
+    }
+}
+
+/// Compute the point where the plane cuts an edge
+///
+/// Neighboring triangles share edges. To make sure they agree on where those
+/// edges are cut, the result must not depend on the order of the points.
+fn cut_edge(points: [Point<3>; 2], sides: [Scalar; 2]) -> Point<3> {
+    let [a, b] = points;
+    let [side_a, side_b] = sides;
+
+    let (a, b, side_a, side_b) = if a <= b {
+        (a, b, side_a, side_b)
+    } else {
+        (b, a, side_b, side_a)
+    };
+
+    let t = side_a / (side_a - side_b);
+    a + (b - a) * t
+}
+
+fn cross_section(
+    plane: &Plane,
+    cut_segments: Vec<[Point<3>; 2]>,
+    stores: &Stores,
+    color: [u8; 4],
+) -> Sketch {
+    let loops: Vec<Vec<Point<2>>> = chain_segments(cut_segments)
+        .into_iter()
+        .map(|points| {
+            points
+                .into_iter()
+                .map(|point| plane.point_to_surface_coords(point))
+                .collect()
+        })
+        .collect();
+
+    // A loop that is contained in an even number of other loops bounds the
+    // cross-section on the outside. All other loops are holes, which belong
+    // to the smallest loop that contains them.
+    let containers: Vec<Vec<usize>> = loops
+        .iter()
+        .enumerate()
+        .map(|(i, points)| {
+            (0..loops.len())
+                .filter(|&j| j != i && polygon_contains(&loops[j], points[0]))
+                .collect()
+        })
+        .collect();
+
+    let mut faces = Vec::new();
+
+    for (i, exterior) in loops.iter().enumerate() {
+        if containers[i].len() % 2 != 0 {
+            continue;
+        }
+
+        let mut face = Face::builder(stores, plane.surface)
+            .with_exterior_polygon(oriented(exterior, true))
+            .with_color(color);
+
+        for (j, interior) in loops.iter().enumerate() {
+            let is_direct_hole = containers[j].len() == containers[i].len() + 1
+                && containers[j].contains(&i);
+
+            if is_direct_hole {
+                face = face.with_interior_polygon(oriented(interior, false));
+            }
+        }
+
+        faces.push(face.build());
+    }
+
+    Sketch::from_faces(faces)
+}
+
+/// Chain segments into closed loops
+///
+/// Segments that can't be chained into a closed loop are ignored.
+fn chain_segments(segments: Vec<[Point<3>; 2]>) -> Vec<Vec<Point<3>>> {
+    let mut neighbors: BTreeMap<Point<3>, Vec<Point<3>>> = BTreeMap::new();
+    for [a, b] in segments {
+        neighbors.entry(a).or_default().push(b);
+        neighbors.entry(b).or_default().push(a);
+    }
+
+    let mut visited = HashSet::new();
+    let mut loops = Vec::new();
+
+    for &start in neighbors.keys() {
+        if visited.contains(&start) {
+            continue;
+        }
+        visited.insert(start);
+
+        let mut points = vec![start];
+        let mut previous = None;
+        let mut current = start;
+        let mut is_closed = false;
+
+        loop {
+            let next = neighbors[&current].iter().copied().find(|&point| {
+                let is_start = point == start && points.len() > 2;
+                Some(point) != previous
+                    && (is_start || !visited.contains(&point))
+            });
+
+            match next {
+                Some(point) if point == start => {
+                    is_closed = true;
+                    break;
+                }
+                Some(point) => {
+                    visited.insert(point);
+                    points.push(point);
+
+                    previous = Some(current);
+                    current = point;
+                }
+                None => break,
+            }
+        }
+
+        if is_closed {
+            loops.push(points);
+        }
+    }
+
+    loops
+}
+
+fn polygon_contains(polygon: &[Point<2>], point: Point<2>) -> bool {
+    let mut contains = false;
+
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+
+        if (a.v > point.v) != (b.v > point.v) {
+            let u = a.u + (b.u - a.u) * (point.v - a.v) / (b.v - a.v);
+            if point.u < u {
+                contains = !contains;
+            }
+        }
+    }
+
+    contains
+}
+
+/// Return the points of the polygon, ordered counter-clockwise or clockwise
+fn oriented(polygon: &[Point<2>], counter_clockwise: bool) -> Vec<Point<2>> {
+    let mut double_area = Scalar::ZERO;
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        double_area += a.u * b.v - b.u * a.v;
+    }
+
+    let mut polygon = polygon.to_vec();
+    if (double_area > Scalar::ZERO) != counter_clockwise {
+        polygon.reverse();
+    }
+
+    polygon
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{Tolerance, TransformObject},
+        iter::ObjectIters,
+        objects::{Face, Solid, Stores, Surface},
+    };
+
+    #[test]
+    fn section_cube() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 2.);
+        let section = super::section(
+            &cube,
+            Surface::xy_plane(),
+            tolerance,
+            &stores,
+            [0, 255, 0, 255],
+            &mut DebugInfo::new(),
+        );
+
+        assert_eq!(section.cross_section.faces().count(), 1);
+
+        // The top face is removed completely, and the cap is added.
+        assert_eq!(section.solid.faces().count(), 6);
+
+        for vertex in section.solid.global_vertex_iter() {
+            assert!(vertex.position().z <= Scalar::ZERO);
+        }
+        for face in section.solid.faces() {
+            if let Face::Triangles(triangles) = face {
+                for (triangle, _) in triangles {
+                    for point in triangle.points() {
+                        assert!(point.z <= Scalar::ZERO);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_miss() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 2.);
+        let plane = Surface::xy_plane().translate([0., 0., 2.]);
+
+        let section = super::section(
+            &cube,
+            plane,
+            tolerance,
+            &stores,
+            [0, 255, 0, 255],
+            &mut DebugInfo::new(),
+        );
+
+        assert_eq!(section.cross_section.faces().count(), 0);
+        assert_eq!(section.solid.faces().count(), 6);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use fj_math::{Point, Scalar};
+
+use crate::objects::{Curve, Edge, GlobalVertex, Vertex, VerticesOfEdge};
+
+use super::Tolerance;
+
+/// Split an edge at the given curve coordinate
+///
+/// Returns two edges on the same curve as the original one. The first runs
+/// from the original edge's first vertex to a new vertex at `position`, the
+/// second from that new vertex to the original edge's second vertex. The new
+/// global vertex is inserted into the store of the original ones, so it is
+/// shared with any other edge that ends at the same point.
+///
+/// Returns an error, if the edge has no vertices, or if `position` is not
+/// strictly in between the positions of its vertices.
+pub fn split_edge(
+    edge: &Edge,
+    position: impl Into<Point<1>>,
+) -> Result<[Edge; 2], SplitError> {
+    let position = position.into();
+
+    let [a, b] = edge.vertices().ok_or(SplitError::Continuous)?;
+    let [t_a, t_b] = [a.position().t, b.position().t];
+
+    if (position.t - t_a) * (t_b - position.t) <= Scalar::ZERO {
+        return Err(SplitError::OutOfRange(position.t));
+    }
+
+    let global = a.global().store().insert(GlobalVertex::from_position(
+        edge.curve().point_from_curve_coords(position),
+    ));
+    let vertex = Vertex::new(position, global);
+
+    Ok([[a, vertex.clone()], [vertex, b]].map(|vertices| Edge {
+        curve: edge.curve.clone(),
+        vertices: VerticesOfEdge::from_vertices(vertices),
+    }))
+}
+
+/// Split an edge at the given point
+///
+/// Like [`split_edge`], but `point` is given in model coordinates. Returns an
+/// error, if `point` is further than `tolerance` from the edge's curve.
+pub fn split_edge_at_point(
+    edge: &Edge,
+    point: impl Into<Point<3>>,
+    tolerance: Tolerance,
+) -> Result<[Edge; 2], SplitError> {
+    let point = point.into();
+    let curve = edge.curve();
+
+    let [a, b] = edge.vertices().ok_or(SplitError::Continuous)?;
+    let mut position = curve.point_to_curve_coords(point);
+
+    let distance =
+        (curve.point_from_curve_coords(position) - point).magnitude();
+    if distance > tolerance.inner() {
+        return Err(SplitError::NotOnCurve(point));
+    }
+
+    // Circle coordinates are only defined up to a multiple of `2π`. Pick the
+    // coordinate that is past the edge's first vertex, in its direction.
+    if let Curve::Circle(_) = curve {
+        let [t_a, t_b] = [a.position().t, b.position().t];
+        let tau = Scalar::PI * 2.;
+
+        let offset = (position.t - t_a) % tau;
+        let offset = if offset < Scalar::ZERO {
+            offset + tau
+        } else {
+            offset
+        };
+
+        position = Point::from([if t_b < t_a {
+            t_a - (tau - offset)
+        } else {
+            t_a + offset
+        }]);
+    }
+
+    split_edge(edge, position)
+}
+
+/// An error that can occur while splitting an edge
+#[derive(Debug, thiserror::Error)]
+pub enum SplitError {
+    /// The edge has no vertices
+    #[error("Can't split continuous edge")]
+    Continuous,
+
+    /// The position to split at is not in between the edge's vertices
+    #[error("Position to split edge at ({0}) is outside of the edge")]
+    OutOfRange(Scalar),
+
+    /// The point to split at is not on the edge's curve
+    #[error("Point to split edge at ({0:?}) is not on the edge's curve")]
+    NotOnCurve(Point<3>),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::{ArcDirection, Circle, Point, Vector};
+
+    use crate::{
+        algorithms::Tolerance,
+        local::Local,
+        objects::{
+            Curve, Edge, GlobalVertex, Stores, Surface, Vertex, VerticesOfEdge,
+        },
+    };
+
+    use super::{split_edge, split_edge_at_point, SplitError};
+
+    #[test]
+    fn split_line() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let surface = Surface::xy_plane();
+
+        let edge = Edge::line_segment_from_points(
+            &stores,
+            &surface,
+            [[0., 0.], [2., 0.]],
+        );
+
+        let [a, b] = split_edge(&edge, [0.25])?;
+        let [a_start, a_end] = a.vertices.clone().expect_vertices();
+        let [b_start, b_end] = b.vertices.clone().expect_vertices();
+
+        assert_eq!(a.curve, edge.curve);
+        assert!(a_end.global().is_same(b_start.global()));
+        assert_eq!(a_end.global().position(), Point::from([0.5, 0., 0.]));
+        assert_eq!(a_start, edge.vertices().unwrap()[0]);
+        assert_eq!(b_end, edge.vertices().unwrap()[1]);
+
+        // The new vertex is shared with other edges.
+        let other = Edge::line_segment_from_points(
+            &stores,
+            &surface,
+            [[0.5, 0.], [0.5, 1.]],
+        );
+        assert!(other.vertices().unwrap()[0]
+            .global()
+            .is_same(a_end.global()));
+
+        assert!(matches!(
+            split_edge(&edge, [1.]),
+            Err(SplitError::OutOfRange(_))
+        ));
+        assert!(matches!(
+            split_edge_at_point(&edge, [1., 1., 0.], Tolerance::from(0.001)),
+            Err(SplitError::NotOnCurve(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_arc() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        // A half circle, from the negative x axis to the positive one, through
+        // the positive y axis, which is in the negative direction.
+        let circle = Circle {
+            center: Point::origin(),
+            a: Vector::unit_x(),
+            b: Vector::unit_y(),
+        };
+        let vertices = [[-1., 0., 0.], [1., 0., 0.]].map(|position| {
+            stores
+                .global_vertices
+                .insert(GlobalVertex::from_position(position))
+        });
+        let [start, end] = vertices;
+        let edge = Edge {
+            curve: Local::new(
+                Curve::Circle(Circle {
+                    center: Point::origin(),
+                    a: Vector::unit_u(),
+                    b: Vector::unit_v(),
+                }),
+                stores.curves.insert(Curve::Circle(circle)),
+            ),
+            vertices: VerticesOfEdge::from_vertices([
+                Vertex::new([PI], start),
+                Vertex::new([0.], end),
+            ]),
+        };
+
+        let [a, b] = split_edge_at_point(
+            &edge,
+            [0., 1., 0.],
+            Tolerance::from_scalar(0.001)?,
+        )?;
+
+        let [_, middle] = a.vertices.clone().expect_vertices();
+        assert!((middle.position().t.into_f64() - PI / 2.).abs() < 1e-12);
+
+        // Both halves run in the direction of the original edge.
+        for arc in [a, b].map(|edge| edge.arc().unwrap()) {
+            assert_eq!(arc.direction, ArcDirection::Negative);
+            assert!((arc.span().into_f64() - PI / 2.).abs() < 1e-12);
+        }
+
+        Ok(())
+    }
+}
@@ -1,68 +1,405 @@
-use fj_math::{Point, Scalar, Transform, Triangle, Vector};
+use std::collections::BTreeMap;
+
+use fj_interop::{
+    mesh::Welder,
+    progress::{Cancelled, Phase, ProgressHandle},
+};
+use fj_math::{Circle, Point, Scalar, Triangle, Vector};
 
 use crate::{
     iter::ObjectIters,
     local::Local,
+    naming::{Name, Names},
     objects::{
-        Curve, Cycle, Edge, Face, GlobalVertex, Sketch, Solid, Surface, Vertex,
-        VerticesOfEdge,
+        Curve, Cycle, Edge, Face, GlobalVertex, Handle, Sketch, Solid, Stores,
+        Surface, SweptCurve, Vertex, VerticesOfEdge, Winding,
     },
 };
 
-use super::{reverse_face, CycleApprox, Tolerance, TransformObject};
+use super::{
+    reverse_face, CycleApprox, EdgeApprox, Tolerance, TransformObject,
+};
 
 /// Create a solid by sweeping a sketch
+///
+/// Interior cycles of the sketch's faces become holes through the solid. Their
+/// side faces point into the hole.
+///
+/// The path doesn't need to be perpendicular to the sketch. An oblique path
+/// results in slanted side faces, and a top face that is offset sideways from
+/// the bottom face.
+///
+/// # Errors
+///
+/// Returns an error, if the path doesn't move a face of the sketch out of its
+/// plane by at least `tolerance`, as the resulting solid would be flat. The
+/// swept solid is checked for being closed, and an error is returned, if it
+/// isn't.
+///
+/// If `taper` (an angle, in radians) is not zero, the sketch is offset while
+/// it is swept, making the solid shrink (for a positive angle) or grow (for a
+/// negative one) linearly along the path. The angle is measured between the
+/// side faces and the path.
+///
+/// # Panics
+///
+/// Tapering is only supported for sketches whose cycles consist of line
+/// segments or full circles. Panics, if `taper` is not zero and this is not
+/// the case, or if the taper shrinks a circle down to nothing.
+///
+/// The side faces share their vertices and curves with the bottom and top
+/// faces. New objects are inserted into `stores`.
 pub fn sweep(
     source: Sketch,
     path: impl Into<Vector<3>>,
+    taper: impl Into<Scalar>,
     tolerance: Tolerance,
+    stores: &Stores,
     color: [u8; 4],
-) -> Solid {
-    let path = path.into();
+    progress: &mut ProgressHandle,
+) -> Result<Solid, SweepError> {
+    let name = Name::new("sweep");
+    let (solid, _) = sweep_named(
+        source, &name, path, taper, tolerance, stores, color, progress,
+    )?;
+    Ok(solid)
+}
 
-    let is_sweep_along_negative_direction =
-        path.dot(&Vector::from([0., 0., 1.])) < Scalar::ZERO;
+/// Create a solid by sweeping a sketch, and name its faces, edges, and vertices
+///
+/// Works like [`sweep`], but also returns the [`Names`] of the solid's
+/// topology. All names are nested within `name`. For the sketch face `i`, the
+/// names of the generated faces are:
+///
+/// - `{name}/face{i}/bottom`, for the face that the sweep starts from
+/// - `{name}/face{i}/top`, for the face that the sweep ends at
+/// - `{name}/face{i}/side/cycle{j}/edge{k}`, for the face swept from edge `k`
+///   of cycle `j`
+///
+/// Edges and vertices are named after their position in those faces, as
+/// described in [`Names::insert_face`]. Since names don't depend on geometry,
+/// they stay the same when the sketch changes, as long as the number and
+/// order of its faces, cycles, and edges stays the same.
+#[tracing::instrument(level = "debug", skip_all)]
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_named(
+    source: Sketch,
+    name: &Name,
+    path: impl Into<Vector<3>>,
+    taper: impl Into<Scalar>,
+    tolerance: Tolerance,
+    stores: &Stores,
+    color: [u8; 4],
+    progress: &mut ProgressHandle,
+) -> Result<(Solid, Names), SweepError> {
+    let path = path.into();
+    let taper = taper.into();
 
     let mut target = Vec::new();
+    let mut names = Names::new();
+    let mut name_last = |name: Name, target: &Vec<Face>| {
+        let face = target.last().expect("Face was just created");
+        names.insert_face(name, face);
+    };
+
+    let faces: Vec<_> = source.face_iter().collect();
+    let num_faces = faces.len();
+
+    for (i, face) in faces.into_iter().enumerate() {
+        progress.report(Phase::Sweep, i, num_faces);
+        progress.check()?;
+
+        let face_name = name.child(format_args!("face{i}"));
+
+        let normal = surface_normal(&face.surface());
+        let is_sweep_along_negative_direction =
+            path.dot(&normal) < Scalar::ZERO;
+
+        // How far the path moves the face out of its plane. For an oblique
+        // path, that is less than the length of the path.
+        let height = path.dot(&normal.normalize()).abs();
+        if height < tolerance.inner() {
+            return Err(SweepError::PathParallelToFace { path, face: i });
+        }
+
+        // How far the boundary of the top face is moved towards its inside.
+        let offset = height * taper.tan();
+        let top = if offset == Scalar::ZERO {
+            face.clone()
+        } else {
+            offset_face(&face, offset, stores)
+        };
 
-    for face in source.face_iter() {
         create_bottom_faces(
             &face,
             is_sweep_along_negative_direction,
             &mut target,
         );
+        name_last(face_name.child("bottom"), &target);
         create_top_face(
-            face.clone(),
+            top.clone(),
             path,
             is_sweep_along_negative_direction,
             &mut target,
         );
+        name_last(face_name.child("top"), &target);
+
+        let cycles = face.all_cycles().zip(top.all_cycles()).enumerate();
+        for (j, (cycle, top_cycle)) in cycles {
+            let edges = cycle.edges.into_iter().zip(top_cycle.edges);
+            for (k, (edge, top_edge)) in edges.enumerate() {
+                let side_name = face_name
+                    .child("side")
+                    .child(format_args!("cycle{j}"))
+                    .child(format_args!("edge{k}"));
 
-        for cycle in face.all_cycles() {
-            for edge in cycle.edges {
                 if let Some(vertices) = edge.vertices() {
+                    let vertices_top = top_edge
+                        .vertices()
+                        .expect("Offset edge should have vertices");
+
                     create_non_continuous_side_face(
                         path,
                         is_sweep_along_negative_direction,
-                        vertices.map(|vertex| vertex.global()),
+                        vertices.map(|vertex| vertex.global().clone()),
+                        vertices_top.map(|vertex| vertex.global().clone()),
+                        stores,
                         color,
                         &mut target,
                     );
+                    name_last(side_name, &target);
                     continue;
                 }
 
-                create_continuous_side_face(
-                    edge,
-                    path,
-                    tolerance,
-                    color,
-                    &mut target,
-                );
+                match edge.curve() {
+                    Curve::Circle(circle) if edge == top_edge => {
+                        create_cylindrical_side_face(
+                            circle,
+                            path,
+                            is_sweep_along_negative_direction,
+                            stores,
+                            color,
+                            &mut target,
+                        );
+                    }
+                    _ => {
+                        create_continuous_side_face(
+                            edge.get().clone(),
+                            top_edge.get().clone(),
+                            path,
+                            is_sweep_along_negative_direction,
+                            tolerance,
+                            color,
+                            &mut target,
+                        );
+                    }
+                }
+                name_last(side_name, &target);
+            }
+        }
+    }
+
+    progress.report(Phase::Sweep, num_faces, num_faces);
+
+    let solid = Solid::from_faces(target);
+
+    let open_edges = open_edges(&solid, tolerance);
+    if !open_edges.is_empty() {
+        return Err(SweepError::NotClosed { open_edges });
+    }
+
+    Ok((solid, names))
+}
+
+/// An error that can occur when sweeping a sketch
+#[derive(Debug, thiserror::Error)]
+pub enum SweepError {
+    /// The path doesn't move a face of the sketch out of its plane
+    ///
+    /// This is the case, if the path is zero, or parallel to the face, within
+    /// the tolerance.
+    #[error("Sweep path {path:?} is parallel to sketch face {face}")]
+    PathParallelToFace {
+        /// The path of the sweep
+        path: Vector<3>,
+
+        /// The index of the face within the sketch
+        face: usize,
+    },
+
+    /// The swept solid is not closed
+    ///
+    /// This is a bug in the sweep, or the result of a sketch that it doesn't
+    /// support.
+    #[error("Swept solid is not closed; it has {} open edges", open_edges.len())]
+    NotClosed {
+        /// The segments of the solid's approximation that are open
+        ///
+        /// Each of them is part of the boundary of only one face, or of
+        /// faces that don't agree on its direction.
+        open_edges: Vec<[Point<3>; 2]>,
+    },
+
+    /// The sweep was cancelled
+    #[error("Sweep was cancelled")]
+    Cancelled(#[from] Cancelled),
+}
+
+/// Find the open edges of a solid
+///
+/// The boundaries of the solid's faces are approximated, and the segments of
+/// those approximations are compared. In a closed solid, each segment is shared
+/// by two faces, which run along it in opposite directions. Since faces are
+/// approximated independently, points that are much closer to each other than
+/// `tolerance` are considered identical.
+fn open_edges(solid: &Solid, tolerance: Tolerance) -> Vec<[Point<3>; 2]> {
+    let mut welder = Welder::new(tolerance.inner() / 1000.);
+    let mut count: BTreeMap<[Point<3>; 2], i32> = BTreeMap::new();
+
+    let mut add_boundary = |points: &[Point<3>]| {
+        let points: Vec<_> =
+            points.iter().map(|&point| welder.weld(point)).collect();
+
+        for segment in points.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            if a == b {
+                continue;
+            }
+
+            *count.entry([a, b]).or_default() += 1;
+            *count.entry([b, a]).or_default() -= 1;
+        }
+    };
+
+    for face in solid.face_iter() {
+        match face {
+            Face::Face(face) => {
+                for cycle in face.all_cycles() {
+                    let points: Vec<_> = CycleApprox::new(&cycle, tolerance)
+                        .points
+                        .into_iter()
+                        .map(|point| point.global())
+                        .collect();
+                    add_boundary(&points);
+                }
+            }
+            Face::Triangles(triangles) => {
+                for (triangle, _) in triangles {
+                    let [a, b, c] = triangle.points();
+                    add_boundary(&[a, b, c, a]);
+                }
             }
         }
     }
 
-    Solid::from_faces(target)
+    count
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .map(|(segment, _)| segment)
+        .collect()
+}
+
+fn surface_normal(surface: &Surface) -> Vector<3> {
+    let u = surface.vector_from_surface_coords([1., 0.]);
+    let v = surface.vector_from_surface_coords([0., 1.]);
+
+    u.cross(&v)
+}
+
+/// Move the boundary of a face towards its inside, by `offset`
+///
+/// The exteriors of the face shrink, while its interiors grow.
+fn offset_face(face: &Face, offset: Scalar, stores: &Stores) -> Face {
+    let surface = face.surface();
+
+    let exteriors = face
+        .exteriors()
+        .map(|cycle| offset_cycle(&surface, &cycle, offset, stores));
+    let interiors = face
+        .interiors()
+        .map(|cycle| offset_cycle(&surface, &cycle, -offset, stores));
+
+    Face::new(surface, exteriors, interiors, face.color())
+}
+
+/// Move a cycle towards its inside, by `offset`
+fn offset_cycle(
+    surface: &Surface,
+    cycle: &Cycle,
+    offset: Scalar,
+    stores: &Stores,
+) -> Cycle {
+    if let [edge] = cycle.edges.as_slice() {
+        if let (Curve::Circle(local), Curve::Circle(global)) =
+            (edge.curve.local(), edge.curve())
+        {
+            let radius = local.a.magnitude();
+            assert!(
+                offset < radius,
+                "Taper is too large; tapered circle would vanish"
+            );
+            let scale = (radius - offset) / radius;
+
+            let local = Circle {
+                a: local.a * scale,
+                b: local.b * scale,
+                ..local
+            };
+            let global = Circle {
+                a: global.a * scale,
+                b: global.b * scale,
+                ..global
+            };
+
+            let edge = Edge {
+                curve: Local::new(
+                    Curve::Circle(local),
+                    stores.curves.insert(Curve::Circle(global)),
+                ),
+                vertices: VerticesOfEdge::none(),
+            };
+
+            return Cycle::new(stores, [edge]);
+        }
+    }
+
+    let mut points = Vec::new();
+    let mut directions = Vec::new();
+    for edge in &cycle.edges {
+        let (line, [a, b]) = match (edge.curve.local(), edge.vertices()) {
+            (Curve::Line(line), Some(vertices)) => (line, vertices),
+            _ => panic!("Tapering is only supported for lines and circles"),
+        };
+
+        let [a, b] =
+            [a, b].map(|vertex| line.point_from_line_coords(vertex.position()));
+
+        points.push(a);
+        directions.push((b - a).normalize());
+    }
+
+    // We need to know on which side of the edges the inside of the cycle is.
+    // That depends on whether it runs clockwise or counter-clockwise.
+    let offset = match cycle.winding() {
+        Winding::CounterClockwise => offset,
+        Winding::Clockwise => -offset,
+    };
+
+    // Each point is moved along the bisector of its adjacent edges, such that
+    // both of the edges are moved by `offset`.
+    let num_points = points.len();
+    let points = points.iter().enumerate().map(|(i, &point)| {
+        let previous = directions[(i + num_points - 1) % num_points];
+        let next = directions[i];
+
+        let [previous, next] =
+            [previous, next].map(|d| Vector::from([-d.v, d.u]));
+        let miter = (previous + next) / (Scalar::ONE + previous.dot(&next));
+
+        point + miter * offset
+    });
+
+    Cycle::polygon_from_points(stores, surface, points)
 }
 
 fn create_bottom_faces(
@@ -97,14 +434,17 @@ fn create_top_face(
 fn create_non_continuous_side_face(
     path: Vector<3>,
     is_sweep_along_negative_direction: bool,
-    vertices_bottom: [GlobalVertex; 2],
+    vertices_bottom: [Handle<GlobalVertex>; 2],
+    vertices_top: [Handle<GlobalVertex>; 2],
+    stores: &Stores,
     color: [u8; 4],
     target: &mut Vec<Face>,
 ) {
     let vertices = {
-        let vertices_top = vertices_bottom.map(|vertex| {
-            let position = vertex.position() + path;
-            GlobalVertex::from_position(position)
+        // The top vertices are translated the same way as the top face, so
+        // they end up being the same vertices as those of the top face.
+        let vertices_top = vertices_top.map(|vertex| {
+            stores.global_vertices.insert(vertex.translate(path))
         });
 
         let [[a, b], [c, d]] = [vertices_bottom, vertices_top];
@@ -117,15 +457,23 @@ fn create_non_continuous_side_face(
     };
 
     let surface = {
-        let [a, b, _, c] = vertices.map(|vertex| vertex.position());
+        let [a, b, _, c] = [0, 1, 2, 3].map(|i| vertices[i].position());
         Surface::plane_from_points([a, b, c])
     };
 
     let cycle = {
         let [a, b, c, d] = vertices;
 
-        let mut vertices =
-            vec![([0., 0.], a), ([1., 0.], b), ([1., 1.], c), ([0., 1.], d)];
+        // If the sweep is tapered, the side face is a trapezoid, not a
+        // parallelogram. Then `c` doesn't end up at `[1., 1.]`.
+        let c_local = surface.point_to_surface_coords(c.position());
+
+        let mut vertices = vec![
+            (Point::from([0., 0.]), a),
+            (Point::from([1., 0.]), b),
+            (c_local, c),
+            (Point::from([0., 1.]), d),
+        ];
         if let Some(vertex) = vertices.first().cloned() {
             vertices.push(vertex);
         }
@@ -142,14 +490,15 @@ fn create_non_continuous_side_face(
                 let local = Curve::line_from_points([a.0, b.0]);
 
                 let global = [a, b].map(|vertex| vertex.1.position());
-                let global = Curve::line_from_points(global);
+                let global =
+                    stores.curves.insert(Curve::line_from_points(global));
 
                 Local::new(local, global)
             };
 
             let vertices = VerticesOfEdge::from_vertices([
-                Vertex::new(Point::from([0.]), a.1),
-                Vertex::new(Point::from([1.]), b.1),
+                Vertex::new(Point::from([0.]), a.1.clone()),
+                Vertex::new(Point::from([1.]), b.1.clone()),
             ]);
 
             let edge = Edge { curve, vertices };
@@ -157,55 +506,168 @@ fn create_non_continuous_side_face(
             edges.push(edge);
         }
 
-        Cycle { edges }
+        Cycle::new(stores, edges)
     };
 
     let face = Face::new(surface, [cycle], [], color);
     target.push(face);
 }
 
+fn create_cylindrical_side_face(
+    circle: Circle<3>,
+    path: Vector<3>,
+    is_sweep_along_negative_direction: bool,
+    stores: &Stores,
+    color: [u8; 4],
+    target: &mut Vec<Face>,
+) {
+    let bottom = if is_sweep_along_negative_direction {
+        circle.reverse()
+    } else {
+        circle
+    };
+    let top = Circle {
+        center: bottom.center + path,
+        ..bottom
+    };
+
+    let surface = Surface::SweptCurve(SweptCurve {
+        curve: Curve::Circle(bottom),
+        path,
+    });
+
+    // The surface wraps around, so the face needs a seam where it meets
+    // itself. In surface coordinates, the face is a rectangle, with the seam
+    // on its left and right side.
+    let seam = [bottom, top].map(|circle| {
+        let position = circle.point_from_circle_coords([0.]);
+        stores
+            .global_vertices
+            .insert(GlobalVertex::from_position(position))
+    });
+    let seam_global = stores.curves.insert(Curve::line_from_points(
+        [&seam[0], &seam[1]].map(|vertex| vertex.position()),
+    ));
+
+    let full_turn = Scalar::PI * 2.;
+    let edges = [
+        (
+            [[0., 0.], [1., 0.]],
+            stores.curves.insert(Curve::Circle(bottom)),
+            [(Scalar::ZERO, &seam[0]), (full_turn, &seam[0])],
+        ),
+        (
+            [[full_turn.into_f64(), 0.], [full_turn.into_f64(), 1.]],
+            seam_global.clone(),
+            [(Scalar::ZERO, &seam[0]), (Scalar::ONE, &seam[1])],
+        ),
+        (
+            [[0., 1.], [1., 1.]],
+            stores.curves.insert(Curve::Circle(top)),
+            [(full_turn, &seam[1]), (Scalar::ZERO, &seam[1])],
+        ),
+        (
+            [[0., 0.], [0., 1.]],
+            seam_global,
+            [(Scalar::ONE, &seam[1]), (Scalar::ZERO, &seam[0])],
+        ),
+    ];
+
+    let edges = edges.into_iter().map(|(local, global, vertices)| {
+        let curve = Local::new(Curve::line_from_points(local), global);
+        let vertices =
+            VerticesOfEdge::from_vertices(vertices.map(
+                |(position, global)| Vertex::new([position], global.clone()),
+            ));
+
+        Edge { curve, vertices }
+    });
+
+    let face = Face::new(surface, [Cycle::new(stores, edges)], [], color);
+    target.push(face);
+}
+
+/// Create a side face that approximates a continuous edge
+///
+/// This is only used where the side face can't be represented exactly, which
+/// is the case for tapered circles, as cones are not supported yet.
 fn create_continuous_side_face(
     edge: Edge,
+    top_edge: Edge,
     path: Vector<3>,
+    is_sweep_along_negative_direction: bool,
     tolerance: Tolerance,
     color: [u8; 4],
     target: &mut Vec<Face>,
 ) {
-    let translation = Transform::translation(path);
+    // The top edge is either the same as the bottom edge, or a circle that
+    // was scaled around its center, if the sweep is tapered. In the latter
+    // case, its approximation can have a different number of points, so both
+    // edges are approximated separately, the same way the bottom and top faces
+    // are. The points are then matched up by how far along the edge they are.
+    let [bottom, top] = [(edge, Vector::from([0., 0., 0.])), (top_edge, path)]
+        .map(|(edge, offset)| {
+            let points = EdgeApprox::new(&edge, tolerance).points;
+            let last = points.len().saturating_sub(1).max(1);
 
-    let cycle = Cycle { edges: vec![edge] };
-    let approx = CycleApprox::new(&cycle, tolerance);
+            points
+                .into_iter()
+                .enumerate()
+                .map(|(i, point)| {
+                    (i as f64 / last as f64, point.global() + offset)
+                })
+                .collect::<Vec<_>>()
+        });
 
-    let mut quads = Vec::new();
-    for segment in approx.segments() {
-        let [v0, v1] = segment.points();
-        let [v3, v2] = {
-            let segment = translation.transform_segment(&segment);
-            segment.points()
-        };
+    let mut triangles = Vec::new();
+    let [mut i, mut j] = [0, 0];
+    while i + 1 < bottom.len() || j + 1 < top.len() {
+        let advance_bottom = j + 1 == top.len()
+            || (i + 1 < bottom.len() && bottom[i + 1].0 <= top[j + 1].0);
 
-        quads.push([v0, v1, v2, v3]);
+        if advance_bottom {
+            triangles.push([bottom[i].1, bottom[i + 1].1, top[j].1]);
+            i += 1;
+        } else {
+            triangles.push([bottom[i].1, top[j + 1].1, top[j].1]);
+            j += 1;
+        }
     }
 
-    let mut side_face: Vec<(Triangle<3>, _)> = Vec::new();
-    for [v0, v1, v2, v3] in quads {
-        side_face.push(([v0, v1, v2].into(), color));
-        side_face.push(([v0, v2, v3].into(), color));
-    }
+    let side_face = triangles
+        .into_iter()
+        .map(|[a, b, c]| {
+            let triangle = if is_sweep_along_negative_direction {
+                [a, c, b]
+            } else {
+                [a, b, c]
+            };
+            (Triangle::from(triangle), color)
+        })
+        .collect();
 
     target.push(Face::Triangles(side_face));
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::HashMap, f64::consts::PI};
+
+    use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
     use fj_math::{Point, Scalar, Vector};
 
     use crate::{
-        algorithms::Tolerance,
+        algorithms::{triangulate, Tolerance, TriangulationConfig},
         iter::ObjectIters,
-        objects::{Face, Sketch, Surface},
+        naming::Name,
+        objects::{
+            Curve, Cycle, Edge, Face, Sketch, Solid, Stores, Surface,
+            SweptCurve,
+        },
     };
 
+    use super::SweepError;
+
     #[test]
     fn bottom_positive() -> anyhow::Result<()> {
         test_bottom_top(
@@ -266,6 +728,337 @@ mod tests {
         )
     }
 
+    #[test]
+    fn taper() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .build();
+        let sketch = Sketch::from_faces([face]);
+
+        let solid = super::sweep(
+            sketch,
+            [0., 0., 1.],
+            Scalar::from_f64(0.5).atan2(Scalar::ONE),
+            tolerance,
+            &stores,
+            [255, 0, 0, 255],
+            &mut ProgressHandle::new(),
+        )?;
+
+        let expected_top = [[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]]
+            .map(|[x, y]| Point::from([x, y, 1.]));
+        for expected in expected_top {
+            let is_top_vertex = solid.global_vertex_iter().any(|vertex| {
+                (vertex.position() - expected).magnitude() < Scalar::from(1e-9)
+            });
+            assert!(is_top_vertex);
+        }
+
+        // Bottom, top, and 4 sides
+        assert_eq!(solid.face_iter().count(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn circle() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let face = Face::new(
+            Surface::xy_plane(),
+            [Cycle::new(
+                &stores,
+                [Edge::circle_from_radius(&stores, Scalar::ONE)],
+            )],
+            [],
+            [255, 0, 0, 255],
+        );
+        let sketch = Sketch::from_faces([face]);
+
+        for path in [[0., 0., 2.], [0., 0., -2.]] {
+            let solid = super::sweep(
+                sketch.clone(),
+                path,
+                0.,
+                tolerance,
+                &stores,
+                [255, 0, 0, 255],
+                &mut ProgressHandle::new(),
+            )?;
+
+            // The side face is an exact cylinder, not an approximation.
+            let side = solid
+                .face_iter()
+                .find(|face| {
+                    matches!(
+                        face.surface(),
+                        Surface::SweptCurve(SweptCurve {
+                            curve: Curve::Circle(_),
+                            ..
+                        })
+                    )
+                })
+                .expect("Expected cylindrical side face");
+            assert!(matches!(side, Face::Face(_)));
+
+            let volume = volume_of_closed_mesh(solid, tolerance)?;
+
+            // The circle is approximated by a polygon, which is between the
+            // circle and a circle that is smaller by the tolerance.
+            let radius = Scalar::ONE - tolerance.inner();
+            let [min, max] = [radius, Scalar::ONE]
+                .map(|radius| Scalar::PI * radius * radius * 2.);
+            assert!(volume > min && volume < max, "{volume}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn holes() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        // A square with a square hole and a circular one
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[-2., -2.], [3., -2.], [3., 3.], [-2., 3.]])
+            .with_interior_polygon([
+                [1.5, 1.5],
+                [2.5, 1.5],
+                [2.5, 2.5],
+                [1.5, 2.5],
+            ])
+            .build();
+        let circle = Cycle::new(
+            &stores,
+            [Edge::circle_from_radius(&stores, Scalar::ONE)],
+        )
+        .reverse();
+        let face = Face::new(
+            face.surface(),
+            face.exteriors(),
+            face.interiors().chain([circle]),
+            face.color(),
+        );
+        let sketch = Sketch::from_faces([face]);
+
+        // Oblique paths result in the same volume, as the height is the same.
+        let paths =
+            [[0., 0., 2.], [0., 0., -2.], [1., 0.5, 2.], [-1., 0.5, -2.]];
+        for path in paths {
+            for taper in [0., 0.1] {
+                let solid = super::sweep(
+                    sketch.clone(),
+                    path,
+                    taper,
+                    tolerance,
+                    &stores,
+                    [255, 0, 0, 255],
+                    &mut ProgressHandle::new(),
+                )?;
+
+                // Bottom, top, 4 sides for the exterior, 4 for the square
+                // hole, and 1 for the circular hole
+                assert_eq!(solid.face_iter().count(), 11);
+
+                // The boundary of the cross section moves by `d` at height
+                // `z`. Its area is quadratic in `z`, so Simpson's rule is
+                // exact. The circle is approximated by a polygon inside of
+                // it, which makes the solid a bit larger.
+                let area = |z: f64| {
+                    let d = z * f64::tan(taper);
+                    let exterior = (5. - d * 2.) * (5. - d * 2.);
+                    let square = (1. + d * 2.) * (1. + d * 2.);
+                    let circle = PI * (1. + d) * (1. + d);
+                    exterior - square - circle
+                };
+                let expected = (area(0.) + area(1.) * 4. + area(2.)) / 3.;
+
+                let volume = volume_of_closed_mesh(solid, tolerance)?;
+                let error = volume - Scalar::from(expected);
+                assert!(
+                    error > Scalar::ZERO && error < Scalar::from(0.2),
+                    "{volume}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn oblique_and_parallel() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
+            .build();
+        let sketch = Sketch::from_faces([face]);
+
+        let sweep = |path: [f64; 3]| {
+            super::sweep(
+                sketch.clone(),
+                path,
+                0.,
+                tolerance,
+                &stores,
+                [255, 0, 0, 255],
+                &mut ProgressHandle::new(),
+            )
+        };
+
+        let solid = sweep([2., -1., 1.])?;
+        let is_top_vertex = solid
+            .global_vertex_iter()
+            .any(|vertex| vertex.position() == Point::from([2., -1., 1.]));
+        assert!(is_top_vertex);
+
+        for path in [[1., 1., 0.], [1., 1., 1e-9], [0., 0., -0.5]] {
+            assert!(matches!(
+                sweep(path),
+                Err(SweepError::PathParallelToFace { face: 0, .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn open_edges() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        assert!(super::open_edges(&cube, tolerance).is_empty());
+
+        // Without one of its faces, the cube is open along that face's edges.
+        let faces: Vec<_> = cube.into_faces().into_iter().skip(1).collect();
+        let open = Solid::from_faces(faces);
+        assert_eq!(super::open_edges(&open, tolerance).len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn shared_objects() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
+            .build();
+        let sketch = Sketch::from_faces([face]);
+
+        let solid = super::sweep(
+            sketch,
+            [0., 0., 1.],
+            0.,
+            tolerance,
+            &stores,
+            [255, 0, 0, 255],
+            &mut ProgressHandle::new(),
+        )?;
+
+        // The faces of the solid share their vertices, instead of each having
+        // their own copy.
+        assert_eq!(stores.global_vertices.len(), 6);
+        for vertex in solid.vertex_iter() {
+            assert!(stores.global_vertices.contains(vertex.global()));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn names() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let name = Name::new("sweep");
+
+        // Regenerate the same sweep with different dimensions. The names must
+        // refer to corresponding faces, edges, and vertices each time.
+        for size in [1., 2.] {
+            let face = Face::builder(&stores, Surface::xy_plane())
+                .with_exterior_polygon([[0., 0.], [size, 0.], [0., size]])
+                .build();
+            let sketch = Sketch::from_faces([face]);
+
+            let (solid, names) = super::sweep_named(
+                sketch,
+                &name,
+                [0., 0., size],
+                0.,
+                tolerance,
+                &stores,
+                [255, 0, 0, 255],
+                &mut ProgressHandle::new(),
+            )?;
+
+            // Bottom, top, and 3 sides
+            assert_eq!(names.faces().count(), 5);
+            for (_, face) in names.faces() {
+                assert!(solid.face_iter().any(|f| f == *face));
+            }
+
+            let top = names.face(&name.child("face0/top")).unwrap();
+            let top_origin = top.surface().point_from_surface_coords([0., 0.]);
+            assert_eq!(top_origin.z, Scalar::from(size));
+
+            let side = name.child("face0/side/cycle0/edge0");
+            assert!(names.face(&side).is_some());
+
+            let vertex = names
+                .vertex(&name.child("face0/top/cycle0/vertex1"))
+                .unwrap();
+            assert_eq!(vertex.position(), Point::from([size, 0., size]));
+        }
+
+        Ok(())
+    }
+
+    /// Triangulate a solid, check that the mesh is closed, and compute its
+    /// volume
+    fn volume_of_closed_mesh(
+        solid: Solid,
+        tolerance: Tolerance,
+    ) -> anyhow::Result<Scalar> {
+        let mesh = triangulate(
+            solid.into_faces().into_iter().collect(),
+            tolerance,
+            &TriangulationConfig {
+                min_angle: Scalar::ZERO,
+                ..TriangulationConfig::default()
+            },
+            &mut DebugInfo::new(),
+            &mut ProgressHandle::new(),
+        )?
+        .weld(Scalar::from(1e-9));
+
+        let mut edges: HashMap<[Point<3>; 2], i32> = HashMap::new();
+        let mut volume = Scalar::ZERO;
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.points;
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                *edges.entry([p, q]).or_default() += 1;
+                *edges.entry([q, p]).or_default() -= 1;
+            }
+
+            volume += a.coords.dot(&b.coords.cross(&c.coords)) / 6.;
+        }
+        assert!(edges.values().all(|&count| count == 0));
+
+        Ok(volume)
+    }
+
     fn test_side(
         direction: impl Into<Vector<3>>,
         expected_surfaces: [[impl Into<Point<3>>; 3]; 3],
@@ -290,15 +1083,23 @@ mod tests {
         expected_surfaces: impl IntoIterator<Item = [impl Into<Point<3>>; 3]>,
         expected_vertices: impl IntoIterator<Item = impl Into<Point<2>>>,
     ) -> anyhow::Result<()> {
+        let stores = Stores::new();
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
 
-        let face = Face::builder(Surface::xy_plane())
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
             .build();
         let sketch = Sketch::from_faces([face]);
 
-        let solid =
-            super::sweep(sketch, direction, tolerance, [255, 0, 0, 255]);
+        let solid = super::sweep(
+            sketch,
+            direction,
+            0.,
+            tolerance,
+            &stores,
+            [255, 0, 0, 255],
+            &mut ProgressHandle::new(),
+        )?;
 
         let expected_vertices: Vec<_> = expected_vertices
             .into_iter()
@@ -308,15 +1109,37 @@ mod tests {
         let faces = expected_surfaces.into_iter().map(|surface| {
             let surface = Surface::plane_from_points(surface);
 
-            Face::builder(surface)
+            Face::builder(&stores, surface)
                 .with_exterior_polygon(expected_vertices.clone())
                 .build()
         });
 
+        // Reversed faces don't have the same representation as those that are
+        // built from scratch. Compare their geometry instead.
         for face in faces {
-            assert!(solid.face_iter().any(|f| f == face));
+            let vertices = exterior_vertices(&face);
+            assert!(solid.face_iter().any(|f| {
+                f.surface() == face.surface()
+                    && exterior_vertices(&f) == vertices
+            }));
         }
 
         Ok(())
     }
+
+    /// The positions of the exterior vertices, starting at the smallest one
+    fn exterior_vertices(face: &Face) -> Vec<Point<3>> {
+        let mut vertices: Vec<_> = face
+            .exteriors()
+            .flat_map(|cycle| cycle.edges)
+            .filter_map(|edge| edge.vertices())
+            .map(|[a, _]| a.global().position())
+            .collect();
+
+        if let Some(min) = (0..vertices.len()).min_by_key(|&i| vertices[i]) {
+            vertices.rotate_left(min);
+        }
+
+        vertices
+    }
 }
@@ -0,0 +1,138 @@
+use fj_interop::progress::ProgressHandle;
+use fj_math::{Scalar, Vector};
+
+use crate::objects::{Curve, Face, Sketch, Solid, Stores, Surface, SweptCurve};
+
+use super::{sweep, SweepError, Tolerance, TransformObject};
+
+/// Create a solid by thickening faces
+///
+/// Each face is offset along the normal of its surface, by `thickness`, to the
+/// side (or sides) specified by `side`. The front of a face is the side that
+/// its normal points to.
+///
+/// # Implementation Note
+///
+/// Faces are thickened individually. Thickening a shell whose faces are not
+/// coplanar results in overlapping solids (or gaps between them) at the edges
+/// where those faces meet, as the offset faces are not trimmed against each
+/// other yet.
+///
+/// # Errors
+///
+/// Returns an error, if sweeping any of the faces fails. See [`sweep`].
+///
+/// # Panics
+///
+/// Panics, if any of the faces is not defined in a plane.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn thicken(
+    faces: impl IntoIterator<Item = Face>,
+    thickness: impl Into<Scalar>,
+    side: ThickenSide,
+    tolerance: Tolerance,
+    stores: &Stores,
+    color: [u8; 4],
+    progress: &mut ProgressHandle,
+) -> Result<Solid, SweepError> {
+    let thickness = thickness.into();
+
+    let [front, back] = match side {
+        ThickenSide::Front => [thickness, Scalar::ZERO],
+        ThickenSide::Back => [Scalar::ZERO, thickness],
+        ThickenSide::Both => [thickness, thickness],
+    };
+
+    let mut target = Vec::new();
+
+    for face in faces {
+        let normal = plane_normal(&face.surface());
+
+        let sketch = Sketch::from_faces([face]).translate(normal * -back);
+        let solid = sweep(
+            sketch,
+            normal * (front + back),
+            0.,
+            tolerance,
+            stores,
+            color,
+            progress,
+        )?;
+
+        target.extend(solid.into_faces());
+    }
+
+    Ok(Solid::from_faces(target))
+}
+
+/// The side of a face that [`thicken`] offsets it to
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ThickenSide {
+    /// Offset the face to the side its normal points to
+    Front,
+
+    /// Offset the face to the side opposite of where its normal points
+    Back,
+
+    /// Offset the face to both sides, by the full thickness each
+    Both,
+}
+
+fn plane_normal(surface: &Surface) -> Vector<3> {
+    match surface {
+        Surface::SweptCurve(SweptCurve {
+            curve: Curve::Line(_),
+            ..
+        }) => {}
+        _ => panic!("Can only thicken faces that are defined in planes"),
+    }
+
+    let u = surface.vector_from_surface_coords([1., 0.]);
+    let v = surface.vector_from_surface_coords([0., 1.]);
+
+    u.cross(&v).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::progress::ProgressHandle;
+
+    use crate::{
+        algorithms::{reverse_face, Tolerance, TransformObject},
+        iter::ObjectIters,
+        objects::{Face, Stores, Surface},
+    };
+
+    use super::{thicken, ThickenSide};
+
+    #[test]
+    fn thicken_both_sides() {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(1.).unwrap();
+
+        let face = Face::builder(&stores, Surface::xz_plane())
+            .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
+            .build();
+
+        let solid = thicken(
+            [face.clone()],
+            0.5,
+            ThickenSide::Both,
+            tolerance,
+            &stores,
+            [255, 0, 0, 255],
+            &mut ProgressHandle::new(),
+        )
+        .unwrap();
+
+        // The normal of the xz-plane points towards negative y. The back of
+        // the solid faces the other way.
+        let front = face.clone().translate([0., -0.5, 0.]);
+        let back = reverse_face(&face.translate([0., 0.5, 0.]));
+
+        assert_eq!(solid.face_iter().count(), 5);
+        assert!(solid.face_iter().any(|f| f == front));
+        assert!(solid.face_iter().any(|f| f == back));
+    }
+}
@@ -3,8 +3,8 @@ use fj_math::{Transform, Vector};
 use crate::{
     local::Local,
     objects::{
-        Curve, Cycle, CyclesInFace, Edge, Face, FaceBRep, GlobalVertex, Sketch,
-        Solid, Surface, Vertex,
+        Curve, Cycle, CyclesInFace, Edge, Face, FaceBRep, GlobalVertex, Shell,
+        Sketch, Solid, Surface, Vertex,
     },
 };
 
@@ -49,7 +49,8 @@ impl TransformObject for Curve<3> {
 impl TransformObject for Cycle {
     fn transform(mut self, transform: &Transform) -> Self {
         for edge in &mut self.edges {
-            *edge = edge.transform(transform);
+            *edge =
+                edge.store().insert(edge.get().clone().transform(transform));
         }
 
         self
@@ -58,9 +59,10 @@ impl TransformObject for Cycle {
 
 impl TransformObject for Edge {
     fn transform(self, transform: &Transform) -> Self {
+        let global = self.curve.global();
         let curve = Local::new(
             self.curve.local(),
-            self.curve.global().transform(transform),
+            global.store().insert(global.transform(transform)),
         );
 
         let vertices = self.vertices.map(|vertex| vertex.transform(transform));
@@ -79,12 +81,14 @@ impl TransformObject for Face {
                 let interiors = transform_cycles(&face.interiors, transform);
 
                 let color = face.color;
+                let tolerance = face.tolerance;
 
                 Self::Face(FaceBRep {
                     surface,
                     exteriors,
                     interiors,
                     color,
+                    tolerance,
                 })
             }
             Self::Triangles(triangles) => {
@@ -108,7 +112,7 @@ impl TransformObject for GlobalVertex {
     }
 }
 
-impl TransformObject for Sketch {
+impl TransformObject for Shell {
     fn transform(self, transform: &Transform) -> Self {
         let faces = self
             .into_faces()
@@ -118,7 +122,7 @@ impl TransformObject for Sketch {
     }
 }
 
-impl TransformObject for Solid {
+impl TransformObject for Sketch {
     fn transform(self, transform: &Transform) -> Self {
         let faces = self
             .into_faces()
@@ -128,6 +132,16 @@ impl TransformObject for Solid {
     }
 }
 
+impl TransformObject for Solid {
+    fn transform(self, transform: &Transform) -> Self {
+        let shells = self
+            .into_shells()
+            .into_iter()
+            .map(|shell| shell.transform(transform));
+        Self::from_shells(shells)
+    }
+}
+
 impl TransformObject for Surface {
     fn transform(self, transform: &Transform) -> Self {
         match self {
@@ -140,11 +154,15 @@ impl TransformObject for Surface {
 
 impl TransformObject for Vertex {
     fn transform(self, transform: &Transform) -> Self {
-        Self::new(self.position(), self.global().transform(transform))
+        let global = self.global();
+        let global = global.store().insert(global.transform(transform));
+
+        Self::new(self.position(), global)
     }
 }
 
 /// Transform a shape
+#[tracing::instrument(level = "debug", skip_all, fields(faces = faces.len()))]
 pub fn transform_faces(faces: &mut Vec<Face>, transform: &Transform) {
     for face in faces {
         *face = face.clone().transform(transform);
@@ -1,25 +1,78 @@
-use fj_math::{Point, Scalar, Triangle, Winding};
+use fj_math::{Point, Scalar};
 use spade::HasPosition;
 
 use crate::local::Local;
 
-/// Create a Delaunay triangulation of all points
-pub fn triangulate(points: Vec<Local<Point<2>>>) -> Vec<[Local<Point<2>>; 3]> {
+/// Create a constrained Delaunay triangulation of all points
+///
+/// The segments of the provided cycles become edges of the triangulation. This
+/// makes sure that the boundaries of a face, including those of its holes, are
+/// respected, even where the points alone wouldn't produce those edges.
+///
+/// Segments that would cross a segment that was already added are skipped, as
+/// the triangulation can't represent them. Those can only occur, if the cycles
+/// intersect each other, which they shouldn't.
+pub fn triangulate<'r>(
+    points: Vec<Local<Point<2>>>,
+    cycles: impl IntoIterator<Item = &'r [Local<Point<2>>]>,
+) -> Vec<[Local<Point<2>>; 3]> {
     use spade::Triangulation as _;
 
-    let triangulation = spade::DelaunayTriangulation::<_>::bulk_load(points)
-        .expect("Inserted invalid values into triangulation");
+    let mut triangulation =
+        spade::ConstrainedDelaunayTriangulation::<_>::bulk_load(points)
+            .expect("Inserted invalid values into triangulation");
+
+    for cycle in cycles {
+        for segment in cycle.windows(2) {
+            let [a, b] = [segment[0], segment[1]].map(|point| {
+                triangulation
+                    .insert(point)
+                    .expect("Inserted invalid values into triangulation")
+            });
+
+            if a == b
+                || triangulation.exists_constraint(a, b)
+                || triangulation.intersects_constraint(
+                    segment[0].position(),
+                    segment[1].position(),
+                )
+            {
+                continue;
+            }
+
+            triangulation.add_constraint(a, b);
+        }
+    }
 
     let mut triangles = Vec::new();
     for triangle in triangulation.inner_faces() {
         let [v0, v1, v2] = triangle.vertices().map(|vertex| *vertex.data());
-        let orientation =
-            Triangle::<2>::from_points([v0.local(), v1.local(), v2.local()])
-                .winding_direction();
 
-        let triangle = match orientation {
-            Winding::Ccw => [v0, v1, v2],
-            Winding::Cw => [v0, v2, v1],
+        let [a, b, c] = [v0, v1, v2].map(|point| robust::Coord {
+            x: point.local().u.into_f64(),
+            y: point.local().v.into_f64(),
+        });
+        let orientation = robust::orient2d(a, b, c);
+
+        // A point that splits a constraint is off that constraint by a
+        // rounding error, which results in a triangle that doesn't span any
+        // meaningful area. Such a triangle doesn't contribute to the mesh, and
+        // its orientation is not reliable, so we skip it.
+        //
+        // The orientation is twice the area of the triangle, so this compares
+        // the sine of the triangle's smallest angle against the threshold.
+        let longest_edge_squared = [(a, b), (b, c), (c, a)]
+            .into_iter()
+            .map(|(p, q)| (q.x - p.x).powi(2) + (q.y - p.y).powi(2))
+            .fold(0., f64::max);
+        if orientation.abs() <= longest_edge_squared * 1e-9 {
+            continue;
+        }
+
+        let triangle = if orientation > 0. {
+            [v0, v1, v2]
+        } else {
+            [v0, v2, v1]
         };
 
         triangles.push(triangle);
@@ -1,90 +1,275 @@
-mod delaunay;
-mod polygon;
+pub(super) mod delaunay;
+pub(super) mod polygon;
 mod ray;
+mod refine;
 
-use fj_interop::{debug::DebugInfo, mesh::Mesh};
-use fj_math::Point;
+use fj_interop::{
+    debug::{DebugCategory, DebugInfo},
+    mesh::{FaceId, FaceMesh, Mesh, Triangle},
+    progress::{Cancelled, Phase, ProgressHandle},
+};
+use fj_math::{Point, Scalar};
 
-use crate::objects::Face;
+use crate::{
+    local::Local,
+    objects::{Face, Surface},
+};
 
 use self::polygon::Polygon;
 
-use super::{FaceApprox, Tolerance};
+use super::{ApproxCache, FaceApprox, Tolerance};
 
 /// Triangulate a shape
+///
+/// Returns an error, if the triangulation has been cancelled via `progress`.
 pub fn triangulate(
     faces: Vec<Face>,
     tolerance: Tolerance,
+    config: &TriangulationConfig,
     debug_info: &mut DebugInfo,
-) -> Mesh<Point<3>> {
+    progress: &mut ProgressHandle,
+) -> Result<Mesh<Point<3>>, Cancelled> {
     let mut mesh = Mesh::new();
 
-    for face in faces {
+    triangulate_faces(
+        faces,
+        tolerance,
+        config,
+        debug_info,
+        progress,
+        |face_mesh| {
+            for triangle in face_mesh.triangles {
+                mesh.push_triangle(triangle.points, triangle.color);
+            }
+        },
+    )?;
+
+    Ok(mesh)
+}
+
+/// Triangulate a shape, face by face
+///
+/// Passes the triangles of each face to `face_mesh`, as soon as that face has
+/// been triangulated. Faces are identified by their index within `faces`.
+///
+/// Returns an error, if the triangulation has been cancelled via `progress`.
+#[tracing::instrument(level = "debug", skip_all, fields(faces = faces.len()))]
+pub fn triangulate_faces(
+    faces: Vec<Face>,
+    tolerance: Tolerance,
+    config: &TriangulationConfig,
+    debug_info: &mut DebugInfo,
+    progress: &mut ProgressHandle,
+    mut face_mesh: impl FnMut(FaceMesh),
+) -> Result<(), Cancelled> {
+    let num_faces = faces.len();
+
+    // Faces share curves with their neighbors, which only need to be
+    // approximated once.
+    let cache = ApproxCache::new();
+
+    for (i, face) in faces.into_iter().enumerate() {
+        progress.check()?;
+        progress.report(Phase::Triangulation, i, num_faces);
+
+        let _span =
+            tracing::trace_span!("triangulate_face", face = i).entered();
+
+        let mut mesh = FaceMesh {
+            face_id: FaceId(i),
+            triangles: Vec::new(),
+        };
+
+        // Some faces need to be approximated more finely than the rest.
+        let tolerance = match face.tolerance() {
+            Some(face_tolerance) => face_tolerance.min(tolerance),
+            None => tolerance,
+        };
+
         match &face {
             Face::Face(brep) => {
                 let surface = brep.surface;
-                let approx = FaceApprox::new(&face, tolerance);
-
-                let points: Vec<_> = approx.points.into_iter().collect();
-                let face_as_polygon = Polygon::new(surface)
-                    .with_exterior(
-                        approx
-                            .exterior
-                            .points
-                            .into_iter()
-                            .map(|point| point.local()),
-                    )
-                    .with_interiors(approx.interiors.into_iter().map(
-                        |interior| {
-                            interior
-                                .points
-                                .into_iter()
-                                .map(|point| point.local())
-                        },
-                    ));
-
-                let mut triangles = delaunay::triangulate(points);
-                triangles.retain(|triangle| {
-                    face_as_polygon.contains_triangle(
-                        triangle.map(|point| point.local()),
+                let grid = match config.strategy {
+                    TriangulationStrategy::Delaunay => None,
+                    TriangulationStrategy::Grid => {
+                        FaceApprox::on_grid_with_cache(
+                            &face,
+                            tolerance,
+                            Some(&cache),
+                        )
+                    }
+                };
+                let approx = grid.unwrap_or_else(|| {
+                    let mut approx =
+                        FaceApprox::cached(&face, tolerance, &cache);
+                    refine::refine(
+                        &mut approx,
+                        &surface,
+                        tolerance,
+                        config,
                         debug_info,
-                    )
+                    );
+                    approx
                 });
 
+                for cycle in
+                    Some(&approx.exterior).into_iter().chain(&approx.interiors)
+                {
+                    debug_info.push_curve(
+                        DebugCategory::FaceApproximation,
+                        cycle.points.iter().map(|point| point.global()),
+                        [0, 0, 255, 255],
+                    );
+                }
+                if let Some(point) = approx.exterior.points.first() {
+                    debug_info.push_label(
+                        DebugCategory::FaceApproximation,
+                        point.global(),
+                        format!("face {i}"),
+                        [0, 0, 255, 255],
+                    );
+                }
+
+                let triangles = triangulate_face(&approx, surface, debug_info);
+
                 for triangle in triangles {
-                    let points = triangle.map(|point| point.global());
-                    mesh.push_triangle(points, brep.color);
+                    mesh.triangles.push(Triangle {
+                        points: triangle.map(|point| point.global()),
+                        color: brep.color,
+                    });
                 }
             }
             Face::Triangles(triangles) => {
                 for &(triangle, color) in triangles {
-                    mesh.push_triangle(triangle.points(), color);
+                    mesh.triangles.push(Triangle {
+                        points: triangle.points(),
+                        color,
+                    });
                 }
             }
         }
+
+        face_mesh(mesh);
     }
 
-    mesh
+    progress.report(Phase::Triangulation, num_faces, num_faces);
+
+    Ok(())
+}
+
+/// Configuration for the triangulation of faces
+#[derive(Clone, Copy, Debug)]
+pub struct TriangulationConfig {
+    /// The minimum angle of the triangles, in radians
+    ///
+    /// Points are inserted into the triangulation of a face, until none of
+    /// its triangles have an angle that is smaller than this. This eliminates
+    /// sliver triangles, which cause problems for rendering and for any
+    /// downstream processing of the mesh. Set this to zero to disable the
+    /// insertion of points.
+    ///
+    /// Angles greater than about 20 degrees might lead to the insertion of
+    /// ever more points, until `max_steiner_points` is reached. The same can
+    /// happen near boundaries that meet at a very small angle.
+    pub min_angle: Scalar,
+
+    /// The maximum number of points to insert into the triangulation of a face
+    pub max_steiner_points: usize,
+
+    /// The strategy used to triangulate faces on curved surfaces
+    pub strategy: TriangulationStrategy,
+}
+
+impl Default for TriangulationConfig {
+    fn default() -> Self {
+        Self {
+            min_angle: Scalar::PI / 9., // 20 degrees
+            max_steiner_points: 1000,
+            strategy: TriangulationStrategy::Delaunay,
+        }
+    }
+}
+
+/// The strategy used to triangulate faces on curved surfaces
+///
+/// Faces on planes are always triangulated using
+/// [`TriangulationStrategy::Delaunay`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TriangulationStrategy {
+    /// Sample the surface evenly, then insert points to improve quality
+    ///
+    /// Points are inserted until the minimum angle from [`TriangulationConfig`]
+    /// is reached. This results in well-shaped triangles, but the mesh has no
+    /// particular structure.
+    Delaunay,
+
+    /// Sample the surface on a structured grid of isolines
+    ///
+    /// The spacing of the grid adapts to the curvature of the surface. The
+    /// resulting mesh follows the lines of constant u and v of the surface,
+    /// which is nicer for rendering and for simulation. No points are inserted
+    /// to improve quality, as they would break up that structure.
+    Grid,
+}
+
+/// Triangulate a face approximation
+///
+/// Triangles that are not part of the face are discarded.
+fn triangulate_face(
+    approx: &FaceApprox,
+    surface: Surface,
+    debug_info: &mut DebugInfo,
+) -> Vec<[Local<Point<2>>; 3]> {
+    let points: Vec<_> = approx.points.iter().copied().collect();
+    let face_as_polygon = face_as_polygon(approx, surface);
+
+    let mut triangles = delaunay::triangulate(
+        points,
+        Some(&approx.exterior)
+            .into_iter()
+            .chain(&approx.interiors)
+            .map(|cycle| cycle.points.as_slice()),
+    );
+    triangles.retain(|triangle| {
+        face_as_polygon
+            .contains_triangle(triangle.map(|point| point.local()), debug_info)
+    });
+
+    triangles
+}
+
+fn face_as_polygon(approx: &FaceApprox, surface: Surface) -> Polygon {
+    Polygon::new(surface)
+        .with_exterior(approx.exterior.points.iter().map(|point| point.local()))
+        .with_interiors(
+            approx.interiors.iter().map(|interior| {
+                interior.points.iter().map(|point| point.local())
+            }),
+        )
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_interop::{debug::DebugInfo, mesh::Mesh};
-    use fj_math::{Point, Scalar};
+    use std::f64::consts::PI;
+
+    use fj_interop::{debug::DebugInfo, mesh::Mesh, progress::ProgressHandle};
+    use fj_math::{Circle, Point, Scalar, Vector};
 
     use crate::{
-        algorithms::Tolerance,
-        objects::{Face, Surface},
+        algorithms::{Tolerance, TriangulationConfig, TriangulationStrategy},
+        objects::{Curve, Face, Stores, Surface, SweptCurve},
     };
 
     #[test]
     fn simple() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
         let a = [0., 0.];
         let b = [2., 0.];
         let c = [2., 2.];
         let d = [0., 1.];
 
-        let face = Face::builder(Surface::xy_plane())
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([a, b, c, d])
             .build();
 
@@ -105,6 +290,8 @@ mod tests {
 
     #[test]
     fn simple_hole() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
         let a = [0., 0.];
         let b = [4., 0.];
         let c = [4., 4.];
@@ -115,7 +302,7 @@ mod tests {
         let g = [3., 3.];
         let h = [1., 2.];
 
-        let face = Face::builder(Surface::xy_plane())
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([a, b, c, d])
             .with_interior_polygon([e, f, g, h])
             .build();
@@ -146,6 +333,8 @@ mod tests {
     #[ignore]
     #[test]
     fn sharp_concave_shape() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
         //
         //                c
         //               /|
@@ -165,7 +354,7 @@ mod tests {
         let d = Point::from([0.1, 0.1]);
         let e = Point::from([0., 0.8]);
 
-        let face = Face::builder(Surface::xy_plane())
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([a, b, c, d, e])
             .build();
 
@@ -186,10 +375,133 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn curved_face_with_hole() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let surface = Surface::SweptCurve(SweptCurve {
+            curve: Curve::Circle(Circle {
+                center: Point::origin(),
+                a: Vector::unit_x(),
+                b: Vector::unit_y(),
+            }),
+            path: Vector::unit_z(),
+        });
+        let face = Face::builder(&stores, surface)
+            .with_exterior_polygon([[0., 0.], [PI, 0.], [PI, 1.], [0., 1.]])
+            .with_interior_polygon([[1., 0.3], [2., 0.3], [2., 0.7], [1., 0.7]])
+            .build();
+
+        for strategy in
+            [TriangulationStrategy::Delaunay, TriangulationStrategy::Grid]
+        {
+            let config = TriangulationConfig {
+                strategy,
+                ..TriangulationConfig::default()
+            };
+
+            let mesh = super::triangulate(
+                vec![face.clone()],
+                tolerance,
+                &config,
+                &mut DebugInfo::new(),
+                &mut ProgressHandle::new(),
+            )?;
+
+            let mut area = Scalar::ZERO;
+            for triangle in mesh.triangles() {
+                let [a, b, c] = triangle.points;
+                area += (b - a).cross(&(c - a)).magnitude() / 2.;
+
+                // The triangle must not deviate from the cylinder by more than
+                // the tolerance, and must not cover the hole.
+                let center = a + ((b - a) + (c - a)) / 3.;
+                let [x, y, z] = center.coords.components;
+                let distance_from_axis = Vector::from([x, y]).magnitude();
+                let angle = Scalar::atan2(y, x);
+
+                assert!(
+                    (distance_from_axis - Scalar::ONE).abs()
+                        <= tolerance.inner()
+                );
+                assert!(
+                    !(angle > Scalar::ONE
+                        && angle < Scalar::from(2.)
+                        && z > Scalar::from(0.3)
+                        && z < Scalar::from(0.7)),
+                    "Triangle {:?} covers hole ({strategy:?})",
+                    triangle.points,
+                );
+            }
+
+            // Half a cylinder of height 1, minus the hole.
+            let expected_area = Scalar::PI - Scalar::from(0.4);
+            assert!((area - expected_area).abs() < expected_area * 0.01);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn refinement() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let config = TriangulationConfig::default();
+
+        // Without refinement, this would be triangulated into two long and
+        // thin triangles.
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [10., 0.], [10., 1.], [0., 1.]])
+            .build();
+
+        let mesh = super::triangulate(
+            vec![face],
+            tolerance,
+            &config,
+            &mut DebugInfo::new(),
+            &mut ProgressHandle::new(),
+        )?;
+
+        let mut area = Scalar::ZERO;
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.points;
+            area += (b - a).cross(&(c - a)).magnitude() / 2.;
+
+            for [p, q, r] in [[a, b, c], [b, c, a], [c, a, b]] {
+                let u = q - p;
+                let v = r - p;
+                let angle = Scalar::atan2(u.cross(&v).magnitude(), u.dot(&v));
+
+                assert!(angle >= config.min_angle);
+            }
+        }
+
+        assert!((area - Scalar::from(10.)).abs() < Scalar::from(1e-9));
+
+        Ok(())
+    }
+
     fn triangulate(face: Face) -> anyhow::Result<Mesh<Point<3>>> {
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
 
+        // These tests check which triangles are part of the face. Inserting
+        // additional points would get in the way of that.
+        let config = TriangulationConfig {
+            min_angle: Scalar::ZERO,
+            ..TriangulationConfig::default()
+        };
+
         let mut debug_info = DebugInfo::new();
-        Ok(super::triangulate(vec![face], tolerance, &mut debug_info))
+        let mesh = super::triangulate(
+            vec![face],
+            tolerance,
+            &config,
+            &mut debug_info,
+            &mut ProgressHandle::new(),
+        )?;
+        Ok(mesh)
     }
 }
@@ -1,4 +1,4 @@
-use fj_interop::debug::{DebugInfo, TriangleEdgeCheck};
+use fj_interop::debug::{DebugCategory, DebugInfo};
 use fj_math::{Point, PolyChain, Segment};
 
 use crate::objects::Surface;
@@ -139,9 +139,7 @@ impl Polygon {
             origin: point.into(),
         };
 
-        let mut check = TriangleEdgeCheck::new(
-            self.surface.point_from_surface_coords(ray.origin),
-        );
+        let mut hits = Vec::new();
 
         let mut num_hits = 0;
 
@@ -203,16 +201,35 @@ impl Polygon {
                         Segment::from_points(edge.points().map(|point| {
                             self.surface.point_from_surface_coords(point)
                         }));
-                    check.hits.push(edge);
+                    hits.push(edge);
                 }
 
                 previous_hit = hit;
             }
         }
 
-        debug_info.triangle_edge_checks.push(check);
+        let contains = num_hits % 2 == 1;
+
+        let color = if contains {
+            [0, 255, 0, 255]
+        } else {
+            [255, 0, 0, 255]
+        };
+        debug_info.push_ray(
+            DebugCategory::TriangleEdgeCheck,
+            self.surface.point_from_surface_coords(ray.origin),
+            self.surface.vector_from_surface_coords([1., 0.]),
+            color,
+        );
+        for hit in hits {
+            debug_info.push_curve(
+                DebugCategory::TriangleEdgeCheck,
+                hit.points(),
+                [0, 0, 0, 255],
+            );
+        }
 
-        num_hits % 2 == 1
+        contains
     }
 }
 
@@ -0,0 +1,253 @@
+use std::collections::BTreeSet;
+
+use fj_interop::debug::DebugInfo;
+use fj_math::{Point, Scalar};
+
+use crate::{
+    algorithms::{CycleApprox, FaceApprox, Tolerance},
+    local::Local,
+    objects::Surface,
+};
+
+use super::{face_as_polygon, triangulate_face, TriangulationConfig};
+
+/// Insert points into a face approximation, to improve its triangulation
+///
+/// This is a variant of Ruppert's algorithm: Each triangle whose smallest
+/// angle is less than the configured minimum gets a new point at the center of
+/// its circumcircle. If that point would encroach upon a segment of the face's
+/// boundary (i.e. lie within the circle that has the segment as its diameter),
+/// the segment is split in half instead.
+///
+/// Angles and circumcircles are computed in model space, as surface
+/// coordinates can be distorted.
+pub fn refine(
+    approx: &mut FaceApprox,
+    surface: &Surface,
+    tolerance: Tolerance,
+    config: &TriangulationConfig,
+    debug_info: &mut DebugInfo,
+) {
+    if config.min_angle <= Scalar::ZERO {
+        return;
+    }
+
+    let mut num_points = 0;
+
+    while num_points < config.max_steiner_points {
+        let triangles = triangulate_face(approx, *surface, debug_info);
+
+        let segments: BTreeSet<_> = Some(&approx.exterior)
+            .into_iter()
+            .chain(&approx.interiors)
+            .flat_map(|cycle| {
+                cycle
+                    .points
+                    .windows(2)
+                    .map(|segment| normalize([segment[0], segment[1]]))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        // Segments that are encroached upon by an existing point need to be
+        // split first. Otherwise, the center of a circumcircle could end up
+        // outside of the face.
+        let mut split_segments = Vec::new();
+        for triangle in &triangles {
+            for i in 0..3 {
+                let segment = normalize([triangle[i], triangle[(i + 1) % 3]]);
+                let apex = triangle[(i + 2) % 3];
+
+                if segments.contains(&segment)
+                    && encroaches(apex.global(), segment)
+                    && can_split(segment, tolerance)
+                    && !split_segments.contains(&segment)
+                {
+                    split_segments.push(segment);
+                }
+            }
+        }
+        split_segments.truncate(config.max_steiner_points - num_points);
+
+        if !split_segments.is_empty() {
+            num_points += split_segments.len();
+
+            for segment in split_segments {
+                split_segment(approx, segment);
+            }
+
+            continue;
+        }
+
+        let mut bad_triangles: Vec<_> = triangles
+            .into_iter()
+            .filter_map(|triangle| {
+                let angle = min_angle(triangle.map(|point| point.global()));
+                (angle < config.min_angle).then_some((angle, triangle))
+            })
+            .collect();
+
+        // Handle the worst triangles first, in case we run out of points.
+        bad_triangles.sort_by_key(|&(angle, _)| angle);
+
+        let polygon = face_as_polygon(approx, *surface);
+
+        let mut new_points: Vec<(Local<Point<2>>, Scalar)> = Vec::new();
+
+        for (_, triangle) in bad_triangles {
+            if num_points + new_points.len() + split_segments.len()
+                >= config.max_steiner_points
+            {
+                break;
+            }
+
+            let (center, radius) =
+                match circumcircle(triangle.map(|point| point.global())) {
+                    Some(circumcircle) => circumcircle,
+                    None => continue,
+                };
+
+            let encroached = segments
+                .iter()
+                .find(|&&segment| encroaches(center, segment));
+            if let Some(&segment) = encroached {
+                if can_split(segment, tolerance)
+                    && !split_segments.contains(&segment)
+                {
+                    split_segments.push(segment);
+                }
+
+                continue;
+            }
+
+            // Points that are too close to each other would create new bad
+            // triangles. If the circumcircles of two triangles overlap that
+            // much, the other triangle is handled in the next round.
+            let is_too_close = new_points.iter().any(|&(point, r)| {
+                let min_distance = if r < radius { r } else { radius };
+                (point.global() - center).magnitude() < min_distance / 2.
+            });
+            if is_too_close {
+                continue;
+            }
+
+            let local = surface.point_to_surface_coords(center);
+            if !polygon.contains_point(local, debug_info) {
+                continue;
+            }
+
+            let global = surface.point_from_surface_coords(local);
+            new_points.push((Local::new(local, global), radius));
+        }
+
+        if new_points.is_empty() && split_segments.is_empty() {
+            break;
+        }
+        num_points += new_points.len() + split_segments.len();
+
+        approx
+            .points
+            .extend(new_points.into_iter().map(|(point, _)| point));
+
+        for segment in split_segments {
+            split_segment(approx, segment);
+        }
+    }
+}
+
+/// Order the points of a segment, so it can be compared regardless of
+/// direction
+fn normalize([a, b]: [Local<Point<2>>; 2]) -> [Local<Point<2>>; 2] {
+    if a <= b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+/// Determine whether a point encroaches upon a segment
+///
+/// This is the case, if the point lies within the circle that has the segment
+/// as its diameter.
+fn encroaches(point: Point<3>, [a, b]: [Local<Point<2>>; 2]) -> bool {
+    (a.global() - point).dot(&(b.global() - point)) < Scalar::ZERO
+}
+
+/// Determine whether a segment can be split
+///
+/// Splitting segments that are already shorter than the tolerance doesn't
+/// improve the approximation of the face, and would never terminate near
+/// boundaries that meet at a small angle.
+fn can_split([a, b]: [Local<Point<2>>; 2], tolerance: Tolerance) -> bool {
+    (b.global() - a.global()).magnitude() > tolerance.inner()
+}
+
+/// Split a segment of a face approximation's boundary in half
+///
+/// The new point is placed on the straight line between the segment's points
+/// in model space, not on the surface. That way, it lies on the approximation
+/// of the neighboring face's boundary too, and no gaps open up in the mesh.
+fn split_segment(approx: &mut FaceApprox, [a, b]: [Local<Point<2>>; 2]) {
+    let center = Local::new(
+        a.local() + (b.local() - a.local()) / 2.,
+        a.global() + (b.global() - a.global()) / 2.,
+    );
+
+    let split = |cycle: &mut CycleApprox| {
+        let i = cycle
+            .points
+            .windows(2)
+            .position(|segment| segment == [a, b] || segment == [b, a]);
+
+        if let Some(i) = i {
+            cycle.points.insert(i + 1, center);
+        }
+    };
+
+    split(&mut approx.exterior);
+    approx.interiors = approx
+        .interiors
+        .drain()
+        .map(|mut interior| {
+            split(&mut interior);
+            interior
+        })
+        .collect();
+
+    approx.points.insert(center);
+}
+
+/// Compute the smallest angle of a triangle
+fn min_angle([a, b, c]: [Point<3>; 3]) -> Scalar {
+    let angle = |p: Point<3>, q: Point<3>, r: Point<3>| {
+        let u = q - p;
+        let v = r - p;
+
+        Scalar::atan2(u.cross(&v).magnitude(), u.dot(&v))
+    };
+
+    [angle(a, b, c), angle(b, c, a), angle(c, a, b)]
+        .into_iter()
+        .min()
+        .unwrap_or(Scalar::ZERO)
+}
+
+/// Compute the center and radius of a triangle's circumcircle
+///
+/// Returns `None`, if the triangle is degenerate.
+fn circumcircle([a, b, c]: [Point<3>; 3]) -> Option<(Point<3>, Scalar)> {
+    let ab = b - a;
+    let ac = c - a;
+    let normal = ab.cross(&ac);
+
+    let normal_squared = normal.dot(&normal);
+    if normal_squared == Scalar::ZERO {
+        return None;
+    }
+
+    let offset = (normal.cross(&ab) * ac.dot(&ac)
+        + ac.cross(&normal) * ab.dot(&ab))
+        / (normal_squared * 2.);
+
+    Some((a + offset, offset.magnitude()))
+}
@@ -0,0 +1,69 @@
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_math::Scalar;
+
+use crate::objects::Shell;
+
+use super::{triangulate, Tolerance, TriangulationConfig};
+
+/// Compute the signed volume that a shell encloses
+///
+/// The volume is positive, if the faces of the shell point away from the
+/// volume, as they do for the outer shells of a solid. It is negative, if the
+/// faces point into the volume, as they do for inner shells, which bound the
+/// voids within a solid.
+///
+/// The shell is triangulated using `tolerance`, and the volume is computed from
+/// the resulting mesh. The result is only meaningful, if the shell is closed.
+pub fn volume(shell: &Shell, tolerance: Tolerance) -> Scalar {
+    let mesh = triangulate(
+        shell.faces().cloned().collect(),
+        tolerance,
+        &TriangulationConfig::default(),
+        &mut DebugInfo::new(),
+        &mut ProgressHandle::new(),
+    )
+    .expect("Triangulation can't be cancelled without cancellation token");
+
+    // The volume of a closed mesh is the sum of the signed volumes of the
+    // tetrahedra between each triangle and an arbitrary point. Picking a point
+    // on the mesh keeps the numbers small, wherever the shell is.
+    let mut triangles = mesh.triangles().peekable();
+    let origin = match triangles.peek() {
+        Some(triangle) => triangle.points[0],
+        None => return Scalar::ZERO,
+    };
+
+    triangles
+        .map(|triangle| {
+            let [a, b, c] = triangle.points.map(|point| point - origin);
+            a.dot(&b.cross(&c)) / 6.
+        })
+        .fold(Scalar::ZERO, |volume, tetrahedron| volume + tetrahedron)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{reverse_face, Tolerance},
+        objects::{Shell, Stores},
+    };
+
+    use super::volume;
+
+    #[test]
+    fn cube() -> anyhow::Result<()> {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let outer = Shell::cube_from_edge_length(&stores, 2.);
+        let volume_of_outer = volume(&outer, tolerance).into_f64();
+        assert!((volume_of_outer - 8.).abs() < 1e-9);
+
+        // Facing inwards, the cube bounds a void.
+        let inner = Shell::from_faces(outer.faces().map(reverse_face));
+        let volume_of_inner = volume(&inner, tolerance).into_f64();
+        assert!((volume_of_inner + 8.).abs() < 1e-9);
+
+        Ok(())
+    }
+}
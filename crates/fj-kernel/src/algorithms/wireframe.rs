@@ -0,0 +1,74 @@
+use fj_math::PolyChain;
+
+use crate::objects::Face;
+
+use super::{features, Tolerance};
+
+/// Approximate the edges of a shape as polygonal chains
+///
+/// This provides the actual edges of the boundary representation, which can
+/// be used to render feature edges, without inferring them from a triangle
+/// mesh.
+///
+/// Edges that are shared between faces are only returned once. Faces that are
+/// represented as triangles, and thus don't have any edges, are ignored.
+#[tracing::instrument(level = "debug", skip_all, fields(faces = faces.len()))]
+pub fn wireframe(faces: &[Face], tolerance: Tolerance) -> Vec<PolyChain<3>> {
+    features(faces, tolerance)
+        .edges
+        .into_iter()
+        .map(|edge| edge.approx)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Cycle, Edge, Face, Solid, Stores, Surface},
+    };
+
+    #[test]
+    fn cube() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+        let faces: Vec<_> = cube.into_faces().into_iter().collect();
+
+        let edges = super::wireframe(&faces, tolerance);
+
+        assert_eq!(edges.len(), 12);
+        for edge in edges {
+            assert_eq!(edge.segments().len(), 1);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn circle() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(Scalar::from(0.1))?;
+
+        let circle = Edge::circle_from_radius(&stores, Scalar::ONE);
+        let face = Face::new(
+            Surface::xy_plane(),
+            [Cycle::new(&stores, [circle])],
+            [],
+            [255, 0, 0, 255],
+        );
+        let faces = vec![face];
+
+        let edges = super::wireframe(&faces, tolerance);
+
+        assert_eq!(edges.len(), 1);
+        assert!(edges[0].segments().len() > 3);
+
+        Ok(())
+    }
+}
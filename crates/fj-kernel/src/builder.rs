@@ -2,21 +2,25 @@
 
 use fj_math::Point;
 
-use crate::objects::{Cycle, Face, Surface};
+use crate::objects::{Cycle, Face, Stores, Surface, Winding};
 
 /// API for building a [`Face`]
 #[must_use]
-pub struct FaceBuilder {
+pub struct FaceBuilder<'a> {
+    stores: &'a Stores,
     surface: Surface,
     exterior: Option<Vec<Point<2>>>,
     interiors: Vec<Vec<Point<2>>>,
     color: Option<[u8; 4]>,
 }
 
-impl FaceBuilder {
+impl<'a> FaceBuilder<'a> {
     /// Construct a new instance of `FaceBuilder`
-    pub fn new(surface: Surface) -> Self {
+    ///
+    /// The objects that make up the face are inserted into `stores`.
+    pub fn new(stores: &'a Stores, surface: Surface) -> Self {
         Self {
+            stores,
             surface,
             exterior: None,
             interiors: Vec::new(),
@@ -25,6 +29,9 @@ impl FaceBuilder {
     }
 
     /// Make the exterior or the face a polygon
+    ///
+    /// The order of the points doesn't matter. The polygon is oriented
+    /// counter-clockwise, as required for an exterior.
     pub fn with_exterior_polygon(
         self,
         points: impl IntoIterator<Item = impl Into<Point<2>>>,
@@ -38,6 +45,9 @@ impl FaceBuilder {
     }
 
     /// Add an interior polygon to the face
+    ///
+    /// The order of the points doesn't matter. The polygon is oriented
+    /// clockwise, as required for an interior.
     pub fn with_interior_polygon(
         self,
         points: impl IntoIterator<Item = impl Into<Point<2>>>,
@@ -62,13 +72,19 @@ impl FaceBuilder {
 
         let mut exteriors = Vec::new();
         if let Some(points) = self.exterior {
-            let cycle = Cycle::polygon_from_points(&self.surface, points);
+            let cycle = polygon(
+                self.stores,
+                &surface,
+                points,
+                Winding::CounterClockwise,
+            );
             exteriors.push(cycle);
         }
 
         let mut interiors = Vec::new();
         for points in self.interiors {
-            let cycle = Cycle::polygon_from_points(&self.surface, points);
+            let cycle =
+                polygon(self.stores, &surface, points, Winding::Clockwise);
             interiors.push(cycle);
         }
 
@@ -77,3 +93,19 @@ impl FaceBuilder {
         Face::new(surface, exteriors, interiors, color)
     }
 }
+
+/// Create a polygon with the given winding, regardless of the order of `points`
+fn polygon(
+    stores: &Stores,
+    surface: &Surface,
+    mut points: Vec<Point<2>>,
+    winding: Winding,
+) -> Cycle {
+    let cycle = Cycle::polygon_from_points(stores, surface, points.clone());
+    if cycle.winding() == winding {
+        return cycle;
+    }
+
+    points.reverse();
+    Cycle::polygon_from_points(stores, surface, points)
+}
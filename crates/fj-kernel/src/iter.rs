@@ -3,7 +3,8 @@
 use std::collections::VecDeque;
 
 use crate::objects::{
-    Curve, Cycle, Edge, Face, GlobalVertex, Sketch, Solid, Surface, Vertex,
+    Curve, Cycle, Edge, Face, GlobalVertex, Shell, Sketch, Solid, Surface,
+    Vertex,
 };
 
 /// Access iterators over all objects of a shape, or part of it
@@ -26,6 +27,9 @@ pub trait ObjectIters {
     /// Iterate over all global vertices
     fn global_vertex_iter(&self) -> Iter<GlobalVertex>;
 
+    /// Iterate over all shells
+    fn shell_iter(&self) -> Iter<Shell>;
+
     /// Iterate over all sketches
     fn sketch_iter(&self) -> Iter<Sketch>;
 
@@ -60,6 +64,10 @@ impl ObjectIters for Curve<3> {
         Iter::empty()
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         Iter::empty()
     }
@@ -122,6 +130,10 @@ impl ObjectIters for Cycle {
         iter
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         let mut iter = Iter::empty();
 
@@ -185,7 +197,7 @@ impl ObjectIters for Edge {
     }
 
     fn edge_iter(&self) -> Iter<Edge> {
-        Iter::from_object(*self)
+        Iter::from_object(self.clone())
     }
 
     fn face_iter(&self) -> Iter<Face> {
@@ -208,6 +220,10 @@ impl ObjectIters for Edge {
         iter
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         let mut iter = Iter::empty().with(self.curve().sketch_iter());
 
@@ -311,6 +327,10 @@ impl ObjectIters for Face {
         Iter::empty()
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         if let Face::Face(face) = self {
             let mut iter = Iter::empty().with(face.surface().sketch_iter());
@@ -389,6 +409,10 @@ impl ObjectIters for GlobalVertex {
         Iter::from_object(*self)
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         Iter::empty()
     }
@@ -457,6 +481,10 @@ impl ObjectIters for Sketch {
         iter
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         Iter::from_object(self.clone())
     }
@@ -492,6 +520,96 @@ impl ObjectIters for Sketch {
     }
 }
 
+impl ObjectIters for Shell {
+    fn curve_iter(&self) -> Iter<Curve<3>> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.curve_iter());
+        }
+
+        iter
+    }
+
+    fn cycle_iter(&self) -> Iter<Cycle> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.cycle_iter());
+        }
+
+        iter
+    }
+
+    fn edge_iter(&self) -> Iter<Edge> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.edge_iter());
+        }
+
+        iter
+    }
+
+    fn face_iter(&self) -> Iter<Face> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.face_iter());
+        }
+
+        iter
+    }
+
+    fn global_vertex_iter(&self) -> Iter<GlobalVertex> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.global_vertex_iter());
+        }
+
+        iter
+    }
+
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::from_object(self.clone())
+    }
+
+    fn sketch_iter(&self) -> Iter<Sketch> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.sketch_iter());
+        }
+
+        iter
+    }
+
+    fn solid_iter(&self) -> Iter<Solid> {
+        Iter::empty()
+    }
+
+    fn surface_iter(&self) -> Iter<Surface> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.surface_iter());
+        }
+
+        iter
+    }
+
+    fn vertex_iter(&self) -> Iter<Vertex> {
+        let mut iter = Iter::empty();
+
+        for edge in self.faces() {
+            iter = iter.with(edge.vertex_iter());
+        }
+
+        iter
+    }
+}
+
 impl ObjectIters for Solid {
     fn curve_iter(&self) -> Iter<Curve<3>> {
         let mut iter = Iter::empty();
@@ -543,6 +661,16 @@ impl ObjectIters for Solid {
         iter
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        let mut iter = Iter::empty();
+
+        for shell in self.shells() {
+            iter = iter.with(shell.shell_iter());
+        }
+
+        iter
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         let mut iter = Iter::empty();
 
@@ -599,6 +727,10 @@ impl ObjectIters for Surface {
         Iter::empty()
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        Iter::empty()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         Iter::empty()
     }
@@ -637,6 +769,10 @@ impl ObjectIters for Vertex {
         self.global().global_vertex_iter()
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        self.global().shell_iter()
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         self.global().sketch_iter()
     }
@@ -650,7 +786,7 @@ impl ObjectIters for Vertex {
     }
 
     fn vertex_iter(&self) -> Iter<Vertex> {
-        Iter::from_object(*self)
+        Iter::from_object(self.clone())
     }
 }
 
@@ -714,6 +850,16 @@ where
         iter
     }
 
+    fn shell_iter(&self) -> Iter<Shell> {
+        let mut iter = Iter::empty();
+
+        for object in self.into_iter() {
+            iter = iter.with(object.shell_iter());
+        }
+
+        iter
+    }
+
     fn sketch_iter(&self) -> Iter<Sketch> {
         let mut iter = Iter::empty();
 
@@ -796,7 +942,8 @@ impl<T> Iterator for Iter<T> {
 #[cfg(test)]
 mod tests {
     use crate::objects::{
-        Curve, Cycle, Edge, Face, GlobalVertex, Sketch, Solid, Surface, Vertex,
+        Curve, Cycle, Edge, Face, GlobalVertex, Shell, Sketch, Solid, Stores,
+        Surface, Vertex,
     };
 
     use super::ObjectIters as _;
@@ -810,6 +957,7 @@ mod tests {
         assert_eq!(0, object.edge_iter().count());
         assert_eq!(0, object.face_iter().count());
         assert_eq!(0, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(0, object.surface_iter().count());
@@ -818,7 +966,10 @@ mod tests {
 
     #[test]
     fn cycle() {
+        let stores = Stores::new();
+
         let object = Cycle::polygon_from_points(
+            &stores,
             &Surface::xy_plane(),
             [[0., 0.], [1., 0.], [0., 1.]],
         );
@@ -828,6 +979,7 @@ mod tests {
         assert_eq!(3, object.edge_iter().count());
         assert_eq!(0, object.face_iter().count());
         assert_eq!(3, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(0, object.surface_iter().count());
@@ -836,7 +988,10 @@ mod tests {
 
     #[test]
     fn edge() {
+        let stores = Stores::new();
+
         let object = Edge::line_segment_from_points(
+            &stores,
             &Surface::xy_plane(),
             [[0., 0.], [1., 0.]],
         );
@@ -846,6 +1001,7 @@ mod tests {
         assert_eq!(1, object.edge_iter().count());
         assert_eq!(0, object.face_iter().count());
         assert_eq!(2, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(0, object.surface_iter().count());
@@ -854,7 +1010,9 @@ mod tests {
 
     #[test]
     fn face() {
-        let object = Face::builder(Surface::xy_plane())
+        let stores = Stores::new();
+
+        let object = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
             .build();
 
@@ -863,6 +1021,7 @@ mod tests {
         assert_eq!(3, object.edge_iter().count());
         assert_eq!(1, object.face_iter().count());
         assert_eq!(3, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(1, object.surface_iter().count());
@@ -878,15 +1037,36 @@ mod tests {
         assert_eq!(0, object.edge_iter().count());
         assert_eq!(0, object.face_iter().count());
         assert_eq!(1, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(0, object.surface_iter().count());
         assert_eq!(0, object.vertex_iter().count());
     }
 
+    #[test]
+    fn shell() {
+        let stores = Stores::new();
+
+        let object = Shell::cube_from_edge_length(&stores, 1.);
+
+        assert_eq!(24, object.curve_iter().count());
+        assert_eq!(6, object.cycle_iter().count());
+        assert_eq!(24, object.edge_iter().count());
+        assert_eq!(6, object.face_iter().count());
+        assert_eq!(8, object.global_vertex_iter().count());
+        assert_eq!(1, object.shell_iter().count());
+        assert_eq!(0, object.sketch_iter().count());
+        assert_eq!(0, object.solid_iter().count());
+        assert_eq!(6, object.surface_iter().count());
+        assert_eq!(16, object.vertex_iter().count());
+    }
+
     #[test]
     fn sketch() {
-        let face = Face::builder(Surface::xy_plane())
+        let stores = Stores::new();
+
+        let face = Face::builder(&stores, Surface::xy_plane())
             .with_exterior_polygon([[0., 0.], [1., 0.], [0., 1.]])
             .build();
         let object = Sketch::from_faces([face]);
@@ -896,6 +1076,7 @@ mod tests {
         assert_eq!(3, object.edge_iter().count());
         assert_eq!(1, object.face_iter().count());
         assert_eq!(3, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(1, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(1, object.surface_iter().count());
@@ -904,13 +1085,16 @@ mod tests {
 
     #[test]
     fn solid() {
-        let object = Solid::cube_from_edge_length(1.);
+        let stores = Stores::new();
 
-        assert_eq!(18, object.curve_iter().count());
+        let object = Solid::cube_from_edge_length(&stores, 1.);
+
+        assert_eq!(24, object.curve_iter().count());
         assert_eq!(6, object.cycle_iter().count());
-        assert_eq!(20, object.edge_iter().count());
+        assert_eq!(24, object.edge_iter().count());
         assert_eq!(6, object.face_iter().count());
         assert_eq!(8, object.global_vertex_iter().count());
+        assert_eq!(1, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(1, object.solid_iter().count());
         assert_eq!(6, object.surface_iter().count());
@@ -926,6 +1110,7 @@ mod tests {
         assert_eq!(0, object.edge_iter().count());
         assert_eq!(0, object.face_iter().count());
         assert_eq!(0, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(1, object.surface_iter().count());
@@ -934,7 +1119,11 @@ mod tests {
 
     #[test]
     fn vertex() {
-        let global_vertex = GlobalVertex::from_position([0., 0., 0.]);
+        let stores = Stores::new();
+
+        let global_vertex = stores
+            .global_vertices
+            .insert(GlobalVertex::from_position([0., 0., 0.]));
         let object = Vertex::new([0.], global_vertex);
 
         assert_eq!(0, object.curve_iter().count());
@@ -942,6 +1131,7 @@ mod tests {
         assert_eq!(0, object.edge_iter().count());
         assert_eq!(0, object.face_iter().count());
         assert_eq!(1, object.global_vertex_iter().count());
+        assert_eq!(0, object.shell_iter().count());
         assert_eq!(0, object.sketch_iter().count());
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(0, object.surface_iter().count());
@@ -91,5 +91,9 @@ pub mod algorithms;
 pub mod builder;
 pub mod iter;
 pub mod local;
+pub mod naming;
 pub mod objects;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+pub mod topology;
 pub mod validation;
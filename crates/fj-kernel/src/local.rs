@@ -2,7 +2,7 @@
 
 use fj_math::Point;
 
-use crate::objects::Curve;
+use crate::objects::{Curve, Handle};
 
 /// A wrapper around the local and global forms of a type
 ///
@@ -13,7 +13,7 @@ use crate::objects::Curve;
 /// The purpose of storing both forms is to be able to losslessly convert
 /// between them. Even if this conversion can be computed on the fly, it might
 /// be lossy due to floating point accuracy issues.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Local<T: LocalForm> {
     local: T,
     global: T::GlobalForm,
@@ -38,20 +38,22 @@ impl<T: LocalForm> Local<T> {
 
     /// Access the global form of the value
     pub fn global(&self) -> T::GlobalForm {
-        self.global
+        self.global.clone()
     }
 }
 
+impl<T: LocalForm> Copy for Local<T> where T::GlobalForm: Copy {}
+
 /// Implemented for types that are the local form of a global type
 ///
 /// See [`Local`] for more information.
 pub trait LocalForm: Copy {
     /// The global form of the implementing type
-    type GlobalForm: Copy;
+    type GlobalForm: Clone;
 }
 
 impl LocalForm for Curve<2> {
-    type GlobalForm = Curve<3>;
+    type GlobalForm = Handle<Curve<3>>;
 }
 
 impl LocalForm for Point<1> {
@@ -0,0 +1,158 @@
+//! Persistent names for faces, edges, and vertices
+//!
+//! Operations that create new topology can assign a [`Name`] to each face,
+//! edge, and vertex they create. Names are derived from the operation that
+//! generated an object and the position of its inputs, not from the object's
+//! geometry. A name like `sweep/face0/top` refers to the top face of a sweep,
+//! regardless of where that face ends up after the model's parameters change.
+//!
+//! This allows downstream operations to refer to specific parts of a shape in
+//! a way that survives regeneration of the model.
+
+use std::{collections::BTreeMap, fmt};
+
+use fj_math::Transform;
+
+use crate::{
+    algorithms::TransformObject,
+    objects::{Edge, Face, GlobalVertex},
+};
+
+/// The name of a face, edge, or vertex
+///
+/// Names are hierarchical. Their segments are separated by `/`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Name(String);
+
+impl Name {
+    /// Create a new name, made up of a single segment
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Create a name that is nested within this one
+    pub fn child(&self, segment: impl fmt::Display) -> Self {
+        Self(format!("{}/{}", self.0, segment))
+    }
+
+    /// Access the name as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The names assigned to the faces, edges, and vertices of a shape
+///
+/// The same object can have multiple names. An edge that bounds two faces, for
+/// example, is named once as part of each face.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Names {
+    faces: BTreeMap<Name, Face>,
+    edges: BTreeMap<Name, Edge>,
+    vertices: BTreeMap<Name, GlobalVertex>,
+}
+
+impl Names {
+    /// Construct an empty instance of `Names`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name a face, its edges, and its vertices
+    ///
+    /// The edges and vertices are named after their position in the face.
+    /// Edge `k` of cycle `j` of the face is named `{name}/cycle{j}/edge{k}`,
+    /// and its start vertex is named `{name}/cycle{j}/vertex{k}`.
+    pub fn insert_face(&mut self, name: Name, face: &Face) {
+        if let Face::Face(brep) = face {
+            for (j, cycle) in brep.all_cycles().enumerate() {
+                let cycle_name = name.child(format_args!("cycle{j}"));
+
+                for (k, edge) in cycle.edges().enumerate() {
+                    if let Some([vertex, _]) = edge.vertices() {
+                        self.vertices.insert(
+                            cycle_name.child(format_args!("vertex{k}")),
+                            *vertex.global().get(),
+                        );
+                    }
+
+                    self.edges.insert(
+                        cycle_name.child(format_args!("edge{k}")),
+                        edge,
+                    );
+                }
+            }
+        }
+
+        self.faces.insert(name, face.clone());
+    }
+
+    /// Access the face with the given name
+    pub fn face(&self, name: &Name) -> Option<&Face> {
+        self.faces.get(name)
+    }
+
+    /// Access the edge with the given name
+    pub fn edge(&self, name: &Name) -> Option<&Edge> {
+        self.edges.get(name)
+    }
+
+    /// Access the vertex with the given name
+    pub fn vertex(&self, name: &Name) -> Option<&GlobalVertex> {
+        self.vertices.get(name)
+    }
+
+    /// Find the name of a face
+    ///
+    /// If the face has multiple names, the first one in alphabetical order is
+    /// returned.
+    pub fn name_of_face(&self, face: &Face) -> Option<&Name> {
+        self.faces
+            .iter()
+            .find(|(_, f)| *f == face)
+            .map(|(name, _)| name)
+    }
+
+    /// Iterate over all named faces
+    pub fn faces(&self) -> impl Iterator<Item = (&Name, &Face)> {
+        self.faces.iter()
+    }
+
+    /// Iterate over all named edges
+    pub fn edges(&self) -> impl Iterator<Item = (&Name, &Edge)> {
+        self.edges.iter()
+    }
+
+    /// Iterate over all named vertices
+    pub fn vertices(&self) -> impl Iterator<Item = (&Name, &GlobalVertex)> {
+        self.vertices.iter()
+    }
+}
+
+impl TransformObject for Names {
+    fn transform(self, transform: &Transform) -> Self {
+        Self {
+            faces: self
+                .faces
+                .into_iter()
+                .map(|(name, face)| (name, face.transform(transform)))
+                .collect(),
+            edges: self
+                .edges
+                .into_iter()
+                .map(|(name, edge)| (name, edge.transform(transform)))
+                .collect(),
+            vertices: self
+                .vertices
+                .into_iter()
+                .map(|(name, vertex)| (name, vertex.transform(transform)))
+                .collect(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use fj_math::{Circle, Line, Point, Vector};
+use fj_math::{Circle, Line, Point, Scalar, Vector};
 
 /// A one-dimensional shape
 ///
@@ -47,6 +47,35 @@ impl<const D: usize> Curve<D> {
         }
     }
 
+    /// Convert a point in model coordinates into curve coordinates
+    ///
+    /// Projects the point onto the curve. The coordinates of a point on a
+    /// circle are between `0.` (inclusive) and `PI * 2.` (exclusive).
+    pub fn point_to_curve_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        let point = point.into();
+
+        match self {
+            Self::Circle(curve) => {
+                let v = point - curve.center;
+                let cos = v.dot(&curve.a) / curve.a.dot(&curve.a);
+                let sin = v.dot(&curve.b) / curve.b.dot(&curve.b);
+
+                let t = Scalar::atan2(sin, cos);
+                let t = if t < Scalar::ZERO {
+                    t + Scalar::PI * 2.
+                } else {
+                    t
+                };
+
+                Point::from([t])
+            }
+            Self::Line(curve) => curve.point_to_line_coords(point),
+        }
+    }
+
     /// Convert a point on the curve into model coordinates
     pub fn point_from_curve_coords(
         &self,
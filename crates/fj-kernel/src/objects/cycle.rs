@@ -1,21 +1,31 @@
-use fj_math::Point;
+use fj_math::{Circle, Point, Scalar, Vector};
 
-use super::{Edge, Surface};
+use super::{Curve, Edge, Handle, Stores, Surface};
 
 /// A cycle of connected edges
 ///
 /// The end of each edge in the cycle must connect to the beginning of the next
 /// edge. The end of the last edge must connect to the beginning of the first
 /// one.
+///
+/// The cycles of a face have a winding, which relates them to the face's
+/// orientation: Exterior cycles wind counter-clockwise, and interior cycles
+/// clockwise, when viewed from the front of the face (the side that the normal
+/// of its surface points to). See [`Cycle::winding`].
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Cycle {
     /// The edges that make up the cycle
-    pub edges: Vec<Edge>,
+    ///
+    /// The edges are kept in [`Stores::edges`].
+    pub edges: Vec<Handle<Edge>>,
 }
 
 impl Cycle {
     /// Create a polygon from a list of points
+    ///
+    /// Adjacent edges share the global vertex between them.
     pub fn polygon_from_points(
+        stores: &Stores,
         surface: &Surface,
         points: impl IntoIterator<Item = impl Into<Point<2>>>,
     ) -> Cycle {
@@ -34,14 +44,102 @@ impl Cycle {
             // Can be cleaned up, once `array_windows` is stable.
             let points = [points[0], points[1]];
 
-            edges.push(Edge::line_segment_from_points(surface, points));
+            edges.push(Edge::line_segment_from_points(stores, surface, points));
         }
 
-        Cycle { edges }
+        Cycle::new(stores, edges)
+    }
+
+    /// Create a cycle from the given edges
+    ///
+    /// The edges are inserted into `stores`.
+    pub fn new(stores: &Stores, edges: impl IntoIterator<Item = Edge>) -> Self {
+        let edges = edges
+            .into_iter()
+            .map(|edge| stores.edges.insert(edge))
+            .collect();
+
+        Self { edges }
     }
 
     /// Access this cycle's edges
     pub fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
-        self.edges.iter().cloned()
+        self.edges.iter().map(|edge| edge.get().clone())
+    }
+
+    /// Create a new instance that runs in the opposite direction
+    ///
+    /// Reverses the order of the edges, as well as each edge itself. This
+    /// reverses the winding of the cycle. The reversed edges are inserted into
+    /// the store of the original ones.
+    #[must_use]
+    pub fn reverse(&self) -> Self {
+        let edges = self
+            .edges
+            .iter()
+            .rev()
+            .map(|edge| edge.store().insert(edge.get().clone().reverse()));
+        Self {
+            edges: edges.collect(),
+        }
+    }
+
+    /// Compute the winding of the cycle, in surface coordinates
+    ///
+    /// The winding is determined from the signed area that the cycle encloses,
+    /// which is computed exactly from the local forms of its edges. A cycle
+    /// that doesn't enclose any area is considered to wind counter-clockwise.
+    pub fn winding(&self) -> Winding {
+        let doubled_area = self
+            .edges
+            .iter()
+            .map(|edge| doubled_area_of_edge(edge))
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+
+        if doubled_area < Scalar::ZERO {
+            Winding::Clockwise
+        } else {
+            Winding::CounterClockwise
+        }
+    }
+}
+
+/// The direction in which a [`Cycle`] runs around the area it encloses
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Winding {
+    /// The cycle runs counter-clockwise
+    CounterClockwise,
+
+    /// The cycle runs clockwise
+    Clockwise,
+}
+
+/// Compute twice the signed area that an edge contributes to its cycle
+///
+/// This is the integral of `p × p'` along the edge, where `p` is a point on
+/// the edge, in surface coordinates.
+fn doubled_area_of_edge(edge: &Edge) -> Scalar {
+    let cross = |a: Vector<2>, b: Vector<2>| a.u * b.v - a.v * b.u;
+
+    if let Some(arc) = edge.local_arc() {
+        let Circle { center, a, b } = arc.circle;
+        let [t0, t1] = arc.range();
+        let [(sin0, cos0), (sin1, cos1)] = [t0, t1].map(Scalar::sin_cos);
+        let center = center.coords;
+
+        return cross(center, a) * (cos1 - cos0)
+            + cross(center, b) * (sin1 - sin0)
+            + cross(a, b) * (t1 - t0);
+    }
+
+    match (edge.curve.local(), edge.vertices()) {
+        (Curve::Line(line), Some(vertices)) => {
+            let [a, b] = vertices
+                .map(|vertex| line.point_from_line_coords(vertex.position()));
+            cross(a.coords, b.coords)
+        }
+        // Edges without vertices are only possible on circles, which have
+        // been handled above.
+        _ => Scalar::ZERO,
     }
 }
@@ -1,13 +1,13 @@
 use std::fmt;
 
-use fj_math::{Circle, Line, Point, Scalar, Vector};
+use fj_math::{Arc, Circle, Line, Point, Scalar, Vector};
 
 use crate::local::Local;
 
-use super::{Curve, GlobalVertex, Surface, Vertex};
+use super::{Curve, GlobalVertex, Stores, Surface, Vertex};
 
 /// An edge of a shape
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Edge {
     /// Access the curve that defines the edge's geometry
     ///
@@ -25,17 +25,17 @@ pub struct Edge {
 
 impl Edge {
     /// Create a circle from the given radius
-    pub fn circle_from_radius(radius: Scalar) -> Self {
+    pub fn circle_from_radius(stores: &Stores, radius: Scalar) -> Self {
         let curve_local = Curve::Circle(Circle {
             center: Point::origin(),
             a: Vector::from([radius, Scalar::ZERO]),
             b: Vector::from([Scalar::ZERO, radius]),
         });
-        let curve_canonical = Curve::Circle(Circle {
+        let curve_canonical = stores.curves.insert(Curve::Circle(Circle {
             center: Point::origin(),
             a: Vector::from([radius, Scalar::ZERO, Scalar::ZERO]),
             b: Vector::from([Scalar::ZERO, radius, Scalar::ZERO]),
-        });
+        }));
 
         Edge {
             curve: Local::new(curve_local, curve_canonical),
@@ -44,7 +44,11 @@ impl Edge {
     }
 
     /// Create a line segment from two points
+    ///
+    /// The global vertices are inserted into `stores`, which means they are
+    /// shared with any other edge that starts or ends at the same points.
     pub fn line_segment_from_points(
+        stores: &Stores,
         surface: &Surface,
         points: [impl Into<Point<2>>; 2],
     ) -> Self {
@@ -52,14 +56,16 @@ impl Edge {
 
         let global_vertices = points.map(|position| {
             let position = surface.point_from_surface_coords(position);
-            GlobalVertex::from_position(position)
+            stores
+                .global_vertices
+                .insert(GlobalVertex::from_position(position))
         });
 
         let curve_local = Curve::Line(Line::from_points(points));
         let curve_canonical = {
-            let points =
-                global_vertices.map(|global_vertex| global_vertex.position());
-            Curve::Line(Line::from_points(points))
+            let [a, b] = &global_vertices;
+            let points = [a, b].map(|global_vertex| global_vertex.position());
+            stores.curves.insert(Curve::Line(Line::from_points(points)))
         };
 
         let vertices = {
@@ -78,12 +84,62 @@ impl Edge {
 
     /// Access this edge's curve
     pub fn curve(&self) -> Curve<3> {
-        self.curve.global()
+        *self.curve.global()
     }
 
     /// Access this edge's vertices
     pub fn vertices(&self) -> Option<[Vertex; 2]> {
-        self.vertices.0
+        self.vertices.0.clone()
+    }
+
+    /// Access the arc that this edge covers, if its curve is a circle
+    ///
+    /// An edge without vertices covers the whole circle. Otherwise, the arc
+    /// runs from the first vertex to the second one, towards decreasing circle
+    /// coordinates, if the second vertex' position is smaller, and towards
+    /// increasing ones otherwise.
+    pub fn arc(&self) -> Option<Arc<3>> {
+        match self.curve() {
+            Curve::Circle(circle) => Some(self.arc_on(circle)),
+            Curve::Line(_) => None,
+        }
+    }
+
+    /// Access the arc that this edge covers, in surface coordinates
+    ///
+    /// See [`Edge::arc`].
+    pub fn local_arc(&self) -> Option<Arc<2>> {
+        match self.curve.local() {
+            Curve::Circle(circle) => Some(self.arc_on(circle)),
+            Curve::Line(_) => None,
+        }
+    }
+
+    fn arc_on<const D: usize>(&self, circle: Circle<D>) -> Arc<D> {
+        match self.vertices() {
+            Some(vertices) => Arc::from_circle_coords(
+                circle,
+                vertices.map(|vertex| vertex.position().t),
+            ),
+            None => Arc::full(circle),
+        }
+    }
+
+    /// Create a new instance that runs in the opposite direction
+    ///
+    /// Both the local and the global form of the curve are reversed, and so
+    /// are the vertices. The reversed global curve is inserted into the store
+    /// of the original one.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        let global = self.curve.global();
+        let curve = Local::new(
+            self.curve.local().reverse(),
+            global.store().insert(global.reverse()),
+        );
+        let vertices = self.vertices.reverse();
+
+        Self { curve, vertices }
     }
 }
 
@@ -104,7 +160,7 @@ impl fmt::Display for Edge {
 }
 
 /// The vertices that bound an edge
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct VerticesOfEdge(Option<[Vertex; 2]>);
 
 impl VerticesOfEdge {
@@ -127,9 +183,9 @@ impl VerticesOfEdge {
     ///
     /// The order of vertices is ignored.
     pub fn are_same(&self, other: &Self) -> bool {
-        if let Some([a, b]) = self.0 {
-            if let Some(other) = other.0 {
-                return [a, b] == other || [b, a] == other;
+        if let Some([a, b]) = &self.0 {
+            if let Some([c, d]) = &other.0 {
+                return (a == c && b == d) || (a == d && b == c);
             }
         }
 
@@ -156,8 +212,8 @@ impl VerticesOfEdge {
     pub fn reverse(self) -> Self {
         Self(self.0.map(|[a, b]| {
             [
-                Vertex::new(-b.position(), b.global()),
-                Vertex::new(-a.position(), a.global()),
+                Vertex::new(-b.position(), b.global().clone()),
+                Vertex::new(-a.position(), a.global().clone()),
             ]
         }))
     }
@@ -1,9 +1,9 @@
 use fj_interop::mesh::Color;
 use fj_math::Triangle;
 
-use crate::builder::FaceBuilder;
+use crate::{algorithms::Tolerance, builder::FaceBuilder};
 
-use super::{Cycle, Surface};
+use super::{Cycle, Stores, Surface};
 
 /// A face of a shape
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -39,11 +39,31 @@ impl Face {
             exteriors,
             interiors,
             color,
+            tolerance: None,
         })
     }
+
+    /// Require the face to be approximated with a tighter tolerance
+    ///
+    /// Has no effect, if the face already has a tighter tolerance, or if it is
+    /// represented by triangles.
+    #[must_use]
+    pub fn with_tolerance(self, tolerance: Tolerance) -> Self {
+        match self {
+            Self::Face(mut face) => {
+                face.tolerance = Some(match face.tolerance {
+                    Some(existing) => existing.min(tolerance),
+                    None => tolerance,
+                });
+                Self::Face(face)
+            }
+            Self::Triangles(triangles) => Self::Triangles(triangles),
+        }
+    }
+
     /// Build a face using the [`FaceBuilder`] API
-    pub fn builder(surface: Surface) -> FaceBuilder {
-        FaceBuilder::new(surface)
+    pub fn builder(stores: &Stores, surface: Surface) -> FaceBuilder<'_> {
+        FaceBuilder::new(stores, surface)
     }
 
     /// Access the boundary representation of the face
@@ -85,6 +105,16 @@ impl Face {
     pub fn color(&self) -> [u8; 4] {
         self.brep().color
     }
+
+    /// Access the tolerance that the face requires, if it has one
+    ///
+    /// See [`FaceBRep::tolerance`].
+    pub fn tolerance(&self) -> Option<Tolerance> {
+        match self {
+            Self::Face(face) => face.tolerance,
+            Self::Triangles(_) => None,
+        }
+    }
 }
 
 /// The boundary representation of a face
@@ -120,6 +150,12 @@ pub struct FaceBRep {
 
     /// The color of the face
     pub color: [u8; 4],
+
+    /// The tolerance that the face must be approximated with
+    ///
+    /// If this is `None`, or if it's looser than the tolerance that the face
+    /// is approximated with anyway, it has no effect.
+    pub tolerance: Option<Tolerance>,
 }
 
 impl FaceBRep {
@@ -3,25 +3,33 @@
 //! Objects, in Fornjot parlance, are the elements that make up shapes. An
 //! object can be simple and just contain data (like, for example, [`Vertex`]),
 //! or they can be quite complex and refer to other objects.
+//!
+//! Objects that are shared between other objects, like the global vertices
+//! that bound multiple edges, are kept in [`Stores`] and referred to using
+//! [`Handle`]s.
 
 mod curve;
 mod cycle;
 mod edge;
 mod face;
 mod global_vertex;
+mod shell;
 mod sketch;
 mod solid;
+mod store;
 mod surface;
 mod vertex;
 
 pub use self::{
     curve::Curve,
-    cycle::Cycle,
+    cycle::{Cycle, Winding},
     edge::{Edge, VerticesOfEdge},
     face::{CyclesInFace, Face, FaceBRep},
     global_vertex::GlobalVertex,
+    shell::Shell,
     sketch::Sketch,
     solid::Solid,
+    store::{Handle, ObjectId, Snapshot, Store, Stores, StoresSnapshot},
     surface::{Surface, SweptCurve},
     vertex::Vertex,
 };
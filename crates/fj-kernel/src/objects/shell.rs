@@ -0,0 +1,77 @@
+use fj_math::Scalar;
+
+use crate::algorithms::TransformObject;
+
+use super::{Face, Stores, Surface};
+
+/// A 3-dimensional closed shell
+///
+/// A shell is a set of faces that are connected through shared edges, and that
+/// enclose a volume together. The faces of an outer shell point away from the
+/// volume they enclose. An inner shell bounds a void within a [`Solid`], and
+/// its faces point into that void, which is away from the material.
+///
+/// # Implementation Note
+///
+/// The faces that make up the shell must be connected and form a closed shape.
+/// This is not validated.
+///
+/// [`Solid`]: super::Solid
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Shell {
+    // Kept sorted and deduplicated, like the faces of a `Sketch`.
+    faces: Vec<Face>,
+}
+
+impl Shell {
+    /// Construct a shell from faces
+    pub fn from_faces(faces: impl IntoIterator<Item = Face>) -> Self {
+        let mut faces: Vec<_> = faces.into_iter().collect();
+        faces.sort();
+        faces.dedup();
+
+        Self { faces }
+    }
+
+    /// Create a cube from the length of its edges
+    pub fn cube_from_edge_length(
+        stores: &Stores,
+        edge_length: impl Into<Scalar>,
+    ) -> Self {
+        // Let's define a short-hand for half the edge length. We're going to
+        // need it a lot.
+        let h = edge_length.into() / 2.;
+
+        let points = [[-h, -h], [h, -h], [h, h], [-h, h]];
+
+        // The planes face outwards. The xz-plane's normal points towards
+        // negative y, so it is reversed for the back face, not the front.
+        const Z: Scalar = Scalar::ZERO;
+        let planes = [
+            Surface::xy_plane().reverse().translate([Z, Z, -h]), // bottom
+            Surface::xy_plane().translate([Z, Z, h]),            // top
+            Surface::xz_plane().translate([Z, -h, Z]),           // front
+            Surface::xz_plane().reverse().translate([Z, h, Z]),  // back
+            Surface::yz_plane().reverse().translate([-h, Z, Z]), // left
+            Surface::yz_plane().translate([h, Z, Z]),            // right
+        ];
+
+        let faces = planes.map(|plane| {
+            Face::builder(stores, plane)
+                .with_exterior_polygon(points)
+                .build()
+        });
+
+        Shell::from_faces(faces)
+    }
+
+    /// Access the shell's faces
+    pub fn faces(&self) -> impl Iterator<Item = &Face> {
+        self.faces.iter()
+    }
+
+    /// Convert the shell into a list of faces
+    pub fn into_faces(self) -> Vec<Face> {
+        self.faces
+    }
+}
@@ -1,5 +1,3 @@
-use std::collections::BTreeSet;
-
 use super::Face;
 
 /// A 2-dimensional shape
@@ -10,13 +8,18 @@ use super::Face;
 /// currently validated.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Sketch {
-    faces: BTreeSet<Face>,
+    // Sorted and without duplicates, so sketches with the same faces are equal,
+    // no matter in which order those were provided.
+    faces: Vec<Face>,
 }
 
 impl Sketch {
     /// Construct a sketch from faces
     pub fn from_faces(faces: impl IntoIterator<Item = Face>) -> Self {
-        let faces = faces.into_iter().collect();
+        let mut faces: Vec<_> = faces.into_iter().collect();
+        faces.sort();
+        faces.dedup();
+
         Self { faces }
     }
 
@@ -26,7 +29,7 @@ impl Sketch {
     }
 
     /// Convert the sketch into a list of faces
-    pub fn into_faces(self) -> BTreeSet<Face> {
+    pub fn into_faces(self) -> Vec<Face> {
         self.faces
     }
 }
@@ -1,61 +1,95 @@
-use std::collections::BTreeSet;
-
 use fj_math::Scalar;
 
-use crate::algorithms::TransformObject;
+use crate::topology::Topology;
 
-use super::{Face, Surface};
+use super::{Face, Shell, Stores};
 
 /// A 3-dimensional shape
 ///
+/// A solid is bounded by one or more [`Shell`]s. Each outer shell encloses a
+/// separate piece of material, while each inner shell encloses a void within
+/// the material. Outer and inner shells are told apart by the sign of their
+/// [`volume`], which depends on the direction their faces point in.
+///
 /// # Implementation Note
 ///
-/// The faces that make up the solid must form a closed shape. This is not
-/// currently validated.
+/// The faces that make up the solid must form closed shells. This is checked
+/// for solids that are created by [`sweep`], but not validated in general.
+///
+/// [`volume`]: crate::algorithms::volume
+/// [`sweep`]: crate::algorithms::sweep
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Solid {
-    faces: BTreeSet<Face>,
+    // Sorted and deduplicated, so the order of the shells doesn't matter.
+    shells: Vec<Shell>,
 }
 
 impl Solid {
+    /// Construct a solid from shells
+    pub fn from_shells(shells: impl IntoIterator<Item = Shell>) -> Self {
+        let mut shells: Vec<_> = shells.into_iter().collect();
+        shells.sort();
+        shells.dedup();
+
+        Self { shells }
+    }
+
     /// Construct a solid from faces
+    ///
+    /// The faces are grouped into shells, according to which faces share
+    /// edges. Faces in triangle representation don't have any edges, so they
+    /// all end up in a single shell together.
     pub fn from_faces(faces: impl IntoIterator<Item = Face>) -> Self {
-        let faces = faces.into_iter().collect();
-        Self { faces }
+        let (breps, triangles): (Vec<_>, Vec<_>) = faces
+            .into_iter()
+            .partition(|face| matches!(face, Face::Face(_)));
+
+        let topology = Topology::new(&breps);
+        let mut shells: Vec<_> = topology
+            .shells()
+            .into_iter()
+            .map(|shell| {
+                Shell::from_faces(
+                    shell.into_iter().map(|face| topology.face(face).clone()),
+                )
+            })
+            .collect();
+
+        if !triangles.is_empty() {
+            shells.push(Shell::from_faces(triangles));
+        }
+
+        Self::from_shells(shells)
     }
 
     /// Create a cube from the length of its edges
-    pub fn cube_from_edge_length(edge_length: impl Into<Scalar>) -> Self {
-        // Let's define a short-hand for half the edge length. We're going to
-        // need it a lot.
-        let h = edge_length.into() / 2.;
-
-        let points = [[-h, -h], [h, -h], [h, h], [-h, h]];
-
-        const Z: Scalar = Scalar::ZERO;
-        let planes = [
-            Surface::xy_plane().translate([Z, Z, -h]), // bottom
-            Surface::xy_plane().translate([Z, Z, h]),  // top
-            Surface::xz_plane().translate([Z, -h, Z]), // front
-            Surface::xz_plane().translate([Z, h, Z]),  // back
-            Surface::yz_plane().translate([-h, Z, Z]), // left
-            Surface::yz_plane().translate([h, Z, Z]),  // right
-        ];
-
-        let faces = planes.map(|plane| {
-            Face::builder(plane).with_exterior_polygon(points).build()
-        });
-
-        Solid::from_faces(faces)
+    pub fn cube_from_edge_length(
+        stores: &Stores,
+        edge_length: impl Into<Scalar>,
+    ) -> Self {
+        Self::from_shells([Shell::cube_from_edge_length(stores, edge_length)])
+    }
+
+    /// Access the solid's shells
+    pub fn shells(&self) -> impl Iterator<Item = &Shell> {
+        self.shells.iter()
     }
 
-    /// Access the solid's faces
+    /// Access the faces of all of the solid's shells
     pub fn faces(&self) -> impl Iterator<Item = &Face> {
-        self.faces.iter()
+        self.shells.iter().flat_map(|shell| shell.faces())
+    }
+
+    /// Convert the solid into a list of shells
+    pub fn into_shells(self) -> Vec<Shell> {
+        self.shells
     }
 
     /// Convert the solid into a list of faces
-    pub fn into_faces(self) -> BTreeSet<Face> {
-        self.faces
+    pub fn into_faces(self) -> Vec<Face> {
+        self.shells
+            .into_iter()
+            .flat_map(|shell| shell.into_faces())
+            .collect()
     }
 }
@@ -0,0 +1,459 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+use parking_lot::RwLock;
+use slotmap::{DefaultKey, SlotMap};
+
+use super::{Curve, Edge, GlobalVertex};
+
+/// The stores for all objects that are shared within a shape
+///
+/// Objects that are part of other objects, like a vertex that bounds multiple
+/// edges, or the edges that make up a cycle, are not copied into each of those
+/// objects. They are inserted into a store once, and referenced via [`Handle`]s
+/// from there.
+///
+/// This makes sure that the objects which refer to a shared object all agree on
+/// its exact value, which could otherwise drift apart, as each copy is computed
+/// and transformed separately.
+///
+/// A host application can implement undo/redo by taking a snapshot of the
+/// stores after each regeneration of a shape, using [`Stores::snapshot`], and
+/// going back to an earlier one using [`Stores::restore`].
+#[derive(Clone, Debug, Default)]
+pub struct Stores {
+    /// Store for the global form of curves
+    pub curves: Store<Curve<3>>,
+
+    /// Store for global vertices
+    pub global_vertices: Store<GlobalVertex>,
+
+    /// Store for edges
+    pub edges: Store<Edge>,
+}
+
+impl Stores {
+    /// Construct a new instance of `Stores`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a snapshot of all stores
+    ///
+    /// See [`Store::snapshot`].
+    pub fn snapshot(&self) -> StoresSnapshot {
+        StoresSnapshot {
+            curves: self.curves.snapshot(),
+            global_vertices: self.global_vertices.snapshot(),
+            edges: self.edges.snapshot(),
+        }
+    }
+
+    /// Restore all stores to the state of a snapshot
+    ///
+    /// See [`Store::restore`].
+    pub fn restore(&self, snapshot: &StoresSnapshot) {
+        self.curves.restore(&snapshot.curves);
+        self.global_vertices.restore(&snapshot.global_vertices);
+        self.edges.restore(&snapshot.edges);
+    }
+}
+
+/// A snapshot of [`Stores`]
+///
+/// Returned by [`Stores::snapshot`].
+#[derive(Clone, Debug)]
+pub struct StoresSnapshot {
+    /// Snapshot of the store for the global form of curves
+    pub curves: Snapshot<Curve<3>>,
+
+    /// Snapshot of the store for global vertices
+    pub global_vertices: Snapshot<GlobalVertex>,
+
+    /// Snapshot of the store for edges
+    pub edges: Snapshot<Edge>,
+}
+
+/// A store for objects of one type
+///
+/// Inserting an object returns a [`Handle`] that refers to it. Inserting an
+/// object that is identical to one that is already in the store doesn't create
+/// a new object, and returns a handle to the existing object instead.
+///
+/// `Store` can be cloned cheaply. All clones refer to the same store.
+///
+/// The contents of a store can be captured in a [`Snapshot`], which a host
+/// application can use to implement undo/redo. See [`Store::snapshot`].
+pub struct Store<T> {
+    inner: Arc<RwLock<Arc<StoreInner<T>>>>,
+}
+
+impl<T> Store<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Construct a new, empty instance of `Store`
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Arc::new(StoreInner {
+                objects: SlotMap::new(),
+                index: HashMap::new(),
+            }))),
+        }
+    }
+
+    /// Insert an object into the store
+    ///
+    /// Returns a handle to the existing object, if an identical object is
+    /// already in the store.
+    pub fn insert(&self, object: T) -> Handle<T> {
+        let mut inner = self.inner.write();
+
+        if let Some(&key) = inner.index.get(&object) {
+            let object = inner.objects[key].clone();
+            return self.handle(key, object);
+        }
+
+        // If a snapshot still refers to the current contents of the store,
+        // they are copied here, leaving the snapshot unchanged.
+        let inner = Arc::make_mut(&mut inner);
+
+        let object = Arc::new(object);
+        let key = inner.objects.insert(object.clone());
+        inner.index.insert((*object).clone(), key);
+
+        self.handle(key, object)
+    }
+
+    /// Indicate whether the store contains the object a handle refers to
+    pub fn contains(&self, handle: &Handle<T>) -> bool {
+        self.inner
+            .read()
+            .objects
+            .get(handle.key)
+            .map(|object| Arc::ptr_eq(object, &handle.object))
+            .unwrap_or(false)
+    }
+
+    /// The number of objects in the store
+    pub fn len(&self) -> usize {
+        self.inner.read().objects.len()
+    }
+
+    /// Indicate whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Access handles to all objects in the store
+    pub fn handles(&self) -> Vec<Handle<T>> {
+        self.inner
+            .read()
+            .objects
+            .iter()
+            .map(|(key, object)| self.handle(key, object.clone()))
+            .collect()
+    }
+
+    /// Take a snapshot of the store
+    ///
+    /// This is cheap, as the snapshot shares the contents of the store,
+    /// instead of copying them. Only once another object is inserted, the
+    /// store copies its contents, leaving the snapshot unchanged. Even then,
+    /// the objects themselves are shared, not copied.
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot {
+            inner: self.inner.read().clone(),
+        }
+    }
+
+    /// Restore the store to the state of a snapshot
+    ///
+    /// Objects that were inserted after the snapshot was taken are removed
+    /// from the store. Existing handles to them stay valid, but the store no
+    /// longer [contains](Store::contains) them.
+    ///
+    /// Restoring from a snapshot doesn't affect that snapshot, nor any other
+    /// ones. Snapshots that have been taken after it can still be restored,
+    /// which allows for implementing redo.
+    pub fn restore(&self, snapshot: &Snapshot<T>) {
+        *self.inner.write() = snapshot.inner.clone();
+    }
+
+    fn handle(&self, key: DefaultKey, object: Arc<T>) -> Handle<T> {
+        Handle {
+            key,
+            object,
+            store: self.clone(),
+        }
+    }
+}
+
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Default for Store<T>
+where
+    T: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Store<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Store")
+            .field("len", &self.inner.read().objects.len())
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+struct StoreInner<T> {
+    objects: SlotMap<DefaultKey, Arc<T>>,
+    index: HashMap<T, DefaultKey>,
+}
+
+/// An immutable snapshot of a [`Store`]
+///
+/// Returned by [`Store::snapshot`]. Can be cloned cheaply.
+pub struct Snapshot<T> {
+    inner: Arc<StoreInner<T>>,
+}
+
+impl<T> Snapshot<T> {
+    /// Indicate whether the snapshot contains the object a handle refers to
+    pub fn contains(&self, handle: &Handle<T>) -> bool {
+        self.inner
+            .objects
+            .get(handle.key)
+            .map(|object| Arc::ptr_eq(object, &handle.object))
+            .unwrap_or(false)
+    }
+
+    /// The number of objects in the snapshot
+    pub fn len(&self) -> usize {
+        self.inner.objects.len()
+    }
+
+    /// Indicate whether the snapshot is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the objects in the snapshot
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.inner.objects.values().map(|object| &**object)
+    }
+}
+
+impl<T> Clone for Snapshot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Snapshot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("len", &self.inner.objects.len())
+            .finish()
+    }
+}
+
+/// A handle to an object in a [`Store`]
+///
+/// Dereferences to the object. Handles are compared, ordered, and hashed by the
+/// value of the object they refer to, so objects that contain handles behave
+/// just like they would, if they contained the object directly. Use
+/// [`Handle::is_same`] to check whether two handles refer to the same object.
+///
+/// A handle also refers to the store that contains its object. Objects that are
+/// derived from it, for example by transforming it, can be inserted into the
+/// same store using [`Handle::store`].
+pub struct Handle<T> {
+    key: DefaultKey,
+    object: Arc<T>,
+    store: Store<T>,
+}
+
+impl<T> Handle<T> {
+    /// Access the object that the handle refers to
+    pub fn get(&self) -> &T {
+        &self.object
+    }
+
+    /// Access the store that contains the object
+    pub fn store(&self) -> &Store<T> {
+        &self.store
+    }
+
+    /// Indicate whether two handles refer to the same object
+    ///
+    /// Two handles that refer to identical objects from different stores are
+    /// not the same.
+    pub fn is_same(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.object, &other.object)
+    }
+
+    /// Access the ID of the object that the handle refers to
+    ///
+    /// Handles that are [the same](Self::is_same) have the same ID. The ID of
+    /// an object might be reused, once no handle refers to it anymore.
+    pub fn id(&self) -> ObjectId {
+        ObjectId(Arc::as_ptr(&self.object) as *const () as usize)
+    }
+}
+
+impl<T> Deref for Handle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key,
+            object: self.object.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get().eq(other.get())
+    }
+}
+
+impl<T: Eq> Eq for Handle<T> {}
+
+impl<T: PartialOrd> PartialOrd for Handle<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.get().partial_cmp(other.get())
+    }
+}
+
+impl<T: Ord> Ord for Handle<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+impl<T: Hash> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// The ID of an object in a [`Store`]
+///
+/// Returned by [`Handle::id`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ObjectId(usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::objects::{Cycle, GlobalVertex, Surface};
+
+    use super::{Store, Stores};
+
+    #[test]
+    fn insert() {
+        let store = Store::new();
+
+        let a = store.insert(GlobalVertex::from_position([0., 0., 0.]));
+        let b = store.insert(GlobalVertex::from_position([1., 0., 0.]));
+        let c = store.insert(GlobalVertex::from_position([0., 0., 0.]));
+
+        assert!(a.is_same(&c));
+        assert!(!a.is_same(&b));
+        assert_eq!(a.id(), c.id());
+        assert_ne!(a.id(), b.id());
+        assert_eq!(store.len(), 2);
+
+        assert!(store.contains(&a));
+        assert!(!Store::new().contains(&a));
+    }
+
+    #[test]
+    fn snapshot() {
+        let store = Store::new();
+
+        let a = store.insert(GlobalVertex::from_position([0., 0., 0.]));
+        let before = store.snapshot();
+
+        let b = store.insert(GlobalVertex::from_position([1., 0., 0.]));
+        let after = store.snapshot();
+
+        assert_eq!(before.len(), 1);
+        assert!(before.contains(&a));
+        assert!(!before.contains(&b));
+
+        // Undo
+        store.restore(&before);
+        assert_eq!(store.len(), 1);
+        assert!(store.contains(&a));
+        assert!(!store.contains(&b));
+
+        // Inserting after the undo doesn't affect the other snapshot.
+        let c = store.insert(GlobalVertex::from_position([2., 0., 0.]));
+        assert!(!after.contains(&c));
+        assert_eq!(before.len(), 1);
+
+        // Redo
+        store.restore(&after);
+        assert_eq!(store.len(), 2);
+        assert!(store.contains(&b));
+        assert!(!store.contains(&c));
+        assert!(b.is_same(&store.insert(*b.get())));
+    }
+
+    #[test]
+    fn edges() {
+        let stores = Stores::new();
+        let before = stores.snapshot();
+
+        let cycle = Cycle::polygon_from_points(
+            &stores,
+            &Surface::xy_plane(),
+            [[0., 0.], [1., 0.], [0., 1.]],
+        );
+        assert_eq!(stores.edges.len(), 3);
+        assert!(cycle.edges.iter().all(|edge| stores.edges.contains(edge)));
+
+        // Reversing the cycle twice refers to the same edges again.
+        let reversed = cycle.reverse();
+        assert_eq!(stores.edges.len(), 6);
+        for (a, b) in cycle.edges.iter().zip(&reversed.reverse().edges) {
+            assert!(a.is_same(b));
+        }
+
+        stores.restore(&before);
+        assert!(stores.edges.is_empty());
+        assert!(stores.global_vertices.is_empty());
+    }
+}
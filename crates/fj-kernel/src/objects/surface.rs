@@ -1,4 +1,4 @@
-use fj_math::{Line, Point, Transform, Vector};
+use fj_math::{Line, Point, Scalar, Transform, Vector};
 
 use crate::algorithms::TransformObject;
 
@@ -77,6 +77,18 @@ impl Surface {
             }
         }
     }
+
+    /// Convert a point in model coordinates to surface coordinates
+    ///
+    /// See [`SweptCurve::point_to_surface_coords`].
+    pub fn point_to_surface_coords(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Point<2> {
+        match self {
+            Self::SweptCurve(surface) => surface.point_to_surface_coords(point),
+        }
+    }
 }
 
 /// A surface that was swept from a curve
@@ -125,6 +137,46 @@ impl SweptCurve {
             + self.path_to_line().vector_from_line_coords([vector.v])
     }
 
+    /// Convert a point in model coordinates to surface coordinates
+    ///
+    /// Projects the point onto the surface before the conversion. The point is
+    /// projected along the normal of the plane, for surfaces swept from a
+    /// line. For surfaces swept from a circle, it is projected along the path,
+    /// then radially towards the circle's center.
+    pub fn point_to_surface_coords(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Point<2> {
+        let point = point.into();
+
+        match self.curve {
+            Curve::Line(line) => {
+                let normal = line.direction.cross(&self.path);
+                let [u, v, _] = solve(
+                    [line.direction, self.path, normal],
+                    point - line.origin,
+                );
+
+                Point::from([u, v])
+            }
+            Curve::Circle(circle) => {
+                let [x, y, v] = solve(
+                    [circle.a, circle.b, self.path],
+                    point - circle.center,
+                );
+
+                let atan = Scalar::atan2(y, x);
+                let u = if atan >= Scalar::ZERO {
+                    atan
+                } else {
+                    atan + Scalar::PI * 2.
+                };
+
+                Point::from([u, v])
+            }
+        }
+    }
+
     fn path_to_line(&self) -> Line<3> {
         Line {
             origin: self.curve.origin(),
@@ -133,9 +185,23 @@ impl SweptCurve {
     }
 }
 
+/// Express `vector` as a linear combination of `basis`, using Cramer's rule
+fn solve(basis: [Vector<3>; 3], vector: Vector<3>) -> [Scalar; 3] {
+    let [a, b, c] = basis;
+    let det = a.dot(&b.cross(&c));
+
+    [
+        vector.dot(&b.cross(&c)) / det,
+        a.dot(&vector.cross(&c)) / det,
+        a.dot(&b.cross(&vector)) / det,
+    ]
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use std::f64::consts::FRAC_PI_2;
+
+    use fj_math::{Circle, Line, Point, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::objects::Curve;
@@ -180,6 +246,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn point_to_surface_coords() {
+        let plane = SweptCurve {
+            curve: Curve::Line(Line {
+                origin: Point::from([1., 1., 1.]),
+                direction: Vector::from([0., 2., 0.]),
+            }),
+            path: Vector::from([0., 0., 2.]),
+        };
+
+        assert_eq!(
+            plane.point_to_surface_coords([1., 5., 9.]),
+            Point::from([2., 4.]),
+        );
+        assert_eq!(
+            plane.point_to_surface_coords([3., 5., 9.]),
+            Point::from([2., 4.]),
+        );
+
+        let cylinder = SweptCurve {
+            curve: Curve::Circle(Circle {
+                center: Point::origin(),
+                a: Vector::from([1., 0., 0.]),
+                b: Vector::from([0., 1., 0.]),
+            }),
+            path: Vector::from([0., 0., 2.]),
+        };
+
+        assert_eq!(
+            cylinder.point_to_surface_coords([0., 3., 4.]),
+            Point::from([FRAC_PI_2, 2.]),
+        );
+    }
+
     #[test]
     fn vector_from_surface_coords() {
         let swept = SweptCurve {
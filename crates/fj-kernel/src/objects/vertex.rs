@@ -2,7 +2,7 @@ use std::hash::Hash;
 
 use fj_math::Point;
 
-use super::GlobalVertex;
+use super::{GlobalVertex, Handle};
 
 /// A vertex
 ///
@@ -17,15 +17,18 @@ use super::GlobalVertex;
 /// still lives in [`Edge`].
 ///
 /// [`Edge`]: super::Edge
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Vertex {
     position: Point<1>,
-    global: GlobalVertex,
+    global: Handle<GlobalVertex>,
 }
 
 impl Vertex {
     /// Construct an instance of `Vertex`
-    pub fn new(position: impl Into<Point<1>>, global: GlobalVertex) -> Self {
+    pub fn new(
+        position: impl Into<Point<1>>,
+        global: Handle<GlobalVertex>,
+    ) -> Self {
         let position = position.into();
         Self { position, global }
     }
@@ -36,7 +39,10 @@ impl Vertex {
     }
 
     /// The global form of this vertex
-    pub fn global(&self) -> GlobalVertex {
-        self.global
+    ///
+    /// The global vertex is shared with the other vertices at the same location,
+    /// for example the vertices of adjacent edges.
+    pub fn global(&self) -> &Handle<GlobalVertex> {
+        &self.global
     }
 }
@@ -0,0 +1,291 @@
+//! Utilities for property-based testing of kernel algorithms
+//!
+//! This module provides generators for random, but valid, objects, as well as
+//! helpers that check invariants that kernel algorithms must uphold. Together,
+//! they can be used to test algorithms against a large number of inputs:
+//!
+//! ``` rust
+//! use fj_kernel::{algorithms::Tolerance, objects::Stores, test_support};
+//!
+//! let tolerance = Tolerance::from_scalar(0.001).unwrap();
+//! let stores = Stores::new();
+//! let mut rng = test_support::rng(0);
+//!
+//! for _ in 0..10 {
+//!     let face = test_support::random_face(&stores, &mut rng, 10.);
+//!     test_support::assert_triangulation_on_surface(&face, tolerance);
+//! }
+//! ```
+//!
+//! All generators take a `size`, which bounds the generated objects. Points
+//! have coordinates within `-size..=size`, and objects don't extend beyond
+//! that by more than a small factor.
+//!
+//! This module is only available, if the `test-support` feature is enabled.
+
+use std::{f64::consts::TAU, fmt::Debug};
+
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    algorithms::{
+        triangulate, ApproxEq, Tolerance, TransformObject, TriangulationConfig,
+    },
+    iter::ObjectIters,
+    objects::{Curve, Face, Stores, Surface},
+    validation::{validate, ValidationConfig},
+};
+
+/// Create a random number generator from a seed
+///
+/// Using a fixed seed makes test failures reproducible.
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Create a random point, with coordinates within `-size..=size`
+pub fn random_point(rng: &mut impl Rng, size: f64) -> Point<3> {
+    Point::from([(); 3].map(|_| rng.gen_range(-size..=size)))
+}
+
+/// Create a random vector of unit length
+pub fn random_direction(rng: &mut impl Rng) -> Vector<3> {
+    loop {
+        let vector = Vector::from([(); 3].map(|_| rng.gen_range(-1. ..=1.)));
+
+        // Reject vectors outside of the unit sphere, so directions are
+        // distributed uniformly, and vectors that are too short to be
+        // normalized accurately.
+        let magnitude = vector.magnitude();
+        if magnitude > Scalar::from(0.1) && magnitude <= Scalar::ONE {
+            return vector.normalize();
+        }
+    }
+}
+
+/// Create a random line, with its origin within `-size..=size`
+pub fn random_line(rng: &mut impl Rng, size: f64) -> Curve<3> {
+    Curve::Line(Line {
+        origin: random_point(rng, size),
+        direction: random_direction(rng) * rng.gen_range(0.1..=1.) * size,
+    })
+}
+
+/// Create a random circle, with its center within `-size..=size`
+///
+/// The radius of the circle is between `0.1 * size` and `size`.
+pub fn random_circle(rng: &mut impl Rng, size: f64) -> Curve<3> {
+    let radius = rng.gen_range(0.1..=1.) * size;
+
+    let normal = random_direction(rng);
+    let a = perpendicular(normal).normalize();
+    let b = normal.cross(&a);
+
+    Curve::Circle(Circle {
+        center: random_point(rng, size),
+        a: a * radius,
+        b: b * radius,
+    })
+}
+
+/// Create a random transform, made up of a rotation and a translation
+///
+/// The translation is within `-size..=size` along each axis.
+pub fn random_transform(rng: &mut impl Rng, size: f64) -> Transform {
+    let rotation =
+        Transform::rotation(random_direction(rng) * rng.gen_range(0. ..TAU));
+    let translation = Transform::translation(random_point(rng, size).coords);
+
+    translation * rotation
+}
+
+/// Create a random face
+///
+/// The face is a convex polygon with 3 to 8 vertices, in a random plane. Its
+/// vertices are no further than `size` from its center, which is within
+/// `-size..=size`.
+pub fn random_face(stores: &Stores, rng: &mut impl Rng, size: f64) -> Face {
+    let num_vertices = rng.gen_range(3..=8);
+
+    // Distribute the vertices around a circle. Keeping a minimum distance
+    // between their angles prevents the polygon from degenerating.
+    let min_step = TAU / num_vertices as f64 / 2.;
+    let offset = rng.gen_range(0. ..TAU);
+    let points: Vec<_> = (0..num_vertices)
+        .map(|i| {
+            let step = TAU / num_vertices as f64;
+            let angle = offset + i as f64 * step + rng.gen_range(0. ..min_step);
+            let radius = size * rng.gen_range(0.5..=1.);
+
+            [angle.cos() * radius, angle.sin() * radius]
+        })
+        .collect();
+
+    Face::builder(stores, Surface::xy_plane())
+        .with_exterior_polygon(points)
+        .build()
+        .transform(&random_transform(rng, size))
+}
+
+/// Assert that an object passes validation
+///
+/// # Panics
+///
+/// Panics, if validation with the default configuration fails.
+pub fn assert_valid<T: ObjectIters>(object: T) {
+    if let Err(err) = validate(object, &ValidationConfig::default()) {
+        panic!("Object is invalid: {err}");
+    }
+}
+
+/// Assert that applying a transform and its inverse restores an object
+///
+/// # Panics
+///
+/// Panics, if the restored object deviates from the original by more than
+/// `tolerance`.
+pub fn assert_transform_inverts<T>(
+    object: &T,
+    transform: &Transform,
+    tolerance: impl Into<Scalar>,
+) where
+    T: ApproxEq + Clone + Debug + TransformObject,
+{
+    let restored = object
+        .clone()
+        .transform(transform)
+        .transform(&transform.inverse());
+
+    assert!(
+        object.approx_eq(&restored, tolerance.into()),
+        "Transform didn't invert\n\
+        original: {object:#?}\n\
+        restored: {restored:#?}",
+    );
+}
+
+/// Assert that the triangulation of a face lies on the face's surface
+///
+/// Checks that each point of the triangulation is within `tolerance` of the
+/// surface, and that each triangle faces the same way as the surface.
+///
+/// # Panics
+///
+/// Panics, if the face is not a planar face using a boundary representation,
+/// or if any of the checks fail.
+pub fn assert_triangulation_on_surface(face: &Face, tolerance: Tolerance) {
+    let surface = face.surface();
+    let normal = {
+        let u = surface.vector_from_surface_coords([1., 0.]);
+        let v = surface.vector_from_surface_coords([0., 1.]);
+        u.cross(&v)
+    };
+
+    let mesh = triangulate(
+        vec![face.clone()],
+        tolerance,
+        &TriangulationConfig::default(),
+        &mut DebugInfo::new(),
+        &mut ProgressHandle::new(),
+    )
+    .expect("Progress handle has no cancellation token");
+
+    assert!(
+        mesh.triangles().next().is_some(),
+        "Triangulation of face is empty: {face:#?}"
+    );
+
+    for triangle in mesh.triangles() {
+        for point in triangle.points {
+            let on_surface = surface.point_from_surface_coords(
+                surface.point_to_surface_coords(point),
+            );
+            assert!(
+                (on_surface - point).magnitude() <= tolerance.inner(),
+                "Triangulation point {point:?} is not on surface {surface:?}"
+            );
+        }
+
+        let [a, b, c] = triangle.points;
+        assert!(
+            (b - a).cross(&(c - a)).dot(&normal) > Scalar::ZERO,
+            "Triangle {:?} faces away from surface {surface:?}",
+            triangle.points,
+        );
+    }
+}
+
+/// Compute a vector that is perpendicular to `vector`
+fn perpendicular(vector: Vector<3>) -> Vector<3> {
+    // Cross with the axis that is least parallel to the vector, to avoid a
+    // result that is too short to be accurate.
+    let [x, y, z] = vector.components.map(Scalar::abs);
+    let axis = if x <= y && x <= z {
+        Vector::unit_x()
+    } else if y <= z {
+        Vector::unit_y()
+    } else {
+        Vector::unit_z()
+    };
+
+    vector.cross(&axis)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Curve, Stores},
+    };
+
+    use super::{
+        assert_transform_inverts, assert_triangulation_on_surface,
+        assert_valid, random_circle, random_face, random_line,
+        random_transform, rng,
+    };
+
+    #[test]
+    fn faces() {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let mut rng = rng(0);
+
+        for _ in 0..100 {
+            let face = random_face(&stores, &mut rng, 10.);
+            let transform = random_transform(&mut rng, 10.);
+
+            assert_valid(face.clone());
+            assert_triangulation_on_surface(&face, tolerance);
+            assert_transform_inverts(&face, &transform, 1e-9);
+        }
+    }
+
+    #[test]
+    fn curves() {
+        let mut rng = rng(0);
+
+        for _ in 0..100 {
+            let transform = random_transform(&mut rng, 10.);
+
+            let line = random_line(&mut rng, 10.);
+            let circle = random_circle(&mut rng, 10.);
+
+            assert_transform_inverts(&line, &transform, 1e-9);
+            assert_transform_inverts(&circle, &transform, 1e-9);
+
+            if let Curve::Circle(circle) = circle {
+                let epsilon = Scalar::from(1e-9);
+                assert!(circle.a.dot(&circle.b).abs() < epsilon);
+                assert!(
+                    (circle.a.magnitude() - circle.b.magnitude()).abs()
+                        < epsilon
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Adjacency queries on the topology of a shape
+//!
+//! Faces refer to their edges, and edges to their vertices, but not the other
+//! way around. [`Topology`] builds an index of a shape's faces, that also
+//! answers the reverse questions: Which faces are adjacent to an edge? Which
+//! edges meet at a vertex? Which faces are connected to each other?
+
+use std::collections::{HashMap, VecDeque};
+
+use fj_math::{Point, Scalar};
+
+use crate::objects::{Curve, Edge, Face, GlobalVertex};
+
+/// An index of the topology of a shape
+///
+/// Edges of different faces are considered to be the same edge, if they are
+/// bounded by the same global vertices, and lie on the same curve. The
+/// direction of an edge doesn't matter. Edges without vertices are the same,
+/// if they lie on the same circle.
+///
+/// Faces that are not in boundary representation have no edges, and are not
+/// adjacent to any other face.
+pub struct Topology<'r> {
+    faces: Vec<&'r Face>,
+
+    /// The edges of each face, in the same order as `faces`
+    edges_of_faces: Vec<Vec<EdgeId>>,
+
+    edges: Vec<EdgeEntry>,
+    edges_by_vertices: HashMap<Option<[GlobalVertex; 2]>, Vec<EdgeId>>,
+    edges_at_vertices: HashMap<GlobalVertex, Vec<EdgeId>>,
+}
+
+impl<'r> Topology<'r> {
+    /// Build the topology of the given faces
+    pub fn new(faces: impl IntoIterator<Item = &'r Face>) -> Self {
+        let mut topology = Self {
+            faces: Vec::new(),
+            edges_of_faces: Vec::new(),
+            edges: Vec::new(),
+            edges_by_vertices: HashMap::new(),
+            edges_at_vertices: HashMap::new(),
+        };
+
+        for face in faces {
+            let id = FaceId(topology.faces.len());
+            topology.faces.push(face);
+
+            let mut edges = Vec::new();
+            if let Face::Face(_) = face {
+                for cycle in face.all_cycles() {
+                    for edge in cycle.edges() {
+                        let edge = topology.insert_edge(edge, id);
+                        if !edges.contains(&edge) {
+                            edges.push(edge);
+                        }
+                    }
+                }
+            }
+            topology.edges_of_faces.push(edges);
+        }
+
+        topology
+    }
+
+    /// Iterate over all faces
+    pub fn faces(&self) -> impl Iterator<Item = (FaceId, &'r Face)> + '_ {
+        self.faces
+            .iter()
+            .enumerate()
+            .map(|(i, face)| (FaceId(i), *face))
+    }
+
+    /// Access a face
+    pub fn face(&self, id: FaceId) -> &'r Face {
+        self.faces[id.0]
+    }
+
+    /// Iterate over all edges
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, &Edge)> {
+        self.edges
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (EdgeId(i), &entry.edge))
+    }
+
+    /// Access an edge
+    ///
+    /// If multiple faces share the edge, this returns the edge of the face
+    /// that was passed to [`Topology::new`] first.
+    pub fn edge(&self, id: EdgeId) -> &Edge {
+        &self.edges[id.0].edge
+    }
+
+    /// Find the ID of an edge
+    ///
+    /// Returns `None`, if the edge is not part of any of the faces.
+    pub fn edge_id(&self, edge: &Edge) -> Option<EdgeId> {
+        let key = EdgeKey::of(edge);
+
+        self.edges_by_vertices
+            .get(&key.vertices)?
+            .iter()
+            .copied()
+            .find(|id| self.edges[id.0].key.matches(&key))
+    }
+
+    /// Access the edges of a face
+    pub fn edges_of_face(&self, face: FaceId) -> &[EdgeId] {
+        &self.edges_of_faces[face.0]
+    }
+
+    /// Access the faces that are adjacent to an edge
+    pub fn faces_of_edge(&self, edge: EdgeId) -> &[FaceId] {
+        &self.edges[edge.0].faces
+    }
+
+    /// Access the edges that are bounded by a vertex
+    pub fn edges_at_vertex(&self, vertex: &GlobalVertex) -> &[EdgeId] {
+        self.edges_at_vertices
+            .get(vertex)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Find the faces that share an edge with a face
+    pub fn adjacent_faces(&self, face: FaceId) -> Vec<FaceId> {
+        let mut adjacent = Vec::new();
+
+        for &edge in self.edges_of_face(face) {
+            for &other in self.faces_of_edge(edge) {
+                if other != face && !adjacent.contains(&other) {
+                    adjacent.push(other);
+                }
+            }
+        }
+
+        adjacent
+    }
+
+    /// Group the faces into shells
+    ///
+    /// A shell is a set of faces that are connected to each other through
+    /// shared edges. The shells are ordered by their first face, and the faces
+    /// within a shell are ordered by their ID.
+    pub fn shells(&self) -> Vec<Vec<FaceId>> {
+        let mut shell_of_face = vec![None; self.faces.len()];
+        let mut shells = Vec::new();
+
+        for start in 0..self.faces.len() {
+            if shell_of_face[start].is_some() {
+                continue;
+            }
+
+            let mut shell = Vec::new();
+            let mut queue = VecDeque::from([FaceId(start)]);
+            shell_of_face[start] = Some(shells.len());
+
+            while let Some(face) = queue.pop_front() {
+                shell.push(face);
+
+                for adjacent in self.adjacent_faces(face) {
+                    if shell_of_face[adjacent.0].is_none() {
+                        shell_of_face[adjacent.0] = Some(shells.len());
+                        queue.push_back(adjacent);
+                    }
+                }
+            }
+
+            shell.sort();
+            shells.push(shell);
+        }
+
+        shells
+    }
+
+    fn insert_edge(&mut self, edge: Edge, face: FaceId) -> EdgeId {
+        if let Some(id) = self.edge_id(&edge) {
+            let faces = &mut self.edges[id.0].faces;
+            if !faces.contains(&face) {
+                faces.push(face);
+            }
+            return id;
+        }
+
+        let id = EdgeId(self.edges.len());
+        let key = EdgeKey::of(&edge);
+
+        self.edges_by_vertices
+            .entry(key.vertices)
+            .or_default()
+            .push(id);
+        for vertex in key.vertices.into_iter().flatten() {
+            self.edges_at_vertices.entry(vertex).or_default().push(id);
+        }
+
+        self.edges.push(EdgeEntry {
+            edge,
+            key,
+            faces: vec![face],
+        });
+
+        id
+    }
+}
+
+/// Identifies a face within a [`Topology`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct FaceId(pub usize);
+
+/// Identifies an edge within a [`Topology`]
+///
+/// Edges that are shared between faces have the same ID.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct EdgeId(usize);
+
+struct EdgeEntry {
+    edge: Edge,
+    key: EdgeKey,
+    faces: Vec<FaceId>,
+}
+
+/// What makes edges the same, regardless of their direction
+struct EdgeKey {
+    /// The global vertices of the edge, in ascending order
+    vertices: Option<[GlobalVertex; 2]>,
+
+    /// The point halfway along the edge
+    ///
+    /// Tells apart arcs that are bounded by the same vertices. Edges without
+    /// vertices are whole circles, which are told apart by their center.
+    middle: Point<3>,
+
+    /// The size of the edge's curve, for comparing `middle` in relative terms
+    scale: Scalar,
+}
+
+impl EdgeKey {
+    fn of(edge: &Edge) -> Self {
+        let curve = edge.curve();
+
+        let vertices = edge.vertices().map(|vertices| {
+            let mut vertices = vertices.map(|vertex| *vertex.global().get());
+            vertices.sort();
+            vertices
+        });
+
+        let (middle, scale) = match (edge.vertices(), curve) {
+            (Some([a, b]), _) => {
+                let t = (a.position().t + b.position().t) / 2.;
+                let middle = curve.point_from_curve_coords([t]);
+                let scale =
+                    (a.global().position() - b.global().position()).magnitude();
+                (middle, scale)
+            }
+            (None, Curve::Circle(circle)) => {
+                (circle.center, circle.a.magnitude())
+            }
+            (None, Curve::Line(line)) => (line.origin, Scalar::ONE),
+        };
+
+        Self {
+            vertices,
+            middle,
+            scale,
+        }
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        let epsilon = self.scale.max(other.scale) * RELATIVE_EPSILON;
+
+        self.vertices == other.vertices
+            && (self.middle - other.middle).magnitude() <= epsilon
+            && (self.vertices.is_some()
+                || (self.scale - other.scale).abs() <= epsilon)
+    }
+}
+
+/// The distance at which points are considered identical, relative to the size
+/// of the edge they belong to
+const RELATIVE_EPSILON: f64 = 1e-9;
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::TransformObject,
+        objects::{GlobalVertex, Solid, Stores},
+    };
+
+    use super::{FaceId, Topology};
+
+    #[test]
+    fn cube() {
+        let stores = Stores::new();
+        let cube = Solid::cube_from_edge_length(&stores, 1.);
+
+        let topology = Topology::new(cube.faces());
+        assert_eq!(topology.faces().count(), 6);
+        assert_eq!(topology.edges().count(), 12);
+
+        for (id, _) in topology.faces() {
+            assert_eq!(topology.edges_of_face(id).len(), 4);
+            assert_eq!(topology.adjacent_faces(id).len(), 4);
+        }
+        for (id, edge) in topology.edges() {
+            assert_eq!(topology.faces_of_edge(id).len(), 2);
+            assert_eq!(topology.edge_id(edge), Some(id));
+            assert_eq!(topology.edge_id(&edge.clone().reverse()), Some(id));
+        }
+
+        let corner = GlobalVertex::from_position([0.5, 0.5, 0.5]);
+        assert_eq!(topology.edges_at_vertex(&corner).len(), 3);
+        let not_a_corner = GlobalVertex::from_position(Point::origin());
+        assert!(topology.edges_at_vertex(&not_a_corner).is_empty());
+
+        assert_eq!(topology.shells(), [(0..6).map(FaceId).collect::<Vec<_>>()]);
+    }
+
+    #[test]
+    fn shells() {
+        let stores = Stores::new();
+
+        let a = Solid::cube_from_edge_length(&stores, 1.);
+        let b =
+            a.clone()
+                .translate([Scalar::from(2.), Scalar::ZERO, Scalar::ZERO]);
+
+        let topology = Topology::new(a.faces().chain(b.faces()));
+        let shells = topology.shells();
+
+        assert_eq!(shells.len(), 2);
+        assert!(shells.iter().all(|shell| shell.len() == 6));
+    }
+}
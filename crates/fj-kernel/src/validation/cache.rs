@@ -0,0 +1,244 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use fj_math::Scalar;
+use parking_lot::Mutex;
+use sha2::{Digest as _, Sha256};
+
+use crate::objects::{Edge, Face, GlobalVertex};
+
+use super::ValidationConfig;
+
+/// A cache of objects that have passed validation
+///
+/// Objects are identified by a SHA-256 hash of their content, so a changed
+/// object can't be mistaken for one that passed validation before. An object
+/// that has been validated before, and hasn't changed since, doesn't need to be
+/// validated again. Checks that involve multiple objects, like making sure that
+/// vertices are unique, are only skipped for objects that have been validated
+/// together.
+///
+/// Entries survive until the end of the run after the one they were last used
+/// in. Call [`ValidationCache::begin_run`] whenever a shape is regenerated, to
+/// keep the cache from growing without bounds. Use a separate cache for each
+/// shape that is regenerated independently, or the runs of one shape evict the
+/// entries of the other.
+///
+/// The cache is cleared, whenever it's used with a different configuration.
+/// Clones of a cache share the same entries.
+#[derive(Clone, Default)]
+pub struct ValidationCache {
+    state: Arc<Mutex<State>>,
+}
+
+impl ValidationCache {
+    /// Construct an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Begin a new run
+    ///
+    /// Drops all entries that haven't been used since the previous call.
+    pub fn begin_run(&self) {
+        let mut state = self.state.lock();
+        state.previous = std::mem::take(&mut state.current);
+    }
+
+    /// Drop all entries
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.current = Generation::default();
+        state.previous = Generation::default();
+    }
+
+    /// Return the number of objects in the cache
+    pub fn len(&self) -> usize {
+        let state = self.state.lock();
+        let mut keys = HashSet::new();
+
+        for generation in [&state.current, &state.previous] {
+            keys.extend(generation.vertices.keys().map(|key| (0, *key)));
+            keys.extend(generation.edges.iter().map(|key| (1, *key)));
+            keys.extend(generation.faces.iter().map(|key| (2, *key)));
+        }
+
+        keys.len()
+    }
+
+    /// Indicate whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Look up which of the given objects have been validated before
+    pub(super) fn lookup(
+        &self,
+        vertices: &[GlobalVertex],
+        edges: &[Edge],
+        faces: &[Face],
+        config: &ValidationConfig,
+    ) -> Lookup {
+        let vertices: Vec<_> = vertices.iter().map(key).collect();
+        let edges: Vec<_> = edges.iter().map(key).collect();
+        let faces: Vec<_> = faces.iter().map(key).collect();
+
+        let mut state = self.state.lock();
+
+        let distances =
+            [config.distinct_min_distance, config.identical_max_distance];
+        if state.distances != Some(distances) {
+            state.current = Generation::default();
+            state.previous = Generation::default();
+            state.distances = Some(distances);
+        }
+
+        let vertex_sets =
+            vertices.iter().map(|key| state.vertex_set(*key)).collect();
+        let cached_edges =
+            edges.iter().map(|key| state.contains_edge(*key)).collect();
+        let cached_faces =
+            faces.iter().map(|key| state.contains_face(*key)).collect();
+
+        Lookup {
+            vertices,
+            edges,
+            faces,
+            vertex_sets,
+            cached_edges,
+            cached_faces,
+        }
+    }
+
+    /// Record that the looked-up objects have passed validation together
+    pub(super) fn insert(&self, lookup: Lookup) {
+        let mut state = self.state.lock();
+
+        let set = state.next_set;
+        state.next_set += 1;
+
+        let current = &mut state.current;
+        current
+            .vertices
+            .extend(lookup.vertices.into_iter().map(|key| (key, set)));
+        current.edges.extend(lookup.edges);
+        current.faces.extend(lookup.faces);
+    }
+}
+
+impl fmt::Debug for ValidationCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ValidationCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// The result of [`ValidationCache::lookup`]
+pub(super) struct Lookup {
+    vertices: Vec<Key>,
+    edges: Vec<Key>,
+    faces: Vec<Key>,
+
+    /// For each vertex, the set of vertices it has been validated with
+    ///
+    /// Vertices with the same set have been checked against each other.
+    pub vertex_sets: Vec<Option<u64>>,
+
+    /// For each edge, whether it has been validated before
+    pub cached_edges: Vec<bool>,
+
+    /// For each face, whether it has been validated before
+    pub cached_faces: Vec<bool>,
+}
+
+#[derive(Default)]
+struct State {
+    /// The distances from the configuration that the entries are valid for
+    distances: Option<[Scalar; 2]>,
+
+    /// The entries that have been used in the current run
+    current: Generation,
+
+    /// The entries that have been used in the previous run
+    previous: Generation,
+
+    /// The ID of the next set of vertices that passes validation
+    next_set: u64,
+}
+
+impl State {
+    fn vertex_set(&mut self, key: Key) -> Option<u64> {
+        if let Some(set) = self.current.vertices.get(&key) {
+            return Some(*set);
+        }
+
+        let set = self.previous.vertices.get(&key).copied()?;
+        self.current.vertices.insert(key, set);
+        Some(set)
+    }
+
+    fn contains_edge(&mut self, key: Key) -> bool {
+        promote(&mut self.current.edges, &self.previous.edges, key)
+    }
+
+    fn contains_face(&mut self, key: Key) -> bool {
+        promote(&mut self.current.faces, &self.previous.faces, key)
+    }
+}
+
+#[derive(Default)]
+struct Generation {
+    /// Maps vertices to the last set of vertices they passed validation with
+    vertices: HashMap<Key, u64>,
+
+    edges: HashSet<Key>,
+    faces: HashSet<Key>,
+}
+
+/// Check whether an entry exists, moving it into the current generation
+fn promote(
+    current: &mut HashSet<Key>,
+    previous: &HashSet<Key>,
+    key: Key,
+) -> bool {
+    if current.contains(&key) {
+        return true;
+    }
+    if previous.contains(&key) {
+        current.insert(key);
+        return true;
+    }
+
+    false
+}
+
+/// The SHA-256 hash of an object's content
+type Key = [u8; 32];
+
+fn key(object: &impl Hash) -> Key {
+    let mut hasher = Sha256Hasher(Sha256::new());
+    object.hash(&mut hasher);
+    hasher.0.finalize().into()
+}
+
+/// Feeds everything that a [`Hash`] implementation writes into SHA-256
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_le_bytes(bytes)
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+use fj_math::Polygon;
+
+use crate::objects::{Curve, Cycle, Face};
+
+pub fn validate_face(face: &Face) -> Result<(), GeometricIssues> {
+    // Faces in triangle representation don't have any cycles.
+    let face = match face {
+        Face::Face(face) => face,
+        Face::Triangles(_) => return Ok(()),
+    };
+
+    let mut self_intersecting_cycles = Vec::new();
+
+    for cycle in face.all_cycles() {
+        let polygon = match polygon_of_cycle(&cycle) {
+            Some(polygon) => polygon,
+            None => continue,
+        };
+
+        if polygon.self_intersection().is_some() {
+            self_intersecting_cycles.push(cycle);
+        }
+    }
+
+    if !self_intersecting_cycles.is_empty() {
+        return Err(GeometricIssues {
+            self_intersecting_cycles,
+        });
+    }
+
+    Ok(())
+}
+
+/// Convert a cycle into a polygon, in surface coordinates
+///
+/// Returns `None`, if the cycle has edges that aren't line segments. Checking
+/// those for intersections is not supported yet.
+fn polygon_of_cycle(cycle: &Cycle) -> Option<Polygon> {
+    let mut points = Vec::new();
+
+    for edge in cycle.edges() {
+        let line = match edge.curve.local() {
+            Curve::Line(line) => line,
+            Curve::Circle(_) => return None,
+        };
+        let [start, _] = edge.vertices()?;
+
+        points.push(line.point_from_line_coords(start.position()));
+    }
+
+    Some(Polygon::from_points(points))
+}
+
+/// Geometric issues found during validation
+///
+/// Used by [`ValidationError`].
+///
+/// [`ValidationError`]: super::ValidationError
+#[derive(Debug, Default, thiserror::Error)]
+pub struct GeometricIssues {
+    /// Cycles whose edges intersect each other
+    ///
+    /// This includes cycles that don't enclose any area, like cycles made of
+    /// fewer than three points, or of points that are all on one line.
+    pub self_intersecting_cycles: Vec<Cycle>,
+}
+
+impl fmt::Display for GeometricIssues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Geometric issues found:")?;
+
+        if !self.self_intersecting_cycles.is_empty() {
+            writeln!(f, "- Self-intersecting cycles:")?;
+
+            for cycle in &self.self_intersecting_cycles {
+                writeln!(f, "  - {cycle:?}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
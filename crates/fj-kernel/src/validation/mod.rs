@@ -1,34 +1,59 @@
 //! Infrastructure for validating shapes
 //!
 //! Validation enforces various constraints about shapes and the objects that
-//! constitute them. These constraints fall into 4 categories:
+//! constitute them. These constraints fall into 5 categories:
 //!
 //! - **Coherence:** Local forms of objects must be consistent with their
 //!   canonical forms.
 //! - **Geometric:** Comprises various object-specific constraints, for example
 //!   edges or faces might not be allowed to intersect.
+//! - **Orientation:** The cycles of a face must wind in the direction that is
+//!   defined by the face's surface: counter-clockwise for exteriors, clockwise
+//!   for interiors. The inner shells of a solid, whose faces point inwards,
+//!   must be enclosed by an outer shell.
 //! - **Structural:** All other objects that an object references must be part
 //!   of the same shape.
 //! - **Uniqueness:** Objects within a shape must be unique.
 //!
 //! Please note that not all of these validation categories are fully
 //! implemented, as of this writing.
+//!
+//! Validating large shapes can be expensive. A [`ValidationCache`] can be used
+//! to skip objects that have already passed validation.
 
+mod cache;
 mod coherence;
+mod geometric;
+mod orientation;
 mod uniqueness;
 
 pub use self::{
+    cache::ValidationCache,
     coherence::{CoherenceIssues, CoherenceMismatch},
+    geometric::GeometricIssues,
+    orientation::OrientationIssues,
     uniqueness::UniquenessIssues,
 };
 
-use std::{collections::HashSet, ops::Deref};
+use std::ops::Deref;
 
+use fj_interop::progress::Cancelled;
 use fj_math::Scalar;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
 
-use crate::iter::ObjectIters;
+use crate::{
+    algorithms::{InvalidTolerance, SweepError},
+    iter::ObjectIters,
+};
 
 /// Validate the given object
+///
+/// Each check is run for all relevant objects in parallel.
+///
+/// If the configuration has a [`ValidationCache`], objects that are found in
+/// there are skipped, and the objects are added to it, if they pass.
+#[tracing::instrument(level = "debug", skip_all)]
 pub fn validate<T>(
     object: T,
     config: &ValidationConfig,
@@ -36,27 +61,92 @@ pub fn validate<T>(
 where
     T: ObjectIters,
 {
-    let mut vertices = HashSet::new();
+    let vertices: Vec<_> = object.global_vertex_iter().collect();
+    let edges: Vec<_> = object.edge_iter().collect();
+    let faces: Vec<_> = object.face_iter().collect();
+
+    let lookup = config
+        .cache
+        .as_ref()
+        .map(|cache| cache.lookup(&vertices, &edges, &faces, config));
+
+    let vertex_sets = match &lookup {
+        Some(lookup) => lookup.vertex_sets.clone(),
+        None => vec![None; vertices.len()],
+    };
+    let edges = uncached(
+        &edges,
+        lookup.as_ref().map(|lookup| lookup.cached_edges.as_slice()),
+    );
+    let faces = uncached(
+        &faces,
+        lookup.as_ref().map(|lookup| lookup.cached_faces.as_slice()),
+    );
+
+    // Each vertex is only checked against the vertices that come before it,
+    // which makes sure every pair of vertices is checked exactly once. Pairs
+    // of vertices that have passed validation together before are skipped.
+    par_iter(&vertices)
+        .enumerate()
+        .try_for_each(|(i, vertex)| {
+            let set = vertex_sets[i];
+            let others = vertices[..i]
+                .iter()
+                .zip(&vertex_sets)
+                .filter(|(_, other)| set.is_none() || **other != set)
+                .map(|(other, _)| other);
+
+            uniqueness::validate_vertex(
+                vertex,
+                others,
+                config.distinct_min_distance,
+            )
+        })?;
+
+    par_iter(&edges).try_for_each(|edge| {
+        coherence::validate_edge(edge, config.identical_max_distance)
+    })?;
+
+    // Checking the winding of a cycle only makes sense, if it doesn't
+    // intersect itself.
+    par_iter(&faces).try_for_each(|face| geometric::validate_face(face))?;
+    par_iter(&faces).try_for_each(|face| orientation::validate_face(face))?;
+
+    // Solids aren't cached, as telling their shells apart is only expensive
+    // for the rare solids that have more than one.
+    let solids: Vec<_> = object.solid_iter().collect();
+    par_iter(&solids).try_for_each(orientation::validate_solid)?;
+
+    if let (Some(cache), Some(lookup)) = (&config.cache, lookup) {
+        cache.insert(lookup);
+    }
 
-    for vertex in object.global_vertex_iter() {
-        uniqueness::validate_vertex(
-            &vertex,
-            &vertices,
-            config.distinct_min_distance,
-        )?;
+    Ok(Validated(object))
+}
 
-        vertices.insert(vertex);
-    }
+/// Filter out the objects that have passed validation before
+fn uncached<'r, T>(objects: &'r [T], cached: Option<&[bool]>) -> Vec<&'r T> {
+    objects
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !matches!(cached, Some(cached) if cached[*i]))
+        .map(|(_, object)| object)
+        .collect()
+}
 
-    for edge in object.edge_iter() {
-        coherence::validate_edge(&edge, config.identical_max_distance)?;
-    }
+#[cfg(not(target_arch = "wasm32"))]
+fn par_iter<T: Sync>(objects: &[T]) -> rayon::slice::Iter<'_, T> {
+    objects.par_iter()
+}
 
-    Ok(Validated(object))
+/// WebAssembly has no threads, so the objects are validated one after the other
+#[cfg(target_arch = "wasm32")]
+fn par_iter<T>(objects: &[T]) -> std::slice::Iter<'_, T> {
+    objects.iter()
 }
 
 /// Configuration required for the validation process
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ValidationConfig {
     /// The minimum distance between distinct objects
     ///
@@ -71,6 +161,11 @@ pub struct ValidationConfig {
     /// that distance is less than the one defined in this field, can not be
     /// considered identical.
     pub identical_max_distance: Scalar,
+
+    /// The cache of objects that have already passed validation
+    ///
+    /// If this is `None`, all objects are validated every time.
+    pub cache: Option<ValidationCache>,
 }
 
 impl Default for ValidationConfig {
@@ -83,6 +178,8 @@ impl Default for ValidationConfig {
             // false positives due to floating-point accuracy issues), we can
             // adjust it.
             identical_max_distance: Scalar::from_f64(5e-14),
+
+            cache: None,
         }
     }
 }
@@ -118,11 +215,38 @@ pub enum ValidationError {
 
     /// Geometric validation failed
     #[error("Geometric validation failed")]
-    Geometric,
+    Geometric(#[from] GeometricIssues),
+
+    /// Orientation validation failed
+    #[error("Orientation validation failed")]
+    Orientation(#[from] OrientationIssues),
 
     /// Uniqueness validation failed
     #[error("Uniqueness validation failed")]
     Uniqueness(#[from] UniquenessIssues),
+
+    /// Sweeping a sketch failed, or resulted in a solid that is not closed
+    #[error("Sweep failed")]
+    Sweep(#[source] SweepError),
+
+    /// An operation overrides the tolerance with an invalid value
+    #[error("Invalid tolerance override")]
+    Tolerance(#[from] InvalidTolerance),
+
+    /// The operation that produced the objects was cancelled
+    #[error("Operation was cancelled")]
+    Cancelled(#[from] Cancelled),
+}
+
+impl From<SweepError> for ValidationError {
+    fn from(err: SweepError) -> Self {
+        // A cancelled sweep didn't fail. Keep it distinguishable from one that
+        // did, for callers that only look at this error.
+        match err {
+            SweepError::Cancelled(cancelled) => Self::Cancelled(cancelled),
+            err => Self::Sweep(err),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -130,24 +254,37 @@ mod tests {
     use fj_math::{Point, Scalar};
 
     use crate::{
+        algorithms::{reverse_face, TransformObject},
         local::Local,
-        objects::{Curve, Edge, GlobalVertex, Vertex, VerticesOfEdge},
-        validation::{validate, ValidationConfig, ValidationError},
+        objects::{
+            Curve, Edge, Face, GlobalVertex, Shell, Solid, Stores, Surface,
+            Vertex, VerticesOfEdge,
+        },
+        validation::{
+            validate, ValidationCache, ValidationConfig, ValidationError,
+        },
     };
 
     #[test]
     fn coherence_edge() {
+        let stores = Stores::new();
+
         let a = Point::from([0., 0., 0.]);
         let b = Point::from([1., 0., 0.]);
 
         let curve = {
             let curve_local = Curve::line_from_points([[0., 0.], [1., 0.]]);
-            let curve_canonical = Curve::line_from_points([a, b]);
+            let curve_canonical =
+                stores.curves.insert(Curve::line_from_points([a, b]));
             Local::new(curve_local, curve_canonical)
         };
 
-        let a = GlobalVertex::from_position(a);
-        let b = GlobalVertex::from_position(b);
+        let a = stores
+            .global_vertices
+            .insert(GlobalVertex::from_position(a));
+        let b = stores
+            .global_vertices
+            .insert(GlobalVertex::from_position(b));
 
         let deviation = Scalar::from_f64(0.25);
 
@@ -158,7 +295,7 @@ mod tests {
         let edge = Edge { curve, vertices };
 
         let result = validate(
-            edge,
+            edge.clone(),
             &ValidationConfig {
                 identical_max_distance: deviation * 2.,
                 ..ValidationConfig::default()
@@ -176,6 +313,77 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn geometric_face() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let config = ValidationConfig::default();
+
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [1., 0.], [1., 1.], [0., 1.]])
+            .build();
+        validate(face, &config)?;
+
+        // A face whose exterior crosses itself should fail.
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [1., 1.], [1., 0.], [0., 1.]])
+            .build();
+        let result = validate(face, &config);
+        assert!(matches!(result, Err(ValidationError::Geometric(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn orientation_face() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let config = ValidationConfig::default();
+
+        let face = Face::builder(&stores, Surface::xy_plane())
+            .with_exterior_polygon([[0., 0.], [3., 0.], [3., 3.], [0., 3.]])
+            .with_interior_polygon([[1., 1.], [2., 1.], [2., 2.], [1., 2.]])
+            .build();
+        validate(face.clone(), &config)?;
+
+        // A face whose exterior winds the wrong way should fail.
+        let brep = face.brep();
+        let exteriors = brep.exteriors().map(|cycle| cycle.reverse());
+        let face = Face::new(brep.surface, exteriors, brep.interiors(), [0; 4]);
+        let result = validate(face, &config);
+        assert!(matches!(result, Err(ValidationError::Orientation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn orientation_solid() -> anyhow::Result<()> {
+        let stores = Stores::new();
+
+        let config = ValidationConfig::default();
+
+        let outer = Shell::cube_from_edge_length(&stores, 4.);
+        let void = Shell::from_faces(
+            Shell::cube_from_edge_length(&stores, 2.)
+                .faces()
+                .map(reverse_face),
+        );
+
+        // A cube with a cubic void in its center.
+        let solid = Solid::from_shells([outer.clone(), void.clone()]);
+        assert_eq!(solid.shells().count(), 2);
+        validate(solid, &config)?;
+
+        // The void must be enclosed by the outer shell.
+        let void =
+            void.translate([Scalar::from(5.), Scalar::ZERO, Scalar::ZERO]);
+        let solid = Solid::from_shells([outer, void]);
+        let result = validate(solid, &config);
+        assert!(matches!(result, Err(ValidationError::Orientation(_))));
+
+        Ok(())
+    }
+
     #[test]
     fn uniqueness_vertex() -> anyhow::Result<()> {
         let mut shape = Vec::new();
@@ -203,4 +411,47 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn cache() -> anyhow::Result<()> {
+        let deviation = Scalar::from_f64(0.25);
+
+        let a = GlobalVertex::from_position([0., 0., 0.]);
+        let b = GlobalVertex::from_position([0.25, 0., 0.]);
+
+        let cache = ValidationCache::new();
+        let config = ValidationConfig {
+            distinct_min_distance: deviation * 2.,
+            cache: Some(cache.clone()),
+            ..ValidationConfig::default()
+        };
+
+        // Each vertex is valid on its own, and ends up in the cache.
+        validate(vec![a], &config)?;
+        validate(vec![b], &config)?;
+        assert_eq!(cache.len(), 2);
+
+        // Vertices that passed validation separately haven't been checked
+        // against each other.
+        let result = validate(vec![a, b], &config);
+        assert!(matches!(result, Err(ValidationError::Uniqueness(_))));
+
+        // A different configuration invalidates the cache.
+        validate(
+            vec![a],
+            &ValidationConfig {
+                distinct_min_distance: deviation / 2.,
+                ..config.clone()
+            },
+        )?;
+        assert_eq!(cache.len(), 1);
+
+        // Entries are dropped, if they weren't used during a whole run.
+        cache.begin_run();
+        assert_eq!(cache.len(), 1);
+        cache.begin_run();
+        assert!(cache.is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use fj_math::{Aabb, Scalar};
+
+use crate::{
+    algorithms::{volume, BoundingVolume, Tolerance},
+    iter::ObjectIters,
+    objects::{Curve, Cycle, Face, Shell, Solid, Winding},
+};
+
+pub fn validate_face(face: &Face) -> Result<(), OrientationIssues> {
+    // Faces in triangle representation don't have any cycles.
+    let face = match face {
+        Face::Face(face) => face,
+        Face::Triangles(_) => return Ok(()),
+    };
+
+    let mut wrong_windings = Vec::new();
+
+    let exteriors = face
+        .exteriors()
+        .map(|cycle| (cycle, Winding::CounterClockwise));
+    let interiors = face.interiors().map(|cycle| (cycle, Winding::Clockwise));
+
+    for (cycle, expected) in exteriors.chain(interiors) {
+        if cycle.winding() != expected {
+            wrong_windings.push((cycle, expected));
+        }
+    }
+
+    if !wrong_windings.is_empty() {
+        return Err(OrientationIssues {
+            wrong_windings,
+            ..OrientationIssues::default()
+        });
+    }
+
+    Ok(())
+}
+
+pub fn validate_solid(solid: &Solid) -> Result<(), OrientationIssues> {
+    // A solid with a single shell can only be valid, if that is an outer
+    // shell. Checking that requires approximating the shell, which is
+    // expensive, so it's only done where there's a need to tell shells apart.
+    if solid.shells().nth(1).is_none() {
+        return Ok(());
+    }
+
+    let mut outer = Vec::new();
+    let mut inner = Vec::new();
+
+    for shell in solid.shells() {
+        let tolerance = tolerance_for_shell(shell);
+        let volume = volume(shell, tolerance);
+        let aabb = shell.aabb(tolerance);
+
+        if volume < Scalar::ZERO {
+            inner.push((shell, -volume, aabb));
+        } else {
+            outer.push((volume, aabb));
+        }
+    }
+
+    // An inner shell bounds a void, which must be within the material that an
+    // outer shell encloses.
+    let unenclosed_inner_shells: Vec<_> = inner
+        .into_iter()
+        .filter(|(_, volume, aabb)| {
+            !outer.iter().any(|(outer_volume, outer_aabb)| {
+                outer_volume > volume && encloses(outer_aabb, aabb)
+            })
+        })
+        .map(|(shell, _, _)| shell.clone())
+        .collect();
+
+    if !unenclosed_inner_shells.is_empty() {
+        return Err(OrientationIssues {
+            unenclosed_inner_shells,
+            ..OrientationIssues::default()
+        });
+    }
+
+    Ok(())
+}
+
+/// Choose the tolerance for approximating a shell
+///
+/// Like the default tolerance of a model, this is derived from the smallest
+/// extent of the shell. Only the sign of the shell's volume is of interest,
+/// which doesn't require much precision.
+fn tolerance_for_shell(shell: &Shell) -> Tolerance {
+    let mut points: Vec<_> = shell
+        .global_vertex_iter()
+        .map(|vertex| vertex.position())
+        .collect();
+    for curve in shell.curve_iter() {
+        if let Curve::Circle(circle) = curve {
+            points.extend(
+                [circle.a, circle.b, -circle.a, -circle.b]
+                    .map(|radius| circle.center + radius),
+            );
+        }
+    }
+
+    // Shells in triangle representation have neither vertices nor curves,
+    // and end up with the maximum tolerance. They aren't approximated, so
+    // that doesn't matter.
+    let mut min_extent = Scalar::MAX;
+    if !points.is_empty() {
+        for extent in Aabb::<3>::from_points(points).size().components {
+            if extent > Scalar::ZERO && extent < min_extent {
+                min_extent = extent;
+            }
+        }
+    }
+
+    Tolerance::from(min_extent / 1000.)
+}
+
+/// Check whether a bounding box encloses another one
+fn encloses(outer: &Option<Aabb<3>>, inner: &Option<Aabb<3>>) -> bool {
+    match (outer, inner) {
+        (Some(outer), Some(inner)) => {
+            outer.contains(inner.min) && outer.contains(inner.max)
+        }
+        _ => false,
+    }
+}
+
+/// Orientation issues found during validation
+///
+/// Used by [`ValidationError`].
+///
+/// [`ValidationError`]: super::ValidationError
+#[derive(Debug, Default, thiserror::Error)]
+pub struct OrientationIssues {
+    /// Cycles that don't have the winding that their face requires
+    ///
+    /// Each cycle comes with the winding that it should have.
+    pub wrong_windings: Vec<(Cycle, Winding)>,
+
+    /// Inner shells that aren't enclosed by any outer shell of their solid
+    ///
+    /// This includes shells that are meant to be outer shells, but whose faces
+    /// point inwards.
+    pub unenclosed_inner_shells: Vec<Shell>,
+}
+
+impl fmt::Display for OrientationIssues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Orientation issues found:")?;
+
+        if !self.wrong_windings.is_empty() {
+            writeln!(f, "- Cycles with wrong winding:")?;
+
+            for (cycle, expected) in &self.wrong_windings {
+                writeln!(f, "  - expected {expected:?}: {cycle:?}")?;
+            }
+        }
+
+        if !self.unenclosed_inner_shells.is_empty() {
+            writeln!(f, "- Inner shells that no outer shell encloses:")?;
+
+            for shell in &self.unenclosed_inner_shells {
+                writeln!(f, "  - {shell:?}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
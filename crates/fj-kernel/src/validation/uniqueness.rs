@@ -1,12 +1,12 @@
-use std::{collections::HashSet, fmt};
+use std::fmt;
 
 use fj_math::Scalar;
 
 use crate::objects::GlobalVertex;
 
-pub fn validate_vertex(
+pub fn validate_vertex<'r>(
     vertex: &GlobalVertex,
-    vertices: &HashSet<GlobalVertex>,
+    vertices: impl IntoIterator<Item = &'r GlobalVertex>,
     min_distance: Scalar,
 ) -> Result<(), UniquenessIssues> {
     for existing in vertices {
@@ -0,0 +1,351 @@
+use crate::{Circle, Point, Scalar};
+
+/// An n-dimensional arc, a section of a circle
+///
+/// The dimensionality of the arc is defined by the const generic `D`
+/// parameter.
+///
+/// The arc runs from `start` to `end`, which are circle coordinates, in the
+/// given direction. Which part of the circle it covers only depends on those
+/// coordinates modulo `2π`, and start and end coordinates that are equal in
+/// that sense define an arc that covers the whole circle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Arc<const D: usize> {
+    /// The circle that the arc is a section of
+    pub circle: Circle<D>,
+
+    /// The circle coordinate at which the arc starts
+    pub start: Scalar,
+
+    /// The circle coordinate at which the arc ends
+    pub end: Scalar,
+
+    /// The direction in which the arc runs from `start` to `end`
+    pub direction: ArcDirection,
+}
+
+impl<const D: usize> Arc<D> {
+    /// Construct an arc that covers the whole circle
+    pub fn full(circle: Circle<D>) -> Self {
+        Self {
+            circle,
+            start: Scalar::ZERO,
+            end: Scalar::PI * 2.,
+            direction: ArcDirection::Positive,
+        }
+    }
+
+    /// Construct an arc from a range of circle coordinates
+    ///
+    /// The arc runs in the direction of decreasing coordinates, if the end of
+    /// the range is smaller than its start, and of increasing coordinates
+    /// otherwise.
+    pub fn from_circle_coords(
+        circle: Circle<D>,
+        [start, end]: [impl Into<Scalar>; 2],
+    ) -> Self {
+        let [start, end] = [start.into(), end.into()];
+        let direction = if end < start {
+            ArcDirection::Negative
+        } else {
+            ArcDirection::Positive
+        };
+
+        Self {
+            circle,
+            start,
+            end,
+            direction,
+        }
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The reversed arc covers the same part of the circle, running from the
+    /// end of the original arc to its start.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self {
+            start: self.end,
+            end: self.start,
+            direction: self.direction.reverse(),
+            ..self
+        }
+    }
+
+    /// Compute the angle that the arc spans
+    ///
+    /// The result is in the range `(0, 2π]`.
+    pub fn span(&self) -> Scalar {
+        let tau = Scalar::PI * 2.;
+
+        let span = self.direction.signum() * (self.end - self.start) % tau;
+        if span <= Scalar::ZERO {
+            span + tau
+        } else {
+            span
+        }
+    }
+
+    /// Compute the length of the arc
+    pub fn length(&self) -> Scalar {
+        self.circle.a.magnitude() * self.span()
+    }
+
+    /// Access the range of circle coordinates that the arc covers
+    ///
+    /// Starts at `start`, and ends at the coordinate that is reached by going
+    /// around the circle from there, in the direction of the arc, for the
+    /// arc's span. That is equal to `end`, up to a multiple of `2π`.
+    pub fn range(&self) -> [Scalar; 2] {
+        [
+            self.start,
+            self.start + self.direction.signum() * self.span(),
+        ]
+    }
+
+    /// Create a new instance, whose start and end are normalized
+    ///
+    /// The normalized arc covers the same part of the circle, but its start
+    /// and end are in the range `[0, 2π)`.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        Self {
+            start: normalize_angle(self.start),
+            end: normalize_angle(self.end),
+            ..self
+        }
+    }
+
+    /// Check whether the arc contains the point at a circle coordinate
+    ///
+    /// The start and end of the arc count as being contained.
+    pub fn contains_angle(&self, angle: impl Into<Scalar>) -> bool {
+        let angle = angle.into();
+        self.offset_of(angle) <= self.span()
+    }
+
+    /// Compute the point on the arc at the given fraction of its span
+    ///
+    /// Returns the start of the arc for `0`, and its end for `1`.
+    pub fn point_at(&self, fraction: impl Into<Scalar>) -> Point<D> {
+        let angle = self.start
+            + self.direction.signum() * self.span() * fraction.into();
+        self.circle.point_from_circle_coords([angle])
+    }
+
+    /// Sample the arc at regular intervals
+    ///
+    /// Returns `n + 1` points that divide the arc into `n` sections of equal
+    /// length, including the start and the end of the arc.
+    pub fn sample(&self, n: usize) -> Vec<Point<D>> {
+        let n = n.max(1);
+        (0..=n)
+            .map(|i| self.point_at(Scalar::from_f64(i as f64 / n as f64)))
+            .collect()
+    }
+
+    /// Compute the multiples of `step` that are within the arc's range
+    ///
+    /// Only the multiples that are strictly in between the boundaries of the
+    /// [range](Self::range) are returned. Those very close to the boundaries
+    /// are left out too, as the points there would be almost identical to the
+    /// arc's start or end. The results are ordered from start to end.
+    ///
+    /// Since the same step results in the same multiples, this can be used to
+    /// sample neighboring arcs on the same circle in a consistent way.
+    pub fn grid_angles(&self, step: impl Into<Scalar>) -> Vec<Scalar> {
+        let step = step.into();
+
+        // The range, in units of `step`.
+        let [start, end] = self.range().map(|t| (t / step).into_f64());
+        let [min, max] = [start.min(end), start.max(end)];
+        let margin = 1e-6;
+
+        let mut steps: Vec<_> = (min.floor() as i64..=max.ceil() as i64)
+            .filter(|&i| min + margin < i as f64 && (i as f64) < max - margin)
+            .collect();
+        if start > end {
+            steps.reverse();
+        }
+
+        steps.into_iter().map(|i| step * i as f64).collect()
+    }
+
+    /// Compute the points of the arc where any coordinate is at an extreme
+    ///
+    /// Those are the points that can extend the bounding box of the arc beyond
+    /// the bounding box of its start and end. Only points that are strictly
+    /// in between start and end are returned.
+    pub fn extremes(&self) -> Vec<Point<D>> {
+        let span = self.span();
+        let mut points = Vec::new();
+
+        for i in 0..D {
+            let [a, b] =
+                [self.circle.a, self.circle.b].map(|v| v.components[i]);
+            let extreme = Scalar::atan2(b, a);
+
+            for angle in [extreme, extreme + Scalar::PI] {
+                let offset = self.offset_of(angle);
+                if offset > Scalar::ZERO && offset < span {
+                    points.push(self.circle.point_from_circle_coords([
+                        self.start + self.direction.signum() * offset,
+                    ]));
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Compute how far along the arc an angle is, from its start
+    ///
+    /// The result is in the range `[0, 2π)`.
+    fn offset_of(&self, angle: Scalar) -> Scalar {
+        normalize_angle(self.direction.signum() * (angle - self.start))
+    }
+}
+
+impl<const D: usize> From<Circle<D>> for Arc<D> {
+    fn from(circle: Circle<D>) -> Self {
+        Self::full(circle)
+    }
+}
+
+/// The direction in which an [`Arc`] runs around its circle
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ArcDirection {
+    /// The arc runs towards increasing circle coordinates
+    ///
+    /// That is the direction from the circle's `a` towards its `b`.
+    Positive,
+
+    /// The arc runs towards decreasing circle coordinates
+    Negative,
+}
+
+impl ArcDirection {
+    /// Return the opposite direction
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Positive => Self::Negative,
+            Self::Negative => Self::Positive,
+        }
+    }
+
+    /// Return `1` for the positive direction, and `-1` for the negative one
+    pub fn signum(self) -> Scalar {
+        match self {
+            Self::Positive => Scalar::ONE,
+            Self::Negative => -Scalar::ONE,
+        }
+    }
+}
+
+/// Normalize an angle into the range `[0, 2π)`
+fn normalize_angle(angle: Scalar) -> Scalar {
+    let tau = Scalar::PI * 2.;
+
+    let angle = angle % tau;
+    let angle = if angle < Scalar::ZERO {
+        angle + tau
+    } else {
+        angle
+    };
+
+    // Adding `2π` to a tiny negative angle can round to `2π` exactly.
+    if angle >= tau {
+        Scalar::ZERO
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use crate::{Circle, Point, Scalar, Vector};
+
+    use super::{Arc, ArcDirection};
+
+    fn circle() -> Circle<2> {
+        Circle {
+            center: Point::origin(),
+            a: Vector::from([1., 0.]),
+            b: Vector::from([0., 1.]),
+        }
+    }
+
+    #[test]
+    fn span_and_direction() {
+        let quarter = Arc::from_circle_coords(circle(), [0., FRAC_PI_2]);
+        assert_eq!(quarter.direction, ArcDirection::Positive);
+        assert_eq!(quarter.span(), Scalar::from(FRAC_PI_2));
+
+        // The same coordinates, in the other direction, cover the rest of the
+        // circle.
+        let rest = Arc {
+            direction: ArcDirection::Negative,
+            ..quarter
+        };
+        assert_eq!(rest.span(), Scalar::from(FRAC_PI_2 * 3.));
+        assert_eq!(rest.range(), [0., -FRAC_PI_2 * 3.].map(Scalar::from));
+
+        // Reversing an arc keeps its span.
+        let reversed = quarter.reverse();
+        assert_eq!(reversed.direction, ArcDirection::Negative);
+        assert_eq!(reversed.span(), quarter.span());
+        assert!(reversed.contains_angle(FRAC_PI_2 / 2.));
+        assert!(!reversed.contains_angle(PI));
+
+        // Coinciding start and end mean that the arc covers the whole circle.
+        let full = Arc::from_circle_coords(circle(), [1., 1.]);
+        assert_eq!(full.span(), Scalar::PI * 2.);
+        assert_eq!(Arc::full(circle()).span(), Scalar::PI * 2.);
+
+        let normalized =
+            Arc::from_circle_coords(circle(), [-PI, PI * 3.]).normalized();
+        assert_eq!(normalized.start, Scalar::PI);
+        assert_eq!(normalized.end, Scalar::PI);
+    }
+
+    #[test]
+    fn sampling() {
+        let arc = Arc::from_circle_coords(circle(), [PI, 0.]);
+
+        let points = arc.sample(2);
+        let expected = [[-1., 0.], [0., 1.], [1., 0.]].map(Point::from);
+        for (point, expected) in points.iter().zip(expected) {
+            assert!((*point - expected).magnitude() < Scalar::from(1e-12));
+        }
+
+        // Decreasing, and without the multiples at the boundaries.
+        let angles = arc.grid_angles(FRAC_PI_2 / 2.);
+        let expected = [3., 2., 1.].map(|i| Scalar::from(FRAC_PI_2 / 2. * i));
+        assert_eq!(angles, expected);
+    }
+
+    #[test]
+    fn extremes() {
+        // From the positive y axis, clockwise, to the negative x axis. This
+        // passes the maximum x and the minimum y, but not the maximum y or
+        // the minimum x, which are its start and end.
+        let arc = Arc {
+            circle: circle(),
+            start: Scalar::from(FRAC_PI_2),
+            end: Scalar::PI,
+            direction: ArcDirection::Negative,
+        };
+
+        let mut extremes = arc.extremes();
+        extremes.sort();
+        let expected = [[0., -1.], [1., 0.]].map(Point::from);
+        assert_eq!(extremes.len(), expected.len());
+        for (point, expected) in extremes.iter().zip(expected) {
+            assert!((*point - expected).magnitude() < Scalar::from(1e-12));
+        }
+    }
+}
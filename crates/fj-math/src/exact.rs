@@ -0,0 +1,254 @@
+//! Exact arithmetic, for geometric predicates that must not be rounded
+//!
+//! Floating-point arithmetic can't reliably tell whether a point is exactly on
+//! a plane, or barely off it. Code that needs to know, like the boolean
+//! operations of the kernel, can use the types and predicates in this module
+//! instead. Every `f64` is a rational number, so all computations here are
+//! exact. Only converting a result back into a [`Scalar`] rounds it.
+//!
+//! Exact arithmetic is much slower than floating-point arithmetic, which is why
+//! this module is only available with the `exact` feature.
+
+use std::{cmp::Ordering, fmt, ops};
+
+use num_rational::BigRational;
+use num_traits::{Signed as _, ToPrimitive as _, Zero as _};
+
+use crate::{Point, Scalar};
+
+/// An exact rational number
+///
+/// Can represent any [`Scalar`] exactly, as well as the results of adding,
+/// subtracting, multiplying, and dividing them.
+#[derive(Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Exact(BigRational);
+
+impl Exact {
+    /// The `Exact` instance that represents zero
+    pub fn zero() -> Self {
+        Self(BigRational::zero())
+    }
+
+    /// Determine the sign of the number
+    ///
+    /// Returns `Greater` for positive numbers, `Less` for negative ones, and
+    /// `Equal` for zero.
+    pub fn sign(&self) -> Ordering {
+        if self.0.is_positive() {
+            Ordering::Greater
+        } else if self.0.is_negative() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Convert the number into the nearest [`Scalar`]
+    pub fn to_scalar(&self) -> Scalar {
+        let value = self
+            .0
+            .to_f64()
+            .expect("Converting rational number to `f64` can't fail");
+        Scalar::from_f64(value)
+    }
+}
+
+impl From<Scalar> for Exact {
+    fn from(scalar: Scalar) -> Self {
+        let rational = BigRational::from_float(scalar.into_f64())
+            .expect("`Scalar` is never NaN or infinite");
+        Self(rational)
+    }
+}
+
+impl From<f64> for Exact {
+    fn from(value: f64) -> Self {
+        Self::from(Scalar::from_f64(value))
+    }
+}
+
+impl ops::Neg for Exact {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl ops::Add for Exact {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl ops::Sub for Exact {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl ops::Mul for Exact {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl ops::Div for Exact {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics, if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        Self(self.0 / rhs.0)
+    }
+}
+
+impl fmt::Debug for Exact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (~{:?})", self.0, self.to_scalar())
+    }
+}
+
+/// Compute on which side of the line through `a` and `b` the point `c` is
+///
+/// Returns `Greater`, if `c` is left of the line, `Less`, if it is right of it,
+/// and `Equal`, if it is exactly on the line.
+pub fn orient2d(a: Point<2>, b: Point<2>, c: Point<2>) -> Ordering {
+    let [a, b, c] = [a, b, c].map(exact_point);
+    let [ab, ac] = [sub(&b, &a), sub(&c, &a)];
+
+    let [abu, abv] = ab;
+    let [acu, acv] = ac;
+    (abu * acv - abv * acu).sign()
+}
+
+/// Compute the signed volume of the tetrahedron `a`, `b`, `c`, `d`, times six
+///
+/// The result is positive, if `d` is on the side of the plane through `a`,
+/// `b`, and `c` that the plane's normal (`(b - a) × (c - a)`) points to,
+/// negative, if it is on the other side, and zero, if it is exactly on the
+/// plane. It is also proportional to the distance of `d` from that plane.
+pub fn orient3d([a, b, c]: [Point<3>; 3], d: Point<3>) -> Exact {
+    let [a, b, c, d] = [a, b, c, d].map(exact_point);
+    let [ab, ac, ad] = [sub(&b, &a), sub(&c, &a), sub(&d, &a)];
+
+    let [abx, aby, abz] = ab;
+    let [acx, acy, acz] = ac;
+    let [adx, ady, adz] = ad;
+
+    let normal = [
+        aby.clone() * acz.clone() - abz.clone() * acy.clone(),
+        abz * acx.clone() - abx.clone() * acz,
+        abx * acy - aby * acx,
+    ];
+
+    let [nx, ny, nz] = normal;
+    nx * adx + ny * ady + nz * adz
+}
+
+/// Compute the point where a segment crosses a plane
+///
+/// The plane is defined by three points on it. Returns `None`, unless the end
+/// points of the segment are strictly on opposite sides of the plane. The
+/// crossing is computed exactly and only rounded at the end, so it doesn't
+/// depend on the order of the segment's points.
+pub fn segment_plane_crossing(
+    plane: [Point<3>; 3],
+    [p, q]: [Point<3>; 2],
+) -> Option<Point<3>> {
+    let [dp, dq] = [p, q].map(|point| orient3d(plane, point));
+
+    let crosses = dp.sign() != Ordering::Equal
+        && dq.sign() != Ordering::Equal
+        && dp.sign() != dq.sign();
+    if !crosses {
+        return None;
+    }
+
+    let t = dp.clone() / (dp - dq);
+
+    let [p, q] = [p, q].map(exact_point);
+    let mut point = [Scalar::ZERO; 3];
+    for ((coord, p), q) in point.iter_mut().zip(p).zip(q) {
+        *coord = (p.clone() + (q - p) * t.clone()).to_scalar();
+    }
+
+    Some(Point::from(point))
+}
+
+fn exact_point<const D: usize>(point: Point<D>) -> [Exact; D] {
+    point.coords.components.map(Exact::from)
+}
+
+fn sub<const D: usize>(a: &[Exact; D], b: &[Exact; D]) -> [Exact; D] {
+    let mut result = a.clone();
+    for (result, b) in result.iter_mut().zip(b) {
+        *result = result.clone() - b.clone();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{Point, Scalar};
+
+    use super::{orient2d, orient3d, segment_plane_crossing, Exact};
+
+    #[test]
+    fn arithmetic() {
+        let third = Exact::from(1.) / Exact::from(3.);
+        let one = third.clone() + third.clone() + third;
+
+        assert_eq!(one, Exact::from(1.));
+        assert_eq!(one.to_scalar(), Scalar::ONE);
+    }
+
+    #[test]
+    fn orient2d_near_degenerate() {
+        // These points are not collinear, but only barely.
+        let a = Point::from([0.5, 0.5]);
+        let b = Point::from([12., 12.]);
+        let c = Point::from([24., 24.000000000000004]);
+
+        assert_eq!(orient2d(a, b, c), Ordering::Greater);
+        assert_eq!(orient2d(a, c, b), Ordering::Less);
+        assert_eq!(orient2d(a, b, Point::from([3., 3.])), Ordering::Equal);
+    }
+
+    #[test]
+    fn orient3d_and_crossing() {
+        let plane = [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from);
+
+        assert_eq!(
+            orient3d(plane, Point::from([0.3, 0.3, 1e-300])).sign(),
+            Ordering::Greater,
+        );
+        assert_eq!(
+            orient3d(plane, Point::from([5., 7., 0.])).sign(),
+            Ordering::Equal,
+        );
+
+        let segment = [[0.5, 0.25, -1.], [1.5, 1.25, 3.]].map(Point::from);
+        let crossing = segment_plane_crossing(plane, segment).unwrap();
+        assert_eq!(crossing, Point::from([0.75, 0.5, 0.]));
+
+        let [p, q] = segment;
+        assert_eq!(segment_plane_crossing(plane, [q, p]), Some(crossing));
+        assert_eq!(
+            segment_plane_crossing(
+                plane,
+                [[0., 0., 1.], [1., 1., 2.]].map(Point::from)
+            ),
+            None,
+        );
+    }
+}
@@ -0,0 +1,447 @@
+use std::{fmt, ops};
+
+use crate::Scalar;
+
+/// A closed interval of scalars
+///
+/// Interval arithmetic computes guaranteed bounds: The result of an operation
+/// on intervals contains the results of the operation on all values in those
+/// intervals. Results are rounded outwards, so this holds despite rounding
+/// errors. This makes it possible to decide questions like "is this value
+/// zero?" reliably, or to know that it can't be decided at the available
+/// precision.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Interval {
+    min: Scalar,
+    max: Scalar,
+}
+
+impl Interval {
+    /// Construct an interval from its bounds
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `min` is larger than `max`.
+    pub fn from_bounds(min: impl Into<Scalar>, max: impl Into<Scalar>) -> Self {
+        let [min, max] = [min.into(), max.into()];
+        assert!(min <= max, "Invalid interval: [{min:?}, {max:?}]");
+
+        Self { min, max }
+    }
+
+    /// Construct an interval that contains a single value
+    pub fn from_value(value: impl Into<Scalar>) -> Self {
+        let value = value.into();
+        Self {
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Access the lower bound of the interval
+    pub fn min(&self) -> Scalar {
+        self.min
+    }
+
+    /// Access the upper bound of the interval
+    pub fn max(&self) -> Scalar {
+        self.max
+    }
+
+    /// Compute the width of the interval
+    pub fn width(&self) -> Scalar {
+        self.max - self.min
+    }
+
+    /// Compute the midpoint of the interval
+    pub fn midpoint(&self) -> Scalar {
+        self.min + (self.max - self.min) / 2.
+    }
+
+    /// Check whether the interval contains a value
+    pub fn contains(&self, value: impl Into<Scalar>) -> bool {
+        let value = value.into();
+        self.min <= value && value <= self.max
+    }
+
+    /// Check whether the interval contains zero
+    ///
+    /// If it doesn't, the sign of the value it bounds is known for sure.
+    pub fn contains_zero(&self) -> bool {
+        self.contains(Scalar::ZERO)
+    }
+
+    /// Compute the smallest interval that contains this and another one
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Compute the intersection of this and another interval
+    ///
+    /// Returns `None`, if the intervals don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+
+        if min <= max {
+            Some(Self { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Split the interval in half
+    pub fn bisect(&self) -> [Self; 2] {
+        let mid = self.midpoint();
+        [
+            Self {
+                min: self.min,
+                max: mid,
+            },
+            Self {
+                min: mid,
+                max: self.max,
+            },
+        ]
+    }
+
+    /// Compute the square of the interval
+    ///
+    /// This is tighter than multiplying the interval with itself, as the result
+    /// can't be negative.
+    pub fn square(self) -> Self {
+        let [a, b] = [self.min, self.max].map(|bound| bound.abs());
+        let [lo, hi] = [a.min(b), a.max(b)];
+
+        let min = if self.contains_zero() {
+            Scalar::ZERO
+        } else {
+            next_down(lo * lo).max(Scalar::ZERO)
+        };
+
+        Self {
+            min,
+            max: next_up(hi * hi),
+        }
+    }
+
+    /// Compute the square root of the interval
+    ///
+    /// Negative values are ignored, as their square root is not defined.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the interval only contains negative values.
+    pub fn sqrt(self) -> Self {
+        assert!(
+            self.max >= Scalar::ZERO,
+            "Square root of negative interval: {self:?}"
+        );
+
+        let [min, max] = [self.min.max(Scalar::ZERO), self.max]
+            .map(|bound| bound.into_f64().sqrt());
+
+        Self {
+            min: next_down(Scalar::from_f64(min)).max(Scalar::ZERO),
+            max: next_up(Scalar::from_f64(max)),
+        }
+    }
+
+    /// Compute the sine of the interval
+    pub fn sin(self) -> Self {
+        // sin(x) = cos(x - π/2)
+        (self - Self::from_value(Scalar::PI / 2.)).cos()
+    }
+
+    /// Compute the cosine of the interval
+    pub fn cos(self) -> Self {
+        let tau = Scalar::PI * 2.;
+
+        if self.width() >= tau {
+            return Self::from_bounds(-1., 1.);
+        }
+
+        let [a, b] = [self.min, self.max].map(|bound| bound.cos());
+        let mut min = a.min(b);
+        let mut max = a.max(b);
+
+        // The extremes of the cosine are at multiples of π. Check whether the
+        // interval contains any of them. It is widened for this check, to make
+        // sure rounding errors in the computation of the multiples don't cause
+        // an extreme to be missed.
+        let widened = Self::from_bounds(next_down(self.min), next_up(self.max));
+        let [first, last] = [self.min, self.max]
+            .map(|bound| (bound / Scalar::PI).into_f64().floor() as i64);
+        for k in first..=last + 1 {
+            if !widened.contains(Scalar::PI * (k as f64)) {
+                continue;
+            }
+
+            if k % 2 == 0 {
+                max = Scalar::ONE;
+            } else {
+                min = -Scalar::ONE;
+            }
+        }
+
+        Self {
+            min: next_down(min).max(-Scalar::ONE),
+            max: next_up(max).min(Scalar::ONE),
+        }
+    }
+
+    /// Find the roots of a function within the interval
+    ///
+    /// `f` must compute guaranteed bounds of the function over an interval,
+    /// which is what implementing it in terms of interval arithmetic achieves.
+    /// The interval is bisected repeatedly, discarding any parts where the
+    /// function is provably not zero.
+    ///
+    /// Returns intervals no wider than `min_width` (or that can't be bisected
+    /// further), whose union contains all roots of the function. Adjacent
+    /// intervals can contain the same root, and an interval might not contain
+    /// a root at all, if `f` overestimates the range of the function too much.
+    pub fn isolate_roots(
+        self,
+        f: impl Fn(Self) -> Self,
+        min_width: impl Into<Scalar>,
+    ) -> Vec<Self> {
+        let min_width = min_width.into();
+
+        let mut roots = Vec::new();
+        let mut queue = vec![self];
+
+        while let Some(interval) = queue.pop() {
+            if !f(interval).contains_zero() {
+                continue;
+            }
+
+            let [a, b] = interval.bisect();
+            let is_indivisible = a == interval || b == interval;
+
+            if interval.width() <= min_width || is_indivisible {
+                roots.push(interval);
+                continue;
+            }
+
+            // The second half is pushed first, so the first is processed
+            // first, and the roots come out in order.
+            queue.push(b);
+            queue.push(a);
+        }
+
+        roots
+    }
+}
+
+impl From<Scalar> for Interval {
+    fn from(value: Scalar) -> Self {
+        Self::from_value(value)
+    }
+}
+
+impl From<f64> for Interval {
+    fn from(value: f64) -> Self {
+        Self::from_value(value)
+    }
+}
+
+impl ops::Neg for Interval {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            min: -self.max,
+            max: -self.min,
+        }
+    }
+}
+
+impl<I> ops::Add<I> for Interval
+where
+    I: Into<Self>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: I) -> Self::Output {
+        let rhs = rhs.into();
+        Self {
+            min: next_down(self.min + rhs.min),
+            max: next_up(self.max + rhs.max),
+        }
+    }
+}
+
+impl<I> ops::Sub<I> for Interval
+where
+    I: Into<Self>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: I) -> Self::Output {
+        self + -rhs.into()
+    }
+}
+
+impl<I> ops::Mul<I> for Interval
+where
+    I: Into<Self>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: I) -> Self::Output {
+        let rhs = rhs.into();
+
+        let products = [
+            self.min * rhs.min,
+            self.min * rhs.max,
+            self.max * rhs.min,
+            self.max * rhs.max,
+        ];
+
+        let min = products.into_iter().min().expect("Products not empty");
+        let max = products.into_iter().max().expect("Products not empty");
+
+        Self {
+            min: next_down(min),
+            max: next_up(max),
+        }
+    }
+}
+
+impl<I> ops::Div<I> for Interval
+where
+    I: Into<Self>,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics, if `rhs` contains zero.
+    fn div(self, rhs: I) -> Self::Output {
+        let rhs = rhs.into();
+        assert!(!rhs.contains_zero(), "Division by interval containing zero");
+
+        let quotients = [
+            self.min / rhs.min,
+            self.min / rhs.max,
+            self.max / rhs.min,
+            self.max / rhs.max,
+        ];
+
+        let min = quotients.into_iter().min().expect("Quotients not empty");
+        let max = quotients.into_iter().max().expect("Quotients not empty");
+
+        Self {
+            min: next_down(min),
+            max: next_up(max),
+        }
+    }
+}
+
+impl fmt::Debug for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{:?}, {:?}]", self.min, self.max)
+    }
+}
+
+/// Compute the next larger representable value
+fn next_up(value: Scalar) -> Scalar {
+    let value = value.into_f64();
+
+    let next = if value == f64::INFINITY {
+        value
+    } else if value == 0. {
+        // Covers negative zero too.
+        f64::from_bits(1)
+    } else if value > 0. {
+        f64::from_bits(value.to_bits() + 1)
+    } else {
+        f64::from_bits(value.to_bits() - 1)
+    };
+
+    Scalar::from_f64(next)
+}
+
+/// Compute the next smaller representable value
+fn next_down(value: Scalar) -> Scalar {
+    -next_up(-value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scalar;
+
+    use super::Interval;
+
+    #[test]
+    fn arithmetic() {
+        let a = Interval::from_bounds(1., 2.);
+        let b = Interval::from_bounds(-3., 4.);
+
+        let sum = a + b;
+        assert!(sum.contains(-2.) && sum.contains(6.));
+
+        let product = a * b;
+        assert!(product.contains(-6.) && product.contains(8.));
+        assert!(!product.contains(-7.) && !product.contains(9.));
+
+        let quotient = b / a;
+        assert!(quotient.contains(-3.) && quotient.contains(4.));
+
+        // 0.1 + 0.2 is not 0.3 in floating-point arithmetic, but the interval
+        // still contains the exact result.
+        let sum = Interval::from(0.1) + 0.2;
+        assert!(sum.contains(0.1 + 0.2));
+        assert!(sum.width() > Scalar::ZERO);
+    }
+
+    #[test]
+    fn square_and_sqrt() {
+        let square = Interval::from_bounds(-2., 1.).square();
+        assert_eq!(square.min(), Scalar::ZERO);
+        assert!(square.contains(4.));
+
+        let sqrt = Interval::from_bounds(4., 9.).sqrt();
+        assert!(sqrt.contains(2.) && sqrt.contains(3.));
+        assert!(!sqrt.contains(1.9) && !sqrt.contains(3.1));
+    }
+
+    #[test]
+    fn cos_and_sin() {
+        let cos = Interval::from_bounds(-0.5, 4.).cos();
+        assert_eq!(cos.max(), Scalar::ONE);
+        assert_eq!(cos.min(), -Scalar::ONE);
+
+        let cos = Interval::from_bounds(0.5, 1.).cos();
+        assert!(cos.contains(0.5_f64.cos()) && cos.contains(1_f64.cos()));
+        assert!(cos.max() < Scalar::from(0.9));
+
+        let sin = Interval::from_bounds(0., 1.).sin();
+        assert!(sin.contains(0.) && sin.contains(1_f64.sin()));
+        assert!(sin.max() < Scalar::from(0.85));
+    }
+
+    #[test]
+    fn isolate_roots() {
+        // x² - 2 has its roots at ±√2.
+        let roots = Interval::from_bounds(-4., 4.)
+            .isolate_roots(|x| x.square() - 2., 1e-9);
+
+        let sqrt_2 = Scalar::from(2_f64.sqrt());
+        assert!(roots.iter().any(|root| root.contains(-sqrt_2)));
+        assert!(roots.iter().any(|root| root.contains(sqrt_2)));
+        assert!(roots.iter().all(|root| {
+            let distance = (root.midpoint().abs() - sqrt_2).abs();
+            root.width() <= Scalar::from(1e-9) && distance < Scalar::from(1e-8)
+        }));
+
+        // cos has no roots in this interval.
+        let roots =
+            Interval::from_bounds(-1., 1.).isolate_roots(Interval::cos, 1e-9);
+        assert!(roots.is_empty());
+    }
+}
@@ -35,28 +35,41 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "exact")]
+pub mod exact;
+
 mod aabb;
+mod arc;
 mod circle;
 mod coordinates;
+mod interval;
 mod line;
+mod obb;
 mod point;
 mod poly_chain;
+mod polygon;
 mod scalar;
 mod segment;
+mod sphere;
 mod transform;
 mod triangle;
 mod vector;
 
 pub use self::{
     aabb::Aabb,
+    arc::{Arc, ArcDirection},
     circle::Circle,
     coordinates::{Uv, Xyz, T},
+    interval::Interval,
     line::Line,
+    obb::Obb,
     point::Point,
     poly_chain::PolyChain,
+    polygon::Polygon,
     scalar::Scalar,
     segment::Segment,
-    transform::Transform,
+    sphere::Sphere,
+    transform::{Decomposition, Transform},
     triangle::{Triangle, Winding},
     vector::Vector,
 };
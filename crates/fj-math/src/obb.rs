@@ -0,0 +1,235 @@
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// An oriented bounding box (OBB)
+///
+/// Unlike an [`Aabb`], the edges of an OBB don't need to be parallel to the
+/// coordinate axes. This allows it to fit much tighter around shapes that are
+/// rotated relative to the coordinate system.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Obb {
+    /// The center point of the OBB
+    pub center: Point<3>,
+
+    /// The axes of the OBB
+    ///
+    /// The axes are normalized, perpendicular to each other, and form a
+    /// right-handed coordinate system.
+    pub axes: [Vector<3>; 3],
+
+    /// The distances from the center to the faces of the OBB, along each axis
+    pub half_extents: Vector<3>,
+}
+
+impl Obb {
+    /// Construct an OBB from a list of points
+    ///
+    /// The axes of the OBB are the principal axes of the points, unless the
+    /// axis-aligned bounding box of the points is smaller. The resulting OBB
+    /// contains all the points, but is not guaranteed to be the smallest
+    /// possible one.
+    ///
+    /// Returns `None`, if no points are provided.
+    pub fn from_points(
+        points: impl IntoIterator<Item = impl Into<Point<3>>>,
+    ) -> Option<Self> {
+        Self::from_points_and_axes(points, [])
+    }
+
+    /// Construct an OBB from a list of points, considering additional axes
+    ///
+    /// Works like [`Obb::from_points`], but also considers the provided sets
+    /// of axes, and picks the smallest of the resulting OBBs. This is useful,
+    /// if the caller knows about directions that are likely to result in a
+    /// tight fit, like the normals of planar faces. Principal axes are not
+    /// reliable for symmetric shapes, like a cube.
+    ///
+    /// Each set of axes must be normalized and perpendicular to each other.
+    ///
+    /// Returns `None`, if no points are provided.
+    pub fn from_points_and_axes(
+        points: impl IntoIterator<Item = impl Into<Point<3>>>,
+        candidates: impl IntoIterator<Item = [Vector<3>; 3]>,
+    ) -> Option<Self> {
+        let points: Vec<_> = points.into_iter().map(Into::into).collect();
+        if points.is_empty() {
+            return None;
+        }
+
+        let aligned = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()];
+
+        [aligned, principal_axes(&points)]
+            .into_iter()
+            .chain(candidates)
+            .map(|axes| Self::from_axes(axes, &points))
+            .min_by_key(Self::volume)
+    }
+
+    /// Construct the OBB with the given axes that contains all points
+    ///
+    /// The axes must be normalized and perpendicular to each other.
+    pub fn from_axes(axes: [Vector<3>; 3], points: &[Point<3>]) -> Self {
+        let mut min = [Scalar::MAX; 3];
+        let mut max = [-Scalar::MAX; 3];
+
+        for point in points {
+            for (i, axis) in axes.iter().enumerate() {
+                let projection = point.coords.dot(axis);
+
+                if projection < min[i] {
+                    min[i] = projection;
+                }
+                if projection > max[i] {
+                    max[i] = projection;
+                }
+            }
+        }
+
+        let mut center = Point::origin();
+        let mut half_extents = [Scalar::ZERO; 3];
+        for (i, axis) in axes.iter().enumerate() {
+            center = center + *axis * ((min[i] + max[i]) / 2.);
+            half_extents[i] = (max[i] - min[i]) / 2.;
+        }
+
+        Self {
+            center,
+            axes,
+            half_extents: Vector::from(half_extents),
+        }
+    }
+
+    /// Construct an OBB from an AABB
+    pub fn from_aabb(aabb: &Aabb<3>) -> Self {
+        Self {
+            center: aabb.center(),
+            axes: [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()],
+            half_extents: aabb.size() / 2.,
+        }
+    }
+
+    /// Determine whether the OBB contains a given point
+    pub fn contains(&self, point: impl Into<Point<3>>) -> bool {
+        let offset = point.into() - self.center;
+
+        self.axes
+            .iter()
+            .zip(self.half_extents.components)
+            .all(|(axis, half_extent)| offset.dot(axis).abs() <= half_extent)
+    }
+
+    /// Access the vertices of the OBB
+    pub fn vertices(&self) -> [Point<3>; 8] {
+        let [x, y, z] = self.axes;
+        let [hx, hy, hz] = self.half_extents.components;
+
+        [
+            [-1., -1., -1.],
+            [1., -1., -1.],
+            [1., 1., -1.],
+            [-1., 1., -1.],
+            [-1., -1., 1.],
+            [1., -1., 1.],
+            [1., 1., 1.],
+            [-1., 1., 1.],
+        ]
+        .map(|[sx, sy, sz]| {
+            self.center + x * (hx * sx) + y * (hy * sy) + z * (hz * sz)
+        })
+    }
+
+    /// Compute the volume of the OBB
+    pub fn volume(&self) -> Scalar {
+        let [hx, hy, hz] = self.half_extents.components;
+        hx * hy * hz * 8.
+    }
+
+    /// Compute the AABB that contains this OBB
+    pub fn aabb(&self) -> Aabb<3> {
+        Aabb::<3>::from_points(self.vertices())
+    }
+}
+
+/// Compute the principal axes of a list of points
+///
+/// These are the eigenvectors of the points' covariance matrix.
+fn principal_axes(points: &[Point<3>]) -> [Vector<3>; 3] {
+    let mean = points
+        .iter()
+        .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point.coords)
+        / points.len() as f64;
+
+    let mut covariance = nalgebra::Matrix3::<f64>::zeros();
+    for point in points {
+        let offset = (point.coords - mean).to_na();
+        covariance += offset * offset.transpose();
+    }
+
+    let eigen = covariance.symmetric_eigen();
+    let [x, y] =
+        [0, 1].map(|i| Vector::from(eigen.eigenvectors.column(i).into_owned()));
+
+    // Make sure the axes form a right-handed coordinate system.
+    [x, y, x.cross(&y)]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb, Point, Scalar, Transform, Vector};
+
+    use super::Obb;
+
+    #[test]
+    fn from_points() {
+        assert!(Obb::from_points(Vec::<Point<3>>::new()).is_none());
+
+        // The vertices of a box, rotated around the z axis. The AABB of those
+        // is much larger than the box itself.
+        let transform = Transform::rotation(Vector::unit_z() * 0.5);
+        let points = Aabb {
+            min: Point::from([-4., -1., -0.5]),
+            max: Point::from([4., 1., 0.5]),
+        }
+        .vertices()
+        .map(|point| transform.transform_point(&point));
+
+        let obb = Obb::from_points(points).unwrap();
+        assert!((obb.volume() - Scalar::from(16.)).abs() < Scalar::from(1e-9));
+        for point in points {
+            // Allow for floating-point noise.
+            assert!(obb.contains(point + (obb.center - point) * 1e-9));
+        }
+
+        let aabb = Aabb::<3>::from_points(points);
+        assert!(obb.volume() < Obb::from_aabb(&aabb).volume());
+    }
+
+    #[test]
+    fn from_points_and_axes() {
+        // The principal axes of a cube's vertices are arbitrary.
+        let transform = Transform::rotation(Vector::unit_z() * 0.5);
+        let axes = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .map(|axis| transform.transform_vector(&axis));
+        let points = Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        }
+        .vertices()
+        .map(|point| transform.transform_point(&point));
+
+        let obb = Obb::from_points_and_axes(points, [axes]).unwrap();
+        assert!((obb.volume() - Scalar::from(8.)).abs() < Scalar::from(1e-9));
+    }
+
+    #[test]
+    fn contains() {
+        let obb = Obb::from_aabb(&Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        });
+
+        assert!(obb.contains([0., 0., 0.]));
+        assert!(obb.contains([1., 1., 1.]));
+        assert!(!obb.contains([1.5, 0., 0.]));
+        assert!(!obb.contains([0., 0., -1.5]));
+    }
+}
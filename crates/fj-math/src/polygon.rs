@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+
+use crate::{Point, Scalar, Winding};
+
+/// A polygon in 2D space
+///
+/// The polygon is implicitly closed: Its last point connects to its first one.
+/// Nothing guarantees that the polygon is actually simple. Use
+/// [`Polygon::self_intersection`] to check that.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Polygon {
+    points: Vec<Point<2>>,
+}
+
+impl Polygon {
+    /// Construct a polygon from a number of points
+    ///
+    /// The points must not repeat the first point at the end.
+    pub fn from_points(
+        points: impl IntoIterator<Item = impl Into<Point<2>>>,
+    ) -> Self {
+        let points = points.into_iter().map(Into::into).collect();
+        Self { points }
+    }
+
+    /// Access the polygon's points
+    pub fn points(&self) -> &[Point<2>] {
+        &self.points
+    }
+
+    /// Access the edges of the polygon, as pairs of points
+    ///
+    /// Edge `i` runs from point `i` to the next point, and the last edge back
+    /// to the first point. Edges are not returned as [`Segment`]s, as they can
+    /// be degenerate, if the polygon contains the same point twice in a row.
+    ///
+    /// [`Segment`]: crate::Segment
+    pub fn edges(&self) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+        let next = self.points.iter().cycle().skip(1);
+        self.points.iter().zip(next).map(|(&a, &b)| [a, b])
+    }
+
+    /// Compute the signed area of the polygon
+    ///
+    /// The area is positive, if the polygon winds counter-clockwise, negative,
+    /// if it winds clockwise. The result is meaningless, if the polygon
+    /// intersects itself.
+    pub fn signed_area(&self) -> Scalar {
+        let doubled_area = self
+            .edges()
+            .map(|[a, b]| a.u * b.v - a.v * b.u)
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+
+        doubled_area / 2.
+    }
+
+    /// Determine the winding of the polygon
+    ///
+    /// Returns `None`, if the polygon doesn't span any area.
+    pub fn winding(&self) -> Option<Winding> {
+        let area = self.signed_area();
+
+        if area > Scalar::ZERO {
+            Some(Winding::Ccw)
+        } else if area < Scalar::ZERO {
+            Some(Winding::Cw)
+        } else {
+            None
+        }
+    }
+
+    /// Return a polygon with the given winding
+    ///
+    /// Reverses the order of the points, if the polygon winds in the opposite
+    /// direction. A polygon that doesn't span any area is returned unchanged.
+    pub fn with_winding(mut self, winding: Winding) -> Self {
+        match self.winding() {
+            Some(current) if current != winding => {
+                self.points.reverse();
+                self
+            }
+            _ => self,
+        }
+    }
+
+    /// Check whether the polygon contains the point
+    ///
+    /// Points on the boundary of the polygon are considered to be contained.
+    pub fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
+        let point = point.into();
+
+        let mut inside = false;
+
+        for [a, b] in self.edges() {
+            if is_on_segment(point, [a, b]) {
+                return true;
+            }
+
+            // Cast a ray from the point in the positive u direction, and count
+            // how many edges it crosses. Each edge includes its lower point,
+            // but not its upper one, so a ray through a vertex is counted
+            // correctly.
+            if (a.v > point.v) != (b.v > point.v) {
+                let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+                if point.u < u {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+
+    /// Find a pair of edges that intersect each other
+    ///
+    /// Returns the indices of the first pair of intersecting edges (see
+    /// [`Polygon::edges`]), or `None`, if the polygon is simple. Adjacent
+    /// edges are only considered intersecting, if they overlap beyond the
+    /// point they share. A polygon with fewer than three points always
+    /// intersects itself.
+    pub fn self_intersection(&self) -> Option<[usize; 2]> {
+        let edges: Vec<_> = self.edges().collect();
+        let n = edges.len();
+
+        if n < 3 {
+            return Some([0, n.saturating_sub(1)]);
+        }
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let [a, b] = edges[i];
+                let [c, d] = edges[j];
+
+                let intersects = if j == i + 1 {
+                    // Edge `i` ends where edge `j` starts.
+                    is_on_segment(d, [a, b]) || is_on_segment(a, [c, d])
+                } else if i == 0 && j == n - 1 {
+                    // Edge `j` ends where edge `i` starts.
+                    is_on_segment(c, [a, b]) || is_on_segment(b, [c, d])
+                } else {
+                    segments_intersect([a, b], [c, d])
+                };
+
+                if intersects {
+                    return Some([i, j]);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<P, Ps> From<Ps> for Polygon
+where
+    P: Into<Point<2>>,
+    Ps: IntoIterator<Item = P>,
+{
+    fn from(points: Ps) -> Self {
+        Self::from_points(points)
+    }
+}
+
+/// Compute on which side of the line through `a` and `b` the point `c` is
+///
+/// Returns `Greater`, if `c` is left of the line, `Less`, if it is right of it,
+/// and `Equal`, if it is on the line.
+fn orientation(a: Point<2>, b: Point<2>, c: Point<2>) -> Ordering {
+    let cross = (b.u - a.u) * (c.v - a.v) - (b.v - a.v) * (c.u - a.u);
+    cross.cmp(&Scalar::ZERO)
+}
+
+fn is_on_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> bool {
+    orientation(a, b, point) == Ordering::Equal
+        && point.u >= a.u.min(b.u)
+        && point.u <= a.u.max(b.u)
+        && point.v >= a.v.min(b.v)
+        && point.v <= a.v.max(b.v)
+}
+
+fn segments_intersect([a, b]: [Point<2>; 2], [c, d]: [Point<2>; 2]) -> bool {
+    // The segments properly cross each other, if the end points of each lie on
+    // different sides of the other.
+    let crosses = orientation(a, b, c) != orientation(a, b, d)
+        && orientation(c, d, a) != orientation(c, d, b);
+
+    // Otherwise, they can still touch, or be collinear and overlap.
+    crosses
+        || is_on_segment(c, [a, b])
+        || is_on_segment(d, [a, b])
+        || is_on_segment(a, [c, d])
+        || is_on_segment(b, [c, d])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Scalar, Winding};
+
+    use super::Polygon;
+
+    #[test]
+    fn signed_area_and_winding() {
+        let ccw =
+            Polygon::from_points([[0., 0.], [2., 0.], [2., 1.], [0., 1.]]);
+        assert_eq!(ccw.signed_area(), Scalar::from(2.));
+        assert_eq!(ccw.winding(), Some(Winding::Ccw));
+
+        let cw = ccw.clone().with_winding(Winding::Cw);
+        assert_eq!(cw.signed_area(), Scalar::from(-2.));
+        assert_eq!(cw.winding(), Some(Winding::Cw));
+        assert_eq!(cw.with_winding(Winding::Ccw), ccw);
+
+        let degenerate = Polygon::from_points([[0., 0.], [1., 0.], [2., 0.]]);
+        assert_eq!(degenerate.winding(), None);
+    }
+
+    #[test]
+    fn contains_point() {
+        // A U-shape, opening upwards
+        let polygon = Polygon::from_points([
+            [0., 0.],
+            [3., 0.],
+            [3., 2.],
+            [2., 2.],
+            [2., 1.],
+            [1., 1.],
+            [1., 2.],
+            [0., 2.],
+        ]);
+
+        assert!(polygon.contains_point([0.5, 1.5]));
+        assert!(polygon.contains_point([1.5, 0.5]));
+        assert!(!polygon.contains_point([1.5, 1.5]));
+        assert!(!polygon.contains_point([4., 1.]));
+
+        // The ray from this point passes through vertices.
+        assert!(polygon.contains_point([0.5, 1.]));
+        assert!(!polygon.contains_point([-1., 2.]));
+
+        // Points on the boundary
+        assert!(polygon.contains_point([3., 1.]));
+        assert!(polygon.contains_point(Point::from([1., 1.])));
+    }
+
+    #[test]
+    fn self_intersection() {
+        let square =
+            Polygon::from_points([[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+        assert_eq!(square.self_intersection(), None);
+
+        let bowtie =
+            Polygon::from_points([[0., 0.], [1., 1.], [1., 0.], [0., 1.]]);
+        assert_eq!(bowtie.self_intersection(), Some([0, 2]));
+
+        let touching =
+            Polygon::from_points([[0., 0.], [2., 0.], [1., 0.], [1., 1.]]);
+        assert_eq!(touching.self_intersection(), Some([0, 1]));
+
+        let collinear = Polygon::from_points([[0., 0.], [1., 0.], [2., 0.]]);
+        assert!(collinear.self_intersection().is_some());
+
+        let too_few = Polygon::from_points([[0., 0.], [1., 0.]]);
+        assert!(too_few.self_intersection().is_some());
+    }
+}
@@ -92,6 +92,11 @@ impl Scalar {
         (sin.into(), cos.into())
     }
 
+    /// Compute the tangent
+    pub fn tan(self) -> Self {
+        self.0.tan().into()
+    }
+
     /// Compute the arccosine
     pub fn acos(self) -> Self {
         self.0.acos().into()
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use parry3d_f64::query::PointQuery as _;
+
 use crate::Scalar;
 
 use super::Point;
@@ -59,6 +61,66 @@ impl Segment<3> {
     pub fn to_parry(self) -> parry3d_f64::shape::Segment {
         self.points.map(|point| point.to_na()).into()
     }
+
+    /// Compute the distance between the segment and a point
+    pub fn distance_to_point(&self, point: impl Into<Point<3>>) -> Scalar {
+        self.to_parry()
+            .distance_to_local_point(&point.into().to_na(), true)
+            .into()
+    }
+
+    /// Compute the closest points between this segment and another
+    ///
+    /// Returns the point on this segment first, the point on the other one
+    /// second. If the segments are parallel, there are infinitely many pairs
+    /// of closest points, and one of them is returned.
+    pub fn closest_points(&self, other: &Self) -> [Point<3>; 2] {
+        let [p1, q1] = self.points;
+        let [p2, q2] = other.points;
+
+        let d1 = q1 - p1;
+        let d2 = q2 - p2;
+        let r = p1 - p2;
+
+        let a = d1.dot(&d1);
+        let e = d2.dot(&d2);
+        let f = d2.dot(&r);
+        let c = d1.dot(&r);
+        let b = d1.dot(&d2);
+
+        let clamp = |t: Scalar| {
+            if t < Scalar::ZERO {
+                Scalar::ZERO
+            } else if t > Scalar::ONE {
+                Scalar::ONE
+            } else {
+                t
+            }
+        };
+
+        // Segments are never degenerate, so `a` and `e` are positive. For
+        // parallel segments, `denominator` is zero, and we can pick any point
+        // on this segment, then find the closest point on the other.
+        let denominator = a * e - b * b;
+        let s = if denominator > Scalar::ZERO {
+            clamp((b * f - c * e) / denominator)
+        } else {
+            Scalar::ZERO
+        };
+
+        let mut t = (b * s + f) / e;
+        let s = if t < Scalar::ZERO {
+            t = Scalar::ZERO;
+            clamp(-c / a)
+        } else if t > Scalar::ONE {
+            t = Scalar::ONE;
+            clamp((b - c) / a)
+        } else {
+            s
+        };
+
+        [p1 + d1 * s, p2 + d2 * t]
+    }
 }
 
 impl<P, const D: usize> From<[P; 2]> for Segment<D>
@@ -0,0 +1,234 @@
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// A sphere
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Sphere {
+    /// The center point of the sphere
+    pub center: Point<3>,
+
+    /// The radius of the sphere
+    pub radius: Scalar,
+}
+
+impl Sphere {
+    /// Construct the minimal bounding sphere of a list of points
+    ///
+    /// Uses Welzl's algorithm. The points are shuffled first, as the algorithm
+    /// takes quadratic time, if they are ordered (like the points of an
+    /// approximation would be). The shuffle is deterministic, so the result is
+    /// too.
+    ///
+    /// Returns `None`, if no points are provided.
+    pub fn from_points(
+        points: impl IntoIterator<Item = impl Into<Point<3>>>,
+    ) -> Option<Self> {
+        let mut points: Vec<_> = points.into_iter().map(Into::into).collect();
+        shuffle(&mut points);
+
+        let mut sphere = Self::from_point(*points.first()?);
+
+        for i in 0..points.len() {
+            if sphere.contains_approx(points[i]) {
+                continue;
+            }
+
+            sphere = Self::from_point(points[i]);
+            for j in 0..i {
+                if sphere.contains_approx(points[j]) {
+                    continue;
+                }
+
+                sphere = Self::from_two_points(points[i], points[j]);
+                for k in 0..j {
+                    if sphere.contains_approx(points[k]) {
+                        continue;
+                    }
+
+                    sphere = Self::from_three_points(
+                        points[i], points[j], points[k],
+                    );
+                    for l in 0..k {
+                        if sphere.contains_approx(points[l]) {
+                            continue;
+                        }
+
+                        sphere = Self::from_four_points(
+                            points[i], points[j], points[k], points[l],
+                        );
+                    }
+                }
+            }
+        }
+
+        Some(sphere)
+    }
+
+    /// Determine whether the sphere contains a given point
+    pub fn contains(&self, point: impl Into<Point<3>>) -> bool {
+        (point.into() - self.center).magnitude() <= self.radius
+    }
+
+    /// Compute the AABB that contains this sphere
+    pub fn aabb(&self) -> Aabb<3> {
+        let radius = Vector::from([self.radius; 3]);
+
+        Aabb {
+            min: self.center - radius,
+            max: self.center + radius,
+        }
+    }
+
+    /// Determine whether the sphere contains a point, up to a rounding error
+    fn contains_approx(&self, point: Point<3>) -> bool {
+        let epsilon = Scalar::from(1e-9) * self.radius.max(Scalar::ONE);
+        (point - self.center).magnitude() <= self.radius + epsilon
+    }
+
+    fn from_point(point: Point<3>) -> Self {
+        Self {
+            center: point,
+            radius: Scalar::ZERO,
+        }
+    }
+
+    fn from_two_points(a: Point<3>, b: Point<3>) -> Self {
+        Self {
+            center: a + (b - a) / 2.,
+            radius: (b - a).magnitude() / 2.,
+        }
+    }
+
+    fn from_three_points(a: Point<3>, b: Point<3>, c: Point<3>) -> Self {
+        let ab = b - a;
+        let ac = c - a;
+        let normal = ab.cross(&ac);
+
+        let denominator = normal.dot(&normal) * 2.;
+        if denominator == Scalar::ZERO {
+            // The points are collinear. The sphere through the two points that
+            // are farthest apart contains the third one.
+            return [(a, b), (a, c), (b, c)]
+                .map(|(a, b)| Self::from_two_points(a, b))
+                .into_iter()
+                .max_by_key(|sphere| sphere.radius)
+                .expect("Array is not empty");
+        }
+
+        let offset = (normal.cross(&ab) * ac.dot(&ac)
+            + ac.cross(&normal) * ab.dot(&ab))
+            / denominator;
+
+        Self {
+            center: a + offset,
+            radius: offset.magnitude(),
+        }
+    }
+
+    fn from_four_points(
+        a: Point<3>,
+        b: Point<3>,
+        c: Point<3>,
+        d: Point<3>,
+    ) -> Self {
+        // The center is equidistant from all points, which results in a linear
+        // system of equations.
+        let [ab, ac, ad] = [b, c, d].map(|p| (p - a).to_na());
+        let matrix = nalgebra::Matrix3::from_rows(&[
+            ab.transpose(),
+            ac.transpose(),
+            ad.transpose(),
+        ]);
+        let rhs = nalgebra::Vector3::new(
+            ab.dot(&ab) / 2.,
+            ac.dot(&ac) / 2.,
+            ad.dot(&ad) / 2.,
+        );
+
+        if let Some(offset) = matrix.lu().solve(&rhs) {
+            let offset = Vector::from(offset);
+
+            return Self {
+                center: a + offset,
+                radius: offset.magnitude(),
+            };
+        }
+
+        // The points are coplanar. The smallest sphere through three of them
+        // that contains the fourth one is the one we're looking for.
+        [(a, b, c, d), (a, b, d, c), (a, c, d, b), (b, c, d, a)]
+            .into_iter()
+            .map(|(a, b, c, d)| (Self::from_three_points(a, b, c), d))
+            .filter(|(sphere, d)| sphere.contains_approx(*d))
+            .map(|(sphere, _)| sphere)
+            .min_by_key(|sphere| sphere.radius)
+            .unwrap_or_else(|| Self::from_three_points(a, b, c))
+    }
+}
+
+/// Shuffle points, using a fixed seed
+fn shuffle(points: &mut [Point<3>]) {
+    // A xorshift generator is good enough for this purpose.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let j = (state % (i as u64 + 1)) as usize;
+        points.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb, Point, Scalar, Vector};
+
+    use super::Sphere;
+
+    #[test]
+    fn from_points() {
+        assert!(Sphere::from_points(Vec::<Point<3>>::new()).is_none());
+
+        // The minimal bounding sphere of a box passes through its vertices.
+        let aabb = Aabb {
+            min: Point::from([-1., -2., -3.]),
+            max: Point::from([3., 2., 1.]),
+        };
+        let sphere = Sphere::from_points(aabb.vertices()).unwrap();
+
+        assert!((sphere.center - aabb.center()).magnitude() < 1e-9.into());
+        assert!(
+            (sphere.radius - aabb.size().magnitude() / 2.).abs()
+                < Scalar::from(1e-9)
+        );
+
+        // Points in the interior don't make a difference. Points on a line
+        // define the sphere through the points that are farthest apart.
+        let points = (0..100).map(|i| Point::from([i as f64, 0., 0.])).chain(
+            aabb.vertices()
+                .map(|point| point * 0.1 + Vector::from([50., 0., 0.])),
+        );
+        let sphere = Sphere::from_points(points).unwrap();
+
+        assert!(
+            (sphere.center - Point::from([49.5, 0., 0.])).magnitude()
+                < 1e-9.into()
+        );
+        assert!(
+            (sphere.radius - Scalar::from(49.5)).abs() < Scalar::from(1e-9)
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let sphere = Sphere {
+            center: Point::from([1., 1., 1.]),
+            radius: Scalar::ONE,
+        };
+
+        assert!(sphere.contains([1., 1., 2.]));
+        assert!(sphere.contains([1.5, 1.5, 1.5]));
+        assert!(!sphere.contains([2., 2., 2.]));
+    }
+}
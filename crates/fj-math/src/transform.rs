@@ -1,6 +1,6 @@
 use std::ops;
 
-use nalgebra::Perspective3;
+use nalgebra::{Orthographic3, Perspective3};
 
 use crate::{Circle, Line, Scalar};
 
@@ -40,6 +40,27 @@ impl Transform {
         ))
     }
 
+    /// Construct a scaling
+    ///
+    /// Each component of `factors` defines the scale factor along the
+    /// respective axis.
+    pub fn scaling(factors: impl Into<Vector<3>>) -> Self {
+        let factors = factors.into();
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Matrix4::new_nonuniform_scaling(&factors.to_na()),
+        ))
+    }
+
+    /// Construct a transform from its decomposition
+    ///
+    /// This is the inverse of [`Transform::decompose`].
+    pub fn from_decomposition(decomposition: &Decomposition) -> Self {
+        Self::translation(decomposition.translation)
+            * Self::rotation(decomposition.rotation)
+            * Self::scaling(decomposition.scale)
+    }
+
     /// Transform the given point
     pub fn transform_point(&self, point: &Point<3>) -> Point<3> {
         Point::from(self.0.transform_point(&point.to_na()))
@@ -89,10 +110,23 @@ impl Transform {
     }
 
     /// Inverse transform
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the transform is not invertible. Use
+    /// [`Transform::try_inverse`], if that is a possibility.
     pub fn inverse(&self) -> Transform {
         Self(self.0.inverse())
     }
 
+    /// Inverse transform, if the transform is invertible
+    ///
+    /// Returns `None`, if the transform collapses space into a plane, a line,
+    /// or a point (as a scaling by zero does).
+    pub fn try_inverse(&self) -> Option<Transform> {
+        self.0.try_inverse().map(Self)
+    }
+
     /// Transpose transform
     pub fn transpose(&self) -> Transform {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -119,6 +153,30 @@ impl Transform {
         array.map(Scalar::from)
     }
 
+    /// Project transform using an orthographic projection, return data as an
+    /// array
+    ///
+    /// The view volume is centered on the view axis, and `width` and `height`
+    /// define its extent. Used primarily for graphics code.
+    pub fn project_orthographic_to_array(
+        &self,
+        width: f64,
+        height: f64,
+        znear: f64,
+        zfar: f64,
+    ) -> [Scalar; 16] {
+        let [right, top] = [width / 2., height / 2.];
+        let projection =
+            Orthographic3::new(-right, right, -top, top, znear, zfar);
+
+        let mut array = [0.; 16];
+        array.copy_from_slice(
+            (projection.to_projective() * self.0).matrix().as_slice(),
+        );
+
+        array.map(Scalar::from)
+    }
+
     /// Transform the given axis-aligned bounding box
     pub fn transform_aabb(&self, aabb: &Aabb<3>) -> Aabb<3> {
         Aabb {
@@ -132,6 +190,16 @@ impl Transform {
         self.0.matrix().data.as_slice()
     }
 
+    /// Construct a transform from its data, as exposed by [`Transform::data`]
+    ///
+    /// The data is the matrix of the transform, in column-major order. It is
+    /// not checked, whether it actually describes an affine transform.
+    pub fn from_data(data: [f64; 16]) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Matrix4::from_column_slice(&data),
+        ))
+    }
+
     /// Extract the rotation component of this transform
     pub fn extract_rotation(&self) -> Transform {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -143,6 +211,94 @@ impl Transform {
     pub fn extract_translation(&self) -> Transform {
         *self * self.extract_rotation().inverse()
     }
+
+    /// Decompose this transform into translation, rotation, and scale
+    ///
+    /// Shear can't be represented by the decomposition and is lost. A
+    /// reflection is represented as a negative scale factor along the x-axis.
+    ///
+    /// See [`Transform::from_decomposition`] for the inverse operation.
+    pub fn decompose(&self) -> Decomposition {
+        let matrix = self.0.matrix();
+
+        let translation = Vector::from_na(matrix.column(3).xyz());
+
+        let linear = matrix.fixed_resize::<3, 3>(0.);
+        let mut scale = [0, 1, 2].map(|i| linear.column(i).norm());
+        if linear.determinant() < 0. {
+            scale[0] = -scale[0];
+        }
+
+        // Remove the scale from the linear part, which leaves the rotation. If
+        // there's shear, the remainder isn't an exact rotation, and the
+        // conversion to a quaternion only approximates it.
+        let mut rotation = linear;
+        for (mut column, &factor) in rotation.column_iter_mut().zip(&scale) {
+            if factor != 0. {
+                column /= factor;
+            }
+        }
+        let rotation = nalgebra::UnitQuaternion::from_rotation_matrix(
+            &nalgebra::Rotation3::from_matrix_unchecked(rotation),
+        );
+
+        Decomposition {
+            translation,
+            rotation: Vector::from_na(rotation.scaled_axis()),
+            scale: Vector::from(scale),
+        }
+    }
+
+    /// Interpolate between this transform and another one
+    ///
+    /// `t` is the interpolation parameter. A value of `0` results in this
+    /// transform, a value of `1` in `other`. Translation and scale are
+    /// interpolated linearly, rotation is interpolated spherically, along the
+    /// shortest path between both rotations.
+    ///
+    /// Both transforms are decomposed first, so any shear is lost. See
+    /// [`Transform::decompose`].
+    pub fn interpolate(&self, other: &Self, t: impl Into<Scalar>) -> Self {
+        let t = t.into();
+
+        let a = self.decompose();
+        let b = other.decompose();
+
+        let rotation = {
+            let a =
+                nalgebra::UnitQuaternion::from_scaled_axis(a.rotation.to_na());
+            let b =
+                nalgebra::UnitQuaternion::from_scaled_axis(b.rotation.to_na());
+
+            // This only fails, if both rotations are practically the same.
+            a.try_slerp(&b, t.into_f64(), f64::EPSILON).unwrap_or(a)
+        };
+
+        Self::from_decomposition(&Decomposition {
+            translation: a.translation + (b.translation - a.translation) * t,
+            rotation: Vector::from_na(rotation.scaled_axis()),
+            scale: a.scale + (b.scale - a.scale) * t,
+        })
+    }
+}
+
+/// A transform, decomposed into translation, rotation, and scale
+///
+/// Returned by [`Transform::decompose`]. The transform it represents first
+/// scales, then rotates, then translates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decomposition {
+    /// The translation
+    pub translation: Vector<3>,
+
+    /// The rotation
+    ///
+    /// The direction of the vector defines the rotation axis. Its length
+    /// defines the angle of the rotation, as with [`Transform::rotation`].
+    pub rotation: Vector<3>,
+
+    /// The scale factor along each axis
+    pub scale: Vector<3>,
 }
 
 impl ops::Mul<Self> for Transform {
@@ -212,4 +368,108 @@ mod tests {
             epsilon = 1e-8,
         );
     }
+
+    #[test]
+    fn decompose() {
+        let transform = Transform::translation([1., 2., 3.])
+            * Transform::rotation(Vector::unit_z() * (Scalar::PI / 2.))
+            * Transform::scaling([2., 3., 4.]);
+
+        let decomposition = transform.decompose();
+        assert_abs_diff_eq!(
+            decomposition.translation,
+            Vector::from([1., 2., 3.]),
+            epsilon = 1e-8,
+        );
+        assert_abs_diff_eq!(
+            decomposition.rotation,
+            Vector::unit_z() * (Scalar::PI / 2.),
+            epsilon = 1e-8,
+        );
+        assert_abs_diff_eq!(
+            decomposition.scale,
+            Vector::from([2., 3., 4.]),
+            epsilon = 1e-8,
+        );
+
+        assert_abs_diff_eq!(
+            Transform::from_decomposition(&decomposition).data(),
+            transform.data(),
+            epsilon = 1e-8,
+        );
+
+        let mirrored = Transform::scaling([1., -1., 1.]).decompose();
+        assert_abs_diff_eq!(
+            Transform::from_decomposition(&mirrored).data(),
+            Transform::scaling([1., -1., 1.]).data(),
+            epsilon = 1e-8,
+        );
+    }
+
+    #[test]
+    fn interpolate() {
+        let a = Transform::identity();
+        let b = Transform::translation([2., 0., 0.])
+            * Transform::rotation(Vector::unit_z() * (Scalar::PI / 2.))
+            * Transform::scaling([3., 3., 3.]);
+
+        assert_abs_diff_eq!(
+            a.interpolate(&b, 0.).data(),
+            a.data(),
+            epsilon = 1e-8,
+        );
+        assert_abs_diff_eq!(
+            a.interpolate(&b, 1.).data(),
+            b.data(),
+            epsilon = 1e-8,
+        );
+
+        let expected = Transform::translation([1., 0., 0.])
+            * Transform::rotation(Vector::unit_z() * (Scalar::PI / 4.))
+            * Transform::scaling([2., 2., 2.]);
+        assert_abs_diff_eq!(
+            a.interpolate(&b, 0.5).data(),
+            expected.data(),
+            epsilon = 1e-8,
+        );
+    }
+
+    #[test]
+    fn try_inverse() {
+        let transform = Transform::translation([1., 2., 3.])
+            * Transform::scaling([2., 2., 2.]);
+        let inverse = transform.try_inverse().unwrap();
+        assert_abs_diff_eq!(
+            (transform * inverse).data(),
+            Transform::identity().data(),
+            epsilon = 1e-8,
+        );
+
+        assert!(Transform::scaling([1., 0., 1.]).try_inverse().is_none());
+    }
+
+    #[test]
+    fn from_data() {
+        let transform = Transform::translation([1., 2., 3.])
+            * Transform::rotation(Vector::unit_z() * (Scalar::PI / 2.));
+
+        let mut data = [0.; 16];
+        data.copy_from_slice(transform.data());
+
+        assert_eq!(Transform::from_data(data).data(), transform.data());
+    }
+
+    #[test]
+    fn project_orthographic() {
+        let m = Transform::identity()
+            .project_orthographic_to_array(4., 2., 1., 10.)
+            .map(Scalar::into_f64);
+
+        // The matrix is stored in column-major order.
+        let [x, y, z] = [1., 0.5, -5.];
+        let clip = [0, 1, 3]
+            .map(|i| m[i] * x + m[4 + i] * y + m[8 + i] * z + m[12 + i]);
+
+        assert_abs_diff_eq!(clip.as_slice(), [0.5, 0.5, 1.].as_slice());
+    }
 }
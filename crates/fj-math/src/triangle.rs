@@ -1,7 +1,7 @@
 use parry2d_f64::utils::point_in_triangle::{corner_direction, Orientation};
-use parry3d_f64::query::{Ray, RayCast as _};
+use parry3d_f64::query::{PointQuery as _, Ray, RayCast as _};
 
-use crate::Vector;
+use crate::{Segment, Vector};
 
 use super::{Point, Scalar};
 
@@ -88,6 +88,70 @@ impl Triangle<3> {
             .map(|f| f.into())
     }
 
+    /// Compute the distance between the triangle and a point
+    pub fn distance_to_point(&self, point: impl Into<Point<3>>) -> Scalar {
+        self.to_parry()
+            .distance_to_local_point(&point.into().to_na(), true)
+            .into()
+    }
+
+    /// Compute the point on the triangle that is closest to another point
+    pub fn closest_point(&self, point: impl Into<Point<3>>) -> Point<3> {
+        self.to_parry()
+            .project_local_point(&point.into().to_na(), true)
+            .point
+            .into()
+    }
+
+    /// Compute the closest points between this triangle and another
+    ///
+    /// Returns the point on this triangle first, the point on the other one
+    /// second. If the triangles intersect, both points are the same point of
+    /// the intersection.
+    pub fn closest_points(&self, other: &Self) -> [Point<3>; 2] {
+        let edges = |triangle: &Self| {
+            let [a, b, c] = triangle.points;
+            [[a, b], [b, c], [c, a]].map(Segment::from_points)
+        };
+
+        let mut candidates = Vec::new();
+
+        // If the triangles intersect, an edge of one of them pierces the
+        // other, or the triangles are coplanar. The latter case is covered by
+        // the checks below.
+        for (a, b, swap) in [(self, other, false), (other, self, true)] {
+            for edge in edges(a) {
+                let [start, end] = edge.points();
+                if let Some(t) = b.cast_local_ray(start, end - start, 1., true)
+                {
+                    let point = start + (end - start) * t;
+                    candidates.push([point, point]);
+                }
+            }
+
+            // Otherwise, the closest points are a vertex of one triangle and a
+            // point on the other, or points on two edges.
+            for point in a.points {
+                let closest = b.closest_point(point);
+                candidates.push(if swap {
+                    [closest, point]
+                } else {
+                    [point, closest]
+                });
+            }
+        }
+        for a in edges(self) {
+            for b in edges(other) {
+                candidates.push(a.closest_points(&b));
+            }
+        }
+
+        candidates
+            .into_iter()
+            .min_by_key(|&[a, b]| (b - a).magnitude())
+            .expect("Candidates are not empty")
+    }
+
     /// Compute the triangle's normal
     pub fn normal(&self) -> Vector<3> {
         self.to_parry()
@@ -108,6 +172,7 @@ where
 }
 
 /// Winding direction of a triangle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum Winding {
     /// Counter-clockwise
     Ccw,
@@ -127,7 +192,7 @@ impl From<Orientation> for Winding {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Point, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Triangle;
 
@@ -171,4 +236,47 @@ mod tests {
             Triangle::from([[0.0, 0.0, 0.0], [2.0, 1.0, 0.0], [2.0, 0.0, 0.0]]);
         assert_eq!(triangle.normal(), Vector::from([0.0, 0.0, -1.0]));
     }
+
+    #[test]
+    fn distance_to_point() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+        assert_eq!(triangle.distance_to_point([0.5, 0.5, 3.0]), 3.0.into());
+        assert_eq!(triangle.distance_to_point([-1.0, 0.0, 0.0]), 1.0.into());
+    }
+
+    #[test]
+    fn closest_points() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        // Vertex of one triangle above the face of the other.
+        let other =
+            Triangle::from([[0.5, 0.5, 1.0], [0.5, 0.5, 3.0], [1.5, 0.5, 3.0]]);
+        assert_eq!(
+            triangle.closest_points(&other),
+            [Point::from([0.5, 0.5, 0.0]), Point::from([0.5, 0.5, 1.0])]
+        );
+
+        // Closest points on the edges of both triangles.
+        let other = Triangle::from([
+            [1.5, 1.5, -1.0],
+            [1.5, 1.5, 1.0],
+            [3.0, 3.0, 0.0],
+        ]);
+        let [a, b] = triangle.closest_points(&other);
+        assert_eq!(a, Point::from([1.0, 1.0, 0.0]));
+        assert_eq!(b, Point::from([1.5, 1.5, 0.0]));
+
+        // An edge that pierces the other triangle.
+        let other = Triangle::from([
+            [0.5, 0.5, -1.0],
+            [0.5, 0.5, 1.0],
+            [3.0, 3.0, 1.0],
+        ]);
+        let [a, b] = triangle.closest_points(&other);
+        assert_eq!(a, b);
+        assert!(triangle.distance_to_point(a) < Scalar::from(1e-12));
+        assert!(other.distance_to_point(a) < Scalar::from(1e-12));
+    }
 }
@@ -0,0 +1,71 @@
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_kernel::{
+    algorithms::{boolean, BooleanBackend, BooleanOperation, ToleranceStack},
+    objects::{Face, Solid, Stores},
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::Aabb;
+
+use super::{compute_breps_in_parallel, Shape};
+
+impl Shape for fj::Boolean {
+    type Brep = Vec<Face>;
+
+    #[tracing::instrument(name = "boolean", level = "debug", skip_all)]
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: ToleranceStack,
+        stores: &Stores,
+        debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let [a, b] = compute_breps_in_parallel(
+            [&self.a, &self.b],
+            config,
+            tolerance,
+            stores,
+            debug_info,
+            progress,
+        )?;
+        let [a, b] = [a, b].map(|faces| Solid::from_faces(faces.into_inner()));
+
+        let operation = match self.operation {
+            fj::BooleanOperation::Union => BooleanOperation::Union,
+            fj::BooleanOperation::Intersection => {
+                BooleanOperation::Intersection
+            }
+            fj::BooleanOperation::Difference => BooleanOperation::Difference,
+        };
+        let backend = match self.backend {
+            fj::BooleanBackend::Exact => BooleanBackend::Exact,
+            fj::BooleanBackend::Mesh => BooleanBackend::Mesh,
+        };
+
+        let faces = boolean(
+            &a,
+            &b,
+            operation,
+            backend,
+            tolerance.current(),
+            debug_info,
+        )
+        .into_faces()
+        .into_iter()
+        .collect();
+
+        validate(faces, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let a = self.a.bounding_volume();
+
+        // The results of the other operations are contained in the first
+        // shape.
+        match self.operation {
+            fj::BooleanOperation::Union => a.merged(&self.b.bounding_volume()),
+            fj::BooleanOperation::Intersection
+            | fj::BooleanOperation::Difference => a,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Color inheritance in the operation tree
+//!
+//! Any operation can have a color. That color applies to all parts of the
+//! operation's shape, that don't have a color of their own. In other words,
+//! children inherit the color of their parent, unless they override it, and
+//! the closest color up the tree wins. This makes it possible, for example, to
+//! tint all members of a group, while keeping the color of some of them.
+//!
+//! Only sketches and primitives end up as faces, so that's where colors are
+//! needed. [`resolve_colors`] pushes inherited colors down the tree, to those
+//! leaves. Leaves that neither have a color, nor inherit one, end up with
+//! [`fj::DEFAULT_COLOR`].
+
+/// Resolve color inheritance, by pushing inherited colors down to the leaves
+///
+/// Returns a shape in which every sketch and primitive has the color that it
+/// is going to be rendered in, and which is otherwise identical to `shape`.
+pub fn resolve_colors(shape: &fj::Shape) -> fj::Shape {
+    resolve_3d(shape, None)
+}
+
+fn resolve_3d(shape: &fj::Shape, inherited: Option<[u8; 4]>) -> fj::Shape {
+    match shape {
+        fj::Shape::Shape2d(shape) => {
+            fj::Shape::Shape2d(resolve_2d(shape, inherited))
+        }
+        fj::Shape::Group(group) => {
+            let inherited = group.color().or(inherited);
+
+            let mut resolved = fj::Group::builder();
+            for member in group.members() {
+                let mut resolved_member =
+                    fj::GroupMember::new(resolve_3d(member.shape(), inherited));
+                if let Some(placement) = member.placement() {
+                    resolved_member = resolved_member.with_placement(placement);
+                }
+                if let Some(name) = member.name() {
+                    resolved_member = resolved_member.with_name(name);
+                }
+
+                resolved = resolved.with_member(resolved_member);
+            }
+
+            let mut resolved = resolved.build();
+            if let Some(color) = group.color() {
+                resolved = resolved.with_color(color);
+            }
+            if let Some(tolerance) = group.tolerance() {
+                resolved = resolved.with_tolerance(tolerance);
+            }
+
+            resolved.into()
+        }
+        fj::Shape::Sweep(sweep) => {
+            let inherited = sweep.color().or(inherited);
+
+            let mut resolved = fj::Sweep::from_path(
+                resolve_2d(sweep.shape(), inherited),
+                sweep.path(),
+            )
+            .with_back_length(sweep.back_length())
+            .with_taper(sweep.taper());
+            if let Some(color) = sweep.color() {
+                resolved = resolved.with_color(color);
+            }
+            if let Some(tolerance) = sweep.tolerance() {
+                resolved = resolved.with_tolerance(tolerance);
+            }
+
+            resolved.into()
+        }
+        fj::Shape::Transform(transform) => {
+            let inherited = transform.color().or(inherited);
+
+            let mut resolved = (**transform).clone();
+            resolved.shape = resolve_3d(&transform.shape, inherited);
+
+            resolved.into()
+        }
+        fj::Shape::Primitive(primitive) => {
+            match (primitive.explicit_color(), inherited) {
+                (None, Some(color)) => primitive.clone().with_color(color),
+                _ => primitive.clone(),
+            }
+            .into()
+        }
+        fj::Shape::Offset(offset) => {
+            let inherited = offset.color().or(inherited);
+
+            let mut resolved = (**offset).clone();
+            resolved.shape = resolve_3d(&offset.shape, inherited);
+
+            resolved.into()
+        }
+        fj::Shape::Boolean(boolean) => {
+            let inherited = boolean.color().or(inherited);
+
+            let mut resolved = (**boolean).clone();
+            resolved.a = resolve_3d(&boolean.a, inherited);
+            resolved.b = resolve_3d(&boolean.b, inherited);
+
+            resolved.into()
+        }
+    }
+}
+
+fn resolve_2d(shape: &fj::Shape2d, inherited: Option<[u8; 4]>) -> fj::Shape2d {
+    match shape {
+        fj::Shape2d::Difference(difference) => fj::Difference2d::from_shapes(
+            difference
+                .shapes()
+                .clone()
+                .map(|shape| resolve_2d(&shape, inherited)),
+        )
+        .into(),
+        fj::Shape2d::Sketch(sketch) => {
+            match (sketch.explicit_color(), inherited) {
+                (None, Some(color)) => sketch.clone().with_color(color),
+                _ => sketch.clone(),
+            }
+            .into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::{
+        graph::Node,
+        syntax::{Boolean as _, Sweep as _, Transform as _},
+    };
+
+    use super::resolve_colors;
+
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    #[test]
+    fn inheritance() {
+        let square = fj::Sketch::from_points(vec![
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+        ]);
+        let cube = fj::Primitive::cuboid([1., 1., 1.]);
+
+        let group = fj::Group::builder()
+            // Inherits the color of the group.
+            .with_shape(square.sweep([0., 0., 1.]))
+            // Keeps its own color.
+            .with_shape(cube.clone().with_color(BLUE))
+            // The closest color up the tree wins.
+            .with_shape(cube.translate([2., 0., 0.]).with_color(WHITE))
+            // Colors pass through operations that don't have one.
+            .with_shape(cube.union(&cube.translate([0., 0., 1.])))
+            .build()
+            .with_color(GREEN);
+
+        let resolved = resolve_colors(&group.into());
+        assert_eq!(leaf_colors(&resolved), [GREEN, BLUE, WHITE, GREEN, GREEN]);
+    }
+
+    #[test]
+    fn default() {
+        let cube: fj::Shape = fj::Primitive::cuboid([1., 1., 1.]).into();
+
+        let resolved = resolve_colors(&cube);
+        assert_eq!(resolved, cube);
+        assert_eq!(leaf_colors(&resolved), [fj::DEFAULT_COLOR]);
+    }
+
+    fn leaf_colors(shape: &fj::Shape) -> Vec<[u8; 4]> {
+        shape
+            .nodes()
+            .filter_map(|(_, node)| match node {
+                Node::Sketch(sketch) => Some(sketch.color()),
+                Node::Primitive(primitive) => Some(primitive.color()),
+                _ => None,
+            })
+            .collect()
+    }
+}
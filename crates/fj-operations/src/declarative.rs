@@ -0,0 +1,547 @@
+//! Declarative format for shapes
+//!
+//! Models are usually Rust crates, which need to be compiled before they can
+//! be displayed. This module provides an alternative: a tree of operations,
+//! written in JSON, TOML, or RON, that is converted into an [`fj::Shape`]
+//! without compiling anything. This is useful for simple models, for models
+//! that are generated by other programs, and for frontends that aren't written
+//! in Rust.
+//!
+//! Each operation is identified by its name, and names the operations it
+//! applies to as fields. The names follow the methods of [`fj::syntax`]. This
+//! is a 6x6x4 cuboid with a cylindrical hole, in TOML:
+//!
+//! ``` toml
+//! [subtract]
+//! a = { cuboid = { size = [6.0, 6.0, 4.0] } }
+//!
+//! [subtract.b.sweep]
+//! shape = { circle = { radius = 2.0 } }
+//! path = [0.0, 0.0, 4.0]
+//! ```
+//!
+//! The same model in JSON:
+//!
+//! ``` json
+//! { "subtract": {
+//!     "a": { "cuboid": { "size": [6.0, 6.0, 4.0] } },
+//!     "b": { "sweep": {
+//!         "shape": { "circle": { "radius": 2.0 } },
+//!         "path": [0.0, 0.0, 4.0]
+//!     } }
+//! } }
+//! ```
+//!
+//! And in RON:
+//!
+//! ``` ron
+//! subtract(
+//!     a: cuboid(size: (6.0, 6.0, 4.0)),
+//!     b: sweep(shape: circle(radius: 2.0), path: (0.0, 0.0, 4.0)),
+//! )
+//! ```
+
+use std::{fmt, fs, io, path::Path};
+
+use fj::syntax::{
+    Boolean as _, Difference as _, Group as _, Offset as _, Sweep as _,
+    Transform as _,
+};
+use serde::{Deserialize, Serialize};
+
+/// An operation that creates a shape
+///
+/// See the [module documentation](self) for the format.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Operation {
+    /// A 2D sketch, made from a polygonal chain
+    ///
+    /// See [`fj::Sketch::from_points`].
+    Sketch {
+        /// The points of the chain, which is closed automatically
+        points: Vec<[f64; 2]>,
+
+        /// The color of the sketch, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// A 2D sketch, made from a circle around the origin
+    ///
+    /// See [`fj::Sketch::from_circle`].
+    Circle {
+        /// The radius of the circle
+        radius: f64,
+
+        /// The color of the sketch, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// The difference of two 2D shapes
+    ///
+    /// See [`fj::syntax::Difference`].
+    Difference {
+        /// The shape that is subtracted from
+        a: Box<Operation>,
+
+        /// The shape that is subtracted
+        b: Box<Operation>,
+    },
+
+    /// A 2D shape, swept along a straight path
+    ///
+    /// See [`fj::syntax::Sweep`].
+    Sweep {
+        /// The 2D shape
+        shape: Box<Operation>,
+
+        /// The path the shape is swept along
+        path: [f64; 3],
+    },
+
+    /// A cuboid, with one corner at the origin
+    ///
+    /// See [`fj::Primitive::cuboid`].
+    Cuboid {
+        /// The size of the cuboid along each axis
+        size: [f64; 3],
+
+        /// The color of the cuboid, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// A cylinder, standing on the xy-plane
+    ///
+    /// See [`fj::Primitive::cylinder`].
+    Cylinder {
+        /// The radius of the cylinder
+        radius: f64,
+
+        /// The height of the cylinder
+        height: f64,
+
+        /// The color of the cylinder, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// A sphere around the origin
+    ///
+    /// See [`fj::Primitive::sphere`].
+    Sphere {
+        /// The radius of the sphere
+        radius: f64,
+
+        /// The color of the sphere, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// A cone, standing on the xy-plane
+    ///
+    /// See [`fj::Primitive::cone`].
+    Cone {
+        /// The radius of the cone's base
+        radius: f64,
+
+        /// The height of the cone
+        height: f64,
+
+        /// The color of the cone, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// A torus around the z-axis
+    ///
+    /// See [`fj::Primitive::torus`].
+    Torus {
+        /// The distance from the center of the torus to the center of its tube
+        major_radius: f64,
+
+        /// The radius of the tube
+        minor_radius: f64,
+
+        /// The color of the torus, as RGBA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<[u8; 4]>,
+    },
+
+    /// The union of two 3D shapes
+    ///
+    /// See [`fj::syntax::Boolean::union`].
+    Union {
+        /// The first shape
+        a: Box<Operation>,
+
+        /// The second shape
+        b: Box<Operation>,
+    },
+
+    /// The intersection of two 3D shapes
+    ///
+    /// See [`fj::syntax::Boolean::intersection`].
+    Intersection {
+        /// The first shape
+        a: Box<Operation>,
+
+        /// The second shape
+        b: Box<Operation>,
+    },
+
+    /// The difference of two 3D shapes
+    ///
+    /// See [`fj::syntax::Boolean::subtract`].
+    Subtract {
+        /// The shape that is subtracted from
+        a: Box<Operation>,
+
+        /// The shape that is subtracted
+        b: Box<Operation>,
+    },
+
+    /// A group of two disjoint 3D shapes
+    ///
+    /// See [`fj::syntax::Group`].
+    Group {
+        /// The first shape
+        a: Box<Operation>,
+
+        /// The second shape
+        b: Box<Operation>,
+    },
+
+    /// A 3D shape, with its faces offset by a distance
+    ///
+    /// See [`fj::syntax::Offset`].
+    Offset {
+        /// The shape
+        shape: Box<Operation>,
+
+        /// The distance; positive grows the shape, negative shrinks it
+        distance: f64,
+    },
+
+    /// A 3D shape, rotated around an axis through the origin
+    ///
+    /// See [`fj::syntax::Transform::rotate`].
+    Rotate {
+        /// The shape
+        shape: Box<Operation>,
+
+        /// The axis of the rotation
+        axis: [f64; 3],
+
+        /// The angle of the rotation, in degrees
+        degrees: f64,
+    },
+
+    /// A 3D shape, moved by an offset
+    ///
+    /// See [`fj::syntax::Transform::translate`].
+    Translate {
+        /// The shape
+        shape: Box<Operation>,
+
+        /// The offset
+        offset: [f64; 3],
+    },
+}
+
+impl Operation {
+    /// Load an operation from a file
+    ///
+    /// The format is determined by the file's extension.
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let format = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Format::from_extension)
+            .ok_or_else(|| Error::UnknownFormat(path.display().to_string()))?;
+        let source = fs::read_to_string(path)?;
+
+        Self::parse(&source, format)
+    }
+
+    /// Parse an operation from a string in the given format
+    pub fn parse(source: &str, format: Format) -> Result<Self, Error> {
+        let operation = match format {
+            Format::Json => {
+                serde_json::from_str(source).map_err(parse_error)?
+            }
+            // The TOML deserializer only supports enums without fields. Going
+            // through JSON's representation of the document works around that.
+            Format::Toml => {
+                let value: toml::Value =
+                    toml::from_str(source).map_err(parse_error)?;
+                let value = serde_json::to_value(value).map_err(parse_error)?;
+
+                serde_json::from_value(value).map_err(parse_error)?
+            }
+            Format::Ron => ron::from_str(source).map_err(parse_error)?,
+        };
+
+        Ok(operation)
+    }
+
+    /// Convert the operation into a shape
+    ///
+    /// The result can be processed like the shape that a compiled model
+    /// returns. Fails, if a 2D shape is used where a 3D shape is expected, or
+    /// the other way around.
+    pub fn to_shape(&self) -> Result<fj::Shape, Error> {
+        let shape = match self {
+            Self::Sketch { .. }
+            | Self::Circle { .. }
+            | Self::Difference { .. } => {
+                fj::Shape::Shape2d(self.to_shape_2d()?)
+            }
+            Self::Sweep { shape, path } => {
+                shape.to_shape_2d()?.sweep(*path).into()
+            }
+            Self::Cuboid { size, color } => {
+                primitive(fj::Primitive::cuboid(*size), *color)
+            }
+            Self::Cylinder {
+                radius,
+                height,
+                color,
+            } => primitive(fj::Primitive::cylinder(*radius, *height), *color),
+            Self::Sphere { radius, color } => {
+                primitive(fj::Primitive::sphere(*radius), *color)
+            }
+            Self::Cone {
+                radius,
+                height,
+                color,
+            } => primitive(fj::Primitive::cone(*radius, *height), *color),
+            Self::Torus {
+                major_radius,
+                minor_radius,
+                color,
+            } => primitive(
+                fj::Primitive::torus(*major_radius, *minor_radius),
+                *color,
+            ),
+            Self::Union { a, b } => {
+                a.to_shape_3d()?.union(&b.to_shape_3d()?).into()
+            }
+            Self::Intersection { a, b } => {
+                a.to_shape_3d()?.intersection(&b.to_shape_3d()?).into()
+            }
+            Self::Subtract { a, b } => {
+                a.to_shape_3d()?.subtract(&b.to_shape_3d()?).into()
+            }
+            Self::Group { a, b } => {
+                a.to_shape_3d()?.group(&b.to_shape_3d()?).into()
+            }
+            Self::Offset { shape, distance } => {
+                shape.to_shape_3d()?.offset(*distance).into()
+            }
+            Self::Rotate {
+                shape,
+                axis,
+                degrees,
+            } => shape
+                .to_shape_3d()?
+                .rotate(*axis, fj::Angle::from_deg(*degrees))
+                .into(),
+            Self::Translate { shape, offset } => {
+                shape.to_shape_3d()?.translate(*offset).into()
+            }
+        };
+
+        Ok(shape)
+    }
+
+    fn to_shape_2d(&self) -> Result<fj::Shape2d, Error> {
+        let shape = match self {
+            Self::Sketch { points, color } => {
+                sketch(fj::Sketch::from_points(points.clone()), *color)
+            }
+            Self::Circle { radius, color } => sketch(
+                fj::Sketch::from_circle(fj::Circle::from_radius(*radius)),
+                *color,
+            ),
+            Self::Difference { a, b } => {
+                a.to_shape_2d()?.difference(&b.to_shape_2d()?).into()
+            }
+            _ => return Err(Error::Expected2d(self.name())),
+        };
+
+        Ok(shape)
+    }
+
+    fn to_shape_3d(&self) -> Result<fj::Shape, Error> {
+        match self.to_shape()? {
+            fj::Shape::Shape2d(_) => Err(Error::Expected3d(self.name())),
+            shape => Ok(shape),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Sketch { .. } => "sketch",
+            Self::Circle { .. } => "circle",
+            Self::Difference { .. } => "difference",
+            Self::Sweep { .. } => "sweep",
+            Self::Cuboid { .. } => "cuboid",
+            Self::Cylinder { .. } => "cylinder",
+            Self::Sphere { .. } => "sphere",
+            Self::Cone { .. } => "cone",
+            Self::Torus { .. } => "torus",
+            Self::Union { .. } => "union",
+            Self::Intersection { .. } => "intersection",
+            Self::Subtract { .. } => "subtract",
+            Self::Group { .. } => "group",
+            Self::Offset { .. } => "offset",
+            Self::Rotate { .. } => "rotate",
+            Self::Translate { .. } => "translate",
+        }
+    }
+}
+
+fn parse_error(err: impl fmt::Display) -> Error {
+    Error::Parse(err.to_string())
+}
+
+fn sketch(sketch: fj::Sketch, color: Option<[u8; 4]>) -> fj::Shape2d {
+    match color {
+        Some(color) => sketch.with_color(color).into(),
+        None => sketch.into(),
+    }
+}
+
+fn primitive(primitive: fj::Primitive, color: Option<[u8; 4]>) -> fj::Shape {
+    match color {
+        Some(color) => primitive.with_color(color).into(),
+        None => primitive.into(),
+    }
+}
+
+/// A format that operations can be written in
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// JSON, identified by the extension `json`
+    Json,
+
+    /// TOML, identified by the extension `toml`
+    Toml,
+
+    /// RON, identified by the extension `ron`
+    Ron,
+}
+
+impl Format {
+    /// Determine the format from a file extension
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "ron" => Some(Self::Ron),
+            _ => None,
+        }
+    }
+}
+
+/// Error loading an [`Operation`], or converting it into a shape
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// I/O error while reading the file
+    #[error("I/O error while reading operations")]
+    Io(#[from] io::Error),
+
+    /// The format of the file could not be determined from its extension
+    #[error("Unknown format of \"{0}\"; expected `json`, `toml`, or `ron`")]
+    UnknownFormat(String),
+
+    /// The operations could not be parsed
+    #[error("Error parsing operations: {0}")]
+    Parse(String),
+
+    /// A 3D shape was used where a 2D shape is expected
+    #[error("Expected a 2D shape, but `{0}` creates a 3D shape")]
+    Expected2d(&'static str),
+
+    /// A 2D shape was used where a 3D shape is expected
+    #[error("Expected a 3D shape, but `{0}` creates a 2D shape")]
+    Expected3d(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::{Boolean as _, Sweep as _};
+
+    use super::{Error, Format, Operation};
+
+    #[test]
+    fn parse() {
+        let toml = "
+            [subtract]
+            a = { cuboid = { size = [6.0, 6.0, 4.0] } }
+
+            [subtract.b.sweep]
+            shape = { circle = { radius = 2.0 } }
+            path = [0.0, 0.0, 4.0]
+        ";
+        let json = r#"{ "subtract": {
+            "a": { "cuboid": { "size": [6.0, 6.0, 4.0] } },
+            "b": { "sweep": {
+                "shape": { "circle": { "radius": 2.0 } },
+                "path": [0.0, 0.0, 4.0]
+            } }
+        } }"#;
+        let ron = "subtract(
+            a: cuboid(size: (6.0, 6.0, 4.0)),
+            b: sweep(shape: circle(radius: 2.0), path: (0.0, 0.0, 4.0)),
+        )";
+
+        let cuboid = fj::Primitive::cuboid([6., 6., 4.]);
+        let hole = fj::Sketch::from_circle(fj::Circle::from_radius(2.))
+            .sweep([0., 0., 4.]);
+        let expected: fj::Shape = cuboid.subtract(&hole).into();
+
+        for (source, format) in [
+            (toml, Format::Toml),
+            (json, Format::Json),
+            (ron, Format::Ron),
+        ] {
+            let operation = Operation::parse(source, format).unwrap();
+            assert_eq!(operation.to_shape().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn dimensions() {
+        let sweep = Operation::parse(
+            "sweep(shape: sphere(radius: 1.0), path: (0.0, 0.0, 1.0))",
+            Format::Ron,
+        )
+        .unwrap();
+        assert!(matches!(sweep.to_shape(), Err(Error::Expected2d("sphere"))));
+
+        let union = Operation::parse(
+            "union(a: sphere(radius: 1.0), b: circle(radius: 1.0))",
+            Format::Ron,
+        )
+        .unwrap();
+        assert!(matches!(union.to_shape(), Err(Error::Expected3d("circle"))));
+
+        // A 2D shape on its own is fine.
+        let circle = Operation::Circle {
+            radius: 1.,
+            color: None,
+        };
+        assert!(matches!(circle.to_shape(), Ok(fj::Shape::Shape2d(_))));
+    }
+
+    #[test]
+    fn unknown_operation() {
+        let result =
+            Operation::parse(r#"{ "cube": { "size": 1.0 } }"#, Format::Json);
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+}
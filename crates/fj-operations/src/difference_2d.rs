@@ -1,23 +1,25 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
 use fj_kernel::{
-    algorithms::Tolerance,
+    algorithms::ToleranceStack,
     iter::ObjectIters,
-    local::Local,
-    objects::{Cycle, Edge, Face, Sketch},
+    objects::{Face, Sketch, Stores},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
 use fj_math::Aabb;
 
-use super::Shape;
+use super::{compute_breps_in_parallel, Shape};
 
 impl Shape for fj::Difference2d {
     type Brep = Sketch;
 
+    #[tracing::instrument(name = "difference_2d", level = "debug", skip_all)]
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
         // This method assumes that `b` is fully contained within `a`:
         // https://github.com/hannobraun/Fornjot/issues/92
@@ -27,13 +29,15 @@ impl Shape for fj::Difference2d {
         let mut exteriors = Vec::new();
         let mut interiors = Vec::new();
 
-        // Can be cleaned up, once `each_ref` and `try_map` are stable:
-        // - https://doc.rust-lang.org/std/primitive.array.html#method.each_ref
-        // - https://doc.rust-lang.org/std/primitive.array.html#method.try_map
         let [a, b] = self.shapes();
-        let [a, b] = [a, b]
-            .map(|shape| shape.compute_brep(config, tolerance, debug_info));
-        let [a, b] = [a?, b?];
+        let [a, b] = compute_breps_in_parallel(
+            [a, b],
+            config,
+            tolerance,
+            stores,
+            debug_info,
+            progress,
+        )?;
 
         if let Some(face) = a.face_iter().next() {
             // If there's at least one face to subtract from, we can proceed.
@@ -49,14 +53,8 @@ impl Shape for fj::Difference2d {
                     "Trying to subtract faces with different surfaces.",
                 );
 
-                for cycle in face.exteriors.as_local() {
-                    let cycle = add_cycle(cycle, false);
-                    exteriors.push(cycle);
-                }
-                for cycle in face.interiors.as_local() {
-                    let cycle = add_cycle(cycle, true);
-                    interiors.push(cycle);
-                }
+                exteriors.extend(face.exteriors());
+                interiors.extend(face.interiors());
             }
 
             for face in b.face_iter() {
@@ -68,10 +66,9 @@ impl Shape for fj::Difference2d {
                     "Trying to subtract faces with different surfaces.",
                 );
 
-                for cycle in face.exteriors.as_local() {
-                    let cycle = add_cycle(cycle, true);
-                    interiors.push(cycle);
-                }
+                // The exteriors of `b` become interiors of the difference, so
+                // they need to wind the other way.
+                interiors.extend(face.exteriors().map(|cycle| cycle.reverse()));
             }
 
             faces.push(Face::new(surface, exteriors, interiors, self.color()));
@@ -88,41 +85,3 @@ impl Shape for fj::Difference2d {
         self.shapes()[0].bounding_volume()
     }
 }
-
-fn add_cycle(cycle: Cycle, reverse: bool) -> Cycle {
-    let mut edges = Vec::new();
-    for edge in cycle.edges {
-        let curve_local = edge.curve.local();
-        let curve_local = if reverse {
-            curve_local.reverse()
-        } else {
-            curve_local
-        };
-
-        let curve_canonical = edge.curve();
-        let curve_canonical = if reverse {
-            curve_canonical.reverse()
-        } else {
-            curve_canonical
-        };
-
-        let vertices = if reverse {
-            edge.vertices.reverse()
-        } else {
-            edge.vertices
-        };
-
-        let edge = Edge {
-            curve: Local::new(curve_local, curve_canonical),
-            vertices,
-        };
-
-        edges.push(edge);
-    }
-
-    if reverse {
-        edges.reverse();
-    }
-
-    Cycle { edges }
-}
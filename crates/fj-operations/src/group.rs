@@ -1,37 +1,297 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
 use fj_kernel::{
-    algorithms::Tolerance,
-    objects::Face,
+    algorithms::{transform_faces, ToleranceStack},
+    objects::{Face, Stores},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
-use fj_math::Aabb;
+use fj_math::{Aabb, Point, Transform};
 
-use super::Shape;
+use super::{compute_all_breps_in_parallel, transform::make_transform, Shape};
 
 impl Shape for fj::Group {
     type Brep = Vec<Face>;
 
+    #[tracing::instrument(name = "group", level = "debug", skip_all)]
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let members = flatten(self);
+
         let mut faces = Vec::new();
+        for brep in compute_all_breps_in_parallel(
+            &members, config, tolerance, stores, debug_info, progress,
+        )? {
+            faces.extend(brep.into_inner());
+        }
 
-        let a = self.a.compute_brep(config, tolerance, debug_info)?;
-        let b = self.b.compute_brep(config, tolerance, debug_info)?;
+        validate(faces, config)
+    }
 
-        faces.extend(a.into_inner());
-        faces.extend(b.into_inner());
+    fn bounding_volume(&self) -> Aabb<3> {
+        flatten(self)
+            .iter()
+            .map(|member| member.bounding_volume())
+            .reduce(|a, b| a.merged(&b))
+            .unwrap_or(Aabb {
+                min: Point::origin(),
+                max: Point::origin(),
+            })
+    }
+}
 
-        validate(faces, config)
+/// A shape within a group, after nested groups have been flattened
+struct Member<'r> {
+    shape: &'r fj::Shape,
+
+    /// The transform that places the shape within the outermost group
+    ///
+    /// `None`, if the shape is not moved.
+    transform: Option<Transform>,
+}
+
+impl Shape for Member<'_> {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: ToleranceStack,
+        stores: &Stores,
+        debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let brep = self
+            .shape
+            .compute_brep(config, tolerance, stores, debug_info, progress)?;
+
+        match &self.transform {
+            Some(transform) => {
+                let mut faces = brep.into_inner();
+                transform_faces(&mut faces, transform);
+                validate(faces, config)
+            }
+            None => Ok(brep),
+        }
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
-        let a = self.a.bounding_volume();
-        let b = self.b.bounding_volume();
+        let aabb = self.shape.bounding_volume();
+
+        match &self.transform {
+            Some(transform) => transform.transform_aabb(&aabb),
+            None => aabb,
+        }
+    }
+}
+
+/// Flatten a group into a list of all the shapes it's made of
+///
+/// Nested groups, and transforms of groups, are resolved, by moving their
+/// members into the outermost group. This way, large assemblies are computed
+/// in a balanced way, no matter how their groups are nested.
+///
+/// Groups and transforms that override the tolerance are not resolved, as the
+/// tolerance must only apply to their own members.
+fn flatten(group: &fj::Group) -> Vec<Member<'_>> {
+    let mut members = Vec::new();
+    flatten_into(group, None, &mut members);
+    members
+}
+
+fn flatten_into<'r>(
+    group: &'r fj::Group,
+    transform: Option<Transform>,
+    members: &mut Vec<Member<'r>>,
+) {
+    for member in group.members() {
+        let placement = member.placement().map(|placement| {
+            make_transform(placement.axis, placement.angle, placement.offset)
+        });
+
+        let mut shape = member.shape();
+        let mut transform = compose(transform, placement);
+
+        // Transforms don't need to be computed separately, so we can look
+        // through them, to find out whether there's a group inside. Unless
+        // they override the tolerance, which must apply to their shape only.
+        while let fj::Shape::Transform(t) = shape {
+            if t.tolerance().is_some() {
+                break;
+            }
+
+            let inner = make_transform(t.axis, t.angle, t.offset);
+
+            shape = &t.shape;
+            transform = compose(transform, Some(inner));
+        }
+
+        match shape {
+            fj::Shape::Group(group) if group.tolerance().is_none() => {
+                flatten_into(group, transform, members);
+            }
+            shape => members.push(Member { shape, transform }),
+        }
+    }
+}
+
+/// Compose two transforms, `inner` being applied first
+fn compose(
+    outer: Option<Transform>,
+    inner: Option<Transform>,
+) -> Option<Transform> {
+    match (outer, inner) {
+        (Some(outer), Some(inner)) => Some(outer * inner),
+        (outer, None) => outer,
+        (None, inner) => inner,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use fj::syntax::{Group as _, Transform as _};
+    use fj_interop::{
+        debug::DebugInfo,
+        progress::{Phase, ProgressHandle},
+    };
+    use fj_kernel::{
+        algorithms::Tolerance, objects::Stores, validation::ValidationConfig,
+    };
+    use fj_math::{Scalar, Vector};
+
+    use crate::Shape as _;
+
+    #[test]
+    fn compute_brep() {
+        let stores = Stores::new();
+
+        let a = fj::Primitive::cuboid([1., 1., 1.]);
+        let b = a.translate([2., 0., 0.]);
+        let group = a.group(&b);
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let mut progress = ProgressHandle::from_callback({
+            let reports = reports.clone();
+            move |progress| reports.lock().unwrap().push(progress)
+        });
+
+        let faces = group
+            .compute_brep(
+                &ValidationConfig::default(),
+                Tolerance::from_scalar(0.001).unwrap().into(),
+                &stores,
+                &mut DebugInfo::new(),
+                &mut progress,
+            )
+            .unwrap();
+        assert_eq!(faces.len(), 12);
+
+        // Both operands report their progress through the same callback, even
+        // though they are computed in parallel.
+        let reports = reports.lock().unwrap();
+        let finished_sweeps = reports
+            .iter()
+            .filter(|progress| {
+                progress.phase == Phase::Sweep
+                    && progress.completed == progress.total
+            })
+            .count();
+        assert_eq!(finished_sweeps, 2);
+    }
+
+    #[test]
+    fn nested_members() {
+        let stores = Stores::new();
+
+        let cube = fj::Primitive::cuboid([1., 1., 1.]);
+
+        let inner = fj::Group::builder()
+            .with_shape(cube.clone())
+            .with_member(
+                fj::GroupMember::new(cube.clone())
+                    .with_placement(fj::Placement::from_offset([2., 0., 0.])),
+            )
+            .build();
+        let group = fj::Group::builder()
+            .with_shape(cube.clone())
+            .with_member(
+                fj::GroupMember::new(inner)
+                    .with_placement(fj::Placement::from_offset([0., 2., 0.]))
+                    .with_name("inner"),
+            )
+            .with_shape(cube.translate([0., 0., 2.]))
+            .build();
+
+        // The nested group, and the transform, are flattened away.
+        let members = super::flatten(&group);
+        assert_eq!(members.len(), 4);
+        assert!(members
+            .iter()
+            .all(|member| matches!(member.shape, fj::Shape::Primitive(_))));
+
+        let cube_aabb = fj::Shape::from(cube).bounding_volume();
+        let aabb = group.bounding_volume();
+        assert!((aabb.min - cube_aabb.min).magnitude() < Scalar::from(1e-9));
+        assert!(
+            (aabb.max - cube_aabb.max - Vector::from([2., 2., 2.])).magnitude()
+                < Scalar::from(1e-9)
+        );
+
+        let faces = group
+            .compute_brep(
+                &ValidationConfig::default(),
+                Tolerance::from_scalar(0.001).unwrap().into(),
+                &stores,
+                &mut DebugInfo::new(),
+                &mut ProgressHandle::new(),
+            )
+            .unwrap();
+        assert_eq!(faces.len(), 24);
+    }
+
+    #[test]
+    fn tolerance_override() {
+        let stores = Stores::new();
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let cube = fj::Primitive::cuboid([1., 1., 1.]);
+        let fine = cube.translate([2., 0., 0.]).with_tolerance(0.0001);
+        let group = fj::Group::from_shapes([
+            fj::Shape::from(cube.clone()),
+            fine.into(),
+            // Looser than the tolerance that applies anyway.
+            cube.translate([4., 0., 0.]).with_tolerance(0.01).into(),
+        ]);
+
+        // The transforms that override the tolerance are not flattened away.
+        let members = super::flatten(&group);
+        assert!(members
+            .iter()
+            .skip(1)
+            .all(|member| matches!(member.shape, fj::Shape::Transform(_))));
+
+        let faces = fj::Shape::from(group)
+            .compute_brep(
+                &ValidationConfig::default(),
+                tolerance.into(),
+                &stores,
+                &mut DebugInfo::new(),
+                &mut ProgressHandle::new(),
+            )
+            .unwrap();
+        let tolerances: Vec<_> =
+            faces.iter().map(|face| face.tolerance()).collect();
 
-        a.merged(&b)
+        let fine = Tolerance::from_scalar(0.0001).unwrap();
+        assert_eq!(tolerances.len(), 18);
+        assert!(tolerances[..6].iter().all(Option::is_none));
+        assert!(tolerances[6..12].iter().all(|t| *t == Some(fine)));
+        assert!(tolerances[12..].iter().all(Option::is_none));
     }
 }
@@ -16,21 +16,31 @@
 
 #![warn(missing_docs)]
 
+pub mod color;
+pub mod declarative;
+pub mod sdf;
+pub mod select;
+pub mod shape_cache;
 pub mod shape_processor;
 
+mod boolean;
 mod difference_2d;
 mod group;
+mod offset;
+mod primitive;
 mod sketch;
 mod sweep;
 mod transform;
 
-use fj_interop::debug::DebugInfo;
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
 use fj_kernel::{
-    algorithms::Tolerance,
-    objects::{Face, Sketch},
+    algorithms::{Tolerance, ToleranceStack},
+    objects::{Face, Sketch, Stores},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
 use fj_math::Aabb;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::join;
 
 /// Implemented for all operations from the [`fj`] crate
 pub trait Shape {
@@ -41,8 +51,10 @@ pub trait Shape {
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError>;
 
     /// Access the axis-aligned bounding box of a shape
@@ -58,35 +70,80 @@ impl Shape for fj::Shape {
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
-        match self {
+        // Every operation passes through here, on its way to the B-rep of its
+        // operands. Check for cancellation between them.
+        progress.check()?;
+
+        let outer = tolerance;
+        let tolerance = match tolerance_override(self) {
+            Some(tolerance) => {
+                outer.tighten(Tolerance::from_scalar(tolerance)?)
+            }
+            None => outer,
+        };
+
+        let faces = match self {
             Self::Shape2d(shape) => validate(
                 shape
-                    .compute_brep(config, tolerance, debug_info)?
+                    .compute_brep(
+                        config, tolerance, stores, debug_info, progress,
+                    )?
                     .into_inner()
                     .into_faces()
                     .into_iter()
                     .collect(),
                 config,
             ),
-            Self::Group(shape) => {
-                shape.compute_brep(config, tolerance, debug_info)
-            }
+            Self::Group(shape) => shape
+                .compute_brep(config, tolerance, stores, debug_info, progress),
             Self::Sweep(shape) => validate(
                 shape
-                    .compute_brep(config, tolerance, debug_info)?
+                    .compute_brep(
+                        config, tolerance, stores, debug_info, progress,
+                    )?
                     .into_inner()
                     .into_faces()
                     .into_iter()
                     .collect(),
                 config,
             ),
-            Self::Transform(shape) => {
-                shape.compute_brep(config, tolerance, debug_info)
-            }
+            Self::Transform(shape) => shape
+                .compute_brep(config, tolerance, stores, debug_info, progress),
+            Self::Primitive(shape) => validate(
+                shape
+                    .compute_brep(
+                        config, tolerance, stores, debug_info, progress,
+                    )?
+                    .into_inner()
+                    .into_faces()
+                    .into_iter()
+                    .collect(),
+                config,
+            ),
+            Self::Offset(shape) => shape
+                .compute_brep(config, tolerance, stores, debug_info, progress),
+            Self::Boolean(shape) => shape
+                .compute_brep(config, tolerance, stores, debug_info, progress),
+        }?;
+
+        // Faces only know about the tolerance that they require, if it's
+        // tighter than the one that their parent passed on. Otherwise,
+        // triangulation uses the tolerance that applies to the whole shape.
+        if tolerance.current() >= outer.current() {
+            return Ok(faces);
         }
+
+        let faces = faces
+            .into_inner()
+            .into_iter()
+            .map(|face| face.with_tolerance(tolerance.current()))
+            .collect();
+        validate(faces, config)
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
@@ -95,6 +152,9 @@ impl Shape for fj::Shape {
             Self::Group(shape) => shape.bounding_volume(),
             Self::Sweep(shape) => shape.bounding_volume(),
             Self::Transform(shape) => shape.bounding_volume(),
+            Self::Primitive(shape) => shape.bounding_volume(),
+            Self::Offset(shape) => shape.bounding_volume(),
+            Self::Boolean(shape) => shape.bounding_volume(),
         }
     }
 }
@@ -105,16 +165,18 @@ impl Shape for fj::Shape2d {
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
+        progress.check()?;
+
         match self {
-            Self::Difference(shape) => {
-                shape.compute_brep(config, tolerance, debug_info)
-            }
-            Self::Sketch(shape) => {
-                shape.compute_brep(config, tolerance, debug_info)
-            }
+            Self::Difference(shape) => shape
+                .compute_brep(config, tolerance, stores, debug_info, progress),
+            Self::Sketch(shape) => shape
+                .compute_brep(config, tolerance, stores, debug_info, progress),
         }
     }
 
@@ -125,3 +187,121 @@ impl Shape for fj::Shape2d {
         }
     }
 }
+
+/// Access the tolerance that an operation overrides, if any
+fn tolerance_override(shape: &fj::Shape) -> Option<f64> {
+    match shape {
+        fj::Shape::Shape2d(_) => None,
+        fj::Shape::Group(shape) => shape.tolerance(),
+        fj::Shape::Sweep(shape) => shape.tolerance(),
+        fj::Shape::Transform(shape) => shape.tolerance(),
+        fj::Shape::Primitive(shape) => shape.tolerance(),
+        fj::Shape::Offset(shape) => shape.tolerance(),
+        fj::Shape::Boolean(shape) => shape.tolerance(),
+    }
+}
+
+/// Compute the boundary representations of two independent shapes in parallel
+///
+/// The debug info of both shapes is collected into `debug_info`, in the order
+/// of the shapes. Both computations report their progress to `progress`.
+fn compute_breps_in_parallel<T>(
+    [a, b]: [&T; 2],
+    config: &ValidationConfig,
+    tolerance: ToleranceStack,
+    stores: &Stores,
+    debug_info: &mut DebugInfo,
+    progress: &mut ProgressHandle,
+) -> Result<[Validated<T::Brep>; 2], ValidationError>
+where
+    T: Shape + Sync,
+    T::Brep: Send,
+{
+    let mut debug_info_b = DebugInfo::new();
+    let mut progress_b = progress.clone();
+
+    let (a, b) = join(
+        || a.compute_brep(config, tolerance, stores, debug_info, progress),
+        || {
+            b.compute_brep(
+                config,
+                tolerance,
+                stores,
+                &mut debug_info_b,
+                &mut progress_b,
+            )
+        },
+    );
+
+    debug_info.merge(debug_info_b);
+
+    Ok([a?, b?])
+}
+
+/// Compute the boundary representations of any number of shapes in parallel
+///
+/// The shapes are split into halves, which are computed in parallel, and so
+/// on recursively. The depth of that recursion only grows logarithmically with
+/// the number of shapes.
+///
+/// Like in [`compute_breps_in_parallel`], the debug info is collected in the
+/// order of the shapes, and all computations report their progress to
+/// `progress`.
+fn compute_all_breps_in_parallel<T>(
+    shapes: &[T],
+    config: &ValidationConfig,
+    tolerance: ToleranceStack,
+    stores: &Stores,
+    debug_info: &mut DebugInfo,
+    progress: &mut ProgressHandle,
+) -> Result<Vec<Validated<T::Brep>>, ValidationError>
+where
+    T: Shape + Sync,
+    T::Brep: Send,
+{
+    match shapes {
+        [] => Ok(Vec::new()),
+        [shape] => Ok(vec![shape
+            .compute_brep(config, tolerance, stores, debug_info, progress)?]),
+        shapes => {
+            let (a, b) = shapes.split_at(shapes.len() / 2);
+
+            let mut debug_info_b = DebugInfo::new();
+            let mut progress_b = progress.clone();
+
+            let (a, b) = join(
+                || {
+                    compute_all_breps_in_parallel(
+                        a, config, tolerance, stores, debug_info, progress,
+                    )
+                },
+                || {
+                    compute_all_breps_in_parallel(
+                        b,
+                        config,
+                        tolerance,
+                        stores,
+                        &mut debug_info_b,
+                        &mut progress_b,
+                    )
+                },
+            );
+
+            debug_info.merge(debug_info_b);
+
+            let mut breps = a?;
+            breps.extend(b?);
+            Ok(breps)
+        }
+    }
+}
+
+/// WebAssembly has no threads, so both shapes are computed one after the other
+#[cfg(target_arch = "wasm32")]
+fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA,
+    B: FnOnce() -> RB,
+{
+    (a(), b())
+}
@@ -0,0 +1,52 @@
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_kernel::{
+    algorithms::{offset, ToleranceStack},
+    objects::{Face, Solid, Stores},
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Vector};
+
+use super::Shape;
+
+impl Shape for fj::Offset {
+    type Brep = Vec<Face>;
+
+    #[tracing::instrument(name = "offset", level = "debug", skip_all)]
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: ToleranceStack,
+        stores: &Stores,
+        debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let faces = self
+            .shape
+            .compute_brep(config, tolerance, stores, debug_info, progress)?
+            .into_inner();
+
+        let solid = Solid::from_faces(faces);
+        let faces = offset(&solid, self.distance, tolerance.current())
+            .into_faces()
+            .into_iter()
+            .collect();
+
+        validate(faces, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let aabb = self.shape.bounding_volume();
+
+        // Shrinking a shape keeps it within its bounding volume. Growing it
+        // expands the volume in all directions.
+        if self.distance <= 0. {
+            return aabb;
+        }
+
+        let expansion = Vector::from([self.distance; 3]);
+        Aabb {
+            min: aabb.min - expansion,
+            max: aabb.max + expansion,
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_kernel::{
+    algorithms::{reverse_face, sweep, EdgeApprox, Tolerance, ToleranceStack},
+    objects::{Cycle, Edge, Face, Sketch, Solid, Stores, Surface},
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Point, Scalar, Triangle};
+
+use super::Shape;
+
+impl Shape for fj::Primitive {
+    type Brep = Solid;
+
+    #[tracing::instrument(
+        name = "primitive",
+        level = "debug",
+        skip_all,
+        fields(kind = ?self.kind())
+    )]
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: ToleranceStack,
+        stores: &Stores,
+        _: &mut DebugInfo,
+        progress: &mut ProgressHandle,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let color = self.color();
+        let tolerance = tolerance.current();
+
+        let solid = match *self.kind() {
+            fj::PrimitiveKind::Cuboid { size: [x, y, z] } => {
+                let [x, y] = [x, y].map(|size| size / 2.);
+
+                let bottom = Face::builder(stores, Surface::xy_plane())
+                    .with_exterior_polygon([[-x, -y], [x, -y], [x, y], [-x, y]])
+                    .with_color(color)
+                    .build();
+
+                let sketch = Sketch::from_faces([bottom]);
+                sweep(
+                    sketch,
+                    [0., 0., z],
+                    0.,
+                    tolerance,
+                    stores,
+                    color,
+                    progress,
+                )?
+            }
+            fj::PrimitiveKind::Cylinder { radius, height } => {
+                let sketch = Sketch::from_faces([disk(stores, radius, color)]);
+                sweep(
+                    sketch,
+                    [0., 0., height],
+                    0.,
+                    tolerance,
+                    stores,
+                    color,
+                    progress,
+                )?
+            }
+            fj::PrimitiveKind::Sphere { radius } => {
+                Solid::from_faces([sphere(radius, tolerance, color)])
+            }
+            fj::PrimitiveKind::Cone { radius, height } => {
+                let bottom = reverse_face(&disk(stores, radius, color));
+                let side = cone_side(radius, height, tolerance, color);
+
+                Solid::from_faces([bottom, side])
+            }
+            fj::PrimitiveKind::Torus {
+                major_radius,
+                minor_radius,
+            } => Solid::from_faces([torus(
+                major_radius,
+                minor_radius,
+                tolerance,
+                color,
+            )]),
+        };
+
+        validate(solid, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let (min, max) = match *self.kind() {
+            fj::PrimitiveKind::Cuboid { size: [x, y, z] } => {
+                ([-x / 2., -y / 2., 0.], [x / 2., y / 2., z])
+            }
+            fj::PrimitiveKind::Cylinder { radius, height }
+            | fj::PrimitiveKind::Cone { radius, height } => {
+                ([-radius, -radius, 0.], [radius, radius, height])
+            }
+            fj::PrimitiveKind::Sphere { radius } => ([-radius; 3], [radius; 3]),
+            fj::PrimitiveKind::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                let r = major_radius + minor_radius;
+                ([-r, -r, -minor_radius], [r, r, minor_radius])
+            }
+        };
+
+        Aabb {
+            min: Point::from(min),
+            max: Point::from(max),
+        }
+    }
+}
+
+/// A disk in the xy-plane, facing upwards
+fn disk(stores: &Stores, radius: f64, color: [u8; 4]) -> Face {
+    let edge = Edge::circle_from_radius(stores, Scalar::from_f64(radius));
+    let cycle = Cycle::new(stores, [edge]);
+
+    Face::new(Surface::xy_plane(), [cycle], [], color)
+}
+
+// The kernel can't represent spheres, cones, and tori exactly yet. Their curved
+// faces are approximated by triangles, like the side faces of a sweep are.
+
+fn sphere(radius: f64, tolerance: Tolerance, color: [u8; 4]) -> Face {
+    let longitudes = circle_angles(radius, tolerance);
+    let num_latitudes = (longitudes.len() / 2).max(2);
+    let latitudes: Vec<_> = (0..=num_latitudes)
+        .map(|i| Scalar::PI * i as f64 / num_latitudes as f64)
+        .collect();
+
+    let point = |latitude: Scalar, longitude: Scalar| {
+        let (sin_lat, cos_lat) = latitude.sin_cos();
+        let (sin_lon, cos_lon) = longitude.sin_cos();
+
+        Point::from([sin_lat * cos_lon, sin_lat * sin_lon, cos_lat]) * radius
+    };
+
+    let mut triangles = Vec::new();
+    for (i, latitude) in latitudes.windows(2).enumerate() {
+        for longitude in longitudes.windows(2) {
+            let a = point(latitude[0], longitude[0]);
+            let b = point(latitude[1], longitude[0]);
+            let c = point(latitude[1], longitude[1]);
+            let d = point(latitude[0], longitude[1]);
+
+            // At the poles, one of the triangles of each quad degenerates.
+            if i + 1 < num_latitudes {
+                triangles.push((Triangle::from([a, b, c]), color));
+            }
+            if i > 0 {
+                triangles.push((Triangle::from([a, c, d]), color));
+            }
+        }
+    }
+
+    Face::Triangles(triangles)
+}
+
+fn cone_side(
+    radius: f64,
+    height: f64,
+    tolerance: Tolerance,
+    color: [u8; 4],
+) -> Face {
+    let apex = Point::from([0., 0., height]);
+    let point = |angle: Scalar| {
+        let (sin, cos) = angle.sin_cos();
+        Point::from([cos * radius, sin * radius, Scalar::ZERO])
+    };
+
+    let triangles = circle_angles(radius, tolerance)
+        .windows(2)
+        .map(|angle| {
+            let triangle = [point(angle[0]), point(angle[1]), apex];
+            (Triangle::from(triangle), color)
+        })
+        .collect();
+
+    Face::Triangles(triangles)
+}
+
+fn torus(
+    major_radius: f64,
+    minor_radius: f64,
+    tolerance: Tolerance,
+    color: [u8; 4],
+) -> Face {
+    let around_axis = circle_angles(major_radius + minor_radius, tolerance);
+    let around_tube = circle_angles(minor_radius, tolerance);
+
+    let point = |axis_angle: Scalar, tube_angle: Scalar| {
+        let (sin_axis, cos_axis) = axis_angle.sin_cos();
+        let (sin_tube, cos_tube) = tube_angle.sin_cos();
+
+        let distance = cos_tube * minor_radius + Scalar::from(major_radius);
+        Point::from([
+            cos_axis * distance,
+            sin_axis * distance,
+            sin_tube * minor_radius,
+        ])
+    };
+
+    let mut triangles = Vec::new();
+    for axis_angle in around_axis.windows(2) {
+        for tube_angle in around_tube.windows(2) {
+            let a = point(axis_angle[0], tube_angle[0]);
+            let b = point(axis_angle[1], tube_angle[0]);
+            let c = point(axis_angle[1], tube_angle[1]);
+            let d = point(axis_angle[0], tube_angle[1]);
+
+            triangles.push((Triangle::from([a, b, c]), color));
+            triangles.push((Triangle::from([a, c, d]), color));
+        }
+    }
+
+    Face::Triangles(triangles)
+}
+
+/// Approximate a circle of the given radius, as angles
+///
+/// The first angle is repeated at the end, closing the circle.
+fn circle_angles(radius: f64, tolerance: Tolerance) -> Vec<Scalar> {
+    // The edge is only used to compute the approximation, and doesn't become
+    // part of the shape. It doesn't need to go into the shape's stores.
+    let edge =
+        Edge::circle_from_radius(&Stores::new(), Scalar::from_f64(radius));
+
+    EdgeApprox::new(&edge, tolerance)
+        .points
+        .into_iter()
+        .map(|point| point.local().t)
+        .collect()
+}
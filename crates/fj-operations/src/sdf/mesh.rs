@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use fj_interop::{
+    mesh::Mesh,
+    progress::{Cancelled, Phase, ProgressHandle},
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use super::Sdf;
+
+/// Configuration for meshing a signed distance field
+#[derive(Clone, Copy, Debug)]
+pub struct SdfConfig {
+    /// The maximum depth of the adaptive grid
+    ///
+    /// The grid starts as a single cube around the shape, and cells that are
+    /// close to the surface are subdivided, until they have reached this depth.
+    /// At the maximum depth, each edge of the grid is divided into `2^depth`
+    /// cells.
+    pub max_depth: u32,
+}
+
+impl Default for SdfConfig {
+    fn default() -> Self {
+        Self { max_depth: 7 }
+    }
+}
+
+/// Mesh the surface of a signed distance field
+///
+/// The surface is expected to be within `aabb`. The field is sampled on an
+/// adaptive grid, which is only refined close to the surface, and then meshed
+/// using marching tetrahedra.
+///
+/// Returns an error, if meshing has been cancelled via `progress`.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn mesh(
+    sdf: &Sdf,
+    aabb: &Aabb<3>,
+    config: &SdfConfig,
+    progress: &mut ProgressHandle,
+) -> Result<Mesh<Point<3>>, Cancelled> {
+    let grid = Grid::new(aabb, config.max_depth);
+
+    // Refine the grid, starting from a single cell. If the center of a cell is
+    // further from the surface than from the corners of the cell, the cell
+    // can't contain any of the surface, and is discarded.
+    let mut cells = vec![[0; 3]];
+    for depth in 0..config.max_depth {
+        progress.check()?;
+        progress.report(
+            Phase::Sampling,
+            depth as usize,
+            config.max_depth as usize,
+        );
+
+        let size = grid.size / 2_f64.powi(depth as i32);
+        let mut next = Vec::new();
+
+        for cell in cells {
+            let center = grid.origin
+                + Vector::from(cell.map(|i| Scalar::from(i as f64 + 0.5)))
+                    * size;
+
+            // The distance fields of some shapes are only bounds, that can be
+            // off by some amount. Hence the generous margin.
+            let half_diagonal = size * 3_f64.sqrt() / 2.;
+            if sdf.distance(center).abs() > half_diagonal * 1.5 {
+                continue;
+            }
+
+            for offset in CORNERS {
+                next.push([0, 1, 2].map(|i| cell[i] * 2 + offset[i]));
+            }
+        }
+
+        cells = next;
+    }
+
+    progress.check()?;
+    progress.report(
+        Phase::Sampling,
+        config.max_depth as usize,
+        config.max_depth as usize,
+    );
+
+    let mut values = HashMap::new();
+    let mut mesh = Mesh::new();
+
+    for cell in cells {
+        let corners = CORNERS.map(|offset| {
+            let corner = [0, 1, 2].map(|i| cell[i] + offset[i]);
+            let value = *values
+                .entry(corner)
+                .or_insert_with(|| sdf.distance(grid.point(corner)));
+            (corner, value)
+        });
+
+        for tetrahedron in TETRAHEDRA {
+            let tetrahedron = tetrahedron.map(|i| corners[i]);
+            for triangle in polygonize(&grid, tetrahedron) {
+                let [a, b, c] = triangle;
+                if (b - a).cross(&(c - a)).magnitude() == Scalar::ZERO {
+                    continue;
+                }
+
+                let center = a + ((b - a) + (c - a)) / 3.;
+                mesh.push_triangle(triangle, sdf.color(center));
+            }
+        }
+    }
+
+    Ok(mesh)
+}
+
+/// The finest level of the adaptive grid
+struct Grid {
+    origin: Point<3>,
+    size: Scalar,
+    cell_size: Scalar,
+}
+
+impl Grid {
+    fn new(aabb: &Aabb<3>, max_depth: u32) -> Self {
+        // The grid is a cube that has some margin around the bounding box, so
+        // surfaces that are on the bounding box are sampled from both sides.
+        let size = aabb
+            .size()
+            .components
+            .into_iter()
+            .max()
+            .unwrap_or(Scalar::ZERO);
+        let size = size.max(Scalar::from(f64::EPSILON)) * 1.1;
+        let origin = aabb.center() - Vector::from([size / 2.; 3]);
+
+        Self {
+            origin,
+            size,
+            cell_size: size / 2_f64.powi(max_depth as i32),
+        }
+    }
+
+    fn point(&self, corner: [u32; 3]) -> Point<3> {
+        self.origin
+            + Vector::from(corner.map(|i| Scalar::from(i as f64)))
+                * self.cell_size
+    }
+}
+
+/// Triangulate the part of the surface that passes through a tetrahedron
+///
+/// Takes the grid corners of the tetrahedron, and the values of the field at
+/// those corners.
+fn polygonize(
+    grid: &Grid,
+    tetrahedron: [([u32; 3], Scalar); 4],
+) -> Vec<[Point<3>; 3]> {
+    let (inside, outside): (Vec<_>, Vec<_>) = tetrahedron
+        .into_iter()
+        .partition(|(_, value)| *value < Scalar::ZERO);
+
+    // The points where the surface crosses the edges of the tetrahedron. Each
+    // point is computed from the ordered corners of its edge, so neighboring
+    // cells compute identical points.
+    let crossing = |a: ([u32; 3], Scalar), b: ([u32; 3], Scalar)| {
+        let (a, b) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        let [pa, pb] = [a.0, b.0].map(|corner| grid.point(corner));
+        pa + (pb - pa) * (a.1 / (a.1 - b.1))
+    };
+
+    let polygon = match (inside.as_slice(), outside.as_slice()) {
+        ([a], [b, c, d]) | ([b, c, d], [a]) => {
+            vec![crossing(*a, *b), crossing(*a, *c), crossing(*a, *d)]
+        }
+        ([a, b], [c, d]) => vec![
+            crossing(*a, *c),
+            crossing(*a, *d),
+            crossing(*b, *d),
+            crossing(*b, *c),
+        ],
+        _ => return Vec::new(),
+    };
+
+    // The triangles need to face outwards, from the inside to the outside
+    // corners.
+    let centroid = |corners: &[([u32; 3], Scalar)]| {
+        let sum = corners
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, (corner, _)| {
+                sum + grid.point(*corner).coords
+            });
+        sum / corners.len() as f64
+    };
+    let outwards = centroid(&outside) - centroid(&inside);
+
+    let mut triangles = Vec::new();
+    for i in 1..polygon.len() - 1 {
+        let [a, b, c] = [polygon[0], polygon[i], polygon[i + 1]];
+        let triangle = if (b - a).cross(&(c - a)).dot(&outwards) < Scalar::ZERO
+        {
+            [a, c, b]
+        } else {
+            [a, b, c]
+        };
+
+        triangles.push(triangle);
+    }
+
+    triangles
+}
+
+/// The corners of a cell, relative to its first corner
+const CORNERS: [[u32; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+
+/// The tetrahedra that a cell is split into, as indices into [`CORNERS`]
+///
+/// All tetrahedra share the diagonal from the first to the last corner. As all
+/// cells are split the same way, the faces of neighboring cells match up.
+const TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 1, 5, 7],
+    [0, 2, 3, 7],
+    [0, 2, 6, 7],
+    [0, 4, 5, 7],
+    [0, 4, 6, 7],
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fj_interop::progress::ProgressHandle;
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::sdf::{mesh, Sdf, SdfConfig};
+
+    #[test]
+    fn sphere() {
+        let shape = fj::Shape::from(fj::Primitive::sphere(1.));
+        let sdf = Sdf::from_shape(&shape);
+
+        let aabb = Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        };
+        let config = SdfConfig { max_depth: 5 };
+        let mesh =
+            mesh(&sdf, &aabb, &config, &mut ProgressHandle::new()).unwrap();
+
+        // All points are on the sphere, within the size of a cell.
+        let cell_size = 2.2 / 32.;
+        for point in mesh.vertices() {
+            let distance = point.coords.magnitude() - Scalar::ONE;
+            assert!(distance.abs() < Scalar::from(cell_size));
+        }
+
+        // The mesh is closed, and oriented consistently.
+        let mut edges: HashMap<[Point<3>; 2], i32> = HashMap::new();
+        let mut volume = 0.;
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.points;
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                *edges.entry([p, q]).or_default() += 1;
+                *edges.entry([q, p]).or_default() -= 1;
+            }
+
+            volume += a.coords.dot(&b.coords.cross(&c.coords)).into_f64() / 6.;
+        }
+        assert!(edges.values().all(|&count| count == 0));
+
+        let expected = 4. / 3. * std::f64::consts::PI;
+        assert!((volume - expected).abs() < 0.1, "{volume} != {expected}");
+    }
+}
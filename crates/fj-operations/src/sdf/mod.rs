@@ -0,0 +1,524 @@
+//! Signed distance field evaluation of shapes
+//!
+//! An alternative to computing the boundary representation of a shape. The
+//! operation tree is converted into a signed distance field, which is then
+//! sampled and meshed. This is robust against all the things that can go
+//! wrong when computing a boundary representation, but the resulting mesh is
+//! only an approximation, with rounded-off sharp edges.
+
+mod mesh;
+
+use fj_math::{Point, Scalar, Transform, Vector};
+
+use crate::{color::resolve_colors, transform::make_transform};
+
+pub use self::mesh::{mesh, SdfConfig};
+
+/// A signed distance field
+///
+/// The distance is negative inside of the shape, and positive outside of it.
+/// It's exact for some shapes, but only a bound for others. Either way, it's
+/// accurate enough close to the surface, which is what meshing relies on.
+///
+/// 2-dimensional shapes have no interior. Their distance field is never
+/// negative, so they don't show up in the mesh.
+#[derive(Clone, Debug)]
+pub struct Sdf {
+    node: Node,
+}
+
+impl Sdf {
+    /// Convert a shape into a signed distance field
+    pub fn from_shape(shape: &fj::Shape) -> Self {
+        Self {
+            node: Node::from_shape(&resolve_colors(shape)),
+        }
+    }
+
+    /// Compute the signed distance of a point from the surface of the shape
+    pub fn distance(&self, point: impl Into<Point<3>>) -> Scalar {
+        self.node.eval(point.into()).0
+    }
+
+    /// Compute the color of the surface closest to a point
+    pub fn color(&self, point: impl Into<Point<3>>) -> [u8; 4] {
+        self.node.eval(point.into()).1
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Profile {
+        profile: Profile,
+        plane: Plane,
+        color: [u8; 4],
+    },
+    Sweep {
+        profile: Profile,
+        plane: Plane,
+        start: Vector<3>,
+        path: Vector<3>,
+        taper: Scalar,
+        color: [u8; 4],
+    },
+    Primitive {
+        primitive: Primitive,
+        color: [u8; 4],
+    },
+    Transform {
+        transform: Transform,
+        node: Box<Node>,
+    },
+    Offset {
+        node: Box<Node>,
+        distance: Scalar,
+    },
+    Group(Vec<Node>),
+    Union(Box<Node>, Box<Node>),
+    Intersection(Box<Node>, Box<Node>),
+    Difference(Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn from_shape(shape: &fj::Shape) -> Self {
+        match shape {
+            fj::Shape::Shape2d(shape) => Self::Profile {
+                profile: Profile::from_shape(shape),
+                plane: Plane::of(shape),
+                color: shape.color(),
+            },
+            fj::Shape::Group(group) => Self::Group(
+                group
+                    .members()
+                    .iter()
+                    .map(|member| {
+                        let node = Self::from_shape(member.shape());
+
+                        match member.placement() {
+                            Some(placement) => Self::Transform {
+                                transform: make_transform(
+                                    placement.axis,
+                                    placement.angle,
+                                    placement.offset,
+                                ),
+                                node: Box::new(node),
+                            },
+                            None => node,
+                        }
+                    })
+                    .collect(),
+            ),
+            fj::Shape::Sweep(sweep) => {
+                let path = Vector::from(sweep.path());
+                let start = if sweep.back_length() == 0. {
+                    Vector::from([0., 0., 0.])
+                } else {
+                    path.normalize() * -sweep.back_length()
+                };
+
+                Self::Sweep {
+                    profile: Profile::from_shape(sweep.shape()),
+                    plane: Plane::of(sweep.shape()),
+                    start,
+                    path: path - start,
+                    taper: Scalar::from(sweep.taper().rad()),
+                    color: sweep.shape().color(),
+                }
+            }
+            fj::Shape::Transform(transform) => Self::Transform {
+                transform: make_transform(
+                    transform.axis,
+                    transform.angle,
+                    transform.offset,
+                ),
+                node: Box::new(Self::from_shape(&transform.shape)),
+            },
+            fj::Shape::Primitive(primitive) => Self::Primitive {
+                primitive: Primitive::from_kind(primitive.kind()),
+                color: primitive.color(),
+            },
+            fj::Shape::Offset(offset) => Self::Offset {
+                node: Box::new(Self::from_shape(&offset.shape)),
+                distance: Scalar::from(offset.distance),
+            },
+            fj::Shape::Boolean(boolean) => {
+                let a = Box::new(Self::from_shape(&boolean.a));
+                let b = Box::new(Self::from_shape(&boolean.b));
+
+                match boolean.operation {
+                    fj::BooleanOperation::Union => Self::Union(a, b),
+                    fj::BooleanOperation::Intersection => {
+                        Self::Intersection(a, b)
+                    }
+                    fj::BooleanOperation::Difference => Self::Difference(a, b),
+                }
+            }
+        }
+    }
+
+    fn eval(&self, point: Point<3>) -> (Scalar, [u8; 4]) {
+        match self {
+            Self::Profile {
+                profile,
+                plane,
+                color,
+            } => {
+                let height = plane.height(point);
+                let in_plane = point - plane.normal * height;
+
+                let outside = profile.distance(in_plane).max(Scalar::ZERO);
+                let distance = Vector::from([outside, height]).magnitude();
+
+                (distance, *color)
+            }
+            Self::Sweep {
+                profile,
+                plane,
+                start,
+                path,
+                taper,
+                color,
+            } => {
+                // Where the point is along the path, with `0` being the start
+                // and `1` being the end of the sweep.
+                let rise = path.dot(&plane.normal);
+                if rise == Scalar::ZERO {
+                    return (Scalar::MAX, *color);
+                }
+                let t = plane.height(point - *start) / rise;
+
+                // Project the point along the path, back into the plane of
+                // the swept shape.
+                let in_plane = point - *start - *path * t;
+
+                // A tapered sweep shrinks along the path, which is the same as
+                // the distance from its sides growing.
+                let (sin, cos) = taper.sin_cos();
+                let across = (profile.distance(in_plane)
+                    + path.magnitude() * t * sin / cos)
+                    * cos;
+                let along = (-t).max(t - Scalar::ONE) * rise.abs();
+
+                (extrusion(across, along), *color)
+            }
+            Self::Primitive { primitive, color } => {
+                (primitive.distance(point), *color)
+            }
+            Self::Transform { transform, node } => {
+                node.eval(transform.inverse_transform_point(&point))
+            }
+            Self::Offset { node, distance } => {
+                let (d, color) = node.eval(point);
+                (d - *distance, color)
+            }
+            Self::Group(nodes) => nodes
+                .iter()
+                .map(|node| node.eval(point))
+                .min_by_key(|(distance, _)| *distance)
+                .unwrap_or((Scalar::MAX, [0; 4])),
+            Self::Union(a, b) => {
+                let (a, b) = (a.eval(point), b.eval(point));
+                if a.0 <= b.0 {
+                    a
+                } else {
+                    b
+                }
+            }
+            Self::Intersection(a, b) => {
+                let (a, b) = (a.eval(point), b.eval(point));
+                if a.0 >= b.0 {
+                    a
+                } else {
+                    b
+                }
+            }
+            Self::Difference(a, b) => {
+                let (a, (b, color_b)) = (a.eval(point), b.eval(point));
+                if a.0 >= -b {
+                    a
+                } else {
+                    (-b, color_b)
+                }
+            }
+        }
+    }
+}
+
+/// The plane that a 2-dimensional shape is attached to
+#[derive(Clone, Copy, Debug)]
+struct Plane {
+    origin: Point<3>,
+    x_axis: Vector<3>,
+    y_axis: Vector<3>,
+    normal: Vector<3>,
+}
+
+impl Plane {
+    fn of(shape: &fj::Shape2d) -> Self {
+        match shape {
+            fj::Shape2d::Sketch(sketch) => Self::from(sketch.plane()),
+            fj::Shape2d::Difference(difference) => {
+                Self::of(&difference.shapes()[0])
+            }
+        }
+    }
+
+    /// Compute the signed distance of a point from the plane
+    fn height(&self, point: Point<3>) -> Scalar {
+        (point - self.origin).dot(&self.normal)
+    }
+
+    /// Convert a point into plane coordinates
+    fn to_local(self, point: Point<3>) -> Point<2> {
+        let offset = point - self.origin;
+        Point::from([offset.dot(&self.x_axis), offset.dot(&self.y_axis)])
+    }
+}
+
+impl From<fj::Plane> for Plane {
+    fn from(plane: fj::Plane) -> Self {
+        Self {
+            origin: Point::from(plane.origin()),
+            x_axis: Vector::from(plane.x_axis()),
+            y_axis: Vector::from(plane.y_axis()),
+            normal: Vector::from(plane.normal()),
+        }
+    }
+}
+
+/// A 2-dimensional shape
+#[derive(Clone, Debug)]
+enum Profile {
+    Circle { plane: Plane, radius: Scalar },
+    Polygon { plane: Plane, points: Vec<Point<2>> },
+    Difference(Box<Profile>, Box<Profile>),
+}
+
+impl Profile {
+    fn from_shape(shape: &fj::Shape2d) -> Self {
+        match shape {
+            fj::Shape2d::Sketch(sketch) => {
+                let plane = Plane::from(sketch.plane());
+
+                match sketch.chain() {
+                    fj::Chain::Circle(circle) => Self::Circle {
+                        plane,
+                        radius: Scalar::from(circle.radius()),
+                    },
+                    fj::Chain::PolyChain(poly_chain) => Self::Polygon {
+                        plane,
+                        points: poly_chain
+                            .to_points()
+                            .into_iter()
+                            .map(Point::from)
+                            .collect(),
+                    },
+                }
+            }
+            fj::Shape2d::Difference(difference) => {
+                let [a, b] = difference.shapes();
+                Self::Difference(
+                    Box::new(Self::from_shape(a)),
+                    Box::new(Self::from_shape(b)),
+                )
+            }
+        }
+    }
+
+    /// Compute the signed distance from a point in the plane of the shape
+    fn distance(&self, point: Point<3>) -> Scalar {
+        match self {
+            Self::Circle { plane, radius } => {
+                plane.to_local(point).coords.magnitude() - *radius
+            }
+            Self::Polygon { plane, points } => {
+                polygon(points, plane.to_local(point))
+            }
+            Self::Difference(a, b) => a.distance(point).max(-b.distance(point)),
+        }
+    }
+}
+
+/// A primitive, placed like the B-rep of [`fj::Primitive`]
+#[derive(Clone, Debug)]
+enum Primitive {
+    Cuboid {
+        size: Vector<3>,
+    },
+    Cylinder {
+        radius: Scalar,
+        height: Scalar,
+    },
+    Sphere {
+        radius: Scalar,
+    },
+    Cone {
+        profile: [Point<2>; 3],
+    },
+    Torus {
+        major_radius: Scalar,
+        minor_radius: Scalar,
+    },
+}
+
+impl Primitive {
+    fn from_kind(kind: &fj::PrimitiveKind) -> Self {
+        match *kind {
+            fj::PrimitiveKind::Cuboid { size } => Self::Cuboid {
+                size: Vector::from(size),
+            },
+            fj::PrimitiveKind::Cylinder { radius, height } => Self::Cylinder {
+                radius: Scalar::from(radius),
+                height: Scalar::from(height),
+            },
+            fj::PrimitiveKind::Sphere { radius } => Self::Sphere {
+                radius: Scalar::from(radius),
+            },
+            fj::PrimitiveKind::Cone { radius, height } => Self::Cone {
+                // A cross-section through the axis of the cone
+                profile: [[-radius, 0.], [radius, 0.], [0., height]]
+                    .map(Point::from),
+            },
+            fj::PrimitiveKind::Torus {
+                major_radius,
+                minor_radius,
+            } => Self::Torus {
+                major_radius: Scalar::from(major_radius),
+                minor_radius: Scalar::from(minor_radius),
+            },
+        }
+    }
+
+    fn distance(&self, point: Point<3>) -> Scalar {
+        let [x, y, z] = point.coords.components;
+        let radial = Vector::from([x, y]).magnitude();
+
+        match self {
+            Self::Cuboid { size } => {
+                let [sx, sy, sz] = size.components;
+                let half_height = sz / 2.;
+
+                boxed([
+                    x.abs() - sx / 2.,
+                    y.abs() - sy / 2.,
+                    (z - half_height).abs() - half_height,
+                ])
+            }
+            Self::Cylinder { radius, height } => {
+                let half_height = *height / 2.;
+                extrusion(
+                    radial - *radius,
+                    (z - half_height).abs() - half_height,
+                )
+            }
+            Self::Sphere { radius } => point.coords.magnitude() - *radius,
+            Self::Cone { profile } => {
+                polygon(profile, Point::from([radial, z]))
+            }
+            Self::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                Vector::from([radial - *major_radius, z]).magnitude()
+                    - *minor_radius
+            }
+        }
+    }
+}
+
+/// Compute the signed distance from a box, given the distances from its sides
+fn boxed<const D: usize>(distances: [Scalar; D]) -> Scalar {
+    let outside = Vector::from(distances.map(|d| d.max(Scalar::ZERO)));
+    let inside = distances
+        .into_iter()
+        .max()
+        .unwrap_or(Scalar::ZERO)
+        .min(Scalar::ZERO);
+
+    outside.magnitude() + inside
+}
+
+/// Compute the signed distance from an extruded shape
+///
+/// Takes the distances across and along the direction of the extrusion.
+fn extrusion(across: Scalar, along: Scalar) -> Scalar {
+    boxed([across, along])
+}
+
+/// Compute the signed distance from a polygon
+fn polygon(points: &[Point<2>], point: Point<2>) -> Scalar {
+    let mut distance = Scalar::MAX;
+    let mut inside = false;
+
+    for (i, &a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+
+        let edge = b - a;
+        let t = ((point - a).dot(&edge) / edge.dot(&edge))
+            .max(Scalar::ZERO)
+            .min(Scalar::ONE);
+        distance = distance.min((point - (a + edge * t)).magnitude());
+
+        // Count the edges that a ray in positive x-direction crosses.
+        let [px, py] = point.coords.components;
+        let [ax, ay] = a.coords.components;
+        let [bx, by] = b.coords.components;
+        if (ay > py) != (by > py) && px < ax + (py - ay) / (by - ay) * (bx - ax)
+        {
+            inside = !inside;
+        }
+    }
+
+    if inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_math::Scalar;
+
+    use super::Sdf;
+
+    #[test]
+    fn primitives() {
+        let cuboid = fj::Shape::from(fj::Primitive::cuboid([2., 2., 2.]));
+        let sdf = Sdf::from_shape(&cuboid);
+
+        assert_eq!(sdf.distance([0., 0., 1.]), Scalar::from(-1.));
+        assert_eq!(sdf.distance([3., 0., 1.]), Scalar::from(2.));
+        assert_eq!(sdf.distance([0., 0., 4.]), Scalar::from(2.));
+
+        let cone = fj::Shape::from(fj::Primitive::cone(1., 1.));
+        let sdf = Sdf::from_shape(&cone);
+
+        assert_eq!(sdf.distance([0., 0., -1.]), Scalar::from(1.));
+        assert!(sdf.distance([0., 0.5, 0.25]) < Scalar::ZERO);
+        assert!(sdf.distance([0., 0.5, 0.75]) > Scalar::ZERO);
+    }
+
+    #[test]
+    fn operations() {
+        let square = [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]];
+        let hole = fj::Sketch::from_circle(fj::Circle::from_radius(0.5));
+        let shape: fj::Shape = square
+            .sketch()
+            .difference(&hole)
+            .sweep([0., 0., 1.])
+            .translate([0., 0., -2.])
+            .into();
+        let sdf = Sdf::from_shape(&shape);
+
+        assert_eq!(sdf.distance([0.75, 0., -1.5]), Scalar::from(-0.25));
+        assert_eq!(sdf.distance([0., 0., -1.5]), Scalar::from(0.5));
+        assert_eq!(sdf.distance([0.75, 0., 0.]), Scalar::from(1.));
+
+        let offset: fj::Shape = shape.offset(0.5).into();
+        let sdf = Sdf::from_shape(&offset);
+
+        assert_eq!(sdf.distance([0.75, 0., 0.]), Scalar::from(0.5));
+    }
+}
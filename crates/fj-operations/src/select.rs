@@ -0,0 +1,251 @@
+//! Resolve queries from the [`fj`] crate against computed shapes
+//!
+//! Models use [`fj::FaceQuery`] and [`fj::EdgeQuery`] to describe which faces
+//! or edges an operation refers to. The functions in this module find the
+//! faces and edges of a shape that match such a query.
+
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
+use fj_kernel::{
+    algorithms::{triangulate, EdgeApprox, Tolerance, TriangulationConfig},
+    objects::{Curve, Edge, Face, Surface},
+};
+use fj_math::{Point, Scalar, Segment, Triangle, Vector};
+
+/// Select the faces that match a query
+pub fn select_faces(
+    faces: &[Face],
+    query: &fj::FaceQuery,
+    tolerance: Tolerance,
+) -> Vec<Face> {
+    let mut selection = faces.to_vec();
+
+    for filter in query.filters() {
+        match *filter {
+            fj::FaceFilter::ParallelTo { normal } => {
+                let normal = Vector::from(normal);
+                selection.retain(|face| match face {
+                    Face::Face(face) => matches!(
+                        plane_normal(&face.surface()),
+                        Some(n) if are_parallel(n, normal)
+                    ),
+                    Face::Triangles(_) => false,
+                });
+            }
+            fj::FaceFilter::Nearest { point } => {
+                let point = Point::from(point);
+                selection = nearest(selection, |face| {
+                    face_distance(face, point, tolerance)
+                });
+            }
+        }
+    }
+
+    selection
+}
+
+/// Select the edges that match a query
+///
+/// Edges that are shared between faces are only selected once.
+pub fn select_edges(
+    faces: &[Face],
+    query: &fj::EdgeQuery,
+    tolerance: Tolerance,
+) -> Vec<Edge> {
+    let mut selection: Vec<Edge> = Vec::new();
+    for face in faces {
+        let face = match face {
+            Face::Face(face) => face,
+            Face::Triangles(_) => continue,
+        };
+
+        for cycle in face.all_cycles() {
+            for edge in cycle.edges() {
+                if !selection.iter().any(|e| are_same(e, &edge, tolerance)) {
+                    selection.push(edge);
+                }
+            }
+        }
+    }
+
+    for filter in query.filters() {
+        match *filter {
+            fj::EdgeFilter::OfLength { min, max } => {
+                let margin = tolerance.inner().into_f64();
+                selection.retain(|edge| {
+                    let length = edge_length(edge, tolerance);
+                    length >= min - margin && length <= max + margin
+                });
+            }
+            fj::EdgeFilter::ParallelTo { direction } => {
+                let direction = Vector::from(direction);
+                selection.retain(|edge| match edge.curve() {
+                    Curve::Line(line) => {
+                        are_parallel(line.direction, direction)
+                    }
+                    Curve::Circle(_) => false,
+                });
+            }
+            fj::EdgeFilter::Nearest { point } => {
+                let point = Point::from(point);
+                selection = nearest(selection, |edge| {
+                    edge_distance(edge, point, tolerance)
+                });
+            }
+        }
+    }
+
+    selection
+}
+
+/// Keep only the item with the smallest distance
+///
+/// If multiple items are equally near, the first one is kept.
+fn nearest<T>(items: Vec<T>, distance: impl Fn(&T) -> Scalar) -> Vec<T> {
+    let mut nearest: Option<(T, Scalar)> = None;
+
+    for item in items {
+        let d = distance(&item);
+        match &nearest {
+            Some((_, nearest)) if *nearest <= d => {}
+            _ => nearest = Some((item, d)),
+        }
+    }
+
+    nearest.map(|(item, _)| item).into_iter().collect()
+}
+
+fn plane_normal(surface: &Surface) -> Option<Vector<3>> {
+    let Surface::SweptCurve(surface) = surface;
+    match surface.curve {
+        Curve::Line(line) => Some(line.direction.cross(&surface.path)),
+        Curve::Circle(_) => None,
+    }
+}
+
+fn are_parallel(a: Vector<3>, b: Vector<3>) -> bool {
+    // Directions come from user input and floating-point computations, so
+    // they are not going to be exactly parallel.
+    const MAX_SINE: f64 = 1e-9;
+
+    a.normalize().cross(&b.normalize()).magnitude() <= Scalar::from(MAX_SINE)
+}
+
+fn face_distance(face: &Face, point: Point<3>, tolerance: Tolerance) -> Scalar {
+    let mesh = triangulate(
+        vec![face.clone()],
+        tolerance,
+        &TriangulationConfig::default(),
+        &mut DebugInfo::new(),
+        &mut ProgressHandle::new(),
+    )
+    .expect("Progress handle has no cancellation token");
+
+    mesh.triangles()
+        .map(|triangle| {
+            Triangle::from_points(triangle.points).distance_to_point(point)
+        })
+        .min()
+        .unwrap_or(Scalar::MAX)
+}
+
+fn edge_points(edge: &Edge, tolerance: Tolerance) -> Vec<Point<3>> {
+    EdgeApprox::new(edge, tolerance)
+        .points
+        .into_iter()
+        .map(|point| point.global())
+        .collect()
+}
+
+fn edge_length(edge: &Edge, tolerance: Tolerance) -> f64 {
+    if let (Curve::Circle(circle), None) = (edge.curve(), edge.vertices()) {
+        return (Scalar::PI * 2. * circle.a.magnitude()).into_f64();
+    }
+
+    edge_points(edge, tolerance)
+        .windows(2)
+        .map(|points| (points[1] - points[0]).magnitude().into_f64())
+        .sum()
+}
+
+fn edge_distance(edge: &Edge, point: Point<3>, tolerance: Tolerance) -> Scalar {
+    edge_points(edge, tolerance)
+        .windows(2)
+        .filter(|points| points[0] != points[1])
+        .map(|points| {
+            Segment::from_points([points[0], points[1]])
+                .distance_to_point(point)
+        })
+        .min()
+        .unwrap_or(Scalar::MAX)
+}
+
+/// Determine whether two edges of different faces are the same edge
+fn are_same(a: &Edge, b: &Edge, tolerance: Tolerance) -> bool {
+    let is_near =
+        |a: Point<3>, b: Point<3>| Point::distance(&a, &b) <= tolerance.inner();
+
+    match (a.vertices(), b.vertices()) {
+        (Some(a), Some(b)) => {
+            let [a0, a1] = a.map(|vertex| vertex.global().position());
+            let [b0, b1] = b.map(|vertex| vertex.global().position());
+
+            (is_near(a0, b0) && is_near(a1, b1))
+                || (is_near(a0, b1) && is_near(a1, b0))
+        }
+        (None, None) => {
+            a.curve() == b.curve() || a.curve() == b.curve().reverse()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_kernel::{
+        algorithms::Tolerance,
+        objects::{Solid, Stores},
+    };
+    use fj_math::Point;
+
+    use super::{select_edges, select_faces};
+
+    #[test]
+    fn select() {
+        let stores = Stores::new();
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let faces: Vec<_> = Solid::cube_from_edge_length(&stores, 1.)
+            .into_faces()
+            .into_iter()
+            .collect();
+
+        let query = fj::faces().parallel_to(&fj::Plane::xy());
+        assert_eq!(select_faces(&faces, &query, tolerance).len(), 2);
+
+        let query = fj::face_nearest([0.1, 0.2, 2.]);
+        let selection = select_faces(&faces, &query, tolerance);
+        assert_eq!(selection.len(), 1);
+        assert_eq!(
+            selection[0].surface().point_from_surface_coords([0., 0.]),
+            Point::from([0., 0., 0.5])
+        );
+
+        let query = fj::edges();
+        assert_eq!(select_edges(&faces, &query, tolerance).len(), 12);
+
+        let query = fj::edges().of_length(0.9..1.1).parallel_to([0., 0., 1.]);
+        assert_eq!(select_edges(&faces, &query, tolerance).len(), 4);
+
+        let query = fj::edges().of_length(2.0..);
+        assert!(select_edges(&faces, &query, tolerance).is_empty());
+
+        let query = fj::edges().nearest([0.5, 0.5, 0.]);
+        let selection = select_edges(&faces, &query, tolerance);
+        assert_eq!(selection.len(), 1);
+        assert!(matches!(
+            selection[0].curve(),
+            fj_kernel::objects::Curve::Line(line)
+                if line.direction.z.abs() == 1.0.into()
+        ));
+    }
+}
@@ -0,0 +1,340 @@
+//! Persistent cache for the meshes of processed shapes
+//!
+//! Processing a complex shape can take a while. The cache stores the triangle
+//! mesh and the approximated edges of a processed shape on disk, so the shape
+//! can be displayed right away, the next time it is opened, even after a
+//! restart.
+//!
+//! Meshes are stored using the binary mesh format of [`fj_interop`]. The
+//! features of the shape aren't cached. A loaded shape has features that are
+//! derived from its mesh, like those of the SDF backend, which still allow for
+//! picking points on the shape and measuring distances between them.
+
+use std::{fs, io, path::PathBuf};
+
+use fj_interop::{
+    debug::DebugInfo,
+    features::Features,
+    mesh::{FaceId, FaceMesh, Mesh},
+    processed_shape::ProcessedShape,
+};
+use fj_kernel::algorithms::{TriangulationConfig, TriangulationStrategy};
+use fj_math::{Aabb, Point, PolyChain, Scalar};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use tracing::warn;
+
+use crate::{
+    sdf::SdfConfig,
+    shape_processor::{Backend, ShapeProcessor},
+    Shape as _,
+};
+
+/// A cache of processed meshes, stored in a directory
+///
+/// Meshes are identified by the shape they were created from, and by the
+/// configuration of the [`ShapeProcessor`], including its tolerance. The
+/// shape is what a model returns, so it depends on the model's source code and
+/// on its parameters. Changing either leads to a different entry, unless the
+/// resulting shape happens to be the same.
+///
+/// Entries are named after a SHA-256 hash of the shape and the configuration,
+/// so they remain valid across releases, as long as neither encoding changes.
+///
+/// Entries are never removed. Delete the directory to clear the cache.
+#[derive(Clone, Debug)]
+pub struct ShapeCache {
+    dir: PathBuf,
+}
+
+impl ShapeCache {
+    /// Create a cache that stores meshes in `dir`
+    ///
+    /// The directory is created, once the first mesh is stored.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Load a shape that has been processed before
+    ///
+    /// Returns a [`ProcessedShape`] with the cached mesh and edges, and with
+    /// features that are derived from the mesh. Returns `None`, if nothing has
+    /// been stored for this shape and configuration, or if it can't be loaded.
+    /// The cache is only an optimization, so the latter is logged, but isn't
+    /// an error.
+    pub fn load(
+        &self,
+        shape: &fj::Shape,
+        shape_processor: &ShapeProcessor,
+    ) -> Option<ProcessedShape> {
+        let path = self.path(shape, shape_processor).ok()?;
+
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("Error reading cached mesh `{}`: {err}", path.display());
+                return None;
+            }
+        };
+        let decoded = bincode::deserialize::<Entry>(&bytes)
+            .map_err(|err| err.to_string())
+            .and_then(|entry| {
+                let mesh = Mesh::from_bytes(&entry.mesh)
+                    .map_err(|err| err.to_string())?;
+                Ok((mesh, entry.edges))
+            });
+        let (mesh, edges) = match decoded {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                warn!("Error decoding cached mesh `{}`: {err}", path.display());
+                return None;
+            }
+        };
+
+        let aabb = if mesh.vertices().next().is_some() {
+            Aabb::<3>::from_points(mesh.vertices())
+        } else {
+            shape.bounding_volume()
+        };
+
+        // The faces of the shape aren't known. Treat the whole mesh as a single
+        // face, so points on the shape can still be picked.
+        let mut features = Features::new();
+        features.push_face_mesh(&FaceMesh {
+            face_id: FaceId(0),
+            triangles: mesh.triangles().collect(),
+        });
+
+        let edges = edges.into_iter().map(PolyChain::from_points).collect();
+
+        Some(ProcessedShape {
+            aabb,
+            mesh,
+            edges,
+            features,
+            debug_info: DebugInfo::new(),
+        })
+    }
+
+    /// Store the mesh and edges of a processed shape
+    ///
+    /// Replaces anything that was previously stored for the same shape and
+    /// configuration.
+    pub fn store(
+        &self,
+        shape: &fj::Shape,
+        shape_processor: &ShapeProcessor,
+        mesh: &Mesh<Point<3>>,
+        edges: &[PolyChain<3>],
+    ) -> Result<(), Error> {
+        let path = self.path(shape, shape_processor)?;
+        let entry = Entry {
+            mesh: mesh.to_bytes()?,
+            edges: edges
+                .iter()
+                .map(|edge| {
+                    edge.points()
+                        .iter()
+                        .map(|point| {
+                            point.coords.components.map(Scalar::into_f64)
+                        })
+                        .collect()
+                })
+                .collect(),
+        };
+        let bytes = bincode::serialize(&entry)?;
+
+        // Write to a temporary file first, so an interrupted write doesn't
+        // leave a broken entry behind.
+        let tmp = path.with_extension("tmp");
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, &path)?;
+
+        Ok(())
+    }
+
+    fn path(
+        &self,
+        shape: &fj::Shape,
+        shape_processor: &ShapeProcessor,
+    ) -> Result<PathBuf, Error> {
+        let mut hasher = Sha256::new();
+        hasher.update([ENTRY_VERSION]);
+        hasher.update(shape.to_bytes()?);
+        hasher.update(config_to_bytes(shape_processor));
+
+        let name: String = hasher.finalize()[..16]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        Ok(self.dir.join(format!("{name}.fjme")))
+    }
+}
+
+/// A cache entry, as it is stored on disk, encoded using bincode 1.x
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    /// The mesh, in the binary mesh format of [`fj_interop`]
+    mesh: Vec<u8>,
+
+    /// The points of each approximated edge
+    edges: Vec<Vec<[f64; 3]>>,
+}
+
+/// The version of the encoding of entries
+///
+/// Must be bumped, whenever [`Entry`] changes. It is part of the hash that
+/// entries are named after, so outdated entries are never loaded.
+const ENTRY_VERSION: u8 = 1;
+
+/// The version of the encoding of the configuration
+///
+/// Must be bumped, whenever the encoding in [`config_to_bytes`] changes.
+const CONFIG_VERSION: u8 = 1;
+
+/// Encode the configuration of the shape processor, to identify entries
+///
+/// Unlike its `Debug` output, this encoding only changes deliberately. All
+/// structs are destructured, so adding a field fails to compile, until it has
+/// been added here.
+fn config_to_bytes(shape_processor: &ShapeProcessor) -> Vec<u8> {
+    // The validation cache only saves work. It doesn't affect the result.
+    let ShapeProcessor {
+        tolerance,
+        triangulation,
+        backend,
+        validation_cache: _,
+    } = shape_processor;
+    let TriangulationConfig {
+        min_angle,
+        max_steiner_points,
+        strategy,
+    } = triangulation;
+
+    let mut bytes = vec![CONFIG_VERSION];
+
+    match tolerance {
+        Some(tolerance) => {
+            bytes.push(1);
+            bytes.extend(tolerance.inner().into_f64().to_le_bytes());
+        }
+        None => bytes.push(0),
+    }
+
+    bytes.extend(min_angle.into_f64().to_le_bytes());
+    bytes.extend((*max_steiner_points as u64).to_le_bytes());
+    bytes.push(match strategy {
+        TriangulationStrategy::Delaunay => 0,
+        TriangulationStrategy::Grid => 1,
+    });
+
+    match backend {
+        Backend::BRep => bytes.push(0),
+        Backend::Sdf(SdfConfig { max_depth }) => {
+            bytes.push(1);
+            bytes.extend(max_depth.to_le_bytes());
+        }
+    }
+
+    bytes
+}
+
+/// Error storing a mesh in the [`ShapeCache`]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// I/O error while writing the mesh
+    #[error("I/O error while writing mesh to cache")]
+    Io(#[from] io::Error),
+
+    /// Error serializing the shape, to identify it
+    #[error("Error serializing shape")]
+    Shape(#[from] fj::format::FormatError),
+
+    /// Error serializing the mesh
+    #[error("Error serializing mesh")]
+    Mesh(#[from] fj_interop::mesh::FormatError),
+
+    /// Error serializing the cache entry
+    #[error("Error serializing cache entry")]
+    Entry(#[from] bincode::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use fj_kernel::algorithms::{
+        Tolerance, TriangulationConfig, TriangulationStrategy,
+    };
+
+    use crate::{
+        sdf::SdfConfig,
+        shape_processor::{Backend, ShapeProcessor},
+    };
+
+    use super::{config_to_bytes, ShapeCache};
+
+    #[test]
+    fn store_and_load() {
+        let dir =
+            env::temp_dir().join(format!("fj-shape-cache-{}", process::id()));
+        let cache = ShapeCache::new(&dir);
+
+        let mut shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        };
+        let shape = fj::Primitive::cuboid([1., 2., 3.]).into();
+        assert!(cache.load(&shape, &shape_processor).is_none());
+
+        let processed = shape_processor.process(&shape).unwrap();
+        cache
+            .store(&shape, &shape_processor, &processed.mesh, &processed.edges)
+            .unwrap();
+
+        let cached = cache.load(&shape, &shape_processor).unwrap();
+        assert_eq!(
+            cached.mesh.triangles().collect::<Vec<_>>(),
+            processed.mesh.triangles().collect::<Vec<_>>(),
+        );
+        assert_eq!(cached.edges, processed.edges);
+        assert_eq!(cached.aabb, processed.aabb);
+
+        // A different shape, or a different tolerance, needs its own mesh.
+        let other_shape = fj::Primitive::cuboid([1., 2., 4.]).into();
+        assert!(cache.load(&other_shape, &shape_processor).is_none());
+
+        shape_processor.tolerance = Some(Tolerance::from_scalar(0.1).unwrap());
+        assert!(cache.load(&shape, &shape_processor).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn config_encoding() {
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.5).unwrap()),
+            triangulation: TriangulationConfig {
+                min_angle: 0.25.into(),
+                max_steiner_points: 2,
+                strategy: TriangulationStrategy::Grid,
+            },
+            backend: Backend::Sdf(SdfConfig { max_depth: 3 }),
+            validation_cache: None,
+        };
+
+        let mut expected = vec![1, 1];
+        expected.extend(0.5_f64.to_le_bytes());
+        expected.extend(0.25_f64.to_le_bytes());
+        expected.extend(2_u64.to_le_bytes());
+        expected.extend([1, 1]);
+        expected.extend(3_u32.to_le_bytes());
+
+        assert_eq!(config_to_bytes(&shape_processor), expected);
+    }
+}
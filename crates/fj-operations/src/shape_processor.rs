@@ -1,25 +1,295 @@
 //! API for processing shapes
 
-use fj_interop::{debug::DebugInfo, processed_shape::ProcessedShape};
+use fj_interop::{
+    debug::DebugInfo,
+    features::Features,
+    mesh::{Color, FaceId, FaceMesh, Mesh},
+    processed_shape::ProcessedShape,
+    progress::{Cancelled, ProgressHandle},
+};
 use fj_kernel::{
-    algorithms::{triangulate, InvalidTolerance, Tolerance},
-    validation::{ValidationConfig, ValidationError},
+    algorithms::{
+        features, section, triangulate, triangulate_faces, BoundingVolume,
+        InvalidTolerance, Tolerance, ToleranceStack, TriangulationConfig,
+    },
+    objects::{Solid, Stores, Surface},
+    validation::{ValidationCache, ValidationConfig, ValidationError},
 };
-use fj_math::Scalar;
+use fj_math::{Aabb, Point, Scalar, Vector};
 
-use crate::Shape as _;
+use crate::{
+    color::resolve_colors,
+    sdf::{self, Sdf, SdfConfig},
+    Shape as _,
+};
 
 /// Processes an [`fj::Shape`] into a [`ProcessedShape`]
+#[derive(Clone, Debug)]
 pub struct ShapeProcessor {
     /// The tolerance value used for creating the triangle mesh
     pub tolerance: Option<Tolerance>,
+
+    /// The configuration used for creating the triangle mesh
+    pub triangulation: TriangulationConfig,
+
+    /// The backend used for evaluating the shape
+    pub backend: Backend,
+
+    /// The cache of objects that have passed validation
+    ///
+    /// Worth setting for a processor that processes the same shape over and
+    /// over, as it changes. Each call to one of the processing methods begins
+    /// a new run of the cache. If this is `None`, all objects are validated
+    /// every time.
+    pub validation_cache: Option<ValidationCache>,
 }
 
 impl ShapeProcessor {
     /// Process an [`fj::Shape`] into [`ProcessedShape`]
     pub fn process(&self, shape: &fj::Shape) -> Result<ProcessedShape, Error> {
+        self.process_with_progress(shape, &mut ProgressHandle::new())
+    }
+
+    /// Process an [`fj::Shape`] into [`ProcessedShape`], reporting progress
+    ///
+    /// Returns [`Error::Cancelled`], if processing has been cancelled via
+    /// `progress`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn process_with_progress(
+        &self,
+        shape: &fj::Shape,
+        progress: &mut ProgressHandle,
+    ) -> Result<ProcessedShape, Error> {
+        let mut processed = None;
+        self.process_levels_of_detail(shape, 1, progress, |shape| {
+            processed = Some(shape);
+        })?;
+
+        Ok(processed.expect("Processing a single level must produce a shape"))
+    }
+
+    /// Process an [`fj::Shape`] into multiple levels of detail
+    ///
+    /// Passes `num_levels` instances of [`ProcessedShape`] to `level`, ordered
+    /// from the coarsest to the finest. The finest level uses the configured
+    /// tolerance, and each coarser level uses a tolerance that is
+    /// [`LEVEL_OF_DETAIL_FACTOR`] times larger than the next finer one.
+    ///
+    /// Each level is passed to `level` as soon as it is ready. This allows the
+    /// caller to display a coarse mesh right away, and replace it, as finer
+    /// levels become available.
+    ///
+    /// The boundary representation of the shape is only computed once, using
+    /// the finest tolerance, and shared by all levels. The SDF backend doesn't
+    /// support multiple levels of detail, and always produces a single level.
+    ///
+    /// Returns [`Error::Cancelled`], if processing has been cancelled via
+    /// `progress`. Levels that were passed to `level` before that remain
+    /// valid.
+    pub fn process_levels_of_detail(
+        &self,
+        shape: &fj::Shape,
+        num_levels: usize,
+        progress: &mut ProgressHandle,
+        level: impl FnMut(ProcessedShape),
+    ) -> Result<(), Error> {
+        self.process_streaming(shape, num_levels, progress, |_| {}, level)
+    }
+
+    /// Process an [`fj::Shape`] into multiple levels of detail, streaming faces
+    ///
+    /// Works like [`ShapeProcessor::process_levels_of_detail`], but also
+    /// passes the triangles of each face of the coarsest level to `face`, as
+    /// soon as that face has been triangulated. This allows the caller to
+    /// display parts of a large shape, before all of it has been processed.
+    ///
+    /// All faces of the coarsest level are passed to `face`, before that level
+    /// is passed to `level`. The SDF backend doesn't produce faces, and passes
+    /// its whole mesh to `face` as a single face.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn process_streaming(
+        &self,
+        shape: &fj::Shape,
+        num_levels: usize,
+        progress: &mut ProgressHandle,
+        mut face: impl FnMut(FaceMesh),
+        mut level: impl FnMut(ProcessedShape),
+    ) -> Result<(), Error> {
+        let shape = &resolve_colors(shape);
         let aabb = shape.bounding_volume();
 
+        if let Backend::Sdf(config) = self.backend {
+            let sdf = Sdf::from_shape(shape);
+            let mesh = sdf::mesh(&sdf, &aabb, &config, progress)?;
+
+            // The bounding volume of the operations can be quite loose. The
+            // mesh gives a tighter one, unless it's empty.
+            let aabb = if mesh.vertices().next().is_some() {
+                Aabb::<3>::from_points(mesh.vertices())
+            } else {
+                aabb
+            };
+
+            let face_mesh = FaceMesh {
+                face_id: FaceId(0),
+                triangles: mesh.triangles().collect(),
+            };
+
+            // There is no exact geometry, but the mesh can still be used to
+            // pick points on the shape and measure distances between them.
+            let mut features = Features::new();
+            features.push_face_mesh(&face_mesh);
+
+            face(face_mesh);
+            level(ProcessedShape {
+                aabb,
+                mesh,
+                edges: Vec::new(),
+                features,
+                debug_info: DebugInfo::new(),
+            });
+
+            return Ok(());
+        }
+
+        let tolerance = self.tolerance(&aabb)?;
+
+        let config = self.validation_config();
+        let stores = Stores::new();
+        let mut debug_info = DebugInfo::new();
+        let shape = shape.compute_brep(
+            &config,
+            ToleranceStack::new(tolerance),
+            &stores,
+            &mut debug_info,
+            progress,
+        )?;
+        progress.check()?;
+
+        // The bounding volume of the operations was only good enough to pick
+        // a tolerance. Now that the B-rep is available, compute a tighter one.
+        let aabb = shape.aabb(tolerance).unwrap_or(aabb);
+
+        for i in (0..num_levels).rev() {
+            let factor = LEVEL_OF_DETAIL_FACTOR.powi(i as i32);
+            let tolerance = Tolerance::from_scalar(tolerance.inner() * factor)?;
+
+            let mut shape_features = features(&shape, tolerance);
+            let edges = shape_features
+                .edges
+                .iter()
+                .map(|edge| edge.approx.clone())
+                .collect();
+            progress.check()?;
+
+            let mut debug_info = debug_info.clone();
+            let mut mesh = Mesh::new();
+            triangulate_faces(
+                shape.clone().into_inner(),
+                tolerance,
+                &self.triangulation,
+                &mut debug_info,
+                progress,
+                |face_mesh| {
+                    for triangle in &face_mesh.triangles {
+                        mesh.push_triangle(triangle.points, triangle.color);
+                    }
+                    shape_features.push_face_mesh(&face_mesh);
+
+                    if i == num_levels - 1 {
+                        face(face_mesh);
+                    }
+                },
+            )?;
+
+            level(ProcessedShape {
+                aabb,
+                mesh,
+                edges,
+                features: shape_features,
+                debug_info,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Compute the cross-section of an [`fj::Shape`], where a plane cuts it
+    ///
+    /// The plane goes through `origin` and is perpendicular to `normal`.
+    /// Returns the triangle mesh of the cross-section, in the provided color.
+    /// It caps the part of the shape that lies behind the plane, on the side
+    /// opposite to where `normal` points.
+    ///
+    /// The SDF backend doesn't compute a boundary representation, which is
+    /// required to compute a cross-section. It always returns an empty mesh.
+    ///
+    /// Returns [`Error::Cancelled`], if processing has been cancelled via
+    /// `progress`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn process_section(
+        &self,
+        shape: &fj::Shape,
+        origin: Point<3>,
+        normal: Vector<3>,
+        color: Color,
+        progress: &mut ProgressHandle,
+    ) -> Result<Mesh<Point<3>>, Error> {
+        if let Backend::Sdf(_) = self.backend {
+            return Ok(Mesh::new());
+        }
+
+        let tolerance = self.tolerance(&shape.bounding_volume())?;
+
+        let config = self.validation_config();
+        let stores = Stores::new();
+        let mut debug_info = DebugInfo::new();
+        let faces = shape.compute_brep(
+            &config,
+            ToleranceStack::new(tolerance),
+            &stores,
+            &mut debug_info,
+            progress,
+        )?;
+        progress.check()?;
+
+        // Any two vectors that are perpendicular to the normal, and to each
+        // other, span the plane. They just need to be ordered such, that the
+        // plane's normal points in the right direction.
+        let normal = normal.normalize();
+        let u = if normal.x.abs() < Scalar::from(0.9) {
+            Vector::unit_x()
+        } else {
+            Vector::unit_y()
+        };
+        let u = (u - normal * u.dot(&normal)).normalize();
+        let v = normal.cross(&u);
+        let plane =
+            Surface::plane_from_points([origin, origin + u, origin + v]);
+
+        let section = section(
+            &Solid::from_faces(faces.into_inner()),
+            plane,
+            tolerance,
+            &stores,
+            color,
+            &mut debug_info,
+        );
+        progress.check()?;
+
+        let mesh = triangulate(
+            section.cross_section.faces().cloned().collect(),
+            tolerance,
+            &self.triangulation,
+            &mut debug_info,
+            progress,
+        )?;
+
+        Ok(mesh)
+    }
+
+    /// Determine the tolerance that is used to process a shape
+    fn tolerance(&self, aabb: &Aabb<3>) -> Result<Tolerance, Error> {
         let tolerance = match self.tolerance {
             None => {
                 // Compute a reasonable default for the tolerance value. To do
@@ -38,28 +308,287 @@ impl ShapeProcessor {
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
 
-        let config = ValidationConfig::default();
-        let mut debug_info = DebugInfo::new();
-        let shape = shape.compute_brep(&config, tolerance, &mut debug_info)?;
-        let mesh = triangulate(shape.into_inner(), tolerance, &mut debug_info);
+        Ok(tolerance)
+    }
 
-        Ok(ProcessedShape {
-            aabb,
-            mesh,
-            debug_info,
-        })
+    /// Begin a new run of the validation cache, and return the configuration
+    fn validation_config(&self) -> ValidationConfig {
+        // Parts of the shape that haven't changed since the last time it was
+        // processed don't need to be validated again.
+        if let Some(cache) = &self.validation_cache {
+            cache.begin_run();
+        }
+
+        ValidationConfig {
+            cache: self.validation_cache.clone(),
+            ..ValidationConfig::default()
+        }
     }
 }
 
+/// The factor between the tolerances of consecutive levels of detail
+///
+/// See [`ShapeProcessor::process_levels_of_detail`].
+pub const LEVEL_OF_DETAIL_FACTOR: f64 = 4.;
+
+/// The backend used for evaluating a shape
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Backend {
+    /// Compute a boundary representation, then triangulate it
+    ///
+    /// This is the default.
+    #[default]
+    BRep,
+
+    /// Convert the shape into a signed distance field, then mesh that
+    ///
+    /// More robust than [`Backend::BRep`], but the result is an approximation
+    /// that doesn't preserve sharp edges. No edges are produced for display.
+    Sdf(SdfConfig),
+}
+
 /// A shape processing error
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Error converting to shape
     #[error("Error converting to shape")]
-    ToShape(#[from] ValidationError),
+    ToShape(#[source] ValidationError),
 
     /// Model has zero size
     #[error("Model has zero size")]
     Extent(#[from] InvalidTolerance),
+
+    /// Processing was cancelled
+    #[error("Processing was cancelled")]
+    Cancelled(#[from] Cancelled),
+}
+
+impl From<ValidationError> for Error {
+    fn from(err: ValidationError) -> Self {
+        match err {
+            ValidationError::Cancelled(cancelled) => Self::Cancelled(cancelled),
+            err => Self::ToShape(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        sync::{Arc, Mutex},
+    };
+
+    use fj_interop::{
+        features::{Feature, Ray},
+        mesh::FaceId,
+        progress::{CancellationToken, Phase, ProgressHandle},
+    };
+    use fj_kernel::{
+        algorithms::{Tolerance, TriangulationConfig},
+        validation::ValidationCache,
+    };
+    use fj_math::{Point, Scalar, Vector};
+
+    use super::{Backend, Error, ShapeProcessor};
+
+    #[test]
+    fn process_levels_of_detail() {
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        };
+        let shape = fj::Primitive::cylinder(1., 1.).into();
+
+        let mut levels = Vec::new();
+        shape_processor
+            .process_levels_of_detail(
+                &shape,
+                3,
+                &mut ProgressHandle::new(),
+                |level| levels.push(level),
+            )
+            .unwrap();
+        assert_eq!(levels.len(), 3);
+
+        // Levels are ordered from coarsest to finest.
+        let num_triangles: Vec<_> = levels
+            .iter()
+            .map(|level| level.mesh.triangles().count())
+            .collect();
+        assert!(num_triangles[0] < num_triangles[1]);
+        assert!(num_triangles[1] < num_triangles[2]);
+    }
+
+    #[test]
+    fn process_streaming() {
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        };
+        let shape = fj::Primitive::cuboid([1., 1., 1.]).into();
+
+        let faces = RefCell::new(Vec::new());
+        let mut levels = Vec::new();
+        shape_processor
+            .process_streaming(
+                &shape,
+                2,
+                &mut ProgressHandle::new(),
+                |face| faces.borrow_mut().push(face),
+                |level| {
+                    // All faces of the coarsest level are streamed first.
+                    assert_eq!(faces.borrow().len(), 6);
+                    levels.push(level);
+                },
+            )
+            .unwrap();
+        assert_eq!(levels.len(), 2);
+
+        let faces = faces.into_inner();
+
+        let face_ids: Vec<_> = faces.iter().map(|face| face.face_id).collect();
+        assert_eq!(face_ids, (0..6).map(FaceId).collect::<Vec<_>>());
+
+        let num_triangles: usize =
+            faces.iter().map(|face| face.triangles.len()).sum();
+        assert_eq!(num_triangles, levels[0].mesh.triangles().count());
+    }
+
+    #[test]
+    fn cancel_during_sweep() {
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        };
+        let shape = fj::Primitive::cylinder(1., 1.).into();
+
+        let token = CancellationToken::new();
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let mut progress = ProgressHandle::from_callback({
+            let token = token.clone();
+            let phases = phases.clone();
+            move |progress| {
+                phases.lock().unwrap().push(progress.phase);
+                token.cancel();
+            }
+        })
+        .with_cancellation_token(token);
+
+        let result =
+            shape_processor.process_with_progress(&shape, &mut progress);
+        assert!(matches!(result, Err(Error::Cancelled(_))));
+
+        // The sweep stopped at its first face, before triangulation started.
+        assert_eq!(*phases.lock().unwrap(), [Phase::Sweep]);
+    }
+
+    #[test]
+    fn validation_cache() {
+        let cache = ValidationCache::new();
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: Some(cache.clone()),
+        };
+
+        shape_processor
+            .process(&fj::Primitive::cuboid([1., 1., 1.]).into())
+            .unwrap();
+        let len = cache.len();
+        assert!(len > 0);
+
+        // A processor with its own cache leaves the entries of others alone.
+        let other = ShapeProcessor {
+            validation_cache: Some(ValidationCache::new()),
+            ..shape_processor
+        };
+        for _ in 0..2 {
+            other
+                .process(&fj::Primitive::cylinder(1., 1.).into())
+                .unwrap();
+        }
+        assert_eq!(cache.len(), len);
+    }
+
+    #[test]
+    fn process_features() {
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        };
+        let shape = fj::Primitive::cuboid([1., 2., 3.]).into();
+
+        let processed = shape_processor.process(&shape).unwrap();
+        let features = &processed.features;
+
+        assert_eq!(features.vertices.len(), 8);
+        assert_eq!(features.edges.len(), 12);
+        assert_eq!(features.faces.len(), 6);
+        assert_eq!(processed.edges.len(), 12);
+
+        // Pick the top and bottom faces, by looking through the cuboid.
+        let ray = Ray {
+            origin: Point::from([0.1, 0.2, 10.]),
+            direction: Vector::from([0., 0., -1.]),
+        };
+        let top = features.cast_ray(&ray).unwrap();
+        let bottom = features
+            .cast_ray(&Ray {
+                origin: Point::from([0.1, 0.2, -10.]),
+                direction: -ray.direction,
+            })
+            .unwrap();
+
+        let top = features.pick(&top, Scalar::from(0.01)).feature;
+        let bottom = features.pick(&bottom, Scalar::from(0.01)).feature;
+        assert!(matches!(top, Feature::Face(_)));
+        assert!(matches!(bottom, Feature::Face(_)));
+
+        let measurement = features.measure(top, bottom).unwrap();
+        assert_eq!(measurement.distance, Scalar::from(3.));
+        assert_eq!(measurement.angle, Some(Scalar::ZERO));
+    }
+
+    #[test]
+    fn process_section() {
+        let shape_processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            triangulation: TriangulationConfig::default(),
+            backend: Backend::BRep,
+            validation_cache: None,
+        };
+        let shape = fj::Primitive::cuboid([1., 2., 3.]).into();
+
+        let mesh = shape_processor
+            .process_section(
+                &shape,
+                Point::origin(),
+                Vector::from([0., 0., 1.]),
+                [255, 0, 0, 255],
+                &mut ProgressHandle::new(),
+            )
+            .unwrap();
+
+        let mut area = Scalar::ZERO;
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.points;
+            area += (b - a).cross(&(c - a)).magnitude() / 2.;
+
+            for point in triangle.points {
+                assert_eq!(point.z, Scalar::ZERO);
+            }
+        }
+        assert_eq!(area, Scalar::from(2.));
+    }
 }
@@ -1,32 +1,61 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
 use fj_kernel::{
-    algorithms::Tolerance,
-    objects::{Cycle, Edge, Face, Sketch, Surface},
+    algorithms::ToleranceStack,
+    local::Local,
+    objects::{
+        Curve, Cycle, Edge, Face, Sketch, Stores, Surface, SweptCurve,
+        VerticesOfEdge,
+    },
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
-use fj_math::{Aabb, Point, Scalar};
+use fj_math::{Aabb, Circle, Line, Point, Scalar, Vector};
 
 use super::Shape;
 
 impl Shape for fj::Sketch {
     type Brep = Sketch;
 
+    #[tracing::instrument(name = "sketch", level = "debug", skip_all)]
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        _: Tolerance,
+        _: ToleranceStack,
+        stores: &Stores,
         _: &mut DebugInfo,
+        _: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
-        let surface = Surface::xy_plane();
+        let plane = self.plane();
+        let surface = Surface::SweptCurve(SweptCurve {
+            curve: Curve::Line(Line {
+                origin: Point::from(plane.origin()),
+                direction: Vector::from(plane.x_axis()),
+            }),
+            path: Vector::from(plane.y_axis()),
+        });
 
         let face = match self.chain() {
             fj::Chain::Circle(circle) => {
                 // Circles have just a single round edge with no vertices. So
                 // none need to be added here.
 
-                let edge =
-                    Edge::circle_from_radius(Scalar::from_f64(circle.radius()));
-                let cycle = Cycle { edges: vec![edge] };
+                let radius = Scalar::from_f64(circle.radius());
+                let curve_local = Curve::Circle(Circle {
+                    center: Point::origin(),
+                    a: Vector::from([radius, Scalar::ZERO]),
+                    b: Vector::from([Scalar::ZERO, radius]),
+                });
+                let curve_global =
+                    stores.curves.insert(Curve::Circle(Circle {
+                        center: Point::from(plane.origin()),
+                        a: Vector::from(plane.x_axis()) * radius,
+                        b: Vector::from(plane.y_axis()) * radius,
+                    }));
+
+                let edge = Edge {
+                    curve: Local::new(curve_local, curve_global),
+                    vertices: VerticesOfEdge::none(),
+                };
+                let cycle = Cycle::new(stores, [edge]);
 
                 Face::new(surface, vec![cycle], Vec::new(), self.color())
             }
@@ -34,7 +63,7 @@ impl Shape for fj::Sketch {
                 let points =
                     poly_chain.to_points().into_iter().map(Point::from);
 
-                Face::builder(surface)
+                Face::builder(stores, surface)
                     .with_exterior_polygon(points)
                     .with_color(self.color())
                     .build()
@@ -46,18 +75,20 @@ impl Shape for fj::Sketch {
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
-        match self.chain() {
-            fj::Chain::Circle(circle) => Aabb {
-                min: Point::from([-circle.radius(), -circle.radius(), 0.0]),
-                max: Point::from([circle.radius(), circle.radius(), 0.0]),
-            },
-            fj::Chain::PolyChain(poly_chain) => Aabb::<3>::from_points(
-                poly_chain
-                    .to_points()
-                    .into_iter()
-                    .map(Point::from)
-                    .map(Point::to_xyz),
-            ),
-        }
+        let plane = self.plane();
+
+        let points = match self.chain() {
+            fj::Chain::Circle(circle) => {
+                let r = circle.radius();
+                vec![[-r, -r], [r, -r], [r, r], [-r, r]]
+            }
+            fj::Chain::PolyChain(poly_chain) => poly_chain.to_points(),
+        };
+
+        Aabb::<3>::from_points(
+            points
+                .into_iter()
+                .map(|point| Point::from(plane.point_to_global(point))),
+        )
     }
 }
@@ -1,39 +1,84 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
 use fj_kernel::{
-    algorithms::{sweep, Tolerance},
-    objects::Solid,
+    algorithms::{sweep, ToleranceStack, TransformObject},
+    objects::{Solid, Stores},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
-use fj_math::{Aabb, Vector};
+use fj_math::{Aabb, Scalar, Vector};
 
 use super::Shape;
 
 impl Shape for fj::Sweep {
     type Brep = Solid;
 
+    #[tracing::instrument(
+        name = "sweep",
+        level = "debug",
+        skip_all,
+        fields(
+            path = ?self.path(),
+            back_length = self.back_length(),
+            taper = ?self.taper(),
+        )
+    )]
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
-        let sketch =
-            self.shape().compute_brep(config, tolerance, debug_info)?;
-        let path = Vector::from(self.path());
+        let sketch = self
+            .shape()
+            .compute_brep(config, tolerance, stores, debug_info, progress)?;
+        let start = start_of(self);
+        let path = Vector::from(self.path()) - start;
+        let taper = self.taper().rad();
         let color = self.shape().color();
 
-        let solid = sweep(sketch.into_inner(), path, tolerance, color);
+        let sketch = sketch.into_inner().translate(start);
+
+        let solid = sweep(
+            sketch,
+            path,
+            taper,
+            tolerance.current(),
+            stores,
+            color,
+            progress,
+        )?;
         validate(solid, config)
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
-        self.shape()
-            .bounding_volume()
-            .merged(&Aabb::<3>::from_points(
-                self.shape()
-                    .bounding_volume()
-                    .vertices()
-                    .map(|v| v + self.path()),
-            ))
+        let start = start_of(self);
+        let path = Vector::from(self.path()) - start;
+
+        // If the sweep is tapered outwards, the end of the sweep grows beyond
+        // the bounding volume of the swept shape.
+        let growth = path.magnitude() * Scalar::from(-self.taper().rad()).tan();
+        let growth = Vector::from([growth.max(Scalar::ZERO); 3]);
+
+        let shape = self.shape().bounding_volume();
+        let end = Aabb {
+            min: shape.min - growth,
+            max: shape.max + growth,
+        };
+
+        Aabb::<3>::from_points(shape.vertices().map(|v| v + start)).merged(
+            &Aabb::<3>::from_points(end.vertices().map(|v| v + self.path())),
+        )
     }
 }
+
+/// The offset from the swept shape to where the sweep starts
+///
+/// That's not the shape itself, if the sweep extends in both directions.
+fn start_of(sweep: &fj::Sweep) -> Vector<3> {
+    if sweep.back_length() == 0. {
+        return Vector::from([0., 0., 0.]);
+    }
+
+    Vector::from(sweep.path()).normalize() * -sweep.back_length()
+}
@@ -1,7 +1,7 @@
-use fj_interop::debug::DebugInfo;
+use fj_interop::{debug::DebugInfo, progress::ProgressHandle};
 use fj_kernel::{
-    algorithms::{transform_faces, Tolerance},
-    objects::Face,
+    algorithms::{transform_faces, ToleranceStack},
+    objects::{Face, Stores},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
 use fj_math::{Aabb, Transform, Vector};
@@ -11,29 +11,42 @@ use super::Shape;
 impl Shape for fj::Transform {
     type Brep = Vec<Face>;
 
+    #[tracing::instrument(name = "transform", level = "debug", skip_all)]
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        tolerance: Tolerance,
+        tolerance: ToleranceStack,
+        stores: &Stores,
         debug_info: &mut DebugInfo,
+        progress: &mut ProgressHandle,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
         let mut faces = self
             .shape
-            .compute_brep(config, tolerance, debug_info)?
+            .compute_brep(config, tolerance, stores, debug_info, progress)?
             .into_inner();
 
-        transform_faces(&mut faces, &make_transform(self));
+        transform_faces(&mut faces, &transform_of(self));
 
         validate(faces, config)
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
-        make_transform(self).transform_aabb(&self.shape.bounding_volume())
+        transform_of(self).transform_aabb(&self.shape.bounding_volume())
     }
 }
 
-fn make_transform(transform: &fj::Transform) -> Transform {
-    let axis = Vector::from(transform.axis).normalize();
-    Transform::translation(transform.offset)
-        * Transform::rotation(axis * transform.angle.rad())
+/// Make a transform that rotates around `axis`, then translates by `offset`
+///
+/// This is how both [`fj::Transform`] and [`fj::Placement`] are defined.
+pub(crate) fn make_transform(
+    axis: [f64; 3],
+    angle: fj::Angle,
+    offset: [f64; 3],
+) -> Transform {
+    let axis = Vector::from(axis).normalize();
+    Transform::translation(offset) * Transform::rotation(axis * angle.rad())
+}
+
+fn transform_of(transform: &fj::Transform) -> Transform {
+    make_transform(transform.axis, transform.angle, transform.offset)
 }
@@ -13,15 +13,25 @@ pub fn model(_: TokenStream, input: TokenStream) -> TokenStream {
         inputs.iter().map(|inp| parse_quote!(#inp)).collect();
 
     let mut parameter_extraction = Vec::new();
+    let mut parameter_metadata = Vec::new();
 
     let mut min_checks = Vec::new();
     let mut max_checks = Vec::new();
     for arg in args {
         let ident = arg.ident;
         let ty = arg.ty;
+
+        let mut metadata_bounds = Vec::new();
+
         if let Some(attr) = arg.attr {
             if let Some(default) = attr.get_default() {
                 let def = default.val;
+                metadata_bounds.push(quote! {
+                    .with_default({
+                        let default: #ty = #def;
+                        default.to_string()
+                    })
+                });
                 parameter_extraction.push(quote! {
                     let #ident: #ty = args.get(stringify!(#ident))
                             .map(|arg| arg.parse().unwrap())
@@ -37,6 +47,9 @@ pub fn model(_: TokenStream, input: TokenStream) -> TokenStream {
 
             if let Some(minimum) = attr.get_minimum() {
                 let min = minimum.val;
+                metadata_bounds.push(quote! {
+                    .with_minimum(fj::ParameterBound::to_f64(#min))
+                });
                 min_checks.push(quote! {
                 if #ident < #min {
                     panic!("Value of `{}` must not be smaller than: {}",stringify!(#ident), #min);
@@ -45,6 +58,9 @@ pub fn model(_: TokenStream, input: TokenStream) -> TokenStream {
             }
             if let Some(maximum) = attr.get_maximum() {
                 let max = maximum.val;
+                metadata_bounds.push(quote! {
+                    .with_maximum(fj::ParameterBound::to_f64(#max))
+                });
                 max_checks.push(quote! {
                 if #ident > #max {
                     panic!("Value of `{}` must not be larger than: {}", stringify!(#ident), #max);
@@ -58,6 +74,15 @@ pub fn model(_: TokenStream, input: TokenStream) -> TokenStream {
                         .expect(format!("A value for `{}` has to be provided since no default is specified",stringify!(#ident)).as_str());
             });
         }
+
+        parameter_metadata.push(quote! {
+            fj::ParameterMetadata::new(
+                stringify!(#ident).to_owned(),
+                stringify!(#ty).to_owned(),
+                #ident.to_string(),
+            )
+            #(#metadata_bounds)*
+        });
     }
     let block = item.block;
 
@@ -81,6 +106,62 @@ pub fn model(_: TokenStream, input: TokenStream) -> TokenStream {
         )*
         #block
     }
+
+    #[no_mangle]
+    pub extern "C" fn fj_parameters(
+        args: &std::collections::HashMap<String, String>,
+    ) -> fj::FfiVec<fj::ParameterMetadata> {
+        #(
+            #parameter_extraction
+        )*
+        fj::FfiVec::from_vec(vec![
+            #(
+                #parameter_metadata
+            ),*
+        ])
+    }
+    }
+    .into()
+}
+
+/// Marks a function that saves the model's state before it is unloaded
+///
+/// The function must have the signature `fn() -> Vec<u8>`. When the model is
+/// reloaded, the returned state is passed to the function marked with
+/// `#[fj::on_load]`.
+#[proc_macro_attribute]
+pub fn on_unload(_: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as syn::ItemFn);
+    let ident = &item.sig.ident;
+
+    quote! {
+        #item
+
+        #[no_mangle]
+        pub extern "C" fn fj_on_unload() -> fj::FfiVec<u8> {
+            fj::FfiVec::from_vec(#ident())
+        }
+    }
+    .into()
+}
+
+/// Marks a function that restores the model's state after it was reloaded
+///
+/// The function must have the signature `fn(Vec<u8>)`. It receives the state
+/// returned by the function marked with `#[fj::on_unload]`, before the model
+/// was last unloaded. It is not called, if there is no such state.
+#[proc_macro_attribute]
+pub fn on_load(_: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as syn::ItemFn);
+    let ident = &item.sig.ident;
+
+    quote! {
+        #item
+
+        #[no_mangle]
+        pub extern "C" fn fj_on_load(state: &fj::FfiVec<u8>) {
+            #ident(state.to_vec());
+        }
     }
     .into()
 }
@@ -0,0 +1,329 @@
+//! # Fornjot Python Bindings
+//!
+//! This library is part of the [Fornjot] ecosystem. Fornjot is an open-source,
+//! code-first CAD application; and collection of libraries that make up the CAD
+//! application, but can be used independently.
+//!
+//! This library provides a Python module, `fornjot`, that loads and evaluates
+//! Fornjot models, and exports the shapes they return. This allows models to
+//! be driven from Python scripts and notebooks, for example to evaluate a
+//! model for many sets of parameters.
+//!
+//! ``` python
+//! import fornjot
+//!
+//! model = fornjot.Model("models/spacer")
+//! for outer in [50, 60, 70]:
+//!     shape = model.load_once({"outer": outer, "inner": 25})
+//!     shape.export(f"spacer-{outer}.3mf")
+//! ```
+//!
+//! The module is built using [maturin]. Run `maturin develop` in this crate's
+//! directory, to install it into the current Python environment.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+//! [maturin]: https://www.maturin.rs/
+
+#![warn(missing_docs)]
+
+use std::{collections::HashMap, error, path::PathBuf, time::Duration};
+
+use fj_kernel::algorithms::{Tolerance, TriangulationConfig};
+use fj_operations::shape_processor::{Backend, ShapeProcessor};
+use pyo3::{
+    create_exception,
+    exceptions::PyException,
+    prelude::*,
+    types::{PyBool, PyDict},
+};
+
+create_exception!(
+    fornjot,
+    FornjotError,
+    PyException,
+    "An error that occurred while loading a model or processing a shape"
+);
+
+/// A Fornjot model
+///
+/// The model is compiled, when it's loaded for the first time, and whenever
+/// its source code has changed since.
+#[pyclass(module = "fornjot")]
+struct Model {
+    inner: fj_host::Model,
+}
+
+#[pymethods]
+impl Model {
+    /// Initialize the model using the path to its crate
+    ///
+    /// If `target_dir` is not provided, the target directory is assumed to be
+    /// located within the model path. If a `timeout` (in seconds) is provided,
+    /// compiling or evaluating the model fails, if it takes longer than that.
+    #[new]
+    #[pyo3(signature = (path, target_dir = None, timeout = None))]
+    fn new(
+        path: PathBuf,
+        target_dir: Option<PathBuf>,
+        timeout: Option<f64>,
+    ) -> PyResult<Self> {
+        let mut inner =
+            fj_host::Model::from_path(path, target_dir).map_err(host_error)?;
+        if let Some(timeout) = timeout {
+            inner = inner.with_timeout(Duration::from_secs_f64(timeout));
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Compile and load the model, then evaluate it with the parameters
+    ///
+    /// Parameters are passed as a `dict` that maps parameter names to values.
+    /// Parameters that aren't passed use their default values.
+    #[pyo3(signature = (parameters = None))]
+    fn load_once(
+        &self,
+        py: Python,
+        parameters: Option<Parameters>,
+    ) -> PyResult<Shape> {
+        let parameters = parameters.unwrap_or_default();
+        let shape = py
+            .allow_threads(|| self.inner.load_once(&parameters.0))
+            .map_err(host_error)?;
+
+        Ok(Shape { inner: shape })
+    }
+
+    /// Compile and load the model once, then evaluate it for each set of
+    /// parameters
+    ///
+    /// This is much faster than calling `load_once` repeatedly. Returns the
+    /// shapes in the order of the sets of parameters.
+    fn load_for_each(
+        &self,
+        py: Python,
+        parameters: Vec<Parameters>,
+    ) -> PyResult<Vec<Shape>> {
+        let parameters: Vec<_> = parameters
+            .into_iter()
+            .map(|parameters| parameters.0)
+            .collect();
+        let shapes = py
+            .allow_threads(|| self.inner.load_for_each(&parameters))
+            .map_err(host_error)?;
+
+        Ok(shapes.into_iter().map(|inner| Shape { inner }).collect())
+    }
+
+    /// Evaluate the model again, without compiling it first
+    ///
+    /// Uses the library from the last time the model was loaded.
+    #[pyo3(signature = (parameters = None))]
+    fn evaluate(
+        &self,
+        py: Python,
+        parameters: Option<Parameters>,
+    ) -> PyResult<Shape> {
+        let parameters = parameters.unwrap_or_default();
+        let shape = py
+            .allow_threads(|| self.inner.evaluate(&parameters.0))
+            .map_err(host_error)?;
+
+        Ok(Shape { inner: shape })
+    }
+
+    /// Describe the parameters of the model
+    ///
+    /// Returns a `list` with a `dict` for each parameter, as of the last time
+    /// the model was evaluated. The list is empty, if the model hasn't been
+    /// evaluated yet.
+    fn parameter_metadata(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.inner
+            .parameter_metadata()
+            .into_iter()
+            .map(|parameter| {
+                let dict = PyDict::new(py);
+                dict.set_item("name", parameter.name())?;
+                dict.set_item("type", parameter.ty())?;
+                dict.set_item("value", parameter.value())?;
+                dict.set_item("default", parameter.default())?;
+                dict.set_item("minimum", parameter.minimum())?;
+                dict.set_item("maximum", parameter.maximum())?;
+                Ok(dict.into())
+            })
+            .collect()
+    }
+
+    /// Load a preset from the model's `presets.toml`
+    ///
+    /// Returns the parameters of the preset, as a `dict`.
+    fn parameter_preset(
+        &self,
+        preset: &str,
+    ) -> PyResult<HashMap<String, String>> {
+        let parameters =
+            self.inner.parameter_preset(preset).map_err(host_error)?;
+        Ok(parameters.0)
+    }
+}
+
+/// A shape that a model has returned
+#[pyclass(module = "fornjot")]
+struct Shape {
+    inner: fj::Shape,
+}
+
+#[pymethods]
+impl Shape {
+    /// Triangulate the shape
+    ///
+    /// Returns a tuple of the vertices, each a tuple of 3 coordinates, and
+    /// the triangles, each a tuple of 3 vertex indices. If no `tolerance` is
+    /// provided, one is derived from the size of the shape.
+    #[pyo3(signature = (tolerance = None))]
+    fn triangulate(
+        &self,
+        py: Python,
+        tolerance: Option<f64>,
+    ) -> PyResult<(Vertices, Triangles)> {
+        let mesh = py.allow_threads(|| {
+            shape_processor(tolerance)?
+                .process(&self.inner)
+                .map_err(error)
+        })?;
+        let mesh = mesh.mesh;
+
+        let vertices = mesh
+            .vertices()
+            .map(|point| [point.x, point.y, point.z].map(|c| c.into_f64()))
+            .collect();
+        let indices: Vec<_> = mesh.indices().collect();
+        let triangles = indices
+            .chunks(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect();
+
+        Ok((vertices, triangles))
+    }
+
+    /// Triangulate the shape, then export it to a file
+    ///
+    /// The file format is selected using the file extension of `path`. 3MF,
+    /// STL, SVG, and DXF are supported. If no `tolerance` is provided, one is
+    /// derived from the size of the shape.
+    #[pyo3(signature = (path, tolerance = None))]
+    fn export(
+        &self,
+        py: Python,
+        path: PathBuf,
+        tolerance: Option<f64>,
+    ) -> PyResult<()> {
+        py.allow_threads(|| {
+            let shape = shape_processor(tolerance)?
+                .process(&self.inner)
+                .map_err(error)?;
+            fj_export::export(&shape.mesh, &path).map_err(error)
+        })
+    }
+}
+
+/// The vertices of a triangle mesh, as returned to Python
+type Vertices = Vec<[f64; 3]>;
+
+/// The triangles of a triangle mesh, as vertex indices
+type Triangles = Vec<[u32; 3]>;
+
+/// Compile and load the model at `path`, then evaluate it with the parameters
+///
+/// This is a shortcut for `Model(path).load_once(parameters)`.
+#[pyfunction]
+#[pyo3(signature = (path, parameters = None))]
+fn load_once(
+    py: Python,
+    path: PathBuf,
+    parameters: Option<Parameters>,
+) -> PyResult<Shape> {
+    Model::new(path, None, None)?.load_once(py, parameters)
+}
+
+/// The Python module
+#[pymodule]
+fn fornjot(py: Python, module: &PyModule) -> PyResult<()> {
+    module.add("FornjotError", py.get_type::<FornjotError>())?;
+    module.add_class::<Model>()?;
+    module.add_class::<Shape>()?;
+    module.add_function(wrap_pyfunction!(load_once, module)?)?;
+
+    Ok(())
+}
+
+/// Parameters that are passed to a model, converted from a Python `dict`
+///
+/// Values of any type are accepted, and converted to strings. Booleans are
+/// converted to the spelling that Rust understands.
+struct Parameters(fj_host::Parameters);
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self(fj_host::Parameters::empty())
+    }
+}
+
+impl<'source> FromPyObject<'source> for Parameters {
+    fn extract(object: &'source PyAny) -> PyResult<Self> {
+        let dict: &PyDict = object.downcast()?;
+
+        let mut parameters = fj_host::Parameters::empty();
+        for (key, value) in dict {
+            let key: String = key.extract()?;
+            let value = match value.downcast::<PyBool>() {
+                Ok(value) => value.is_true().to_string(),
+                Err(_) => value.str()?.to_str()?.to_string(),
+            };
+
+            parameters.insert(key, value);
+        }
+
+        Ok(Self(parameters))
+    }
+}
+
+fn shape_processor(tolerance: Option<f64>) -> PyResult<ShapeProcessor> {
+    let tolerance = tolerance
+        .map(Tolerance::from_scalar)
+        .transpose()
+        .map_err(error)?;
+
+    Ok(ShapeProcessor {
+        tolerance,
+        triangulation: TriangulationConfig::default(),
+        backend: Backend::default(),
+        validation_cache: None,
+    })
+}
+
+/// Convert an error from the host into a Python exception
+///
+/// The output of the compiler is included, as it explains why compiling a
+/// model failed.
+fn host_error(err: fj_host::Error) -> PyErr {
+    match err {
+        fj_host::Error::Compile { output } => {
+            FornjotError::new_err(format!("Error compiling model\n\n{output}"))
+        }
+        err => error(err),
+    }
+}
+
+/// Convert a Rust error into a Python exception, including its sources
+fn error(err: impl error::Error) -> PyErr {
+    let mut message = err.to_string();
+
+    let mut current_err: &dyn error::Error = &err;
+    while let Some(err) = current_err.source() {
+        message.push_str(&format!("\n\nCaused by:\n    {}", err));
+        current_err = err;
+    }
+
+    FornjotError::new_err(message)
+}
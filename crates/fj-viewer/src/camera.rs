@@ -1,8 +1,10 @@
 //! Viewer camera module
-use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
-use fj_interop::processed_shape::ProcessedShape;
+use fj_interop::{features::Ray, processed_shape::ProcessedShape};
 use fj_math::{Aabb, Point, Scalar, Transform, Triangle, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::screen::NormalizedPosition;
 
@@ -25,6 +27,9 @@ pub struct Camera {
 
     /// The locational part of the transform
     pub translation: Transform,
+
+    /// The projection that is used to render the model
+    pub projection: Projection,
 }
 
 impl Camera {
@@ -80,9 +85,89 @@ impl Camera {
                 initial_offset.y,
                 -initial_distance,
             ]),
+
+            projection: Projection::default(),
         }
     }
 
+    /// Move the camera to look at a bounding box from a standard direction
+    ///
+    /// The camera is placed such that the whole bounding box is visible.
+    /// Doesn't change the camera's projection.
+    pub fn look_from(&mut self, view: StandardView, aabb: &Aabb<3>) {
+        self.look_with(view.rotation(), aabb);
+    }
+
+    /// Move the camera, so the whole bounding box is in view
+    ///
+    /// Keeps the direction the camera looks from, and moves it closer to or
+    /// further away from the bounding box, as required by its size. Doesn't
+    /// change the camera's projection.
+    pub fn fit_view(&mut self, aabb: &Aabb<3>) {
+        self.look_with(self.rotation, aabb);
+    }
+
+    /// Move the camera to look at a bounding box while turning around it
+    ///
+    /// Looks at the bounding box from the isometric view, after the bounding
+    /// box has been turned around the z axis by `angle`, in radians. Rendering
+    /// views for a full turn results in a turntable animation.
+    pub fn turntable(&mut self, angle: f64, aabb: &Aabb<3>) {
+        let rotation = StandardView::Isometric.rotation()
+            * Transform::rotation(Vector::unit_z() * angle);
+
+        self.look_with(rotation, aabb);
+    }
+
+    fn look_with(&mut self, rotation: Transform, aabb: &Aabb<3>) {
+        // Make sure the bounding box fits into the view, with some margin,
+        // regardless of the direction it is viewed from.
+        let radius = aabb.size().magnitude().into_f64() / 2.;
+        let distance =
+            radius * 2. / (Self::INITIAL_FIELD_OF_VIEW_IN_X / 2.).tan();
+
+        let center = rotation.transform_point(&aabb.center());
+
+        self.rotation = rotation;
+        self.translation = Transform::translation([
+            -center.x,
+            -center.y,
+            -center.z - Scalar::from(distance),
+        ]);
+    }
+
+    /// Switch between perspective and orthographic projection
+    pub fn toggle_projection(&mut self) {
+        self.projection = match self.projection {
+            Projection::Perspective => Projection::Orthographic,
+            Projection::Orthographic => Projection::Perspective,
+        };
+    }
+
+    /// Access the state of the camera, to restore it later
+    ///
+    /// See [`Camera::restore`].
+    pub fn state(&self) -> CameraState {
+        let data = |transform: &Transform| {
+            let mut data = [0.; 16];
+            data.copy_from_slice(transform.data());
+            data
+        };
+
+        CameraState {
+            rotation: data(&self.rotation),
+            translation: data(&self.translation),
+            projection: self.projection,
+        }
+    }
+
+    /// Restore a state of the camera, as returned by [`Camera::state`]
+    pub fn restore(&mut self, state: &CameraState) {
+        self.rotation = Transform::from_data(state.rotation);
+        self.translation = Transform::from_data(state.translation);
+        self.projection = state.projection;
+    }
+
     /// Returns the distance between the camera and the minimum distance for rendering.
     pub fn near_plane(&self) -> f64 {
         self.near_plane
@@ -98,6 +183,19 @@ impl Camera {
         Self::INITIAL_FIELD_OF_VIEW_IN_X
     }
 
+    /// Returns half the width of the view volume of the orthographic projection
+    ///
+    /// This is chosen such that, at the distance of the model's origin, the
+    /// orthographic projection shows the same area as the perspective one.
+    /// Since zooming moves the model closer to the camera, the orthographic
+    /// projection zooms along with it.
+    pub fn orthographic_half_width(&self) -> f64 {
+        let origin = self.camera_to_model().transform_point(&Point::origin());
+        let distance = origin.z.into_f64().abs().max(Self::DEFAULT_NEAR_PLANE);
+
+        (self.field_of_view_in_x() / 2.).tan() * distance
+    }
+
     /// Returns the position of the camera in world space.
     pub fn position(&self) -> Point<3> {
         self.camera_to_model()
@@ -110,9 +208,24 @@ impl Camera {
         cursor: NormalizedPosition,
     ) -> Point<3> {
         // Cursor position in camera space.
-        let f = (self.field_of_view_in_x() / 2.).tan() * self.near_plane();
-        let cursor = Point::origin()
-            + Vector::from([cursor.x * f, cursor.y * f, -self.near_plane()]);
+        let cursor = match self.projection {
+            Projection::Perspective => {
+                let f =
+                    (self.field_of_view_in_x() / 2.).tan() * self.near_plane();
+                Point::origin()
+                    + Vector::from([
+                        cursor.x * f,
+                        cursor.y * f,
+                        -self.near_plane(),
+                    ])
+            }
+            Projection::Orthographic => {
+                // All rays are parallel, so the cursor can be placed on the
+                // plane of the camera itself.
+                let f = self.orthographic_half_width();
+                Point::origin() + Vector::from([cursor.x * f, cursor.y * f, 0.])
+            }
+        };
 
         self.camera_to_model().inverse_transform_point(&cursor)
     }
@@ -132,17 +245,14 @@ impl Camera {
         cursor: Option<NormalizedPosition>,
         shape: &ProcessedShape,
     ) -> Option<FocusPoint> {
-        // Transform camera and cursor positions to model space.
-        let origin = self.position();
-        let cursor = self.cursor_to_model_space(cursor?);
-        let dir = (cursor - origin).normalize();
+        let Ray { origin, direction } = self.cursor_ray(cursor?);
 
         let mut min_t = None;
 
         for triangle in shape.mesh.triangles() {
             let t = Triangle::from_points(triangle.points).cast_local_ray(
                 origin,
-                dir,
+                direction,
                 f64::INFINITY,
                 true,
             );
@@ -154,7 +264,51 @@ impl Camera {
             }
         }
 
-        Some(FocusPoint(origin + dir * min_t?))
+        Some(FocusPoint(origin + direction * min_t?))
+    }
+
+    /// Compute the ray in model space, that goes through the cursor
+    ///
+    /// The direction of the returned ray is normalized.
+    pub fn cursor_ray(&self, cursor: NormalizedPosition) -> Ray {
+        // Transform camera and cursor positions to model space.
+        let cursor = self.cursor_to_model_space(cursor);
+
+        match self.projection {
+            Projection::Perspective => {
+                let origin = self.position();
+                Ray {
+                    origin,
+                    direction: (cursor - origin).normalize(),
+                }
+            }
+            Projection::Orthographic => {
+                let direction = self
+                    .camera_to_model()
+                    .inverse()
+                    .transform_vector(&Vector::from([0., 0., -1.]));
+                Ray {
+                    origin: cursor,
+                    direction: direction.normalize(),
+                }
+            }
+        }
+    }
+
+    /// Returns half the width of the view, at the depth of a point
+    ///
+    /// This can be used to convert distances on the screen into distances in
+    /// model space, at the location of the point.
+    pub fn half_view_width_at(&self, point: Point<3>) -> f64 {
+        match self.projection {
+            Projection::Perspective => {
+                let point = self.camera_to_model().transform_point(&point);
+                let distance = point.z.into_f64().abs();
+
+                (self.field_of_view_in_x() / 2.).tan() * distance
+            }
+            Projection::Orthographic => self.orthographic_half_width(),
+        }
     }
 
     /// Access the transform from camera to model space.
@@ -215,6 +369,85 @@ impl Camera {
     }
 }
 
+/// The state of a [`Camera`] that is controlled by the user
+///
+/// This can be saved, and restored later, for example when the application is
+/// started again. The transforms are stored as their matrices, in column-major
+/// order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct CameraState {
+    /// The data of [`Camera::rotation`]
+    pub rotation: [f64; 16],
+
+    /// The data of [`Camera::translation`]
+    pub translation: [f64; 16],
+
+    /// The projection of the camera
+    pub projection: Projection,
+}
+
+/// The projection that a [`Camera`] uses
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Projection {
+    /// Perspective projection
+    ///
+    /// This is the default.
+    #[default]
+    Perspective,
+
+    /// Orthographic projection
+    ///
+    /// Preserves parallel lines and the relative size of objects, regardless
+    /// of their distance from the camera. This makes it easier to compare
+    /// dimensions.
+    Orthographic,
+}
+
+/// A standard direction to view a model from
+///
+/// See [`Camera::look_from`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StandardView {
+    /// Look at the model from the front (negative y), with z pointing up
+    Front,
+
+    /// Look at the model from the top (positive z), with y pointing up
+    Top,
+
+    /// Look at the model from the right (positive x), with z pointing up
+    Right,
+
+    /// Look at the model from the front, right, and top
+    Isometric,
+}
+
+impl StandardView {
+    /// The rotation that transforms the model into this view
+    fn rotation(&self) -> Transform {
+        let front = Transform::rotation(Vector::unit_x() * -FRAC_PI_2);
+
+        match self {
+            Self::Front => front,
+            Self::Top => Transform::identity(),
+            Self::Right => {
+                Transform::rotation(Vector::unit_y() * -FRAC_PI_2) * front
+            }
+            Self::Isometric => {
+                // The angle that tilts a cube's space diagonal towards the
+                // viewer, after it has been turned by 45 degrees.
+                let tilt = (1. / 2f64.sqrt()).atan();
+
+                Transform::rotation(Vector::unit_x() * tilt)
+                    * Transform::rotation(Vector::unit_y() * -FRAC_PI_4)
+                    * front
+            }
+        }
+    }
+}
+
 /// The point around which camera movement happens.
 ///
 /// This will be the point on the model that the cursor is currently pointing at if such a point exists,
@@ -0,0 +1,212 @@
+use std::{fs::File, io, io::BufWriter, num::NonZeroU32, path::Path};
+
+use thiserror::Error;
+
+use crate::{camera::Camera, screen::Size};
+
+use super::{draw_config::DrawConfig, scene::Scene};
+
+/// An image of the model
+///
+/// See [`Renderer::capture`] and [`Headless::capture`].
+///
+/// [`Renderer::capture`]: super::Renderer::capture
+/// [`Headless::capture`]: super::Headless::capture
+#[derive(Clone, Debug)]
+pub struct Image {
+    /// The width of the image, in pixels
+    pub width: u32,
+
+    /// The height of the image, in pixels
+    pub height: u32,
+
+    /// The pixels of the image, row by row, as 8-bit RGBA
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    /// Save the image as a PNG file
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), CaptureError> {
+        let file = BufWriter::new(File::create(path)?);
+
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+
+        Ok(())
+    }
+}
+
+/// Save images as the frames of an animated GIF file
+///
+/// `frame_delay` is the time that each frame is displayed for, in hundredths
+/// of a second. The animation loops forever.
+pub fn save_gif(
+    frames: &[Image],
+    frame_delay: u16,
+    path: impl AsRef<Path>,
+) -> Result<(), CaptureError> {
+    let (width, height) = match frames.first() {
+        Some(frame) => (frame.width, frame.height),
+        None => (0, 0),
+    };
+    let file = BufWriter::new(File::create(path)?);
+
+    let mut encoder = gif::Encoder::new(
+        file,
+        gif_dimension(width)?,
+        gif_dimension(height)?,
+        &[],
+    )?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for image in frames {
+        let mut data = image.data.clone();
+        let mut frame = gif::Frame::from_rgba_speed(
+            gif_dimension(image.width)?,
+            gif_dimension(image.height)?,
+            &mut data,
+            10,
+        );
+        frame.delay = frame_delay;
+
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+fn gif_dimension(dimension: u32) -> Result<u16, CaptureError> {
+    dimension
+        .try_into()
+        .map_err(|_| CaptureError::ImageTooLarge(dimension))
+}
+
+/// Draw the scene into an image
+pub(super) async fn capture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    scene: &Scene,
+    camera: &Camera,
+    config: &DrawConfig,
+    size: Size,
+    format: wgpu::TextureFormat,
+) -> Result<Image, CaptureError> {
+    let Size { width, height } = size;
+    let extent = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::COPY_SRC,
+    });
+    let color_view =
+        texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let targets = scene.create_targets(device, width, height);
+
+    scene.update_uniforms(queue, camera, config, size);
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: None,
+        });
+
+    Scene::clear(&mut encoder, &color_view, &targets);
+    scene.draw(&mut encoder, &color_view, &targets, config);
+
+    // Rows in the buffer need to be aligned, so they might be longer than the
+    // rows of the image.
+    let row_len = width as usize * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+    let padded_row_len = (row_len + align - 1) / align * align;
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (padded_row_len * height as usize) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(padded_row_len as u32),
+                rows_per_image: None,
+            },
+        },
+        extent,
+    );
+
+    queue.submit(Some(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let mapped = slice.map_async(wgpu::MapMode::Read);
+    device.poll(wgpu::Maintain::Wait);
+    mapped.await?;
+
+    let mut data = Vec::with_capacity(row_len * height as usize);
+    for row in slice.get_mapped_range().chunks(padded_row_len) {
+        data.extend_from_slice(&row[..row_len]);
+    }
+    buffer.unmap();
+
+    // Surfaces often prefer BGRA, but images are stored as RGBA.
+    let is_bgra = matches!(
+        format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    );
+    if is_bgra {
+        for pixel in data.chunks_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    Ok(Image {
+        width,
+        height,
+        data,
+    })
+}
+
+/// Error capturing an image of the model
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    #[error("Error reading image from graphics device: {0}")]
+    /// Error reading the image back from the graphics device
+    Read(#[from] wgpu::BufferAsyncError),
+
+    #[error("I/O error: {0}")]
+    /// I/O error while saving the image
+    Io(#[from] io::Error),
+
+    #[error("Error encoding PNG: {0}")]
+    /// Error encoding the image as PNG
+    Png(#[from] png::EncodingError),
+
+    #[error("Error encoding GIF: {0}")]
+    /// Error encoding the image as GIF
+    Gif(#[from] gif::EncodingError),
+
+    #[error("Image dimension {0} is too large for GIF")]
+    /// The image is too large to be saved as GIF
+    ImageTooLarge(u32),
+}
@@ -72,8 +72,12 @@ impl ConfigUi {
         surface_config: &wgpu::SurfaceConfiguration,
         aabb: &Aabb<3>,
         draw_config: &DrawConfig,
+        scale_factor: f32,
     ) -> Result<(), String> {
-        let mut section = Section::new().with_screen_position((50.0, 50.0));
+        // The text is drawn in physical pixels, so it needs to be scaled, to
+        // have the same size on any display.
+        let margin = 50.0 * scale_factor;
+        let mut section = Section::new().with_screen_position((margin, margin));
 
         for element in Element::elements() {
             let enabled = element.is_enabled(draw_config);
@@ -83,7 +87,7 @@ impl ConfigUi {
 
             let text = Text::new(text)
                 .with_color([0.0, 0.0, 0.0, alpha])
-                .with_scale(50.0);
+                .with_scale(50.0 * scale_factor);
 
             section = section.add_text(text);
         }
@@ -98,7 +102,7 @@ impl ConfigUi {
         );
         let text = Text::new(&info)
             .with_color([0.0, 0.0, 0.0, 1.0])
-            .with_scale(50.0);
+            .with_scale(50.0 * scale_factor);
         section = section.add_text(text);
 
         self.glyph_brush.queue(section);
@@ -1,22 +1,241 @@
 //! High level configuration for graphics rendering
 
+use std::{collections::BTreeSet, fmt};
+
+use fj_interop::debug::DebugCategory;
+use fj_math::{Aabb, Point, Scalar, Vector};
+
 /// High level configuration for rendering the active model
 #[derive(Debug)]
 pub struct DrawConfig {
     /// Toggle for displaying the shaded model
     pub draw_model: bool,
+    /// How the model is displayed
+    ///
+    /// Only has an effect, if `draw_model` is enabled.
+    pub display_mode: DisplayMode,
+    /// Toggle for drawing all of the model transparently
+    ///
+    /// Reveals features inside of the model, without cutting it.
+    pub x_ray: bool,
+    /// The bodies of the model that are drawn transparently
+    ///
+    /// These are indices into the bodies of the model's mesh, as returned by
+    /// [`Mesh::bodies`](fj_interop::mesh::Mesh::bodies).
+    pub transparent_bodies: BTreeSet<usize>,
     /// Toggle for displaying the wireframe model
     pub draw_mesh: bool,
     /// Toggle for displaying model debug information
     pub draw_debug: bool,
+    /// The categories of debug information that are displayed
+    ///
+    /// Only has an effect, if `draw_debug` is enabled.
+    pub debug_categories: BTreeSet<DebugCategory>,
+    /// Configuration of the section plane, that cuts away part of the model
+    pub section: SectionConfig,
+    /// Toggle for displaying a grid in the xy plane
+    pub draw_grid: bool,
+    /// Toggle for displaying the axes, at the origin and as a gizmo
+    pub draw_axes: bool,
+    /// Toggle for the shadows that the model casts onto itself
+    pub shadows: bool,
+    /// Toggle for darkening creases and corners of the model
+    ///
+    /// This is screen-space ambient occlusion, which costs some performance.
+    pub ambient_occlusion: bool,
 }
 
 impl Default for DrawConfig {
     fn default() -> Self {
         Self {
             draw_model: true,
+            display_mode: DisplayMode::default(),
+            x_ray: false,
+            transparent_bodies: BTreeSet::new(),
             draw_mesh: false,
             draw_debug: false,
+            debug_categories: DebugCategory::ALL.into_iter().collect(),
+            section: SectionConfig::default(),
+            draw_grid: true,
+            draw_axes: true,
+            shadows: true,
+            ambient_occlusion: false,
+        }
+    }
+}
+
+impl DrawConfig {
+    /// Indicate whether the model casts shadows onto itself
+    ///
+    /// Transparent parts of the model don't cast shadows, so there are none in
+    /// x-ray mode.
+    pub fn casts_shadows(&self) -> bool {
+        self.shadows && !self.x_ray
+    }
+
+    /// Indicate whether ambient occlusion is computed
+    ///
+    /// Transparent parts of the model don't occlude anything, so it isn't
+    /// computed in x-ray mode.
+    pub fn occludes_ambient_light(&self) -> bool {
+        self.ambient_occlusion && !self.x_ray
+    }
+}
+
+/// How the model is displayed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DisplayMode {
+    /// The shaded faces of the model
+    #[default]
+    Shaded,
+    /// The shaded faces of the model, and its edges
+    ShadedWithEdges,
+    /// All edges of the model, including those that are hidden behind faces
+    Wireframe,
+    /// The edges of the model, that aren't hidden behind faces
+    HiddenLine,
+}
+
+impl DisplayMode {
+    /// All display modes
+    pub const ALL: [Self; 4] = [
+        Self::Shaded,
+        Self::ShadedWithEdges,
+        Self::Wireframe,
+        Self::HiddenLine,
+    ];
+
+    /// Access the display mode that follows this one, in [`Self::ALL`]
+    ///
+    /// The last display mode is followed by the first one.
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&mode| mode == self)
+            .expect("Display mode is missing from `ALL`");
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Indicate whether the faces of the model are shaded in this mode
+    pub fn is_shaded(&self) -> bool {
+        matches!(self, Self::Shaded | Self::ShadedWithEdges)
+    }
+
+    /// Indicate whether the edges of the model are drawn in this mode
+    pub fn draws_edges(&self) -> bool {
+        !matches!(self, Self::Shaded)
+    }
+}
+
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Shaded => "Shaded",
+            Self::ShadedWithEdges => "Shaded with edges",
+            Self::Wireframe => "Wireframe",
+            Self::HiddenLine => "Hidden line",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Configuration of the section plane, that cuts away part of the model
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SectionConfig {
+    /// Toggle for cutting the model with the section plane
+    pub enabled: bool,
+    /// The axis that the section plane is perpendicular to
+    pub axis: SectionAxis,
+    /// The position of the section plane along its axis
+    ///
+    /// Relative to the model's bounding box, with `0.` being its minimum along
+    /// the axis, and `1.` its maximum.
+    pub position: f64,
+    /// Toggle for cutting away the other side of the model
+    pub flip: bool,
+    /// Toggle for displaying the cross-section, where the plane cuts the model
+    pub draw_cross_section: bool,
+}
+
+impl SectionConfig {
+    /// Compute the section plane, for a model with the given bounding box
+    ///
+    /// Returns `None`, if the section plane is disabled.
+    pub fn plane(&self, aabb: &Aabb<3>) -> Option<SectionPlane> {
+        if !self.enabled {
+            return None;
+        }
+
+        let axis = match self.axis {
+            SectionAxis::X => 0,
+            SectionAxis::Y => 1,
+            SectionAxis::Z => 2,
+        };
+
+        let mut origin = aabb.center();
+        let [min, max] =
+            [aabb.min, aabb.max].map(|point| point.coords.components[axis]);
+        origin.coords.components[axis] =
+            min + (max - min) * Scalar::from(self.position);
+
+        let mut normal = Vector::from([0., 0., 0.]);
+        normal.components[axis] =
+            if self.flip { -Scalar::ONE } else { Scalar::ONE };
+
+        Some(SectionPlane { origin, normal })
+    }
+}
+
+impl Default for SectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            axis: SectionAxis::Z,
+            position: 0.5,
+            flip: false,
+            draw_cross_section: true,
         }
     }
 }
+
+/// An axis that the section plane can be perpendicular to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SectionAxis {
+    /// The x axis
+    X,
+    /// The y axis
+    Y,
+    /// The z axis
+    Z,
+}
+
+impl SectionAxis {
+    /// All axes
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+}
+
+impl fmt::Display for SectionAxis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::Z => "Z",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// A plane that cuts away part of the model
+///
+/// The part of the model in front of the plane, on the side where its normal
+/// points, is cut away.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SectionPlane {
+    /// A point on the plane
+    pub origin: Point<3>,
+    /// The normal of the plane
+    pub normal: Vector<3>,
+}
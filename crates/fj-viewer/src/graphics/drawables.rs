@@ -1,3 +1,5 @@
+use fj_interop::debug::DebugCategory;
+
 use super::{
     geometries::{Geometries, Geometry},
     pipelines::{Pipeline, Pipelines},
@@ -5,17 +7,21 @@ use super::{
 
 pub struct Drawables<'r> {
     pub model: Drawable<'r>,
-    pub mesh: Drawable<'r>,
-    pub lines: Drawable<'r>,
+    pub debug: Vec<(DebugCategory, Drawable<'r>)>,
 }
 
 impl<'r> Drawables<'r> {
     pub fn new(geometries: &'r Geometries, pipelines: &'r Pipelines) -> Self {
         let model = Drawable::new(&geometries.mesh, &pipelines.model);
-        let mesh = Drawable::new(&geometries.mesh, &pipelines.mesh);
-        let lines = Drawable::new(&geometries.lines, &pipelines.lines);
+        let debug = geometries
+            .debug
+            .iter()
+            .map(|(&category, geometry)| {
+                (category, Drawable::new(geometry, &pipelines.debug))
+            })
+            .collect();
 
-        Self { model, mesh, lines }
+        Self { model, debug }
     }
 }
 
@@ -25,16 +31,19 @@ pub struct Drawable<'r> {
 }
 
 impl<'r> Drawable<'r> {
-    fn new(geometry: &'r Geometry, pipeline: &'r Pipeline) -> Self {
+    pub fn new(geometry: &'r Geometry, pipeline: &'r Pipeline) -> Self {
         Self { geometry, pipeline }
     }
 
+    /// Draw the geometry
+    ///
+    /// The bind groups are bound in order, starting with group 0.
     pub fn draw(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         color_view: &wgpu::TextureView,
         depth_view: &wgpu::TextureView,
-        bind_group: &wgpu::BindGroup,
+        bind_groups: &[&wgpu::BindGroup],
     ) {
         let mut render_pass =
             encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -60,7 +69,9 @@ impl<'r> Drawable<'r> {
             });
 
         render_pass.set_pipeline(&self.pipeline.0);
-        render_pass.set_bind_group(0, bind_group, &[]);
+        for (index, bind_group) in bind_groups.iter().enumerate() {
+            render_pass.set_bind_group(index as u32, bind_group, &[]);
+        }
         render_pass.set_vertex_buffer(0, self.geometry.vertex_buffer.slice(..));
         render_pass.set_index_buffer(
             self.geometry.index_buffer.slice(..),
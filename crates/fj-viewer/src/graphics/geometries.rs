@@ -1,6 +1,10 @@
-use std::convert::TryInto;
+use std::{collections::BTreeMap, convert::TryInto};
 
-use fj_math::Aabb;
+use fj_interop::{
+    debug::{DebugCategory, DebugGeometryKind, DebugInfo},
+    mesh::Color,
+};
+use fj_math::{Aabb, Point, PolyChain};
 use wgpu::util::DeviceExt;
 
 use super::vertices::{Vertex, Vertices};
@@ -8,25 +12,100 @@ use super::vertices::{Vertex, Vertices};
 #[derive(Debug)]
 pub struct Geometries {
     pub mesh: Geometry,
-    pub lines: Geometry,
+    pub transparent: Geometry,
+    pub edges: Geometry,
+    pub debug: BTreeMap<DebugCategory, Geometry>,
+    pub debug_counts: BTreeMap<DebugCategory, usize>,
+    pub labels: Vec<Label>,
     pub aabb: Aabb<3>,
 }
 
 impl Geometries {
+    const EDGE_COLOR: [f32; 4] = [0.1, 0.1, 0.1, 1.];
+
     pub fn new(
         device: &wgpu::Device,
         mesh: &Vertices,
-        debug_info: &Vertices,
+        transparent: &Vertices,
+        edges: &[PolyChain<3>],
+        debug_info: &DebugInfo,
         aabb: Aabb<3>,
     ) -> Self {
         let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
-        let lines =
-            Geometry::new(device, debug_info.vertices(), debug_info.indices());
+        let transparent = Geometry::new(
+            device,
+            transparent.vertices(),
+            transparent.indices(),
+        );
+
+        let mut edge_lines = Vertices::empty();
+        for segment in edges.iter().flat_map(PolyChain::segments) {
+            edge_lines.push_line(segment.points(), [0.; 3], Self::EDGE_COLOR);
+        }
+        let edges =
+            Geometry::new(device, edge_lines.vertices(), edge_lines.indices());
+
+        // Markers scale with the model, so they are neither tiny nor huge in
+        // relation to it.
+        let marker_size = aabb.size().magnitude().into_f64() * 0.01;
+
+        let debug = debug_info
+            .categories()
+            .into_iter()
+            .map(|category| {
+                let lines = Vertices::from_debug_geometry(
+                    debug_info.geometry_in(category),
+                    marker_size,
+                );
+                let geometry =
+                    Geometry::new(device, lines.vertices(), lines.indices());
+
+                (category, geometry)
+            })
+            .collect();
+        let debug_counts = debug_info
+            .categories()
+            .into_iter()
+            .map(|category| {
+                (category, debug_info.geometry_in(category).count())
+            })
+            .collect();
+
+        let labels = debug_info
+            .geometry
+            .iter()
+            .filter_map(|geometry| match &geometry.kind {
+                DebugGeometryKind::Label { position, text } => Some(Label {
+                    category: geometry.category,
+                    position: *position,
+                    text: text.clone(),
+                    color: geometry.color,
+                }),
+                _ => None,
+            })
+            .collect();
 
-        Self { mesh, lines, aabb }
+        Self {
+            mesh,
+            transparent,
+            edges,
+            debug,
+            debug_counts,
+            labels,
+            aabb,
+        }
     }
 }
 
+/// A debug label, whose text is drawn by the UI
+#[derive(Debug)]
+pub struct Label {
+    pub category: DebugCategory,
+    pub position: Point<3>,
+    pub text: String,
+    pub color: Color,
+}
+
 #[derive(Debug)]
 pub struct Geometry {
     pub vertex_buffer: wgpu::Buffer,
@@ -35,7 +114,7 @@ pub struct Geometry {
 }
 
 impl Geometry {
-    fn new(
+    pub fn new(
         device: &wgpu::Device,
         vertices: &[Vertex],
         indices: &[u32],
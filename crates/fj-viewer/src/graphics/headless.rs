@@ -0,0 +1,125 @@
+use std::f64::consts::TAU;
+
+use fj_interop::debug::DebugInfo;
+use fj_math::{Aabb, PolyChain};
+
+use crate::{camera::Camera, screen::Size};
+
+use super::{
+    capture::{self, CaptureError, Image},
+    device_descriptor,
+    draw_config::DrawConfig,
+    geometries::Geometries,
+    renderer::InitError,
+    scene::Scene,
+    vertices::Vertices,
+    BACKENDS,
+};
+
+/// Renders the model to images, without a window
+///
+/// Use this to create screenshots and animations of a model from the command
+/// line, for example for documentation.
+#[derive(Debug)]
+pub struct Headless {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+
+    scene: Scene,
+}
+
+impl Headless {
+    const COLOR_FORMAT: wgpu::TextureFormat =
+        wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// Returns a new `Headless` renderer
+    pub async fn new() -> Result<Self, InitError> {
+        let instance = wgpu::Instance::new(BACKENDS);
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .await
+            .ok_or(InitError::RequestAdapter)?;
+
+        let (device, queue) = adapter
+            .request_device(&device_descriptor(&adapter), None)
+            .await?;
+
+        let scene = Scene::new(&device, Self::COLOR_FORMAT);
+
+        Ok(Self {
+            device,
+            queue,
+
+            scene,
+        })
+    }
+
+    /// Updates the geometry of the model being rendered.
+    pub fn update_geometry(
+        &mut self,
+        mesh: Vertices,
+        edges: &[PolyChain<3>],
+        debug_info: &DebugInfo,
+        aabb: Aabb<3>,
+    ) {
+        self.scene.geometries = Geometries::new(
+            &self.device,
+            &mesh,
+            &Vertices::empty(),
+            edges,
+            debug_info,
+            aabb,
+        );
+    }
+
+    /// Captures the model, as seen by the camera, as an image
+    pub async fn capture(
+        &mut self,
+        camera: &Camera,
+        config: &DrawConfig,
+        size: Size,
+    ) -> Result<Image, CaptureError> {
+        self.scene.update_reference(&self.device, camera);
+
+        capture::capture(
+            &self.device,
+            &self.queue,
+            &self.scene,
+            camera,
+            config,
+            size,
+            Self::COLOR_FORMAT,
+        )
+        .await
+    }
+
+    /// Captures a full turn around the model, as a sequence of images
+    ///
+    /// See [`Camera::turntable`].
+    pub async fn turntable(
+        &mut self,
+        config: &DrawConfig,
+        size: Size,
+        frames: u32,
+    ) -> Result<Vec<Image>, CaptureError> {
+        let aabb = self.scene.geometries.aabb;
+        let mut camera = Camera::new(&aabb);
+
+        let mut images = Vec::new();
+        for frame in 0..frames {
+            let angle = TAU * frame as f64 / frames as f64;
+
+            camera.turntable(angle, &aabb);
+            camera.update_planes(&aabb);
+
+            images.push(self.capture(&camera, config, size).await?);
+        }
+
+        Ok(images)
+    }
+}
@@ -1,19 +1,62 @@
 //! Rendering primitives, routines, and structures.
 
+mod capture;
 mod config_ui;
 mod draw_config;
 mod drawables;
 mod geometries;
+mod headless;
 mod pipelines;
+mod reference;
 mod renderer;
+mod scene;
 mod shaders;
+mod shading;
 mod transform;
 mod uniforms;
 mod vertices;
 
 pub use self::{
-    draw_config::DrawConfig,
+    capture::{save_gif, CaptureError, Image},
+    draw_config::{
+        DisplayMode, DrawConfig, SectionAxis, SectionConfig, SectionPlane,
+    },
+    headless::Headless,
     renderer::{DrawError, InitError, Renderer},
 };
 
 const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// The backends that the renderer can use
+///
+/// In the browser, only WebGL2 is available, which `wgpu` counts as a
+/// secondary backend.
+#[cfg(not(target_arch = "wasm32"))]
+const BACKENDS: wgpu::Backends = wgpu::Backends::PRIMARY;
+#[cfg(target_arch = "wasm32")]
+const BACKENDS: wgpu::Backends = wgpu::Backends::GL;
+
+/// Describe the device that the renderer requests from the adapter
+///
+/// Don't just blindly assume that we can request optional features. If they
+/// aren't available, that might cause a panic, or an error to be returned. Only
+/// the ones the adapter supports are requested, and whatever depends on them
+/// is left out, if they're missing.
+///
+/// See this issue:
+/// https://github.com/hannobraun/fornjot/issues/33
+fn device_descriptor(
+    adapter: &wgpu::Adapter,
+) -> wgpu::DeviceDescriptor<'static> {
+    // WebGL2 doesn't come close to the default limits.
+    #[cfg(not(target_arch = "wasm32"))]
+    let limits = wgpu::Limits::default();
+    #[cfg(target_arch = "wasm32")]
+    let limits = wgpu::Limits::downlevel_webgl2_defaults();
+
+    wgpu::DeviceDescriptor {
+        label: None,
+        features: adapter.features() & wgpu::Features::POLYGON_MODE_LINE,
+        limits,
+    }
+}
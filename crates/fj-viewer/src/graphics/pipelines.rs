@@ -2,6 +2,7 @@ use std::mem::size_of;
 
 use super::{
     shaders::{Shader, Shaders},
+    shading::Shading,
     vertices::Vertex,
     DEPTH_FORMAT,
 };
@@ -9,14 +10,26 @@ use super::{
 #[derive(Debug)]
 pub struct Pipelines {
     pub model: Pipeline,
-    pub mesh: Pipeline,
+    pub transparent: Pipeline,
+    /// Draws the triangles of the mesh as wireframe
+    ///
+    /// This is `None`, if the device doesn't support drawing polygons as
+    /// lines, as is the case for WebGL.
+    pub mesh: Option<Pipeline>,
+    pub cross_section: Pipeline,
     pub lines: Pipeline,
+    pub debug: Pipeline,
+    pub edges: Pipeline,
+    pub hidden_surfaces: Pipeline,
+    pub shadow: Pipeline,
+    pub view_depth: Pipeline,
 }
 
 impl Pipelines {
     pub fn new(
         device: &wgpu::Device,
         bind_group_layout: &wgpu::BindGroupLayout,
+        shading: &Shading,
         color_format: wgpu::TextureFormat,
     ) -> Self {
         let pipeline_layout =
@@ -26,23 +39,63 @@ impl Pipelines {
                 push_constant_ranges: &[],
             });
 
+        // Only the model is shaded using the shadow map and ambient
+        // occlusion. The other pipelines draw into the textures those are
+        // read from, so they must not have access to them.
+        let model_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[
+                    bind_group_layout,
+                    &shading.bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
         let shaders = Shaders::new(device);
 
         Self {
             model: Pipeline::new(
                 device,
-                &pipeline_layout,
+                &model_pipeline_layout,
                 shaders.model(),
                 wgpu::PrimitiveTopology::TriangleList,
                 wgpu::PolygonMode::Fill,
                 color_format,
             ),
-            mesh: Pipeline::new(
+            // Transparent parts of the model must not hide what's behind
+            // them, so they don't write to the depth buffer.
+            transparent: Pipeline::with_targets(
+                device,
+                &model_pipeline_layout,
+                shaders.transparent(),
+                wgpu::PrimitiveTopology::TriangleList,
+                &[wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                }],
+                depth_stencil_state(false, wgpu::DepthBiasState::default()),
+            ),
+            mesh: device
+                .features()
+                .contains(wgpu::Features::POLYGON_MODE_LINE)
+                .then(|| {
+                    Pipeline::new(
+                        device,
+                        &pipeline_layout,
+                        shaders.mesh(),
+                        wgpu::PrimitiveTopology::TriangleList,
+                        wgpu::PolygonMode::Line,
+                        color_format,
+                    )
+                }),
+            cross_section: Pipeline::new(
                 device,
                 &pipeline_layout,
-                shaders.mesh(),
+                shaders.cross_section(),
                 wgpu::PrimitiveTopology::TriangleList,
-                wgpu::PolygonMode::Line,
+                wgpu::PolygonMode::Fill,
                 color_format,
             ),
             lines: Pipeline::new(
@@ -50,9 +103,82 @@ impl Pipelines {
                 &pipeline_layout,
                 shaders.lines(),
                 wgpu::PrimitiveTopology::LineList,
-                wgpu::PolygonMode::Line,
+                // The polygon mode only applies to triangles. Lines are
+                // always drawn as lines.
+                wgpu::PolygonMode::Fill,
+                color_format,
+            ),
+            // Debug geometry is often inside of the model, or on its surface.
+            // It is drawn on top, so it's always visible.
+            debug: Pipeline::with_targets(
+                device,
+                &pipeline_layout,
+                shaders.lines(),
+                wgpu::PrimitiveTopology::LineList,
+                &[wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                }],
+                wgpu::DepthStencilState {
+                    depth_compare: wgpu::CompareFunction::Always,
+                    ..depth_stencil_state(
+                        false,
+                        wgpu::DepthBiasState::default(),
+                    )
+                },
+            ),
+            edges: Pipeline::new(
+                device,
+                &pipeline_layout,
+                shaders.edges(),
+                wgpu::PrimitiveTopology::LineList,
+                wgpu::PolygonMode::Fill,
                 color_format,
             ),
+            // Only writes the depth of the model, so it hides edges and other
+            // geometry behind it, without being visible itself.
+            hidden_surfaces: Pipeline::with_targets(
+                device,
+                &pipeline_layout,
+                shaders.mesh(),
+                wgpu::PrimitiveTopology::TriangleList,
+                &[wgpu::ColorTargetState {
+                    format: color_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::empty(),
+                }],
+                depth_stencil_state(true, wgpu::DepthBiasState::default()),
+            ),
+            shadow: Pipeline::with_targets(
+                device,
+                &pipeline_layout,
+                shaders.shadow(),
+                wgpu::PrimitiveTopology::TriangleList,
+                &[],
+                // Offset the depth in the shadow map, so surfaces don't
+                // shadow themselves due to limited precision.
+                depth_stencil_state(
+                    true,
+                    wgpu::DepthBiasState {
+                        constant: 2,
+                        slope_scale: 2.0,
+                        clamp: 0.0,
+                    },
+                ),
+            ),
+            view_depth: Pipeline::with_targets(
+                device,
+                &pipeline_layout,
+                shaders.view_depth(),
+                wgpu::PrimitiveTopology::TriangleList,
+                &[wgpu::ColorTargetState {
+                    format: Shading::VIEW_DEPTH_FORMAT,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                }],
+                depth_stencil_state(true, wgpu::DepthBiasState::default()),
+            ),
         }
     }
 }
@@ -68,6 +194,49 @@ impl Pipeline {
         topology: wgpu::PrimitiveTopology,
         polygon_mode: wgpu::PolygonMode,
         color_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self::create(
+            device,
+            pipeline_layout,
+            shader,
+            topology,
+            polygon_mode,
+            &[wgpu::ColorTargetState {
+                format: color_format,
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            }],
+            depth_stencil_state(true, wgpu::DepthBiasState::default()),
+        )
+    }
+
+    fn with_targets(
+        device: &wgpu::Device,
+        pipeline_layout: &wgpu::PipelineLayout,
+        shader: Shader,
+        topology: wgpu::PrimitiveTopology,
+        targets: &[wgpu::ColorTargetState],
+        depth_stencil: wgpu::DepthStencilState,
+    ) -> Self {
+        Self::create(
+            device,
+            pipeline_layout,
+            shader,
+            topology,
+            wgpu::PolygonMode::Fill,
+            targets,
+            depth_stencil,
+        )
+    }
+
+    fn create(
+        device: &wgpu::Device,
+        pipeline_layout: &wgpu::PipelineLayout,
+        shader: Shader,
+        topology: wgpu::PrimitiveTopology,
+        polygon_mode: wgpu::PolygonMode,
+        targets: &[wgpu::ColorTargetState],
+        depth_stencil: wgpu::DepthStencilState,
     ) -> Self {
         let pipeline =
             device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -75,7 +244,7 @@ impl Pipeline {
                 layout: Some(pipeline_layout),
                 vertex: wgpu::VertexState {
                     module: shader.module,
-                    entry_point: "vertex",
+                    entry_point: shader.vert_entry,
                     buffers: &[wgpu::VertexBufferLayout {
                         array_stride: size_of::<Vertex>() as u64,
                         step_mode: wgpu::VertexStepMode::Vertex,
@@ -95,18 +264,7 @@ impl Pipeline {
                     polygon_mode,
                     conservative: false,
                 },
-                depth_stencil: Some(wgpu::DepthStencilState {
-                    format: DEPTH_FORMAT,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::LessEqual,
-                    stencil: wgpu::StencilState {
-                        front: wgpu::StencilFaceState::IGNORE,
-                        back: wgpu::StencilFaceState::IGNORE,
-                        read_mask: 0,
-                        write_mask: 0,
-                    },
-                    bias: wgpu::DepthBiasState::default(),
-                }),
+                depth_stencil: Some(depth_stencil),
                 multisample: wgpu::MultisampleState {
                     count: 1,
                     mask: !0,
@@ -115,13 +273,7 @@ impl Pipeline {
                 fragment: Some(wgpu::FragmentState {
                     module: shader.module,
                     entry_point: shader.frag_entry,
-                    targets: &[wgpu::ColorTargetState {
-                        format: color_format,
-                        blend: Some(
-                            wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
-                        ),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    }],
+                    targets,
                 }),
                 multiview: None,
             });
@@ -129,3 +281,21 @@ impl Pipeline {
         Self(pipeline)
     }
 }
+
+fn depth_stencil_state(
+    depth_write_enabled: bool,
+    bias: wgpu::DepthBiasState,
+) -> wgpu::DepthStencilState {
+    wgpu::DepthStencilState {
+        format: DEPTH_FORMAT,
+        depth_write_enabled,
+        depth_compare: wgpu::CompareFunction::LessEqual,
+        stencil: wgpu::StencilState {
+            front: wgpu::StencilFaceState::IGNORE,
+            back: wgpu::StencilFaceState::IGNORE,
+            read_mask: 0,
+            write_mask: 0,
+        },
+        bias,
+    }
+}
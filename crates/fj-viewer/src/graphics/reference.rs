@@ -0,0 +1,149 @@
+use fj_math::{Point, Scalar};
+
+use crate::camera::Camera;
+
+use super::{geometries::Geometry, vertices::Vertices};
+
+/// Lines that give the user a sense of space
+///
+/// This is a grid in the xy plane, whose spacing adapts to how far the camera
+/// is zoomed in, and a marker at the origin that shows the axes.
+#[derive(Debug)]
+pub struct Reference {
+    spacing: f64,
+    center: [i64; 2],
+
+    pub grid: Geometry,
+    pub origin: Geometry,
+}
+
+impl Reference {
+    /// The number of grid cells between two major lines
+    const MAJOR_EVERY: i64 = 10;
+
+    /// The number of grid cells from the center of the grid to its edge
+    const HALF_EXTENT: i64 = 100;
+
+    const MINOR_COLOR: [f32; 4] = [0.85, 0.85, 0.85, 1.];
+    const MAJOR_COLOR: [f32; 4] = [0.65, 0.65, 0.65, 1.];
+
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self::with_spacing(device, 1., [0, 0])
+    }
+
+    /// Access the distance between two lines of the grid
+    pub fn spacing(&self) -> f64 {
+        self.spacing
+    }
+
+    /// Adapt the grid to the camera
+    ///
+    /// The spacing is chosen such that the view is a few dozen grid cells
+    /// wide at `target`, which should be the point the user is looking at.
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        camera: &Camera,
+        target: Point<3>,
+    ) {
+        let view_width = camera.half_view_width_at(target) * 2.;
+        let spacing = grid_spacing(view_width);
+
+        // Keep the grid centered below the target, but only move it by whole
+        // major cells, so the lines stay where they are.
+        let major = spacing * Self::MAJOR_EVERY as f64;
+        let center = [target.x, target.y]
+            .map(|coord| (coord.into_f64() / major).round() as i64);
+
+        if spacing != self.spacing || center != self.center {
+            *self = Self::with_spacing(device, spacing, center);
+        }
+    }
+
+    fn with_spacing(
+        device: &wgpu::Device,
+        spacing: f64,
+        center: [i64; 2],
+    ) -> Self {
+        let normal = [0.; 3];
+        let [center_x, center_y] =
+            center.map(|coord| (coord * Self::MAJOR_EVERY) as f64 * spacing);
+        let extent = Self::HALF_EXTENT as f64 * spacing;
+        let (min_x, max_x) = (center_x - extent, center_x + extent);
+        let (min_y, max_y) = (center_y - extent, center_y + extent);
+
+        let mut grid = Vertices::empty();
+        for i in -Self::HALF_EXTENT..=Self::HALF_EXTENT {
+            // The grid is centered on a major line, so that's where the major
+            // lines are.
+            let color = if i % Self::MAJOR_EVERY == 0 {
+                Self::MAJOR_COLOR
+            } else {
+                Self::MINOR_COLOR
+            };
+
+            let x = center_x + i as f64 * spacing;
+            let y = center_y + i as f64 * spacing;
+
+            grid.push_line(
+                [Point::from([x, min_y, 0.]), Point::from([x, max_y, 0.])],
+                normal,
+                color,
+            );
+            grid.push_line(
+                [Point::from([min_x, y, 0.]), Point::from([max_x, y, 0.])],
+                normal,
+                color,
+            );
+        }
+
+        // The origin marker is as long as a major grid cell, so it scales
+        // along with the grid.
+        let length = spacing * Self::MAJOR_EVERY as f64;
+        let mut origin = Vertices::empty();
+        for (axis, color) in AXIS_COLORS.into_iter().enumerate() {
+            let mut end = [Scalar::ZERO; 3];
+            end[axis] = Scalar::from(length);
+
+            origin.push_line(
+                [Point::origin(), Point::from(end)],
+                normal,
+                color,
+            );
+        }
+
+        Self {
+            spacing,
+            center,
+
+            grid: Geometry::new(device, grid.vertices(), grid.indices()),
+            origin: Geometry::new(device, origin.vertices(), origin.indices()),
+        }
+    }
+}
+
+/// The colors of the x, y, and z axes
+pub const AXIS_COLORS: [[f32; 4]; 3] = [
+    [0.9, 0.1, 0.1, 1.],
+    [0.1, 0.7, 0.1, 1.],
+    [0.1, 0.3, 0.9, 1.],
+];
+
+/// Choose a grid spacing for a view of the given width
+///
+/// Returns a power of ten, so the grid lines are at round numbers.
+fn grid_spacing(view_width: f64) -> f64 {
+    if !view_width.is_normal() {
+        return 1.;
+    }
+
+    let exponent = (view_width / 10.).log10().floor() as i32;
+
+    // Dividing by an exact power of ten, instead of multiplying by a fraction
+    // of one, results in numbers that are displayed without rounding errors.
+    if exponent >= 0 {
+        10f64.powi(exponent)
+    } else {
+        1. / 10f64.powi(-exponent)
+    }
+}
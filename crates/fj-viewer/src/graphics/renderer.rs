@@ -1,22 +1,42 @@
-use std::{io, mem::size_of};
+use std::{collections::BTreeSet, io};
 
-use fj_math::{Aabb, Point};
+use fj_interop::{
+    debug::{DebugCategory, DebugInfo},
+    mesh::{Mesh, Triangle},
+};
+use fj_math::{Aabb, Point, PolyChain, Scalar};
 use thiserror::Error;
 use tracing::debug;
-use wgpu::util::DeviceExt as _;
 use wgpu_glyph::ab_glyph::InvalidFont;
 
 use crate::{
     camera::Camera,
+    measure::Overlay,
+    parameters::{Parameter, ParameterPanel},
     screen::{Screen, Size},
+    selection::{self, Highlight},
 };
 
 use super::{
-    config_ui::ConfigUi, draw_config::DrawConfig, drawables::Drawables,
-    geometries::Geometries, pipelines::Pipelines, transform::Transform,
-    uniforms::Uniforms, vertices::Vertices, DEPTH_FORMAT,
+    capture::{self, CaptureError, Image},
+    config_ui::ConfigUi,
+    device_descriptor,
+    draw_config::{DisplayMode, DrawConfig, SectionAxis},
+    geometries::{Geometries, Geometry},
+    reference::AXIS_COLORS,
+    scene::Scene,
+    shading::Targets,
+    transform::Transform,
+    vertices::Vertices,
+    BACKENDS,
 };
 
+/// WebGL2 only supports presenting in sync with the display
+#[cfg(not(target_arch = "wasm32"))]
+const PRESENT_MODE: wgpu::PresentMode = wgpu::PresentMode::Mailbox;
+#[cfg(target_arch = "wasm32")]
+const PRESENT_MODE: wgpu::PresentMode = wgpu::PresentMode::Fifo;
+
 #[derive(Default)]
 struct EguiOptionsState {
     show_trace: bool,
@@ -48,16 +68,29 @@ pub struct Renderer {
     queue: wgpu::Queue,
 
     surface_config: wgpu::SurfaceConfiguration,
-    depth_view: wgpu::TextureView,
+    targets: Targets,
+
+    scene: Scene,
 
-    uniform_buffer: wgpu::Buffer,
-    bind_group: wgpu::BindGroup,
+    /// The triangles of each body of the model
+    bodies: Vec<Vec<Triangle>>,
 
-    geometries: Geometries,
-    pipelines: Pipelines,
+    /// The bodies that are currently drawn transparently
+    transparent_bodies: BTreeSet<usize>,
 
     config_ui: ConfigUi,
 
+    model_error: Option<String>,
+    status: Option<String>,
+    measurement: Option<Overlay>,
+    selection: selection::Overlay,
+    parameters: ParameterPanel,
+
+    /// The names of the open models, and which of them is displayed
+    tabs: Vec<String>,
+    active_tab: usize,
+    selected_tab: Option<usize>,
+
     /// State required for integration with `egui`.
     pub egui: EguiState,
 }
@@ -67,7 +100,7 @@ impl Renderer {
     pub async fn new(
         screen: &impl Screen<Window = egui_winit::winit::window::Window>,
     ) -> Result<Self, InitError> {
-        let instance = wgpu::Instance::new(wgpu::Backends::PRIMARY);
+        let instance = wgpu::Instance::new(BACKENDS);
 
         //
         // NOTE: The implementation of the integration with `egui` is
@@ -135,20 +168,7 @@ impl Renderer {
             .ok_or(InitError::RequestAdapter)?;
 
         let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: None,
-                    // Don't just blindly assume that we can request this
-                    // feature. If it isn't available, that might cause a panic,
-                    // or an error to be returned here.
-                    //
-                    // See this issue:
-                    // https://github.com/hannobraun/fornjot/issues/33
-                    features: wgpu::Features::POLYGON_MODE_LINE,
-                    limits: wgpu::Limits::default(),
-                },
-                None,
-            )
+            .request_device(&device_descriptor(&adapter), None)
             .await?;
 
         let color_format = surface
@@ -161,61 +181,12 @@ impl Renderer {
             format: color_format,
             width,
             height,
-            present_mode: wgpu::PresentMode::Mailbox,
+            present_mode: PRESENT_MODE,
         };
         surface.configure(&device, &surface_config);
 
-        let depth_view = Self::create_depth_buffer(&device, &surface_config);
-
-        let uniform_buffer =
-            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: None,
-                contents: bytemuck::cast_slice(&[Uniforms::default()]),
-                usage: wgpu::BufferUsages::UNIFORM
-                    | wgpu::BufferUsages::COPY_DST,
-            });
-        let bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::all(),
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: wgpu::BufferSize::new(size_of::<
-                            Uniforms,
-                        >(
-                        )
-                            as u64),
-                    },
-                    count: None,
-                }],
-                label: None,
-            });
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                    buffer: &uniform_buffer,
-                    offset: 0,
-                    size: None,
-                }),
-            }],
-            label: None,
-        });
-
-        let geometries = Geometries::new(
-            &device,
-            &Vertices::empty(),
-            &Vertices::empty(),
-            Aabb {
-                min: Point::from([0.0, 0.0, 0.0]),
-                max: Point::from([0.0, 0.0, 0.0]),
-            },
-        );
-        let pipelines =
-            Pipelines::new(&device, &bind_group_layout, color_format);
+        let scene = Scene::new(&device, color_format);
+        let targets = scene.create_targets(&device, width, height);
 
         let config_ui = ConfigUi::new(&device, color_format)?;
 
@@ -243,16 +214,25 @@ impl Renderer {
             queue,
 
             surface_config,
-            depth_view,
+            targets,
 
-            uniform_buffer,
-            bind_group,
+            scene,
 
-            geometries,
-            pipelines,
+            bodies: Vec::new(),
+            transparent_bodies: BTreeSet::new(),
 
             config_ui,
 
+            model_error: None,
+            status: None,
+            measurement: None,
+            selection: selection::Overlay::default(),
+            parameters: ParameterPanel::default(),
+
+            tabs: Vec::new(),
+            active_tab: 0,
+            selected_tab: None,
+
             egui: EguiState {
                 context: egui_context,
                 winit_state: egui_winit_state,
@@ -265,11 +245,117 @@ impl Renderer {
     /// Updates the geometry of the model being rendered.
     pub fn update_geometry(
         &mut self,
-        mesh: Vertices,
-        lines: Vertices,
+        mesh: &Mesh<Point<3>>,
+        edges: &[PolyChain<3>],
+        debug_info: &DebugInfo,
         aabb: Aabb<3>,
     ) {
-        self.geometries = Geometries::new(&self.device, &mesh, &lines, aabb);
+        self.bodies = mesh
+            .bodies()
+            .iter()
+            .map(|body| body.triangles().collect())
+            .collect();
+
+        let [opaque, transparent] = self.split_bodies();
+        self.scene.geometries = Geometries::new(
+            &self.device,
+            &opaque,
+            &transparent,
+            edges,
+            debug_info,
+            aabb,
+        );
+    }
+
+    /// Split the bodies of the model into opaque and transparent ones
+    fn split_bodies(&self) -> [Vertices; 2] {
+        let mut opaque = Mesh::new();
+        let mut transparent = Mesh::new();
+
+        for (i, body) in self.bodies.iter().enumerate() {
+            let mesh = if self.transparent_bodies.contains(&i) {
+                &mut transparent
+            } else {
+                &mut opaque
+            };
+
+            for triangle in body {
+                mesh.push_triangle(triangle.points, triangle.color);
+            }
+        }
+
+        [&opaque, &transparent].map(Vertices::from)
+    }
+
+    /// Updates the cross-section, where the section plane cuts the model
+    ///
+    /// The cross-section is only displayed, while the section plane is
+    /// enabled. It is kept, when the geometry of the model is updated.
+    pub fn update_cross_section(&mut self, mesh: Vertices) {
+        self.scene.cross_section =
+            Geometry::new(&self.device, mesh.vertices(), mesh.indices());
+    }
+
+    /// Sets the error that occurred while loading the model
+    ///
+    /// The error is displayed in a panel over the model, until it is reset by
+    /// passing `None`.
+    pub fn set_model_error(&mut self, error: Option<String>) {
+        self.model_error = error;
+    }
+
+    /// Sets the status line, which is displayed below the model's size
+    ///
+    /// Use this to tell the user how long regenerating the model took, for
+    /// example.
+    pub fn set_status(&mut self, status: Option<String>) {
+        self.status = status;
+    }
+
+    /// Sets the measurement that is displayed over the model
+    ///
+    /// See [`MeasureTool::overlay`].
+    ///
+    /// [`MeasureTool::overlay`]: crate::measure::MeasureTool::overlay
+    pub fn set_measurement(&mut self, measurement: Option<Overlay>) {
+        self.measurement = measurement;
+    }
+
+    /// Sets the selection that is highlighted on the model
+    ///
+    /// See [`Selection::overlay`].
+    ///
+    /// [`Selection::overlay`]: crate::selection::Selection::overlay
+    pub fn set_selection(&mut self, selection: selection::Overlay) {
+        self.selection = selection;
+    }
+
+    /// Sets the parameters of the model, that the user can edit
+    ///
+    /// See [`ParameterPanel::set_parameters`].
+    pub fn set_parameters(&mut self, parameters: Vec<Parameter>) {
+        self.parameters.set_parameters(parameters);
+    }
+
+    /// Takes the values of the model's parameters, if the user changed any
+    ///
+    /// See [`ParameterPanel::take_changes`].
+    pub fn take_parameter_changes(&mut self) -> Option<Vec<(String, String)>> {
+        self.parameters.take_changes()
+    }
+
+    /// Sets the names of the open models, and which of them is displayed
+    ///
+    /// A tab is displayed for each model, which the user can select, if more
+    /// than one is open.
+    pub fn set_tabs(&mut self, tabs: Vec<String>, active: usize) {
+        self.tabs = tabs;
+        self.active_tab = active;
+    }
+
+    /// Takes the tab that the user selected, if they selected one
+    pub fn take_selected_tab(&mut self) -> Option<usize> {
+        self.selected_tab.take()
     }
 
     /// Resizes the render surface.
@@ -277,14 +363,48 @@ impl Renderer {
     /// # Arguments
     /// - `size`: The target size for the render surface.
     pub fn handle_resize(&mut self, size: Size) {
+        // The window is minimized. A surface can't have a size of zero, and
+        // there's nothing to draw anyway.
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
         self.surface_config.width = size.width;
         self.surface_config.height = size.height;
 
         self.surface.configure(&self.device, &self.surface_config);
 
-        let depth_view =
-            Self::create_depth_buffer(&self.device, &self.surface_config);
-        self.depth_view = depth_view;
+        self.targets = self.scene.create_targets(
+            &self.device,
+            self.surface_config.width,
+            self.surface_config.height,
+        );
+    }
+
+    /// Captures the model, as currently drawn, as an image
+    ///
+    /// The image has the size of the window, but only contains the model, not
+    /// the user interface.
+    pub async fn capture(
+        &self,
+        camera: &Camera,
+        config: &DrawConfig,
+    ) -> Result<Image, CaptureError> {
+        let size = Size {
+            width: self.surface_config.width,
+            height: self.surface_config.height,
+        };
+
+        capture::capture(
+            &self.device,
+            &self.queue,
+            &self.scene,
+            camera,
+            config,
+            size,
+            self.surface_config.format,
+        )
+        .await
     }
 
     /// Draws the renderer, camera, and config state to the window.
@@ -294,18 +414,31 @@ impl Renderer {
         config: &mut DrawConfig,
         window: &egui_winit::winit::window::Window,
     ) -> Result<(), DrawError> {
-        let aspect_ratio = self.surface_config.width as f64
-            / self.surface_config.height as f64;
-        let uniforms = Uniforms {
-            transform: Transform::for_vertices(camera, aspect_ratio),
-            transform_normals: Transform::for_normals(camera),
-        };
+        self.scene.update_reference(&self.device, camera);
 
-        self.queue.write_buffer(
-            &self.uniform_buffer,
-            0,
-            bytemuck::cast_slice(&[uniforms]),
-        );
+        if config.transparent_bodies != self.transparent_bodies {
+            self.transparent_bodies = config.transparent_bodies.clone();
+
+            let [opaque, transparent] = self.split_bodies();
+            self.scene.geometries.mesh = Geometry::new(
+                &self.device,
+                opaque.vertices(),
+                opaque.indices(),
+            );
+            self.scene.geometries.transparent = Geometry::new(
+                &self.device,
+                transparent.vertices(),
+                transparent.indices(),
+            );
+        }
+
+        let size = Size {
+            width: self.surface_config.width,
+            height: self.surface_config.height,
+        };
+        let uniforms =
+            self.scene
+                .update_uniforms(&self.queue, camera, config, size);
 
         let surface_texture = self.surface.get_current_texture()?;
         let color_view = surface_texture
@@ -316,34 +449,9 @@ impl Renderer {
             &wgpu::CommandEncoderDescriptor { label: None },
         );
 
-        self.clear_views(&mut encoder, &color_view);
-
-        let drawables = Drawables::new(&self.geometries, &self.pipelines);
-
-        if config.draw_model {
-            drawables.model.draw(
-                &mut encoder,
-                &color_view,
-                &self.depth_view,
-                &self.bind_group,
-            );
-        }
-        if config.draw_mesh {
-            drawables.mesh.draw(
-                &mut encoder,
-                &color_view,
-                &self.depth_view,
-                &self.bind_group,
-            );
-        }
-        if config.draw_debug {
-            drawables.lines.draw(
-                &mut encoder,
-                &color_view,
-                &self.depth_view,
-                &self.bind_group,
-            );
-        }
+        Scene::clear(&mut encoder, &color_view, &self.targets);
+        self.scene
+            .draw(&mut encoder, &color_view, &self.targets, config);
 
         if self.egui.options.show_original_ui {
             self.config_ui
@@ -352,8 +460,9 @@ impl Renderer {
                     &mut encoder,
                     &color_view,
                     &self.surface_config,
-                    &self.geometries.aabb,
+                    &self.scene.geometries.aabb,
                     config,
+                    window.scale_factor() as f32,
                 )
                 .map_err(DrawError::Text)?;
         }
@@ -408,26 +517,164 @@ impl Renderer {
             info
         }
 
+        if self.tabs.len() > 1 {
+            egui::TopBottomPanel::top("fj-tabs").show(
+                &self.egui.context,
+                |ui| {
+                    ui.horizontal(|ui| {
+                        for (i, name) in self.tabs.iter().enumerate() {
+                            let selected = i == self.active_tab;
+                            if ui
+                                .selectable_label(selected, name.as_str())
+                                .on_hover_text_at_pointer("Switch with Tab")
+                                .clicked()
+                                && !selected
+                            {
+                                self.selected_tab = Some(i);
+                            }
+                        }
+                    });
+                },
+            );
+        }
+
+        let num_bodies = self.bodies.len();
         egui::SidePanel::left("fj-left-panel").show(&self.egui.context, |ui| {
             ui.add_space(16.0);
 
             ui.group(|ui| {
                 ui.checkbox(&mut config.draw_model, "Render model")
                     .on_hover_text_at_pointer("Toggle with 1");
+                ui.indent("indent-display-mode", |ui| {
+                    ui.set_enabled(config.draw_model);
+
+                    for mode in DisplayMode::ALL {
+                        ui.radio_value(
+                            &mut config.display_mode,
+                            mode,
+                            mode.to_string(),
+                        )
+                        .on_hover_text_at_pointer("Cycle with D");
+                    }
+                });
                 ui.checkbox(&mut config.draw_mesh, "Render mesh")
                     .on_hover_text_at_pointer("Toggle with 2");
                 ui.checkbox(&mut config.draw_debug, "Render debug")
                     .on_hover_text_at_pointer("Toggle with 3");
+                ui.indent("indent-debug-categories", |ui| {
+                    ui.set_enabled(config.draw_debug);
+
+                    for category in DebugCategory::ALL {
+                        let mut enabled =
+                            config.debug_categories.contains(&category);
+                        let count = self
+                            .scene
+                            .geometries
+                            .debug_counts
+                            .get(&category)
+                            .copied()
+                            .unwrap_or(0);
+
+                        if ui
+                            .checkbox(
+                                &mut enabled,
+                                format!("{category} ({count})"),
+                            )
+                            .changed()
+                        {
+                            if enabled {
+                                config.debug_categories.insert(category);
+                            } else {
+                                config.debug_categories.remove(&category);
+                            }
+                        }
+                    }
+                });
+                ui.checkbox(&mut config.section.enabled, "Section plane")
+                    .on_hover_text_at_pointer("Toggle with 4");
+                ui.indent("indent-section", |ui| {
+                    ui.set_enabled(config.section.enabled);
+
+                    ui.horizontal(|ui| {
+                        for axis in SectionAxis::ALL {
+                            ui.radio_value(
+                                &mut config.section.axis,
+                                axis,
+                                axis.to_string(),
+                            );
+                        }
+                    });
+                    ui.add(
+                        egui::Slider::new(
+                            &mut config.section.position,
+                            0.0..=1.0,
+                        )
+                        .text("Position"),
+                    );
+                    ui.checkbox(&mut config.section.flip, "Flip");
+                    ui.checkbox(
+                        &mut config.section.draw_cross_section,
+                        "Show cross-section",
+                    );
+                });
+                ui.checkbox(&mut config.draw_grid, "Render grid")
+                    .on_hover_text_at_pointer("Toggle with 5");
+                ui.checkbox(&mut config.draw_axes, "Render axes")
+                    .on_hover_text_at_pointer("Toggle with 6");
+                ui.checkbox(&mut config.shadows, "Shadows");
+                ui.checkbox(&mut config.ambient_occlusion, "Ambient occlusion");
+                ui.checkbox(&mut config.x_ray, "X-ray")
+                    .on_hover_text_at_pointer("Toggle with X");
+                ui.collapsing("Transparent bodies", |ui| {
+                    ui.set_enabled(!config.x_ray);
+
+                    for body in 0..num_bodies {
+                        let mut transparent =
+                            config.transparent_bodies.contains(&body);
+
+                        if ui
+                            .checkbox(
+                                &mut transparent,
+                                format!("Body {}", body + 1),
+                            )
+                            .changed()
+                        {
+                            if transparent {
+                                config.transparent_bodies.insert(body);
+                            } else {
+                                config.transparent_bodies.remove(&body);
+                            }
+                        }
+                    }
+                });
                 ui.checkbox(
                     &mut self.egui.options.show_original_ui,
                     "Render original UI",
                 );
                 ui.add_space(16.0);
-                ui.strong(get_bbox_size_text(&self.geometries.aabb));
+                ui.strong(get_bbox_size_text(&self.scene.geometries.aabb));
+                if config.draw_grid {
+                    ui.label(format!(
+                        "Grid spacing: {}",
+                        self.scene.reference.spacing()
+                    ));
+                }
+                if let Some(status) = &self.status {
+                    ui.label(status.as_str());
+                }
             });
 
             ui.add_space(16.0);
 
+            if !self.parameters.is_empty() {
+                ui.group(|ui| {
+                    ui.strong("Parameters");
+                    self.parameters.ui(ui);
+                });
+
+                ui.add_space(16.0);
+            }
+
             {
                 ui.group(|ui| {
                     ui.checkbox(
@@ -545,6 +792,142 @@ impl Renderer {
             ui.add_space(16.0);
         });
 
+        if let Some(error) = &self.model_error {
+            egui::Window::new("Model error")
+                .anchor(egui::Align2::CENTER_BOTTOM, [0., -16.])
+                .collapsible(false)
+                .show(&self.egui.context, |ui| {
+                    egui::ScrollArea::vertical().max_height(300.).show(
+                        ui,
+                        |ui| {
+                            ui.label(
+                                egui::RichText::new(error)
+                                    .monospace()
+                                    .color(egui::Color32::LIGHT_RED),
+                            );
+                        },
+                    );
+                });
+        }
+
+        {
+            let painter = self.egui.context.layer_painter(egui::LayerId::new(
+                egui::Order::Background,
+                egui::Id::new("fj-selection"),
+            ));
+            let camera_position = camera.position();
+
+            let highlights = [
+                (
+                    &self.selection.hovered,
+                    egui::Color32::from_rgb(0, 160, 255),
+                ),
+                (
+                    &self.selection.selected,
+                    egui::Color32::from_rgb(0, 80, 255),
+                ),
+            ];
+            for (highlight, color) in highlights {
+                if let Some(highlight) = highlight {
+                    paint_highlight(
+                        &painter,
+                        &uniforms.transform,
+                        camera_position,
+                        highlight,
+                        color,
+                    );
+                }
+            }
+        }
+
+        if config.draw_axes {
+            let painter = self.egui.context.layer_painter(egui::LayerId::new(
+                egui::Order::Background,
+                egui::Id::new("fj-axes"),
+            ));
+            let center = self.egui.context.available_rect().left_bottom()
+                + egui::vec2(48., -48.);
+
+            paint_axes(&painter, camera, center);
+        }
+
+        if let Some(text) = &self.selection.text {
+            egui::Window::new("Selection")
+                .anchor(egui::Align2::RIGHT_BOTTOM, [-16., -16.])
+                .collapsible(false)
+                .resizable(false)
+                .show(&self.egui.context, |ui| {
+                    ui.label(egui::RichText::new(text).monospace());
+                });
+        }
+
+        if let Some(measurement) = &self.measurement {
+            egui::Window::new("Measurement")
+                .anchor(egui::Align2::RIGHT_TOP, [-16., 16.])
+                .collapsible(false)
+                .resizable(false)
+                .show(&self.egui.context, |ui| {
+                    ui.label(
+                        egui::RichText::new(&measurement.text).monospace(),
+                    );
+                });
+
+            let painter = self.egui.context.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("fj-measurement"),
+            ));
+            let screen = painter.clip_rect();
+            let color = egui::Color32::YELLOW;
+
+            if let Some(points) = measurement.line {
+                let [a, b] = points.map(|point| {
+                    project_to_screen(&uniforms.transform, screen, point)
+                });
+                if let (Some(a), Some(b)) = (a, b) {
+                    painter.line_segment([a, b], egui::Stroke::new(2., color));
+                }
+            }
+            for &point in &measurement.points {
+                if let Some(position) =
+                    project_to_screen(&uniforms.transform, screen, point)
+                {
+                    painter.circle_filled(position, 4., color);
+                }
+            }
+        }
+
+        if config.draw_debug {
+            let painter = self.egui.context.layer_painter(egui::LayerId::new(
+                egui::Order::Background,
+                egui::Id::new("fj-debug-labels"),
+            ));
+            let screen = painter.clip_rect();
+
+            for label in &self.scene.geometries.labels {
+                if !config.debug_categories.contains(&label.category) {
+                    continue;
+                }
+
+                let position = match project_to_screen(
+                    &uniforms.transform,
+                    screen,
+                    label.position,
+                ) {
+                    Some(position) => position,
+                    None => continue,
+                };
+                let [r, g, b, a] = label.color;
+
+                painter.text(
+                    position,
+                    egui::Align2::LEFT_BOTTOM,
+                    &label.text,
+                    egui::FontId::default(),
+                    egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+                );
+            }
+        }
+
         // End the UI frame. We could now handle the output and draw the UI with the backend.
         let egui_output = self.egui.context.end_frame();
         let egui_paint_jobs = self.egui.context.tessellate(egui_output.shapes);
@@ -571,54 +954,119 @@ impl Renderer {
         debug!("Finished drawing.");
         Ok(())
     }
+}
 
-    fn create_depth_buffer(
-        device: &wgpu::Device,
-        surface_config: &wgpu::SurfaceConfiguration,
-    ) -> wgpu::TextureView {
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: None,
-            size: wgpu::Extent3d {
-                width: surface_config.width,
-                height: surface_config.height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: DEPTH_FORMAT,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        });
+/// Project a point in model space to a position on the screen
+///
+/// Returns `None`, if the point is behind the camera.
+fn project_to_screen(
+    transform: &Transform,
+    screen: egui::Rect,
+    point: Point<3>,
+) -> Option<egui::Pos2> {
+    let [x, y, _] = transform.project_point(point)?;
+
+    Some(egui::pos2(
+        screen.left() + (x + 1.) / 2. * screen.width(),
+        screen.top() + (1. - y) / 2. * screen.height(),
+    ))
+}
 
-        texture.create_view(&wgpu::TextureViewDescriptor::default())
+/// Paint a highlighted face or edge over the model
+///
+/// Triangles that face away from the camera are skipped, so only the visible
+/// side of a face is highlighted.
+fn paint_highlight(
+    painter: &egui::Painter,
+    transform: &Transform,
+    camera_position: Point<3>,
+    highlight: &Highlight,
+    color: egui::Color32,
+) {
+    let screen = painter.clip_rect();
+
+    match highlight {
+        Highlight::Face(triangles) => {
+            let fill = egui::Color32::from_rgba_unmultiplied(
+                color.r(),
+                color.g(),
+                color.b(),
+                80,
+            );
+
+            for triangle in triangles {
+                let [a, _, _] = triangle.points();
+                if triangle.normal().dot(&(camera_position - a)) <= Scalar::ZERO
+                {
+                    continue;
+                }
+
+                let points = triangle
+                    .points()
+                    .into_iter()
+                    .map(|point| project_to_screen(transform, screen, point))
+                    .collect::<Option<Vec<_>>>();
+                if let Some(points) = points {
+                    painter.add(egui::Shape::convex_polygon(
+                        points,
+                        fill,
+                        egui::Stroke::none(),
+                    ));
+                }
+            }
+        }
+        Highlight::Edge(segments) => {
+            for segment in segments {
+                let [a, b] = segment
+                    .points()
+                    .map(|point| project_to_screen(transform, screen, point));
+                if let (Some(a), Some(b)) = (a, b) {
+                    painter.line_segment([a, b], egui::Stroke::new(3., color));
+                }
+            }
+        }
     }
+}
 
-    fn clear_views(
-        &self,
-        encoder: &mut wgpu::CommandEncoder,
-        view: &wgpu::TextureView,
-    ) {
-        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: None,
-            color_attachments: &[wgpu::RenderPassColorAttachment {
-                view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
-                    store: true,
-                },
-            }],
-            depth_stencil_attachment: Some(
-                wgpu::RenderPassDepthStencilAttachment {
-                    view: &self.depth_view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: true,
-                    }),
-                    stencil_ops: None,
-                },
-            ),
-        });
+/// Paint a gizmo that shows the orientation of the axes, as seen by the camera
+fn paint_axes(painter: &egui::Painter, camera: &Camera, center: egui::Pos2) {
+    let length: f32 = 32.;
+
+    let mut axes = ["X", "Y", "Z"]
+        .into_iter()
+        .zip(AXIS_COLORS)
+        .enumerate()
+        .map(|(i, (name, [r, g, b, _]))| {
+            let mut axis = [0f64; 3];
+            axis[i] = 1.;
+
+            let direction = camera.rotation.transform_vector(&axis.into());
+            (
+                direction,
+                name,
+                egui::Color32::from(egui::Rgba::from_rgb(r, g, b)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Paint the axes that point towards the viewer last, so they are on top.
+    axes.sort_by_key(|(direction, _, _)| direction.z);
+
+    for (direction, name, color) in axes {
+        let offset =
+            egui::vec2(direction.x.into_f32(), -direction.y.into_f32());
+
+        painter.line_segment(
+            [center, center + offset * length],
+            egui::Stroke::new(2., color),
+        );
+        painter.text(
+            center + offset * (length + 8.),
+            egui::Align2::CENTER_CENTER,
+            name,
+            egui::FontId::default(),
+            color,
+        );
     }
 }
 
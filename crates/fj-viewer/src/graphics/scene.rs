@@ -0,0 +1,353 @@
+use std::mem::size_of;
+
+use fj_interop::debug::DebugInfo;
+use fj_math::{Aabb, Point, Vector};
+use wgpu::util::DeviceExt as _;
+
+use crate::{camera::Camera, screen::Size};
+
+use super::{
+    draw_config::{DisplayMode, DrawConfig},
+    drawables::{Drawable, Drawables},
+    geometries::{Geometries, Geometry},
+    pipelines::Pipelines,
+    reference::Reference,
+    shading::{self, Shading, Targets},
+    transform::Transform,
+    uniforms::Uniforms,
+    vertices::Vertices,
+};
+
+/// The model, and everything required to draw it
+///
+/// This is independent of where the model is drawn to, so it can be shared
+/// between rendering to a window and rendering to an image.
+#[derive(Debug)]
+pub struct Scene {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+
+    pub geometries: Geometries,
+    pub cross_section: Geometry,
+    pub reference: Reference,
+    shading: Shading,
+    pipelines: Pipelines,
+}
+
+impl Scene {
+    pub fn new(
+        device: &wgpu::Device,
+        color_format: wgpu::TextureFormat,
+    ) -> Self {
+        let uniform_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&[Uniforms::default()]),
+                usage: wgpu::BufferUsages::UNIFORM
+                    | wgpu::BufferUsages::COPY_DST,
+            });
+        let bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::all(),
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(size_of::<
+                            Uniforms,
+                        >(
+                        )
+                            as u64),
+                    },
+                    count: None,
+                }],
+                label: None,
+            });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &uniform_buffer,
+                    offset: 0,
+                    size: None,
+                }),
+            }],
+            label: None,
+        });
+
+        let geometries = Geometries::new(
+            device,
+            &Vertices::empty(),
+            &Vertices::empty(),
+            &[],
+            &DebugInfo::new(),
+            Aabb {
+                min: Point::from([0.0, 0.0, 0.0]),
+                max: Point::from([0.0, 0.0, 0.0]),
+            },
+        );
+        let cross_section = Geometry::new(device, &[], &[]);
+        let reference = Reference::new(device);
+        let shading = Shading::new(device);
+        let pipelines =
+            Pipelines::new(device, &bind_group_layout, &shading, color_format);
+
+        Self {
+            uniform_buffer,
+            bind_group,
+
+            geometries,
+            cross_section,
+            reference,
+            shading,
+            pipelines,
+        }
+    }
+
+    /// Write the uniforms for drawing the model from the camera's view
+    ///
+    /// Returns the uniforms, which are also needed to place UI elements
+    /// relative to the model.
+    pub fn update_uniforms(
+        &self,
+        queue: &wgpu::Queue,
+        camera: &Camera,
+        config: &DrawConfig,
+        size: Size,
+    ) -> Uniforms {
+        let aabb = &self.geometries.aabb;
+        let aspect_ratio = size.width as f64 / size.height as f64;
+
+        let clip_plane = match config.section.plane(&self.geometries.aabb) {
+            Some(plane) => {
+                let normal = plane.normal;
+                let distance = normal.dot(&plane.origin.coords);
+
+                [normal.x, normal.y, normal.z, distance]
+                    .map(|scalar| scalar.into_f32())
+            }
+            None => [0.; 4],
+        };
+
+        let key_light = Vector::from(shading::KEY_LIGHT).normalize();
+
+        // Geometry occludes ambient light within a distance that is relative
+        // to the size of the model. The shader also needs that distance in
+        // pixels, to know how far away to look for occluding geometry.
+        let occlusion_radius = aabb.size().magnitude().into_f64() * 0.05;
+        let pixels_per_unit =
+            size.width as f64 / 2. / camera.half_view_width_at(aabb.center());
+        let occlusion_radius_in_pixels = occlusion_radius * pixels_per_unit;
+
+        let uniforms = Uniforms {
+            transform: Transform::for_vertices(camera, aspect_ratio),
+            transform_normals: Transform::for_normals(camera),
+            clip_plane,
+            view: Transform::from(&camera.camera_to_model()),
+            light_transform: Transform(
+                shading::light_transform(camera, aabb)
+                    .map(|scalar| scalar.into_f32()),
+            ),
+            key_light: [key_light.x, key_light.y, key_light.z, 0.]
+                .map(|scalar| scalar.into_f32()),
+            shading: [
+                config.casts_shadows() as u8 as f32,
+                config.occludes_ambient_light() as u8 as f32,
+                occlusion_radius as f32,
+                occlusion_radius_in_pixels as f32,
+            ],
+        };
+
+        queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[uniforms]),
+        );
+
+        uniforms
+    }
+
+    /// Adapt the grid to the camera, before drawing from its view
+    pub fn update_reference(&mut self, device: &wgpu::Device, camera: &Camera) {
+        let target = self.geometries.aabb.center();
+        self.reference.update(device, camera, target);
+    }
+
+    /// Create the textures required to draw a view of the given size
+    pub fn create_targets(
+        &self,
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+    ) -> Targets {
+        self.shading.create_targets(device, width, height)
+    }
+
+    /// Clear the color and depth views, before drawing to them
+    pub fn clear(
+        encoder: &mut wgpu::CommandEncoder,
+        color_view: &wgpu::TextureView,
+        targets: &Targets,
+    ) {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(
+                wgpu::RenderPassDepthStencilAttachment {
+                    view: &targets.depth,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                },
+            ),
+        });
+    }
+
+    /// Draw the model, as configured
+    pub fn draw(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        color_view: &wgpu::TextureView,
+        targets: &Targets,
+        config: &DrawConfig,
+    ) {
+        let drawables = Drawables::new(&self.geometries, &self.pipelines);
+        let depth_view = &targets.depth;
+
+        let display_mode = config.display_mode;
+
+        if config.draw_model && display_mode.is_shaded() {
+            if config.casts_shadows() {
+                self.shading.draw_shadow_map(
+                    encoder,
+                    &self.geometries.mesh,
+                    &self.pipelines.shadow,
+                    &self.bind_group,
+                );
+            }
+            if config.occludes_ambient_light() {
+                targets.draw_view_depth(
+                    encoder,
+                    &self.geometries.mesh,
+                    &self.pipelines.view_depth,
+                    &self.bind_group,
+                );
+            }
+
+            // In x-ray mode, all of the model is transparent, and drawn after
+            // everything else.
+            if !config.x_ray {
+                drawables.model.draw(
+                    encoder,
+                    color_view,
+                    depth_view,
+                    &[&self.bind_group, &targets.bind_group],
+                );
+            }
+        }
+        if config.draw_model
+            && display_mode == DisplayMode::HiddenLine
+            && !config.x_ray
+        {
+            Drawable::new(
+                &self.geometries.mesh,
+                &self.pipelines.hidden_surfaces,
+            )
+            .draw(
+                encoder,
+                color_view,
+                depth_view,
+                &[&self.bind_group],
+            );
+        }
+        if config.draw_model && display_mode.draws_edges() {
+            Drawable::new(&self.geometries.edges, &self.pipelines.edges).draw(
+                encoder,
+                color_view,
+                depth_view,
+                &[&self.bind_group],
+            );
+        }
+        if config.draw_model
+            && display_mode.is_shaded()
+            && config.section.enabled
+            && config.section.draw_cross_section
+        {
+            Drawable::new(&self.cross_section, &self.pipelines.cross_section)
+                .draw(encoder, color_view, depth_view, &[&self.bind_group]);
+        }
+        if config.draw_grid {
+            Drawable::new(&self.reference.grid, &self.pipelines.lines).draw(
+                encoder,
+                color_view,
+                depth_view,
+                &[&self.bind_group],
+            );
+        }
+        if config.draw_axes {
+            Drawable::new(&self.reference.origin, &self.pipelines.lines).draw(
+                encoder,
+                color_view,
+                depth_view,
+                &[&self.bind_group],
+            );
+        }
+        if config.draw_model && display_mode.is_shaded() {
+            // Transparent surfaces don't write to the depth buffer, so they
+            // need to be drawn after the opaque ones, or those would end up
+            // in front of them.
+            let mut transparent = vec![&self.geometries.transparent];
+            if config.x_ray {
+                transparent.push(&self.geometries.mesh);
+            }
+
+            for geometry in transparent {
+                Drawable::new(geometry, &self.pipelines.transparent).draw(
+                    encoder,
+                    color_view,
+                    depth_view,
+                    &[&self.bind_group, &targets.bind_group],
+                );
+            }
+        }
+        if config.draw_mesh {
+            // Without support for drawing polygons as lines, there's no way
+            // to draw the mesh.
+            if let Some(pipeline) = &self.pipelines.mesh {
+                for geometry in
+                    [&self.geometries.mesh, &self.geometries.transparent]
+                {
+                    Drawable::new(geometry, pipeline).draw(
+                        encoder,
+                        color_view,
+                        depth_view,
+                        &[&self.bind_group],
+                    );
+                }
+            }
+        }
+        if config.draw_debug {
+            for (category, drawable) in &drawables.debug {
+                if config.debug_categories.contains(category) {
+                    drawable.draw(
+                        encoder,
+                        color_view,
+                        depth_view,
+                        &[&self.bind_group],
+                    );
+                }
+            }
+        }
+    }
+}
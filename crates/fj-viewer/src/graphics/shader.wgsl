@@ -2,16 +2,56 @@ struct VertexOutput {
     [[builtin(position)]] position: vec4<f32>;
     [[location(0)]] normal: vec3<f32>;
     [[location(1)]] color: vec4<f32>;
+    [[location(2)]] model_position: vec3<f32>;
+    [[location(3)]] view_position: vec3<f32>;
 };
 
 struct Uniforms {
     transform: mat4x4<f32>;
     transform_normals: mat4x4<f32>;
+    // The section plane, with its normal in `xyz` and its distance from the
+    // origin in `w`. Everything in front of it is cut away. If the normal is
+    // zero, nothing is.
+    clip_plane: vec4<f32>;
+    // Transforms from model space into view space.
+    view: mat4x4<f32>;
+    // Transforms from model space into the clip space of the key light.
+    light_transform: mat4x4<f32>;
+    // The direction towards the key light, in view space, in `xyz`.
+    key_light: vec4<f32>;
+    // Whether shadows (`x`) and ambient occlusion (`y`) are enabled, the
+    // distance within which geometry occludes, in model units (`z`), and that
+    // distance in pixels, at the model's distance from the camera (`w`).
+    shading: vec4<f32>;
 };
 
 [[group(0), binding(0)]]
 var<uniform> uniforms: Uniforms;
 
+// Only available to `frag_model`.
+[[group(1), binding(0)]]
+var shadow_map: texture_depth_2d;
+[[group(1), binding(1)]]
+var shadow_sampler: sampler_comparison;
+[[group(1), binding(2)]]
+var view_depth: texture_2d<f32>;
+
+fn transform_vertex(
+    position: vec3<f32>,
+    normal: vec3<f32>,
+    color: vec4<f32>,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.normal = (uniforms.transform_normals * vec4<f32>(normal, 0.0)).xyz;
+    out.position = uniforms.transform * vec4<f32>(position, 1.0);
+    out.model_position = position;
+    out.view_position = (uniforms.view * vec4<f32>(position, 1.0)).xyz;
+    // We use premultiplied alpha blending.
+    out.color = vec4<f32>(color.rgb * color.a, color.a);
+
+    return out;
+}
+
 [[stage(vertex)]]
 fn vertex(
     [[location(0)]] position: vec3<f32>,
@@ -19,38 +59,270 @@ fn vertex(
     [[location(2)]] color: vec4<f32>,
 )
     -> VertexOutput
+{
+    return transform_vertex(position, normal, color);
+}
+
+// Draws the edges of the model slightly in front of the faces they bound, so
+// they aren't hidden by them.
+[[stage(vertex)]]
+fn vertex_edges(
+    [[location(0)]] position: vec3<f32>,
+    [[location(1)]] normal: vec3<f32>,
+    [[location(2)]] color: vec4<f32>,
+)
+    -> VertexOutput
+{
+    var out: VertexOutput = transform_vertex(position, normal, color);
+    out.position.z = out.position.z - 0.0002 * out.position.w;
+
+    return out;
+}
+
+// Draws the model as seen from the key light, into the shadow map.
+[[stage(vertex)]]
+fn vertex_shadow(
+    [[location(0)]] position: vec3<f32>,
+    [[location(1)]] normal: vec3<f32>,
+    [[location(2)]] color: vec4<f32>,
+)
+    -> VertexOutput
 {
     var out: VertexOutput;
-    out.normal = (uniforms.transform_normals * vec4<f32>(normal, 0.0)).xyz;
-    out.position = uniforms.transform * vec4<f32>(position, 1.0);
-    // We use premultiplied alpha blending.
-    out.color = vec4<f32>(color.rgb * color.a, color.a);
+    out.normal = normal;
+    out.position = uniforms.light_transform * vec4<f32>(position, 1.0);
+    out.model_position = position;
+    out.view_position = out.position.xyz;
+    out.color = color;
 
     return out;
 }
 
 let pi: f32 = 3.14159265359;
 
+fn is_clipped(position: vec3<f32>) -> bool {
+    return dot(uniforms.clip_plane.xyz, position) > uniforms.clip_plane.w;
+}
+
+// Light that reaches a surface from a directional light, and is reflected
+// towards the viewer.
+//
+// The diffuse part is Lambertian. The specular part uses the normalized
+// Blinn-Phong distribution, and Schlick's approximation of the Fresnel
+// effect, for a dielectric material.
+fn direct_light(
+    normal: vec3<f32>,
+    to_viewer: vec3<f32>,
+    to_light: vec3<f32>,
+    albedo: vec3<f32>,
+) -> vec3<f32> {
+    let n_dot_l = max(dot(normal, to_light), 0.0);
+
+    let halfway = normalize(to_light + to_viewer);
+    let n_dot_h = max(dot(normal, halfway), 0.0);
+    let v_dot_h = max(dot(to_viewer, halfway), 0.0);
+
+    let shininess = 64.0;
+    let fresnel = 0.04 + 0.96 * pow(1.0 - v_dot_h, 5.0);
+    // The distribution is multiplied by pi, like the diffuse part, whose
+    // division by pi cancels out against the light's intensity.
+    let specular = (shininess + 8.0) / 8.0 * pow(n_dot_h, shininess) * fresnel;
+
+    let diffuse = albedo * (1.0 - fresnel);
+    return (diffuse + vec3<f32>(specular, specular, specular)) * n_dot_l;
+}
+
+fn shade(
+    interpolated_normal: vec3<f32>,
+    view_position: vec3<f32>,
+    color: vec4<f32>,
+    key_light_visibility: f32,
+    occlusion: f32,
+) -> vec4<f32> {
+    let normal = normalize(interpolated_normal);
+    let albedo = color.rgb;
+
+    // This is only exact for the perspective projection, but close enough
+    // for the orthographic one.
+    let to_viewer = normalize(-view_position);
+
+    let key_light = normalize(uniforms.key_light.xyz);
+    let fill_light = normalize(vec3<f32>(0.6, -0.2, 0.8));
+
+    // Ambient light comes from a bright sky and a darker ground.
+    let sky = vec3<f32>(1.0, 1.0, 1.0);
+    let ground = vec3<f32>(0.45, 0.45, 0.4);
+    let ambient = mix(ground, sky, 0.5 + 0.5 * normal.y);
+
+    let key = direct_light(normal, to_viewer, key_light, albedo);
+    let fill = direct_light(normal, to_viewer, fill_light, albedo);
+
+    var light = albedo * ambient * 0.25 * occlusion;
+    light = light + key * 0.8 * key_light_visibility;
+    light = light + fill * 0.25;
+
+    return vec4<f32>(light, color.a);
+}
+
+// The fraction of the key light that reaches a point of the model.
+fn key_light_fraction(model_position: vec3<f32>) -> f32 {
+    let clip = uniforms.light_transform * vec4<f32>(model_position, 1.0);
+    let position = clip.xyz / clip.w;
+    let uv = position.xy * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
+
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        return 1.0;
+    }
+
+    // Percentage-closer filtering softens the edges of the shadows.
+    let texel = 1.0 / f32(textureDimensions(shadow_map).x);
+    var lit = 0.0;
+    for (var x: i32 = -2; x <= 2; x = x + 1) {
+        for (var y: i32 = -2; y <= 2; y = y + 1) {
+            let offset = vec2<f32>(f32(x), f32(y)) * texel * 1.5;
+            lit = lit + textureSampleCompareLevel(
+                shadow_map,
+                shadow_sampler,
+                uv + offset,
+                position.z,
+            );
+        }
+    }
+
+    return lit / 25.0;
+}
+
+// The fraction of the ambient light that isn't occluded by nearby geometry.
+//
+// Compares the distance of the point from the camera to that of the model at
+// surrounding pixels. `depth_gradient` is how that distance changes from
+// pixel to pixel, so a surface that's slanted towards the camera doesn't
+// occlude itself.
+fn ambient_occlusion(
+    pixel: vec2<f32>,
+    depth: f32,
+    depth_gradient: vec2<f32>,
+) -> f32 {
+    let size = textureDimensions(view_depth);
+    let radius = uniforms.shading.z;
+    let radius_in_pixels = uniforms.shading.w;
+
+    // Rotate the samples from pixel to pixel, which turns banding into noise
+    // that is less noticeable.
+    let noise = fract(
+        52.9829189 * fract(dot(pixel, vec2<f32>(0.06711056, 0.00583715)))
+    );
+
+    let samples = 16;
+    var occlusion = 0.0;
+    for (var i: i32 = 0; i < samples; i = i + 1) {
+        // Spread the samples in a spiral, using the golden angle.
+        let offset_length =
+            (f32(i) + 0.5) / f32(samples) * radius_in_pixels;
+        let angle = f32(i) * 2.39996 + noise * 2.0 * pi;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * offset_length;
+
+        let coords = vec2<i32>(pixel + offset);
+        if (coords.x < 0 || coords.y < 0 || coords.x >= size.x
+            || coords.y >= size.y) {
+            continue;
+        }
+
+        let expected_depth = depth + dot(depth_gradient, offset);
+        let sample_depth = textureLoad(view_depth, coords, 0).r;
+        let difference = expected_depth - sample_depth;
+
+        // Only geometry that is in front of the surface occludes. The further
+        // in front it is, the less it does.
+        if (difference > radius * 0.02) {
+            occlusion = occlusion + clamp(radius / difference, 0.0, 1.0);
+        }
+    }
+
+    return 1.0 - occlusion / f32(samples);
+}
+
+fn shade_model(in: VertexOutput, occlusion: f32) -> vec4<f32> {
+    var key_light = 1.0;
+    if (uniforms.shading.x > 0.5) {
+        key_light = key_light_fraction(in.model_position);
+    }
+
+    return shade(
+        in.normal,
+        in.view_position,
+        in.color,
+        key_light,
+        occlusion,
+    );
+}
+
 [[stage(fragment)]]
 fn frag_model(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    let light = vec3<f32>(0.0, 0.0, -1.0);
+    let depth = -in.view_position.z;
+    let depth_gradient = vec2<f32>(dpdx(depth), dpdy(depth));
 
-    let angle = acos(dot(light, -in.normal));
-    let f_angle = angle / (pi / 2.0);
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
 
-    let f_normal = max(1.0 - f_angle, 0.0);
+    var occlusion = 1.0;
+    if (uniforms.shading.y > 0.5) {
+        occlusion = ambient_occlusion(in.position.xy, depth, depth_gradient);
+    }
 
-    let color = vec4<f32>(in.color.rgb * f_normal, in.color.a);
+    return shade_model(in, occlusion);
+}
+
+// Transparent parts of the model don't write to the depth buffer, so they
+// aren't part of the texture that ambient occlusion is computed from.
+[[stage(fragment)]]
+fn frag_transparent(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
 
-    return color;
+    // We use premultiplied alpha blending, so the opacity scales all
+    // components.
+    let opacity = 0.25;
+    return shade_model(in, 1.0) * opacity;
 }
 
 [[stage(fragment)]]
 fn frag_mesh(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
+
     return vec4<f32>(1.0 - in.color.rgb, in.color.a);
 }
 
+// The cross-section lies in the section plane, and must not be cut away.
+[[stage(fragment)]]
+fn frag_cross_section(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    return shade(in.normal, in.view_position, in.color, 1.0, 1.0);
+}
+
 [[stage(fragment)]]
 fn frag_lines(in: VertexOutput) -> [[location(0)]] vec4<f32> {
     return vec4<f32>(in.color.rgb, in.color.a);
 }
+
+// Parts of the model that are cut away by the section plane don't cast
+// shadows.
+[[stage(fragment)]]
+fn frag_shadow(in: VertexOutput) {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
+}
+
+// Draws the distance from the camera, for ambient occlusion.
+[[stage(fragment)]]
+fn frag_view_depth(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
+
+    return vec4<f32>(-in.view_position.z, 0.0, 0.0, 1.0);
+}
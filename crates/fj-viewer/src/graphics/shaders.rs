@@ -18,27 +18,71 @@ impl Shaders {
     pub fn model(&self) -> Shader {
         Shader {
             module: &self.0,
+            vert_entry: "vertex",
             frag_entry: "frag_model",
         }
     }
 
+    pub fn transparent(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex",
+            frag_entry: "frag_transparent",
+        }
+    }
+
     pub fn mesh(&self) -> Shader {
         Shader {
             module: &self.0,
+            vert_entry: "vertex",
             frag_entry: "frag_mesh",
         }
     }
 
+    pub fn cross_section(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex",
+            frag_entry: "frag_cross_section",
+        }
+    }
+
     pub fn lines(&self) -> Shader {
         Shader {
             module: &self.0,
+            vert_entry: "vertex",
+            frag_entry: "frag_lines",
+        }
+    }
+
+    pub fn edges(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex_edges",
             frag_entry: "frag_lines",
         }
     }
+
+    pub fn shadow(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex_shadow",
+            frag_entry: "frag_shadow",
+        }
+    }
+
+    pub fn view_depth(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex",
+            frag_entry: "frag_view_depth",
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
 pub struct Shader<'r> {
     pub module: &'r wgpu::ShaderModule,
+    pub vert_entry: &'static str,
     pub frag_entry: &'static str,
 }
@@ -0,0 +1,299 @@
+use fj_math::{Aabb, Scalar, Transform, Vector};
+
+use crate::camera::Camera;
+
+use super::{geometries::Geometry, pipelines::Pipeline, DEPTH_FORMAT};
+
+/// The direction towards the key light, in view space
+///
+/// The light comes from the top left, and from slightly behind the viewer, so
+/// the shadows fall down and to the right, where they don't obscure much.
+pub const KEY_LIGHT: [f64; 3] = [-0.5, 0.7, 0.6];
+
+/// Resources that are used to shade the model
+///
+/// This is a shadow map for the key light, and everything the model shader
+/// needs to access it. Ambient occlusion requires a texture of the size of the
+/// view, which is part of the [`Targets`].
+#[derive(Debug)]
+pub struct Shading {
+    pub bind_group_layout: wgpu::BindGroupLayout,
+
+    shadow_map: wgpu::TextureView,
+    shadow_sampler: wgpu::Sampler,
+}
+
+impl Shading {
+    const SHADOW_MAP_SIZE: u32 = 2048;
+
+    /// The format of the texture that the view-space depth is drawn into
+    pub const VIEW_DEPTH_FORMAT: wgpu::TextureFormat =
+        wgpu::TextureFormat::R32Float;
+
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Comparison,
+                        ),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float {
+                                filterable: false,
+                            },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
+                label: None,
+            });
+
+        let shadow_map = create_texture(
+            device,
+            Self::SHADOW_MAP_SIZE,
+            Self::SHADOW_MAP_SIZE,
+            DEPTH_FORMAT,
+        );
+        let shadow_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: None,
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        });
+
+        Self {
+            bind_group_layout,
+
+            shadow_map,
+            shadow_sampler,
+        }
+    }
+
+    /// Create the textures that are required to draw a view of a given size
+    pub fn create_targets(
+        &self,
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+    ) -> Targets {
+        let depth = create_texture(device, width, height, DEPTH_FORMAT);
+        let view_depth =
+            create_texture(device, width, height, Self::VIEW_DEPTH_FORMAT);
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(
+                        &self.shadow_map,
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(
+                        &self.shadow_sampler,
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&view_depth),
+                },
+            ],
+            label: None,
+        });
+
+        Targets {
+            depth,
+            view_depth,
+            bind_group,
+        }
+    }
+
+    /// Draw the model, as seen from the key light, into the shadow map
+    pub fn draw_shadow_map(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        geometry: &Geometry,
+        pipeline: &Pipeline,
+        bind_group: &wgpu::BindGroup,
+    ) {
+        let mut render_pass =
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[],
+                depth_stencil_attachment: Some(
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view: &self.shadow_map,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(1.0),
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    },
+                ),
+            });
+
+        draw_geometry(&mut render_pass, geometry, pipeline, bind_group);
+    }
+}
+
+/// The textures that a view of the model is drawn into
+#[derive(Debug)]
+pub struct Targets {
+    /// The depth buffer
+    pub depth: wgpu::TextureView,
+
+    /// The distance of the model from the camera, for ambient occlusion
+    pub view_depth: wgpu::TextureView,
+
+    /// Provides the shadow map and `view_depth` to the model shader
+    pub bind_group: wgpu::BindGroup,
+}
+
+impl Targets {
+    /// Draw the distance of the model from the camera into `view_depth`
+    ///
+    /// This also fills the depth buffer, so it must be cleared before.
+    pub fn draw_view_depth(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        geometry: &Geometry,
+        pipeline: &Pipeline,
+        bind_group: &wgpu::BindGroup,
+    ) {
+        let mut render_pass =
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view: &self.view_depth,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        // Where there's no model, it is infinitely far away.
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: f32::MAX as f64,
+                            g: 0.,
+                            b: 0.,
+                            a: 0.,
+                        }),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view: &self.depth,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    },
+                ),
+            });
+
+        draw_geometry(&mut render_pass, geometry, pipeline, bind_group);
+    }
+}
+
+/// Compute the transform from model space into the clip space of the key light
+///
+/// The key light is directional, so this is an orthographic projection. It
+/// covers the bounding sphere of the model, regardless of the direction it is
+/// viewed from.
+pub fn light_transform(camera: &Camera, aabb: &Aabb<3>) -> [Scalar; 16] {
+    let to_light = Vector::from(KEY_LIGHT).normalize();
+    let z = Vector::unit_z();
+
+    // Rotate the view, so the key light looks along the negative z axis, like
+    // a camera.
+    let axis = to_light.cross(&z);
+    let rotation = if axis.magnitude() > Scalar::ZERO {
+        let angle = to_light.dot(&z).into_f64().acos();
+        Transform::rotation(axis.normalize() * angle)
+    } else {
+        Transform::identity()
+    };
+
+    let transform = rotation
+        * camera.rotation
+        * Transform::translation(-aabb.center().coords);
+
+    // Make sure the projection doesn't degenerate for empty models.
+    let radius = (aabb.size().magnitude().into_f64() / 2.).max(1e-6);
+
+    // The projection maps z from `-near` to `-far` into the range from -1 to
+    // 1, but only the range from 0 to 1 ends up in the shadow map. Choose near
+    // and far, such that the bounding sphere ends up in that range.
+    transform.project_orthographic_to_array(
+        radius * 2.,
+        radius * 2.,
+        -radius * 3.,
+        radius,
+    )
+}
+
+fn create_texture(
+    device: &wgpu::Device,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING,
+    });
+
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn draw_geometry<'r>(
+    render_pass: &mut wgpu::RenderPass<'r>,
+    geometry: &'r Geometry,
+    pipeline: &'r Pipeline,
+    bind_group: &'r wgpu::BindGroup,
+) {
+    render_pass.set_pipeline(&pipeline.0);
+    render_pass.set_bind_group(0, bind_group, &[]);
+    render_pass.set_vertex_buffer(0, geometry.vertex_buffer.slice(..));
+    render_pass.set_index_buffer(
+        geometry.index_buffer.slice(..),
+        wgpu::IndexFormat::Uint32,
+    );
+
+    render_pass.draw_indexed(0..geometry.num_indices, 0, 0..1);
+}
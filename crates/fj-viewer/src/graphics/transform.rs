@@ -1,6 +1,8 @@
 use bytemuck::{Pod, Zeroable};
 
-use crate::camera::Camera;
+use fj_math::Point;
+
+use crate::camera::{Camera, Projection};
 
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(transparent)]
@@ -15,19 +17,53 @@ impl Transform {
     ///
     /// The returned transform is used for transforming vertices on the GPU.
     pub fn for_vertices(camera: &Camera, aspect_ratio: f64) -> Self {
-        let field_of_view_in_y = 2.
-            * ((camera.field_of_view_in_x() / 2.).tan() / aspect_ratio).atan();
+        let transform = match camera.projection {
+            Projection::Perspective => {
+                let field_of_view_in_y = 2.
+                    * ((camera.field_of_view_in_x() / 2.).tan() / aspect_ratio)
+                        .atan();
+
+                camera.camera_to_model().project_to_array(
+                    aspect_ratio,
+                    field_of_view_in_y,
+                    camera.near_plane(),
+                    camera.far_plane(),
+                )
+            }
+            Projection::Orthographic => {
+                let width = camera.orthographic_half_width() * 2.;
 
-        let transform = camera.camera_to_model().project_to_array(
-            aspect_ratio,
-            field_of_view_in_y,
-            camera.near_plane(),
-            camera.far_plane(),
-        );
+                camera.camera_to_model().project_orthographic_to_array(
+                    width,
+                    width / aspect_ratio,
+                    camera.near_plane(),
+                    camera.far_plane(),
+                )
+            }
+        };
 
         Self(transform.map(|scalar| scalar.into_f32()))
     }
 
+    /// Project a point into normalized device coordinates
+    ///
+    /// Returns `None`, if the point is behind the camera.
+    pub fn project_point(&self, point: Point<3>) -> Option<[f32; 3]> {
+        let m = &self.0;
+        let [x, y, z] = point.coords.components.map(|s| s.into_f32());
+
+        // The matrix is stored in column-major order.
+        let clip = [0, 1, 2, 3]
+            .map(|i| m[i] * x + m[4 + i] * y + m[8 + i] * z + m[12 + i]);
+
+        let w = clip[3];
+        if w <= 0. {
+            return None;
+        }
+
+        Some([clip[0] / w, clip[1] / w, clip[2] / w])
+    }
+
     /// Compute transform used for normals
     ///
     /// This method is only relevant for the graphics code. The returned
@@ -7,6 +7,26 @@ use super::transform::Transform;
 pub struct Uniforms {
     pub transform: Transform,
     pub transform_normals: Transform,
+
+    /// The section plane, with its normal and its distance from the origin
+    ///
+    /// Everything in front of the plane is cut away. A zero normal disables
+    /// this.
+    pub clip_plane: [f32; 4],
+
+    /// Transforms from model space into view space
+    pub view: Transform,
+
+    /// Transforms from model space into the clip space of the key light
+    pub light_transform: Transform,
+
+    /// The direction towards the key light, in view space
+    pub key_light: [f32; 4],
+
+    /// Parameters for shadows and ambient occlusion
+    ///
+    /// See the shader for what the components mean.
+    pub shading: [f32; 4],
 }
 
 impl Default for Uniforms {
@@ -14,6 +34,11 @@ impl Default for Uniforms {
         Self {
             transform: Transform::identity(),
             transform_normals: Transform::identity(),
+            clip_plane: [0.; 4],
+            view: Transform::identity(),
+            light_transform: Transform::identity(),
+            key_light: [0.; 4],
+            shading: [0.; 4],
         }
     }
 }
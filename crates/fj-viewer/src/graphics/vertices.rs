@@ -1,9 +1,9 @@
 use bytemuck::{Pod, Zeroable};
 use fj_interop::{
-    debug::DebugInfo,
+    debug::{DebugGeometry, DebugGeometryKind},
     mesh::{Index, Mesh},
 };
-use fj_math::{Point, Vector};
+use fj_math::{Point, Scalar, Vector};
 
 #[derive(Debug)]
 pub struct Vertices {
@@ -45,93 +45,91 @@ impl Vertices {
         self.indices.push(self.indices.len() as u32);
     }
 
+    /// Push a cross that marks a position, with arms along all three axes
     pub fn push_cross(
         &mut self,
         position: Point<3>,
+        size: f64,
         normal: [f32; 3],
         color: [f32; 4],
     ) {
-        let d = 0.05;
+        let d = size / 2.;
 
-        self.push_line(
-            [
-                position - Vector::from([d, 0., 0.]),
-                position + Vector::from([d, 0., 0.]),
-            ],
-            normal,
-            color,
-        );
-        self.push_line(
-            [
-                position - Vector::from([0., d, 0.]),
-                position + Vector::from([0., d, 0.]),
-            ],
-            normal,
-            color,
-        );
+        for arm in [[d, 0., 0.], [0., d, 0.], [0., 0., d]] {
+            let arm = Vector::from(arm);
+            self.push_line([position - arm, position + arm], normal, color);
+        }
     }
-}
 
-impl From<&Mesh<fj_math::Point<3>>> for Vertices {
-    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
-        let mut m = Mesh::new();
+    /// Convert debug geometry into lines
+    ///
+    /// Points, the origins of rays, and the positions of labels are marked
+    /// with crosses of the given size.
+    pub fn from_debug_geometry<'r>(
+        geometry: impl IntoIterator<Item = &'r DebugGeometry>,
+        marker_size: f64,
+    ) -> Self {
+        let mut self_ = Self::empty();
 
-        for triangle in mesh.triangles() {
-            let [a, b, c] = triangle.points;
+        for geometry in geometry {
+            let normal = [0.; 3];
+            let color = geometry.color.map(|v| f32::from(v) / 255.0);
+
+            match &geometry.kind {
+                DebugGeometryKind::Point(point) => {
+                    self_.push_cross(*point, marker_size, normal, color);
+                }
+                DebugGeometryKind::Ray { origin, direction } => {
+                    self_.push_cross(*origin, marker_size, normal, color);
+                    self_.push_line(
+                        [*origin, *origin + *direction],
+                        normal,
+                        color,
+                    );
+                }
+                DebugGeometryKind::Curve(points) => {
+                    for segment in points.windows(2) {
+                        self_.push_line(
+                            [segment[0], segment[1]],
+                            normal,
+                            color,
+                        );
+                    }
+                }
+                DebugGeometryKind::Label { position, .. } => {
+                    // The text itself is drawn by the UI. Just mark the
+                    // position here.
+                    self_.push_cross(*position, marker_size, normal, color);
+                }
+            }
+        }
 
-            let normal = (b - a).cross(&(c - a)).normalize();
-            let color = triangle.color;
+        self_
+    }
+}
 
-            m.push_vertex((a, normal, color));
-            m.push_vertex((b, normal, color));
-            m.push_vertex((c, normal, color));
-        }
+impl From<&Mesh<fj_math::Point<3>>> for Vertices {
+    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+        // Triangles that meet at a smaller angle than this are considered to
+        // approximate the same curved surface, and are shaded smoothly.
+        let crease_angle = Scalar::PI / 6.;
+        let mesh = mesh.with_attributes(crease_angle);
 
-        let vertices = m
+        let vertices = mesh
             .vertices()
-            .map(|(vertex, normal, color)| Vertex {
-                position: vertex.into(),
-                normal: normal.into(),
-                color: color.map(|v| f32::from(v) / 255.0),
+            .map(|vertex| Vertex {
+                position: vertex.position.into(),
+                normal: vertex.normal.into(),
+                color: vertex.color.map(|v| f32::from(v) / 255.0),
             })
             .collect();
 
-        let indices = m.indices().collect();
+        let indices = mesh.indices().collect();
 
         Self { vertices, indices }
     }
 }
 
-impl From<&DebugInfo> for Vertices {
-    fn from(debug_info: &DebugInfo) -> Self {
-        let mut self_ = Self::empty();
-
-        for triangle_edge_check in &debug_info.triangle_edge_checks {
-            let normal = [0.; 3];
-
-            let red = [1., 0., 0., 1.];
-            let green = [0., 1., 0., 1.];
-
-            let color = if triangle_edge_check.hits.len() % 2 == 0 {
-                red
-            } else {
-                green
-            };
-
-            self_.push_cross(triangle_edge_check.origin, normal, color);
-
-            for &hit in &triangle_edge_check.hits {
-                let line = hit.points();
-                let color = [0., 0., 0., 1.];
-
-                self_.push_line(line, normal, color);
-            }
-        }
-
-        self_
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 #[repr(C)]
 pub struct Vertex {
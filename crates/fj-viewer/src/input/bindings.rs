@@ -0,0 +1,286 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+/// Configures which mouse input moves the camera in which way
+///
+/// Create this from a [`NavigationStyle`], or from a [`NavigationConfig`], to
+/// override parts of a style.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bindings {
+    /// The mouse button that rotates the model, when dragging
+    pub rotate: MouseBinding,
+
+    /// The mouse button that moves the model, when dragging
+    pub pan: MouseBinding,
+
+    /// The mouse button that zooms, when dragging up and down, if any
+    ///
+    /// Zooming using the mouse wheel is always possible.
+    pub zoom: Option<MouseBinding>,
+
+    /// Whether scrolling the mouse wheel up zooms out, instead of in
+    pub invert_scroll: bool,
+
+    /// Factor that the speed of rotation is multiplied with
+    pub rotation_sensitivity: f64,
+
+    /// Factor that the speed of zooming is multiplied with
+    pub zoom_sensitivity: f64,
+}
+
+impl Bindings {
+    /// Determine what dragging with a mouse button does
+    ///
+    /// Bindings that require the held modifier keys take precedence over those
+    /// that don't require any. Returns `None`, if the button isn't bound.
+    pub fn action(
+        &self,
+        button: MouseButton,
+        modifiers: Modifiers,
+    ) -> Option<Action> {
+        let bindings = [
+            (Some(self.rotate), Action::Rotate),
+            (Some(self.pan), Action::Pan),
+            (self.zoom, Action::Zoom),
+        ];
+        let bindings = bindings.iter().filter_map(|&(binding, action)| {
+            let binding = binding.filter(|binding| binding.button == button)?;
+            Some((binding, action))
+        });
+
+        let mut fallback = None;
+        for (binding, action) in bindings {
+            match binding.modifier {
+                Some(modifier) if modifiers.contains(modifier) => {
+                    return Some(action);
+                }
+                Some(_) => {}
+                None => {
+                    fallback = fallback.or(Some(action));
+                }
+            }
+        }
+
+        fallback
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        NavigationStyle::default().bindings()
+    }
+}
+
+/// What dragging with a mouse button does
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Rotate the model around the focus point
+    Rotate,
+
+    /// Move the model up, down, left, or right
+    Pan,
+
+    /// Move the view forwards and backwards
+    Zoom,
+}
+
+/// A mouse button, optionally combined with a modifier key
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct MouseBinding {
+    /// The mouse button
+    pub button: MouseButton,
+
+    /// The modifier key that must be held, if any
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modifier: Option<Modifier>,
+}
+
+impl MouseBinding {
+    /// Bind a mouse button, without a modifier key
+    pub fn new(button: MouseButton) -> Self {
+        Self {
+            button,
+            modifier: None,
+        }
+    }
+
+    /// Require a modifier key to be held, in addition to the mouse button
+    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifier = Some(modifier);
+        self
+    }
+}
+
+/// A mouse button that can be bound to an [`Action`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MouseButton {
+    /// The left mouse button
+    Left,
+
+    /// The right mouse button
+    Right,
+
+    /// The middle mouse button, usually the mouse wheel
+    Middle,
+}
+
+/// A modifier key that can be combined with a [`MouseButton`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Modifier {
+    /// The shift key
+    Shift,
+
+    /// The control key
+    Ctrl,
+
+    /// The alt key
+    Alt,
+}
+
+/// The modifier keys that are currently held
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Modifiers {
+    /// Whether the shift key is held
+    pub shift: bool,
+
+    /// Whether the control key is held
+    pub ctrl: bool,
+
+    /// Whether the alt key is held
+    pub alt: bool,
+}
+
+impl Modifiers {
+    /// Indicate whether a modifier key is held
+    pub fn contains(&self, modifier: Modifier) -> bool {
+        match modifier {
+            Modifier::Shift => self.shift,
+            Modifier::Ctrl => self.ctrl,
+            Modifier::Alt => self.alt,
+        }
+    }
+}
+
+/// A preset of [`Bindings`], resembling the navigation of other applications
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum NavigationStyle {
+    /// Rotate with the left mouse button, pan with the right one
+    #[default]
+    Fornjot,
+
+    /// Rotate with the middle mouse button, pan with it while holding control
+    ///
+    /// Resembles the navigation of many traditional CAD applications.
+    Cad,
+
+    /// Rotate with the middle mouse button, pan with it while holding shift,
+    /// zoom with it while holding control
+    ///
+    /// Resembles the navigation of Blender.
+    Blender,
+}
+
+impl NavigationStyle {
+    /// Access the bindings of this style
+    pub fn bindings(&self) -> Bindings {
+        let (rotate, pan, zoom, invert_scroll) = match self {
+            Self::Fornjot => (
+                MouseBinding::new(MouseButton::Left),
+                MouseBinding::new(MouseButton::Right),
+                None,
+                false,
+            ),
+            Self::Cad => (
+                MouseBinding::new(MouseButton::Middle),
+                MouseBinding::new(MouseButton::Middle)
+                    .with_modifier(Modifier::Ctrl),
+                None,
+                true,
+            ),
+            Self::Blender => (
+                MouseBinding::new(MouseButton::Middle),
+                MouseBinding::new(MouseButton::Middle)
+                    .with_modifier(Modifier::Shift),
+                Some(
+                    MouseBinding::new(MouseButton::Middle)
+                        .with_modifier(Modifier::Ctrl),
+                ),
+                false,
+            ),
+        };
+
+        Bindings {
+            rotate,
+            pan,
+            zoom,
+            invert_scroll,
+            rotation_sensitivity: 1.,
+            zoom_sensitivity: 1.,
+        }
+    }
+}
+
+/// Configuration of the viewer's navigation, as loaded from a file
+///
+/// Starts from the bindings of a [`NavigationStyle`], and overrides those
+/// that are specified.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NavigationConfig {
+    /// The style that the bindings are based on
+    pub style: NavigationStyle,
+
+    /// Overrides [`Bindings::rotate`]
+    pub rotate: Option<MouseBinding>,
+
+    /// Overrides [`Bindings::pan`]
+    pub pan: Option<MouseBinding>,
+
+    /// Overrides [`Bindings::zoom`]
+    pub zoom: Option<MouseBinding>,
+
+    /// Overrides [`Bindings::invert_scroll`]
+    pub invert_scroll: Option<bool>,
+
+    /// Overrides [`Bindings::rotation_sensitivity`]
+    pub rotation_sensitivity: Option<f64>,
+
+    /// Overrides [`Bindings::zoom_sensitivity`]
+    pub zoom_sensitivity: Option<f64>,
+}
+
+impl NavigationConfig {
+    /// Compute the bindings that this configuration results in
+    pub fn bindings(&self) -> Bindings {
+        let mut bindings = self.style.bindings();
+
+        if let Some(rotate) = self.rotate {
+            bindings.rotate = rotate;
+        }
+        if let Some(pan) = self.pan {
+            bindings.pan = pan;
+        }
+        if let Some(zoom) = self.zoom {
+            bindings.zoom = Some(zoom);
+        }
+        if let Some(invert_scroll) = self.invert_scroll {
+            bindings.invert_scroll = invert_scroll;
+        }
+        if let Some(sensitivity) = self.rotation_sensitivity {
+            bindings.rotation_sensitivity = sensitivity;
+        }
+        if let Some(sensitivity) = self.zoom_sensitivity {
+            bindings.zoom_sensitivity = sensitivity;
+        }
+
+        bindings
+    }
+}
@@ -1,9 +1,17 @@
 //! User input parsing and propagation.
 
+mod bindings;
 mod event;
 mod handler;
 mod movement;
 mod rotation;
 mod zoom;
 
-pub use self::{event::Event, handler::Handler};
+pub use self::{
+    bindings::{
+        Action, Bindings, Modifier, Modifiers, MouseBinding, MouseButton,
+        NavigationConfig, NavigationStyle,
+    },
+    event::Event,
+    handler::Handler,
+};
@@ -1,7 +1,7 @@
 use fj_math::{Point, Scalar, Transform, Vector};
 
 use crate::{
-    camera::{Camera, FocusPoint},
+    camera::{Camera, FocusPoint, Projection},
     screen::NormalizedPosition,
 };
 
@@ -18,10 +18,19 @@ impl Movement {
         let previous = camera.cursor_to_model_space(previous);
         let cursor = camera.cursor_to_model_space(current);
 
-        let d1 = Point::distance(&camera.position(), &cursor);
-        let d2 = Point::distance(&camera.position(), &focus_point.0);
+        let diff = match camera.projection {
+            Projection::Perspective => {
+                let d1 = Point::distance(&camera.position(), &cursor);
+                let d2 = Point::distance(&camera.position(), &focus_point.0);
 
-        let diff = (cursor - previous) * d2 / d1;
+                (cursor - previous) * d2 / d1
+            }
+            Projection::Orthographic => {
+                // Without perspective, the model moves exactly as far as the
+                // cursor, regardless of its distance to the camera.
+                cursor - previous
+            }
+        };
         let offset = camera.camera_to_model().transform_vector(&diff);
 
         camera.translation = camera.translation
@@ -17,4 +17,7 @@
 pub mod camera;
 pub mod graphics;
 pub mod input;
+pub mod measure;
+pub mod parameters;
 pub mod screen;
+pub mod selection;
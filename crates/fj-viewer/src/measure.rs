@@ -0,0 +1,169 @@
+//! Interactive measurement of a model
+
+use std::fmt::Write as _;
+
+use fj_interop::features::{
+    CurveGeometry, Feature, Features, Pick, SurfaceGeometry,
+};
+use fj_math::{Point, Scalar};
+
+use crate::{camera::Camera, screen::NormalizedPosition};
+
+/// Measures the features of a model, that the user picks
+///
+/// While the tool is active, the user can pick two features of the model,
+/// vertices, edges, or faces, and see the distance and angle between them, as
+/// well as their radius, if they are circular.
+#[derive(Debug, Default)]
+pub struct MeasureTool {
+    active: bool,
+    picks: Vec<Pick>,
+}
+
+impl MeasureTool {
+    /// The distance within which vertices and edges are picked
+    ///
+    /// Relative to the width of the view.
+    const SNAP_DISTANCE: f64 = 0.02;
+
+    /// Indicate whether the tool is active
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Activate or deactivate the tool
+    ///
+    /// Discards all features that have been picked so far.
+    pub fn toggle(&mut self) {
+        self.active = !self.active;
+        self.clear();
+    }
+
+    /// Discard all features that have been picked so far
+    ///
+    /// This must be called, when the model changes, as the picked features
+    /// refer to the features of a specific model.
+    pub fn clear(&mut self) {
+        self.picks.clear();
+    }
+
+    /// Pick the feature that the cursor points at
+    ///
+    /// Does nothing, if the tool is not active, or if the cursor doesn't point
+    /// at the model. Starts a new measurement, if two features have already
+    /// been picked.
+    pub fn pick(
+        &mut self,
+        cursor: NormalizedPosition,
+        camera: &Camera,
+        features: &Features,
+    ) {
+        if !self.active {
+            return;
+        }
+
+        let hit = match features.cast_ray(&camera.cursor_ray(cursor)) {
+            Some(hit) => hit,
+            None => return,
+        };
+        let snap_distance =
+            camera.half_view_width_at(hit.point) * 2. * Self::SNAP_DISTANCE;
+
+        if self.picks.len() >= 2 {
+            self.picks.clear();
+        }
+        self.picks
+            .push(features.pick(&hit, Scalar::from(snap_distance)));
+    }
+
+    /// Describe the current measurement, for display to the user
+    ///
+    /// Returns `None`, if the tool is not active.
+    pub fn overlay(&self, features: &Features) -> Option<Overlay> {
+        if !self.active {
+            return None;
+        }
+
+        let mut overlay = Overlay {
+            text: String::new(),
+            points: self.picks.iter().map(|pick| pick.point).collect(),
+            line: None,
+        };
+
+        for (i, pick) in self.picks.iter().enumerate() {
+            let _ = writeln!(
+                overlay.text,
+                "{}: {}",
+                i + 1,
+                describe(features, pick.feature)
+            );
+            if let Some(radius) = features.radius(pick.feature) {
+                let _ = writeln!(overlay.text, "   Radius: {:.3}", radius);
+            }
+        }
+
+        match self.picks.as_slice() {
+            [] => {
+                overlay
+                    .text
+                    .push_str("Click a vertex, edge, or face to measure it.");
+            }
+            [_] => {
+                overlay.text.push_str("Click a second feature to measure.");
+            }
+            [a, b, ..] => {
+                if let Some(measurement) =
+                    features.measure(a.feature, b.feature)
+                {
+                    let _ = writeln!(
+                        overlay.text,
+                        "Distance: {:.3}",
+                        measurement.distance
+                    );
+                    if let Some(angle) = measurement.angle {
+                        let _ = writeln!(
+                            overlay.text,
+                            "Angle: {:.2}°",
+                            angle.into_f64().to_degrees()
+                        );
+                    }
+
+                    overlay.line = Some(measurement.points);
+                }
+            }
+        }
+
+        overlay.text = overlay.text.trim_end().to_owned();
+        Some(overlay)
+    }
+}
+
+/// A measurement, as it is displayed to the user
+#[derive(Debug)]
+pub struct Overlay {
+    /// The text that describes the measurement
+    pub text: String,
+
+    /// The points where features have been picked
+    pub points: Vec<Point<3>>,
+
+    /// The line between the closest points of the picked features
+    pub line: Option<[Point<3>; 2]>,
+}
+
+/// Describe the kind of a feature, including its exact geometry, if known
+pub(crate) fn describe(features: &Features, feature: Feature) -> &'static str {
+    match feature {
+        Feature::Vertex(_) => "Vertex",
+        Feature::Edge(i) => match features.edges[i].curve {
+            Some(CurveGeometry::Circle(_)) => "Circular edge",
+            Some(CurveGeometry::Line(_)) => "Straight edge",
+            None => "Edge",
+        },
+        Feature::Face(i) => match features.faces[i].surface {
+            Some(SurfaceGeometry::Cylinder { .. }) => "Cylindrical face",
+            Some(SurfaceGeometry::Plane { .. }) => "Planar face",
+            None => "Face",
+        },
+    }
+}
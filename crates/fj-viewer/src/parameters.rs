@@ -0,0 +1,200 @@
+//! Editing the parameters of a model
+
+use std::{fmt, ops::RangeInclusive};
+
+/// A parameter of a model, as it is presented to the user
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    /// The name of the parameter
+    pub name: String,
+
+    /// The current value of the parameter
+    pub value: ParameterValue,
+
+    /// The minimum value of the parameter, if it has one
+    pub minimum: Option<f64>,
+
+    /// The maximum value of the parameter, if it has one
+    pub maximum: Option<f64>,
+}
+
+impl Parameter {
+    /// Construct a parameter from its description
+    ///
+    /// `ty` is the name of the parameter's Rust type, and `value` its value as
+    /// a string. The type determines how the parameter can be edited. Values
+    /// of unknown types, or values that don't match their type, are edited as
+    /// text.
+    pub fn new(
+        name: impl Into<String>,
+        ty: &str,
+        value: &str,
+        minimum: Option<f64>,
+        maximum: Option<f64>,
+    ) -> Self {
+        let parsed = match ty {
+            "f32" | "f64" => value.parse().ok().map(ParameterValue::Float),
+            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32"
+            | "u64" | "usize" => {
+                value.parse().ok().map(ParameterValue::Integer)
+            }
+            "bool" => value.parse().ok().map(ParameterValue::Bool),
+            _ => None,
+        };
+
+        Self {
+            name: name.into(),
+            value: parsed
+                .unwrap_or_else(|| ParameterValue::Text(value.to_owned())),
+            minimum,
+            maximum,
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let minimum = self.minimum.unwrap_or(f64::NEG_INFINITY);
+        let maximum = self.maximum.unwrap_or(f64::INFINITY);
+        let bounded = self.minimum.is_some() && self.maximum.is_some();
+
+        match &mut self.value {
+            ParameterValue::Float(value) => {
+                if bounded {
+                    ui.add(
+                        egui::Slider::new(value, minimum..=maximum)
+                            .text(self.name.as_str()),
+                    )
+                    .changed()
+                } else {
+                    labeled(ui, &self.name, |ui| {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .speed(0.01)
+                                .clamp_range(minimum..=maximum),
+                        )
+                    })
+                    .changed()
+                }
+            }
+            ParameterValue::Integer(value) => {
+                let range = integer_range(minimum..=maximum);
+                if bounded {
+                    ui.add(
+                        egui::Slider::new(value, range)
+                            .text(self.name.as_str()),
+                    )
+                    .changed()
+                } else {
+                    labeled(ui, &self.name, |ui| {
+                        ui.add(egui::DragValue::new(value).clamp_range(range))
+                    })
+                    .changed()
+                }
+            }
+            ParameterValue::Bool(value) => {
+                ui.checkbox(value, self.name.as_str()).changed()
+            }
+            ParameterValue::Text(value) => {
+                // The model is only evaluated, once the user is done
+                // editing, as intermediate values are likely invalid.
+                labeled(ui, &self.name, |ui| ui.text_edit_singleline(value))
+                    .lost_focus()
+            }
+        }
+    }
+}
+
+/// The value of a [`Parameter`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterValue {
+    /// A floating-point number
+    Float(f64),
+
+    /// An integer
+    Integer(i64),
+
+    /// A boolean
+    Bool(bool),
+
+    /// A value of any other type, edited as text
+    Text(String),
+}
+
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The parameters of a model, as they are edited by the user
+#[derive(Debug, Default)]
+pub struct ParameterPanel {
+    parameters: Vec<Parameter>,
+    changed: bool,
+}
+
+impl ParameterPanel {
+    /// Indicate whether the model has any parameters
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Replace the parameters with those of a newly evaluated model
+    ///
+    /// The minimum and maximum of a parameter can depend on the values of
+    /// other parameters, so this should be called every time the model has
+    /// been evaluated.
+    pub fn set_parameters(&mut self, parameters: Vec<Parameter>) {
+        self.parameters = parameters;
+    }
+
+    /// Take the values of the parameters, if the user has changed any
+    ///
+    /// Returns the names and values of all parameters, as the model expects
+    /// them, so it can be evaluated with them.
+    pub fn take_changes(&mut self) -> Option<Vec<(String, String)>> {
+        if !self.changed {
+            return None;
+        }
+        self.changed = false;
+
+        Some(
+            self.parameters
+                .iter()
+                .map(|parameter| {
+                    (parameter.name.clone(), parameter.value.to_string())
+                })
+                .collect(),
+        )
+    }
+
+    /// Show the parameters in the UI, letting the user edit them
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        for parameter in &mut self.parameters {
+            self.changed |= parameter.ui(ui);
+        }
+    }
+}
+
+fn labeled(
+    ui: &mut egui::Ui,
+    label: &str,
+    add_contents: impl FnOnce(&mut egui::Ui) -> egui::Response,
+) -> egui::Response {
+    ui.horizontal(|ui| {
+        let response = add_contents(ui);
+        ui.label(label);
+        response
+    })
+    .inner
+}
+
+fn integer_range(range: RangeInclusive<f64>) -> RangeInclusive<i64> {
+    // Casting saturates, so unbounded ranges map to the full range of `i64`.
+    let (minimum, maximum) = range.into_inner();
+    (minimum.ceil() as i64)..=(maximum.floor() as i64)
+}
@@ -0,0 +1,145 @@
+//! Hovering over and selecting faces and edges of a model
+
+use std::fmt::Write as _;
+
+use fj_interop::features::{Feature, Features};
+use fj_math::{Scalar, Segment, Triangle};
+
+use crate::{camera::Camera, measure::describe, screen::NormalizedPosition};
+
+/// Tracks the face or edge that the cursor hovers over, and the selected one
+///
+/// Features are referred to by their index in [`Features`]. The levels of
+/// detail of a model share their features, so the selection is kept, while a
+/// model is refined.
+#[derive(Debug, Default)]
+pub struct Selection {
+    hovered: Option<Feature>,
+    selected: Option<Feature>,
+}
+
+impl Selection {
+    /// The distance within which edges are hovered over
+    ///
+    /// Relative to the width of the view.
+    const SNAP_DISTANCE: f64 = 0.01;
+
+    /// Access the feature that the cursor hovers over
+    pub fn hovered(&self) -> Option<Feature> {
+        self.hovered
+    }
+
+    /// Access the selected feature
+    pub fn selected(&self) -> Option<Feature> {
+        self.selected
+    }
+
+    /// Update the feature that the cursor hovers over
+    ///
+    /// Pass `None` as the cursor, if it has left the window. Returns whether
+    /// the hovered feature has changed.
+    pub fn hover(
+        &mut self,
+        cursor: Option<NormalizedPosition>,
+        camera: &Camera,
+        features: &Features,
+    ) -> bool {
+        let hovered = cursor.and_then(|cursor| {
+            let hit = features.cast_ray(&camera.cursor_ray(cursor))?;
+            let snap_distance =
+                camera.half_view_width_at(hit.point) * 2. * Self::SNAP_DISTANCE;
+
+            let pick =
+                features.pick_edge_or_face(&hit, Scalar::from(snap_distance));
+            Some(pick.feature)
+        });
+
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        changed
+    }
+
+    /// Select the feature that the cursor hovers over
+    ///
+    /// Clears the selection, if the cursor doesn't hover over any feature.
+    pub fn select(&mut self) {
+        self.selected = self.hovered;
+    }
+
+    /// Discard the hovered and selected features
+    ///
+    /// This must be called, when the model changes, as the features refer to
+    /// the features of a specific model.
+    pub fn clear(&mut self) {
+        self.hovered = None;
+        self.selected = None;
+    }
+
+    /// Describe the selection, for display to the user
+    pub fn overlay(&self, features: &Features) -> Overlay {
+        let hovered = self
+            .hovered
+            .filter(|&hovered| Some(hovered) != self.selected)
+            .map(|feature| Highlight::new(features, feature));
+        let selected = self
+            .selected
+            .map(|feature| Highlight::new(features, feature));
+        let text = self.selected.map(|feature| properties(features, feature));
+
+        Overlay {
+            hovered,
+            selected,
+            text,
+        }
+    }
+}
+
+/// A selection, as it is displayed to the user
+#[derive(Debug, Default)]
+pub struct Overlay {
+    /// The feature that the cursor hovers over, unless it is selected
+    pub hovered: Option<Highlight>,
+
+    /// The selected feature
+    pub selected: Option<Highlight>,
+
+    /// The properties of the selected feature
+    pub text: Option<String>,
+}
+
+/// The geometry of a feature, for highlighting it
+#[derive(Debug)]
+pub enum Highlight {
+    /// A face, approximated by triangles
+    Face(Vec<Triangle<3>>),
+
+    /// An edge, approximated by segments
+    Edge(Vec<Segment<3>>),
+}
+
+impl Highlight {
+    fn new(features: &Features, feature: Feature) -> Self {
+        match feature {
+            Feature::Face(i) => Self::Face(features.faces[i].triangles.clone()),
+            Feature::Edge(i) => Self::Edge(features.edges[i].approx.segments()),
+            // Only faces and edges are hovered over or selected.
+            Feature::Vertex(_) => Self::Edge(Vec::new()),
+        }
+    }
+}
+
+fn properties(features: &Features, feature: Feature) -> String {
+    let mut text = describe(features, feature).to_owned();
+
+    if let Some(length) = features.length(feature) {
+        let _ = write!(text, "\nLength: {:.3}", length);
+    }
+    if let Some(area) = features.area(feature) {
+        let _ = write!(text, "\nArea: {:.3}", area);
+    }
+    if let Some(radius) = features.radius(feature) {
+        let _ = write!(text, "\nRadius: {:.3}", radius);
+    }
+
+    text
+}
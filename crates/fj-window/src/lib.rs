@@ -14,4 +14,5 @@
 #![warn(missing_docs)]
 
 pub mod run;
+pub mod session;
 pub mod window;
@@ -3,18 +3,39 @@
 //! Provides the functionality to create a window and perform basic viewing
 //! with programmed models.
 
-use std::error;
+use std::{
+    error,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-use fj_host::Watcher;
-use fj_operations::shape_processor::ShapeProcessor;
+use fj_host::{Timings, Watcher, WatcherEvent};
+use fj_interop::{
+    debug::DebugInfo,
+    features::Features,
+    mesh::{Color, FaceMesh, Mesh},
+    processed_shape::ProcessedShape,
+    progress::{CancellationToken, Progress, ProgressHandle},
+};
+use fj_kernel::validation::ValidationCache;
+use fj_math::{Aabb, Point};
+use fj_operations::{
+    shape_cache::ShapeCache,
+    shape_processor::{self, Backend, ShapeProcessor},
+    Shape as _,
+};
 use fj_viewer::{
-    camera::Camera,
-    graphics::{self, DrawConfig, Renderer},
-    input,
+    camera::{Camera, StandardView},
+    graphics::{self, DrawConfig, Renderer, SectionPlane},
+    input::{self, Action, Bindings, Modifiers},
+    measure::MeasureTool,
+    parameters::Parameter,
     screen::{NormalizedPosition, Screen as _, Size},
+    selection::Selection,
 };
 use futures::executor::block_on;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 use winit::{
     dpi::PhysicalPosition,
     event::{
@@ -24,64 +45,545 @@ use winit::{
     event_loop::{ControlFlow, EventLoop},
 };
 
-use crate::window::{self, Window};
+use crate::{
+    session::Session,
+    window::{self, Window},
+};
+
+/// The number of levels of detail that each shape is processed into
+const LEVELS_OF_DETAIL: usize = 3;
 
-/// Initializes a model viewer for a given model and enters its process loop.
-pub fn run(
+/// The color of the cross-section, where the section plane cuts the model
+const CROSS_SECTION_COLOR: Color = [255, 128, 0, 255];
+
+/// A message from the thread that processes a shape
+enum Processed {
+    /// Processing has started
+    ///
+    /// Contains a shape without any geometry, that the faces of the shape are
+    /// added to, as they are received.
+    Started(ProcessedShape),
+
+    /// A face of the shape has been triangulated
+    Face(FaceMesh),
+
+    /// A level of detail of the shape has been completed
+    Level(ProcessedShape),
+
+    /// Processing has progressed
+    Progress(Progress),
+
+    /// All levels of detail have been completed, which took the given time
+    Finished(Duration),
+
+    /// Processing has failed
+    Error(shape_processor::Error),
+}
+
+/// A model that is open in the viewer, with everything that belongs to it
+///
+/// Each model is displayed in its own tab. Only one of them is displayed at a
+/// time, but all of them are watched and processed.
+struct Tab {
+    name: String,
     watcher: Watcher,
+
+    /// The model that was loaded last
+    ///
+    /// It is kept around, so its cross-section can be computed, whenever the
+    /// section plane changes.
+    model: Option<fj::Shape>,
+
+    /// The shape that is displayed
+    shape: Option<ProcessedShape>,
+
+    /// A shape whose processing has started, but whose first face hasn't been
+    /// received yet. Until then, the previous shape is still displayed.
+    started_shape: Option<ProcessedShape>,
+
+    /// The error that occurred while loading the model, if any
+    error: Option<String>,
+
+    /// How long loading the model took, according to the host
+    timings: Option<Timings>,
+
+    /// Tells the user how far along processing is, or how long regenerating the
+    /// model took
+    status: Option<String>,
+
+    camera: Option<Camera>,
+    camera_fitted: bool,
+
+    measure_tool: MeasureTool,
+    selection: Selection,
+
+    /// Cancels processing of the current shape, if the model changes
+    cancellation_token: Option<CancellationToken>,
+
+    /// Identifies the shape that is currently being processed
+    ///
+    /// Messages about outdated shapes that are still in the channel are
+    /// recognized by that, and ignored.
+    generation: u64,
+
+    /// The objects of the tab's model that have passed validation
+    ///
+    /// Each tab has its own, so regenerating one model doesn't evict the
+    /// entries of the others.
+    validation_cache: ValidationCache,
+}
+
+impl Tab {
+    fn new(name: String, watcher: Watcher) -> Self {
+        Self {
+            name,
+            watcher,
+            model: None,
+            shape: None,
+            started_shape: None,
+            error: None,
+            timings: None,
+            status: None,
+            camera: None,
+            camera_fitted: false,
+            measure_tool: MeasureTool::default(),
+            selection: Selection::default(),
+            cancellation_token: None,
+            generation: 0,
+            validation_cache: ValidationCache::new(),
+        }
+    }
+
+    /// Configure a shape processor to use the tab's validation cache
+    fn shape_processor(
+        &self,
+        shape_processor: &ShapeProcessor,
+    ) -> ShapeProcessor {
+        ShapeProcessor {
+            validation_cache: Some(self.validation_cache.clone()),
+            ..shape_processor.clone()
+        }
+    }
+}
+
+/// Initializes a model viewer for the given models and enters its process
+/// loop.
+///
+/// `models` contains each model's watcher, together with the name of the tab
+/// that it is displayed in. The first model is displayed initially.
+///
+/// If a `shape_cache` is provided, shapes that have been processed before are
+/// displayed from the cache, instead of being processed again.
+///
+/// `bindings` configures how the mouse moves the camera.
+pub fn run(
+    models: Vec<(String, Watcher)>,
     shape_processor: ShapeProcessor,
+    shape_cache: Option<ShapeCache>,
+    bindings: Bindings,
 ) -> Result<(), Error> {
+    if models.is_empty() {
+        return Err(Error::NoModel);
+    }
+
+    let mut tabs: Vec<_> = models
+        .into_iter()
+        .map(|(name, watcher)| Tab::new(name, watcher))
+        .collect();
+    let mut active_tab = 0;
+
+    // The window and camera are restored, as the user left them in the
+    // previous session.
+    let mut session = Session::load();
+
     let event_loop = EventLoop::new();
-    let window = Window::new(&event_loop)?;
+    let window = Window::new(&event_loop, session.window.as_ref())?;
 
     let mut previous_cursor = None;
     let mut held_mouse_button = None;
+    let mut modifiers = Modifiers::default();
+
+    // What dragging with the held mouse button does, according to the
+    // bindings.
+    let mut drag_action = None;
     let mut focus_point = None;
 
+    // The cursor position when the left mouse button was pressed. Used to
+    // tell clicks, which pick features to measure, from dragging.
+    let mut click_cursor = None;
+
     let mut input_handler = input::Handler::default();
     let mut renderer = block_on(Renderer::new(&window))?;
+    show_tab(&mut renderer, &tabs, active_tab);
 
     let mut draw_config = DrawConfig::default();
 
-    let mut shape = None;
-    let mut camera = None;
+    // Shapes are processed in the background, so the window stays responsive.
+    // If a model changes while a shape is still being processed, processing
+    // of the outdated shape is cancelled.
+    //
+    // Messages carry the index of the tab they belong to, and the generation
+    // of the shape they are about.
+    let (processed_tx, processed_rx) = mpsc::channel();
+
+    // Cross-sections are computed in the background too, just like shapes.
+    // The section generation identifies the most recent request, while the
+    // requested section records the tab, shape generation, and plane it was
+    // made for, so a new one is only computed, if any of them changes.
+    let (section_tx, section_rx) = mpsc::channel();
+    let mut section_cancellation_token: Option<CancellationToken> = None;
+    let mut section_generation = 0u64;
+    let mut requested_section: Option<(usize, u64, SectionPlane)> = None;
 
     event_loop.run(move |event, _, control_flow| {
         trace!("Handling event: {:?}", event);
 
-        if let Some(new_shape) = watcher.receive() {
-            match shape_processor.process(&new_shape) {
-                Ok(new_shape) => {
-                    renderer.update_geometry(
-                        (&new_shape.mesh).into(),
-                        (&new_shape.debug_info).into(),
-                        new_shape.aabb,
+        // The tab to display from now on, if the user switched tabs.
+        let mut selected_tab = renderer.take_selected_tab();
+
+        // Evaluate the model again, if the user has changed its parameters.
+        if let Some(changes) = renderer.take_parameter_changes() {
+            let watcher = &mut tabs[active_tab].watcher;
+
+            let mut parameters = watcher.parameters().clone();
+            for (name, value) in changes {
+                parameters.insert(name, value);
+            }
+            watcher.update_parameters(parameters);
+        }
+
+        // All models are kept up to date, not just the displayed one, so
+        // switching tabs is instant.
+        for (index, tab) in tabs.iter_mut().enumerate() {
+            let is_active = index == active_tab;
+
+            let new_shape = match tab.watcher.receive_event() {
+                Ok(Some(WatcherEvent::Shape(new_shape))) => {
+                    tab.error = None;
+                    tab.timings = tab.watcher.timings();
+                    if is_active {
+                        renderer.set_model_error(None);
+                        renderer.set_parameters(parameters(&tab.watcher));
+                    }
+                    Some(new_shape)
+                }
+                Ok(None) => None,
+                Err(fj_host::Error::Compile { output }) => {
+                    // The compiler output has already been forwarded to the
+                    // terminal. Display it in the UI too, where the user can
+                    // actually see it.
+                    println!("Error compiling model `{}`", tab.name);
+                    show_model_error(&mut renderer, tab, is_active, output);
+                    None
+                }
+                Err(fj_host::Error::Disconnected) => {
+                    println!("Stopped watching model for changes");
+                    *control_flow = ControlFlow::Exit;
+                    return;
+                }
+                Err(err) => {
+                    let context =
+                        format!("Error reloading model `{}`", tab.name);
+                    print_error(&context, &err);
+                    show_model_error(
+                        &mut renderer,
+                        tab,
+                        is_active,
+                        error_chain(&context, &err),
                     );
+                    None
+                }
+            };
+
+            if let Some(new_shape) = new_shape {
+                tab.model = Some(new_shape.clone());
+
+                if let Some(token) = tab.cancellation_token.take() {
+                    token.cancel();
+                }
+
+                let token = CancellationToken::new();
+                tab.cancellation_token = Some(token.clone());
+
+                tab.generation += 1;
+                let generation = tab.generation;
 
-                    if camera.is_none() {
-                        camera = Some(Camera::new(&new_shape.aabb));
+                let processed_tx = processed_tx.clone();
+                let shape_cache = shape_cache.clone();
+                let shape_processor = tab.shape_processor(&shape_processor);
+                thread::spawn(move || {
+                    let mut progress = ProgressHandle::from_callback({
+                        let processed_tx = processed_tx.clone();
+                        move |progress| {
+                            let _ = processed_tx.send((
+                                index,
+                                generation,
+                                Processed::Progress(progress),
+                            ));
+                        }
+                    })
+                    .with_cancellation_token(token);
+
+                    // The receiver only disconnects, if the window has been
+                    // closed. Then nobody is interested in the result anyway.
+                    let send = |processed| {
+                        let _ =
+                            processed_tx.send((index, generation, processed));
+                    };
+
+                    send(Processed::Started(ProcessedShape {
+                        aabb: new_shape.bounding_volume(),
+                        mesh: Mesh::new(),
+                        edges: Vec::new(),
+                        features: Features::new(),
+                        debug_info: DebugInfo::new(),
+                    }));
+
+                    // A shape that has been processed before is displayed
+                    // from the cache. There is no need to process it again.
+                    let start = Instant::now();
+                    let cached = shape_cache.as_ref().and_then(|cache| {
+                        cache.load(&new_shape, &shape_processor)
+                    });
+                    if let Some(cached) = cached {
+                        send(Processed::Level(cached));
+                        send(Processed::Finished(start.elapsed()));
+                        return;
+                    }
+
+                    // Only the finest level is stored in the cache. That's the
+                    // last one, and the SDF backend only produces that one.
+                    let num_levels = match shape_processor.backend {
+                        Backend::BRep => LEVELS_OF_DETAIL,
+                        Backend::Sdf(_) => 1,
+                    };
+                    let mut levels = 0;
+                    let mut finest = None;
+                    let level = |level: ProcessedShape| {
+                        levels += 1;
+                        if shape_cache.is_some() && levels == num_levels {
+                            finest =
+                                Some((level.mesh.clone(), level.edges.clone()));
+                        }
+                        send(Processed::Level(level));
+                    };
+
+                    // Stream the faces of a coarse version of the shape first,
+                    // so something can be displayed quickly, then refine it.
+                    let result = shape_processor.process_streaming(
+                        &new_shape,
+                        LEVELS_OF_DETAIL,
+                        &mut progress,
+                        |face| send(Processed::Face(face)),
+                        level,
+                    );
+                    if let Err(err) = result {
+                        send(Processed::Error(err));
+                        return;
+                    }
+                    send(Processed::Finished(start.elapsed()));
+
+                    if let (Some(cache), Some((mesh, edges))) =
+                        (&shape_cache, finest)
+                    {
+                        if let Err(err) = cache.store(
+                            &new_shape,
+                            &shape_processor,
+                            &mesh,
+                            &edges,
+                        ) {
+                            warn!("Error caching mesh: {err}");
+                        }
                     }
+                });
+            }
+        }
 
-                    shape = Some(new_shape);
+        // Process all pending messages, but only update the geometry once.
+        let mut geometry_changed = false;
+        for (index, message_generation, processed) in processed_rx.try_iter() {
+            let tab = &mut tabs[index];
+            if message_generation != tab.generation {
+                continue;
+            }
+
+            match processed {
+                Processed::Started(new_shape) => {
+                    tab.started_shape = Some(new_shape);
                 }
-                Err(err) => {
-                    // Can be cleaned up, once `Report` is stable:
-                    // https://doc.rust-lang.org/std/error/struct.Report.html
+                Processed::Face(face) => {
+                    if let Some(new_shape) = tab.started_shape.take() {
+                        tab.shape = Some(new_shape);
+
+                        // The picked features belong to the previous model.
+                        // The levels of detail of the same model share their
+                        // features, so picks are kept, while it's refined.
+                        tab.measure_tool.clear();
+                        tab.selection.clear();
+                    }
+
+                    if let Some(shape) = &mut tab.shape {
+                        for triangle in face.triangles {
+                            shape
+                                .mesh
+                                .push_triangle(triangle.points, triangle.color);
+                        }
+                    }
+
+                    geometry_changed |= index == active_tab;
+                }
+                Processed::Level(new_shape) => {
+                    // A cached mesh arrives before any faces.
+                    if tab.started_shape.take().is_some() {
+                        tab.measure_tool.clear();
+                        tab.selection.clear();
+                    }
 
-                    println!("Shape processing error: {}", err);
+                    tab.shape = Some(new_shape);
+                    geometry_changed |= index == active_tab;
+                }
+                Processed::Progress(progress) => {
+                    // Replaced by the timings, once processing has finished.
+                    tab.status = Some(progress.to_string());
+                    if index == active_tab {
+                        renderer.set_status(tab.status.clone());
+                    }
+                }
+                Processed::Finished(triangulation) => {
+                    let status = status(tab.timings, triangulation);
+                    debug!("{}: {status}", tab.name);
 
-                    let mut current_err = &err as &dyn error::Error;
-                    while let Some(err) = current_err.source() {
-                        println!();
-                        println!("Caused by:");
-                        println!("    {}", err);
+                    tab.status = Some(status);
+                    if index == active_tab {
+                        renderer.set_status(tab.status.clone());
+                    }
+                }
+                Processed::Error(shape_processor::Error::Cancelled(_)) => {
+                    // A newer shape is already being processed.
+                }
+                Processed::Error(err) => {
+                    print_error("Shape processing error", &err);
 
-                        current_err = err;
+                    tab.status = None;
+                    if index == active_tab {
+                        renderer.set_status(None);
                     }
                 }
             }
         }
 
+        // Frame the first shape of each model, so it's neither tiny nor
+        // off-screen, regardless of its size. A camera might already exist, if
+        // an error was displayed before the first shape was loaded.
+        //
+        // The first model is displayed on startup, so it gets the camera of
+        // the previous session instead, if there is one.
+        for (index, tab) in tabs.iter_mut().enumerate() {
+            if let (Some(shape), false) = (&tab.shape, tab.camera_fitted) {
+                let camera =
+                    tab.camera.get_or_insert_with(|| Camera::new(&shape.aabb));
+                let state = if index == 0 {
+                    session.camera.take()
+                } else {
+                    None
+                };
+                match state {
+                    Some(state) => camera.restore(&state),
+                    None => camera.fit_view(&shape.aabb),
+                }
+                tab.camera_fitted = true;
+            }
+        }
+
+        let num_tabs = tabs.len();
+        let tab = &mut tabs[active_tab];
+
+        if geometry_changed {
+            if let Some(shape) = &tab.shape {
+                renderer.update_geometry(
+                    &shape.mesh,
+                    &shape.edges,
+                    &shape.debug_info,
+                    shape.aabb,
+                );
+            }
+
+            update_measurement(
+                &mut renderer,
+                &tab.measure_tool,
+                tab.shape.as_ref(),
+            );
+            update_selection(&mut renderer, &tab.selection, tab.shape.as_ref());
+        }
+
+        // Compute a new cross-section, if the section plane or the model has
+        // changed since the last one was requested.
+        let section = match (&tab.shape, draw_config.section.draw_cross_section)
+        {
+            (Some(shape), true) => draw_config
+                .section
+                .plane(&shape.aabb)
+                .map(|plane| (active_tab, tab.generation, plane)),
+            _ => None,
+        };
+        if section != requested_section {
+            requested_section = section;
+
+            if let Some(token) = section_cancellation_token.take() {
+                token.cancel();
+            }
+            section_generation += 1;
+
+            match (section, &tab.model) {
+                (Some((_, _, plane)), Some(model)) => {
+                    let token = CancellationToken::new();
+                    section_cancellation_token = Some(token.clone());
+
+                    let section_generation = section_generation;
+                    let section_tx = section_tx.clone();
+                    let model = model.clone();
+                    let shape_processor = tab.shape_processor(&shape_processor);
+                    thread::spawn(move || {
+                        let mut progress = ProgressHandle::new()
+                            .with_cancellation_token(token);
+
+                        let result = shape_processor.process_section(
+                            &model,
+                            plane.origin,
+                            plane.normal,
+                            CROSS_SECTION_COLOR,
+                            &mut progress,
+                        );
+
+                        // The receiver only disconnects, if the window has
+                        // been closed.
+                        let _ = section_tx.send((section_generation, result));
+                    });
+                }
+                _ => {
+                    renderer.update_cross_section(
+                        (&Mesh::<Point<3>>::new()).into(),
+                    );
+                }
+            }
+        }
+
+        for (message_generation, result) in section_rx.try_iter() {
+            if message_generation != section_generation {
+                continue;
+            }
+
+            match result {
+                Ok(mesh) => renderer.update_cross_section((&mesh).into()),
+                Err(shape_processor::Error::Cancelled(_)) => {
+                    // A newer cross-section has already been requested.
+                }
+                Err(err) => {
+                    print_error("Error computing cross-section", &err);
+                }
+            }
+        }
+
         //
 
         if let Event::WindowEvent {
@@ -142,7 +644,54 @@ pub fn run(
                 VirtualKeyCode::Key3 => {
                     draw_config.draw_debug = !draw_config.draw_debug
                 }
-                _ => {}
+                VirtualKeyCode::Key4 => {
+                    draw_config.section.enabled = !draw_config.section.enabled
+                }
+                VirtualKeyCode::Key5 => {
+                    draw_config.draw_grid = !draw_config.draw_grid
+                }
+                VirtualKeyCode::Key6 => {
+                    draw_config.draw_axes = !draw_config.draw_axes
+                }
+                VirtualKeyCode::D => {
+                    draw_config.display_mode = draw_config.display_mode.next()
+                }
+                VirtualKeyCode::X => draw_config.x_ray = !draw_config.x_ray,
+                VirtualKeyCode::Tab => {
+                    selected_tab = Some((active_tab + 1) % num_tabs);
+                }
+                VirtualKeyCode::P => {
+                    if let Some(camera) = &mut tab.camera {
+                        camera.toggle_projection();
+                    }
+                }
+                VirtualKeyCode::M => {
+                    tab.measure_tool.toggle();
+                    update_measurement(
+                        &mut renderer,
+                        &tab.measure_tool,
+                        tab.shape.as_ref(),
+                    );
+                }
+                VirtualKeyCode::Z => {
+                    if let (Some(shape), Some(camera)) =
+                        (&tab.shape, &mut tab.camera)
+                    {
+                        camera.fit_view(&shape.aabb);
+                    }
+                }
+                VirtualKeyCode::S => {
+                    if let Some(camera) = &tab.camera {
+                        save_screenshot(&renderer, camera, &draw_config);
+                    }
+                }
+                key => {
+                    if let (Some(view), Some(shape), Some(camera)) =
+                        (standard_view(key), &tab.shape, &mut tab.camera)
+                    {
+                        camera.look_from(view, &shape.aabb);
+                    }
+                }
             },
             Event::WindowEvent {
                 event: WindowEvent::Resized(size),
@@ -154,21 +703,105 @@ pub fn run(
                 };
                 renderer.handle_resize(size);
             }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::ScaleFactorChanged {
+                        ref new_inner_size, ..
+                    },
+                ..
+            } => {
+                // The window might have been moved to a monitor with a
+                // different scale factor. The new size is in physical pixels,
+                // just like the one of a regular resize.
+                let size = Size {
+                    width: new_inner_size.width,
+                    height: new_inner_size.height,
+                };
+                renderer.handle_resize(size);
+            }
+            Event::WindowEvent {
+                event: WindowEvent::ModifiersChanged(state),
+                ..
+            } => {
+                modifiers = Modifiers {
+                    shift: state.shift(),
+                    ctrl: state.ctrl(),
+                    alt: state.alt(),
+                };
+            }
             Event::WindowEvent {
                 event: WindowEvent::MouseInput { state, button, .. },
                 ..
             } => {
                 match state {
-                    ElementState::Pressed => held_mouse_button = Some(button),
-                    ElementState::Released => held_mouse_button = None,
+                    ElementState::Pressed => {
+                        held_mouse_button = Some(button);
+                        drag_action = bound_button(button).and_then(|button| {
+                            bindings.action(button, modifiers)
+                        });
+                        click_cursor = previous_cursor;
+                    }
+                    ElementState::Released => {
+                        held_mouse_button = None;
+                        drag_action = None;
+
+                        let clicked =
+                            click(click_cursor.take(), previous_cursor);
+                        if let (
+                            MouseButton::Left,
+                            Some(cursor),
+                            Some(shape),
+                            Some(camera),
+                        ) = (button, clicked, &tab.shape, &tab.camera)
+                        {
+                            if tab.measure_tool.is_active() {
+                                tab.measure_tool.pick(
+                                    cursor,
+                                    camera,
+                                    &shape.features,
+                                );
+                                update_measurement(
+                                    &mut renderer,
+                                    &tab.measure_tool,
+                                    Some(shape),
+                                );
+                            } else {
+                                tab.selection.hover(
+                                    Some(cursor),
+                                    camera,
+                                    &shape.features,
+                                );
+                                tab.selection.select();
+                                update_selection(
+                                    &mut renderer,
+                                    &tab.selection,
+                                    Some(shape),
+                                );
+                            }
+                        }
+                    }
                 };
             }
+            Event::LoopDestroyed => {
+                // The camera is restored for the first model on startup, but
+                // the one the user looked at last is the one to remember.
+                session.window = Some(window.state());
+                session.camera = tab.camera.as_ref().map(Camera::state);
+
+                if let Err(err) = session.save() {
+                    print_error("Error saving session", &err);
+                }
+            }
             Event::MainEventsCleared => {
                 window.window().request_redraw();
             }
             Event::RedrawRequested(_) => {
-                if let (Some(shape), Some(camera)) = (&shape, &mut camera) {
-                    camera.update_planes(&shape.aabb);
+                // There might not be a shape yet, if the model failed to load.
+                // The window is still drawn then, to display the error.
+                if let Some(camera) = &mut tab.camera {
+                    if let Some(shape) = &tab.shape {
+                        camera.update_planes(&shape.aabb);
+                    }
 
                     if let Err(err) =
                         renderer.draw(camera, &mut draw_config, window.window())
@@ -184,7 +817,7 @@ pub fn run(
         // These can fire multiple times per frame
 
         if let (Some(shape), Some(camera), Some(should_focus)) =
-            (&shape, &camera, focus_event(&event))
+            (&tab.shape, &tab.camera, focus_event(&event, drag_action))
         {
             if should_focus {
                 // Don't unnecessarily recalculate focus point
@@ -200,21 +833,56 @@ pub fn run(
         let input_event = input_event(
             &event,
             &window,
-            &held_mouse_button,
+            drag_action,
+            &bindings,
             &mut previous_cursor,
         );
         if let (Some(input_event), Some(fp), Some(camera)) =
-            (input_event, focus_point, &mut camera)
+            (input_event, focus_point, &mut tab.camera)
         {
             input_handler.handle_event(input_event, fp, camera);
         }
+
+        // Highlight the face or edge under the cursor. Not while dragging,
+        // as the model moves under the cursor then anyway.
+        let hover_cursor = match event {
+            Event::WindowEvent {
+                event: WindowEvent::CursorMoved { .. },
+                ..
+            } if held_mouse_button.is_none() => Some(previous_cursor),
+            Event::WindowEvent {
+                event: WindowEvent::CursorLeft { .. },
+                ..
+            } => Some(None),
+            _ => None,
+        };
+        if let (Some(cursor), Some(shape), Some(camera)) =
+            (hover_cursor, &tab.shape, &tab.camera)
+        {
+            if tab.selection.hover(cursor, camera, &shape.features) {
+                update_selection(&mut renderer, &tab.selection, Some(shape));
+            }
+        }
+
+        if let Some(index) = selected_tab {
+            if index != active_tab {
+                active_tab = index;
+                focus_point = None;
+
+                // Body indices refer to a specific model.
+                draw_config.transparent_bodies.clear();
+
+                show_tab(&mut renderer, &tabs, active_tab);
+            }
+        }
     });
 }
 
 fn input_event(
     event: &Event<()>,
     window: &Window,
-    held_mouse_button: &Option<MouseButton>,
+    drag_action: Option<Action>,
+    bindings: &Bindings,
     previous_cursor: &mut Option<NormalizedPosition>,
 ) -> Option<input::Event> {
     match event {
@@ -231,20 +899,30 @@ fn input_event(
                 x: position.x / width * 2. - 1.,
                 y: -(position.y / height * 2. - 1.) / aspect_ratio,
             };
-            let event = match (*previous_cursor, held_mouse_button) {
-                (Some(previous), Some(button)) => match button {
-                    MouseButton::Left => {
+            let event = match (*previous_cursor, drag_action) {
+                (Some(previous), Some(action)) => match action {
+                    Action::Rotate => {
+                        let sensitivity = ROTATION_SENSITIVITY
+                            * bindings.rotation_sensitivity;
+
                         let diff_x = current.x - previous.x;
                         let diff_y = current.y - previous.y;
-                        let angle_x = -diff_y * ROTATION_SENSITIVITY;
-                        let angle_y = diff_x * ROTATION_SENSITIVITY;
+                        let angle_x = -diff_y * sensitivity;
+                        let angle_y = diff_x * sensitivity;
 
                         Some(input::Event::Rotation { angle_x, angle_y })
                     }
-                    MouseButton::Right => {
+                    Action::Pan => {
                         Some(input::Event::Translate { previous, current })
                     }
-                    _ => None,
+                    Action::Zoom => {
+                        let diff_y = current.y - previous.y;
+                        Some(input::Event::Zoom(
+                            diff_y
+                                * ZOOM_FACTOR_DRAG
+                                * bindings.zoom_sensitivity,
+                        ))
+                    }
                 },
                 _ => None,
             };
@@ -254,30 +932,40 @@ fn input_event(
         Event::WindowEvent {
             event: WindowEvent::MouseWheel { delta, .. },
             ..
-        } => Some(input::Event::Zoom(match delta {
-            MouseScrollDelta::LineDelta(_, y) => (*y as f64) * ZOOM_FACTOR_LINE,
-            MouseScrollDelta::PixelDelta(PhysicalPosition { y, .. }) => {
-                y * ZOOM_FACTOR_PIXEL
-            }
-        })),
+        } => {
+            let zoom = match delta {
+                MouseScrollDelta::LineDelta(_, y) => {
+                    (*y as f64) * ZOOM_FACTOR_LINE
+                }
+                MouseScrollDelta::PixelDelta(PhysicalPosition {
+                    y, ..
+                }) => {
+                    // Physical pixels, which depend on the scale factor. The
+                    // zoom should feel the same on any display.
+                    y / window.window().scale_factor() * ZOOM_FACTOR_PIXEL
+                }
+            };
+            let direction = if bindings.invert_scroll { -1. } else { 1. };
+
+            Some(input::Event::Zoom(
+                zoom * direction * bindings.zoom_sensitivity,
+            ))
+        }
         _ => None,
     }
 }
 
 /// Returns true/false if focus point point should be created/removed
 /// None means no change to focus point is needed
-fn focus_event(event: &Event<()>) -> Option<bool> {
+///
+/// `drag_action` is what dragging with the held mouse button does.
+fn focus_event(event: &Event<()>, drag_action: Option<Action>) -> Option<bool> {
     match event {
         Event::WindowEvent {
-            event:
-                WindowEvent::MouseInput {
-                    state,
-                    button: MouseButton::Left | MouseButton::Right,
-                    ..
-                },
+            event: WindowEvent::MouseInput { state, .. },
             ..
         } => match state {
-            ElementState::Pressed => Some(true),
+            ElementState::Pressed => drag_action.map(|_| true),
             ElementState::Released => Some(false),
         },
         Event::WindowEvent {
@@ -288,9 +976,224 @@ fn focus_event(event: &Event<()>) -> Option<bool> {
     }
 }
 
+/// Converts a mouse button into one that can be bound to an action
+fn bound_button(button: MouseButton) -> Option<input::MouseButton> {
+    match button {
+        MouseButton::Left => Some(input::MouseButton::Left),
+        MouseButton::Right => Some(input::MouseButton::Right),
+        MouseButton::Middle => Some(input::MouseButton::Middle),
+        MouseButton::Other(_) => None,
+    }
+}
+
+/// Returns the cursor position of a click, if the cursor hasn't been dragged
+///
+/// Takes the cursor positions when the mouse button was pressed and when it
+/// was released.
+fn click(
+    pressed: Option<NormalizedPosition>,
+    released: Option<NormalizedPosition>,
+) -> Option<NormalizedPosition> {
+    let (pressed, released) = (pressed?, released?);
+
+    let distance = ((released.x - pressed.x).powi(2)
+        + (released.y - pressed.y).powi(2))
+    .sqrt();
+    if distance > CLICK_TOLERANCE {
+        return None;
+    }
+
+    Some(released)
+}
+
+/// Update the measurement that is displayed
+///
+/// Computing a measurement can be expensive, so this should only be called,
+/// when the measurement or the shape has changed.
+fn update_measurement(
+    renderer: &mut Renderer,
+    measure_tool: &MeasureTool,
+    shape: Option<&ProcessedShape>,
+) {
+    renderer.set_measurement(
+        shape.and_then(|shape| measure_tool.overlay(&shape.features)),
+    );
+}
+
+/// Update the selection that is highlighted
+///
+/// Should only be called, when the selection or the shape has changed, to
+/// avoid needlessly copying the geometry of the selected features.
+fn update_selection(
+    renderer: &mut Renderer,
+    selection: &Selection,
+    shape: Option<&ProcessedShape>,
+) {
+    renderer.set_selection(
+        shape
+            .map(|shape| selection.overlay(&shape.features))
+            .unwrap_or_default(),
+    );
+}
+
+/// The standard view that a key switches to, if any
+fn standard_view(key: VirtualKeyCode) -> Option<StandardView> {
+    match key {
+        VirtualKeyCode::F => Some(StandardView::Front),
+        VirtualKeyCode::T => Some(StandardView::Top),
+        VirtualKeyCode::R => Some(StandardView::Right),
+        VirtualKeyCode::I => Some(StandardView::Isometric),
+        _ => None,
+    }
+}
+
+/// Save the model, as currently drawn, to a PNG file in the current directory
+fn save_screenshot(
+    renderer: &Renderer,
+    camera: &Camera,
+    draw_config: &DrawConfig,
+) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let path = format!("screenshot-{}.png", timestamp);
+
+    let result = block_on(renderer.capture(camera, draw_config))
+        .and_then(|image| image.save_png(&path));
+    match result {
+        Ok(()) => println!("Saved screenshot to `{}`", path),
+        Err(err) => print_error("Error saving screenshot", &err),
+    }
+}
+
+fn print_error(context: &str, err: &dyn error::Error) {
+    println!("{}", error_chain(context, err));
+}
+
+fn error_chain(context: &str, err: &dyn error::Error) -> String {
+    // Can be cleaned up, once `Report` is stable:
+    // https://doc.rust-lang.org/std/error/struct.Report.html
+
+    let mut text = format!("{}: {}", context, err);
+
+    let mut current_err = err;
+    while let Some(err) = current_err.source() {
+        text.push_str(&format!("\n\nCaused by:\n    {}", err));
+        current_err = err;
+    }
+
+    text
+}
+
+/// Display a tab, instead of the one that was displayed before
+///
+/// Replaces everything the renderer displays about the model with the state of
+/// the tab.
+fn show_tab(renderer: &mut Renderer, tabs: &[Tab], active: usize) {
+    renderer
+        .set_tabs(tabs.iter().map(|tab| tab.name.clone()).collect(), active);
+
+    let tab = &tabs[active];
+
+    renderer.set_model_error(tab.error.clone());
+    renderer.set_status(tab.status.clone());
+    renderer.set_parameters(parameters(&tab.watcher));
+
+    match &tab.shape {
+        Some(shape) => renderer.update_geometry(
+            &shape.mesh,
+            &shape.edges,
+            &shape.debug_info,
+            shape.aabb,
+        ),
+        None => renderer.update_geometry(
+            &Mesh::new(),
+            &[],
+            &DebugInfo::new(),
+            empty_aabb(),
+        ),
+    }
+
+    update_measurement(renderer, &tab.measure_tool, tab.shape.as_ref());
+    update_selection(renderer, &tab.selection, tab.shape.as_ref());
+}
+
+/// Describe how long regenerating a model took
+///
+/// Takes the timings of the host, if available, and how long processing the
+/// shape took.
+fn status(timings: Option<Timings>, triangulation: Duration) -> String {
+    let seconds =
+        |duration: Duration| format!("{:.1} s", duration.as_secs_f64());
+
+    let mut total = triangulation;
+    let mut steps = Vec::new();
+    if let Some(timings) = timings {
+        if let Some(compile) = timings.compile {
+            total += compile;
+            steps.push(format!("compiled in {}", seconds(compile)));
+        }
+        total += timings.evaluate;
+        steps.push(format!("evaluated in {}", seconds(timings.evaluate)));
+    }
+    steps.push(format!("triangulated in {}", seconds(triangulation)));
+
+    format!("Regenerated in {}\n({})", seconds(total), steps.join(", "))
+}
+
+/// The parameters of a model, for the user to edit
+fn parameters(watcher: &Watcher) -> Vec<Parameter> {
+    watcher
+        .parameter_metadata()
+        .into_iter()
+        .map(|parameter| {
+            Parameter::new(
+                parameter.name(),
+                parameter.ty(),
+                parameter.value(),
+                parameter.minimum(),
+                parameter.maximum(),
+            )
+        })
+        .collect()
+}
+
+/// Display an error that occurred while loading the model of a tab
+///
+/// If there's no camera yet, because no shape has been loaded so far, this
+/// creates one. Without a camera, nothing would be drawn.
+fn show_model_error(
+    renderer: &mut Renderer,
+    tab: &mut Tab,
+    is_active: bool,
+    error: String,
+) {
+    if is_active {
+        renderer.set_model_error(Some(error.clone()));
+    }
+    tab.error = Some(error);
+
+    if tab.camera.is_none() {
+        tab.camera = Some(Camera::new(&empty_aabb()));
+    }
+}
+
+/// The bounding volume of a model that hasn't been loaded yet
+fn empty_aabb() -> Aabb<3> {
+    Aabb {
+        min: Point::origin(),
+        max: Point::origin(),
+    }
+}
+
 /// Error in main loop
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    /// No model was passed to display
+    #[error("No model to display")]
+    NoModel,
+
     /// Error initializing window
     #[error("Error initializing window")]
     WindowInit(#[from] window::Error),
@@ -312,8 +1215,21 @@ const ZOOM_FACTOR_LINE: f64 = 0.075;
 /// Larger values will move the camera more with the same input.
 const ZOOM_FACTOR_PIXEL: f64 = 0.005;
 
+/// Affects the speed of zoom movement given a change in normalized screen
+/// position [-1, 1], when dragging to zoom
+///
+/// Smaller values will move the camera less with the same input.
+/// Larger values will move the camera more with the same input.
+const ZOOM_FACTOR_DRAG: f64 = 1.;
+
 /// Affects the speed of rotation given a change in normalized screen position [-1, 1]
 ///
 /// Smaller values will move the camera less with the same input.
 /// Larger values will move the camera more with the same input.
 const ROTATION_SENSITIVITY: f64 = 5.;
+
+/// How far the cursor can move between pressing and releasing a mouse button,
+/// for that to still count as a click
+///
+/// Given as a distance in normalized screen position [-1, 1].
+const CLICK_TOLERANCE: f64 = 0.01;
@@ -0,0 +1,114 @@
+//! Persistence of the window and camera between runs
+//!
+//! The state of the previous session is stored in `session.toml`, in the
+//! Fornjot configuration directory of the current user.
+
+use std::{env, fs, io, path::PathBuf};
+
+use fj_viewer::camera::CameraState;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// The state of the application that is restored on startup
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Session {
+    /// The state of the window
+    pub window: Option<WindowState>,
+
+    /// The state of the camera
+    pub camera: Option<CameraState>,
+}
+
+impl Session {
+    const FILE_NAME: &'static str = "session.toml";
+
+    /// Load the session that was saved by a previous run
+    ///
+    /// Returns an empty session, if there is none, or it can't be loaded.
+    /// Losing the session is just an inconvenience, so this doesn't fail.
+    pub fn load() -> Self {
+        let path = match config_dir() {
+            Some(dir) => dir.join(Self::FILE_NAME),
+            None => return Self::default(),
+        };
+
+        let session = match fs::read_to_string(&path) {
+            Ok(session) => session,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Self::default();
+            }
+            Err(err) => {
+                warn!("Error reading session from `{}`: {err}", path.display());
+                return Self::default();
+            }
+        };
+
+        toml::from_str(&session).unwrap_or_else(|err| {
+            warn!("Error parsing session from `{}`: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    /// Save the session, so it can be restored by the next run
+    pub fn save(&self) -> Result<(), Error> {
+        let dir = config_dir().ok_or(Error::NoConfigDir)?;
+        let session = toml::to_string(self)?;
+
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(Self::FILE_NAME), session)?;
+
+        Ok(())
+    }
+}
+
+/// The state of the window
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct WindowState {
+    /// The position of the window's top-left corner, in physical pixels
+    ///
+    /// Not available on all platforms.
+    pub position: Option<[i32; 2]>,
+
+    /// The size of the window's client area, in logical pixels
+    ///
+    /// Logical pixels are independent of the scale factor, so the window keeps
+    /// its size, if it is moved to a monitor with a different one.
+    pub size: [f64; 2],
+
+    /// Whether the window is maximized
+    pub maximized: bool,
+}
+
+/// The directory that Fornjot stores its per-user configuration in
+fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?)
+            .join("Library")
+            .join("Application Support")
+    } else {
+        match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        }
+    };
+
+    Some(base.join("fornjot"))
+}
+
+/// Error saving the session
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// No configuration directory could be determined
+    #[error("Could not determine configuration directory")]
+    NoConfigDir,
+
+    /// I/O error while writing the session
+    #[error("I/O error while writing session")]
+    Io(#[from] io::Error),
+
+    /// Error serializing the session
+    #[error("Error serializing session")]
+    Serialize(#[from] toml::ser::Error),
+}
@@ -1,23 +1,69 @@
 //! CAD viewer utility windowing abstraction
 
 use fj_viewer::screen::{Screen, Size};
-use winit::{event_loop::EventLoop, window::WindowBuilder};
+use winit::{
+    dpi::{LogicalSize, PhysicalPosition},
+    event_loop::EventLoop,
+    window::WindowBuilder,
+};
+
+use crate::session::WindowState;
 
 /// Window abstraction providing details such as the width or height and easing initialization.
 pub struct Window(winit::window::Window);
 
 impl Window {
     /// Returns a new window with the given `EventLoop`.
-    pub fn new(event_loop: &EventLoop<()>) -> Result<Self, Error> {
-        let window = WindowBuilder::new()
+    ///
+    /// Restores the window of a previous session, if its `state` is given.
+    /// Otherwise the window is maximized.
+    pub fn new(
+        event_loop: &EventLoop<()>,
+        state: Option<&WindowState>,
+    ) -> Result<Self, Error> {
+        let mut builder = WindowBuilder::new()
             .with_title("Fornjot")
-            .with_maximized(true)
             .with_decorations(true)
-            .with_transparent(false)
-            .build(event_loop)?;
+            .with_transparent(false);
+
+        builder = match state {
+            Some(state) => {
+                let [width, height] = state.size;
+                builder = builder
+                    .with_inner_size(LogicalSize::new(width, height))
+                    .with_maximized(state.maximized);
+
+                match state.position {
+                    Some([x, y]) => {
+                        builder.with_position(PhysicalPosition::new(x, y))
+                    }
+                    None => builder,
+                }
+            }
+            None => builder.with_maximized(true),
+        };
+
+        let window = builder.build(event_loop)?;
 
         Ok(Self(window))
     }
+
+    /// Access the state of the window, to restore it in the next session
+    pub fn state(&self) -> WindowState {
+        let size: LogicalSize<f64> =
+            self.0.inner_size().to_logical(self.0.scale_factor());
+        let position = self
+            .0
+            .outer_position()
+            .ok()
+            .map(|position| [position.x, position.y]);
+
+        WindowState {
+            position,
+            size: [size.width, size.height],
+            maximized: self.0.is_maximized(),
+        }
+    }
 }
 
 impl Screen for Window {
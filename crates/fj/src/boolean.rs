@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ffi_option::FfiOption, Shape};
+
+/// A boolean operation on two 3-dimensional shapes
+///
+/// Nodes of this kind can be constructed conveniently using the methods of
+/// [`syntax::Boolean`].
+///
+/// [`syntax::Boolean`]: crate::syntax::Boolean
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Boolean {
+    /// The first shape
+    pub a: Shape,
+
+    /// The second shape
+    pub b: Shape,
+
+    /// The operation that is applied to the shapes
+    pub operation: BooleanOperation,
+
+    /// The backend that computes the operation
+    pub backend: BooleanBackend,
+
+    /// The color that the operation passes on to both shapes
+    ///
+    /// Applies to all parts of the shapes that don't have a color of their own.
+    pub(crate) color: FfiOption<[u8; 4]>,
+
+    /// The tolerance that the result is approximated with
+    ///
+    /// Only has an effect, if it's tighter than the tolerance that applies
+    /// otherwise.
+    pub(crate) tolerance: FfiOption<f64>,
+}
+
+impl Boolean {
+    /// Select the backend that computes the operation
+    pub fn with_backend(mut self, backend: BooleanBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Set the rendering color of the result in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = FfiOption::Some(color);
+        self
+    }
+
+    /// Access the color that the operation passes on, if it has one
+    pub fn color(&self) -> Option<[u8; 4]> {
+        self.color.get()
+    }
+
+    /// Approximate the result with a tighter tolerance
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = FfiOption::Some(tolerance);
+        self
+    }
+
+    /// Access the tolerance of the operation, if it has one
+    pub fn tolerance(&self) -> Option<f64> {
+        self.tolerance.get()
+    }
+}
+
+impl From<Boolean> for Shape {
+    fn from(shape: Boolean) -> Self {
+        Self::Boolean(Box::new(shape))
+    }
+}
+
+/// A boolean operation
+///
+/// See [`Boolean`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum BooleanOperation {
+    /// Everything that is in either shape
+    Union,
+
+    /// Everything that is in both shapes
+    Intersection,
+
+    /// Everything that is in the first shape, but not in the second one
+    Difference,
+}
+
+/// The backend that computes a [`Boolean`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum BooleanBackend {
+    /// Compute an exact result, where possible
+    ///
+    /// Falls back to [`BooleanBackend::Mesh`], where an exact result can't be
+    /// computed. This is the default.
+    #[default]
+    Exact,
+
+    /// Compute the result from triangle meshes of the shapes
+    ///
+    /// Always produces a result, but that result is only as accurate as the
+    /// tolerance that the shapes are approximated with.
+    Mesh,
+}
@@ -0,0 +1,2 @@
+/// The color of shapes that neither have a color, nor inherit one, in RGBA
+pub const DEFAULT_COLOR: [u8; 4] = [255, 0, 0, 255];
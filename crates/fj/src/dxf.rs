@@ -0,0 +1,596 @@
+use std::{
+    error,
+    f64::consts::{PI, TAU},
+    fmt,
+};
+
+use crate::{Circle, Sketch};
+
+impl Sketch {
+    /// Create a sketch from the 2D profile in a DXF file
+    ///
+    /// Reads the `LINE`, `ARC`, `CIRCLE`, `LWPOLYLINE`, and `POLYLINE`
+    /// entities from the DXF file, and chains them into a single closed
+    /// profile. All other entities, like text or dimensions, are ignored, as
+    /// are the z-coordinates of all points.
+    ///
+    /// Arcs are approximated by straight lines that deviate no further than
+    /// `tolerance` from the arc. Endpoints of entities that are no further than
+    /// `tolerance` apart are considered to be connected.
+    ///
+    /// `tolerance` must be positive.
+    ///
+    /// The DXF file can be embedded in a model using `include_str!`.
+    pub fn from_dxf(dxf: &str, tolerance: f64) -> Result<Self, DxfError> {
+        if tolerance.is_nan() || tolerance <= 0. {
+            return Err(DxfError::InvalidTolerance(tolerance));
+        }
+
+        let entities = parse(dxf)?;
+
+        // A lone circle around the origin can be represented exactly.
+        if let [Entity::Circle { center, radius }] = entities.as_slice() {
+            if *center == [0., 0.] {
+                return Ok(Self::from_circle(Circle::from_radius(*radius)));
+            }
+        }
+
+        let mut loops = Vec::new();
+        let mut open = Vec::new();
+
+        for entity in entities {
+            match entity.approximate(tolerance) {
+                // Polylines without vertices don't contribute to the profile.
+                Path::Closed(points) | Path::Open(points)
+                    if points.is_empty() => {}
+                Path::Closed(points) => loops.push(points),
+                Path::Open(points) => open.push(points),
+            }
+        }
+
+        loops.extend(chain(open, tolerance)?);
+
+        match loops.len() {
+            0 => Err(DxfError::NoProfile),
+            1 => {
+                let mut points = loops.remove(0);
+
+                // Make sure the profile is oriented counter-clockwise.
+                if signed_area(&points) < 0. {
+                    points.reverse();
+                }
+
+                Ok(Self::from_points(points))
+            }
+            n => Err(DxfError::MultipleProfiles(n)),
+        }
+    }
+}
+
+/// An error that can occur while reading a DXF file
+#[derive(Clone, Debug, PartialEq)]
+pub enum DxfError {
+    /// The DXF file is malformed at the given line
+    Syntax {
+        /// The line number, starting at 1
+        line: usize,
+    },
+
+    /// The DXF file doesn't contain any supported entities
+    NoProfile,
+
+    /// The entities in the DXF file don't form a closed profile
+    OpenProfile,
+
+    /// The DXF file contains more than one closed profile
+    MultipleProfiles(usize),
+
+    /// The tolerance is not a positive number
+    InvalidTolerance(f64),
+}
+
+impl fmt::Display for DxfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syntax { line } => {
+                write!(f, "malformed DXF file at line {line}")
+            }
+            Self::NoProfile => write!(f, "DXF file contains no profile"),
+            Self::OpenProfile => {
+                write!(f, "DXF file contains a profile that is not closed")
+            }
+            Self::MultipleProfiles(n) => {
+                write!(f, "DXF file contains {n} profiles, expected 1")
+            }
+            Self::InvalidTolerance(tolerance) => {
+                write!(f, "tolerance must be positive, got {tolerance}")
+            }
+        }
+    }
+}
+
+impl error::Error for DxfError {}
+
+#[derive(Debug, PartialEq)]
+enum Entity {
+    Line {
+        start: [f64; 2],
+        end: [f64; 2],
+    },
+    Arc {
+        center: [f64; 2],
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    },
+    Circle {
+        center: [f64; 2],
+        radius: f64,
+    },
+    Polyline {
+        // Each vertex with the bulge of the segment that starts there
+        vertices: Vec<([f64; 2], f64)>,
+        closed: bool,
+    },
+}
+
+impl Entity {
+    fn approximate(&self, tolerance: f64) -> Path {
+        match *self {
+            Self::Line { start, end } => Path::Open(vec![start, end]),
+            Self::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                // Arcs in DXF files always go counter-clockwise.
+                let start_angle = start_angle.to_radians();
+                let mut sweep = (end_angle.to_radians() - start_angle) % TAU;
+                if sweep <= 0. {
+                    sweep += TAU;
+                }
+
+                Path::Open(arc(center, radius, start_angle, sweep, tolerance))
+            }
+            Self::Circle { center, radius } => {
+                let mut points = arc(center, radius, 0., TAU, tolerance);
+                points.pop();
+                Path::Closed(points)
+            }
+            Self::Polyline {
+                ref vertices,
+                closed,
+            } => {
+                let mut points = Vec::new();
+
+                let num_segments = if closed {
+                    vertices.len()
+                } else {
+                    vertices.len().saturating_sub(1)
+                };
+                for i in 0..num_segments {
+                    let (start, bulge) = vertices[i];
+                    let (end, _) = vertices[(i + 1) % vertices.len()];
+
+                    let segment = bulge_arc(start, end, bulge, tolerance);
+                    points.extend_from_slice(&segment[..segment.len() - 1]);
+                }
+
+                if closed {
+                    Path::Closed(points)
+                } else {
+                    if let Some(&(last, _)) = vertices.last() {
+                        points.push(last);
+                    }
+                    Path::Open(points)
+                }
+            }
+        }
+    }
+}
+
+enum Path {
+    Open(Vec<[f64; 2]>),
+    Closed(Vec<[f64; 2]>),
+}
+
+fn parse(dxf: &str) -> Result<Vec<Entity>, DxfError> {
+    let mut lines = dxf.lines().enumerate();
+    let mut pairs = Vec::new();
+
+    while let Some((i, code)) = lines.next() {
+        if code.trim().is_empty() && lines.clone().next().is_none() {
+            break;
+        }
+
+        let code = code
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| DxfError::Syntax { line: i + 1 })?;
+        let (j, value) =
+            lines.next().ok_or(DxfError::Syntax { line: i + 2 })?;
+
+        pairs.push((code, value.trim(), j + 1));
+    }
+
+    let mut entities = Vec::new();
+    let mut in_entities = false;
+
+    // Entities start with a pair with group code 0. Split the pairs into
+    // groups, each of which starts with such a pair.
+    let mut groups = Vec::new();
+    for (i, &(code, _, _)) in pairs.iter().enumerate() {
+        if code == 0 {
+            groups.push(i);
+        }
+    }
+    groups.push(pairs.len());
+
+    // `POLYLINE` entities are followed by a number of `VERTEX` entities, and
+    // finally a `SEQEND` entity.
+    let mut polyline = None;
+
+    for range in groups.windows(2) {
+        let (_, kind, _) = pairs[range[0]];
+        let fields = Fields(&pairs[range[0] + 1..range[1]]);
+
+        match kind {
+            "SECTION" => {
+                in_entities = fields.text(2) == Some("ENTITIES");
+                continue;
+            }
+            "ENDSEC" => {
+                in_entities = false;
+                continue;
+            }
+            _ => {}
+        }
+        if !in_entities {
+            continue;
+        }
+
+        match kind {
+            "LINE" => entities.push(Entity::Line {
+                start: fields.point(10)?,
+                end: fields.point(11)?,
+            }),
+            "ARC" => entities.push(Entity::Arc {
+                center: fields.point(10)?,
+                radius: fields.number(40)?,
+                start_angle: fields.number(50)?,
+                end_angle: fields.number(51)?,
+            }),
+            "CIRCLE" => entities.push(Entity::Circle {
+                center: fields.point(10)?,
+                radius: fields.number(40)?,
+            }),
+            "LWPOLYLINE" => {
+                let mut vertices = Vec::new();
+
+                // The vertices are stored as a sequence of x, y, and
+                // optional bulge values.
+                for &(code, value, line) in fields.0 {
+                    match code {
+                        10 => vertices
+                            .push(([parse_number(value, line)?, 0.], 0.)),
+                        20 => {
+                            let (vertex, _) = vertices
+                                .last_mut()
+                                .ok_or(DxfError::Syntax { line })?;
+                            vertex[1] = parse_number(value, line)?;
+                        }
+                        42 => {
+                            let (_, bulge) = vertices
+                                .last_mut()
+                                .ok_or(DxfError::Syntax { line })?;
+                            *bulge = parse_number(value, line)?;
+                        }
+                        _ => {}
+                    }
+                }
+
+                entities.push(Entity::Polyline {
+                    vertices,
+                    closed: fields.flags(70)? & 1 != 0,
+                });
+            }
+            "POLYLINE" => {
+                polyline = Some(Entity::Polyline {
+                    vertices: Vec::new(),
+                    closed: fields.flags(70)? & 1 != 0,
+                });
+            }
+            "VERTEX" => {
+                if let Some(Entity::Polyline { vertices, .. }) = &mut polyline {
+                    let bulge = fields.optional_number(42)?.unwrap_or(0.);
+                    vertices.push((fields.point(10)?, bulge));
+                }
+            }
+            "SEQEND" => {
+                if let Some(polyline) = polyline.take() {
+                    entities.push(polyline);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(entities)
+}
+
+/// The fields of an entity, as pairs of group code, value, and line number
+struct Fields<'r>(&'r [(u32, &'r str, usize)]);
+
+impl Fields<'_> {
+    fn text(&self, code: u32) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|&&(c, _, _)| c == code)
+            .map(|&(_, value, _)| value)
+    }
+
+    fn optional_number(&self, code: u32) -> Result<Option<f64>, DxfError> {
+        match self.0.iter().find(|&&(c, _, _)| c == code) {
+            Some(&(_, value, line)) => parse_number(value, line).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn number(&self, code: u32) -> Result<f64, DxfError> {
+        self.optional_number(code)?.ok_or_else(|| self.missing())
+    }
+
+    fn flags(&self, code: u32) -> Result<u32, DxfError> {
+        Ok(self.optional_number(code)?.unwrap_or(0.) as u32)
+    }
+
+    /// Read a point, from the x- and y-coordinates at `code` and `code + 10`
+    fn point(&self, code: u32) -> Result<[f64; 2], DxfError> {
+        Ok([self.number(code)?, self.number(code + 10)?])
+    }
+
+    fn missing(&self) -> DxfError {
+        let line = self.0.first().map(|&(_, _, line)| line).unwrap_or(0);
+        DxfError::Syntax { line }
+    }
+}
+
+fn parse_number(value: &str, line: usize) -> Result<f64, DxfError> {
+    value.parse().map_err(|_| DxfError::Syntax { line })
+}
+
+/// Approximate a counter-clockwise arc, including its start and end points
+fn arc(
+    center: [f64; 2],
+    radius: f64,
+    start_angle: f64,
+    sweep: f64,
+    tolerance: f64,
+) -> Vec<[f64; 2]> {
+    // The maximum angle of a segment, for which the segment doesn't deviate
+    // from the arc by more than the tolerance.
+    let max_angle = if tolerance < radius {
+        2. * (1. - tolerance / radius).acos()
+    } else {
+        PI
+    };
+    let num_segments = (sweep.abs() / max_angle).ceil().max(1.) as usize;
+
+    (0..=num_segments)
+        .map(|i| {
+            let angle = start_angle + sweep * i as f64 / num_segments as f64;
+            [
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]
+        })
+        .collect()
+}
+
+/// Approximate a polyline segment, including its start and end points
+///
+/// The bulge is the tangent of a quarter of the arc's angle. It's positive for
+/// counter-clockwise arcs, negative for clockwise ones, and zero for straight
+/// lines.
+fn bulge_arc(
+    start: [f64; 2],
+    end: [f64; 2],
+    bulge: f64,
+    tolerance: f64,
+) -> Vec<[f64; 2]> {
+    let chord = [end[0] - start[0], end[1] - start[1]];
+    let length = chord[0].hypot(chord[1]);
+
+    if bulge == 0. || length == 0. {
+        return vec![start, end];
+    }
+
+    let sweep = 4. * bulge.atan();
+    let radius = length / (2. * (sweep / 2.).sin()).abs();
+
+    // The center is on the perpendicular bisector of the chord. For positive
+    // bulges, it's on the left of the chord.
+    let distance = length / (2. * (sweep / 2.).tan());
+    let normal = [-chord[1] / length, chord[0] / length];
+    let center = [
+        (start[0] + end[0]) / 2. + normal[0] * distance,
+        (start[1] + end[1]) / 2. + normal[1] * distance,
+    ];
+
+    let start_angle = (start[1] - center[1]).atan2(start[0] - center[0]);
+
+    let mut points = arc(center, radius, start_angle, sweep, tolerance);
+
+    // Avoid rounding errors at the connection to neighboring segments.
+    let last = points.len() - 1;
+    points[0] = start;
+    points[last] = end;
+
+    points
+}
+
+/// Chain open paths into closed loops
+fn chain(
+    mut paths: Vec<Vec<[f64; 2]>>,
+    tolerance: f64,
+) -> Result<Vec<Vec<[f64; 2]>>, DxfError> {
+    let is_close = |a: [f64; 2], b: [f64; 2]| {
+        (a[0] - b[0]).hypot(a[1] - b[1]) <= tolerance
+    };
+
+    let mut loops = Vec::new();
+
+    while let Some(mut points) = paths.pop() {
+        loop {
+            let start = points[0];
+            let end = points[points.len() - 1];
+
+            if points.len() > 2 && is_close(start, end) {
+                points.pop();
+                loops.push(points);
+                break;
+            }
+
+            let next = paths.iter().position(|path| {
+                is_close(end, path[0]) || is_close(end, path[path.len() - 1])
+            });
+            let mut next = match next {
+                Some(i) => paths.swap_remove(i),
+                None => return Err(DxfError::OpenProfile),
+            };
+
+            if !is_close(end, next[0]) {
+                next.reverse();
+            }
+            points.extend_from_slice(&next[1..]);
+        }
+    }
+
+    Ok(loops)
+}
+
+fn signed_area(points: &[[f64; 2]]) -> f64 {
+    let mut double_area = 0.;
+
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        double_area += a[0] * b[1] - b[0] * a[1];
+    }
+
+    double_area / 2.
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Chain, Sketch};
+
+    use super::DxfError;
+
+    fn dxf(entities: &str) -> String {
+        format!("0\nSECTION\n2\nENTITIES\n{entities}0\nENDSEC\n0\nEOF\n")
+    }
+
+    fn line(start: [f64; 2], end: [f64; 2]) -> String {
+        format!(
+            "0\nLINE\n8\n0\n10\n{}\n20\n{}\n11\n{}\n21\n{}\n",
+            start[0], start[1], end[0], end[1]
+        )
+    }
+
+    fn points(sketch: &Sketch) -> Vec<[f64; 2]> {
+        match sketch.chain() {
+            Chain::PolyChain(poly_chain) => poly_chain.to_points(),
+            Chain::Circle(_) => panic!("Expected polygonal chain"),
+        }
+    }
+
+    #[test]
+    fn lines() {
+        // Lines in arbitrary order and direction, forming a clockwise square.
+        let dxf = dxf(&[
+            line([0., 0.], [0., 1.]),
+            line([1., 0.], [1., 1.]),
+            line([0., 1.], [1., 1.]),
+            line([1., 0.], [0., 0.]),
+        ]
+        .concat());
+
+        let sketch = Sketch::from_dxf(&dxf, 0.001).unwrap();
+        let points = points(&sketch);
+
+        assert_eq!(points.len(), 4);
+        assert!(super::signed_area(&points) > 0.);
+    }
+
+    #[test]
+    fn lwpolyline_with_bulge() {
+        // A closed slot: two straight segments and two semicircles.
+        let dxf = dxf("0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n\
+            10\n0\n20\n0\n\
+            10\n2\n20\n0\n42\n1\n\
+            10\n2\n20\n2\n\
+            10\n0\n20\n2\n42\n1\n");
+
+        let sketch = Sketch::from_dxf(&dxf, 0.01).unwrap();
+        let points = points(&sketch);
+
+        assert!(points.len() > 4);
+        for [x, _] in points {
+            assert!((-1. - 1e-9..=3. + 1e-9).contains(&x));
+        }
+    }
+
+    #[test]
+    fn circle() {
+        let dxf = dxf("0\nCIRCLE\n8\n0\n10\n0\n20\n0\n40\n2.5\n");
+
+        let sketch = Sketch::from_dxf(&dxf, 0.01).unwrap();
+        match sketch.chain() {
+            Chain::Circle(circle) => assert_eq!(circle.radius(), 2.5),
+            Chain::PolyChain(_) => panic!("Expected circle"),
+        }
+    }
+
+    #[test]
+    fn open_profile() {
+        let dxf =
+            dxf(&[line([0., 0.], [1., 0.]), line([1., 0.], [1., 1.])].concat());
+
+        assert_eq!(Sketch::from_dxf(&dxf, 0.001), Err(DxfError::OpenProfile));
+    }
+
+    #[test]
+    fn invalid_tolerance() {
+        let dxf = dxf("0\nCIRCLE\n8\n0\n10\n1\n20\n0\n40\n2.5\n");
+
+        for tolerance in [0., -0.1, f64::NAN] {
+            assert!(matches!(
+                Sketch::from_dxf(&dxf, tolerance),
+                Err(DxfError::InvalidTolerance(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn empty_polylines() {
+        let square = [
+            line([0., 0.], [1., 0.]),
+            line([1., 0.], [1., 1.]),
+            line([1., 1.], [0., 1.]),
+            line([0., 1.], [0., 0.]),
+        ]
+        .concat();
+        let empty = "0\nLWPOLYLINE\n8\n0\n90\n0\n70\n0\n\
+            0\nPOLYLINE\n8\n0\n70\n1\n0\nSEQEND\n";
+
+        let sketch =
+            Sketch::from_dxf(&dxf(&format!("{empty}{square}")), 0.001).unwrap();
+        assert_eq!(points(&sketch).len(), 4);
+
+        assert_eq!(
+            Sketch::from_dxf(&dxf(empty), 0.001),
+            Err(DxfError::NoProfile)
+        );
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An optional value that is FFI-safe
+///
+/// This is equivalent to `Option<T>`, which isn't FFI-safe for most `T`, and
+/// can't be stored in the types that are passed across the FFI boundary by
+/// value.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C, u8)]
+pub(crate) enum FfiOption<T> {
+    /// No value has been set
+    #[default]
+    None,
+
+    /// A value has been set
+    Some(T),
+}
+
+impl<T: Copy> FfiOption<T> {
+    /// Convert into an `Option`
+    pub(crate) fn get(self) -> Option<T> {
+        match self {
+            Self::None => None,
+            Self::Some(value) => Some(value),
+        }
+    }
+}
+
+impl<T> FfiOption<T> {
+    /// Convert into an `Option` that refers to the value
+    pub(crate) fn as_ref(&self) -> Option<&T> {
+        match self {
+            Self::None => None,
+            Self::Some(value) => Some(value),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for FfiOption<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            None => Self::None,
+            Some(value) => Self::Some(value),
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::{fmt, mem, ops::Deref, sync::atomic};
+
+#[cfg(feature = "serde")]
+use serde::{de, ser, Deserialize, Serialize};
+
+/// A list of values that is FFI-safe
+///
+/// This is equivalent to an immutable `Vec<T>`, which isn't FFI-safe, and
+/// can't be stored in the types that are passed across the FFI boundary by
+/// value. Like [`PolyChain`], it stores the raw parts of a `Vec` instead.
+///
+/// Besides being used within shapes, it carries the data that the host and a
+/// model exchange directly, like the model's state.
+///
+/// [`PolyChain`]: crate::PolyChain
+#[repr(C)]
+pub struct FfiVec<T> {
+    ptr: *mut T,
+    length: usize,
+    capacity: usize,
+
+    // Clones share the buffer behind `ptr`, so we need to track the number of
+    // live instances, so as to free it only when the last one is dropped.
+    rc: *mut atomic::AtomicUsize,
+}
+
+impl<T> FfiVec<T> {
+    /// Construct an instance from a `Vec`
+    pub fn from_vec(mut values: Vec<T>) -> Self {
+        // This can be cleaned up, once `Vec::into_raw_parts` is stable.
+        let ptr = values.as_mut_ptr();
+        let length = values.len();
+        let capacity = values.capacity();
+
+        // We're taking ownership of the memory here, so we can't allow
+        // `values` to deallocate it.
+        mem::forget(values);
+
+        // Allocate the reference counter on the heap. It will be reclaimed
+        // alongside `values` when it reaches 0.
+        let rc = Box::new(atomic::AtomicUsize::new(1));
+        let rc = Box::leak(rc) as *mut _;
+
+        Self {
+            ptr,
+            length,
+            capacity,
+            rc,
+        }
+    }
+}
+
+impl<T> Deref for FfiVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        // This is sound, as the raw parts come from a `Vec`, and the values
+        // behind them are never modified after construction.
+        unsafe { std::slice::from_raw_parts(self.ptr, self.length) }
+    }
+}
+
+impl<T> Clone for FfiVec<T> {
+    fn clone(&self) -> Self {
+        // Increment the reference counter
+        unsafe {
+            (*self.rc).fetch_add(1, atomic::Ordering::AcqRel);
+        }
+
+        Self {
+            ptr: self.ptr,
+            length: self.length,
+            capacity: self.capacity,
+            rc: self.rc,
+        }
+    }
+}
+
+impl<T> Default for FfiVec<T> {
+    fn default() -> Self {
+        Self::from_vec(Vec::new())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FfiVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for FfiVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T> Drop for FfiVec<T> {
+    fn drop(&mut self) {
+        // Decrement the reference counter
+        let rc_last =
+            unsafe { (*self.rc).fetch_sub(1, atomic::Ordering::AcqRel) };
+
+        // If the value of the refcount before decrementing was 1, then this
+        // must be the last Drop call. Reclaim all resources allocated on the
+        // heap, including those of the values.
+        if rc_last == 1 {
+            unsafe {
+                let values =
+                    Vec::from_raw_parts(self.ptr, self.length, self.capacity);
+                let rc = Box::from_raw(self.rc);
+
+                drop(values);
+                drop(rc);
+            }
+        }
+    }
+}
+
+// `FfiVec` can be `Send` and `Sync`, if its values are, because it encapsulates
+// the raw pointer it contains, the values behind it are never modified after
+// construction, and the reference counter is atomic.
+unsafe impl<T: Send + Sync> Send for FfiVec<T> {}
+unsafe impl<T: Send + Sync> Sync for FfiVec<T> {}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> ser::Serialize for FfiVec<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        // Encoded like a `Vec`.
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> de::Deserialize<'de> for FfiVec<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(Self::from_vec)
+    }
+}
+
+/// A string that is FFI-safe
+///
+/// This is equivalent to an immutable `String`, which isn't FFI-safe for the
+/// same reasons as `Vec`. See [`FfiVec`].
+#[derive(Clone, Default, PartialEq)]
+#[repr(C)]
+pub(crate) struct FfiString(FfiVec<u8>);
+
+impl FfiString {
+    /// Construct an instance from a `String`
+    pub(crate) fn from_string(string: String) -> Self {
+        Self(FfiVec::from_vec(string.into_bytes()))
+    }
+}
+
+impl Deref for FfiString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        // This is sound, as the bytes come from a `String`.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl fmt::Debug for FfiString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ser::Serialize for FfiString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        // Encoded like a `String`.
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for FfiString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self::from_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{FfiString, FfiVec};
+
+    #[test]
+    fn ffi_vec_drops_values_once() {
+        let value = Arc::new(());
+
+        let values = FfiVec::from_vec(vec![value.clone(), value.clone()]);
+        let clone = values.clone();
+        assert_eq!(Arc::strong_count(&value), 3);
+
+        drop(values);
+        assert_eq!(clone.len(), 2);
+        assert_eq!(Arc::strong_count(&value), 3);
+
+        drop(clone);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn ffi_string() {
+        let string = FfiString::from_string(String::from("name"));
+        assert_eq!(&*string, "name");
+        assert_eq!(format!("{string:?}"), "\"name\"");
+    }
+}
@@ -0,0 +1,149 @@
+//! Binary format for shapes
+//!
+//! A serialized shape consists of the following parts:
+//!
+//! 1. The 4 bytes `FJSH`, identifying the format.
+//! 2. The version of the format, as a little-endian `u32`.
+//! 3. The shape, encoded using [bincode] 1.x with its default configuration.
+//!
+//! The version is increased whenever a change to [`Shape`] or any of the types
+//! it contains changes the encoding. Shapes that were serialized using a
+//! different version can't be deserialized.
+//!
+//! [bincode]: https://docs.rs/bincode/1
+
+use std::{error, fmt};
+
+use crate::Shape;
+
+const MAGIC: [u8; 4] = *b"FJSH";
+
+/// The current version of the binary format
+pub const FORMAT_VERSION: u32 = 10;
+
+impl Shape {
+    /// Serialize the shape into the binary format
+    ///
+    /// See the [`format`](crate::format) module for details.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+
+        bincode::serialize_into(&mut bytes, self)?;
+
+        Ok(bytes)
+    }
+
+    /// Deserialize a shape from the binary format
+    ///
+    /// See the [`format`](crate::format) module for details.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormatError> {
+        let payload = match bytes {
+            [m0, m1, m2, m3, v0, v1, v2, v3, payload @ ..]
+                if [*m0, *m1, *m2, *m3] == MAGIC =>
+            {
+                let version = u32::from_le_bytes([*v0, *v1, *v2, *v3]);
+                if version != FORMAT_VERSION {
+                    return Err(FormatError::UnsupportedVersion(version));
+                }
+
+                payload
+            }
+            _ => return Err(FormatError::InvalidHeader),
+        };
+
+        let shape = bincode::deserialize(payload)?;
+        Ok(shape)
+    }
+}
+
+/// An error that can occur while serializing or deserializing a shape
+#[derive(Debug)]
+pub enum FormatError {
+    /// The data doesn't start with the expected header
+    InvalidHeader,
+
+    /// The data was serialized using an unsupported version of the format
+    UnsupportedVersion(u32),
+
+    /// Error encoding or decoding the shape
+    Encoding(bincode::Error),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "invalid header"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported format version {version}, expected \
+                {FORMAT_VERSION}"
+            ),
+            Self::Encoding(err) => write!(f, "encoding error: {err}"),
+        }
+    }
+}
+
+impl error::Error for FormatError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Encoding(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<bincode::Error> for FormatError {
+    fn from(err: bincode::Error) -> Self {
+        Self::Encoding(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ffi_option::FfiOption, Angle, Shape, Sketch, Sweep, Transform,
+    };
+
+    use super::{FormatError, FORMAT_VERSION};
+
+    #[test]
+    fn loopback() {
+        let sketch = Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+        let shape: Shape = Transform {
+            shape: Sweep::from_path(sketch.into(), [0., 0., 1.])
+                .with_taper(Angle::from_deg(5.))
+                .into(),
+            axis: [0., 0., 1.],
+            angle: Angle::from_deg(45.),
+            offset: [1., 2., 3.],
+            color: FfiOption::Some([0, 0, 255, 255]),
+            tolerance: FfiOption::None,
+        }
+        .into();
+
+        let bytes = shape.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], b"FJSH");
+
+        assert_eq!(Shape::from_bytes(&bytes).unwrap(), shape);
+    }
+
+    #[test]
+    fn invalid_header() {
+        let shape: Shape = Sketch::from_points(vec![[0., 0.]]).into();
+
+        let mut bytes = shape.to_bytes().unwrap();
+        bytes[4] += 1;
+
+        assert!(matches!(
+            Shape::from_bytes(&bytes),
+            Err(FormatError::UnsupportedVersion(version))
+                if version == FORMAT_VERSION + 1
+        ));
+        assert!(matches!(
+            Shape::from_bytes(b"FJ"),
+            Err(FormatError::InvalidHeader)
+        ));
+    }
+}
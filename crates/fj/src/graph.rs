@@ -0,0 +1,454 @@
+//! Introspection of the operation tree that makes up a shape
+//!
+//! A [`Shape`] is a tree of operations (groups, sweeps, transforms, ...) with
+//! sketches at its leaves. The types in this module provide a uniform way to
+//! walk that tree, for example to display a feature tree, compute statistics,
+//! or serialize the structure of a model.
+
+use std::fmt;
+
+use crate::{
+    Boolean, BooleanBackend, BooleanOperation, Chain, Difference2d, Group,
+    Offset, Primitive, PrimitiveKind, Shape, Shape2d, Sketch, Sweep, Transform,
+};
+
+/// A node in the operation tree of a shape
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Node<'r> {
+    /// A group of 3-dimensional shapes
+    Group(&'r Group),
+
+    /// A difference between two 2-dimensional shapes
+    Difference2d(&'r Difference2d),
+
+    /// A sketch
+    Sketch(&'r Sketch),
+
+    /// A sweep of a 2-dimensional shape
+    Sweep(&'r Sweep),
+
+    /// A transformed 3-dimensional shape
+    Transform(&'r Transform),
+
+    /// A 3-dimensional primitive
+    Primitive(&'r Primitive),
+
+    /// A 3-dimensional shape, with its faces offset by a distance
+    Offset(&'r Offset),
+
+    /// A boolean operation on two 3-dimensional shapes
+    Boolean(&'r Boolean),
+}
+
+impl<'r> Node<'r> {
+    /// Access the kind of operation that this node represents
+    pub fn kind(&self) -> NodeKind {
+        match self {
+            Self::Group(_) => NodeKind::Group,
+            Self::Difference2d(_) => NodeKind::Difference2d,
+            Self::Sketch(_) => NodeKind::Sketch,
+            Self::Sweep(_) => NodeKind::Sweep,
+            Self::Transform(_) => NodeKind::Transform,
+            Self::Primitive(_) => NodeKind::Primitive,
+            Self::Offset(_) => NodeKind::Offset,
+            Self::Boolean(_) => NodeKind::Boolean,
+        }
+    }
+
+    /// Access the parameters of the operation, as name-value pairs
+    ///
+    /// The parameters don't include the child nodes. Use [`Node::children`]
+    /// to access those.
+    pub fn parameters(&self) -> Vec<(&'static str, Value)> {
+        let mut parameters = match self {
+            Self::Group(_) => Vec::new(),
+            Self::Difference2d(difference) => {
+                vec![("color", Value::Color(difference.color()))]
+            }
+            Self::Sketch(sketch) => {
+                let chain = match sketch.chain() {
+                    Chain::Circle(circle) => {
+                        ("radius", Value::Float(circle.radius()))
+                    }
+                    Chain::PolyChain(poly_chain) => {
+                        ("points", Value::Points(poly_chain.to_points()))
+                    }
+                };
+
+                vec![chain, ("color", Value::Color(sketch.color()))]
+            }
+            Self::Sweep(sweep) => vec![
+                ("path", Value::Vector(sweep.path())),
+                ("back_length", Value::Float(sweep.back_length())),
+                ("taper", Value::Float(sweep.taper().rad())),
+            ],
+            Self::Transform(transform) => vec![
+                ("axis", Value::Vector(transform.axis)),
+                ("angle", Value::Float(transform.angle.rad())),
+                ("offset", Value::Vector(transform.offset)),
+            ],
+            Self::Primitive(primitive) => {
+                let mut parameters = match *primitive.kind() {
+                    PrimitiveKind::Cuboid { size } => {
+                        vec![("size", Value::Vector(size))]
+                    }
+                    PrimitiveKind::Cylinder { radius, height }
+                    | PrimitiveKind::Cone { radius, height } => vec![
+                        ("radius", Value::Float(radius)),
+                        ("height", Value::Float(height)),
+                    ],
+                    PrimitiveKind::Sphere { radius } => {
+                        vec![("radius", Value::Float(radius))]
+                    }
+                    PrimitiveKind::Torus {
+                        major_radius,
+                        minor_radius,
+                    } => vec![
+                        ("major_radius", Value::Float(major_radius)),
+                        ("minor_radius", Value::Float(minor_radius)),
+                    ],
+                };
+
+                parameters.push(("color", Value::Color(primitive.color())));
+                parameters
+            }
+            Self::Offset(offset) => {
+                vec![("distance", Value::Float(offset.distance))]
+            }
+            Self::Boolean(boolean) => {
+                let operation = match boolean.operation {
+                    BooleanOperation::Union => "union",
+                    BooleanOperation::Intersection => "intersection",
+                    BooleanOperation::Difference => "difference",
+                };
+                let backend = match boolean.backend {
+                    BooleanBackend::Exact => "exact",
+                    BooleanBackend::Mesh => "mesh",
+                };
+
+                vec![
+                    ("operation", Value::Name(operation)),
+                    ("backend", Value::Name(backend)),
+                ]
+            }
+        };
+
+        // Sketches and primitives always have a color, which is already part
+        // of their parameters. Other operations only have a color, if it has
+        // been set explicitly.
+        let color = match self {
+            Self::Group(group) => group.color(),
+            Self::Sweep(sweep) => sweep.color(),
+            Self::Transform(transform) => transform.color(),
+            Self::Offset(offset) => offset.color(),
+            Self::Boolean(boolean) => boolean.color(),
+            Self::Difference2d(_) | Self::Sketch(_) | Self::Primitive(_) => {
+                None
+            }
+        };
+        if let Some(color) = color {
+            parameters.push(("color", Value::Color(color)));
+        }
+
+        let tolerance = match self {
+            Self::Group(group) => group.tolerance(),
+            Self::Sweep(sweep) => sweep.tolerance(),
+            Self::Transform(transform) => transform.tolerance(),
+            Self::Primitive(primitive) => primitive.tolerance(),
+            Self::Offset(offset) => offset.tolerance(),
+            Self::Boolean(boolean) => boolean.tolerance(),
+            Self::Difference2d(_) | Self::Sketch(_) => None,
+        };
+        if let Some(tolerance) = tolerance {
+            parameters.push(("tolerance", Value::Float(tolerance)));
+        }
+
+        parameters
+    }
+
+    /// Access the child nodes of this node
+    pub fn children(&self) -> Vec<Node<'r>> {
+        match self {
+            Self::Group(group) => group
+                .members()
+                .iter()
+                .map(|member| Node::from(member.shape()))
+                .collect(),
+            Self::Difference2d(difference) => {
+                difference.shapes().iter().map(Node::from).collect()
+            }
+            Self::Sketch(_) => Vec::new(),
+            Self::Sweep(sweep) => vec![sweep.shape().into()],
+            Self::Transform(transform) => vec![(&transform.shape).into()],
+            Self::Primitive(_) => Vec::new(),
+            Self::Offset(offset) => vec![(&offset.shape).into()],
+            Self::Boolean(boolean) => {
+                vec![(&boolean.a).into(), (&boolean.b).into()]
+            }
+        }
+    }
+
+    /// Iterate over this node and all of its descendants
+    ///
+    /// Nodes are visited depth-first, parents before their children. Each
+    /// node is returned together with its depth, with this node having a depth
+    /// of `0`.
+    pub fn descendants(&self) -> Nodes<'r> {
+        Nodes {
+            stack: vec![(0, *self)],
+        }
+    }
+
+    /// Visit this node and all of its descendants
+    ///
+    /// Calls [`Visitor::enter`] before visiting a node's children, and
+    /// [`Visitor::leave`] after.
+    pub fn accept(&self, visitor: &mut impl Visitor) {
+        self.accept_at_depth(visitor, 0);
+    }
+
+    fn accept_at_depth(&self, visitor: &mut impl Visitor, depth: usize) {
+        visitor.enter(*self, depth);
+        for child in self.children() {
+            child.accept_at_depth(visitor, depth + 1);
+        }
+        visitor.leave(*self, depth);
+    }
+}
+
+impl<'r> From<&'r Shape> for Node<'r> {
+    fn from(shape: &'r Shape) -> Self {
+        match shape {
+            Shape::Group(group) => Self::Group(group),
+            Shape::Shape2d(shape) => shape.into(),
+            Shape::Sweep(sweep) => Self::Sweep(sweep),
+            Shape::Transform(transform) => Self::Transform(transform),
+            Shape::Primitive(primitive) => Self::Primitive(primitive),
+            Shape::Offset(offset) => Self::Offset(offset),
+            Shape::Boolean(boolean) => Self::Boolean(boolean),
+        }
+    }
+}
+
+impl<'r> From<&'r Shape2d> for Node<'r> {
+    fn from(shape: &'r Shape2d) -> Self {
+        match shape {
+            Shape2d::Difference(difference) => Self::Difference2d(difference),
+            Shape2d::Sketch(sketch) => Self::Sketch(sketch),
+        }
+    }
+}
+
+/// The kind of operation that a [`Node`] represents
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum NodeKind {
+    /// A group of 3-dimensional shapes
+    Group,
+
+    /// A difference between two 2-dimensional shapes
+    Difference2d,
+
+    /// A sketch
+    Sketch,
+
+    /// A sweep of a 2-dimensional shape
+    Sweep,
+
+    /// A transformed 3-dimensional shape
+    Transform,
+
+    /// A 3-dimensional primitive
+    Primitive,
+
+    /// A 3-dimensional shape, with its faces offset by a distance
+    Offset,
+
+    /// A boolean operation on two 3-dimensional shapes
+    Boolean,
+}
+
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Group => "Group",
+            Self::Difference2d => "Difference",
+            Self::Sketch => "Sketch",
+            Self::Sweep => "Sweep",
+            Self::Transform => "Transform",
+            Self::Primitive => "Primitive",
+            Self::Offset => "Offset",
+            Self::Boolean => "Boolean",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The value of a parameter of a [`Node`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A number
+    Float(f64),
+
+    /// A 3-dimensional vector
+    Vector([f64; 3]),
+
+    /// A list of 2-dimensional points
+    Points(Vec<[f64; 2]>),
+
+    /// An RGBA color
+    Color([u8; 4]),
+
+    /// One of a fixed set of named options
+    Name(&'static str),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Vector([x, y, z]) => write!(f, "[{x}, {y}, {z}]"),
+            Self::Points(points) => {
+                write!(f, "[")?;
+                for (i, [x, y]) in points.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "[{x}, {y}]")?;
+                }
+                write!(f, "]")
+            }
+            Self::Color([r, g, b, a]) => {
+                write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}")
+            }
+            Self::Name(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Receives the nodes of an operation tree
+///
+/// See [`Node::accept`] and [`Shape::accept`].
+pub trait Visitor {
+    /// Called when entering a node, before its children are visited
+    fn enter(&mut self, node: Node, depth: usize);
+
+    /// Called when leaving a node, after its children have been visited
+    fn leave(&mut self, _node: Node, _depth: usize) {}
+}
+
+/// Iterator over the nodes of an operation tree
+///
+/// Returned by [`Node::descendants`] and [`Shape::nodes`].
+#[derive(Clone, Debug)]
+pub struct Nodes<'r> {
+    stack: Vec<(usize, Node<'r>)>,
+}
+
+impl<'r> Iterator for Nodes<'r> {
+    type Item = (usize, Node<'r>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.stack.pop()?;
+
+        // Push the children in reverse, so the first child is returned next.
+        let children = node.children().into_iter().rev();
+        self.stack.extend(children.map(|child| (depth + 1, child)));
+
+        Some((depth, node))
+    }
+}
+
+impl Shape {
+    /// Access the root node of the shape's operation tree
+    pub fn root(&self) -> Node<'_> {
+        self.into()
+    }
+
+    /// Iterate over all nodes of the shape's operation tree
+    ///
+    /// See [`Node::descendants`].
+    pub fn nodes(&self) -> Nodes<'_> {
+        self.root().descendants()
+    }
+
+    /// Visit all nodes of the shape's operation tree
+    ///
+    /// See [`Node::accept`].
+    pub fn accept(&self, visitor: &mut impl Visitor) {
+        self.root().accept(visitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ffi_option::FfiOption, Angle, Circle, Group, Shape, Sketch, Sweep,
+        Transform,
+    };
+
+    use super::{Node, NodeKind, Value, Visitor};
+
+    fn shape() -> Shape {
+        let circle = Sketch::from_circle(Circle::from_radius(1.));
+        let square =
+            Sketch::from_points(vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+
+        let a = Sweep::from_path(circle.into(), [0., 0., 1.]);
+        let b = Transform {
+            shape: Sweep::from_path(square.into(), [0., 0., 2.]).into(),
+            axis: [0., 0., 1.],
+            angle: Angle::from_rad(0.),
+            offset: [3., 0., 0.],
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
+        };
+
+        Group::from_shapes([Shape::from(a), b.into()]).into()
+    }
+
+    #[test]
+    fn nodes() {
+        let shape = shape();
+
+        let nodes: Vec<_> = shape
+            .nodes()
+            .map(|(depth, node)| (depth, node.kind()))
+            .collect();
+        assert_eq!(
+            nodes,
+            [
+                (0, NodeKind::Group),
+                (1, NodeKind::Sweep),
+                (2, NodeKind::Sketch),
+                (1, NodeKind::Transform),
+                (2, NodeKind::Sweep),
+                (3, NodeKind::Sketch),
+            ]
+        );
+
+        let (_, circle) = shape.nodes().nth(2).unwrap();
+        assert_eq!(circle.parameters()[0], ("radius", Value::Float(1.)));
+    }
+
+    #[test]
+    fn accept() {
+        struct Printer(String);
+
+        impl Visitor for Printer {
+            fn enter(&mut self, node: Node, depth: usize) {
+                self.0.push_str(&"  ".repeat(depth));
+                self.0.push_str(&node.kind().to_string());
+                self.0.push('\n');
+            }
+        }
+
+        let mut printer = Printer(String::new());
+        shape().accept(&mut printer);
+
+        assert_eq!(
+            printer.0,
+            "Group\n  Sweep\n    Sketch\n  Transform\n    Sweep\n      Sketch\n"
+        );
+    }
+}
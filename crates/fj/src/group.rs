@@ -1,25 +1,100 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::Shape;
+use crate::{
+    ffi_option::FfiOption,
+    ffi_vec::{FfiString, FfiVec},
+    Angle, Shape,
+};
 
-/// A group of two 3-dimensional shapes
+/// A group of 3-dimensional shapes
 ///
 /// A group is a collection of disjoint shapes. It is not a union, in that the
 /// shapes in the group are not allowed to touch or overlap.
 ///
+/// Each member of the group can be placed individually, and named. Groups can
+/// be nested, but they don't need to be: A group can have any number of
+/// members, which is the better choice for large assemblies.
+///
 /// # Limitations
 ///
 /// Whether the shapes in the group touch or overlap is not currently checked.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(C)]
 pub struct Group {
-    /// The first of the shapes
-    pub a: Shape,
+    /// The members of the group
+    members: FfiVec<GroupMember>,
+
+    /// The color that the group passes on to its members
+    ///
+    /// Members that have a color of their own keep it. If this is `None`, the
+    /// members inherit the color of whatever the group is part of.
+    color: FfiOption<[u8; 4]>,
+
+    /// The tolerance that the group's members are approximated with
+    ///
+    /// Only has an effect, if it's tighter than the tolerance that applies
+    /// otherwise.
+    tolerance: FfiOption<f64>,
+}
+
+impl Group {
+    /// Create a group from the given shapes
+    ///
+    /// The shapes are neither placed, nor named. Use [`Group::builder`] for
+    /// more control.
+    pub fn from_shapes(
+        shapes: impl IntoIterator<Item = impl Into<Shape>>,
+    ) -> Self {
+        let members = shapes.into_iter().map(GroupMember::new).collect();
+        Self {
+            members: FfiVec::from_vec(members),
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
+        }
+    }
+
+    /// Create a [`GroupBuilder`] to build a group
+    pub fn builder() -> GroupBuilder {
+        GroupBuilder::default()
+    }
+
+    /// Set the color that the group passes on to its members, in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = FfiOption::Some(color);
+        self
+    }
+
+    /// Access the color that the group passes on, if it has one
+    pub fn color(&self) -> Option<[u8; 4]> {
+        self.color.get()
+    }
+
+    /// Approximate the group's members with a tighter tolerance
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = FfiOption::Some(tolerance);
+        self
+    }
+
+    /// Access the tolerance of the group, if it has one
+    pub fn tolerance(&self) -> Option<f64> {
+        self.tolerance.get()
+    }
+
+    /// Access the members of the group
+    pub fn members(&self) -> &[GroupMember] {
+        &self.members
+    }
 
-    /// The second of the shapes
-    pub b: Shape,
+    /// Access the member with the given name
+    ///
+    /// If multiple members have that name, the first one is returned.
+    pub fn member(&self, name: &str) -> Option<&GroupMember> {
+        self.members
+            .iter()
+            .find(|member| member.name() == Some(name))
+    }
 }
 
 impl From<Group> for Shape {
@@ -27,3 +102,158 @@ impl From<Group> for Shape {
         Self::Group(Box::new(shape))
     }
 }
+
+/// A member of a [`Group`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct GroupMember {
+    /// The shape
+    shape: Shape,
+
+    /// Where the shape is placed within the group
+    placement: FfiOption<Placement>,
+
+    /// The name of the member
+    name: FfiOption<FfiString>,
+}
+
+impl GroupMember {
+    /// Create a group member that is neither placed, nor named
+    pub fn new(shape: impl Into<Shape>) -> Self {
+        Self {
+            shape: shape.into(),
+            placement: FfiOption::None,
+            name: FfiOption::None,
+        }
+    }
+
+    /// Place the member within the group
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = FfiOption::Some(placement);
+        self
+    }
+
+    /// Name the member
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = FfiOption::Some(FfiString::from_string(name.into()));
+        self
+    }
+
+    /// Access the shape
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the placement of the member within the group
+    ///
+    /// If this is `None`, the shape is not moved.
+    pub fn placement(&self) -> Option<Placement> {
+        self.placement.get()
+    }
+
+    /// Access the name of the member, if it has one
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| &**name)
+    }
+}
+
+/// Where a [`GroupMember`] is placed within its group
+///
+/// Like [`Transform`], a placement is a rotation, followed by a translation.
+///
+/// [`Transform`]: crate::Transform
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Placement {
+    /// The axis of the rotation
+    pub axis: [f64; 3],
+
+    /// The angle of the rotation
+    pub angle: Angle,
+
+    /// The offset of the translation
+    pub offset: [f64; 3],
+}
+
+impl Placement {
+    /// Create a placement that only translates
+    pub fn from_offset(offset: [f64; 3]) -> Self {
+        Self {
+            axis: [0., 0., 1.],
+            angle: Angle::from_rad(0.),
+            offset,
+        }
+    }
+
+    /// Rotate the member, before it is translated
+    pub fn with_rotation(mut self, axis: [f64; 3], angle: Angle) -> Self {
+        self.axis = axis;
+        self.angle = angle;
+        self
+    }
+}
+
+/// API for building a [`Group`]
+///
+/// Returned by [`Group::builder`].
+#[derive(Clone, Debug, Default)]
+pub struct GroupBuilder {
+    members: Vec<GroupMember>,
+}
+
+impl GroupBuilder {
+    /// Add a shape to the group, that is neither placed, nor named
+    pub fn with_shape(self, shape: impl Into<Shape>) -> Self {
+        self.with_member(GroupMember::new(shape))
+    }
+
+    /// Add a member to the group
+    pub fn with_member(mut self, member: GroupMember) -> Self {
+        self.members.push(member);
+        self
+    }
+
+    /// Build the group
+    pub fn build(self) -> Group {
+        Group {
+            members: FfiVec::from_vec(self.members),
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Primitive, Shape};
+
+    use super::{Group, GroupMember, Placement};
+
+    #[test]
+    fn builder() {
+        let cube = Primitive::cuboid([1., 1., 1.]);
+
+        let group = Group::builder()
+            .with_shape(cube.clone())
+            .with_member(
+                GroupMember::new(cube.clone())
+                    .with_placement(Placement::from_offset([2., 0., 0.]))
+                    .with_name("right"),
+            )
+            .with_shape(cube.clone())
+            .build();
+
+        assert_eq!(group.members().len(), 3);
+        assert_eq!(group.members()[0], GroupMember::new(cube));
+
+        let right = group.member("right").unwrap();
+        assert_eq!(right.placement().unwrap().offset, [2., 0., 0.]);
+        assert_eq!(right.name(), Some("right"));
+        assert!(group.member("left").is_none());
+
+        let shape: Shape = group.into();
+        assert!(matches!(shape, Shape::Group(_)));
+    }
+}
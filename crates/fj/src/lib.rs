@@ -18,16 +18,45 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "serde")]
+pub mod format;
+pub mod graph;
 pub mod syntax;
 
 mod angle;
+mod boolean;
+mod color;
+mod dxf;
+mod ffi_option;
+mod ffi_vec;
 mod group;
+mod offset;
+mod parameter;
+mod plane;
+mod primitive;
+mod query;
 mod shape_2d;
 mod sweep;
 mod transform;
 
 pub use self::{
-    angle::*, group::Group, shape_2d::*, sweep::Sweep, transform::Transform,
+    angle::*,
+    boolean::{Boolean, BooleanBackend, BooleanOperation},
+    color::DEFAULT_COLOR,
+    dxf::DxfError,
+    ffi_vec::FfiVec,
+    group::{Group, GroupBuilder, GroupMember, Placement},
+    offset::Offset,
+    parameter::{ParameterBound, ParameterMetadata},
+    plane::Plane,
+    primitive::{Primitive, PrimitiveKind},
+    query::{
+        edges, face_nearest, faces, EdgeFilter, EdgeQuery, FaceFilter,
+        FaceQuery,
+    },
+    shape_2d::*,
+    sweep::Sweep,
+    transform::Transform,
 };
 pub use fj_proc::*;
 #[cfg(feature = "serde")]
@@ -38,7 +67,7 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(C)]
 pub enum Shape {
-    /// A group of two 3-dimensional shapes
+    /// A group of 3-dimensional shapes
     Group(Box<Group>),
 
     /// A 2D shape
@@ -49,4 +78,13 @@ pub enum Shape {
 
     /// A transformed 3-dimensional shape
     Transform(Box<Transform>),
+
+    /// A 3-dimensional primitive
+    Primitive(Primitive),
+
+    /// A 3-dimensional shape, with its faces offset by a distance
+    Offset(Box<Offset>),
+
+    /// A boolean operation on two 3-dimensional shapes
+    Boolean(Box<Boolean>),
 }
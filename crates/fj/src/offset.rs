@@ -0,0 +1,65 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ffi_option::FfiOption, Shape};
+
+/// A 3-dimensional shape, with all of its faces offset by a distance
+///
+/// A positive distance grows the shape, a negative one shrinks it. Unlike a
+/// shell, the result is a solid shape. This is useful for compensating
+/// manufacturing tolerances, for example.
+///
+/// Where the faces of the shape move apart from each other, the gap is filled
+/// with rounded blends. Where they move towards each other, they meet at a
+/// sharp edge.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Offset {
+    /// The shape being offset
+    pub shape: Shape,
+
+    /// The distance by which the faces are offset
+    pub distance: f64,
+
+    /// The color that the offset passes on to the shape being offset
+    ///
+    /// Applies to all parts of the shape that don't have a color of their own.
+    pub(crate) color: FfiOption<[u8; 4]>,
+
+    /// The tolerance that the shape is approximated with
+    ///
+    /// Only has an effect, if it's tighter than the tolerance that applies
+    /// otherwise.
+    pub(crate) tolerance: FfiOption<f64>,
+}
+
+impl Offset {
+    /// Set the rendering color of the offset shape in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = FfiOption::Some(color);
+        self
+    }
+
+    /// Access the color that the offset passes on, if it has one
+    pub fn color(&self) -> Option<[u8; 4]> {
+        self.color.get()
+    }
+
+    /// Approximate the offset shape with a tighter tolerance
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = FfiOption::Some(tolerance);
+        self
+    }
+
+    /// Access the tolerance of the operation, if it has one
+    pub fn tolerance(&self) -> Option<f64> {
+        self.tolerance.get()
+    }
+}
+
+impl From<Offset> for Shape {
+    fn from(shape: Offset) -> Self {
+        Self::Offset(Box::new(shape))
+    }
+}
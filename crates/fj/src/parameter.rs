@@ -0,0 +1,216 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ffi_option::FfiOption, ffi_vec::FfiString};
+
+/// Describes a parameter of a model
+///
+/// Models that are defined using [`macro@crate::model`] provide this for each
+/// of their parameters, so applications can present them to the user, for
+/// example as sliders.
+///
+/// This is passed from the model to the host across the FFI boundary, which is
+/// why it stores its fields in FFI-safe types, and only provides access to
+/// them through methods.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "ParameterMetadataSerde", into = "ParameterMetadataSerde")
+)]
+#[repr(C)]
+pub struct ParameterMetadata {
+    name: FfiString,
+    ty: FfiString,
+    value: FfiString,
+    default: FfiOption<FfiString>,
+    minimum: FfiOption<f64>,
+    maximum: FfiOption<f64>,
+}
+
+impl ParameterMetadata {
+    /// Create a `ParameterMetadata` without default, minimum, or maximum
+    pub fn new(name: String, ty: String, value: String) -> Self {
+        Self {
+            name: FfiString::from_string(name),
+            ty: FfiString::from_string(ty),
+            value: FfiString::from_string(value),
+            default: FfiOption::None,
+            minimum: FfiOption::None,
+            maximum: FfiOption::None,
+        }
+    }
+
+    /// Set the default value of the parameter
+    pub fn with_default(mut self, default: String) -> Self {
+        self.default = FfiOption::Some(FfiString::from_string(default));
+        self
+    }
+
+    /// Set the minimum value of the parameter
+    pub fn with_minimum(mut self, minimum: f64) -> Self {
+        self.minimum = FfiOption::Some(minimum);
+        self
+    }
+
+    /// Set the maximum value of the parameter
+    pub fn with_maximum(mut self, maximum: f64) -> Self {
+        self.maximum = FfiOption::Some(maximum);
+        self
+    }
+
+    /// The name of the parameter
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type of the parameter, as written in the model's source code
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// The value of the parameter that the model has been evaluated with
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The default value of the parameter, if it has one
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_ref().map(|default| &**default)
+    }
+
+    /// The minimum value of the parameter, if it has one
+    ///
+    /// The minimum can depend on the values of other parameters. In that case,
+    /// it has been computed from the values the model has been evaluated with.
+    pub fn minimum(&self) -> Option<f64> {
+        self.minimum.get()
+    }
+
+    /// The maximum value of the parameter, if it has one
+    ///
+    /// The maximum can depend on the values of other parameters. In that case,
+    /// it has been computed from the values the model has been evaluated with.
+    pub fn maximum(&self) -> Option<f64> {
+        self.maximum.get()
+    }
+}
+
+/// A value that can be the minimum or maximum of a parameter
+///
+/// Used by [`macro@crate::model`] to convert the bounds of a parameter, which
+/// can have any numeric type, into the `f64` that [`ParameterMetadata`] stores.
+pub trait ParameterBound {
+    /// Convert the bound into an `f64`
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_parameter_bound {
+    ($($ty:ty),*) => {
+        $(
+            impl ParameterBound for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_parameter_bound!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32
+);
+
+impl ParameterBound for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// The serialized form of [`ParameterMetadata`]
+///
+/// Keeps the encoding that `ParameterMetadata` had before it became FFI-safe,
+/// with plain strings and options.
+#[cfg(feature = "serde")]
+#[derive(Clone, Serialize, Deserialize)]
+struct ParameterMetadataSerde {
+    name: String,
+    ty: String,
+    value: String,
+    default: Option<String>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<ParameterMetadataSerde> for ParameterMetadata {
+    fn from(metadata: ParameterMetadataSerde) -> Self {
+        Self {
+            name: FfiString::from_string(metadata.name),
+            ty: FfiString::from_string(metadata.ty),
+            value: FfiString::from_string(metadata.value),
+            default: metadata.default.map(FfiString::from_string).into(),
+            minimum: metadata.minimum.into(),
+            maximum: metadata.maximum.into(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ParameterMetadata> for ParameterMetadataSerde {
+    fn from(metadata: ParameterMetadata) -> Self {
+        Self {
+            name: metadata.name().to_owned(),
+            ty: metadata.ty().to_owned(),
+            value: metadata.value().to_owned(),
+            default: metadata.default().map(ToOwned::to_owned),
+            minimum: metadata.minimum(),
+            maximum: metadata.maximum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParameterBound, ParameterMetadata};
+
+    #[test]
+    fn parameter_metadata() {
+        let metadata = ParameterMetadata::new(
+            String::from("radius"),
+            String::from("f64"),
+            String::from("2"),
+        )
+        .with_default(String::from("1"))
+        .with_minimum(0.5.to_f64());
+
+        assert_eq!(metadata.name(), "radius");
+        assert_eq!(metadata.ty(), "f64");
+        assert_eq!(metadata.value(), "2");
+        assert_eq!(metadata.default(), Some("1"));
+        assert_eq!(metadata.minimum(), Some(0.5));
+        assert_eq!(metadata.maximum(), None);
+        assert_eq!(3u64.to_f64(), 3.);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parameter_metadata_serde() {
+        let metadata = ParameterMetadata::new(
+            String::from("radius"),
+            String::from("f64"),
+            String::from("2"),
+        )
+        .with_maximum(4.);
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"radius","ty":"f64","value":"2","default":null,"minimum":null,"maximum":4.0}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ParameterMetadata>(&json).unwrap(),
+            metadata
+        );
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Angle;
+
+/// A plane that sketches can be attached to
+///
+/// A plane is defined by an origin and two axes, which make up the coordinate
+/// system of the sketches that are attached to it. The normal of the plane is
+/// the cross product of its x and y axes.
+///
+/// Planes can be derived from other planes, using [`Plane::offset`] and
+/// [`Plane::rotate`]. This makes it possible to define datum planes relative
+/// to each other, instead of sketching in the xy-plane and transforming the
+/// result.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Plane {
+    origin: [f64; 3],
+    x_axis: [f64; 3],
+    y_axis: [f64; 3],
+}
+
+impl Plane {
+    /// The xy-plane, with its normal pointing towards positive z
+    pub fn xy() -> Self {
+        Self::from_axes([0., 0., 0.], [1., 0., 0.], [0., 1., 0.])
+    }
+
+    /// The xz-plane, with its normal pointing towards negative y
+    pub fn xz() -> Self {
+        Self::from_axes([0., 0., 0.], [1., 0., 0.], [0., 0., 1.])
+    }
+
+    /// The yz-plane, with its normal pointing towards positive x
+    pub fn yz() -> Self {
+        Self::from_axes([0., 0., 0.], [0., 1., 0.], [0., 0., 1.])
+    }
+
+    /// Create a plane from its origin and axes
+    ///
+    /// The axes are normalized. `y_axis` is made perpendicular to `x_axis`, by
+    /// removing any component along `x_axis` from it.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the axes are parallel, or if either of them has zero length.
+    pub fn from_axes(
+        origin: [f64; 3],
+        x_axis: [f64; 3],
+        y_axis: [f64; 3],
+    ) -> Self {
+        let x_axis = normalize(x_axis);
+        let y_axis = normalize(sub(y_axis, scale(x_axis, dot(y_axis, x_axis))));
+
+        Self {
+            origin,
+            x_axis,
+            y_axis,
+        }
+    }
+
+    /// Create a plane through three points
+    ///
+    /// The plane's origin is `a`, and its x-axis points towards `b`. `c`
+    /// determines on which side the y-axis is.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the points are collinear.
+    pub fn from_points([a, b, c]: [[f64; 3]; 3]) -> Self {
+        Self::from_axes(a, sub(b, a), sub(c, a))
+    }
+
+    /// Move the plane by `distance`, along its normal
+    pub fn offset(mut self, distance: f64) -> Self {
+        self.origin = add(self.origin, scale(self.normal(), distance));
+        self
+    }
+
+    /// Rotate the plane by `angle`, around an axis through its origin
+    ///
+    /// To create a plane that is angled against this one along one of its
+    /// axes, pass that axis as `axis`, for example [`Plane::x_axis`].
+    pub fn rotate(mut self, axis: [f64; 3], angle: Angle) -> Self {
+        let axis = normalize(axis);
+        let (sin, cos) = angle.rad().sin_cos();
+
+        // Rodrigues' rotation formula
+        let rotate = |v: [f64; 3]| {
+            let parallel = scale(axis, dot(axis, v) * (1. - cos));
+            add(add(scale(v, cos), scale(cross(axis, v), sin)), parallel)
+        };
+
+        self.x_axis = rotate(self.x_axis);
+        self.y_axis = rotate(self.y_axis);
+        self
+    }
+
+    /// Access the origin of the plane
+    pub fn origin(&self) -> [f64; 3] {
+        self.origin
+    }
+
+    /// Access the x-axis of the plane
+    pub fn x_axis(&self) -> [f64; 3] {
+        self.x_axis
+    }
+
+    /// Access the y-axis of the plane
+    pub fn y_axis(&self) -> [f64; 3] {
+        self.y_axis
+    }
+
+    /// Access the normal of the plane
+    pub fn normal(&self) -> [f64; 3] {
+        cross(self.x_axis, self.y_axis)
+    }
+
+    /// Convert a point in plane coordinates into global coordinates
+    pub fn point_to_global(&self, [x, y]: [f64; 2]) -> [f64; 3] {
+        add(
+            self.origin,
+            add(scale(self.x_axis, x), scale(self.y_axis, y)),
+        )
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Self::xy()
+    }
+}
+
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(v: [f64; 3], s: f64) -> [f64; 3] {
+    v.map(|c| c * s)
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f64; 3]) -> [f64; 3] {
+    let length = dot(v, v).sqrt();
+    assert!(length > 0., "Can't create plane from zero-length axis");
+
+    scale(v, 1. / length)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Angle;
+
+    use super::Plane;
+
+    #[test]
+    fn offset() {
+        let plane = Plane::xz().offset(2.);
+
+        assert_eq!(plane.origin(), [0., -2., 0.]);
+        assert_eq!(plane.point_to_global([1., 1.]), [1., -2., 1.]);
+    }
+
+    #[test]
+    fn from_points() {
+        let plane =
+            Plane::from_points([[1., 1., 1.], [3., 1., 1.], [2., 2., 1.]]);
+
+        assert_eq!(plane.x_axis(), [1., 0., 0.]);
+        assert_eq!(plane.y_axis(), [0., 1., 0.]);
+        assert_eq!(plane.normal(), [0., 0., 1.]);
+    }
+
+    #[test]
+    fn rotate() {
+        let plane = Plane::xy().rotate([1., 0., 0.], Angle::from_deg(90.));
+
+        let [x, y, z] = plane.normal();
+        assert!(x.abs() < 1e-12);
+        assert!((y + 1.).abs() < 1e-12);
+        assert!(z.abs() < 1e-12);
+    }
+}
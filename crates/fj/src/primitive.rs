@@ -0,0 +1,160 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{color::DEFAULT_COLOR, ffi_option::FfiOption, Shape};
+
+/// A 3-dimensional primitive
+///
+/// Primitives are constructed by the kernel directly, instead of being built
+/// from sketches and other operations.
+///
+/// Cuboids, cylinders, and cones stand on the xy-plane, centered on the z-axis.
+/// Spheres and tori are centered on the origin, with tori lying in the
+/// xy-plane.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Primitive {
+    kind: PrimitiveKind,
+
+    // The color of the primitive in RGBA, if it has one of its own
+    color: FfiOption<[u8; 4]>,
+
+    // The tolerance that the primitive is approximated with, if it's tighter
+    // than the one that applies otherwise
+    tolerance: FfiOption<f64>,
+}
+
+impl Primitive {
+    /// Create a cuboid from its size along the x, y, and z axes
+    pub fn cuboid(size: [f64; 3]) -> Self {
+        Self::from_kind(PrimitiveKind::Cuboid { size })
+    }
+
+    /// Create a cylinder from its radius and height
+    pub fn cylinder(radius: f64, height: f64) -> Self {
+        Self::from_kind(PrimitiveKind::Cylinder { radius, height })
+    }
+
+    /// Create a sphere from its radius
+    pub fn sphere(radius: f64) -> Self {
+        Self::from_kind(PrimitiveKind::Sphere { radius })
+    }
+
+    /// Create a cone from the radius of its base and its height
+    pub fn cone(radius: f64, height: f64) -> Self {
+        Self::from_kind(PrimitiveKind::Cone { radius, height })
+    }
+
+    /// Create a torus
+    ///
+    /// `major_radius` is the distance from the center of the torus to the
+    /// center of its tube, `minor_radius` is the radius of the tube.
+    pub fn torus(major_radius: f64, minor_radius: f64) -> Self {
+        Self::from_kind(PrimitiveKind::Torus {
+            major_radius,
+            minor_radius,
+        })
+    }
+
+    fn from_kind(kind: PrimitiveKind) -> Self {
+        Self {
+            kind,
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
+        }
+    }
+
+    /// Set the rendering color of the primitive in RGBA
+    ///
+    /// This overrides any color that the primitive would otherwise inherit
+    /// from the operations it is part of.
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = FfiOption::Some(color);
+        self
+    }
+
+    /// Approximate the primitive with a tighter tolerance
+    ///
+    /// Has no effect, if the tolerance that applies otherwise is already
+    /// tighter than this one.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = FfiOption::Some(tolerance);
+        self
+    }
+
+    /// Access the kind of primitive
+    pub fn kind(&self) -> &PrimitiveKind {
+        &self.kind
+    }
+
+    /// Get the rendering color of the primitive in RGBA
+    ///
+    /// Returns [`DEFAULT_COLOR`], if the primitive has no color of its own.
+    pub fn color(&self) -> [u8; 4] {
+        self.color.get().unwrap_or(DEFAULT_COLOR)
+    }
+
+    /// Access the color of the primitive, if it has one of its own
+    ///
+    /// Returns `None`, if the primitive inherits its color.
+    pub fn explicit_color(&self) -> Option<[u8; 4]> {
+        self.color.get()
+    }
+
+    /// Access the tolerance of the primitive, if it has one
+    pub fn tolerance(&self) -> Option<f64> {
+        self.tolerance.get()
+    }
+}
+
+impl From<Primitive> for Shape {
+    fn from(shape: Primitive) -> Self {
+        Self::Primitive(shape)
+    }
+}
+
+/// The kind of a [`Primitive`], and its dimensions
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum PrimitiveKind {
+    /// A cuboid
+    Cuboid {
+        /// The size of the cuboid along the x, y, and z axes
+        size: [f64; 3],
+    },
+
+    /// A cylinder
+    Cylinder {
+        /// The radius of the cylinder
+        radius: f64,
+
+        /// The height of the cylinder
+        height: f64,
+    },
+
+    /// A sphere
+    Sphere {
+        /// The radius of the sphere
+        radius: f64,
+    },
+
+    /// A cone
+    Cone {
+        /// The radius of the cone's base
+        radius: f64,
+
+        /// The height of the cone
+        height: f64,
+    },
+
+    /// A torus
+    Torus {
+        /// The distance from the center of the torus to the center of its tube
+        major_radius: f64,
+
+        /// The radius of the tube
+        minor_radius: f64,
+    },
+}
@@ -0,0 +1,196 @@
+use std::ops::{Bound, RangeBounds};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Plane;
+
+/// Query all faces of a shape
+///
+/// Narrow down the selection using the methods of [`FaceQuery`].
+pub fn faces() -> FaceQuery {
+    FaceQuery {
+        filters: Vec::new(),
+    }
+}
+
+/// Query the face of a shape that is nearest to `point`
+///
+/// Shorthand for `faces().nearest(point)`.
+pub fn face_nearest(point: [f64; 3]) -> FaceQuery {
+    faces().nearest(point)
+}
+
+/// Query all edges of a shape
+///
+/// Narrow down the selection using the methods of [`EdgeQuery`].
+pub fn edges() -> EdgeQuery {
+    EdgeQuery {
+        filters: Vec::new(),
+    }
+}
+
+/// A query that selects faces of a shape
+///
+/// Queries describe which faces to select, based on their geometry. They are
+/// resolved against the shape, once that is computed. This keeps references to
+/// faces working, when the model changes in ways that change the number or
+/// order of its faces.
+///
+/// Filters are applied in the order they were added.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct FaceQuery {
+    filters: Vec<FaceFilter>,
+}
+
+impl FaceQuery {
+    /// Only select planar faces that are parallel to `plane`
+    pub fn parallel_to(mut self, plane: &Plane) -> Self {
+        self.filters.push(FaceFilter::ParallelTo {
+            normal: plane.normal(),
+        });
+        self
+    }
+
+    /// Only select the face that is nearest to `point`
+    pub fn nearest(mut self, point: [f64; 3]) -> Self {
+        self.filters.push(FaceFilter::Nearest { point });
+        self
+    }
+
+    /// Access the filters of this query
+    pub fn filters(&self) -> &[FaceFilter] {
+        &self.filters
+    }
+}
+
+/// A filter that is part of a [`FaceQuery`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum FaceFilter {
+    /// Select planar faces whose normal is parallel to the given one
+    ///
+    /// Faces whose normal points in the opposite direction are selected too.
+    ParallelTo {
+        /// The normal the faces must be parallel to
+        normal: [f64; 3],
+    },
+
+    /// Select the face that is nearest to the given point
+    Nearest {
+        /// The point that the face must be nearest to
+        point: [f64; 3],
+    },
+}
+
+/// A query that selects edges of a shape
+///
+/// See [`FaceQuery`] for how queries work.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct EdgeQuery {
+    filters: Vec<EdgeFilter>,
+}
+
+impl EdgeQuery {
+    /// Only select edges whose length is within `range`
+    ///
+    /// Whether the bounds of the range are inclusive or exclusive is ignored;
+    /// lengths are compared within the tolerance of the model.
+    pub fn of_length(mut self, range: impl RangeBounds<f64>) -> Self {
+        let bound = |bound: Bound<&f64>, unbounded: f64| match bound {
+            Bound::Included(length) | Bound::Excluded(length) => *length,
+            Bound::Unbounded => unbounded,
+        };
+
+        self.filters.push(EdgeFilter::OfLength {
+            min: bound(range.start_bound(), 0.),
+            max: bound(range.end_bound(), f64::INFINITY),
+        });
+        self
+    }
+
+    /// Only select straight edges that are parallel to `direction`
+    pub fn parallel_to(mut self, direction: [f64; 3]) -> Self {
+        self.filters.push(EdgeFilter::ParallelTo { direction });
+        self
+    }
+
+    /// Only select the edge that is nearest to `point`
+    pub fn nearest(mut self, point: [f64; 3]) -> Self {
+        self.filters.push(EdgeFilter::Nearest { point });
+        self
+    }
+
+    /// Access the filters of this query
+    pub fn filters(&self) -> &[EdgeFilter] {
+        &self.filters
+    }
+}
+
+/// A filter that is part of an [`EdgeQuery`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum EdgeFilter {
+    /// Select edges whose length is within the given range
+    OfLength {
+        /// The minimum length
+        min: f64,
+
+        /// The maximum length
+        max: f64,
+    },
+
+    /// Select straight edges that are parallel to the given direction
+    ParallelTo {
+        /// The direction the edges must be parallel to
+        direction: [f64; 3],
+    },
+
+    /// Select the edge that is nearest to the given point
+    Nearest {
+        /// The point that the edge must be nearest to
+        point: [f64; 3],
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Plane;
+
+    use super::{edges, face_nearest, faces, EdgeFilter, FaceFilter};
+
+    #[test]
+    fn filters() {
+        let query = faces().parallel_to(&Plane::xy()).nearest([0., 0., 1.]);
+        assert_eq!(
+            query.filters(),
+            [
+                FaceFilter::ParallelTo {
+                    normal: [0., 0., 1.]
+                },
+                FaceFilter::Nearest {
+                    point: [0., 0., 1.]
+                },
+            ]
+        );
+        assert_eq!(face_nearest([1., 2., 3.]), faces().nearest([1., 2., 3.]));
+
+        let query = edges().of_length(1.0..).of_length(..=2.0);
+        assert_eq!(
+            query.filters(),
+            [
+                EdgeFilter::OfLength {
+                    min: 1.,
+                    max: f64::INFINITY
+                },
+                EdgeFilter::OfLength { min: 0., max: 2. },
+            ]
+        );
+    }
+}
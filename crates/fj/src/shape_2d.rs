@@ -3,7 +3,7 @@ use serde::{de, ser, Deserialize, Serialize};
 use std::mem;
 use std::sync::atomic;
 
-use crate::Shape;
+use crate::{color::DEFAULT_COLOR, ffi_option::FfiOption, Plane, Shape};
 
 /// A 2-dimensional shape
 #[derive(Clone, Debug, PartialEq)]
@@ -79,8 +79,11 @@ impl From<Difference2d> for Shape2d {
 pub struct Sketch {
     chain: Chain,
 
-    // The color of the sketch in RGBA
-    color: [u8; 4],
+    // The plane that the sketch is attached to
+    plane: Plane,
+
+    // The color of the sketch in RGBA, if it has one of its own
+    color: FfiOption<[u8; 4]>,
 }
 
 impl Sketch {
@@ -88,7 +91,8 @@ impl Sketch {
     pub fn from_points(points: Vec<[f64; 2]>) -> Self {
         Self {
             chain: Chain::PolyChain(PolyChain::from_points(points)),
-            color: [255, 0, 0, 255],
+            plane: Plane::xy(),
+            color: FfiOption::None,
         }
     }
 
@@ -96,13 +100,26 @@ impl Sketch {
     pub fn from_circle(circle: Circle) -> Self {
         Self {
             chain: Chain::Circle(circle),
-            color: [255, 0, 0, 255],
+            plane: Plane::xy(),
+            color: FfiOption::None,
         }
     }
 
+    /// Attach the sketch to a plane
+    ///
+    /// The points of the sketch are interpreted in the coordinate system of
+    /// the plane. By default, sketches are attached to the xy-plane.
+    pub fn with_plane(mut self, plane: Plane) -> Self {
+        self.plane = plane;
+        self
+    }
+
     /// Set the rendering color of the sketch in RGBA
+    ///
+    /// This overrides any color that the sketch would otherwise inherit from
+    /// the operations it is part of.
     pub fn with_color(mut self, color: [u8; 4]) -> Self {
-        self.color = color;
+        self.color = FfiOption::Some(color);
         self
     }
 
@@ -111,9 +128,23 @@ impl Sketch {
         &self.chain
     }
 
+    /// Access the plane that the sketch is attached to
+    pub fn plane(&self) -> Plane {
+        self.plane
+    }
+
     /// Get the rendering color of the sketch in RGBA
+    ///
+    /// Returns [`DEFAULT_COLOR`], if the sketch has no color of its own.
     pub fn color(&self) -> [u8; 4] {
-        self.color
+        self.color.get().unwrap_or(DEFAULT_COLOR)
+    }
+
+    /// Access the color of the sketch, if it has one of its own
+    ///
+    /// Returns `None`, if the sketch inherits its color.
+    pub fn explicit_color(&self) -> Option<[u8; 4]> {
+        self.color.get()
     }
 }
 
@@ -266,6 +297,10 @@ impl Drop for PolyChain {
 // contains, making sure memory ownership rules are observed.
 unsafe impl Send for PolyChain {}
 
+// `PolyChain` can be `Sync`, because the points behind its raw pointer are
+// never modified after construction, and the reference counter is atomic.
+unsafe impl Sync for PolyChain {}
+
 #[cfg(feature = "serde")]
 impl ser::Serialize for PolyChain {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1,7 +1,7 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{Shape, Shape2d};
+use crate::{ffi_option::FfiOption, Angle, Shape, Shape2d};
 
 /// A sweep of a 2-dimensional shape along straight path
 #[derive(Clone, Debug, PartialEq)]
@@ -13,12 +13,81 @@ pub struct Sweep {
 
     /// The length and direction of the sweep
     path: [f64; 3],
+
+    /// How far the sweep extends in the direction opposite to the path
+    back_length: f64,
+
+    /// The angle by which the sides of the sweep lean inwards
+    taper: Angle,
+
+    /// The color that the sweep passes on to the shape being swept
+    color: FfiOption<[u8; 4]>,
+
+    /// The tolerance that the sweep is approximated with, if it's tighter than
+    /// the one that applies otherwise
+    tolerance: FfiOption<f64>,
 }
 
 impl Sweep {
     /// Create a `Sweep` along a straight path
     pub fn from_path(shape: Shape2d, path: [f64; 3]) -> Self {
-        Self { shape, path }
+        Self {
+            shape,
+            path,
+            back_length: 0.,
+            taper: Angle::from_rad(0.),
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
+        }
+    }
+
+    /// Create a `Sweep` that extends symmetrically to both sides of the shape
+    ///
+    /// The length of the sweep is the length of `path`, with half of it on
+    /// either side of the shape.
+    pub fn symmetric(shape: Shape2d, path: [f64; 3]) -> Self {
+        let half = path.map(|coord| coord / 2.);
+        let half_length = half.iter().map(|coord| coord * coord).sum::<f64>();
+
+        Self::from_path(shape, half).with_back_length(half_length.sqrt())
+    }
+
+    /// Extend the sweep by `length`, in the direction opposite to the path
+    ///
+    /// This makes the sweep extend in both directions from the shape, with
+    /// different lengths on each side.
+    pub fn with_back_length(mut self, length: f64) -> Self {
+        self.back_length = length;
+        self
+    }
+
+    /// Taper the sweep by the given angle
+    ///
+    /// A positive angle makes the swept shape shrink linearly along the path,
+    /// a negative angle makes it grow. If the sweep extends in both directions,
+    /// the taper starts at the end opposite to the path. The angle is measured between the sides
+    /// of the sweep and the path.
+    pub fn with_taper(mut self, taper: Angle) -> Self {
+        self.taper = taper;
+        self
+    }
+
+    /// Set the rendering color of the sweep in RGBA
+    ///
+    /// The color applies to the shape being swept, unless that has a color of
+    /// its own.
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = FfiOption::Some(color);
+        self
+    }
+
+    /// Approximate the sweep with a tighter tolerance
+    ///
+    /// Has no effect, if the tolerance that applies otherwise is already
+    /// tighter than this one.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = FfiOption::Some(tolerance);
+        self
     }
 
     /// Access the shape being swept
@@ -30,6 +99,26 @@ impl Sweep {
     pub fn path(&self) -> [f64; 3] {
         self.path
     }
+
+    /// Access the length of the sweep opposite to the path
+    pub fn back_length(&self) -> f64 {
+        self.back_length
+    }
+
+    /// Access the taper angle of the sweep
+    pub fn taper(&self) -> Angle {
+        self.taper
+    }
+
+    /// Access the color of the sweep, if it has one
+    pub fn color(&self) -> Option<[u8; 4]> {
+        self.color.get()
+    }
+
+    /// Access the tolerance of the sweep, if it has one
+    pub fn tolerance(&self) -> Option<f64> {
+        self.tolerance.get()
+    }
 }
 
 impl From<Sweep> for Shape {
@@ -37,3 +126,19 @@ impl From<Sweep> for Shape {
         Self::Sweep(shape)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circle, Sketch};
+
+    use super::Sweep;
+
+    #[test]
+    fn symmetric() {
+        let circle = Sketch::from_circle(Circle::from_radius(1.));
+        let sweep = Sweep::symmetric(circle.into(), [0., 0., 4.]);
+
+        assert_eq!(sweep.path(), [0., 0., 2.]);
+        assert_eq!(sweep.back_length(), 2.);
+    }
+}
@@ -3,6 +3,69 @@
 //! This model defines extension traits, which provide convenient syntax for
 //! the various operations defined in this trait.
 
+use crate::ffi_option::FfiOption;
+
+/// Convenient syntax to create an [`fj::Boolean`]
+///
+/// [`fj::Boolean`]: crate::Boolean
+pub trait Boolean {
+    /// Create a union of `self` and `other`
+    fn union<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>;
+
+    /// Create an intersection of `self` and `other`
+    fn intersection<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>;
+
+    /// Subtract `other` from `self`
+    fn subtract<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>;
+}
+
+impl<T> Boolean for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn union<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        boolean(self, other, crate::BooleanOperation::Union)
+    }
+
+    fn intersection<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        boolean(self, other, crate::BooleanOperation::Intersection)
+    }
+
+    fn subtract<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        boolean(self, other, crate::BooleanOperation::Difference)
+    }
+}
+
+fn boolean(
+    a: &(impl Clone + Into<crate::Shape>),
+    b: &(impl Clone + Into<crate::Shape>),
+    operation: crate::BooleanOperation,
+) -> crate::Boolean {
+    crate::Boolean {
+        a: a.clone().into(),
+        b: b.clone().into(),
+        operation,
+        backend: crate::BooleanBackend::default(),
+        color: FfiOption::None,
+        tolerance: FfiOption::None,
+    }
+}
+
 /// Convenient syntax to create an [`fj::Difference2d`]
 ///
 /// [`fj::Difference2d`]: crate::Difference2d
@@ -46,10 +109,35 @@ where
     where
         Other: Clone + Into<crate::Shape>,
     {
-        let a = self.clone().into();
+        let a: crate::Shape = self.clone().into();
         let b = other.clone().into();
 
-        crate::Group { a, b }
+        crate::Group::from_shapes([a, b])
+    }
+}
+
+/// Convenient syntax to create an [`fj::Offset`]
+///
+/// [`fj::Offset`]: crate::Offset
+pub trait Offset {
+    /// Offset the faces of `self` by `distance`
+    ///
+    /// A positive distance grows the shape, a negative one shrinks it.
+    fn offset(&self, distance: f64) -> crate::Offset;
+}
+
+impl<T> Offset for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn offset(&self, distance: f64) -> crate::Offset {
+        let shape = self.clone().into();
+        crate::Offset {
+            shape,
+            distance,
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
+        }
     }
 }
 
@@ -118,6 +206,8 @@ where
             axis,
             angle,
             offset: [0.; 3],
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
         }
     }
 
@@ -128,6 +218,8 @@ where
             axis: [1., 0., 0.],
             angle: crate::Angle::from_rad(0.),
             offset,
+            color: FfiOption::None,
+            tolerance: FfiOption::None,
         }
     }
 }
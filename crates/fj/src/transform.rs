@@ -1,7 +1,7 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{Angle, Shape};
+use crate::{ffi_option::FfiOption, Angle, Shape};
 
 /// A transformed 3-dimensional shape
 ///
@@ -27,6 +27,41 @@ pub struct Transform {
 
     /// The offset of the translation
     pub offset: [f64; 3],
+
+    /// The color that the transform passes on to the shape being transformed
+    ///
+    /// Applies to all parts of the shape that don't have a color of their own.
+    pub(crate) color: FfiOption<[u8; 4]>,
+
+    /// The tolerance that the shape is approximated with
+    ///
+    /// Only has an effect, if it's tighter than the tolerance that applies
+    /// otherwise.
+    pub(crate) tolerance: FfiOption<f64>,
+}
+
+impl Transform {
+    /// Set the rendering color of the transformed shape in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = FfiOption::Some(color);
+        self
+    }
+
+    /// Access the color that the transform passes on, if it has one
+    pub fn color(&self) -> Option<[u8; 4]> {
+        self.color.get()
+    }
+
+    /// Approximate the transformed shape with a tighter tolerance
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = FfiOption::Some(tolerance);
+        self
+    }
+
+    /// Access the tolerance of the operation, if it has one
+    pub fn tolerance(&self) -> Option<f64> {
+        self.tolerance.get()
+    }
 }
 
 impl From<Transform> for Shape {